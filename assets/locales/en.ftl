@@ -0,0 +1,2069 @@
+# English: the source every other language translates, and what they fall
+# back to for anything they don't have yet.
+
+## Menu bar
+
+menu-halo = Halo
+menu-about = About
+menu-load-show = Load Show
+menu-save-show = Save Show
+menu-save-show-as = Save Show As...
+menu-save-package = Save Show As Package...
+menu-merge = Merge From Show...
+menu-compare-shows = Compare Shows...
+menu-revert = Revert to Backup...
+menu-preferences = Preferences...
+menu-properties = Show Properties...
+menu-handoff = Export Operator Handoff...
+menu-report = Export Show Report...
+menu-session = Session Statistics...
+menu-quit = Quit
+
+menu-cues = Cues
+menu-insert-point-cue = Insert Point Cue
+menu-duplicate-at-playhead = Duplicate at Playhead
+menu-copy-cue = Copy Cue
+menu-copy-section = Copy Section
+menu-copy-section-hint = Copy every cue in the selected cue's section
+menu-paste-cues = Paste Cues...
+menu-edit-cue = Edit Selected Cue...
+menu-edit-script = Edit Cue Script...
+menu-fan = Fan...
+menu-renumber = Renumber...
+menu-fixture-replace = Replace Fixture...
+menu-songs = Songs...
+menu-crossfade = A/B Crossfade...
+menu-variables = Variables...
+menu-spectrogram = Spectrogram...
+menu-learn-timing = Learn Timing...
+menu-tracksheet = Tracksheet...
+menu-follow-spots = Follow Spots...
+menu-daw = DAW Markers...
+menu-schedule = Schedule...
+
+menu-view = View
+menu-performance-mode = Performance Mode...
+menu-busk = Busk...
+menu-designer-view = Designer View...
+menu-visualizer = Visualizer
+menu-quick-console = Quick Console
+menu-command-line = Command Line
+menu-command-palette = Command Palette
+menu-shortcuts = Keyboard Shortcuts
+menu-dock-cue-list = Dock Cue List
+menu-detach-cue-list = Detach Cue List
+menu-macros = Macros
+menu-bindings = Trigger Bindings...
+menu-midi-monitor = MIDI Monitor
+menu-osc-monitor = OSC Monitor
+menu-packet-inspector = Packet Inspector
+menu-log = Log Console
+menu-frame-timings = Frame Timings
+menu-stage-display = Stage Display
+menu-clocks = Clocks
+
+menu-output = Output
+menu-output-monitor = Output Monitor
+menu-art-net = Art-Net Output...
+menu-sacn = sACN Output...
+menu-pixel-maps = Pixel Maps...
+menu-effect-presets = Effect Presets...
+menu-smart-bulbs = Smart Bulbs...
+menu-plugins = Plugins...
+menu-speed-masters = Speed Masters...
+menu-group-masters = Group Masters...
+menu-haze = Haze...
+menu-snapshots = Snapshots...
+menu-safe-state = Safe State...
+menu-recording = Output Recording
+menu-power = Power
+
+## Header
+
+header-bpm = BPM:
+header-freeze = ❄ Freeze FX
+header-freeze-hint = Hold every pixel map and busk look where it is
+header-record-look = ⏺ Record Look
+header-record-look-hint = Record everything on stage now as a new cue at the playhead
+header-leave-performance = 🔒 Leave Performance Mode
+header-patch = Patch
+
+## Transport
+
+transport-show-timecode = Show Timecode
+transport-show-system-time = Show System Time
+transport-show-clock = Show { $clock }
+transport-start = Start
+transport-stop = Stop
+transport-pause = Pause
+transport-resume = Resume
+transport-pause-hint = Freeze where it is with the output held
+transport-reset = Reset
+transport-go = GO
+transport-go-hint = Jump to the next cue
+transport-back = Back
+transport-back-hint = Back to the cue before the current one, fading over { $seconds }s
+transport-go-to-cue = Go To Cue
+transport-assert = Assert
+transport-assert-hint = Drop quick levels, busk and pads for the cues' own look
+transport-timecode = Timecode...
+transport-cue-list-detached = The cue list is in its own window.
+transport-override-pads = Override Pads
+transport-stopped = Stopped
+transport-pre-roll = Pre-roll
+transport-running = Running
+transport-paused = Paused
+transport-chasing = Chasing
+transport-chasing-parked = Chasing (parked)
+transport-resume-first = Resume from the safe state before starting playback
+
+## Performance mode
+
+performance-enter-title = Enter performance mode?
+performance-enter-body = Editing is locked and Reset is disabled until you leave performance mode.
+performance-leave-title = Leave performance mode?
+performance-leave-body = Editing will be unlocked.
+performance-passcode = Passcode:
+performance-wrong-passcode = Wrong passcode
+performance-enter = Enter
+performance-leave = Leave
+performance-next = Next: { $number }  { $name }  in { $time }
+performance-last-cue = Last cue
+performance-marker = ◆ { $text }  in { $time }
+performance-entered = Entered performance mode
+performance-left = Left performance mode
+performance-wrong-passcode-log = Wrong passcode given to leave performance mode
+
+## Quitting
+
+quit-title = Quit Halo
+quit-save-changes = Save the changes to "{ $show }" before quitting?
+quit-changes-lost = Your changes will be lost if you don't save them.
+quit-save = Save
+quit-save-disabled = Leave performance mode to choose a file
+quit-dont-save = Don't Save
+
+## Preferences
+
+preferences-title = Preferences
+preferences-appearance = Appearance
+preferences-language = Language:
+preferences-theme = Theme:
+preferences-led-colour = LED colour:
+preferences-accent-downbeat = Flash the first beat of each bar brighter
+preferences-ui-scale = Interface scale:
+preferences-touch = Touch screen:
+preferences-touch-mode = Large controls, long-press for hints
+preferences-remote-control = Remote Control
+preferences-midi = MIDI
+preferences-audio = Audio
+preferences-network-interfaces = Network Interfaces
+preferences-network-input = Network Input
+preferences-main-backup = Main / Backup
+preferences-output = Output
+preferences-backups = Backups
+preferences-startup = Startup
+preferences-display = Display
+preferences-updates = Updates
+preferences-remote-tcp = Accept TCP remote control connections
+preferences-http = Serve HTTP endpoints for Companion / Stream Deck
+preferences-osc-input = Receive OSC for trigger bindings
+preferences-citp = Share the patch and selection with visualisers (CITP)
+preferences-citp-waiting = Waiting for a visualiser
+preferences-citp-connected = Connected: { $peers }
+preferences-psn = Receive PSN trackers for follow spots
+preferences-psn-listening = Listening; { $count ->
+        [one] 1 tracker sending
+       *[other] { $count } trackers sending
+    }
+preferences-designer-views = Designer views:
+preferences-midi-clock-in = Follow MIDI clock tempo
+preferences-midi-timecode-in = Chase MIDI timecode
+preferences-midi-mmc-in = Follow MIDI Machine Control
+preferences-mmc-device = MMC device:
+preferences-mmc-device-hint = The device ID MMC is answered to and sent to; All takes commands for any device
+preferences-song-select = Song select:
+preferences-song-channel = Program changes on channel
+preferences-midi-send-clock = Send MIDI clock
+preferences-midi-mmc-out = Send MIDI Machine Control
+preferences-song-select-hint = In setlist mode, program change n on the input selects song n + 1 unless a trigger binding uses it.
+preferences-click-output = Click output:
+preferences-click-hint = A metronome on every beat, higher on the first beat of the bar when it is flashed brighter.
+preferences-audio-outputs = Show audio outputs:
+preferences-audio-outputs-hint = Plays the show's audio file in time with the show. Give each output the channels of the file it plays, such as the music on 1-2 for front of house and the click and guide on 3-4 for the band.
+preferences-interface-down = ⚠ { $name } is down, so { $protocol } is using every interface
+preferences-interfaces-hint = Art-Net and sACN cover input and output, and OSC the trigger listener. Leave them on Any with only one network.
+preferences-receive = Receive:
+preferences-universes = Universes:
+preferences-fader-wings = Fader wings:
+preferences-fader-wings-hint = Universes read for masters, not merged
+preferences-merge = Merge:
+preferences-halo-priority = Halo at
+preferences-listening-nothing = Listening; nothing received yet.
+preferences-source = Universe { $universe }: { $protocol } from { $name } at priority { $priority }
+preferences-source-per-address = Universe { $universe }: { $protocol } from { $name } at priority { $priority }, per address
+preferences-clock = Clock:
+preferences-main = Main:
+preferences-tracking-backup = Track the main's show and take over if it is lost
+preferences-stop-output = When playback stops:
+preferences-stop-output-hint = Also applies when a show is loaded and when Halo quits. Starting playback or moving the playhead brings the show back.
+preferences-look-pads = Give each recorded look a pad that fires it
+preferences-keep-last = Keep the last
+preferences-saves = saves
+preferences-backups-hint = Backups sit in a hidden folder beside the show file.
+preferences-check-updates = Check for a new release at startup
+preferences-check-updates-hint = Turn this off at venues with no internet connection.
+preferences-open-last = Open the last show
+preferences-enable-output = Switch the Art-Net, sACN and plugin outputs on
+preferences-connect-midi = Use the first MIDI ports found when none are chosen
+preferences-start-playback = Start playing from
+preferences-start-after = after
+preferences-start-delay-hint = Time for the network and devices to come up
+preferences-startup-hint = For installations that must come back by themselves after a power cut. Set the computer to start Halo when it logs in.
+preferences-usb-buttons = USB buttons:
+preferences-debounce = Debounce:
+preferences-debounce-hint = Presses of a button closer together than this count once
+preferences-usb-bind-hint = Bind its keys to GO and Back under View → Trigger Bindings
+preferences-clock-off-hint = Share the clock with a backup Halo, or follow a main one.
+preferences-no-backup = No backup following
+preferences-backup-following = Backup { $backup } following
+preferences-clock-estimate = Offset { $offset } ms, round trip { $round_trip } ms, { $corrections } corrections
+preferences-waiting-main = Waiting for the main
+preferences-main-drives = The main's clock drives this Halo's transport.
+preferences-output-held = Output held while the main runs the show.
+preferences-take-over = Take Over Now
+preferences-take-over-hint = Send this Halo's output in place of the main's
+preferences-taken-over = This backup has taken over the output
+preferences-sending = This backup is sending the output
+preferences-hand-back = Hand Back to Main
+preferences-hand-back-hint = Hold output and follow the main again
+preferences-add-universe = Add a universe
+preferences-channels = Channels
+preferences-channels-to = to
+preferences-add-output = Add Output
+preferences-no-audio-outputs = No audio outputs found
+preferences-interface-missing = { $name } — missing
+
+## Keeping the display on
+
+awake-touch-to-wake = Touch anything to wake
+awake-keep-on = Keep the display on while a show is loaded and the output is on
+awake-holding = Keeping the display on now.
+awake-dim-after = Dim to a clock after
+awake-minutes = {" "}min
+awake-without-input = without input
+awake-dim-hint = Dimming only darkens the window; the output carries on. The touch or key that wakes it does nothing else, except F12 panic.
+awake-letting-sleep = Letting the display sleep again
+awake-keeping = Keeping the display awake while the show runs
+awake-failed = Couldn't keep the display awake: { $error }
+
+## Replacing a fixture
+
+replace-title = Replace Fixture
+replace-find = Find:
+replace-with = Replace with:
+replace-pick = Pick a fixture to see where the show uses it.
+replace-unused = Nothing in the show uses this fixture.
+replace-button = Replace { $count }
+replace-button-hint = Hand the ticked places over; levels the new fixture has no channel for are dropped
+replace-undo = Undo
+replace-undo-hint = Put back what the last replacement changed
+replace-done = Replaced { $from } with { $to } in { $count } places
+replace-dropped = ; { $count } levels had no channel on { $to }
+replace-undone = Gave { $from } back { $count } places from { $to }
+replace-kept = { $count } places were edited after the replacement and were left as they are
+replace-cue = Cue { $number } { $name }: { $uses }
+replace-levels = { $count } levels
+replace-part = part { $parts }
+replace-pad = Pad { $pad }: flash
+replace-haze = Haze schedule
+replace-follow-spot = Follow spot { $name }
+
+## About
+
+about-title = About Halo
+about-tagline = Timecode lighting control for live shows.
+about-version = Version:
+about-build = Build:
+about-build-for = { $profile } for { $os }-{ $arch }
+about-debug = debug
+about-release = release
+about-check-updates = Check for Updates
+about-available = Halo { $version } is available:
+about-download = Download
+about-up-to-date = Halo is up to date.
+about-check-failed = Could not check: { $error }
+
+## Backups
+
+backups-title = Revert to Backup
+backups-unsaved = Save the show to start keeping backups.
+backups-off = Backups are turned off in Preferences.
+backups-none = No backups yet; one is kept each time the show is saved.
+backups-revert-hint = Reverting replaces the open show. Save afterwards to keep it.
+backups-latest = { $saved } (latest)
+backups-revert = Revert
+backups-reverted = Reverted to the backup from { $saved }
+
+## Errors
+
+error-opened = Opened { $path }
+error-not-a-show = { $path } couldn't be opened as a show.
+error-show-still-open = The show that was open is still open.
+error-device-failed = The { $device } "{ $name }" couldn't be opened.
+error-device-hint = Halo carries on without it. Check it is plugged in and not in use by another program, then try again, or choose another { $device }.
+error-more = { $count } more after this
+error-retry = Retry
+error-dismiss = Dismiss
+device-audio-input = audio input
+device-click = click output
+device-playback = audio output
+device-midi-input = MIDI input
+device-midi-output = MIDI output
+device-buttons = button device
+error-title-show = Couldn't Open Show
+error-title-audio-input = Couldn't Open Audio Input
+error-title-click = Couldn't Open Click Output
+error-title-playback = Couldn't Open Audio Output
+error-title-midi-input = Couldn't Open MIDI Input
+error-title-midi-output = Couldn't Open MIDI Output
+error-title-buttons = Couldn't Open Button Device
+error-another-file = Choose Another File...
+error-another-device = Choose Another Device...
+error-open-show = Couldn't open { $path }: { $message }
+error-open-device = Couldn't open { $device } "{ $name }": { $message }
+
+## Operator handoff
+
+handoff-title = Operator Handoff
+handoff-show-name = Show name:
+handoff-contacts = Contacts:
+handoff-contacts-hint = Stage manager, production, venue tech
+handoff-notes = Standby notes:
+handoff-export = Export HTML
+
+## Show report
+
+report-title = Show Report
+report-description = Cue sheet and patch report for the production book.
+report-format = Format:
+report-export = Export
+
+## Scripts
+
+script-cue-title = Cue { $number } Script
+script-pad-title = { $pad } Pad Script
+script-cue-hint = Runs when the cue fires. Call skip() to skip the cue.
+script-pad-hint = Runs when the pad is switched on.
+script-test-run = Test Run
+script-errors-logged = Errors are also written to the log console.
+script-pad = Pad { $label }
+script-failed = { $name } script: { $error }
+script-no-pad = { $name } script: no pad “{ $pad }”
+script-osc-failed = { $name } script: OSC to { $target } failed: { $error }
+script-skipped = { $name } skipped by its script
+script-skip-pad = { $name } script: skip() only applies to cues
+
+## Address map
+
+address-map-title = Address Map
+address-map-named-selected = Address map, universe { $universe }: { $fixture } at { $address }, arrow keys move it
+address-map-named = Address map, universe { $universe }
+address-map-collides = {" "}(collides)
+address-map-moved = Moved { $fixture } to { $address }
+
+## Art-Net output
+
+art-net-rate-hint = Lower it for fixtures or nodes that flicker or lag at the full rate
+art-net-title = Art-Net Output
+art-net-enabled = Send the output over Art-Net
+art-net-broadcast = Broadcast to:
+art-net-broadcast-unrouted = Broadcast universes not routed to a node
+art-net-broadcast-rate = Broadcast rate:
+art-net-changes-only = Send only changes
+art-net-changes-only-hint = Leave out universes that haven't changed, sending each at least every { $seconds }s to keep nodes alive
+art-net-nodes = Nodes
+art-net-stop-discovery = Stop Discovery
+art-net-discover = Discover
+art-net-discovery-port = Discovery needs the Art-Net port, so turn off Art-Net input and RDM while using it.
+art-net-polling = Polling...
+art-net-add-hint = Send to this node, one universe per output
+art-net-unicast = Unicast targets
+art-net-add-universe = Add Universe
+art-net-rate = Rate
+art-net-port-address = → port-address
+art-net-add-target = Add Target
+
+## Trigger bindings
+
+input-note = note { $note } ch { $channel }
+input-control = CC { $controller } ch { $channel }
+input-program = program { $program } ch { $channel }
+input-usb-key = USB key { $key }
+source-midi-note = MIDI note
+source-midi-cc = MIDI CC
+source-midi-program = MIDI program
+source-usb-button = USB button
+action-go-cue = Go cue
+action-toggle-pad = Toggle pad
+action-next-song = Next song
+action-previous-song = Previous song
+action-go-to-song = Go to song
+action-pad-bank = Pad bank
+action-run-macro = Run macro
+action-record-look = Record look
+binding-learned-event = Learned { $event }
+binding-learned = Learned
+binding-no-pad = Trigger: no pad "{ $pad }"
+binding-channel = Ch
+binding-note = Note
+binding-program = Program
+binding-key = Key
+bindings-title = Trigger Bindings
+bindings-description = Incoming MIDI, OSC and HTTP messages mapped to actions.
+bindings-osc-off = OSC input is off; turn it on in Preferences to use OSC bindings.
+bindings-source = Source
+bindings-action = Action
+bindings-learn-hint = Use the next MIDI, OSC or HTTP trigger received
+bindings-conflict = ⚠ Conflict
+bindings-conflict-hint = The same message also triggers binding { $others }
+bindings-none = No bindings yet.
+bindings-add = Add Binding
+bindings-setlist = Bound program changes no longer select setlist songs.
+bindings-recent = Recent messages
+bindings-not-bound = Not bound
+
+## Busk
+
+busk-title = Busk
+busk-pick-groups = Pick the groups to busk on.
+busk-look = Look { $number }: { $template } (beat { $beat }/{ $beats })
+busk-description = Plays generated looks over the cues.
+busk-needs = Needs at least one template and colour.
+busk-groups = Groups:
+busk-palette = Palette:
+busk-add-colour = Add a colour
+busk-templates = Templates:
+busk-intensity = Intensity:
+busk-variation = Variation:
+busk-variation-hint = Low walks through the templates and colours in order, high picks them at random
+busk-change-every = Change every:
+
+## Clocks
+
+clocks-description = The big readout steps through these as well as the timecode and time of day.
+clocks-zones = Time zones
+clocks-daylight-hint = Move it for daylight saving
+clocks-add-zone = Add Time Zone
+clocks-zone = Zone
+clocks-countdowns = Countdowns
+clocks-add-countdown = Add Countdown
+clocks-doors = Doors
+clocks-show = Show
+clocks-countdown = Countdown
+clocks-countdowns-hint = Countdowns are to this computer's time of day, within twelve hours.
+clocks-caption-in = { $name } in
+clocks-caption-since = { $name } since
+
+## Command line
+
+command-line-enter = Enter
+command-group-type = Give a fixture type after group
+command-empty = Type a command
+command-unsure = Not sure what to do with { $word }
+command-unknown = “{ $word }” isn't a command
+command-level-missing = Give a level after @
+command-level-extra = Give one level after @
+command-thru-channel = Give a channel after thru
+command-no-channels = That selects no channels
+command-not-channel = “{ $word }” isn't a channel
+command-not-cue-number = “{ $word }” isn't a cue number
+command-not-number = “{ $word }” isn't a number
+command-out-of-range = { $word } is out of range; use { $start } to { $end }
+command-moved = { $count ->
+        [one] { $count } fixture moved
+       *[other] { $count } fixtures moved
+    }
+command-set = { $count ->
+        [one] { $count } fixture set
+       *[other] { $count } fixtures set
+    }
+command-no-cue = There's no cue { $number }
+command-cue-time = Cue { $number } fades in { $seconds }s
+command-released = Quick levels released
+command-no-group = No fixtures are of a type starting “{ $name }”
+command-select-first = Select some channels first
+command-recorded = Recorded cue { $number }
+command-added = Added cue { $number }
+
+## Command palette
+
+palette-go = GO: next cue
+palette-back = Back: previous cue
+palette-resume = Resume from panic
+palette-panic = Panic: blackout to the safe state
+palette-performance-mode = Performance mode
+palette-pad-off = Pad { $pad } off
+palette-pad-on = Pad { $pad } on
+palette-toggle-patch = Toggle patch view
+palette-open = Open { $window }
+palette-hint = Type to search actions, cues and windows
+palette-no-matches = Nothing matches
+palette-ran = Palette: { $entry }
+shortcuts-close = Press ? or Escape to close
+
+## Keyboard shortcuts
+
+shortcut-palette = Command palette: search for any action
+shortcut-help = Show these shortcuts
+shortcut-start-stop = Start / stop playback, or press the focused control
+shortcut-pause = Pause / resume playback, holding the output
+shortcut-reset = Reset timecode to zero (not in performance mode)
+shortcut-readout = Step the readout through timecode, time of day and the show's clocks
+shortcut-patch = Toggle patch view (not in performance mode)
+shortcut-next-song = Next song (setlist mode)
+shortcut-panic = Panic: fade to the safe state and stop (works while typing)
+shortcut-back = Back: fade to the cue before the current one
+shortcut-performance = Enter / leave performance mode
+shortcut-learn-go = GO: set the next cue's time (while learning timing)
+shortcut-quick-up = Raise the selected quick console channels 5% (Shift: 1%)
+shortcut-quick-down = Lower the selected quick console channels 5% (Shift: 1%)
+shortcut-nudge-second-earlier = Nudge the selected cue 1 second earlier (not in performance mode)
+shortcut-nudge-second-later = Nudge the selected cue 1 second later (not in performance mode)
+shortcut-nudge-frame-earlier = Nudge the selected cue 1 frame earlier (not in performance mode)
+shortcut-nudge-frame-later = Nudge the selected cue 1 frame later (not in performance mode)
+shortcut-focus = Focus the next control (Shift+Tab the previous, arrows a neighbour)
+shortcut-unfocus = Let go of the focused control
+shortcut-pad-menu = Open the focused pad's menu (not in performance mode)
+shortcut-fire = Fire the focused cue
+
+## Cue list
+
+cues-search-hint = Name, number or notes
+cues-clear-search = Clear search
+cues-jump = Jump to Current
+cues-add-marker = Add Marker
+cues-add-marker-hint = Add a note to the timeline at the playhead
+cues-ripple = Ripple
+cues-ripple-hint = Moving a cue moves every later cue in its song by the same amount. [ and ] nudge the selected cue a second, comma and full stop a frame.
+cues-no-match = No cues match the search.
+cues-marker = Marker
+cues-move-to-playhead = Move to the playhead
+cues-armed = Armed
+cues-armed-hint = Armed: disarmed cues are skipped in playback
+cues-only-when = Only when { $condition }
+cues-fires-at-bar = Fires at bar { $at }
+cues-double-click = Double-click to fire
+cues-row-playing = Cue { $number } { $name }, playing
+cues-row-disarmed = Cue { $number } { $name }, disarmed
+cues-went-late = Went { $late } at { $at }
+cues-fade = Fade: { $timing }
+cue-timing-after = {" "}after { $seconds }s
+renumber-title = Renumber Cues
+renumber-start = Start at:
+renumber-interval = Interval:
+renumber-apply = Renumber
+renumber-too-high = { $count } cues numbered from { $start } by { $interval } would go past { $max }, the highest cue number; nothing was renumbered
+cues-copied-one = Copied cue { $number }
+cues-copied = Copied { $count } cues
+cues-pasted = Pasted { $count ->
+        [one] { $count } cue
+       *[other] { $count } cues
+    } as { $numbers }
+cues-no-room = No room to number { $count ->
+        [one] the cue
+       *[other] { $count } cues
+    } below { $max }, the highest cue number; nothing was added
+cues-duplicated = Duplicated cue { $number } as { $new } at { $at }
+paste-title = Paste Cues
+paste-nothing = Copy a cue or section first.
+paste-cues-from = { $count } cues from { $number }
+paste-offset = Time offset:
+paste-to-playhead = To playhead
+paste-to-playhead-hint = Start the first pasted cue at the playhead
+paste-starts-after = The first cue starts at { $at }, after cue { $number }.
+paste-starts-at-end = The first cue starts at { $at }, after the last cue.
+paste-apply = Paste
+
+## Session statistics
+
+session-early = { $seconds }s early
+session-late = { $seconds }s late
+session-title = Session Statistics
+session-open-for = Open for:
+session-played-for = Played for:
+session-run-time = Run time:
+session-not-started = not started
+session-last-saved = Last saved:
+session-ago = { $duration } ago
+session-not-saved = not this session
+session-cues-fired = Cues fired: { $count }
+session-late-count = { $count } late
+session-went-at = Went at
+session-cue = Cue
+session-programmed = Programmed
+session-actual = Actual
+session-drift = Drift
+session-late-header = Late
+session-clear-hint = Start the statistics afresh for the next show
+session-report = Post-show report
+
+## Cue editor
+
+cue-editor-title = Cue { $number }
+cue-editor-armed-hint = Disarmed cues are skipped in playback
+cue-editor-block = Block
+cue-editor-block-hint = Hold every value that tracks in, so edits to earlier cues stop here
+cue-editor-only-when = Only when:
+cue-editor-fire-at = Fire at:
+cue-editor-intensity-fade = Intensity fade:
+cue-editor-attribute-fade = Attribute fade:
+cue-editor-attribute-fade-hint = Colour, position and every channel but intensity
+cue-editor-vary = Vary:
+cue-editor-run-macro = Run macro:
+cue-editor-song = Song:
+cue-editor-section = Section:
+cue-editor-section-hint = Starts a new act or section here
+cue-editor-tag = Tag:
+cue-editor-notes-hint = Standby notes for the operator
+cue-editor-vary-each = Each time it fires
+cue-editor-vary-hint = Move levels by a random amount every firing, so it never looks quite the same
+cue-editor-vary-by = By up to ±
+cue-editor-vary-steps = steps
+cue-editor-vary-tick = Tick the attributes to vary
+cue-editor-vary-per-fixture = Each fixture by its own amount
+cue-editor-vary-seed = Same every run, seed
+cue-editor-vary-seed-hint = Untick to vary differently every time Halo starts
+cue-editor-bar = Bar
+cue-editor-bar-hint = Fire at a bar and beat of the musical timeline instead of at its time
+cue-editor-beat = beat
+cue-editor-always = Always
+cue-editor-add-variables = Add show variables under Cues → Variables
+cue-editor-add-value = Add a value to the cue
+cue-editor-values-count = { $count } values
+cue-editor-values = Values
+cue-editor-timing = Timing
+cue-editor-timing-hint = Everything fades in the cue's time; change a group to give it its own
+cue-editor-parts = Parts
+cue-editor-add-part = Add a part with its own fade and delay
+cue-editor-part = Part { $number }
+cue-editor-no-levels = The cue has no levels yet
+cue-editor-rest = { $count ->
+        [one] 1 other fixture fades
+       *[other] { $count } other fixtures fade
+    } in the cue's { $seconds }s
+cue-editor-custom-curve = Custom
+cue-editor-triggers = Triggers
+cue-editor-add-trigger = Add a trigger
+cue-editor-test = Test
+cue-editor-test-hint = Send this trigger now
+cue-editor-velocity = Vel
+cue-editor-osc-args = args
+cue-editor-json-body = JSON body
+cue-editor-serial-hint = text, \r \n \xFF
+cue-editor-serial-data-hint = Text to send, with \r, \n, \t and \xNN for other bytes
+cue-editor-relay = Relay
+cue-editor-relay-on = On
+cue-editor-pin = Pin
+cue-editor-pin-high = High
+cue-editor-pulse = Pulse
+cue-editor-pulse-hint = Switch back after this long; 0 leaves it switched
+
+## Cue triggers
+
+trigger-on-start = On Start
+trigger-on-end = On End
+trigger-serial = Serial
+trigger-relay = USB relay
+trigger-osc-failed = { $name }: OSC to { $target } failed: { $error }
+trigger-serial-output = { $name }: serial { $port }
+trigger-serial-data = { $name }: serial data for { $port }: { $error }
+trigger-relay-output = { $name }: relay { $relay } on { $port }
+trigger-gpio-output = { $name }: GPIO { $pin }
+trigger-no-midi-output = { $name }: no MIDI output set in Preferences
+trigger-webhook-answered = { $name }: webhook { $url } answered { $status }
+trigger-webhook-failed = { $name }: webhook { $url } failed: { $error }
+trigger-failed = { $what } failed: { $error }
+
+## Option names
+
+daw-format-reaper = REAPER marker CSV
+daw-format-pro-tools = Pro Tools marker list
+curve-linear = Linear
+curve-s = S-curve
+curve-custom = Custom
+busk-template-wash = Wash
+busk-template-split = Split
+busk-template-chase = Chase
+busk-template-pulse = Pulse
+busk-template-sweep = Sweep
+clock-role-off = Off
+clock-role-main = Main
+clock-role-backup = Backup
+end-action-continue = Carry on
+end-action-stop = Stop
+end-action-loop = Loop
+end-action-chain = Chain to song
+audio-band-level = Level
+audio-band-bass = Bass
+audio-band-mid = Mid
+audio-band-high = High
+direction-in = In
+direction-out = Out
+stop-output-hold = Hold the last look
+stop-output-fade = Fade to zero
+stop-output-safe-state = Snap to the safe state
+attribute-group-intensity = Intensity
+attribute-group-colour = Colour
+attribute-group-position = Position
+attribute-group-beam = Beam
+control-lamp-on = Lamp On
+control-lamp-off = Lamp Off
+control-reset = Reset
+flash-mode-add = Add
+flash-mode-solo = Solo
+pattern-full = All @ full
+pattern-ramp = Ramp
+pattern-alternating = Alternating
+pattern-chase = Chase
+colour-red = Red
+colour-orange = Orange
+colour-yellow = Yellow
+colour-green = Green
+colour-cyan = Cyan
+colour-blue = Blue
+colour-purple = Purple
+colour-pink = Pink
+merge-mode-priority = Priority
+conflict-keep-both = Keep both
+conflict-replace = Replace
+conflict-skip = Skip
+snap-seconds = Seconds
+snap-frames = Frames
+snap-beats = Beats
+snap-bars = Bars
+snap-hits = Hits
+edit-mode-track = Track
+edit-mode-cue-only = Cue only
+theme-dark = Dark
+theme-light = Light
+theme-high-contrast = High contrast
+colour-amber = Amber
+colour-white = White
+fan-mode-linear = Linear
+fan-mode-symmetrical = Symmetrical
+fan-mode-random = Random
+fan-order-patch = Patch order
+fan-order-group = Group order
+curve-ease-in = Ease in
+curve-ease-out = Ease out
+generator-gradient = Gradient
+generator-chase = Chase
+generator-plasma = Plasma
+generator-beat-pulse = Beat Pulse
+generator-bar-pulse = Bar Pulse
+generator-media = Media
+generator-plugin = Plugin
+modulation-brightness = Brightness
+modulation-size = Size
+modulation-speed = Speed
+mmc-play = Play
+mmc-locate = Locate { $position }
+macro-fire-cue = Fire cue { $number }
+macro-pad-on = { $pad } on
+macro-pad-off = { $pad } off
+macro-song = Song { $number }
+record-source-output = Halo's output
+record-source-input = Art-Net / sACN input
+bulb-hue-light = Hue light { $light }
+fan-target-hue = Hue
+fan-target-gradient = Colour gradient
+fan-target-white-point = Colour temperature
+sequence-lost = { $count } lost
+sequence-out-of-order = out of order
+sequence-terminated = stream terminated
+schedule-sunrise = Sunrise
+schedule-after-sunrise = { $minutes } min after sunrise
+schedule-before-sunrise = { $minutes } min before sunrise
+schedule-sunset = Sunset
+schedule-after-sunset = { $minutes } min after sunset
+schedule-before-sunset = { $minutes } min before sunset
+schedule-time-of-day = Time of day
+pad-release-toggle = Toggle
+pad-release-momentary = Momentary
+pad-release-seconds = Timed (seconds)
+pad-release-bars = Timed (bars)
+
+## Merging shows
+
+merge-songs = { $count ->
+        [one] 1 song
+       *[other] { $count } songs
+    }
+merge-cues = { $count ->
+        [one] 1 cue
+       *[other] { $count } cues
+    }
+merge-group-masters = { $count ->
+        [one] 1 group master
+       *[other] { $count } group masters
+    }
+merge-macros = { $count ->
+        [one] 1 macro
+       *[other] { $count } macros
+    }
+merge-colours = { $count ->
+        [one] 1 palette colour
+       *[other] { $count } palette colours
+    }
+merge-nothing = nothing
+merge-merged = merged { $parts }
+merge-skipped = ; skipped { $count } clashes
+merge-dropped = ; dropped { $count } values for fixtures not in this show
+merge-title = Merge From Show
+merge-show-file = Show file:
+merge-cue-lists = Cue lists
+merge-song-cues = { $song } ({ $count } cues)
+merge-loose-cues = Cues outside any song ({ $count })
+merge-group-masters-header = Group masters
+merge-palette = Palette
+merge-busk-colours = Busk colours ({ $count })
+merge-profiles = Fixture profiles
+merge-conflict = Same name or number:
+merge-conflict-hint = Keep both renames songs, masters, macros and profiles and numbers cues after the last one
+merge-fixtures-hint = Cues find their fixtures by name; values for fixtures not here are left out.
+merge-button = Merge
+merge-profile-failed = Could not add profile { $profile }: { $error }
+merge-profiles-added = ; added { $count } fixture profiles
+merge-done = From { $path }: { $report }
+
+## Detached panels
+
+panel-cue-list = Cue List
+panel-detach = Detach to Window
+panel-window-title = Halo – { $panel }
+
+## Output engine
+
+engine-stall-details = { $since } ms since its last frame, { $pending } commands pending, playhead at { $playhead }, { $transport }
+engine-exited = Output engine exited ({ $details })
+engine-stalled = Output engine stalled ({ $details })
+engine-restarted = Output engine stopped responding and was restarted
+engine-outputs-waiting = The stalled engine still holds the outputs, drivers and any recording; the restarted one takes them over once it lets go of them
+engine-recording-lost = The output recording or playback stopped with the engine
+engine-took-over = The restarted engine took over the outputs
+
+## Patch
+
+patch-send-as = Send as
+patch-address = Address
+patch-channel = Channel
+patch-first-cc = First CC
+patch-osc-hint = Each channel goes to <address>/<channel name> as a level from 0 to 1.
+patch-http-hint = {"{"}intensity{"}"}, {"{"}red{"}"} and the other channel names in braces become levels from 0 to 255, and {"{"}hex{"}"} the colour as RRGGBB.
+patch-midi-hint = Each channel takes the next controller, with levels from 0 to 127.
+patch-driver-hint = The address holds the fixture's levels in Halo; they aren't sent as DMX.
+patch-title = Patch Editor
+patch-import = Import Patch...
+patch-venue = Venue...
+patch-type = Type
+patch-mib = MIB
+patch-mib-hint = Move in black: while dark, take the position and colour of the next cue
+patch-off = Off
+patch-off-hint = Inhibit: keep the fixture dark and out of channel check, programming and all
+patch-move-in-black-named = Move { $name } in black
+patch-inhibit-named = Inhibit { $name }
+patch-clone = Clone
+patch-clone-hint = Add another of this fixture with the same programming
+patch-output = Output
+patch-output-changed = Output ●
+patch-output-hint = Invert, limits, dimmer curves, pan/tilt orientation and protocol
+patch-cloned = Added { $name } at { $address }
+patch-clone-no-room = No room left in the universe for the clone
+patch-move-in-black = Move in black
+patch-before-cue = before the cue
+patch-add-fixture = Add Fixture
+patch-browse = Browse the fixture library
+patch-inhibited = Inhibited ({ $count }):
+patch-restore-all = Restore All
+patch-restored = Restored every inhibited fixture
+patch-processing = Output Processing: { $name }
+patch-curve = Curve
+patch-invert = Invert
+patch-limits = Limits
+patch-to = to
+patch-levels = Levels:
+patch-remove-point = Remove a point
+patch-add-point = Add a point
+patch-custom-curve-hint = Custom curves give the output level at evenly spaced input levels.
+patch-position = Position
+patch-swap = Swap pan/tilt
+patch-invert-pan = Invert pan
+patch-invert-tilt = Invert tilt
+patch-home-offset = Home offset: pan
+patch-tilt = tilt
+patch-position-hint = Applied at output, so the fixture takes the same positions as the rest of its group. Invert and offsets act on the fixture's own channels, after any swap.
+patch-copy-values = Copy Values
+patch-from = From:
+patch-to-label = To:
+patch-copy = Copy
+patch-copy-hint = Replace everything the second fixture has programmed with the first one's
+patch-copied = Copied fixture values in { $count ->
+        [one] 1 cue
+       *[other] { $count } cues
+    }
+patch-replace-type = Replace Fixture Type
+patch-with = with
+patch-replaced = Replaced { $count } × { $old } with { $new }; { $dropped } cue values had no equivalent
+patch-replaced-footprint = { $new } uses more channels than { $old }; check the addresses
+patch-gateway = Gateway:
+patch-discover = Discover
+patch-rdm-port = RDM needs the Art-Net port, so turn off Art-Net input while using it.
+patch-device = Device
+patch-channels = Channels
+patch-patched-as = Patched as
+patch-set = Set
+patch-set-hint = Send the new start address to the fixture
+patch-identify = Identify
+patch-not-patched = Not in the patch
+
+## Status bar
+
+status-rates = UI: { $ui } fps  Output: { $output } Hz
+status-frame-graph = Show the frame time graph
+status-dropping-frames = ⚠ Dropping output frames ({ $count } total)
+status-rec = ● REC
+status-rec-hint = Recording the output; click to open Output Recording
+status-playback = ▶ PLAYBACK
+status-playback-hint = A recording has the output, not the show; click to open Output Recording
+status-power-hint = Estimated draw is over the circuit limit; click to open Power
+status-venue = Venue: { $name }
+status-venue-hint = Output goes through this venue's mapping; click to open it
+status-capped = ⚠ Capped: { $groups }
+status-capped-hint = Group masters are holding these groups down; click to open them
+status-test-pattern = test pattern on { $universe }
+status-muted = muted { $universes }
+status-universes = ⚠ Universes: { $state }
+status-universes-hint = Some universes aren't playing the show; click to open the output monitor
+status-link-no-peers = Link: no peers
+status-link-off = Link: off
+status-notifications = Notifications
+status-network-any = Network: any
+status-network-any-hint = Every protocol uses all interfaces
+status-network-up = Network: up
+status-interface-down = { $protocol } interface { $name } is down
+status-network-down = Network: down
+status-clock-local = Clock: local
+status-clock-local-hint = Not sharing the clock with a main or backup
+status-clock-error = Clock: error
+status-clock-main = Clock: main
+status-clock-taken-over = Clock: taken over
+status-clock-taken-over-hint = This backup is sending the output in place of the main
+status-clock-lost = Clock: lost
+status-clock-waiting = Clock: waiting
+status-clock-waiting-for = Waiting for { $main }
+status-clock-offset = Clock: { $offset } ms
+status-clock-following = Following { $main }; { $corrections } corrections
+status-art-net-error = Art-Net: error
+status-art-net-standby = Art-Net: standby
+status-art-net-standby-hint = Held while the main sends the output
+status-art-net-off = Art-Net: off
+status-art-net-off-hint = Art-Net output is off
+status-art-net-rate = { $address }: { $achieved } of { $target } Hz
+status-art-net-hz = Art-Net: { $rate } Hz
+status-dmx-in-error = DMX in: error
+status-dmx-in-off = DMX in: off
+status-dmx-in-off-hint = Art-Net and sACN input are off
+status-dmx-in-nothing = Listening; nothing received
+status-dmx-in-source = Universe { $universe }: { $protocol } from { $name }, { $rate } packets/s
+status-dmx-in = DMX in: { $rates }
+status-midi-off = MIDI: off
+status-midi-off-hint = No MIDI ports chosen
+status-midi-connected-port = { $direction }: { $name } (connected)
+status-midi-disconnected-port = { $direction }: { $name } (disconnected)
+status-midi-no-port = { $direction }: none
+status-midi-connected = MIDI: connected
+status-midi-disconnected = MIDI: disconnected
+status-audio-click-error = Audio: click error
+status-audio-off = Audio: off
+status-audio-off-hint = No audio input chosen
+status-audio-error = Audio: error
+status-audio-on = Audio: on
+status-audio-stopped = Audio: stopped
+status-server-off = { $name }: off
+status-server-listening = { $name }: listening on port { $port }
+status-remote-off = Remote: off
+status-remote-error = Remote: error
+status-remote-on = Remote: on
+
+## Pixel maps
+
+pixel-maps-title = Pixel Maps
+pixel-map-name = Pixel Map { $number }
+pixel-presets = Presets...
+pixel-size = Size:
+pixel-serpentine = Serpentine
+pixel-send-as = Send as:
+pixel-controller-address = controller address
+pixel-patch = Patch:
+pixel-content = Content:
+pixel-plugin = Plugin:
+pixel-no-plugins = No generator plugins loaded
+pixel-colours = Colours:
+pixel-brightness = Brightness:
+pixel-audio = Audio:
+pixel-react-to = React to
+pixel-react-on = on
+pixel-depth = Depth:
+pixel-media-file = Media file:
+pixel-playback = Playback:
+pixel-load-failed = Could not load media: { $error }
+pixel-map-error = Pixel map { $name }: { $error }
+pixel-rate = Rate:
+pixel-resized = The map was resized; load the media again.
+pixel-clip = { $frames } frames, { $seconds }s
+pixel-media-hint = GIFs load directly; video needs ffmpeg on the PATH.
+pixel-dmx-summary = { $count } pixels, { $first } to { $last } ({ $per_universe } per universe)
+pixel-stream-summary = { $count } pixels over { $output } to { $host }
+pixel-choose-audio = Choose an audio input in Preferences.
+pixel-media-failed = Pixel map { $name }: could not load media: { $error }
+
+## Output recording
+
+recording-started = Recording { $source } to { $path }
+recording-stopped = Stopped recording to { $path }
+recording-imported = Imported { $count ->
+        [one] 1 cue
+       *[other] { $count } cues
+    } from { $path }
+recording-backwards = A frame in the recording goes back in time, to { $at } ms, so it can't be analyzed
+recording-cue-name = Imported { $number }
+recording-playing = Playing back { $path }
+recording-playback-stopped = Stopped playing back the output recording
+recording-record = Record
+recording-source = Record:
+recording-input-off = Turn on Network Input in the preferences to receive anything.
+recording-stop = ⏹ Stop Recording
+recording-length = ● Recording { $length }
+recording-start = ⏺ Record
+recording-start-hint = Record every frame sent, until stopped
+recording-play-back = Play back
+recording-stop-playback = ⏹ Stop
+recording-play = ▶ Play
+recording-play-hint = Send the recording in place of the show
+recording-playing-hint = The recording has the output until stopped. Panic still blacks out.
+recording-import = Import as cues
+recording-hold = Look holds for:
+recording-hold-hint = Shorter holds are steps in a fade or an effect, not cues
+recording-tolerance = Ignore changes up to:
+recording-tolerance-hint = Levels that flicker by this much are noise
+recording-start-at = Start at:
+recording-start-at-hint = Where in the show the first cue goes
+recording-changes-only = Only store what changed from the cue before
+recording-analyze = Analyze
+recording-analyze-hint = Find the looks in the recording, using this show's patch
+recording-look = Look
+recording-starts = Starts
+recording-add-cues = Add { $count ->
+        [one] 1 Cue
+       *[other] { $count } Cues
+    }
+recording-add-cues-hint = Add them after the last cue
+
+## Show file
+
+show-backup-failed = Could not back up the show: { $error }
+show-saved = Saved { $path }
+show-saved-copy = Saved a copy in show format { $version } to { $path }
+show-newer-warning = { $path } was saved by a newer Halo, in show format { $version }
+show-load-title = Load Show
+show-save-as-title = Save Show As
+show-save-package-title = Save Show As Package
+show-format-label = Format:
+show-format-this = { $version } (this Halo)
+show-format-older = { $version } (older Halo)
+show-format-older-hint = Saves a copy for an older Halo, leaving this show's file as it is. Anything the older format can't hold is left out.
+show-package-hint = Bundles the show, its audio and custom fixture profiles into one .{ $extension } file.
+show-newer-title = Show From a Newer Halo
+show-newer-body = This show was saved in show format { $version }, by a newer Halo. This one reads up to format { $current }.
+show-newer-advice = Anything it doesn't know about was left out, and saving over the show file would lose it for good. Save a copy to keep working here.
+show-save-copy = Save a Copy...
+show-keep-working = Keep Working
+show-properties-title = Show Properties
+show-audio-file = Audio file:
+show-not-saved = Not saved
+show-back-fade = Back fade:
+show-back-fade-hint = How long Back takes to fade to the previous cue
+show-lock = Lock
+show-locked-hint = The show opens in performance mode and needs the passcode to leave it.
+show-remove-lock = Remove Lock
+show-lock-removed = Removed the show lock
+show-lock-hint = Keep the show in performance mode until a passcode is entered.
+show-passcode = Passcode:
+show-passcode-again = Again:
+show-passcode-mismatch = The passcodes don't match
+show-lock-button = Lock Show
+show-lock-button-hint = Save the show afterwards to keep the lock
+show-locked = Locked the show
+schema-pad-banks = Pad banks can come up with songs and cue list sections
+schema-unversioned = Before the format was versioned
+
+## Songs
+
+songs-the-start = the start
+songs-removed = (removed)
+songs-title = Songs
+songs-add = Add Song
+songs-close-gaps = Close Gaps
+songs-close-gaps-hint = Lay the songs out back to back in this order
+songs-cues-hint = Cues in a song are timed from its start. Assign them in the cue editor.
+songs-cue-list-end = At the end of the cue list:
+songs-empty = No songs yet.
+songs-at-end = At the end
+songs-at-end-hint = What happens when the song ends in setlist mode
+songs-audio-hint = The song's own audio file and trim, played in place of the show's
+songs-remove = Remove song
+songs-setlist = Setlist
+
+## Fan
+
+fan-hue-shift = Shift the cue's hues
+fan-hue-shift-hint = Turn the colours round the wheel so the fan chases along the fixtures
+fan-hue-period = Once round every
+fan-title = Fan
+fan-into-cue = Into cue { $number } { $name }
+fan-select-cue = Select a cue to fan into.
+fan-rainbow = 🌈 Rainbow
+fan-rainbow-hint = Set up a full rainbow round the selection
+fan-select-type = Select every fixture of this type
+fan-mode-label = Fan:
+fan-order-label = Order:
+fan-direction-label = Direction:
+fan-wrap = Wrap
+fan-wrap-hint = Make the fan a loop that joins up from the last fixture to the first
+fan-middle = Middle:
+fan-ends = Ends:
+fan-from = From:
+fan-to = To:
+fan-seed = Seed:
+fan-another-seed = Try another seed
+fan-no-fixtures = Fan: none of the fixtures selected have { $target }
+fan-done = Fanned { $target } across { $count } fixtures in cue { $number }
+
+## Pads
+
+pad-default-label = Pad { $number }
+pad-release-label = Release:
+pad-fade-out = Fade out:
+pad-flash-fixtures = Flash fixtures
+pad-flash-solo-hint = Solo takes every other fixture out while the flash is up
+pad-columns = Columns:
+pad-rows = Rows:
+pad-bank-name = Bank name:
+pad-bank-songs = Comes Up for Songs
+pad-bank-sections = Comes Up for Sections
+pad-add-bank = Add Bank
+pad-default-bank = Bank { $number }
+pad-remove-bank = Remove Bank
+pad-remove-bank-hint = Removes this bank and its pads
+pad-layout = ⚙ Layout
+pad-accessible-on = Pad { $label }, on
+pad-accessible-off = Pad { $label }, off
+pad-flash = Flash
+pad-edit-script = Edit Script...
+pad-run-macro = Run Macro
+pad-move-to-bank = Move to Bank
+pad-delete = Delete Pad
+pad-add = Add a pad
+
+## Output monitor
+
+monitor-nothing = Nothing is being output.
+monitor-mute = Mute
+monitor-mute-hint = Stop sending this universe to the DMX outputs
+monitor-muted = Muted universe { $universe }
+monitor-unmuted = Unmuted universe { $universe }
+monitor-unmute-all = Unmute All
+monitor-unmuted-all = Unmuted every universe
+monitor-levels = Output levels of universe { $universe }
+monitor-from = from { $owner }
+monitor-click-channel = Click a channel to see what is controlling it.
+monitor-fading = { $name } fading { $percent }%
+monitor-uncontrolled = nothing is controlling it
+monitor-test-pattern = Test pattern:
+monitor-on-universe = on universe
+monitor-show-not-here = The show is not on this universe
+monitor-channel-check = Channel Check
+monitor-channel-check-hint = Test one channel at a time.
+monitor-previous-channel = Previous channel
+monitor-next-channel = Next channel
+monitor-solo-hint = Black out every other channel
+monitor-inhibited = {" "}(inhibited)
+monitor-checking = Checking { $channel } at { $level }: { $patched }. Use ← and → to step.
+monitor-not-patched = not patched
+
+## Show diff
+
+diff-renamed = Renamed from "{ $before }" to "{ $after }"
+diff-start = Starts at { $after }, was { $before }
+diff-duration = Takes { $after }s, was { $before }s
+diff-also-changed = Also changed: { $fields }
+diff-before = Before
+diff-after = After
+diff-read-failed = Could not read the show: { $error }
+diff-compare-label = Compare:
+diff-last-save = Last save
+diff-show-file = Show file
+diff-this-show = This show
+diff-cues-label = Cues:
+diff-all-cues = All cues
+diff-with-this = With this show's:
+diff-compare = Compare
+diff-pick = Pick what to compare, then Compare.
+diff-none = No differences.
+diff-added = { $count } added
+diff-removed = { $count } removed
+diff-changed = { $count } changed
+
+## Inspecting fixtures
+
+inspect-profile-at = { $profile } at { $address }
+inspect-nothing = nothing
+owner-cue-index = cue #{ $index }
+owner-cue = Cue { $number } { $name }
+owner-move-in-black = Move in black for { $cue }
+owner-pixel-map = Pixel map
+owner-pixel-map-named = Pixel map { $name }
+owner-haze-schedule = Haze schedule
+owner-smoke-pad = { $pad } pad
+owner-input = Network input
+owner-quick-console = Quick console
+owner-follow-spot = Follow spot
+owner-follow-spot-named = Follow spot { $name }
+owner-flash = Flash on the { $pad } pad
+owner-flash-solo = Dimmed by a solo flash
+owner-group-master = Group master
+owner-group-master-at = Group master { $name } at { $percent }%
+owner-grand-master = Grand master at { $percent }%
+owner-processing = Output processing
+owner-inhibited = Inhibited
+owner-channel-check = Channel check
+owner-test-pattern = Test pattern
+owner-panic = Safe state
+owner-release = Release on stop
+owner-recording = Output recording
+
+## Venue
+
+venue-title = Venue
+venue-description = Cues are programmed on the show's patch. A venue mapping plays each fixture there on the units in this venue's rig, so touring only needs a new mapping.
+venue-new-from-patch = New from Patch
+venue-new-from-patch-hint = Start a mapping that plays every fixture where it's patched
+venue-new-name = New venue
+venue-use-show-patch = Use Show Patch
+venue-use-show-patch-hint = Output to the show's own patch
+venue-none = No venue mapping: output goes to the show's patch.
+venue-label = Venue:
+venue-as-programmed = As programmed
+venue-add-unit = Add Unit
+venue-unmapped = Not played at this venue: { $fixtures }
+venue-unknown = Not in the show's patch: { $fixtures }
+venue-cleared = Venue mapping cleared; output goes to the show's patch
+venue-load-failed = Could not load the mapping: { $error }
+venue-saved = Saved venue { $name } to { $path }
+venue-save-failed = Could not save the mapping: { $error }
+venue-loaded = Venue { $name }: { $count ->
+        [one] { $count } unit mapped
+       *[other] { $count } units mapped
+    }
+venue-mapping-failed = Could not load venue mapping { $path }: { $error }
+
+## Timecode
+
+timecode-title = Timecode
+timecode-start = Start:
+timecode-zero = Zero
+timecode-offset = Offset:
+timecode-offset-hint = Added to incoming timecode
+timecode-preroll = Pre-roll:
+timecode-preroll-hint = Count down before playing from the top; 0 is off
+timecode-drift = Drift warning:
+timecode-drift-hint = Flash the readout when Halo and the incoming timecode are further apart
+timecode-chase = Chase tolerance:
+timecode-chase-hint = Jump to incoming timecode when further from it than this
+timecode-late-join = Late join:
+timecode-late-join-hint = After jumping to incoming timecode, fire the actions of cues that started this long before; 0 fires none
+timecode-late-cue = Late cue:
+timecode-late-cue-hint = Mark cues that fire further than this from their time on incoming timecode
+timecode-incoming = Incoming:
+timecode-incoming-rolling = { $position } at { $fps } fps → { $show }
+timecode-incoming-stopped = { $position } at { $fps } fps → { $show } (stopped)
+timecode-none = No MIDI timecode received
+timecode-turn-on-chase = Turn on Chase MIDI timecode in Preferences to follow it.
+timecode-stopped = Timecode stopped
+
+## Packet inspector
+
+inspector-sources = Sources
+inspector-packets = Packets
+inspector-resume = ▶ Resume
+inspector-pause = ⏸ Pause
+inspector-input-off = Network input is off, so nothing is being received.
+inspector-duplicated = ⚠ Universe { $universe } has more than one source sending; they merge highest level wins unless their priorities differ.
+inspector-protocol = Protocol
+inspector-from = From
+inspector-priority = Priority
+inspector-rate = Rate
+inspector-lost-late = Lost / Late
+inspector-show-universe = Show this universe's packets
+inspector-quiet = quiet { $seconds }s
+inspector-only-problems = Only problems
+inspector-time = Time
+inspector-seq = Seq
+
+## Follow spots
+
+follow-tracker = Tracker { $id }
+follow-tracker-silent = Tracker { $id } (silent)
+follow-not-patched = (not patched)
+follow-add-fixture = Add Fixture
+follow-add-group = Add Group
+follow-title = Follow Spots
+follow-turn-on-psn = Turn on PSN input in the preferences for the spots to follow.
+follow-trackers-sending = { $count ->
+        [0] No trackers sending
+        [one] 1 tracker sending
+       *[other] { $count } trackers sending
+    }
+follow-add-spot = Add Spot
+follow-default-name = Spot { $number }
+follow-follows = Follows:
+follow-smoothing = Smoothing:
+follow-height = Height:
+follow-trim = Trim:
+follow-trim-hint = Across and up or downstage
+follow-pan-tilt-range = Pan / tilt range:
+follow-heads = Heads, hung at x, y (height) and z:
+
+## Group masters
+
+masters-move-fader = Move a fader...
+masters-dmx-fader = DMX { $fader }
+masters-dmx-learn = DMX Learn
+masters-dmx-learn-hint = Follow a fader wing channel
+masters-clear-dmx = Clear DMX
+masters-title = Group Masters
+masters-default-name = Group { $number }
+masters-hint = Caps a group's intensity without adding any.
+masters-grand = Grand master:
+masters-full = Full
+masters-flash = Flash master:
+masters-flash-hint = How high flash pads bring their fixtures
+masters-empty = Add a master, then pick the groups it caps.
+masters-no-groups = No groups
+masters-groups = { $count } groups
+masters-move-control = Move a control...
+masters-midi-learn = MIDI Learn
+
+## Smart bulbs
+
+bulbs-title = Smart Bulbs
+bulbs-enable = Send fixture colours to Hue and WLED devices
+bulbs-hue-bridge = Hue bridge
+bulbs-paired = Paired:
+bulbs-pair = Pair
+bulbs-pair-hint = Press the bridge's link button first
+bulbs-bulbs = Bulbs
+bulbs-add = Map a fixture to a bulb
+bulbs-empty = Each fixture's colour and intensity go to the bulb it is mapped to.
+bulbs-missing = (missing)
+bulbs-light = Light
+bulbs-leds = LEDs
+bulbs-paired-done = Paired with the Hue bridge
+bulbs-pair-failed = Couldn't pair with the Hue bridge: { $error }
+bulbs-pair-first = pair with the bridge first
+bulbs-no-user-name = the bridge gave no user name
+
+## Stage display
+
+stage-next = Next: { $number }
+stage-next-named = Next: { $number } { $name }
+stage-no-more = No more cues
+stage-viewport-title = Halo – Stage Display
+stage-open = Show on Stage Display
+stage-open-hint = Opens a window of its own; drag it to the monitor to use
+stage-fullscreen = Full screen
+stage-fullscreen-hint = Fill the monitor the window is on
+stage-next-cue = Next cue
+stage-countdown = Countdown
+stage-text = Text:
+stage-background = Background:
+stage-standby = Standby:
+stage-standby-from = Standby from:
+stage-standby-suffix = {" "}s before the cue
+stage-standby-hint = 0 turns standby off
+stage-flash = Flash in standby
+
+## Spectrogram
+
+spectrogram-title = Spectrogram
+spectrogram-no-audio = Set an audio file in Show Properties to see it here.
+spectrogram-analysing = Analysing { $path }...
+spectrogram-read-failed = Couldn't read the audio: { $error }
+spectrogram-snap = Snap to
+spectrogram-snap-hint = Dragged cues land on the grid, or on a hit when near one
+spectrogram-zoom = Zoom:
+spectrogram-hits = { $count } hits
+spectrogram-beat-grid = Beat grid
+spectrogram-use-current = Use Current
+spectrogram-use-current-hint = Take the tempo from the header
+spectrogram-downbeat = Downbeat:
+spectrogram-at-playhead = At Playhead
+spectrogram-at-playhead-hint = Put a first beat of the bar at the playhead
+spectrogram-accessible = Waveform with cues; drag a cue to move it, or select it and nudge with [ and ]
+spectrogram-hold-shift = Hold Shift to place without snapping
+spectrogram-drag-hint = Drag a cue to move it, drag elsewhere to scroll, scroll to zoom
+
+## Effect presets
+
+presets-title = Effect Presets
+presets-applies-to = Applies to pixel map { $name }
+presets-select-map = Select a pixel map to apply presets to or save from.
+presets-empty = No presets saved yet.
+presets-uncategorised = Uncategorised
+presets-generator-at = { $generator } at { $speed } Hz
+presets-export-hint = Save to the file below
+presets-category = Category:
+presets-save-from-map = Save from Pixel Map
+presets-applied = Applied effect preset { $preset } to { $map }
+presets-choose-file = Choose a file to export to
+presets-exported = Exported effect preset { $preset } to { $path }
+presets-saved = Saved effect preset { $preset }
+presets-imported = Imported effect preset { $preset }
+
+## DAW markers
+
+daw-title = DAW Markers
+daw-description = Cues go out as markers named Q<number> <name>. Reading a list back moves each named cue to its marker, and adds a cue for any other marker.
+daw-timecode = Timecode:
+daw-fps-hint = For Pro Tools locations, from the show's start timecode
+daw-read = Read
+daw-marker = Marker
+daw-does = Does
+daw-already-there = Cue { $number } already there
+daw-moves = Moves cue { $number } from { $from }
+daw-new-cue = New cue
+daw-apply = Apply to Cues
+daw-exported = Exported { $count } cues as markers to { $path }
+daw-export-failed = Could not export: { $error }
+daw-read-failed = Could not read the markers: { $error }
+daw-applied = DAW markers: { $moved } cues moved, { $added } added
+daw-no-markers = no markers found
+daw-no-column = no { $name } column
+daw-bad-time = row { $row }: can't read the time “{ $time }”
+daw-no-location = no LOCATION or NAME column
+daw-bad-location = can't read the location in “{ $line }”
+
+## Power
+
+power-of = { $draw } of { $limit }
+power-whole-rig = Whole rig
+power-total = Total
+power-unrated = { $count } fixtures have no wattage and aren't counted.
+power-breakers = Circuit breakers take
+power-breakers-hint = Volts times amps: 2300 W is a 10 A circuit at 230 V
+power-now = Now
+power-peaks = Peak draw by cue
+power-wattage = Wattage and circuits
+power-peaks-hint = Each cue played in order at full, without masters or effects.
+power-peak = Peak
+power-over = Over the circuit limit
+power-full-hint = Watts at full
+power-idle = Idle
+power-idle-hint = Watts while dark
+power-circuit = Circuit
+power-no-circuit = none
+
+## Importing a patch
+
+import-patch-title = Import Patch
+import-patch-hint = An MVR file, or a patch exported as CSV from grandMA or ETC.
+import-patch-read-failed = Could not read the patch: { $error }
+import-patch-summary = { $fixtures } fixtures of { $types } types. Pick the Halo profile for each type:
+import-patch-profile = Profile
+import-patch-no-type = (no type)
+import-patch-skip = Skip
+import-patch-replace = Replace the current patch
+import-patch-replace-warning = Cue values for the current fixtures will no longer apply.
+import-patch-done = Imported { $added } fixtures from { $path }; { $skipped } skipped
+import-patch-overlap = { $count } fixtures overlap others; check the address map
+
+## Designer view
+
+designer-title = Designer View
+designer-operator = Operator:
+designer-as = As:
+designer-hint = Follows another Halo's remote control port, read-only unless the operator grants sections of the cue list.
+designer-connecting = Connecting...
+designer-bpm = { $bpm } BPM
+designer-current-cue = Cue { $number }: { $name }
+designer-no-cue = No cue
+designer-levels = Output levels:
+designer-no-followers = No designer views have connected.
+designer-gone = { $name } (gone)
+designer-may-fire = may fire:
+designer-unsectioned = Unsectioned
+designer-connected = Designer view { $name } connected
+designer-fired = Designer view { $name } fired cue { $number }
+
+## Haze
+
+haze-title = Haze
+haze-enable = Run the haze schedule
+haze-pad-on = { $pad } pad on
+haze-show-ended = Off, show ended
+haze-hazing = Hazing
+haze-waiting = Waiting
+haze-hazers = Hazers
+haze-none = Patch a fixture with a Haze channel to drive it here.
+haze-fixture = { $name } (haze on { $channel })
+haze-burst = Burst:
+haze-every = Every:
+haze-pad = Override pad:
+haze-pad-output = Pad output:
+haze-hint = The schedule runs while the show plays and shuts the hazers off after the last cue.
+
+## Tracksheet
+
+tracksheet-edit = Edit:
+tracksheet-edit-hint = Track carries changes on until a later cue changes the same value; Cue only puts the old value back in the next cue
+tracksheet-never-set = Never set; double-click to set
+tracksheet-set-here = Set in this cue
+tracksheet-blocked = Held at the level it tracked in at
+tracksheet-tracks-from = Tracks from cue { $number }
+tracksheet-title = Tracksheet
+tracksheet-legend-changed = changed here
+tracksheet-legend-tracked = tracked
+tracksheet-legend-blocked = held by a block
+tracksheet-hint = Double-click a cell to edit; leave it empty to track.
+tracksheet-bad-level = Tracksheet: "{ $text }" isn't a level from 0 to 255
+
+## Schedule
+
+schedule-offset-prefix = offset{" "}
+schedule-title = Schedule
+schedule-hint = Actions run at a time of day, or at sunrise or sunset at the venue.
+schedule-latitude = Latitude:
+schedule-longitude = Longitude:
+schedule-on = On
+schedule-when = When
+schedule-action = Action
+schedule-next = Next
+schedule-never = Never
+schedule-ran = Schedule { $when }: { $action }
+
+## Frame timings
+
+frame-ui-worst = UI { $ms } ms worst
+frame-output-worst = Output { $ms } ms worst
+frame-target = Target { $ms } ms
+frame-dropped = Dropped output frames: { $count }
+frame-stage = Stage
+frame-last = Last
+frame-average = Average
+frame-worst = Worst
+frame-share = Of the frame
+frame-total = Total
+frame-of = of { $time }
+frame-waiting = Waiting for the output engine
+frame-history = Over the last { $count } output frames
+frame-stage-cues = Cues
+frame-stage-merge = Merge
+frame-stage-ownership = Ownership
+frame-stage-effects = Effects and pixel maps
+frame-stage-haze = Haze, input and follow spots
+frame-stage-masters = Masters
+frame-stage-processing = Processing and overrides
+frame-stage-sending = Sending
+
+## Fixture library
+
+library-footprint = Footprint: { $count ->
+        [one] { $count } channel
+       *[other] { $count } channels
+    }
+library-white = White: { $warm }K to { $cool }K
+library-controls = Controls: { $controls }
+library-title = Fixture Library
+library-favourites = ★ Favourites:
+library-recent = Recent:
+library-manufacturer = Manufacturer
+library-model = Model
+library-mode = Mode
+library-favourite-on = ★ Favourite
+library-favourite-off = ☆ Favourite
+library-use = Use for New Fixtures
+
+## MIDI monitor
+
+midi-monitor-in = In
+midi-monitor-out = Out
+midi-monitor-channel = Ch
+midi-monitor-timing = Clock and timecode
+midi-monitor-timing-hint = Record clock pulses, MTC quarter frames and active sensing
+midi-monitor-no-ports = No MIDI ports are open; choose them in Preferences.
+midi-monitor-port = Port
+midi-monitor-data = Data
+
+## Snapshots
+
+snapshots-title = Output Snapshots
+snapshots-capture = Capture
+snapshots-default-name = Snapshot { $number }
+snapshots-reference-label = Reference:
+snapshots-delete-hint = Delete this snapshot from the show
+snapshots-identical = Snapshots are identical
+snapshots-differ = { $count ->
+        [one] { $count } channel differs
+       *[other] { $count } channels differ
+    }
+snapshots-patched = Patched
+snapshots-reference = Reference
+snapshots-unpatched = unpatched
+look-name = Look { $label }
+look-recorded = Recorded { $name } as cue { $number } at { $time }
+
+## Plugins
+
+plugins-title = Plugins
+plugins-folder = Plugins are read from { $path }
+plugins-no-folder = There is no configuration folder for plugins.
+plugins-reload = Reload
+plugins-none = No plugins loaded. Add .rhai scripts to the folder and reload.
+plugins-generator-output = Generator, output
+plugins-generator = Generator
+plugins-output = Output
+plugins-nothing = Nothing
+plugins-ok = OK
+plugins-outputs = Outputs
+plugins-send-to = Send to:
+plugins-no-entry-point = defines neither pixel() nor packet()
+plugins-read-failed = Couldn't read plugin { $path }: { $error }
+plugins-error = Plugin { $name }: { $error }
+plugins-loaded = Loaded { $count ->
+        [one] { $count } plugin
+       *[other] { $count } plugins
+    }
+
+## Macros
+
+macros-recording = ● Recording “{ $name }” ({ $count ->
+        [one] { $count } step
+       *[other] { $count } steps
+    })
+macros-record = ● Record
+macros-default-name = Macro { $number }
+macros-hint = Fire cues, toggle pads and change the BPM to record them.
+macros-none = No macros recorded.
+macros-play = Play
+macros-entry = { $name } ({ $count ->
+        [one] { $count } step
+       *[other] { $count } steps
+    })
+macros-no-cue = Macro: no cue { $number }
+macros-no-next-cue = GO: no next cue
+macros-no-pad = Macro: no pad “{ $pad }”
+macros-no-bank = Macro: no pad bank { $number }
+macros-no-macro = No macro named “{ $name }”
+macros-too-many = Macro “{ $name }” not started: too many macros running
+
+## Speed masters
+
+speed-title = Speed Masters
+speed-freeze = ❄ Freeze Effects
+speed-freeze-hint = Hold every pixel map and busk look where it is
+speed-default-name = Speed { $number }
+speed-empty = Add a master, then pick it for pixel maps and busk.
+
+## OSC monitor
+
+osc-monitor-traffic = Traffic
+osc-monitor-addresses = Addresses
+osc-monitor-input-off = OSC input is off; turn it on in Preferences to see incoming messages.
+osc-monitor-peer = From / to
+osc-monitor-arguments = Arguments
+osc-monitor-addresses-hint = Addresses bound in Trigger Bindings, sent by cues, or seen on the network, with the last arguments at each.
+osc-monitor-no-addresses = No OSC addresses yet.
+osc-monitor-bound = In: { $action }
+osc-monitor-sent = Out: sent to { $target } by cue { $number }
+osc-monitor-last = Last: [{ $args }] at { $time }
+
+## Fixture controls
+
+controls-lamp-on-caution = Lamps strike one after another, each drawing its strike current. A lamp that was just doused may not strike until it has cooled.
+controls-lamp-off-caution = The fixtures go dark at once, and a discharge lamp has to cool for several minutes before it will strike again.
+controls-reset-caution = The fixtures move and may go dark while they home, so it isn't one for the middle of a show.
+controls-menu = Control
+controls-menu-hint = Lamp on, lamp off and reset, as the profile sends them
+controls-title = Fixture Controls
+controls-sending = Sending...
+controls-stop-hint = Let go of the control channels now
+controls-stopped = Stopped the fixture controls
+controls-prompt-title = { $control }?
+controls-prompt = Send { $control } to { $fixtures }?
+controls-takes = Takes { $seconds }s.
+controls-sent = Sending { $control } to { $count ->
+        [one] { $count } fixture
+       *[other] { $count } fixtures
+    }
+
+## Crossfade
+
+crossfade-out = Out
+crossfade-in = In
+crossfade-cc-any = CC { $controller } any ch
+crossfade-cc = CC { $controller } ch { $channel }
+crossfade-no-midi = No MIDI
+crossfade-learn-hint = Assign the next MIDI controller moved
+crossfade-title = A/B Crossfade
+crossfade-top = top of show
+crossfade-cue = cue { $number }
+crossfade-from-to = From { $from } to cue { $number }
+crossfade-no-next = No next cue to crossfade to
+crossfade-hint = Take the Out fader down and the In fader up. When both reach the end, the next cue is in and the faders swap roles.
+crossfade-learned = Learned CC { $controller } ch { $channel } for crossfade fader { $fader }
+crossfade-done = Crossfaded to cue { $number }
+
+## Safe state
+
+safe-resume-hint = Leave the safe state and carry on where the show was
+safe-engaged = ● SAFE STATE
+safe-panic = PANIC
+safe-panic-hint = Fade to the safe state and stop playback (F12)
+safe-title = Safe State
+safe-description = The look the Panic button fades to, such as house lights up with effects and haze off. Anything left at zero goes dark.
+safe-capture = Capture Output
+safe-capture-hint = Use the levels on air now
+safe-panic-fading = Panic: fading to the safe state over { $seconds }s
+safe-resumed = Resumed from the safe state
+
+## sACN output
+
+sacn-priority-hint = Receivers take each universe from the highest priority sending it
+sacn-title = sACN Output
+sacn-enabled = Send the output over sACN
+sacn-source-name = Source name:
+sacn-priority = Priority:
+sacn-universes = Universes
+sacn-universes-hint = Universes not listed go out at the priority above. Patched addresses only sends per-address priority, claiming just the addresses fixtures are patched at and leaving the rest to other sources.
+sacn-per-address = Patched addresses only
+sacn-add-universe = Add Universe
+
+## Learn timing
+
+learn-title = Learn Timing
+learn-description = Play the show and press GO where each cue should fire. The playhead position at each GO becomes the cue's start time. Cues are learned in cue list order from the playhead on.
+learn-start = Start Learning
+learn-progress = { $learned } of { $count ->
+        [one] { $count } cue learned
+       *[other] { $count } cues learned
+    }
+learn-next = Next: cue { $number } { $name }
+learn-not-rolling = Start playback to learn in time.
+learn-undo = Undo GO
+learn-done = Done
+learn-done-hint = Keep the times learned so far
+learn-cancel-hint = Put every cue back to its old time
+learn-no-cues = Learn timing: no armed cues after the playhead
+learn-started = Learning timing for { $count ->
+        [one] { $count } cue
+       *[other] { $count } cues
+    }: press GO (G) as each should fire
+learn-cue-gone = Learn timing: cue { $number } no longer exists
+learn-learned-cue = Learned cue { $number } at { $time }
+learn-finished = Learned timing for { $count ->
+        [one] { $count } cue
+       *[other] { $count } cues
+    }
+learn-cancelled = Learn timing cancelled; cue times restored
+
+## Variables
+
+variables-title = Variables
+variables-description = Cues can be set to play only when a variable has a value. Set them here or over OSC at /halo/var/<name>.
+variables-still-used = { $count ->
+        [one] { $count } cue still tests
+       *[other] { $count } cues still test
+    } { $name }; they won't play until it is set again
+variables-osc-no-value = OSC { $address }: no value to set
+variables-set = Variable { $name } = { $value }
+
+## Quick console
+
+quick-encoder-hint = Drag across or scroll to move the selected channels; Page Up and Page Down do the same from the keyboard
+quick-move = Move
+quick-move-hint = Move every level by the same amount
+quick-scale = Scale
+quick-scale-hint = Scale the levels, keeping their proportions
+quick-release-all = Release All
+quick-hint = Click a channel number to select it. Right-click a fader to release it. Grey levels come from the cues.
+
+## Tags
+
+tag-icon-pyro = Pyro
+tag-icon-video = Video
+tag-icon-sound = Sound
+tag-icon-haze = Haze
+tag-icon-strobe = Strobe
+tag-icon-warning = Warning
+tag-icon-key-moment = Key moment
+tag-icon-talent = Talent
+tag-colour = { $colour } tag
+tag-colour-chosen = { $colour } tag, chosen
+
+## Notifications
+
+toasts-dismiss-all = Dismiss all
+toasts-empty = Nothing yet.
+
+## Watchdog
+
+watchdog-restarted = ⚠ OUTPUT ENGINE RESTARTED — check the rig; details are in the log
+watchdog-restarted-times = ⚠ OUTPUT ENGINE RESTARTED ({ $count } times this session) — check the rig; details are in the log
+watchdog-dismiss = Dismiss
+
+## Quit
+
+quit-fading = Fading out over { $seconds }s before quitting
+quit-output-stopped = Output stopped for quit
+
+## Visualizer
+
+visualizer-empty = Nothing is patched.
+
+## Patch import
+
+import-patch-none-found = no patched fixtures found
+import-patch-not-mvr = not an MVR file: no { $entry }
+import-patch-no-address = no address column found in the CSV header
+
+## Engine
+
+engine-recording-failed = Recording: { $error }
+engine-playback-failed = Playback: { $error }
+engine-end-of-cues = End of the cue list reached
+engine-hazers-off = Hazers off at the end of the show
+engine-held = Held at the end of the song
+
+## CITP
+
+citp-failed = CITP couldn't start: { $error }
+
+## RDM
+
+rdm-not-listed = { $uid } is no longer listed
+rdm-discovering = Discovering universe { $universe }...
+rdm-reading = Reading device { $number } of { $count }...
+rdm-addressing = Addressing { $uid }...
+rdm-no-answer = { $uid } did not answer
+rdm-refused-reason = { $uid } refused (reason { $reason })
+rdm-refused = { $uid } refused the request
+
+## Updates
+
+update-curl-failed = could not run curl: { $error }
+update-no-response = no response from GitHub
+update-no-version = GitHub sent no release version
+update-available = Halo { $version } is available; see Halo → About
+
+## Audio playback
+
+playback-device-missing = Audio output “{ $device }” not found
+playback-sample-format = Unsupported sample format { $format }
+playback-load-failed = Couldn't load { $path }: { $error }
+
+## MIDI
+
+midi-input-missing = MIDI input “{ $port }” not found
+midi-output-missing = MIDI output “{ $port }” not found
+
+## Hotplug
+
+hotplug-input-disconnected = MIDI input “{ $port }” disconnected
+hotplug-input-unplugged = MIDI input { $port } unplugged
+hotplug-input-reconnected = MIDI input { $port } reconnected
+hotplug-output-disconnected = MIDI output “{ $port }” disconnected
+hotplug-output-unplugged = MIDI output { $port } unplugged
+hotplug-output-reconnected = MIDI output { $port } reconnected
+
+## Clock sync
+
+clock-main-silent = The main stopped answering
+clock-show-fetch-failed = Couldn't fetch the main's show: { $error }
+clock-listen-failed = Clock sync couldn't listen on port { $port }: { $error }
+clock-handed-back = Output handed back to the main
+clock-taken-over = Lost the main at { $main }; this backup has taken over the output
+clock-following = Following the main at { $main }
+
+## Network interfaces
+
+interfaces-down-suffix = {" "}— down
+interfaces-down = { $protocol } interface { $name } is down
+interfaces-up = { $protocol } interface { $name } is back up
+
+## Show
+
+patch-copy-name = { $name } copy
+
+## Assert
+
+assert-quick-levels = quick levels
+assert-busk = busk
+assert-pads = pads
+assert-done = Asserted the cues
+assert-done-over = Asserted the cues over { $dropped }
+
+## Startup
+
+startup-opened = Opened { $path } at startup
+startup-midi-input = Using MIDI input { $port } at startup
+startup-midi-output = Using MIDI output { $port } at startup
+startup-playing = Started playing at startup from { $time }
+
+## Show comparison
+
+diff-loose-cues = Cues outside songs
+diff-missing-song = Missing song
+
+## End actions
+
+end-stopped = Stopped at the end of the cue list
+end-looped = Looped at the end of the cue list
+end-chain-missing = The song to chain into is gone
+end-chained = Chained into { $song }
+
+## DMX faders
+
+masters-no-fader-universe = Add a fader wing universe under Preferences → Network Input to learn DMX faders
+masters-the-grand-master = the grand master
+masters-learned-dmx = Learned DMX { $fader } for { $name }
+
+## Setlist
+
+setlist-no-song = Setlist: no song { $number }
+setlist-label = Song { $number }/{ $count }: { $name }
+
+## Beat cues
+
+beat-cue-fired = Bar { $position }: cue { $number }
+
+## Ripple
+
+ripple-nudged = Nudged cue { $number }{ $count ->
+        [0] {""}
+        [one] {" "}and { $count } later cue
+       *[other] {" "}and { $count } later cues
+    } by { $seconds }s
+
+## Back
+
+back-no-previous = Back: no previous cue
+back-to-cue = Back to cue { $number }
+
+## Session
+
+session-fired-off-time = Cue { $number } fired { $late }
+
+## Audio
+
+audio-input-missing = Audio input “{ $device }” not found
+
+## Shared
+
+cancel = Cancel
+exported-to = Exported to { $path }
+export-failed = Export failed: { $error }
+export-to = Export to:
+universe-label = Universe:
+unit-hz = {" "}Hz
+add = Add
+ip-address = IP address
+remove = Remove
+universe = Universe
+listen-failed = Could not listen on port { $port }: { $error }
+any = Any
+learn = Learn
+clear = Clear
+nothing-received = Nothing received yet.
+unit-beats = {" "}beats
+speed-master-label = Speed master:
+search-label = Search:
+cue-name = Cue { $number } { $name }
+unit-seconds = {" "}s
+name-label = Name:
+none = None
+notes-label = Notes:
+fade = Fade
+delay = Delay
+fixture-count = { $count ->
+        [0] No fixtures
+        [one] 1 fixture
+       *[other] { $count } fixtures
+    }
+cue-number = Cue { $number }
+port-label = Port:
+input-label = Input:
+output-label = Output:
+all = All
+level = Level
+fade-label = Fade:
+listening-on-port = Listening on port { $port }
+select = Select...
+fixture = Fixture
+address-label = Address:
+disconnect = Disconnect
+connect = Connect
+delete = Delete
+enabled = Enabled
+speed-label = Speed:
+load = Load
+restart = Restart
+loop = Loop
+file-label = File:
+path-label = Path:
+save = Save
+unit-db = {" "}dB
+gain-trim = Gain trim
+name = Name
+length = Length
+move-up = Move up
+move-down = Move down
+reverse = Reverse
+fixtures = Fixtures
+attribute-label = Attribute:
+apply = Apply
+unit-bars = {" "}bars
+label-label = Label:
+width-label = Width:
+tag = Tag
+close = Close
+channel-label = Channel:
+level-label = Level:
+attribute = Attribute
+address = Address
+type = Type
+unit-ms = {" "}ms
+unit-metres = {" "}m
+standing-by = Standing by while the main has the output.
+no = No
+yes = Yes
+try-again = Try Again
+unit-bpm = {" "}BPM
+import-from = Import from:
+import = Import
+unit-fps = { $fps } fps
+unit-watts = {" "}W
+unit-minutes = {" "}min
+fixtures-label = Fixtures:
+learned-cc = Learned CC { $controller } ch { $channel } for { $name }
+audio-unsupported-format = Unsupported sample format { $format }
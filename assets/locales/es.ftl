@@ -0,0 +1,2068 @@
+# Spanish.
+
+## Menu bar
+
+menu-halo = Halo
+menu-about = Acerca de
+menu-load-show = Abrir show
+menu-save-show = Guardar show
+menu-save-show-as = Guardar show como...
+menu-save-package = Guardar show como paquete...
+menu-merge = Combinar desde otro show...
+menu-compare-shows = Comparar shows...
+menu-revert = Volver a una copia de seguridad...
+menu-preferences = Preferencias...
+menu-properties = Propiedades del show...
+menu-handoff = Exportar traspaso al operador...
+menu-report = Exportar informe del show...
+menu-session = Estadísticas de la sesión...
+menu-quit = Salir
+
+menu-cues = Cues
+menu-insert-point-cue = Insertar cue de punto
+menu-duplicate-at-playhead = Duplicar en el cabezal
+menu-copy-cue = Copiar cue
+menu-copy-section = Copiar sección
+menu-copy-section-hint = Copiar todas las cues de la sección de la cue seleccionada
+menu-paste-cues = Pegar cues...
+menu-edit-cue = Editar la cue seleccionada...
+menu-edit-script = Editar el script de la cue...
+menu-fan = Abanico...
+menu-renumber = Renumerar...
+menu-fixture-replace = Reemplazar foco...
+menu-songs = Canciones...
+menu-crossfade = Fundido A/B...
+menu-variables = Variables...
+menu-spectrogram = Espectrograma...
+menu-learn-timing = Aprender tiempos...
+menu-tracksheet = Hoja de seguimiento...
+menu-follow-spots = Cañones de seguimiento...
+menu-daw = Marcadores del DAW...
+menu-schedule = Programación...
+
+menu-view = Ver
+menu-performance-mode = Modo actuación...
+menu-busk = Busking...
+menu-designer-view = Vista de diseñador...
+menu-visualizer = Visualizador
+menu-quick-console = Consola rápida
+menu-command-line = Línea de comandos
+menu-command-palette = Paleta de comandos
+menu-shortcuts = Atajos de teclado
+menu-dock-cue-list = Acoplar la lista de cues
+menu-detach-cue-list = Separar la lista de cues
+menu-macros = Macros
+menu-bindings = Asignaciones de disparo...
+menu-midi-monitor = Monitor MIDI
+menu-osc-monitor = Monitor OSC
+menu-packet-inspector = Inspector de paquetes
+menu-log = Consola de registro
+menu-frame-timings = Tiempos de fotograma
+menu-stage-display = Pantalla de escenario
+menu-clocks = Relojes
+
+menu-output = Salida
+menu-output-monitor = Monitor de salida
+menu-art-net = Salida Art-Net...
+menu-sacn = Salida sACN...
+menu-pixel-maps = Mapas de píxeles...
+menu-effect-presets = Presets de efectos...
+menu-smart-bulbs = Bombillas inteligentes...
+menu-plugins = Plugins...
+menu-speed-masters = Masters de velocidad...
+menu-group-masters = Masters de grupo...
+menu-haze = Humo...
+menu-snapshots = Instantáneas...
+menu-safe-state = Estado seguro...
+menu-recording = Grabación de salida
+menu-power = Consumo eléctrico
+
+## Header
+
+header-bpm = BPM:
+header-freeze = ❄ Congelar FX
+header-freeze-hint = Mantener cada mapa de píxeles y look de busking donde está
+header-record-look = ⏺ Grabar look
+header-record-look-hint = Grabar todo lo que hay en escena como una cue nueva en el cabezal
+header-leave-performance = 🔒 Salir del modo actuación
+header-patch = Patch
+
+## Transport
+
+transport-show-timecode = Mostrar timecode
+transport-show-system-time = Mostrar hora del sistema
+transport-show-clock = Mostrar { $clock }
+transport-start = Iniciar
+transport-stop = Parar
+transport-pause = Pausa
+transport-resume = Reanudar
+transport-pause-hint = Congelar donde está, manteniendo la salida
+transport-reset = Reiniciar
+transport-go = GO
+transport-go-hint = Saltar a la siguiente cue
+transport-back = Atrás
+transport-back-hint = Volver a la cue anterior a la actual, con un fundido de { $seconds } s
+transport-go-to-cue = Ir a la cue
+transport-assert = Afirmar
+transport-assert-hint = Quitar niveles rápidos, busking y pads para volver al look de las cues
+transport-timecode = Timecode...
+transport-cue-list-detached = La lista de cues está en su propia ventana.
+transport-override-pads = Pads de anulación
+transport-stopped = Parado
+transport-pre-roll = Pre-roll
+transport-running = En marcha
+transport-paused = En pausa
+transport-chasing = Persiguiendo
+transport-chasing-parked = Persiguiendo (detenido)
+transport-resume-first = Sal del estado seguro antes de iniciar la reproducción
+
+## Performance mode
+
+performance-enter-title = ¿Entrar en modo actuación?
+performance-enter-body = La edición queda bloqueada y Reiniciar desactivado hasta que salgas del modo actuación.
+performance-leave-title = ¿Salir del modo actuación?
+performance-leave-body = La edición se desbloqueará.
+performance-passcode = Código:
+performance-wrong-passcode = Código incorrecto
+performance-enter = Entrar
+performance-leave = Salir
+performance-next = Siguiente: { $number }  { $name }  en { $time }
+performance-last-cue = Última cue
+performance-marker = ◆ { $text }  en { $time }
+performance-entered = Modo actuación activado
+performance-left = Modo actuación desactivado
+performance-wrong-passcode-log = Código incorrecto al intentar salir del modo actuación
+
+## Quitting
+
+quit-title = Salir de Halo
+quit-save-changes = ¿Guardar los cambios en «{ $show }» antes de salir?
+quit-changes-lost = Si no los guardas, perderás los cambios.
+quit-save = Guardar
+quit-save-disabled = Sal del modo actuación para elegir un archivo
+quit-dont-save = No guardar
+
+## Preferences
+
+preferences-title = Preferencias
+preferences-appearance = Apariencia
+preferences-language = Idioma:
+preferences-theme = Tema:
+preferences-led-colour = Color de los LED:
+preferences-accent-downbeat = Iluminar más el primer tiempo de cada compás
+preferences-ui-scale = Escala de la interfaz:
+preferences-touch = Pantalla táctil:
+preferences-touch-mode = Controles grandes, pulsación larga para las ayudas
+preferences-remote-control = Control remoto
+preferences-midi = MIDI
+preferences-audio = Audio
+preferences-network-interfaces = Interfaces de red
+preferences-network-input = Entrada de red
+preferences-main-backup = Principal / Respaldo
+preferences-output = Salida
+preferences-backups = Copias de seguridad
+preferences-startup = Inicio
+preferences-display = Pantalla
+preferences-updates = Actualizaciones
+preferences-remote-tcp = Aceptar conexiones TCP de control remoto
+preferences-http = Servir endpoints HTTP para Companion / Stream Deck
+preferences-osc-input = Recibir OSC para las asignaciones de disparo
+preferences-citp = Compartir el patch y la selección con visualizadores (CITP)
+preferences-citp-waiting = Esperando un visualizador
+preferences-citp-connected = Conectado: { $peers }
+preferences-psn = Recibir trackers PSN para los cañones de seguimiento
+preferences-psn-listening = Escuchando; { $count ->
+        [one] 1 tracker enviando
+       *[other] { $count } trackers enviando
+    }
+preferences-designer-views = Vistas de diseño:
+preferences-midi-clock-in = Seguir el tempo del reloj MIDI
+preferences-midi-timecode-in = Perseguir el código de tiempo MIDI
+preferences-midi-mmc-in = Seguir MIDI Machine Control
+preferences-mmc-device = Dispositivo MMC:
+preferences-mmc-device-hint = El ID de dispositivo al que se responde y envía MMC; Todos acepta comandos para cualquier dispositivo
+preferences-song-select = Selección de canción:
+preferences-song-channel = Cambios de programa en el canal
+preferences-midi-send-clock = Enviar reloj MIDI
+preferences-midi-mmc-out = Enviar MIDI Machine Control
+preferences-song-select-hint = En modo setlist, el cambio de programa n en la entrada selecciona la canción n + 1 salvo que lo use una asignación de disparo.
+preferences-click-output = Salida de claqueta:
+preferences-click-hint = Un metrónomo en cada tiempo, más agudo en el primer tiempo del compás cuando este destella más.
+preferences-audio-outputs = Salidas de audio del show:
+preferences-audio-outputs-hint = Reproduce el archivo de audio del show a tiempo con el show. Da a cada salida los canales del archivo que reproduce, como la música en 1-2 para sala y la claqueta y la guía en 3-4 para la banda.
+preferences-interface-down = ⚠ { $name } está caída, así que { $protocol } usa todas las interfaces
+preferences-interfaces-hint = Art-Net y sACN cubren la entrada y la salida, y OSC el receptor de disparos. Déjalas en Cualquiera si solo hay una red.
+preferences-receive = Recibir:
+preferences-universes = Universos:
+preferences-fader-wings = Alas de faders:
+preferences-fader-wings-hint = Universos leídos para los masters, sin mezclar
+preferences-merge = Mezcla:
+preferences-halo-priority = Halo en
+preferences-listening-nothing = Escuchando; aún no se ha recibido nada.
+preferences-source = Universo { $universe }: { $protocol } de { $name } con prioridad { $priority }
+preferences-source-per-address = Universo { $universe }: { $protocol } de { $name } con prioridad { $priority }, por dirección
+preferences-clock = Reloj:
+preferences-main = Principal:
+preferences-tracking-backup = Seguir el show del principal y tomar el control si se pierde
+preferences-stop-output = Al parar la reproducción:
+preferences-stop-output-hint = También se aplica al cargar un show y al salir de Halo. Iniciar la reproducción o mover el cabezal recupera el show.
+preferences-look-pads = Dar a cada look grabado un pad que lo dispare
+preferences-keep-last = Conservar los últimos
+preferences-saves = guardados
+preferences-backups-hint = Las copias de seguridad están en una carpeta oculta junto al archivo del show.
+preferences-check-updates = Buscar una nueva versión al iniciar
+preferences-check-updates-hint = Desactívalo en recintos sin conexión a internet.
+preferences-open-last = Abrir el último show
+preferences-enable-output = Activar las salidas Art-Net, sACN y de plugins
+preferences-connect-midi = Usar los primeros puertos MIDI encontrados si no se ha elegido ninguno
+preferences-start-playback = Empezar a reproducir desde
+preferences-start-after = tras
+preferences-start-delay-hint = Tiempo para que la red y los dispositivos arranquen
+preferences-startup-hint = Para instalaciones que deben volver solas tras un corte de luz. Configura el ordenador para que abra Halo al iniciar sesión.
+preferences-usb-buttons = Botones USB:
+preferences-debounce = Antirrebote:
+preferences-debounce-hint = Las pulsaciones de un botón más cercanas que esto cuentan como una
+preferences-usb-bind-hint = Asigna sus teclas a GO y Atrás en Ver → Asignaciones de disparo
+preferences-clock-off-hint = Comparte el reloj con un Halo de respaldo, o sigue a uno principal.
+preferences-no-backup = Ningún respaldo siguiendo
+preferences-backup-following = Respaldo { $backup } siguiendo
+preferences-clock-estimate = Desfase { $offset } ms, ida y vuelta { $round_trip } ms, { $corrections } correcciones
+preferences-waiting-main = Esperando al principal
+preferences-main-drives = El reloj del principal controla el transporte de este Halo.
+preferences-output-held = Salida retenida mientras el principal lleva el show.
+preferences-take-over = Tomar el control ahora
+preferences-take-over-hint = Enviar la salida de este Halo en lugar de la del principal
+preferences-taken-over = Este respaldo ha tomado el control de la salida
+preferences-sending = Este respaldo está enviando la salida
+preferences-hand-back = Devolver al principal
+preferences-hand-back-hint = Retener la salida y volver a seguir al principal
+preferences-add-universe = Añadir un universo
+preferences-channels = Canales
+preferences-channels-to = a
+preferences-add-output = Añadir salida
+preferences-no-audio-outputs = No se han encontrado salidas de audio
+preferences-interface-missing = { $name } — no está
+
+## Keeping the display on
+
+awake-touch-to-wake = Toca cualquier cosa para despertar
+awake-keep-on = Mantener la pantalla encendida mientras haya un show cargado y la salida activa
+awake-holding = Manteniendo la pantalla encendida.
+awake-dim-after = Atenuar a un reloj tras
+awake-minutes = {" "}min
+awake-without-input = sin actividad
+awake-dim-hint = Atenuar solo oscurece la ventana; la salida continúa. El toque o la tecla que la despierta no hace nada más, salvo F12 pánico.
+awake-letting-sleep = Se vuelve a dejar que la pantalla se apague
+awake-keeping = Manteniendo la pantalla encendida mientras dura el show
+awake-failed = No se pudo mantener la pantalla encendida: { $error }
+
+## Replacing a fixture
+
+replace-title = Reemplazar foco
+replace-find = Buscar:
+replace-with = Reemplazar por:
+replace-pick = Elige un foco para ver dónde lo usa el show.
+replace-unused = Nada en el show usa este foco.
+replace-button = Reemplazar { $count }
+replace-button-hint = Pasar los sitios marcados; se descartan los niveles para los que el foco nuevo no tiene canal
+replace-undo = Deshacer
+replace-undo-hint = Restaurar lo que cambió el último reemplazo
+replace-done = { $from } reemplazado por { $to } en { $count } sitios
+replace-dropped = ; { $count } niveles no tenían canal en { $to }
+replace-undone = { $from } recupera { $count } sitios de { $to }
+replace-kept = { $count } sitios se editaron después del reemplazo y se han dejado como están
+replace-cue = Cue { $number } { $name }: { $uses }
+replace-levels = { $count } niveles
+replace-part = parte { $parts }
+replace-pad = Pad { $pad }: flash
+replace-haze = Programa de humo
+replace-follow-spot = Cañón de seguimiento { $name }
+
+## About
+
+about-title = Acerca de Halo
+about-tagline = Control de iluminación por timecode para espectáculos en directo.
+about-version = Versión:
+about-build = Compilación:
+about-build-for = { $profile } para { $os }-{ $arch }
+about-debug = depuración
+about-release = versión final
+about-check-updates = Buscar actualizaciones
+about-available = Halo { $version } está disponible:
+about-download = Descargar
+about-up-to-date = Halo está actualizado.
+about-check-failed = No se pudo comprobar: { $error }
+
+## Backups
+
+backups-title = Volver a una copia de seguridad
+backups-unsaved = Guarda el show para empezar a hacer copias de seguridad.
+backups-off = Las copias de seguridad están desactivadas en Preferencias.
+backups-none = Aún no hay copias; se guarda una cada vez que se guarda el show.
+backups-revert-hint = Volver a una copia reemplaza el show abierto. Guarda después para conservarlo.
+backups-latest = { $saved } (la última)
+backups-revert = Volver
+backups-reverted = Se ha vuelto a la copia de { $saved }
+
+## Errors
+
+error-opened = Abierto { $path }
+error-not-a-show = { $path } no se pudo abrir como show.
+error-show-still-open = El show que estaba abierto sigue abierto.
+error-device-failed = No se pudo abrir { $device } «{ $name }».
+error-device-hint = Halo continúa sin él. Comprueba que está conectado y que no lo usa otro programa y vuelve a intentarlo, o elige otro dispositivo ({ $device }).
+error-more = { $count } más después de este
+error-retry = Reintentar
+error-dismiss = Descartar
+device-audio-input = entrada de audio
+device-click = salida de claqueta
+device-playback = salida de audio
+device-midi-input = entrada MIDI
+device-midi-output = salida MIDI
+device-buttons = dispositivo de botones
+error-title-show = No se pudo abrir el show
+error-title-audio-input = No se pudo abrir la entrada de audio
+error-title-click = No se pudo abrir la salida de claqueta
+error-title-playback = No se pudo abrir la salida de audio
+error-title-midi-input = No se pudo abrir la entrada MIDI
+error-title-midi-output = No se pudo abrir la salida MIDI
+error-title-buttons = No se pudo abrir el dispositivo de botones
+error-another-file = Elegir otro archivo...
+error-another-device = Elegir otro dispositivo...
+error-open-show = No se pudo abrir { $path }: { $message }
+error-open-device = No se pudo abrir { $device } «{ $name }»: { $message }
+
+## Operator handoff
+
+handoff-title = Traspaso al operador
+handoff-show-name = Nombre del show:
+handoff-contacts = Contactos:
+handoff-contacts-hint = Regidor, producción, técnico de la sala
+handoff-notes = Notas de standby:
+handoff-export = Exportar HTML
+
+## Show report
+
+report-title = Informe del show
+report-description = Hoja de cues e informe de patch para el libro de producción.
+report-format = Formato:
+report-export = Exportar
+
+## Scripts
+
+script-cue-title = Script de la cue { $number }
+script-pad-title = Script del pad { $pad }
+script-cue-hint = Se ejecuta al disparar la cue. Llama a skip() para saltarla.
+script-pad-hint = Se ejecuta al encender el pad.
+script-test-run = Probar
+script-errors-logged = Los errores también se escriben en la consola de registro.
+script-pad = Pad { $label }
+script-failed = Script de { $name }: { $error }
+script-no-pad = Script de { $name }: no hay pad «{ $pad }»
+script-osc-failed = Script de { $name }: falló el OSC a { $target }: { $error }
+script-skipped = { $name } omitida por su script
+script-skip-pad = Script de { $name }: skip() solo se aplica a cues
+
+## Address map
+
+address-map-title = Mapa de direcciones
+address-map-named-selected = Mapa de direcciones, universo { $universe }: { $fixture } en { $address }, las flechas lo mueven
+address-map-named = Mapa de direcciones, universo { $universe }
+address-map-collides = {" "}(se solapa)
+address-map-moved = { $fixture } movido a { $address }
+
+## Art-Net output
+
+art-net-rate-hint = Bájala para focos o nodos que parpadean o se retrasan a la velocidad máxima
+art-net-title = Salida Art-Net
+art-net-enabled = Enviar la salida por Art-Net
+art-net-broadcast = Difundir a:
+art-net-broadcast-unrouted = Difundir los universos no enrutados a un nodo
+art-net-broadcast-rate = Frecuencia de difusión:
+art-net-changes-only = Enviar solo los cambios
+art-net-changes-only-hint = Omitir los universos que no han cambiado, enviando cada uno al menos cada { $seconds } s para mantener vivos los nodos
+art-net-nodes = Nodos
+art-net-stop-discovery = Detener la búsqueda
+art-net-discover = Buscar
+art-net-discovery-port = La búsqueda necesita el puerto de Art-Net, así que desactiva la entrada Art-Net y RDM mientras la usas.
+art-net-polling = Sondeando...
+art-net-add-hint = Enviar a este nodo, un universo por salida
+art-net-unicast = Destinos unicast
+art-net-add-universe = Añadir universo
+art-net-rate = Frecuencia
+art-net-port-address = → port-address
+art-net-add-target = Añadir destino
+
+## Trigger bindings
+
+input-note = nota { $note } canal { $channel }
+input-control = CC { $controller } canal { $channel }
+input-program = programa { $program } canal { $channel }
+input-usb-key = tecla USB { $key }
+source-midi-note = Nota MIDI
+source-midi-cc = CC MIDI
+source-midi-program = Programa MIDI
+source-usb-button = Botón USB
+action-go-cue = Ir a la cue
+action-toggle-pad = Conmutar pad
+action-next-song = Canción siguiente
+action-previous-song = Canción anterior
+action-go-to-song = Ir a la canción
+action-pad-bank = Banco de pads
+action-run-macro = Ejecutar macro
+action-record-look = Grabar look
+binding-learned-event = Aprendido { $event }
+binding-learned = Aprendido
+binding-no-pad = Disparo: no hay pad «{ $pad }»
+binding-channel = Canal
+binding-note = Nota
+binding-program = Programa
+binding-key = Tecla
+bindings-title = Asignaciones de disparo
+bindings-description = Mensajes MIDI, OSC y HTTP entrantes asignados a acciones.
+bindings-osc-off = La entrada OSC está desactivada; actívala en Preferencias para usar asignaciones OSC.
+bindings-source = Origen
+bindings-action = Acción
+bindings-learn-hint = Usar el próximo disparo MIDI, OSC o HTTP que llegue
+bindings-conflict = ⚠ Conflicto
+bindings-conflict-hint = El mismo mensaje también dispara la asignación { $others }
+bindings-none = Aún no hay asignaciones.
+bindings-add = Añadir asignación
+bindings-setlist = Los cambios de programa asignados ya no seleccionan canciones del setlist.
+bindings-recent = Mensajes recientes
+bindings-not-bound = Sin asignar
+
+## Busk
+
+busk-title = Busking
+busk-pick-groups = Elige los grupos sobre los que hacer busking.
+busk-look = Look { $number }: { $template } (tiempo { $beat }/{ $beats })
+busk-description = Reproduce looks generados sobre las cues.
+busk-needs = Necesita al menos una plantilla y un color.
+busk-groups = Grupos:
+busk-palette = Paleta:
+busk-add-colour = Añadir un color
+busk-templates = Plantillas:
+busk-intensity = Intensidad:
+busk-variation = Variación:
+busk-variation-hint = Bajo recorre las plantillas y los colores en orden, alto los elige al azar
+busk-change-every = Cambiar cada:
+
+## Clocks
+
+clocks-description = El marcador grande pasa por estos además del timecode y la hora del día.
+clocks-zones = Zonas horarias
+clocks-daylight-hint = Ajústalo para el horario de verano
+clocks-add-zone = Añadir zona horaria
+clocks-zone = Zona
+clocks-countdowns = Cuentas atrás
+clocks-add-countdown = Añadir cuenta atrás
+clocks-doors = Puertas
+clocks-show = Show
+clocks-countdown = Cuenta atrás
+clocks-countdowns-hint = Las cuentas atrás van a la hora de este ordenador, dentro de doce horas.
+clocks-caption-in = { $name } en
+clocks-caption-since = { $name } desde
+
+## Command line
+
+command-line-enter = Intro
+command-group-type = Indica un tipo de foco después de group
+command-empty = Escribe un comando
+command-unsure = No sé qué hacer con { $word }
+command-unknown = «{ $word }» no es un comando
+command-level-missing = Indica un nivel después de @
+command-level-extra = Indica un solo nivel después de @
+command-thru-channel = Indica un canal después de thru
+command-no-channels = Eso no selecciona ningún canal
+command-not-channel = «{ $word }» no es un canal
+command-not-cue-number = «{ $word }» no es un número de cue
+command-not-number = «{ $word }» no es un número
+command-out-of-range = { $word } está fuera de rango; usa de { $start } a { $end }
+command-moved = { $count ->
+        [one] { $count } foco movido
+       *[other] { $count } focos movidos
+    }
+command-set = { $count ->
+        [one] { $count } foco ajustado
+       *[other] { $count } focos ajustados
+    }
+command-no-cue = No existe la cue { $number }
+command-cue-time = La cue { $number } funde en { $seconds } s
+command-released = Niveles rápidos liberados
+command-no-group = Ningún foco es de un tipo que empiece por «{ $name }»
+command-select-first = Selecciona primero algunos canales
+command-recorded = Cue { $number } grabada
+command-added = Cue { $number } añadida
+
+## Command palette
+
+palette-go = GO: cue siguiente
+palette-back = Atrás: cue anterior
+palette-resume = Salir del pánico
+palette-panic = Pánico: apagón al estado seguro
+palette-performance-mode = Modo actuación
+palette-pad-off = Apagar el pad { $pad }
+palette-pad-on = Encender el pad { $pad }
+palette-toggle-patch = Alternar la vista de patch
+palette-open = Abrir { $window }
+palette-hint = Escribe para buscar acciones, cues y ventanas
+palette-no-matches = No hay coincidencias
+palette-ran = Paleta: { $entry }
+shortcuts-close = Pulsa ? o Escape para cerrar
+
+## Keyboard shortcuts
+
+shortcut-palette = Paleta de comandos: buscar cualquier acción
+shortcut-help = Mostrar estos atajos
+shortcut-start-stop = Iniciar / parar la reproducción, o pulsar el control enfocado
+shortcut-pause = Pausar / reanudar la reproducción, manteniendo la salida
+shortcut-reset = Poner el timecode a cero (no en modo actuación)
+shortcut-readout = Pasar el marcador por el timecode, la hora del día y los relojes del show
+shortcut-patch = Alternar la vista de patch (no en modo actuación)
+shortcut-next-song = Canción siguiente (modo setlist)
+shortcut-panic = Pánico: fundido al estado seguro y parar (funciona mientras escribes)
+shortcut-back = Atrás: fundido a la cue anterior a la actual
+shortcut-performance = Entrar / salir del modo actuación
+shortcut-learn-go = GO: fijar el tiempo de la cue siguiente (mientras se aprenden tiempos)
+shortcut-quick-up = Subir un 5 % los canales seleccionados de la consola rápida (Mayús: 1 %)
+shortcut-quick-down = Bajar un 5 % los canales seleccionados de la consola rápida (Mayús: 1 %)
+shortcut-nudge-second-earlier = Adelantar la cue seleccionada 1 segundo (no en modo actuación)
+shortcut-nudge-second-later = Retrasar la cue seleccionada 1 segundo (no en modo actuación)
+shortcut-nudge-frame-earlier = Adelantar la cue seleccionada 1 fotograma (no en modo actuación)
+shortcut-nudge-frame-later = Retrasar la cue seleccionada 1 fotograma (no en modo actuación)
+shortcut-focus = Enfocar el control siguiente (Mayús+Tab el anterior, las flechas uno vecino)
+shortcut-unfocus = Soltar el control enfocado
+shortcut-pad-menu = Abrir el menú del pad enfocado (no en modo actuación)
+shortcut-fire = Disparar la cue enfocada
+
+## Cue list
+
+cues-search-hint = Nombre, número o notas
+cues-clear-search = Borrar la búsqueda
+cues-jump = Ir a la actual
+cues-add-marker = Añadir marcador
+cues-add-marker-hint = Añadir una nota a la línea de tiempo en el cabezal
+cues-ripple = Arrastre
+cues-ripple-hint = Mover una cue mueve todas las cues posteriores de su canción lo mismo. [ y ] mueven la cue seleccionada un segundo, la coma y el punto un fotograma.
+cues-no-match = Ninguna cue coincide con la búsqueda.
+cues-marker = Marcador
+cues-move-to-playhead = Mover al cabezal
+cues-armed = Armada
+cues-armed-hint = Armada: las cues desarmadas se saltan en la reproducción
+cues-only-when = Solo si { $condition }
+cues-fires-at-bar = Se dispara en el compás { $at }
+cues-double-click = Doble clic para disparar
+cues-row-playing = Cue { $number } { $name }, en curso
+cues-row-disarmed = Cue { $number } { $name }, desarmada
+cues-went-late = Salió { $late } a las { $at }
+cues-fade = Fundido: { $timing }
+cue-timing-after = {" "}tras { $seconds } s
+renumber-title = Renumerar cues
+renumber-start = Empezar en:
+renumber-interval = Intervalo:
+renumber-apply = Renumerar
+renumber-too-high = { $count } cues numeradas desde { $start } de { $interval } en { $interval } pasarían de { $max }, el número de cue más alto; no se renumeró nada
+cues-copied-one = Cue { $number } copiada
+cues-copied = { $count } cues copiadas
+cues-pasted = { $count ->
+        [one] { $count } cue pegada
+       *[other] { $count } cues pegadas
+    } como { $numbers }
+cues-no-room = No hay sitio para numerar { $count ->
+        [one] la cue
+       *[other] { $count } cues
+    } por debajo de { $max }, el número de cue más alto; no se añadió nada
+cues-duplicated = Cue { $number } duplicada como { $new } en { $at }
+paste-title = Pegar cues
+paste-nothing = Copia antes una cue o una sección.
+paste-cues-from = { $count } cues desde la { $number }
+paste-offset = Desplazamiento:
+paste-to-playhead = Al cabezal
+paste-to-playhead-hint = Empezar la primera cue pegada en el cabezal
+paste-starts-after = La primera cue empieza en { $at }, después de la cue { $number }.
+paste-starts-at-end = La primera cue empieza en { $at }, después de la última cue.
+paste-apply = Pegar
+
+## Session statistics
+
+session-early = { $seconds } s antes
+session-late = { $seconds } s tarde
+session-title = Estadísticas de la sesión
+session-open-for = Abierto durante:
+session-played-for = Reproducido durante:
+session-run-time = Duración de la función:
+session-not-started = sin empezar
+session-last-saved = Último guardado:
+session-ago = hace { $duration }
+session-not-saved = no en esta sesión
+session-cues-fired = Cues disparadas: { $count }
+session-late-count = { $count } tarde
+session-went-at = Salió a las
+session-cue = Cue
+session-programmed = Programado
+session-actual = Real
+session-drift = Deriva
+session-late-header = Retraso
+session-clear-hint = Empieza las estadísticas de nuevo para la próxima función
+session-report = Informe posterior a la función
+
+## Cue editor
+
+cue-editor-title = Cue { $number }
+cue-editor-armed-hint = Las cues desarmadas se saltan en la reproducción
+cue-editor-block = Bloqueo
+cue-editor-block-hint = Mantener cada valor que llega por tracking, para que las ediciones de cues anteriores se detengan aquí
+cue-editor-only-when = Solo si:
+cue-editor-fire-at = Disparar en:
+cue-editor-intensity-fade = Fundido de intensidad:
+cue-editor-attribute-fade = Fundido de atributos:
+cue-editor-attribute-fade-hint = Color, posición y todos los canales salvo la intensidad
+cue-editor-vary = Variar:
+cue-editor-run-macro = Ejecutar macro:
+cue-editor-song = Canción:
+cue-editor-section = Sección:
+cue-editor-section-hint = Empieza aquí un nuevo acto o sección
+cue-editor-tag = Etiqueta:
+cue-editor-notes-hint = Notas de standby para el operador
+cue-editor-vary-each = Cada vez que se dispara
+cue-editor-vary-hint = Mover los niveles una cantidad aleatoria en cada disparo, para que nunca se vea exactamente igual
+cue-editor-vary-by = Hasta ±
+cue-editor-vary-steps = pasos
+cue-editor-vary-tick = Marca los atributos que variar
+cue-editor-vary-per-fixture = Cada foco en su propia cantidad
+cue-editor-vary-seed = Igual en cada ejecución, semilla
+cue-editor-vary-seed-hint = Desmárcalo para variar de forma distinta cada vez que arranque Halo
+cue-editor-bar = Compás
+cue-editor-bar-hint = Disparar en un compás y tiempo de la línea musical en vez de en su hora
+cue-editor-beat = tiempo
+cue-editor-always = Siempre
+cue-editor-add-variables = Añade variables del show en Cues → Variables
+cue-editor-add-value = Añadir un valor a la cue
+cue-editor-values-count = { $count } valores
+cue-editor-values = Valores
+cue-editor-timing = Tiempos
+cue-editor-timing-hint = Todo hace el fundido en el tiempo de la cue; cambia un grupo para darle el suyo
+cue-editor-parts = Partes
+cue-editor-add-part = Añadir una parte con su propio fundido y retardo
+cue-editor-part = Parte { $number }
+cue-editor-no-levels = La cue aún no tiene niveles
+cue-editor-rest = { $count ->
+        [one] 1 foco más hace
+       *[other] { $count } focos más hacen
+    } el fundido en los { $seconds } s de la cue
+cue-editor-custom-curve = Personalizada
+cue-editor-triggers = Disparos
+cue-editor-add-trigger = Añadir un disparo
+cue-editor-test = Probar
+cue-editor-test-hint = Enviar este disparo ahora
+cue-editor-velocity = Vel
+cue-editor-osc-args = argumentos
+cue-editor-json-body = cuerpo JSON
+cue-editor-serial-hint = texto, \r \n \xFF
+cue-editor-serial-data-hint = Texto que enviar, con \r, \n, \t y \xNN para otros bytes
+cue-editor-relay = Relé
+cue-editor-relay-on = Encendido
+cue-editor-pin = Pin
+cue-editor-pin-high = Alto
+cue-editor-pulse = Pulso
+cue-editor-pulse-hint = Volver atrás tras este tiempo; 0 lo deja conmutado
+
+## Cue triggers
+
+trigger-on-start = Al empezar
+trigger-on-end = Al terminar
+trigger-serial = Serie
+trigger-relay = Relé USB
+trigger-osc-failed = { $name }: falló el OSC a { $target }: { $error }
+trigger-serial-output = { $name }: serie { $port }
+trigger-serial-data = { $name }: datos serie para { $port }: { $error }
+trigger-relay-output = { $name }: relé { $relay } en { $port }
+trigger-gpio-output = { $name }: GPIO { $pin }
+trigger-no-midi-output = { $name }: no hay salida MIDI configurada en Preferencias
+trigger-webhook-answered = { $name }: el webhook { $url } respondió { $status }
+trigger-webhook-failed = { $name }: falló el webhook { $url }: { $error }
+trigger-failed = { $what } falló: { $error }
+
+## Option names
+
+daw-format-reaper = CSV de marcadores de REAPER
+daw-format-pro-tools = Lista de marcadores de Pro Tools
+curve-linear = Lineal
+curve-s = Curva en S
+curve-custom = Personalizada
+busk-template-wash = Baño
+busk-template-split = Dividido
+busk-template-chase = Persecución
+busk-template-pulse = Pulso
+busk-template-sweep = Barrido
+clock-role-off = Desactivado
+clock-role-main = Principal
+clock-role-backup = Respaldo
+end-action-continue = Continuar
+end-action-stop = Parar
+end-action-loop = Bucle
+end-action-chain = Encadenar con canción
+audio-band-level = Nivel
+audio-band-bass = Graves
+audio-band-mid = Medios
+audio-band-high = Agudos
+direction-in = Entrada
+direction-out = Salida
+stop-output-hold = Mantener el último look
+stop-output-fade = Fundido a cero
+stop-output-safe-state = Saltar al estado seguro
+attribute-group-intensity = Intensidad
+attribute-group-colour = Color
+attribute-group-position = Posición
+attribute-group-beam = Haz
+control-lamp-on = Encender lámpara
+control-lamp-off = Apagar lámpara
+control-reset = Reiniciar
+flash-mode-add = Añadir
+flash-mode-solo = Solo
+pattern-full = Todo a full
+pattern-ramp = Rampa
+pattern-alternating = Alternado
+pattern-chase = Persecución
+colour-red = Rojo
+colour-orange = Naranja
+colour-yellow = Amarillo
+colour-green = Verde
+colour-cyan = Cian
+colour-blue = Azul
+colour-purple = Morado
+colour-pink = Rosa
+merge-mode-priority = Prioridad
+conflict-keep-both = Conservar ambos
+conflict-replace = Reemplazar
+conflict-skip = Omitir
+snap-seconds = Segundos
+snap-frames = Fotogramas
+snap-beats = Tiempos
+snap-bars = Compases
+snap-hits = Golpes
+edit-mode-track = Tracking
+edit-mode-cue-only = Solo cue
+theme-dark = Oscuro
+theme-light = Claro
+theme-high-contrast = Alto contraste
+colour-amber = Ámbar
+colour-white = Blanco
+fan-mode-linear = Lineal
+fan-mode-symmetrical = Simétrico
+fan-mode-random = Aleatorio
+fan-order-patch = Orden del patch
+fan-order-group = Orden del grupo
+curve-ease-in = Entrada suave
+curve-ease-out = Salida suave
+generator-gradient = Degradado
+generator-chase = Persecución
+generator-plasma = Plasma
+generator-beat-pulse = Pulso por tiempo
+generator-bar-pulse = Pulso por compás
+generator-media = Medios
+generator-plugin = Plugin
+modulation-brightness = Brillo
+modulation-size = Tamaño
+modulation-speed = Velocidad
+mmc-play = Reproducir
+mmc-locate = Localizar { $position }
+macro-fire-cue = Disparar cue { $number }
+macro-pad-on = { $pad } encendido
+macro-pad-off = { $pad } apagado
+macro-song = Canción { $number }
+record-source-output = La salida de Halo
+record-source-input = Entrada Art-Net / sACN
+bulb-hue-light = Luz Hue { $light }
+fan-target-hue = Tono
+fan-target-gradient = Degradado de color
+fan-target-white-point = Temperatura de color
+sequence-lost = { $count } perdidos
+sequence-out-of-order = fuera de orden
+sequence-terminated = flujo terminado
+schedule-sunrise = Amanecer
+schedule-after-sunrise = { $minutes } min después del amanecer
+schedule-before-sunrise = { $minutes } min antes del amanecer
+schedule-sunset = Atardecer
+schedule-after-sunset = { $minutes } min después del atardecer
+schedule-before-sunset = { $minutes } min antes del atardecer
+schedule-time-of-day = Hora del día
+pad-release-toggle = Conmutar
+pad-release-momentary = Momentáneo
+pad-release-seconds = Temporizado (segundos)
+pad-release-bars = Temporizado (compases)
+
+## Merging shows
+
+merge-songs = { $count ->
+        [one] 1 canción
+       *[other] { $count } canciones
+    }
+merge-cues = { $count ->
+        [one] 1 cue
+       *[other] { $count } cues
+    }
+merge-group-masters = { $count ->
+        [one] 1 master de grupo
+       *[other] { $count } masters de grupo
+    }
+merge-macros = { $count ->
+        [one] 1 macro
+       *[other] { $count } macros
+    }
+merge-colours = { $count ->
+        [one] 1 color de paleta
+       *[other] { $count } colores de paleta
+    }
+merge-nothing = nada
+merge-merged = fusionado: { $parts }
+merge-skipped = ; { $count } conflictos omitidos
+merge-dropped = ; { $count } valores descartados de focos que no están en este show
+merge-title = Fusionar desde un show
+merge-show-file = Archivo de show:
+merge-cue-lists = Listas de cues
+merge-song-cues = { $song } ({ $count } cues)
+merge-loose-cues = Cues fuera de cualquier canción ({ $count })
+merge-group-masters-header = Masters de grupo
+merge-palette = Paleta
+merge-busk-colours = Colores de busk ({ $count })
+merge-profiles = Perfiles de focos
+merge-conflict = Mismo nombre o número:
+merge-conflict-hint = Conservar ambos renombra canciones, masters, macros y perfiles, y numera las cues tras la última
+merge-fixtures-hint = Las cues encuentran sus focos por nombre; se omiten los valores de focos que no están aquí.
+merge-button = Fusionar
+merge-profile-failed = No se pudo añadir el perfil { $profile }: { $error }
+merge-profiles-added = ; se añadieron { $count } perfiles de focos
+merge-done = Desde { $path }: { $report }
+
+## Detached panels
+
+panel-cue-list = Lista de cues
+panel-detach = Separar en una ventana
+panel-window-title = Halo – { $panel }
+
+## Output engine
+
+engine-stall-details = { $since } ms desde su último fotograma, { $pending } comandos pendientes, cabezal en { $playhead }, { $transport }
+engine-exited = El motor de salida se cerró ({ $details })
+engine-stalled = El motor de salida se bloqueó ({ $details })
+engine-restarted = El motor de salida dejó de responder y se reinició
+engine-outputs-waiting = El motor bloqueado aún retiene las salidas, los drivers y cualquier grabación; el reiniciado los retomará cuando los libere
+engine-recording-lost = La grabación o reproducción de salida se detuvo con el motor
+engine-took-over = El motor reiniciado retomó las salidas
+
+## Patch
+
+patch-send-as = Enviar como
+patch-address = Dirección
+patch-channel = Canal
+patch-first-cc = Primer CC
+patch-osc-hint = Cada canal va a <dirección>/<nombre del canal> como un nivel de 0 a 1.
+patch-http-hint = {"{"}intensity{"}"}, {"{"}red{"}"} y los demás nombres de canal entre llaves se convierten en niveles de 0 a 255, y {"{"}hex{"}"} en el color como RRGGBB.
+patch-midi-hint = Cada canal toma el siguiente controlador, con niveles de 0 a 127.
+patch-driver-hint = La dirección guarda los niveles del foco en Halo; no se envían como DMX.
+patch-title = Editor de patch
+patch-import = Importar patch...
+patch-venue = Recinto...
+patch-type = Tipo
+patch-mib = MIB
+patch-mib-hint = Mover en negro: mientras está apagado, toma la posición y el color de la siguiente cue
+patch-off = Off
+patch-off-hint = Inhibir: mantiene el foco apagado y fuera del chequeo de canales, la programación y todo lo demás
+patch-move-in-black-named = Mover { $name } en negro
+patch-inhibit-named = Inhibir { $name }
+patch-clone = Clonar
+patch-clone-hint = Añadir otro foco como este con la misma programación
+patch-output = Salida
+patch-output-changed = Salida ●
+patch-output-hint = Invertir, límites, curvas de dimmer, orientación de pan/tilt y protocolo
+patch-cloned = Añadido { $name } en { $address }
+patch-clone-no-room = No queda sitio en el universo para el clon
+patch-move-in-black = Mover en negro
+patch-before-cue = antes de la cue
+patch-add-fixture = Añadir foco
+patch-browse = Explorar la biblioteca de focos
+patch-inhibited = Inhibidos ({ $count }):
+patch-restore-all = Restaurar todos
+patch-restored = Restaurados todos los focos inhibidos
+patch-processing = Procesado de salida: { $name }
+patch-curve = Curva
+patch-invert = Invertir
+patch-limits = Límites
+patch-to = a
+patch-levels = Niveles:
+patch-remove-point = Quitar un punto
+patch-add-point = Añadir un punto
+patch-custom-curve-hint = Las curvas personalizadas dan el nivel de salida en niveles de entrada espaciados por igual.
+patch-position = Posición
+patch-swap = Intercambiar pan/tilt
+patch-invert-pan = Invertir pan
+patch-invert-tilt = Invertir tilt
+patch-home-offset = Desfase de origen: pan
+patch-tilt = tilt
+patch-position-hint = Se aplica en la salida, así que el foco toma las mismas posiciones que el resto de su grupo. Invertir y los desfases actúan sobre los canales propios del foco, después de cualquier intercambio.
+patch-copy-values = Copiar valores
+patch-from = De:
+patch-to-label = A:
+patch-copy = Copiar
+patch-copy-hint = Reemplazar todo lo programado en el segundo foco por lo del primero
+patch-copied = Valores del foco copiados en { $count ->
+        [one] 1 cue
+       *[other] { $count } cues
+    }
+patch-replace-type = Reemplazar tipo de foco
+patch-with = por
+patch-replaced = Reemplazados { $count } × { $old } por { $new }; { $dropped } valores de cue no tenían equivalente
+patch-replaced-footprint = { $new } usa más canales que { $old }; revisa las direcciones
+patch-gateway = Pasarela:
+patch-discover = Descubrir
+patch-rdm-port = RDM necesita el puerto de Art-Net, así que desactiva la entrada Art-Net mientras lo usas.
+patch-device = Dispositivo
+patch-channels = Canales
+patch-patched-as = Patcheado como
+patch-set = Fijar
+patch-set-hint = Enviar la nueva dirección de inicio al foco
+patch-identify = Identificar
+patch-not-patched = No está en el patch
+
+## Status bar
+
+status-rates = UI: { $ui } fps  Salida: { $output } Hz
+status-frame-graph = Mostrar la gráfica de tiempos de fotograma
+status-dropping-frames = ⚠ Perdiendo fotogramas de salida ({ $count } en total)
+status-rec = ● GRAB
+status-rec-hint = Grabando la salida; haz clic para abrir Grabación de salida
+status-playback = ▶ REPRODUCCIÓN
+status-playback-hint = Una grabación tiene la salida, no el show; haz clic para abrir Grabación de salida
+status-power-hint = El consumo estimado supera el límite del circuito; haz clic para abrir Potencia
+status-venue = Recinto: { $name }
+status-venue-hint = La salida pasa por el mapeo de este recinto; haz clic para abrirlo
+status-capped = ⚠ Limitados: { $groups }
+status-capped-hint = Los masters de grupo están limitando estos grupos; haz clic para abrirlos
+status-test-pattern = patrón de prueba en { $universe }
+status-muted = silenciados { $universes }
+status-universes = ⚠ Universos: { $state }
+status-universes-hint = Algunos universos no están reproduciendo el show; haz clic para abrir el monitor de salida
+status-link-no-peers = Link: sin pares
+status-link-off = Link: desactivado
+status-notifications = Notificaciones
+status-network-any = Red: cualquiera
+status-network-any-hint = Todos los protocolos usan todas las interfaces
+status-network-up = Red: activa
+status-interface-down = La interfaz { $name } de { $protocol } está caída
+status-network-down = Red: caída
+status-clock-local = Reloj: local
+status-clock-local-hint = No se comparte el reloj con un principal o un respaldo
+status-clock-error = Reloj: error
+status-clock-main = Reloj: principal
+status-clock-taken-over = Reloj: control tomado
+status-clock-taken-over-hint = Este respaldo está enviando la salida en lugar del principal
+status-clock-lost = Reloj: perdido
+status-clock-waiting = Reloj: esperando
+status-clock-waiting-for = Esperando a { $main }
+status-clock-offset = Reloj: { $offset } ms
+status-clock-following = Siguiendo a { $main }; { $corrections } correcciones
+status-art-net-error = Art-Net: error
+status-art-net-standby = Art-Net: en espera
+status-art-net-standby-hint = Retenido mientras el principal envía la salida
+status-art-net-off = Art-Net: desactivado
+status-art-net-off-hint = La salida Art-Net está desactivada
+status-art-net-rate = { $address }: { $achieved } de { $target } Hz
+status-art-net-hz = Art-Net: { $rate } Hz
+status-dmx-in-error = Entrada DMX: error
+status-dmx-in-off = Entrada DMX: desactivada
+status-dmx-in-off-hint = Las entradas Art-Net y sACN están desactivadas
+status-dmx-in-nothing = Escuchando; no se ha recibido nada
+status-dmx-in-source = Universo { $universe }: { $protocol } de { $name }, { $rate } paquetes/s
+status-dmx-in = Entrada DMX: { $rates }
+status-midi-off = MIDI: desactivado
+status-midi-off-hint = No se ha elegido ningún puerto MIDI
+status-midi-connected-port = { $direction }: { $name } (conectado)
+status-midi-disconnected-port = { $direction }: { $name } (desconectado)
+status-midi-no-port = { $direction }: ninguno
+status-midi-connected = MIDI: conectado
+status-midi-disconnected = MIDI: desconectado
+status-audio-click-error = Audio: error de claqueta
+status-audio-off = Audio: desactivado
+status-audio-off-hint = No se ha elegido ninguna entrada de audio
+status-audio-error = Audio: error
+status-audio-on = Audio: activado
+status-audio-stopped = Audio: parado
+status-server-off = { $name }: desactivado
+status-server-listening = { $name }: escuchando en el puerto { $port }
+status-remote-off = Remoto: desactivado
+status-remote-error = Remoto: error
+status-remote-on = Remoto: activado
+
+## Pixel maps
+
+pixel-maps-title = Mapas de píxeles
+pixel-map-name = Mapa de píxeles { $number }
+pixel-presets = Presets...
+pixel-size = Tamaño:
+pixel-serpentine = Serpentina
+pixel-send-as = Enviar como:
+pixel-controller-address = dirección del controlador
+pixel-patch = Patch:
+pixel-content = Contenido:
+pixel-plugin = Plugin:
+pixel-no-plugins = No hay plugins generadores cargados
+pixel-colours = Colores:
+pixel-brightness = Brillo:
+pixel-audio = Audio:
+pixel-react-to = Reaccionar a
+pixel-react-on = en
+pixel-depth = Profundidad:
+pixel-media-file = Archivo de medios:
+pixel-playback = Reproducción:
+pixel-load-failed = No se pudieron cargar los medios: { $error }
+pixel-map-error = Mapa de píxeles { $name }: { $error }
+pixel-rate = Velocidad de reproducción:
+pixel-resized = El mapa cambió de tamaño; vuelve a cargar los medios.
+pixel-clip = { $frames } fotogramas, { $seconds } s
+pixel-media-hint = Los GIF se cargan directamente; el vídeo necesita ffmpeg en el PATH.
+pixel-dmx-summary = { $count } píxeles, de { $first } a { $last } ({ $per_universe } por universo)
+pixel-stream-summary = { $count } píxeles por { $output } a { $host }
+pixel-choose-audio = Elige una entrada de audio en Preferencias.
+pixel-media-failed = Mapa de píxeles { $name }: no se pudo cargar el medio: { $error }
+
+## Output recording
+
+recording-started = Grabando { $source } en { $path }
+recording-stopped = Grabación detenida en { $path }
+recording-imported = { $count ->
+        [one] Importada 1 cue
+       *[other] Importadas { $count } cues
+    } de { $path }
+recording-backwards = Un fotograma de la grabación retrocede en el tiempo, hasta { $at } ms, así que no se puede analizar
+recording-cue-name = Importada { $number }
+recording-playing = Reproduciendo { $path }
+recording-playback-stopped = Se detuvo la reproducción de la grabación de salida
+recording-record = Grabar
+recording-source = Grabar:
+recording-input-off = Activa la entrada de red en las preferencias para recibir algo.
+recording-stop = ⏹ Detener grabación
+recording-length = ● Grabando { $length }
+recording-start = ⏺ Grabar
+recording-start-hint = Grabar cada fotograma enviado, hasta que se detenga
+recording-play-back = Reproducir
+recording-stop-playback = ⏹ Detener
+recording-play = ▶ Reproducir
+recording-play-hint = Enviar la grabación en lugar del show
+recording-playing-hint = La grabación tiene la salida hasta que se detenga. El pánico sigue dejando a negro.
+recording-import = Importar como cues
+recording-hold = El look se mantiene durante:
+recording-hold-hint = Las esperas más cortas son pasos de un fundido o un efecto, no cues
+recording-tolerance = Ignorar cambios de hasta:
+recording-tolerance-hint = Los niveles que varían así de poco son ruido
+recording-start-at = Empezar en:
+recording-start-at-hint = Dónde va la primera cue en el show
+recording-changes-only = Guardar solo lo que cambió respecto a la cue anterior
+recording-analyze = Analizar
+recording-analyze-hint = Buscar los looks de la grabación, con el patch de este show
+recording-look = Look
+recording-starts = Empieza
+recording-add-cues = Añadir { $count ->
+        [one] 1 cue
+       *[other] { $count } cues
+    }
+recording-add-cues-hint = Añadirlas tras la última cue
+
+## Show file
+
+show-backup-failed = No se pudo hacer una copia de seguridad del show: { $error }
+show-saved = Guardado { $path }
+show-saved-copy = Se guardó una copia en el formato de show { $version } en { $path }
+show-newer-warning = { $path } se guardó con un Halo más nuevo, en el formato de show { $version }
+show-load-title = Cargar show
+show-save-as-title = Guardar show como
+show-save-package-title = Guardar show como paquete
+show-format-label = Formato:
+show-format-this = { $version } (este Halo)
+show-format-older = { $version } (Halo anterior)
+show-format-older-hint = Guarda una copia para un Halo anterior y deja el archivo de este show como está. Se omite lo que el formato anterior no puede guardar.
+show-package-hint = Reúne el show, su audio y los perfiles de focos propios en un único archivo .{ $extension }.
+show-newer-title = Show de un Halo más nuevo
+show-newer-body = Este show se guardó en el formato de show { $version }, con un Halo más nuevo. Este lee hasta el formato { $current }.
+show-newer-advice = Se omitió lo que no conoce, y guardar encima del archivo del show lo perdería para siempre. Guarda una copia para seguir trabajando aquí.
+show-save-copy = Guardar una copia...
+show-keep-working = Seguir trabajando
+show-properties-title = Propiedades del show
+show-audio-file = Archivo de audio:
+show-not-saved = Sin guardar
+show-back-fade = Fundido de Back:
+show-back-fade-hint = Lo que tarda Back en fundir a la cue anterior
+show-lock = Bloqueo
+show-locked-hint = El show se abre en modo actuación y necesita el código para salir de él.
+show-remove-lock = Quitar bloqueo
+show-lock-removed = Se quitó el bloqueo del show
+show-lock-hint = Mantiene el show en modo actuación hasta que se introduzca un código.
+show-passcode = Código:
+show-passcode-again = Repetir:
+show-passcode-mismatch = Los códigos no coinciden
+show-lock-button = Bloquear show
+show-lock-button-hint = Guarda el show después para mantener el bloqueo
+show-locked = Se bloqueó el show
+schema-pad-banks = Los bancos de pads pueden aparecer con canciones y secciones de la lista de cues
+schema-unversioned = Antes de que el formato tuviera versión
+
+## Songs
+
+songs-the-start = el principio
+songs-removed = (eliminada)
+songs-title = Canciones
+songs-add = Añadir canción
+songs-close-gaps = Cerrar huecos
+songs-close-gaps-hint = Coloca las canciones una detrás de otra en este orden
+songs-cues-hint = Las cues de una canción se cuentan desde su inicio. Asígnalas en el editor de cues.
+songs-cue-list-end = Al final de la lista de cues:
+songs-empty = Aún no hay canciones.
+songs-at-end = Al terminar
+songs-at-end-hint = Qué pasa cuando termina la canción en modo setlist
+songs-audio-hint = El archivo de audio y el ajuste propios de la canción, que suenan en lugar de los del show
+songs-remove = Quitar canción
+songs-setlist = Setlist
+
+## Fan
+
+fan-hue-shift = Desplazar los tonos de la cue
+fan-hue-shift-hint = Gira los colores por la rueda para que el abanico persiga a lo largo de los focos
+fan-hue-period = Una vuelta cada
+fan-title = Abanico
+fan-into-cue = En la cue { $number } { $name }
+fan-select-cue = Selecciona una cue para el abanico.
+fan-rainbow = 🌈 Arcoíris
+fan-rainbow-hint = Prepara un arcoíris completo en la selección
+fan-select-type = Selecciona todos los focos de este tipo
+fan-mode-label = Abanico:
+fan-order-label = Orden:
+fan-direction-label = Dirección:
+fan-wrap = Cerrar
+fan-wrap-hint = Hace del abanico un bucle que une el último foco con el primero
+fan-middle = Centro:
+fan-ends = Extremos:
+fan-from = Desde:
+fan-to = Hasta:
+fan-seed = Semilla:
+fan-another-seed = Probar otra semilla
+fan-no-fixtures = Abanico: ninguno de los focos seleccionados tiene { $target }
+fan-done = Abanico de { $target } en { $count } focos en la cue { $number }
+
+## Pads
+
+pad-default-label = Pad { $number }
+pad-release-label = Liberación:
+pad-fade-out = Fundido de salida:
+pad-flash-fixtures = Flash de focos
+pad-flash-solo-hint = Solo quita todos los demás focos mientras dura el flash
+pad-columns = Columnas:
+pad-rows = Filas:
+pad-bank-name = Nombre del banco:
+pad-bank-songs = Aparece con canciones
+pad-bank-sections = Aparece con secciones
+pad-add-bank = Añadir banco
+pad-default-bank = Banco { $number }
+pad-remove-bank = Quitar banco
+pad-remove-bank-hint = Quita este banco y sus pads
+pad-layout = ⚙ Disposición
+pad-accessible-on = Pad { $label }, encendido
+pad-accessible-off = Pad { $label }, apagado
+pad-flash = Flash
+pad-edit-script = Editar script...
+pad-run-macro = Ejecutar macro
+pad-move-to-bank = Mover al banco
+pad-delete = Eliminar pad
+pad-add = Añadir un pad
+
+## Output monitor
+
+monitor-nothing = No sale nada.
+monitor-mute = Silenciar
+monitor-mute-hint = Deja de enviar este universo a las salidas DMX
+monitor-muted = Universo { $universe } silenciado
+monitor-unmuted = Universo { $universe } activado
+monitor-unmute-all = Activar todos
+monitor-unmuted-all = Se activaron todos los universos
+monitor-levels = Niveles de salida del universo { $universe }
+monitor-from = de { $owner }
+monitor-click-channel = Haz clic en un canal para ver qué lo controla.
+monitor-fading = { $name } en fundido { $percent }%
+monitor-uncontrolled = nada lo controla
+monitor-test-pattern = Patrón de prueba:
+monitor-on-universe = en el universo
+monitor-show-not-here = El show no está en este universo
+monitor-channel-check = Prueba de canales
+monitor-channel-check-hint = Prueba un canal cada vez.
+monitor-previous-channel = Canal anterior
+monitor-next-channel = Canal siguiente
+monitor-solo-hint = Apaga todos los demás canales
+monitor-inhibited = {" "}(inhibido)
+monitor-checking = Probando { $channel } a { $level }: { $patched }. Usa ← y → para avanzar.
+monitor-not-patched = sin patch
+
+## Show diff
+
+diff-renamed = Renombrada de "{ $before }" a "{ $after }"
+diff-start = Empieza en { $after }, antes { $before }
+diff-duration = Dura { $after } s, antes { $before } s
+diff-also-changed = También cambió: { $fields }
+diff-before = Antes
+diff-after = Después
+diff-read-failed = No se pudo leer el show: { $error }
+diff-compare-label = Comparar:
+diff-last-save = Último guardado
+diff-show-file = Archivo de show
+diff-this-show = Este show
+diff-cues-label = Cues:
+diff-all-cues = Todas las cues
+diff-with-this = Con las de este show:
+diff-compare = Comparar
+diff-pick = Elige qué comparar y pulsa Comparar.
+diff-none = No hay diferencias.
+diff-added = { $count } añadidas
+diff-removed = { $count } eliminadas
+diff-changed = { $count } cambiadas
+
+## Inspecting fixtures
+
+inspect-profile-at = { $profile } en { $address }
+inspect-nothing = nada
+owner-cue-index = cue n.º { $index }
+owner-cue = Cue { $number } { $name }
+owner-move-in-black = Move in black para { $cue }
+owner-pixel-map = Mapa de píxeles
+owner-pixel-map-named = Mapa de píxeles { $name }
+owner-haze-schedule = Programa de haze
+owner-smoke-pad = Pad { $pad }
+owner-input = Entrada de red
+owner-quick-console = Consola rápida
+owner-follow-spot = Cañón de seguimiento
+owner-follow-spot-named = Cañón de seguimiento { $name }
+owner-flash = Flash en el pad { $pad }
+owner-flash-solo = Atenuado por un flash solo
+owner-group-master = Master de grupo
+owner-group-master-at = Master de grupo { $name } al { $percent }%
+owner-grand-master = Gran master al { $percent }%
+owner-processing = Procesado de salida
+owner-inhibited = Inhibido
+owner-channel-check = Prueba de canales
+owner-test-pattern = Patrón de prueba
+owner-panic = Estado seguro
+owner-release = Liberación al parar
+owner-recording = Grabación de salida
+
+## Venue
+
+venue-title = Recinto
+venue-description = Las cues se programan sobre el patch del show. Un mapeo de recinto reproduce cada foco en las unidades del rig de este recinto, así que en gira solo hace falta un mapeo nuevo.
+venue-new-from-patch = Nuevo desde el patch
+venue-new-from-patch-hint = Empieza un mapeo que reproduce cada foco donde está en el patch
+venue-new-name = Nuevo recinto
+venue-use-show-patch = Usar el patch del show
+venue-use-show-patch-hint = Envía la salida al propio patch del show
+venue-none = Sin mapeo de recinto: la salida va al patch del show.
+venue-label = Recinto:
+venue-as-programmed = Como se programó
+venue-add-unit = Añadir unidad
+venue-unmapped = No se reproducen en este recinto: { $fixtures }
+venue-unknown = No están en el patch del show: { $fixtures }
+venue-cleared = Se quitó el mapeo de recinto; la salida va al patch del show
+venue-load-failed = No se pudo cargar el mapeo: { $error }
+venue-saved = Recinto { $name } guardado en { $path }
+venue-save-failed = No se pudo guardar el mapeo: { $error }
+venue-loaded = Recinto { $name }: { $count ->
+        [one] { $count } unidad asignada
+       *[other] { $count } unidades asignadas
+    }
+venue-mapping-failed = No se pudo cargar la asignación del recinto { $path }: { $error }
+
+## Timecode
+
+timecode-title = Código de tiempo
+timecode-start = Inicio:
+timecode-zero = Cero
+timecode-offset = Desfase:
+timecode-offset-hint = Se suma al código de tiempo entrante
+timecode-preroll = Pre-roll:
+timecode-preroll-hint = Cuenta atrás antes de reproducir desde el principio; 0 la desactiva
+timecode-drift = Aviso de deriva:
+timecode-drift-hint = Hace parpadear la lectura cuando Halo y el código de tiempo entrante se separan más que esto
+timecode-chase = Tolerancia de seguimiento:
+timecode-chase-hint = Salta al código de tiempo entrante cuando se aleja más que esto
+timecode-late-join = Entrada tardía:
+timecode-late-join-hint = Tras saltar al código de tiempo entrante, dispara las acciones de las cues que empezaron hasta este tiempo antes; 0 no dispara ninguna
+timecode-late-cue = Cue tardía:
+timecode-late-cue-hint = Marca las cues que se disparan más lejos que esto de su tiempo en el código de tiempo entrante
+timecode-incoming = Entrante:
+timecode-incoming-rolling = { $position } a { $fps } fps → { $show }
+timecode-incoming-stopped = { $position } a { $fps } fps → { $show } (parado)
+timecode-none = No se recibe código de tiempo MIDI
+timecode-turn-on-chase = Activa «Perseguir el código de tiempo MIDI» en Preferencias para seguirlo.
+timecode-stopped = Código de tiempo detenido
+
+## Packet inspector
+
+inspector-sources = Fuentes
+inspector-packets = Paquetes
+inspector-resume = ▶ Reanudar
+inspector-pause = ⏸ Pausa
+inspector-input-off = La entrada de red está desactivada, así que no se recibe nada.
+inspector-duplicated = ⚠ El universo { $universe } tiene más de una fuente enviando; se mezclan con el nivel más alto salvo que sus prioridades sean distintas.
+inspector-protocol = Protocolo
+inspector-from = Desde
+inspector-priority = Prioridad
+inspector-rate = Frecuencia
+inspector-lost-late = Perdidos / Tardíos
+inspector-show-universe = Muestra los paquetes de este universo
+inspector-quiet = sin datos { $seconds } s
+inspector-only-problems = Solo problemas
+inspector-time = Hora
+inspector-seq = Sec.
+
+## Follow spots
+
+follow-tracker = Tracker { $id }
+follow-tracker-silent = Tracker { $id } (sin señal)
+follow-not-patched = (sin patch)
+follow-add-fixture = Añadir foco
+follow-add-group = Añadir grupo
+follow-title = Cañones de seguimiento
+follow-turn-on-psn = Activa la entrada PSN en las preferencias para que los cañones sigan.
+follow-trackers-sending = { $count ->
+        [0] Ningún tracker enviando
+        [one] 1 tracker enviando
+       *[other] { $count } trackers enviando
+    }
+follow-add-spot = Añadir cañón
+follow-default-name = Cañón { $number }
+follow-follows = Sigue a:
+follow-smoothing = Suavizado:
+follow-height = Altura:
+follow-trim = Ajuste:
+follow-trim-hint = A lo ancho y hacia el fondo o el proscenio
+follow-pan-tilt-range = Rango de pan / tilt:
+follow-heads = Cabezas, colgadas en x, y (altura) y z:
+
+## Group masters
+
+masters-move-fader = Mueve un fader...
+masters-dmx-fader = DMX { $fader }
+masters-dmx-learn = Aprender DMX
+masters-dmx-learn-hint = Sigue un canal de un ala de faders
+masters-clear-dmx = Borrar DMX
+masters-title = Masters de grupo
+masters-default-name = Grupo { $number }
+masters-hint = Limita la intensidad de un grupo sin añadir nada.
+masters-grand = Gran master:
+masters-full = Lleno
+masters-flash = Master de flash:
+masters-flash-hint = Hasta dónde suben los pads de flash sus focos
+masters-empty = Añade un master y elige los grupos que limita.
+masters-no-groups = Sin grupos
+masters-groups = { $count } grupos
+masters-move-control = Mueve un control...
+masters-midi-learn = Aprender MIDI
+
+## Smart bulbs
+
+bulbs-title = Bombillas inteligentes
+bulbs-enable = Envía los colores de los focos a dispositivos Hue y WLED
+bulbs-hue-bridge = Puente Hue
+bulbs-paired = Emparejado:
+bulbs-pair = Emparejar
+bulbs-pair-hint = Pulsa antes el botón de enlace del puente
+bulbs-bulbs = Bombillas
+bulbs-add = Asigna un foco a una bombilla
+bulbs-empty = El color y la intensidad de cada foco van a la bombilla asignada.
+bulbs-missing = (no existe)
+bulbs-light = Luz
+bulbs-leds = LEDs
+bulbs-paired-done = Emparejado con el puente Hue
+bulbs-pair-failed = No se pudo emparejar con el puente Hue: { $error }
+bulbs-pair-first = empareja primero con el puente
+bulbs-no-user-name = el puente no dio un nombre de usuario
+
+## Stage display
+
+stage-next = Siguiente: { $number }
+stage-next-named = Siguiente: { $number } { $name }
+stage-no-more = No hay más cues
+stage-viewport-title = Halo – Pantalla de escenario
+stage-open = Mostrar en la pantalla de escenario
+stage-open-hint = Abre una ventana propia; arrástrala al monitor que quieras usar
+stage-fullscreen = Pantalla completa
+stage-fullscreen-hint = Ocupa todo el monitor en el que está la ventana
+stage-next-cue = Siguiente cue
+stage-countdown = Cuenta atrás
+stage-text = Texto:
+stage-background = Fondo:
+stage-standby = Standby:
+stage-standby-from = Standby desde:
+stage-standby-suffix = {" "}s antes de la cue
+stage-standby-hint = 0 desactiva el standby
+stage-flash = Parpadear en standby
+
+## Spectrogram
+
+spectrogram-title = Espectrograma
+spectrogram-no-audio = Elige un archivo de audio en Propiedades del show para verlo aquí.
+spectrogram-analysing = Analizando { $path }...
+spectrogram-read-failed = No se pudo leer el audio: { $error }
+spectrogram-snap = Ajustar a
+spectrogram-snap-hint = Las cues arrastradas caen en la rejilla, o en un golpe si están cerca de uno
+spectrogram-zoom = Zoom:
+spectrogram-hits = { $count } golpes
+spectrogram-beat-grid = Rejilla de pulsos
+spectrogram-use-current = Usar el actual
+spectrogram-use-current-hint = Toma el tempo de la cabecera
+spectrogram-downbeat = Primer tiempo:
+spectrogram-at-playhead = En el cabezal
+spectrogram-at-playhead-hint = Pone un primer tiempo de compás en el cabezal
+spectrogram-accessible = Forma de onda con cues; arrastra una cue para moverla, o selecciónala y muévela con [ y ]
+spectrogram-hold-shift = Mantén Mayús para colocar sin ajustar
+spectrogram-drag-hint = Arrastra una cue para moverla, arrastra en otro sitio para desplazarte, usa la rueda para el zoom
+
+## Effect presets
+
+presets-title = Presets de efectos
+presets-applies-to = Se aplica al mapa de píxeles { $name }
+presets-select-map = Selecciona un mapa de píxeles para aplicarle presets o guardar desde él.
+presets-empty = Aún no hay presets guardados.
+presets-uncategorised = Sin categoría
+presets-generator-at = { $generator } a { $speed } Hz
+presets-export-hint = Guarda en el archivo de abajo
+presets-category = Categoría:
+presets-save-from-map = Guardar desde el mapa de píxeles
+presets-applied = Preset de efecto { $preset } aplicado a { $map }
+presets-choose-file = Elige un archivo al que exportar
+presets-exported = Preset de efecto { $preset } exportado a { $path }
+presets-saved = Preset de efecto { $preset } guardado
+presets-imported = Preset de efecto { $preset } importado
+
+## DAW markers
+
+daw-title = Marcadores de DAW
+daw-description = Las cues salen como marcadores llamados Q<número> <nombre>. Al leer una lista, cada cue con nombre se mueve a su marcador y se añade una cue por cada marcador restante.
+daw-timecode = Código de tiempo:
+daw-fps-hint = Para las ubicaciones de Pro Tools, desde el código de tiempo inicial del show
+daw-read = Leer
+daw-marker = Marcador
+daw-does = Hace
+daw-already-there = La cue { $number } ya está ahí
+daw-moves = Mueve la cue { $number } desde { $from }
+daw-new-cue = Cue nueva
+daw-apply = Aplicar a las cues
+daw-exported = { $count } cues exportadas como marcadores a { $path }
+daw-export-failed = No se pudo exportar: { $error }
+daw-read-failed = No se pudieron leer los marcadores: { $error }
+daw-applied = Marcadores de DAW: { $moved } cues movidas, { $added } añadidas
+daw-no-markers = no se encontraron marcadores
+daw-no-column = falta la columna { $name }
+daw-bad-time = fila { $row }: no se puede leer el tiempo «{ $time }»
+daw-no-location = falta la columna LOCATION o NAME
+daw-bad-location = no se puede leer la posición en «{ $line }»
+
+## Power
+
+power-of = { $draw } de { $limit }
+power-whole-rig = Todo el rig
+power-total = Total
+power-unrated = { $count } focos no tienen potencia y no se cuentan.
+power-breakers = Los magnetotérmicos aguantan
+power-breakers-hint = Voltios por amperios: 2300 W es un circuito de 10 A a 230 V
+power-now = Ahora
+power-peaks = Consumo máximo por cue
+power-wattage = Potencias y circuitos
+power-peaks-hint = Cada cue reproducida en orden a tope, sin masters ni efectos.
+power-peak = Máximo
+power-over = Por encima del límite del circuito
+power-full-hint = Vatios a tope
+power-idle = Reposo
+power-idle-hint = Vatios en negro
+power-circuit = Circuito
+power-no-circuit = ninguno
+
+## Importing a patch
+
+import-patch-title = Importar patch
+import-patch-hint = Un archivo MVR, o un patch exportado como CSV desde grandMA o ETC.
+import-patch-read-failed = No se pudo leer el patch: { $error }
+import-patch-summary = { $fixtures } focos de { $types } tipos. Elige el perfil de Halo para cada tipo:
+import-patch-profile = Perfil
+import-patch-no-type = (sin tipo)
+import-patch-skip = Omitir
+import-patch-replace = Reemplazar el patch actual
+import-patch-replace-warning = Los valores de cue de los focos actuales dejarán de aplicarse.
+import-patch-done = { $added } focos importados desde { $path }; { $skipped } omitidos
+import-patch-overlap = { $count } focos se solapan con otros; revisa el mapa de direcciones
+
+## Designer view
+
+designer-title = Vista de diseño
+designer-operator = Operador:
+designer-as = Como:
+designer-hint = Sigue el puerto de control remoto de otro Halo, solo lectura salvo que el operador conceda secciones de la lista de cues.
+designer-connecting = Conectando...
+designer-bpm = { $bpm } BPM
+designer-current-cue = Cue { $number }: { $name }
+designer-no-cue = Ninguna cue
+designer-levels = Niveles de salida:
+designer-no-followers = No se ha conectado ninguna vista de diseño.
+designer-gone = { $name } (desconectado)
+designer-may-fire = puede disparar:
+designer-unsectioned = Sin sección
+designer-connected = Vista de diseñador { $name } conectada
+designer-fired = La vista de diseñador { $name } disparó la cue { $number }
+
+## Haze
+
+haze-title = Haze
+haze-enable = Ejecutar el programa de haze
+haze-pad-on = Pad { $pad } encendido
+haze-show-ended = Apagado, el show terminó
+haze-hazing = Echando haze
+haze-waiting = Esperando
+haze-hazers = Máquinas de haze
+haze-none = Haz el patch de un foco con un canal de Haze para controlarlo aquí.
+haze-fixture = { $name } (haze en { $channel })
+haze-burst = Ráfaga:
+haze-every = Cada:
+haze-pad = Pad de anulación:
+haze-pad-output = Salida del pad:
+haze-hint = El programa funciona mientras suena el show y apaga las máquinas tras la última cue.
+
+## Tracksheet
+
+tracksheet-edit = Edición:
+tracksheet-edit-hint = Tracking arrastra los cambios hasta que una cue posterior cambia el mismo valor; Cue solo devuelve el valor anterior en la cue siguiente
+tracksheet-never-set = Nunca se ha fijado; doble clic para fijarlo
+tracksheet-set-here = Fijado en esta cue
+tracksheet-blocked = Se mantiene al nivel con el que llegó
+tracksheet-tracks-from = Viene de la cue { $number }
+tracksheet-title = Hoja de tracking
+tracksheet-legend-changed = cambiado aquí
+tracksheet-legend-tracked = arrastrado
+tracksheet-legend-blocked = retenido por un bloqueo
+tracksheet-hint = Doble clic en una celda para editarla; déjala vacía para que arrastre.
+tracksheet-bad-level = Hoja de tracking: "{ $text }" no es un nivel de 0 a 255
+
+## Schedule
+
+schedule-offset-prefix = desfase{" "}
+schedule-title = Programación
+schedule-hint = Las acciones se ejecutan a una hora del día, o al amanecer o atardecer en el recinto.
+schedule-latitude = Latitud:
+schedule-longitude = Longitud:
+schedule-on = Activa
+schedule-when = Cuándo
+schedule-action = Acción
+schedule-next = Próxima
+schedule-never = Nunca
+schedule-ran = Programación { $when }: { $action }
+
+## Frame timings
+
+frame-ui-worst = IU { $ms } ms como máximo
+frame-output-worst = Salida { $ms } ms como máximo
+frame-target = Objetivo { $ms } ms
+frame-dropped = Fotogramas de salida perdidos: { $count }
+frame-stage = Etapa
+frame-last = Último
+frame-average = Media
+frame-worst = Peor
+frame-share = Del fotograma
+frame-total = Total
+frame-of = de { $time }
+frame-waiting = Esperando al motor de salida
+frame-history = En los últimos { $count } fotogramas de salida
+frame-stage-cues = Cues
+frame-stage-merge = Mezcla
+frame-stage-ownership = Propiedad
+frame-stage-effects = Efectos y mapas de píxeles
+frame-stage-haze = Humo, entrada y cañones de seguimiento
+frame-stage-masters = Masters
+frame-stage-processing = Procesado y anulaciones
+frame-stage-sending = Envío
+
+## Fixture library
+
+library-footprint = Huella: { $count ->
+        [one] { $count } canal
+       *[other] { $count } canales
+    }
+library-white = Blanco: de { $warm }K a { $cool }K
+library-controls = Controles: { $controls }
+library-title = Biblioteca de focos
+library-favourites = ★ Favoritos:
+library-recent = Recientes:
+library-manufacturer = Fabricante
+library-model = Modelo
+library-mode = Modo
+library-favourite-on = ★ Favorito
+library-favourite-off = ☆ Favorito
+library-use = Usar para focos nuevos
+
+## MIDI monitor
+
+midi-monitor-in = Entrada
+midi-monitor-out = Salida
+midi-monitor-channel = Can
+midi-monitor-timing = Reloj y código de tiempo
+midi-monitor-timing-hint = Registrar pulsos de reloj, cuartos de fotograma MTC y active sensing
+midi-monitor-no-ports = No hay puertos MIDI abiertos; elígelos en Preferencias.
+midi-monitor-port = Puerto
+midi-monitor-data = Datos
+
+## Snapshots
+
+snapshots-title = Instantáneas de salida
+snapshots-capture = Capturar
+snapshots-default-name = Instantánea { $number }
+snapshots-reference-label = Referencia:
+snapshots-delete-hint = Eliminar esta instantánea del show
+snapshots-identical = Las instantáneas son idénticas
+snapshots-differ = { $count ->
+        [one] { $count } canal distinto
+       *[other] { $count } canales distintos
+    }
+snapshots-patched = Asignado
+snapshots-reference = Referencia
+snapshots-unpatched = sin asignar
+look-name = Look { $label }
+look-recorded = { $name } grabado como cue { $number } en { $time }
+
+## Plugins
+
+plugins-title = Plugins
+plugins-folder = Los plugins se leen de { $path }
+plugins-no-folder = No hay carpeta de configuración para los plugins.
+plugins-reload = Recargar
+plugins-none = No hay plugins cargados. Añade scripts .rhai a la carpeta y recarga.
+plugins-generator-output = Generador, salida
+plugins-generator = Generador
+plugins-output = Salida
+plugins-nothing = Nada
+plugins-ok = OK
+plugins-outputs = Salidas
+plugins-send-to = Enviar a:
+plugins-no-entry-point = no define ni pixel() ni packet()
+plugins-read-failed = No se pudo leer el plugin { $path }: { $error }
+plugins-error = Plugin { $name }: { $error }
+plugins-loaded = { $count ->
+        [one] { $count } plugin cargado
+       *[other] { $count } plugins cargados
+    }
+
+## Macros
+
+macros-recording = ● Grabando «{ $name }» ({ $count ->
+        [one] { $count } paso
+       *[other] { $count } pasos
+    })
+macros-record = ● Grabar
+macros-default-name = Macro { $number }
+macros-hint = Dispara cues, activa pads y cambia el BPM para grabarlos.
+macros-none = No hay macros grabadas.
+macros-play = Reproducir
+macros-entry = { $name } ({ $count ->
+        [one] { $count } paso
+       *[other] { $count } pasos
+    })
+macros-no-cue = Macro: no existe la cue { $number }
+macros-no-next-cue = GO: no hay siguiente cue
+macros-no-pad = Macro: no hay pad «{ $pad }»
+macros-no-bank = Macro: no hay banco de pads { $number }
+macros-no-macro = No hay ninguna macro llamada «{ $name }»
+macros-too-many = La macro «{ $name }» no se inició: hay demasiadas macros en marcha
+
+## Speed masters
+
+speed-title = Masters de velocidad
+speed-freeze = ❄ Congelar efectos
+speed-freeze-hint = Mantener cada mapa de píxeles y look de busk donde está
+speed-default-name = Velocidad { $number }
+speed-empty = Añade un master y elígelo para mapas de píxeles y busk.
+
+## OSC monitor
+
+osc-monitor-traffic = Tráfico
+osc-monitor-addresses = Direcciones
+osc-monitor-input-off = La entrada OSC está desactivada; actívala en Preferencias para ver los mensajes entrantes.
+osc-monitor-peer = De / a
+osc-monitor-arguments = Argumentos
+osc-monitor-addresses-hint = Direcciones asignadas en Disparadores, enviadas por cues o vistas en la red, con los últimos argumentos de cada una.
+osc-monitor-no-addresses = Aún no hay direcciones OSC.
+osc-monitor-bound = Entrada: { $action }
+osc-monitor-sent = Salida: enviado a { $target } por la cue { $number }
+osc-monitor-last = Último: [{ $args }] a las { $time }
+
+## Fixture controls
+
+controls-lamp-on-caution = Las lámparas se encienden una tras otra, cada una con su corriente de arranque. Una lámpara recién apagada puede no encender hasta que se enfríe.
+controls-lamp-off-caution = Los focos se apagan a la vez, y una lámpara de descarga tiene que enfriarse varios minutos antes de volver a encender.
+controls-reset-caution = Los focos se mueven y pueden apagarse mientras se reinician, así que no conviene en mitad de un show.
+controls-menu = Control
+controls-menu-hint = Encender lámpara, apagar lámpara y reiniciar, según los envía el perfil
+controls-title = Controles de focos
+controls-sending = Enviando...
+controls-stop-hint = Soltar ya los canales de control
+controls-stopped = Controles de focos detenidos
+controls-prompt-title = ¿{ $control }?
+controls-prompt = ¿Enviar { $control } a { $fixtures }?
+controls-takes = Tarda { $seconds } s.
+controls-sent = Enviando { $control } a { $count ->
+        [one] { $count } foco
+       *[other] { $count } focos
+    }
+
+## Crossfade
+
+crossfade-out = Sale
+crossfade-in = Entra
+crossfade-cc-any = CC { $controller } cualquier can
+crossfade-cc = CC { $controller } can { $channel }
+crossfade-no-midi = Sin MIDI
+crossfade-learn-hint = Asignar el siguiente controlador MIDI que se mueva
+crossfade-title = Fundido A/B
+crossfade-top = el principio del show
+crossfade-cue = la cue { $number }
+crossfade-from-to = De { $from } a la cue { $number }
+crossfade-no-next = No hay siguiente cue a la que fundir
+crossfade-hint = Baja el fader que sale y sube el que entra. Cuando ambos llegan al final, la siguiente cue está dentro y los faders cambian de papel.
+crossfade-learned = CC { $controller } canal { $channel } aprendido para el fader de fundido { $fader }
+crossfade-done = Fundido a la cue { $number }
+
+## Safe state
+
+safe-resume-hint = Salir del estado seguro y seguir donde estaba el show
+safe-engaged = ● ESTADO SEGURO
+safe-panic = PÁNICO
+safe-panic-hint = Fundir al estado seguro y detener la reproducción (F12)
+safe-title = Estado seguro
+safe-description = El look al que funde el botón de pánico, como luces de sala encendidas con efectos y humo apagados. Lo que quede a cero se apaga.
+safe-capture = Capturar salida
+safe-capture-hint = Usar los niveles que están al aire ahora
+safe-panic-fading = Pánico: fundiendo al estado seguro en { $seconds } s
+safe-resumed = Reanudado desde el estado seguro
+
+## sACN output
+
+sacn-priority-hint = Los receptores toman cada universo de la prioridad más alta que lo envía
+sacn-title = Salida sACN
+sacn-enabled = Enviar la salida por sACN
+sacn-source-name = Nombre de la fuente:
+sacn-priority = Prioridad:
+sacn-universes = Universos
+sacn-universes-hint = Los universos no listados salen con la prioridad de arriba. Solo direcciones asignadas envía prioridad por dirección, reclamando solo las direcciones donde hay focos y dejando el resto a otras fuentes.
+sacn-per-address = Solo direcciones asignadas
+sacn-add-universe = Añadir universo
+
+## Learn timing
+
+learn-title = Aprender tiempos
+learn-description = Reproduce el show y pulsa GO donde deba dispararse cada cue. La posición del cabezal en cada GO pasa a ser el inicio de la cue. Las cues se aprenden en el orden de la lista desde el cabezal.
+learn-start = Empezar a aprender
+learn-progress = { $learned } de { $count ->
+        [one] { $count } cue aprendida
+       *[other] { $count } cues aprendidas
+    }
+learn-next = Siguiente: cue { $number } { $name }
+learn-not-rolling = Inicia la reproducción para aprender a tiempo.
+learn-undo = Deshacer GO
+learn-done = Hecho
+learn-done-hint = Conservar los tiempos aprendidos hasta ahora
+learn-cancel-hint = Devolver cada cue a su tiempo anterior
+learn-no-cues = Aprender tiempos: no hay cues armadas después del cabezal
+learn-started = Aprendiendo tiempos de { $count ->
+        [one] { $count } cue
+       *[other] { $count } cues
+    }: pulsa GO (G) cuando deba dispararse cada una
+learn-cue-gone = Aprender tiempos: la cue { $number } ya no existe
+learn-learned-cue = Cue { $number } aprendida en { $time }
+learn-finished = Tiempos aprendidos para { $count ->
+        [one] { $count } cue
+       *[other] { $count } cues
+    }
+learn-cancelled = Aprendizaje cancelado; tiempos de las cues restaurados
+
+## Variables
+
+variables-title = Variables
+variables-description = Las cues pueden reproducirse solo cuando una variable tiene un valor. Ajústalas aquí o por OSC en /halo/var/<nombre>.
+variables-still-used = { $count ->
+        [one] { $count } cue aún comprueba
+       *[other] { $count } cues aún comprueban
+    } { $name }; no se reproducirán hasta que vuelva a tener valor
+variables-osc-no-value = OSC { $address }: no hay valor que asignar
+variables-set = Variable { $name } = { $value }
+
+## Quick console
+
+quick-encoder-hint = Arrastra o desplaza para mover los canales seleccionados; Re Pág y Av Pág hacen lo mismo desde el teclado
+quick-move = Mover
+quick-move-hint = Mover cada nivel la misma cantidad
+quick-scale = Escalar
+quick-scale-hint = Escalar los niveles, manteniendo sus proporciones
+quick-release-all = Liberar todo
+quick-hint = Haz clic en un número de canal para seleccionarlo. Clic derecho en un fader para liberarlo. Los niveles grises vienen de las cues.
+
+## Tags
+
+tag-icon-pyro = Pirotecnia
+tag-icon-video = Vídeo
+tag-icon-sound = Sonido
+tag-icon-haze = Humo
+tag-icon-strobe = Estroboscopio
+tag-icon-warning = Aviso
+tag-icon-key-moment = Momento clave
+tag-icon-talent = Artista
+tag-colour = Etiqueta { $colour }
+tag-colour-chosen = Etiqueta { $colour }, elegida
+
+## Notifications
+
+toasts-dismiss-all = Descartar todo
+toasts-empty = Nada todavía.
+
+## Watchdog
+
+watchdog-restarted = ⚠ MOTOR DE SALIDA REINICIADO — revisa el equipo; los detalles están en el registro
+watchdog-restarted-times = ⚠ MOTOR DE SALIDA REINICIADO ({ $count } veces en esta sesión) — revisa el equipo; los detalles están en el registro
+watchdog-dismiss = Descartar
+
+## Quit
+
+quit-fading = Fundiendo a negro en { $seconds } s antes de salir
+quit-output-stopped = Salida detenida para salir
+
+## Visualizer
+
+visualizer-empty = No hay nada asignado.
+
+## Patch import
+
+import-patch-none-found = no se encontraron focos asignados
+import-patch-not-mvr = no es un archivo MVR: falta { $entry }
+import-patch-no-address = no hay columna de dirección en la cabecera del CSV
+
+## Engine
+
+engine-recording-failed = Grabación: { $error }
+engine-playback-failed = Reproducción: { $error }
+engine-end-of-cues = Se llegó al final de la lista de cues
+engine-hazers-off = Máquinas de humo apagadas al final del show
+engine-held = Detenido al final de la canción
+
+## CITP
+
+citp-failed = CITP no pudo arrancar: { $error }
+
+## RDM
+
+rdm-not-listed = { $uid } ya no aparece en la lista
+rdm-discovering = Descubriendo el universo { $universe }...
+rdm-reading = Leyendo el dispositivo { $number } de { $count }...
+rdm-addressing = Direccionando { $uid }...
+rdm-no-answer = { $uid } no respondió
+rdm-refused-reason = { $uid } lo rechazó (motivo { $reason })
+rdm-refused = { $uid } rechazó la petición
+
+## Updates
+
+update-curl-failed = no se pudo ejecutar curl: { $error }
+update-no-response = sin respuesta de GitHub
+update-no-version = GitHub no envió la versión
+update-available = Halo { $version } está disponible; consulta Halo → Acerca de
+
+## Audio playback
+
+playback-device-missing = No se encontró la salida de audio «{ $device }»
+playback-sample-format = Formato de muestra no admitido: { $format }
+playback-load-failed = No se pudo cargar { $path }: { $error }
+
+## MIDI
+
+midi-input-missing = No se encontró la entrada MIDI «{ $port }»
+midi-output-missing = No se encontró la salida MIDI «{ $port }»
+
+## Hotplug
+
+hotplug-input-disconnected = Entrada MIDI «{ $port }» desconectada
+hotplug-input-unplugged = Entrada MIDI { $port } desenchufada
+hotplug-input-reconnected = Entrada MIDI { $port } reconectada
+hotplug-output-disconnected = Salida MIDI «{ $port }» desconectada
+hotplug-output-unplugged = Salida MIDI { $port } desenchufada
+hotplug-output-reconnected = Salida MIDI { $port } reconectada
+
+## Clock sync
+
+clock-main-silent = El principal dejó de responder
+clock-show-fetch-failed = No se pudo obtener el show del principal: { $error }
+clock-listen-failed = La sincronización de reloj no pudo escuchar en el puerto { $port }: { $error }
+clock-handed-back = Salida devuelta al principal
+clock-taken-over = Se perdió el principal en { $main }; este respaldo ha tomado la salida
+clock-following = Siguiendo al principal en { $main }
+
+## Network interfaces
+
+interfaces-down-suffix = {" "}— caída
+interfaces-down = La interfaz { $name } de { $protocol } está caída
+interfaces-up = La interfaz { $name } de { $protocol } vuelve a estar activa
+
+## Show
+
+patch-copy-name = { $name } copia
+
+## Assert
+
+assert-quick-levels = niveles rápidos
+assert-busk = busk
+assert-pads = pads
+assert-done = Cues reafirmadas
+assert-done-over = Cues reafirmadas sobre { $dropped }
+
+## Startup
+
+startup-opened = { $path } abierto al arrancar
+startup-midi-input = Usando la entrada MIDI { $port } al arrancar
+startup-midi-output = Usando la salida MIDI { $port } al arrancar
+startup-playing = Reproducción iniciada al arrancar desde { $time }
+
+## Show comparison
+
+diff-loose-cues = Cues fuera de canciones
+diff-missing-song = Canción desaparecida
+
+## End actions
+
+end-stopped = Detenido al final de la lista de cues
+end-looped = Bucle al final de la lista de cues
+end-chain-missing = La canción a la que encadenar ya no existe
+end-chained = Encadenado a { $song }
+
+## DMX faders
+
+masters-no-fader-universe = Añade un universo de ala de faders en Preferencias → Entrada de red para aprender faders DMX
+masters-the-grand-master = el gran master
+masters-learned-dmx = DMX { $fader } aprendido para { $name }
+
+## Setlist
+
+setlist-no-song = Lista de canciones: no existe la canción { $number }
+setlist-label = Canción { $number }/{ $count }: { $name }
+
+## Beat cues
+
+beat-cue-fired = Compás { $position }: cue { $number }
+
+## Ripple
+
+ripple-nudged = Cue { $number }{ $count ->
+        [0] {""}
+        [one] {" "}y { $count } cue posterior
+       *[other] {" "}y { $count } cues posteriores
+    } desplazada { $seconds } s
+
+## Back
+
+back-no-previous = Atrás: no hay cue anterior
+back-to-cue = Atrás a la cue { $number }
+
+## Session
+
+session-fired-off-time = La cue { $number } se disparó { $late }
+
+## Audio
+
+audio-input-missing = No se encontró la entrada de audio «{ $device }»
+
+## Shared
+
+cancel = Cancelar
+exported-to = Exportado a { $path }
+export-failed = La exportación ha fallado: { $error }
+export-to = Exportar a:
+universe-label = Universo:
+unit-hz = {" "}Hz
+add = Añadir
+ip-address = Dirección IP
+remove = Quitar
+universe = Universo
+listen-failed = No se pudo escuchar en el puerto { $port }: { $error }
+any = Cualquiera
+learn = Aprender
+clear = Borrar
+nothing-received = Aún no ha llegado nada.
+unit-beats = {" "}tiempos
+speed-master-label = Master de velocidad:
+search-label = Buscar:
+cue-name = Cue { $number } { $name }
+unit-seconds = {" "}s
+name-label = Nombre:
+none = Ninguna
+notes-label = Notas:
+fade = Fundido
+delay = Retardo
+fixture-count = { $count ->
+        [0] Ningún foco
+        [one] 1 foco
+       *[other] { $count } focos
+    }
+cue-number = Cue { $number }
+port-label = Puerto:
+input-label = Entrada:
+output-label = Salida:
+all = Todos
+level = Nivel
+fade-label = Fundido:
+listening-on-port = Escuchando en el puerto { $port }
+select = Seleccionar...
+fixture = Foco
+address-label = Dirección:
+disconnect = Desconectar
+connect = Conectar
+delete = Eliminar
+enabled = Activado
+speed-label = Velocidad:
+load = Cargar
+restart = Reiniciar
+loop = Bucle
+file-label = Archivo:
+path-label = Ruta:
+save = Guardar
+unit-db = {" "}dB
+gain-trim = Ajuste de ganancia
+name = Nombre
+length = Duración
+move-up = Subir
+move-down = Bajar
+reverse = Invertir
+fixtures = Focos
+attribute-label = Atributo:
+apply = Aplicar
+unit-bars = {" "}compases
+label-label = Etiqueta:
+width-label = Ancho:
+tag = Etiqueta de color
+close = Cerrar
+channel-label = Canal:
+level-label = Nivel:
+attribute = Atributo
+address = Dirección
+type = Tipo
+unit-ms = {" "}ms
+unit-metres = {" "}m
+standing-by = En espera mientras el principal tiene la salida.
+no = No
+yes = Sí
+try-again = Reintentar
+unit-bpm = {" "}BPM
+import-from = Importar desde:
+import = Importar
+unit-fps = { $fps } fps
+unit-watts = {" "}W
+unit-minutes = {" "}min
+fixtures-label = Focos:
+learned-cc = CC { $controller } canal { $channel } aprendido para { $name }
+audio-unsupported-format = Formato de muestra no compatible: { $format }
@@ -0,0 +1,242 @@
+//! Benchmarks of the output engine's per-frame work, run with `cargo bench`.
+//! Each stage is timed on rigs of growing size with every cue mid-fade, so
+//! the merge does its full work, and whole frames are rendered as the engine
+//! renders them. The engine has 1/44 s, about 22.7 ms, for a frame.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use halo_tc_ui::audio::AudioLevels;
+use halo_tc_ui::busk::BuskSettings;
+use halo_tc_ui::cue::{Cue, CueNumber};
+use halo_tc_ui::cue_index::CueIndex;
+use halo_tc_ui::dmx::{self, DmxFrame, OUTPUT_HZ, UNIVERSE_SIZE};
+use halo_tc_ui::dmx_input::DmxInput;
+use halo_tc_ui::engine::{Program, Renderer};
+use halo_tc_ui::fixture::{Fixture, FixtureProfile};
+use halo_tc_ui::pixel::{PixelClock, PixelMap};
+use halo_tc_ui::plugin::PluginHost;
+use halo_tc_ui::psn::Trackers;
+use halo_tc_ui::{driver, ownership, processing};
+use std::time::{Duration, Instant};
+
+/// Rig sizes, in universes of RGB pars.
+const UNIVERSES: [u16; 4] = [1, 4, 16, 64];
+/// Cues in each show, all fading at once.
+const CUES: usize = 20;
+/// Cues in the long show, a few seconds apart.
+const LONG_SHOW: usize = 10_000;
+
+fn rig(universes: u16) -> Vec<Fixture> {
+    let profile = FixtureProfile::rgb_par();
+    let per_universe = UNIVERSE_SIZE / profile.channels.len();
+    (0..universes)
+        .flat_map(|universe| (0..per_universe).map(move |i| (universe + 1, i)))
+        .enumerate()
+        .map(|(id, (universe, i))| {
+            let address = (i * profile.channels.len() + 1) as u16;
+            Fixture::new(
+                id,
+                &format!("Par {}", id + 1),
+                profile.clone(),
+                universe,
+                address,
+            )
+        })
+        .collect()
+}
+
+/// Cues that each set every fixture, overlapping so all are mid-fade at
+/// the time returned, and brought up to it.
+fn cues(fixtures: &[Fixture]) -> (Vec<Cue>, Duration) {
+    let elapsed = Duration::from_secs(CUES as u64);
+    let cues = (0..CUES)
+        .map(|n| {
+            let mut cue = Cue::new(
+                CueNumber::from_whole(n as u32 + 1).unwrap(),
+                "",
+                n as u64,
+                60,
+            );
+            for fixture in fixtures {
+                for &attribute in &fixture.profile.channels {
+                    cue = cue.with_value(fixture.id, attribute, (n * 13 + fixture.id) as u8);
+                }
+            }
+            cue.update(elapsed);
+            cue
+        })
+        .collect();
+    (cues, elapsed)
+}
+
+fn stages(c: &mut Criterion) {
+    for universes in UNIVERSES {
+        let fixtures = rig(universes);
+        let (mut cues, elapsed) = cues(&fixtures);
+        let frame = dmx::render(&fixtures, &cues);
+        let mut group = c.benchmark_group(format!("{} universes", universes));
+
+        group.bench_function("cue update", |b| {
+            b.iter(|| {
+                for cue in &mut cues {
+                    cue.update(black_box(elapsed));
+                }
+            })
+        });
+        group.bench_function("merge", |b| {
+            b.iter(|| dmx::render(black_box(&fixtures), black_box(&cues)))
+        });
+        group.bench_function("ownership", |b| {
+            b.iter(|| ownership::cue_owners(black_box(&fixtures), black_box(&cues)))
+        });
+        group.bench_function("processing", |b| {
+            b.iter(|| {
+                let mut frame = frame.clone();
+                processing::apply(&fixtures, &mut frame);
+                frame
+            })
+        });
+        group.bench_function("driver split", |b| {
+            b.iter(|| driver::dmx_only(black_box(&fixtures), black_box(&frame)).into_owned())
+        });
+        // What the change check costs when nothing has changed, the case it
+        // saves a send in.
+        let last: DmxFrame = frame.clone();
+        group.bench_function("change check", |b| {
+            b.iter(|| {
+                frame
+                    .universe_numbers()
+                    .filter(|&u| frame.universe(u) != last.universe(u))
+                    .count()
+            })
+        });
+        group.finish();
+    }
+}
+
+/// Busk looks over every fixture, and a pixel map a universe of pixels wide.
+fn effects(c: &mut Criterion) {
+    let mut group = c.benchmark_group("effects");
+    for universes in UNIVERSES {
+        let fixtures = rig(universes);
+        let busk = BuskSettings {
+            groups: vec![FixtureProfile::rgb_par().name],
+            ..BuskSettings::default()
+        };
+        let frame = dmx::render(&fixtures, &[]);
+        group.bench_with_input(
+            BenchmarkId::new("busk", universes),
+            &fixtures,
+            |b, fixtures| {
+                let mut beats = 0.0;
+                b.iter(|| {
+                    beats += 0.01;
+                    let mut frame = frame.clone();
+                    busk.render(fixtures, beats, &mut frame);
+                    frame
+                })
+            },
+        );
+    }
+    let mut map = PixelMap::default();
+    map.width = (UNIVERSE_SIZE / 3) as u16;
+    let plugins = PluginHost::default();
+    group.bench_function("pixel map", |b| {
+        let mut time = 0.0;
+        b.iter(|| {
+            time += 0.01;
+            let mut frame = DmxFrame::default();
+            map.render(PixelClock::at(time, 120.0, 4), &plugins, &mut frame);
+            frame
+        })
+    });
+    group.finish();
+}
+
+/// Whole frames through the [`Renderer`], paused with every cue mid-fade.
+fn whole_frame(c: &mut Criterion) {
+    let mut group = c.benchmark_group("whole frame");
+    for universes in UNIVERSES {
+        let fixtures = rig(universes);
+        let (cues, elapsed) = cues(&fixtures);
+        let mut program = Program {
+            cues,
+            fixtures,
+            ..Program::default()
+        };
+        let origin = Instant::now();
+        let mut renderer = Renderer::new(origin, &program, origin);
+        renderer.seek(elapsed, origin);
+        let (plugins, input) = (PluginHost::default(), DmxInput::default());
+        let trackers = Trackers::default();
+        group.bench_function(BenchmarkId::from_parameter(universes), |b| {
+            b.iter(|| {
+                renderer
+                    .render(
+                        &mut program,
+                        &plugins,
+                        AudioLevels::default(),
+                        &input,
+                        &trackers,
+                        Instant::now(),
+                    )
+                    .frame
+            })
+        });
+    }
+    group.finish();
+}
+
+/// A show of [`LONG_SHOW`] short cues played through frame by frame, each
+/// tick updating every cue against updating through the index.
+fn long_show(c: &mut Criterion) {
+    let fixtures = rig(1);
+    let cues: Vec<Cue> = (0..LONG_SHOW)
+        .map(|n| {
+            let cue = Cue::new(
+                CueNumber::from_whole(n as u32 + 1).unwrap(),
+                "",
+                n as u64 * 3,
+                2,
+            );
+            fixtures.iter().fold(cue, |cue, fixture| {
+                cue.with_value(fixture.id, fixture.profile.channels[0], n as u8)
+            })
+        })
+        .collect();
+    let period = Duration::from_secs_f32(1.0 / OUTPUT_HZ);
+    let length = Duration::from_secs(LONG_SHOW as u64 * 3);
+    let step = |elapsed: &mut Duration| {
+        *elapsed += period;
+        if *elapsed > length {
+            *elapsed = Duration::ZERO;
+        }
+        *elapsed
+    };
+
+    let mut group = c.benchmark_group(format!("{} cues", LONG_SHOW));
+    group.bench_function("update every cue", |b| {
+        let mut every = cues.clone();
+        let mut elapsed = Duration::ZERO;
+        b.iter(|| {
+            let now = step(&mut elapsed);
+            for cue in &mut every {
+                cue.update(now);
+            }
+        })
+    });
+    group.bench_function("update through index", |b| {
+        let mut indexed = cues.clone();
+        let mut index = CueIndex::new(&indexed);
+        let mut elapsed = Duration::ZERO;
+        b.iter(|| {
+            let previous = elapsed;
+            let now = step(&mut elapsed);
+            index.update(&mut indexed, now);
+            (index.fired(previous, now), index.ended(previous, now))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, stages, effects, whole_frame, long_show);
+criterion_main!(benches);
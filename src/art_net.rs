@@ -0,0 +1,600 @@
+//! Art-Net output: every universe in the frame sent as ArtDmx, either
+//! broadcast or unicast to the nodes it is routed to, plus ArtPoll discovery
+//! of the nodes on the network.
+//!
+//! Nodes, gateways and consoles all send to the Art-Net port, which only one
+//! socket can listen on, so network input, node discovery and RDM share it
+//! through an [`ArtNetPort`].
+//!
+//! Universes are numbered from 1 as in the rest of Halo; unless routed
+//! otherwise, universe 1 goes out on Art-Net port-address 0.
+//!
+//! Each node, and the broadcast, can be sent fewer frames than the engine
+//! renders, for budget fixtures and nodes that choke at the full rate. With
+//! **Send only changes** on, a universe only goes out to a destination when
+//! its data has changed since the last one sent there, or to keep the node
+//! alive once [`KEEP_ALIVE`] has passed without.
+
+use crate::dmx::{DmxFrame, OUTPUT_HZ, UNIVERSE_SIZE};
+use crate::interfaces::NetworkInterface;
+use crate::stats::FrameTimer;
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+pub const ART_NET_PORT: u16 = 6454;
+pub const ART_NET_ID: &[u8] = b"Art-Net\0";
+const PROTOCOL_VERSION: [u8; 2] = [0, 14];
+const OP_POLL: u16 = 0x2000;
+const OP_POLL_REPLY: u16 = 0x2100;
+pub const OP_DMX: u16 = 0x5000;
+/// Longest an unchanged universe goes unsent. Art-Net nodes may drop their
+/// output after a few seconds without data, and sACN receivers after 2.5s,
+/// so this stays well inside both.
+pub const KEEP_ALIVE: Duration = Duration::from_secs(1);
+/// Nodes that stop answering polls are dropped after this long.
+const NODE_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often discovery polls while it is running.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Sends one universe to a node on an Art-Net port-address.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UniverseRoute {
+    pub universe: u16,
+    /// 15-bit Art-Net port-address: net, sub-net and universe.
+    pub port_address: u16,
+}
+
+/// A node the output unicasts to.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeTarget {
+    pub name: String,
+    /// IP address, with `:port` if it isn't 6454.
+    pub address: String,
+    pub routes: Vec<UniverseRoute>,
+    /// Frames a second sent to the node, up to the engine's rate.
+    pub rate: f32,
+}
+
+impl Default for NodeTarget {
+    fn default() -> Self {
+        Self {
+            name: "Node".to_string(),
+            address: "2.0.0.1".to_string(),
+            routes: vec![UniverseRoute {
+                universe: 1,
+                port_address: 0,
+            }],
+            rate: OUTPUT_HZ,
+        }
+    }
+}
+
+/// Where the output goes, kept in the preferences since it depends on the
+/// network rather than the show.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArtNetOutput {
+    pub enabled: bool,
+    /// Where broadcast universes and polls go.
+    pub broadcast: String,
+    /// Broadcast the universes not routed to any node.
+    pub broadcast_unrouted: bool,
+    /// Frames a second broadcast, up to the engine's rate.
+    pub broadcast_rate: f32,
+    pub nodes: Vec<NodeTarget>,
+    /// Leave out universes that haven't changed, up to [`KEEP_ALIVE`].
+    pub changes_only: bool,
+}
+
+impl Default for ArtNetOutput {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            broadcast: "255.255.255.255".to_string(),
+            broadcast_unrouted: true,
+            broadcast_rate: OUTPUT_HZ,
+            nodes: Vec::new(),
+            changes_only: true,
+        }
+    }
+}
+
+fn socket_address(address: &str) -> io::Result<SocketAddr> {
+    let address = if address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{}:{}", address.trim(), ART_NET_PORT)
+    };
+    address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::other(format!("no such address {}", address)))
+}
+
+pub(crate) fn art_net_header(opcode: u16) -> Vec<u8> {
+    let mut packet = ART_NET_ID.to_vec();
+    packet.extend_from_slice(&opcode.to_le_bytes());
+    packet.extend_from_slice(&PROTOCOL_VERSION);
+    packet
+}
+
+/// The OpCode of an Art-Net packet, or None if it isn't one.
+pub(crate) fn opcode(packet: &[u8]) -> Option<u16> {
+    if packet.len() < 10 || !packet.starts_with(ART_NET_ID) {
+        return None;
+    }
+    Some(u16::from_le_bytes([packet[8], packet[9]]))
+}
+
+/// A packet received on the Art-Net port and who sent it.
+pub type Received = (SocketAddr, Vec<u8>);
+
+/// Who listens to the Art-Net port: the OpCodes each wants and where to
+/// send them.
+type Listeners = Arc<Mutex<Vec<(Vec<u16>, Sender<Received>)>>>;
+
+/// The Art-Net port, received on by a background thread that hands each
+/// listener the packets with the OpCodes it asked for. It stays open until
+/// the last [`ArtNetListener`] is dropped.
+pub struct ArtNetPort {
+    socket: UdpSocket,
+    listeners: Listeners,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ArtNetPort {
+    pub fn open() -> io::Result<Self> {
+        let socket = UdpSocket::bind(("0.0.0.0", ART_NET_PORT))
+            .map_err(|err| io::Error::other(format!("Art-Net port: {}", err)))?;
+        socket.set_broadcast(true)?;
+        // Wake up periodically so the thread exits once stopped.
+        socket.set_read_timeout(Some(Duration::from_millis(250)))?;
+        let receiver = socket.try_clone()?;
+        let listeners = Listeners::default();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (shared, stop) = (listeners.clone(), shutdown.clone());
+        let thread = thread::Builder::new()
+            .name("halo-art-net".to_string())
+            .spawn(move || {
+                let mut buf = [0u8; 1024];
+                while !stop.load(Ordering::Relaxed) {
+                    let Ok((len, from)) = receiver.recv_from(&mut buf) else {
+                        continue;
+                    };
+                    let Some(op) = opcode(&buf[..len]) else {
+                        continue;
+                    };
+                    let Ok(mut listeners) = shared.lock() else {
+                        break;
+                    };
+                    // Listeners that have gone are dropped as they are found.
+                    listeners.retain(|(opcodes, sender)| {
+                        !opcodes.contains(&op) || sender.send((from, buf[..len].to_vec())).is_ok()
+                    });
+                }
+            })?;
+        Ok(Self {
+            socket,
+            listeners,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// Starts passing on the packets with any of `opcodes`.
+    pub fn listen(self: &Arc<Self>, opcodes: &[u16]) -> ArtNetListener {
+        let (sender, packets) = mpsc::channel();
+        if let Ok(mut listeners) = self.listeners.lock() {
+            listeners.push((opcodes.to_vec(), sender));
+        }
+        ArtNetListener {
+            port: self.clone(),
+            packets,
+        }
+    }
+}
+
+impl Drop for ArtNetPort {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Wait for the socket to close so the port can be bound again.
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The packets one user of the [`ArtNetPort`] listens for, and a way to
+/// send from the port, which nodes answer to.
+pub struct ArtNetListener {
+    port: Arc<ArtNetPort>,
+    packets: Receiver<Received>,
+}
+
+impl ArtNetListener {
+    /// The next packet, or None if none came within `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Received> {
+        self.packets.recv_timeout(timeout).ok()
+    }
+
+    pub fn send_to(&self, packet: &[u8], to: SocketAddr) -> io::Result<()> {
+        self.port.socket.send_to(packet, to).map(|_| ())
+    }
+}
+
+fn art_dmx(sequence: u8, port_address: u16, data: &[u8; UNIVERSE_SIZE]) -> Vec<u8> {
+    let mut packet = art_net_header(OP_DMX);
+    let [net, sub_uni] = port_address.to_be_bytes();
+    packet.extend_from_slice(&[sequence, 0, sub_uni, net & 0x7f]);
+    packet.extend_from_slice(&(UNIVERSE_SIZE as u16).to_be_bytes());
+    packet.extend_from_slice(data);
+    packet
+}
+
+/// Sends frames from the output engine.
+/// When a destination is next due a frame, and how often it gets them.
+#[derive(Clone)]
+struct Timing {
+    rate: f32,
+    due: Instant,
+    sent: FrameTimer,
+}
+
+impl Timing {
+    fn new(rate: f32) -> Self {
+        Self {
+            rate,
+            due: Instant::now(),
+            sent: FrameTimer::new(None),
+        }
+    }
+
+    /// Whether a frame rendered at `now` should go out, counting it if so.
+    /// Frames go up to half an engine tick early, so rates the engine's
+    /// doesn't divide into average out right.
+    fn take(&mut self, now: Instant) -> bool {
+        let period = Duration::from_secs_f32(1.0 / self.rate.clamp(1.0, OUTPUT_HZ));
+        let slack = Duration::from_secs_f32(0.5 / OUTPUT_HZ);
+        if now + slack < self.due {
+            return false;
+        }
+        // After a stall, start afresh rather than catching up.
+        self.due = if now > self.due + period {
+            now + period
+        } else {
+            self.due + period
+        };
+        self.sent.tick(now);
+        true
+    }
+}
+
+/// The rate a destination is meant to get frames at and the rate it does.
+#[derive(Clone)]
+pub struct OutputRate {
+    pub address: SocketAddr,
+    pub target: f32,
+    pub achieved: f32,
+}
+
+pub struct ArtNetSender {
+    socket: UdpSocket,
+    interface: Option<NetworkInterface>,
+    settings: ArtNetOutput,
+    broadcast: Option<SocketAddr>,
+    /// Resolved routes: universe to the nodes and port-addresses it goes to.
+    routes: HashMap<u16, Vec<(SocketAddr, u16)>>,
+    timings: HashMap<SocketAddr, Timing>,
+    sequence: u8,
+    /// What each destination and port-address was last sent, and when.
+    sent: HashMap<(SocketAddr, u16), (Instant, [u8; UNIVERSE_SIZE])>,
+}
+
+impl ArtNetSender {
+    /// Opens a socket on `interface`, or on all interfaces.
+    pub fn open(interface: Option<NetworkInterface>) -> io::Result<Self> {
+        let address = interface
+            .as_ref()
+            .map_or(Ipv4Addr::UNSPECIFIED, |interface| interface.address);
+        let socket = UdpSocket::bind((address, 0))?;
+        socket.set_broadcast(true)?;
+        Ok(Self {
+            socket,
+            interface,
+            settings: ArtNetOutput::default(),
+            broadcast: None,
+            routes: HashMap::new(),
+            timings: HashMap::new(),
+            sequence: 0,
+            sent: HashMap::new(),
+        })
+    }
+
+    pub fn interface(&self) -> Option<&NetworkInterface> {
+        self.interface.as_ref()
+    }
+
+    /// Resolves the addresses in `settings` if they changed. Nodes whose
+    /// address doesn't resolve are left out.
+    fn configure(&mut self, settings: &ArtNetOutput) {
+        if *settings == self.settings {
+            return;
+        }
+        self.settings = settings.clone();
+        self.broadcast = socket_address(&settings.broadcast).ok().map(|mut address| {
+            // The limited broadcast leaves by the default route whatever the
+            // socket is bound to, so keep it on the chosen interface's subnet.
+            if let Some(interface) = &self.interface {
+                if address.ip() == IpAddr::V4(Ipv4Addr::BROADCAST) {
+                    address.set_ip(IpAddr::V4(interface.broadcast()));
+                }
+            }
+            address
+        });
+        self.routes.clear();
+        let mut rates = HashMap::new();
+        if let Some(broadcast) = self.broadcast.filter(|_| settings.broadcast_unrouted) {
+            rates.insert(broadcast, settings.broadcast_rate);
+        }
+        for node in &settings.nodes {
+            let Ok(address) = socket_address(&node.address) else {
+                continue;
+            };
+            rates.insert(address, node.rate);
+            for route in &node.routes {
+                self.routes
+                    .entry(route.universe)
+                    .or_default()
+                    .push((address, route.port_address));
+            }
+        }
+        self.timings
+            .retain(|address, _| rates.contains_key(address));
+        self.sent.clear();
+        for (address, rate) in rates {
+            self.timings
+                .entry(address)
+                .and_modify(|timing| timing.rate = rate)
+                .or_insert_with(|| Timing::new(rate));
+        }
+    }
+
+    /// Sends every universe in `frame` where `settings` routes it, to the
+    /// destinations due a frame at their rate.
+    pub fn send(&mut self, settings: &ArtNetOutput, frame: &DmxFrame) {
+        self.configure(settings);
+        let now = Instant::now();
+        let due: Vec<SocketAddr> = self
+            .timings
+            .iter_mut()
+            .filter_map(|(&address, timing)| timing.take(now).then_some(address))
+            .collect();
+        let changes_only = self.settings.changes_only;
+        self.send_to(frame, now, |address| due.contains(&address), changes_only);
+    }
+
+    /// Sends `frame` to every destination straight away, whatever their
+    /// rates and unchanged universes too, for the last frame before closing
+    /// or an assert.
+    pub fn flush(&mut self, settings: &ArtNetOutput, frame: &DmxFrame) {
+        self.configure(settings);
+        self.send_to(frame, Instant::now(), |_| true, false);
+    }
+
+    /// Sends each universe to the destinations `due` says are due a frame,
+    /// leaving out the unchanged ones if `changes_only`.
+    fn send_to(
+        &mut self,
+        frame: &DmxFrame,
+        now: Instant,
+        due: impl Fn(SocketAddr) -> bool,
+        changes_only: bool,
+    ) {
+        // Sequence 0 means "not sequenced", so count 1 to 255.
+        self.sequence = self.sequence % 255 + 1;
+        for universe in frame.universe_numbers() {
+            let Some(data) = frame.universe(universe) else {
+                continue;
+            };
+            let broadcast;
+            let targets: &[(SocketAddr, u16)] = match self.routes.get(&universe) {
+                Some(routes) => routes,
+                None if self.settings.broadcast_unrouted => {
+                    broadcast = self
+                        .broadcast
+                        .map(|address| (address, universe.saturating_sub(1)));
+                    broadcast.as_slice()
+                }
+                None => &[],
+            };
+            for &(address, port_address) in targets {
+                if !due(address) {
+                    continue;
+                }
+                let last = self.sent.get(&(address, port_address));
+                let unchanged = last.is_some_and(|(at, sent)| {
+                    sent == data && now.saturating_duration_since(*at) < KEEP_ALIVE
+                });
+                if changes_only && unchanged {
+                    continue;
+                }
+                let packet = art_dmx(self.sequence, port_address, data);
+                let _ = self.socket.send_to(&packet, address);
+                self.sent.insert((address, port_address), (now, *data));
+            }
+        }
+    }
+
+    /// How often each destination is getting frames, against its rate.
+    pub fn rates(&self) -> Vec<OutputRate> {
+        let mut rates: Vec<OutputRate> = self
+            .timings
+            .iter()
+            .map(|(&address, timing)| OutputRate {
+                address,
+                target: timing.rate.clamp(1.0, OUTPUT_HZ),
+                achieved: timing.sent.fps(),
+            })
+            .collect();
+        rates.sort_by_key(|rate| rate.address);
+        rates
+    }
+}
+
+/// A node that answered a poll.
+#[derive(Clone)]
+pub struct ArtNode {
+    pub ip: Ipv4Addr,
+    pub short_name: String,
+    pub long_name: String,
+    /// Port-addresses of the node's DMX outputs.
+    pub outputs: Vec<u16>,
+    seen: Instant,
+}
+
+fn name(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+}
+
+/// Reads an ArtPollReply. Nodes with more than four ports send one reply per
+/// group of four, so the same IP can answer more than once.
+fn parse_poll_reply(packet: &[u8]) -> Option<ArtNode> {
+    if packet.len() < 194
+        || !packet.starts_with(ART_NET_ID)
+        || u16::from_le_bytes([packet[8], packet[9]]) != OP_POLL_REPLY
+    {
+        return None;
+    }
+    let ip = Ipv4Addr::new(packet[10], packet[11], packet[12], packet[13]);
+    let net = (packet[18] & 0x7f) as u16;
+    let sub_net = (packet[19] & 0x0f) as u16;
+    let ports = (packet[173] as usize).min(4);
+    let outputs = (0..ports)
+        // Bit 7 of the port type: the port can output DMX.
+        .filter(|&port| packet[174 + port] & 0x80 != 0)
+        .map(|port| (net << 8) | (sub_net << 4) | (packet[190 + port] & 0x0f) as u16)
+        .collect();
+    Some(ArtNode {
+        ip,
+        short_name: name(&packet[26..44]),
+        long_name: name(&packet[44..108]),
+        outputs,
+        seen: Instant::now(),
+    })
+}
+
+/// Polls for Art-Net nodes on a background thread and keeps the list of
+/// those answering, until dropped.
+pub struct ArtNetDiscovery {
+    nodes: Arc<Mutex<Vec<ArtNode>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl ArtNetDiscovery {
+    /// Polls `broadcast` from the Art-Net port, which nodes answer on.
+    pub fn start(broadcast: &str, port: &Arc<ArtNetPort>, ctx: egui::Context) -> io::Result<Self> {
+        let target = socket_address(broadcast)?;
+        let replies = port.listen(&[OP_POLL_REPLY]);
+
+        let nodes = Arc::new(Mutex::new(Vec::new()));
+        let running = Arc::new(AtomicBool::new(true));
+        let (shared, keep_running) = (nodes.clone(), running.clone());
+        thread::Builder::new()
+            .name("halo-art-poll".to_string())
+            .spawn(move || {
+                let mut poll = art_net_header(OP_POLL);
+                poll.extend_from_slice(&[0, 0]);
+                let mut polled: Option<Instant> = None;
+                while keep_running.load(Ordering::Relaxed) {
+                    if polled.is_none_or(|at| at.elapsed() >= POLL_INTERVAL) {
+                        let _ = replies.send_to(&poll, target);
+                        polled = Some(Instant::now());
+                    }
+                    let reply = replies
+                        .recv_timeout(Duration::from_millis(200))
+                        .and_then(|(_, packet)| parse_poll_reply(&packet));
+                    let Ok(mut nodes) = shared.lock() else {
+                        break;
+                    };
+                    if let Some(node) = reply {
+                        merge_node(&mut nodes, node);
+                        ctx.request_repaint();
+                    }
+                    nodes.retain(|node| node.seen.elapsed() < NODE_TIMEOUT);
+                }
+            })?;
+        Ok(Self { nodes, running })
+    }
+
+    pub fn nodes(&self) -> Vec<ArtNode> {
+        self.nodes
+            .lock()
+            .map(|nodes| nodes.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for ArtNetDiscovery {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Adds `node` to the list, or updates the entry for the same IP, keeping
+/// the outputs from each of its replies.
+fn merge_node(nodes: &mut Vec<ArtNode>, node: ArtNode) {
+    match nodes.iter_mut().find(|known| known.ip == node.ip) {
+        Some(known) => {
+            for output in node.outputs {
+                if !known.outputs.contains(&output) {
+                    known.outputs.push(output);
+                }
+            }
+            known.short_name = node.short_name;
+            known.long_name = node.long_name;
+            known.seen = node.seen;
+        }
+        None => nodes.push(node),
+    }
+    nodes.sort_by_key(|node| node.ip);
+}
+
+impl HaloApp {
+    /// Starts or stops polling for Art-Net nodes.
+    pub(crate) fn set_art_net_discovery(&mut self, ctx: &egui::Context, enabled: bool) {
+        self.art_net_discovery = None;
+        self.art_net_error = None;
+        if enabled {
+            let started = self.art_net_port().and_then(|port| {
+                let broadcast = &self.preferences.art_net_output.broadcast;
+                ArtNetDiscovery::start(broadcast, &port, ctx.clone())
+            });
+            match started {
+                Ok(discovery) => self.art_net_discovery = Some(discovery),
+                Err(err) => self.art_net_error = Some(err.to_string()),
+            }
+        }
+    }
+
+    /// The Art-Net port, opened unless something is listening on it already.
+    pub(crate) fn art_net_port(&mut self) -> io::Result<Arc<ArtNetPort>> {
+        if let Some(port) = self.art_net_port.upgrade() {
+            return Ok(port);
+        }
+        let port = Arc::new(ArtNetPort::open()?);
+        self.art_net_port = Arc::downgrade(&port);
+        Ok(port)
+    }
+}
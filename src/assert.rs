@@ -0,0 +1,44 @@
+//! Assert: snaps the output back to what the cue stack has programmed after
+//! busking on top of it. The cues' look is worked out from the playhead
+//! every frame, so asserting is a matter of clearing what sits on top of it —
+//! quick console levels, busk mode and latched pads — and having the output
+//! resent in full, for nodes that only hear about changes.
+
+use crate::i18n::tr;
+use crate::HaloApp;
+
+impl HaloApp {
+    /// Drops every live override so the cues' tracked values are on air
+    /// again, straight away rather than fading, and resends the output.
+    pub(crate) fn assert_cues(&mut self) {
+        let mut dropped = Vec::new();
+        if !self.quick_levels.is_empty() {
+            self.quick_levels.clear();
+            dropped.push(tr!("assert-quick-levels"));
+        }
+        if self.busk_enabled {
+            self.busk_enabled = false;
+            dropped.push(tr!("assert-busk"));
+        }
+        let mut pads = 0;
+        for pad in &mut self.show.pads {
+            if pad.active || pad.released_at.is_some() {
+                pads += usize::from(pad.active);
+                pad.active = false;
+                pad.release_at = None;
+                pad.released_at = None;
+            }
+        }
+        self.held_pad = None;
+        if pads > 0 {
+            dropped.push(tr!("assert-pads"));
+        }
+        self.engine.assert();
+        if dropped.is_empty() {
+            self.log.info(tr!("assert-done"));
+        } else {
+            self.log
+                .info(tr!("assert-done-over", dropped = dropped.join(", ")));
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! Audio analysis for audio-reactive pixel maps. A selected input device is
+//! captured with cpal and analysed on the capture thread: overall level, bass,
+//! mid and high band energy, and a coarse spectrum for display. Results are
+//! published through an [`AudioMeter`] that the UI and the output engine read.
+
+use crate::error::{Device, HaloError};
+use crate::i18n::tr;
+use crate::HaloApp;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Samples per analysis window, a power of two for the FFT.
+const FFT_SIZE: usize = 1024;
+/// New samples between analyses; windows overlap by half.
+const HOP: usize = FFT_SIZE / 2;
+/// Bars in the spectrum display.
+pub const SPECTRUM_BANDS: usize = 32;
+/// Levels are shown on a dB scale from this floor up to full scale.
+const FLOOR_DB: f32 = -60.0;
+/// How much of the previous level is kept per analysis when the sound drops,
+/// so levels fall smoothly instead of flickering.
+const RELEASE: f32 = 0.85;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AudioBand {
+    /// Overall loudness.
+    Level,
+    Bass,
+    Mid,
+    High,
+}
+
+impl AudioBand {
+    pub const ALL: [AudioBand; 4] = [
+        AudioBand::Level,
+        AudioBand::Bass,
+        AudioBand::Mid,
+        AudioBand::High,
+    ];
+
+    /// Frequency range of the band in Hz.
+    fn range(self) -> (f32, f32) {
+        match self {
+            AudioBand::Level => (20.0, 20_000.0),
+            AudioBand::Bass => (20.0, 250.0),
+            AudioBand::Mid => (250.0, 4_000.0),
+            AudioBand::High => (4_000.0, 16_000.0),
+        }
+    }
+}
+
+impl fmt::Display for AudioBand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            AudioBand::Level => tr!("audio-band-level"),
+            AudioBand::Bass => tr!("audio-band-bass"),
+            AudioBand::Mid => tr!("audio-band-mid"),
+            AudioBand::High => tr!("audio-band-high"),
+        })
+    }
+}
+
+/// The latest analysis, with every level from 0 (silence) to 1.
+#[derive(Clone, Copy, Default)]
+pub struct AudioLevels {
+    levels: [f32; 4],
+    /// Running integral of each level over time in seconds, for modulating
+    /// rates without the position jumping when the level changes.
+    integrals: [f32; 4],
+}
+
+impl AudioLevels {
+    pub fn level(&self, band: AudioBand) -> f32 {
+        self.levels[band as usize]
+    }
+
+    pub fn integral(&self, band: AudioBand) -> f32 {
+        self.integrals[band as usize]
+    }
+}
+
+#[derive(Default)]
+struct MeterState {
+    levels: AudioLevels,
+    spectrum: Vec<f32>,
+    /// The last error reported by the capture stream.
+    error: Option<String>,
+}
+
+/// Shared view of the latest analysis. Reads silence while no input is open.
+#[derive(Clone, Default)]
+pub struct AudioMeter(Arc<Mutex<MeterState>>);
+
+impl AudioMeter {
+    pub fn levels(&self) -> AudioLevels {
+        self.0.lock().map(|state| state.levels).unwrap_or_default()
+    }
+
+    /// Spectrum bars from low to high, empty while no input is open.
+    pub fn spectrum(&self) -> Vec<f32> {
+        self.0
+            .lock()
+            .map(|state| state.spectrum.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.0.lock().ok().and_then(|state| state.error.clone())
+    }
+
+    fn clear(&self) {
+        if let Ok(mut state) = self.0.lock() {
+            *state = MeterState::default();
+        }
+    }
+}
+
+pub fn input_devices() -> Vec<String> {
+    let Ok(devices) = cpal::default_host().input_devices() else {
+        return Vec::new();
+    };
+    devices.filter_map(|device| device.name().ok()).collect()
+}
+
+/// An open input device being analysed into an [`AudioMeter`]. Capture stops
+/// and the meter falls silent when this is dropped.
+pub struct AudioInput {
+    _stream: cpal::Stream,
+    meter: AudioMeter,
+}
+
+impl AudioInput {
+    pub fn start(device_name: &str, meter: AudioMeter) -> Result<Self, String> {
+        let host = cpal::default_host();
+        let device = host
+            .input_devices()
+            .map_err(|err| err.to_string())?
+            .find(|device| device.name().is_ok_and(|name| name == device_name))
+            .ok_or_else(|| tr!("audio-input-missing", device = device_name))?;
+        let supported = device
+            .default_input_config()
+            .map_err(|err| err.to_string())?;
+        let config = supported.config();
+        let stream = match supported.sample_format() {
+            cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config, meter.clone()),
+            cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config, meter.clone()),
+            cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config, meter.clone()),
+            cpal::SampleFormat::I32 => build_stream::<i32>(&device, &config, meter.clone()),
+            format => return Err(tr!("audio-unsupported-format", format = format.to_string())),
+        }?;
+        stream.play().map_err(|err| err.to_string())?;
+        Ok(Self {
+            _stream: stream,
+            meter,
+        })
+    }
+}
+
+impl Drop for AudioInput {
+    fn drop(&mut self) {
+        self.meter.clear();
+    }
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    meter: AudioMeter,
+) -> Result<cpal::Stream, String>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = config.channels.max(1) as usize;
+    let mut analyser = Analyser::new(config.sample_rate.0 as f32, meter.clone());
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _| {
+                for frame in data.chunks(channels) {
+                    let sum: f32 = frame.iter().map(|&s| s.to_sample::<f32>()).sum();
+                    analyser.push(sum / channels as f32);
+                }
+            },
+            move |err| {
+                if let Ok(mut state) = meter.0.lock() {
+                    state.error = Some(err.to_string());
+                }
+            },
+            None,
+        )
+        .map_err(|err| err.to_string())
+}
+
+/// Runs on the capture thread, collecting mono samples and analysing every
+/// [`HOP`] of them.
+struct Analyser {
+    sample_rate: f32,
+    meter: AudioMeter,
+    window: Vec<f32>,
+    /// Ring buffer of the last [`FFT_SIZE`] samples, oldest at `write`.
+    samples: Vec<f32>,
+    write: usize,
+    pending: usize,
+    levels: AudioLevels,
+    spectrum: Vec<f32>,
+}
+
+impl Analyser {
+    fn new(sample_rate: f32, meter: AudioMeter) -> Self {
+        let window = (0..FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (TAU * i as f32 / FFT_SIZE as f32).cos())
+            .collect();
+        Self {
+            sample_rate,
+            meter,
+            window,
+            samples: vec![0.0; FFT_SIZE],
+            write: 0,
+            pending: 0,
+            levels: AudioLevels::default(),
+            spectrum: vec![0.0; SPECTRUM_BANDS],
+        }
+    }
+
+    fn push(&mut self, sample: f32) {
+        self.samples[self.write] = sample;
+        self.write = (self.write + 1) % FFT_SIZE;
+        self.pending += 1;
+        if self.pending >= HOP {
+            self.pending = 0;
+            self.analyse();
+        }
+    }
+
+    fn analyse(&mut self) {
+        let mut re: Vec<f32> = (0..FFT_SIZE)
+            .map(|i| self.samples[(self.write + i) % FFT_SIZE] * self.window[i])
+            .collect();
+        let mut im = vec![0.0; FFT_SIZE];
+        fft(&mut re, &mut im);
+
+        // Power of each bin, scaled so a full-scale sine reads as 1.
+        let scale = 2.0 / self.window.iter().sum::<f32>();
+        let power: Vec<f32> = (0..FFT_SIZE / 2)
+            .map(|k| (re[k] * re[k] + im[k] * im[k]) * scale * scale)
+            .collect();
+        let bin_hz = self.sample_rate / FFT_SIZE as f32;
+        let band_level = |low: f32, high: f32| {
+            let first = ((low / bin_hz) as usize).max(1);
+            let last = ((high / bin_hz) as usize).clamp(first + 1, power.len());
+            normalize(power[first..last].iter().sum::<f32>().sqrt())
+        };
+
+        let dt = HOP as f32 / self.sample_rate;
+        for band in AudioBand::ALL {
+            let (low, high) = band.range();
+            let level = band_level(low, high);
+            let i = band as usize;
+            self.levels.levels[i] = level.max(self.levels.levels[i] * RELEASE);
+            self.levels.integrals[i] += self.levels.levels[i] * dt;
+        }
+
+        // Log-spaced bars from 20 Hz up to 16 kHz or Nyquist.
+        let top = (self.sample_rate / 2.0).min(16_000.0);
+        let ratio = (top / 20.0).powf(1.0 / SPECTRUM_BANDS as f32);
+        for (i, bar) in self.spectrum.iter_mut().enumerate() {
+            let low = 20.0 * ratio.powi(i as i32);
+            let level = band_level(low, low * ratio);
+            *bar = level.max(*bar * RELEASE);
+        }
+
+        if let Ok(mut state) = self.meter.0.lock() {
+            state.levels = self.levels;
+            state.spectrum.clone_from(&self.spectrum);
+        }
+    }
+}
+
+/// Maps an amplitude onto 0..1 over the dB range shown.
+pub(crate) fn normalize(amplitude: f32) -> f32 {
+    let db = 20.0 * amplitude.max(1e-9).log10();
+    ((db - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0)
+}
+
+/// In-place radix-2 FFT; the length must be a power of two.
+pub(crate) fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -TAU / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * cos - im[b] * sin;
+                let t_im = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+impl HaloApp {
+    /// Opens or closes the audio input to match the current preferences.
+    pub(crate) fn apply_audio_preferences(&mut self) {
+        self.audio_input = None;
+        self.audio_error = None;
+        if let Some(device) = self.preferences.audio_input.clone() {
+            match AudioInput::start(&device, self.audio_meter.clone()) {
+                Ok(input) => self.audio_input = Some(input),
+                Err(err) => {
+                    self.report_error(HaloError::device(Device::AudioInput, device, &err));
+                    self.audio_error = Some(err);
+                }
+            }
+        }
+    }
+}
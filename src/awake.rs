@@ -0,0 +1,320 @@
+//! Keeping the computer awake through a show: while a show is loaded and
+//! the output is on, Halo stops the display sleeping, the screensaver coming
+//! on and, on a Mac, App Nap slowing it down behind other windows. After a
+//! while with nobody at the computer the window can dim to a clock instead,
+//! which the engine carries on underneath; see `ui::awake`.
+//!
+//! Each platform does it its own way: a power assertion on macOS, the thread
+//! execution state on Windows, and on Linux an inhibitor held by a
+//! `gnome-session-inhibit` or `systemd-inhibit` child that lives as long as
+//! Halo does.
+
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Why the display is being kept on, as the system lists it.
+const REASON: &str = "Halo is running a show";
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AwakeSettings {
+    /// Keep the display on while a show is loaded and the output is on.
+    pub keep_awake: bool,
+    /// Dim the window to a clock after `dim_after` without input.
+    pub dim: bool,
+    pub dim_after: Duration,
+}
+
+impl Default for AwakeSettings {
+    fn default() -> Self {
+        Self {
+            keep_awake: true,
+            dim: false,
+            dim_after: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+/// Holds the display on until dropped.
+pub struct SleepGuard {
+    #[cfg(target_os = "linux")]
+    child: std::process::Child,
+    #[cfg(target_os = "macos")]
+    assertion: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl SleepGuard {
+    /// Starts the first inhibitor the desktop has. Each runs `cat`, which
+    /// reads from Halo until Halo's end closes, so the inhibitor goes with
+    /// Halo even if it crashes.
+    pub fn hold() -> io::Result<Self> {
+        use std::process::{Command, Stdio};
+        let why = format!("--why={}", REASON);
+        let inhibitors: [(&str, Vec<&str>); 2] = [
+            (
+                "gnome-session-inhibit",
+                vec!["--inhibit", "idle:suspend", "--reason", REASON, "cat"],
+            ),
+            (
+                "systemd-inhibit",
+                vec!["--what=idle:sleep", "--who=Halo", &why, "cat"],
+            ),
+        ];
+        let mut error = io::Error::new(io::ErrorKind::NotFound, "no inhibitor found");
+        for (program, args) in inhibitors {
+            let spawned = Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            let mut child = match spawned {
+                Ok(child) => child,
+                Err(err) => {
+                    error =
+                        io::Error::new(err.kind(), format!("could not run {}: {}", program, err));
+                    continue;
+                }
+            };
+            // One that can't reach its session manager gives up at once.
+            std::thread::sleep(Duration::from_millis(150));
+            match child.try_wait()? {
+                None => return Ok(Self { child }),
+                Some(status) => {
+                    error = io::Error::other(format!("{} stopped: {}", program, status));
+                }
+            }
+        }
+        Err(error)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for SleepGuard {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod iokit {
+    use std::ffi::{c_char, c_void};
+
+    pub type CFStringRef = *const c_void;
+    pub const UTF8: u32 = 0x0800_0100;
+    /// kIOPMAssertionLevelOn.
+    pub const LEVEL_ON: u32 = 255;
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        pub fn CFStringCreateWithCString(
+            allocator: *const c_void,
+            string: *const c_char,
+            encoding: u32,
+        ) -> CFStringRef;
+        pub fn CFRelease(object: *const c_void);
+    }
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        pub fn IOPMAssertionCreateWithName(
+            kind: CFStringRef,
+            level: u32,
+            name: CFStringRef,
+            id: *mut u32,
+        ) -> i32;
+        pub fn IOPMAssertionRelease(id: u32) -> i32;
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl SleepGuard {
+    /// Takes a power assertion against display sleep. An app holding one is
+    /// also left out of App Nap.
+    pub fn hold() -> io::Result<Self> {
+        use std::ffi::CString;
+        let kind = CString::new("PreventUserIdleDisplaySleep").expect("no nul");
+        let name = CString::new(REASON).expect("no nul");
+        let mut assertion = 0;
+        // SAFETY: the strings are valid C strings for the calls, and the
+        // CFStrings made from them are released once the assertion has
+        // been created.
+        let result = unsafe {
+            let kind =
+                iokit::CFStringCreateWithCString(std::ptr::null(), kind.as_ptr(), iokit::UTF8);
+            let name =
+                iokit::CFStringCreateWithCString(std::ptr::null(), name.as_ptr(), iokit::UTF8);
+            let result =
+                iokit::IOPMAssertionCreateWithName(kind, iokit::LEVEL_ON, name, &mut assertion);
+            iokit::CFRelease(kind);
+            iokit::CFRelease(name);
+            result
+        };
+        if result != 0 {
+            return Err(io::Error::other(format!(
+                "the power assertion failed ({:#x})",
+                result
+            )));
+        }
+        Ok(Self { assertion })
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl Drop for SleepGuard {
+    fn drop(&mut self) {
+        // SAFETY: the assertion was created by `hold` and is released once.
+        unsafe {
+            iokit::IOPMAssertionRelease(self.assertion);
+        }
+    }
+}
+
+#[cfg(windows)]
+mod execution_state {
+    pub const CONTINUOUS: u32 = 0x8000_0000;
+    pub const SYSTEM_REQUIRED: u32 = 0x0000_0001;
+    pub const DISPLAY_REQUIRED: u32 = 0x0000_0002;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn SetThreadExecutionState(flags: u32) -> u32;
+    }
+}
+
+#[cfg(windows)]
+impl SleepGuard {
+    /// Sets the UI thread's execution state, which lasts until it is set
+    /// again.
+    pub fn hold() -> io::Result<Self> {
+        use execution_state::*;
+        // SAFETY: takes and returns plain flags.
+        let previous =
+            unsafe { SetThreadExecutionState(CONTINUOUS | SYSTEM_REQUIRED | DISPLAY_REQUIRED) };
+        if previous == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {})
+    }
+}
+
+#[cfg(windows)]
+impl Drop for SleepGuard {
+    fn drop(&mut self) {
+        // SAFETY: as in `hold`.
+        unsafe {
+            execution_state::SetThreadExecutionState(execution_state::CONTINUOUS);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+impl SleepGuard {
+    pub fn hold() -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "not on this platform",
+        ))
+    }
+}
+
+/// The guard while it's held, and when anyone last touched the computer.
+pub struct Awake {
+    guard: Option<SleepGuard>,
+    /// Taking the guard failed, so it isn't tried again every frame.
+    failed: bool,
+    last_input: Instant,
+    /// The window is dimmed this frame. It stays dimmed for the frame that
+    /// wakes it, so the touch that does isn't taken by what's under it.
+    pub dimmed: bool,
+}
+
+impl Default for Awake {
+    fn default() -> Self {
+        Self {
+            guard: None,
+            failed: false,
+            last_input: Instant::now(),
+            dimmed: false,
+        }
+    }
+}
+
+impl Awake {
+    pub fn is_holding(&self) -> bool {
+        self.guard.is_some()
+    }
+}
+
+impl HaloApp {
+    /// Whether there's a show with a patch and anything to send it on. A
+    /// backup standing by for its main counts, since it may have to take
+    /// over.
+    fn show_is_live(&self) -> bool {
+        let preferences = &self.preferences;
+        !self.show.fixtures.is_empty()
+            && (preferences.art_net_output.enabled
+                || preferences.sacn_output.enabled
+                || preferences
+                    .plugin_outputs
+                    .iter()
+                    .any(|output| output.enabled)
+                || self
+                    .show
+                    .fixtures
+                    .iter()
+                    .any(|fixture| fixture.driver.is_some()))
+    }
+
+    /// Takes or lets go of the sleep guard as the show goes live or stops
+    /// being, and dims or wakes the window. Called before the shortcuts, so
+    /// the key that wakes the window does nothing else, bar panic.
+    pub(crate) fn update_awake(&mut self, ctx: &egui::Context) {
+        let settings = &self.preferences.awake;
+        let idle = self.awake.last_input.elapsed();
+        self.awake.dimmed = settings.dim && idle >= settings.dim_after;
+        if ctx.input(|i| !i.events.is_empty() || i.pointer.any_down()) {
+            self.awake.last_input = Instant::now();
+            if self.awake.dimmed {
+                ctx.input_mut(|i| {
+                    i.events.retain(|event| match event {
+                        egui::Event::Key { key, .. } => *key == egui::Key::F12,
+                        egui::Event::Text(_) => false,
+                        _ => true,
+                    })
+                });
+                ctx.request_repaint();
+            }
+        } else if settings.dim && !self.awake.dimmed {
+            ctx.request_repaint_after(settings.dim_after - idle);
+        }
+
+        let wanted = self.preferences.awake.keep_awake && self.show_is_live();
+        if !wanted {
+            if self.awake.guard.take().is_some() {
+                self.log.info(tr!("awake-letting-sleep"));
+            }
+            self.awake.failed = false;
+            return;
+        }
+        if self.awake.guard.is_some() || self.awake.failed {
+            return;
+        }
+        match SleepGuard::hold() {
+            Ok(guard) => {
+                self.awake.guard = Some(guard);
+                self.log.info(tr!("awake-keeping"));
+            }
+            Err(err) => {
+                self.awake.failed = true;
+                self.log.warn(tr!("awake-failed", error = err.to_string()));
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Back: undoes a GO by returning to the cue before the current one. The
+//! cues' look is worked out from the playhead, so going back is a locate;
+//! the back fade crossfades from what was on air to that look rather than
+//! snapping, so a GO pressed too early can be taken back in front of an
+//! audience.
+
+use crate::dmx::{DmxFrame, UNIVERSE_SIZE};
+use crate::i18n::tr;
+use crate::HaloApp;
+use std::time::{Duration, Instant};
+
+/// A crossfade from the frame on air when Back was pressed to whatever the
+/// output renders, as the engine plays it.
+pub struct BackFade {
+    from: DmxFrame,
+    fade: Duration,
+    started: Instant,
+}
+
+impl BackFade {
+    pub fn new(from: DmxFrame, fade: Duration, started: Instant) -> Self {
+        Self {
+            from,
+            fade,
+            started,
+        }
+    }
+
+    /// Blends `frame` in from the frame on air at the start. Returns false
+    /// once the fade is over and `frame` is left alone.
+    pub fn apply(&self, frame: &mut DmxFrame, now: Instant) -> bool {
+        let t = now.saturating_duration_since(self.started).as_secs_f32()
+            / self.fade.as_secs_f32().max(f32::EPSILON);
+        if t >= 1.0 {
+            return false;
+        }
+        let mut universes: Vec<u16> = self
+            .from
+            .universe_numbers()
+            .chain(frame.universe_numbers())
+            .collect();
+        universes.sort_unstable();
+        universes.dedup();
+        for universe in universes {
+            for channel in 1..=UNIVERSE_SIZE as u16 {
+                let start = self.from.get(universe, channel) as f32;
+                let end = frame.get(universe, channel) as f32;
+                frame.set(universe, channel, (start + (end - start) * t).round() as u8);
+            }
+        }
+        true
+    }
+}
+
+impl HaloApp {
+    /// Goes back to the cue before the current one, fading to it over the
+    /// show's back fade.
+    pub(crate) fn back(&mut self) {
+        let Some(index) = self.previous_cue() else {
+            self.log.warn(tr!("back-no-previous"));
+            return;
+        };
+        self.go_to_cue(index);
+        if !self.show.back_fade.is_zero() {
+            self.engine.back_fade(self.show.back_fade);
+        }
+        self.log.info(tr!(
+            "back-to-cue",
+            number = self.show.cues[index].number.to_string()
+        ));
+    }
+}
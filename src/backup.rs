@@ -0,0 +1,68 @@
+//! Versioned backups of the show file. Every save copies the file just written
+//! into a hidden folder beside it, stamped with the time, and the oldest
+//! copies are pruned so only the last few saves are kept.
+
+use std::cmp::Reverse;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S-%3f";
+
+/// One saved copy of the show.
+pub struct Backup {
+    pub path: PathBuf,
+    pub saved_at: chrono::DateTime<chrono::Local>,
+    /// File size in bytes.
+    pub size: u64,
+}
+
+/// Where the backups of `show` live: `.<file name>.backups` in the same folder.
+fn directory(show: &Path) -> Option<PathBuf> {
+    let name = show.file_name()?.to_string_lossy();
+    Some(show.with_file_name(format!(".{}.backups", name)))
+}
+
+/// Copies the freshly saved `show` into its backup folder, then removes all
+/// but the newest `keep` copies.
+pub fn create(show: &Path, keep: usize) -> io::Result<()> {
+    let dir = directory(show).ok_or_else(|| io::Error::other("show path has no file name"))?;
+    fs::create_dir_all(&dir)?;
+    let stamp = chrono::Local::now().format(STAMP_FORMAT);
+    let name = match show.extension() {
+        Some(ext) => format!("{}.{}", stamp, ext.to_string_lossy()),
+        None => stamp.to_string(),
+    };
+    fs::copy(show, dir.join(name))?;
+
+    for old in list(show).into_iter().skip(keep) {
+        fs::remove_file(old.path)?;
+    }
+    Ok(())
+}
+
+/// The backups of `show`, newest first. Files that don't look like backups
+/// are ignored.
+pub fn list(show: &Path) -> Vec<Backup> {
+    let Some(entries) = directory(show).and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let path = entry.path();
+            let stem = path.file_stem()?.to_string_lossy();
+            let saved_at = chrono::NaiveDateTime::parse_from_str(&stem, STAMP_FORMAT)
+                .ok()?
+                .and_local_timezone(chrono::Local)
+                .earliest()?;
+            Some(Backup {
+                size: entry.metadata().ok()?.len(),
+                path,
+                saved_at,
+            })
+        })
+        .collect();
+    backups.sort_by_key(|backup| Reverse(backup.saved_at));
+    backups
+}
@@ -0,0 +1,45 @@
+//! Beat cues, for bands who won't play to a click or timecode: a cue can
+//! fire at a bar and beat of the musical timeline instead of at a time. The
+//! timeline is the beat count Halo keeps from its own tempo or from incoming
+//! MIDI clock, bar 1 beat 1 being the first beat after a reset or an
+//! external Start. When the beat comes round the playhead jumps to the cue,
+//! so the show follows the band however the tempo drifts.
+
+use crate::i18n::tr;
+use crate::HaloApp;
+
+impl HaloApp {
+    /// Counts beats at Halo's tempo, unless MIDI clock is counting them, and
+    /// fires the cues set to the beat just reached, once each time it is
+    /// reached, while playing.
+    pub(crate) fn update_beat_cues(&mut self) {
+        if !self.transport.is_running() {
+            return;
+        }
+        if !self.follows_midi_clock() {
+            self.beat_indicator.update(self.bpm);
+        }
+        let beat = self.beat_indicator.beat;
+        if self.fired_beat == Some(beat) {
+            return;
+        }
+        self.fired_beat = Some(beat);
+        let position = self.show.time_signature.position(beat);
+        let due: Vec<usize> = self
+            .show
+            .cues
+            .iter()
+            .enumerate()
+            .filter(|(_, cue)| cue.at_beat == Some(position) && self.show.plays(cue))
+            .map(|(i, _)| i)
+            .collect();
+        for index in due {
+            self.log.info(tr!(
+                "beat-cue-fired",
+                position = position.to_string(),
+                number = self.show.cues[index].number.to_string()
+            ));
+            self.go_to_cue(index);
+        }
+    }
+}
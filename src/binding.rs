@@ -0,0 +1,445 @@
+//! Inbound trigger bindings: an incoming MIDI note, controller or program
+//! change, OSC address or HTTP trigger name mapped to an operator action, so
+//! other systems in the show can drive Halo.
+
+use crate::cue::CueNumber;
+use crate::i18n::tr;
+use crate::interfaces::Protocol;
+use crate::macros::MacroAction;
+use crate::midi_monitor::Direction;
+use crate::osc::{OscListener, OscMessage};
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::SocketAddr;
+
+/// How many received messages the bindings window lists.
+const MAX_RECEIVED: usize = 20;
+
+/// Something received that a binding can match. MIDI channels are 1 to 16.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    MidiNote {
+        channel: u8,
+        note: u8,
+    },
+    MidiControl {
+        channel: u8,
+        controller: u8,
+    },
+    MidiProgram {
+        channel: u8,
+        program: u8,
+    },
+    Osc(String),
+    Http(String),
+    /// A key on a USB button or footswitch, by its input event code.
+    HidKey(u16),
+}
+
+impl fmt::Display for InputEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputEvent::MidiNote { channel, note } => {
+                f.write_str(&tr!("input-note", note = *note, channel = *channel))
+            }
+            InputEvent::MidiControl {
+                channel,
+                controller,
+            } => f.write_str(&tr!(
+                "input-control",
+                controller = *controller,
+                channel = *channel
+            )),
+            InputEvent::MidiProgram { channel, program } => f.write_str(&tr!(
+                "input-program",
+                program = *program,
+                channel = *channel
+            )),
+            InputEvent::Osc(address) => write!(f, "OSC {}", address),
+            InputEvent::Http(name) => write!(f, "HTTP {}", name),
+            InputEvent::HidKey(key) => f.write_str(&tr!("input-usb-key", key = *key)),
+        }
+    }
+}
+
+/// What a binding listens for. A MIDI channel of 0 matches any channel.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BindingSource {
+    MidiNote {
+        channel: u8,
+        note: u8,
+    },
+    MidiControl {
+        channel: u8,
+        controller: u8,
+    },
+    MidiProgram {
+        channel: u8,
+        program: u8,
+    },
+    Osc {
+        address: String,
+    },
+    /// Fired by `/api/trigger/<name>` or the `trigger` remote command.
+    Http {
+        name: String,
+    },
+    /// A key on the USB button device in the preferences.
+    HidKey {
+        key: u16,
+    },
+}
+
+impl BindingSource {
+    /// One of each kind, with defaults, for picking a kind in the editor.
+    pub fn kinds() -> [BindingSource; 6] {
+        [
+            BindingSource::MidiNote {
+                channel: 0,
+                note: 60,
+            },
+            BindingSource::MidiControl {
+                channel: 0,
+                controller: 20,
+            },
+            BindingSource::MidiProgram {
+                channel: 0,
+                program: 0,
+            },
+            BindingSource::Osc {
+                address: "/halo/go".to_string(),
+            },
+            BindingSource::Http {
+                name: "go".to_string(),
+            },
+            BindingSource::HidKey { key: 0 },
+        ]
+    }
+
+    pub fn kind(&self) -> String {
+        match self {
+            BindingSource::MidiNote { .. } => tr!("source-midi-note"),
+            BindingSource::MidiControl { .. } => tr!("source-midi-cc"),
+            BindingSource::MidiProgram { .. } => tr!("source-midi-program"),
+            BindingSource::Osc { .. } => "OSC".to_string(),
+            BindingSource::Http { .. } => "HTTP".to_string(),
+            BindingSource::HidKey { .. } => tr!("source-usb-button"),
+        }
+    }
+
+    pub fn matches(&self, event: &InputEvent) -> bool {
+        let channel_matches = |bound: u8, received: u8| bound == 0 || bound == received;
+        match (self, event) {
+            (
+                BindingSource::MidiNote { channel, note },
+                InputEvent::MidiNote {
+                    channel: received,
+                    note: n,
+                },
+            ) => note == n && channel_matches(*channel, *received),
+            (
+                BindingSource::MidiControl {
+                    channel,
+                    controller,
+                },
+                InputEvent::MidiControl {
+                    channel: received,
+                    controller: c,
+                },
+            ) => controller == c && channel_matches(*channel, *received),
+            (
+                BindingSource::MidiProgram { channel, program },
+                InputEvent::MidiProgram {
+                    channel: received,
+                    program: p,
+                },
+            ) => program == p && channel_matches(*channel, *received),
+            (BindingSource::Osc { address }, InputEvent::Osc(received)) => address == received,
+            (BindingSource::Http { name }, InputEvent::Http(received)) => name == received,
+            (BindingSource::HidKey { key }, InputEvent::HidKey(received)) => key == received,
+            _ => false,
+        }
+    }
+
+    /// Whether some message would match both sources.
+    fn overlaps(&self, other: &BindingSource) -> bool {
+        let channels_overlap = |a: u8, b: u8| a == 0 || b == 0 || a == b;
+        match (self, other) {
+            (
+                BindingSource::MidiNote { channel, note },
+                BindingSource::MidiNote {
+                    channel: c,
+                    note: n,
+                },
+            )
+            | (
+                BindingSource::MidiControl {
+                    channel,
+                    controller: note,
+                },
+                BindingSource::MidiControl {
+                    channel: c,
+                    controller: n,
+                },
+            )
+            | (
+                BindingSource::MidiProgram {
+                    channel,
+                    program: note,
+                },
+                BindingSource::MidiProgram {
+                    channel: c,
+                    program: n,
+                },
+            ) => note == n && channels_overlap(*channel, *c),
+            (BindingSource::Osc { address }, BindingSource::Osc { address: a }) => address == a,
+            (BindingSource::Http { name }, BindingSource::Http { name: n }) => name == n,
+            (BindingSource::HidKey { key }, BindingSource::HidKey { key: k }) => key == k,
+            _ => false,
+        }
+    }
+
+    /// The source that matches exactly `event`, for learning a binding.
+    pub fn learned(event: &InputEvent) -> Self {
+        match event.clone() {
+            InputEvent::MidiNote { channel, note } => BindingSource::MidiNote { channel, note },
+            InputEvent::MidiControl {
+                channel,
+                controller,
+            } => BindingSource::MidiControl {
+                channel,
+                controller,
+            },
+            InputEvent::MidiProgram { channel, program } => {
+                BindingSource::MidiProgram { channel, program }
+            }
+            InputEvent::Osc(address) => BindingSource::Osc { address },
+            InputEvent::Http(name) => BindingSource::Http { name },
+            InputEvent::HidKey(key) => BindingSource::HidKey { key },
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum BindingAction {
+    FireCue(CueNumber),
+    /// Jumps to the next cue.
+    Go,
+    /// Fades back to the cue before the current one; see `back`.
+    Back,
+    /// Switches the pad with this label on if it is off, and off if it is on.
+    TogglePad(String),
+    Start,
+    Stop,
+    Pause,
+    Reset,
+    NextSong,
+    PreviousSong,
+    /// Selects the setlist song at this position, from 1.
+    GoToSong(usize),
+    /// Shows the pad bank at this position, from 1.
+    PadBank(usize),
+    /// Drops the overrides on top of the cues.
+    Assert,
+    RunMacro(String),
+    /// Records the live output as a new cue; see `snapshot`.
+    RecordLook,
+}
+
+impl BindingAction {
+    pub fn kind(&self) -> String {
+        match self {
+            BindingAction::FireCue(_) => tr!("action-go-cue"),
+            BindingAction::Go => tr!("transport-go"),
+            BindingAction::Back => tr!("transport-back"),
+            BindingAction::TogglePad(_) => tr!("action-toggle-pad"),
+            BindingAction::Start => tr!("transport-start"),
+            BindingAction::Stop => tr!("transport-stop"),
+            BindingAction::Pause => tr!("transport-pause"),
+            BindingAction::Reset => tr!("transport-reset"),
+            BindingAction::NextSong => tr!("action-next-song"),
+            BindingAction::PreviousSong => tr!("action-previous-song"),
+            BindingAction::GoToSong(_) => tr!("action-go-to-song"),
+            BindingAction::PadBank(_) => tr!("action-pad-bank"),
+            BindingAction::Assert => tr!("transport-assert"),
+            BindingAction::RunMacro(_) => tr!("action-run-macro"),
+            BindingAction::RecordLook => tr!("action-record-look"),
+        }
+    }
+}
+
+impl fmt::Display for BindingAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingAction::FireCue(number) => write!(f, "{} {}", self.kind(), number),
+            BindingAction::TogglePad(pad) => write!(f, "{} {}", self.kind(), pad),
+            BindingAction::GoToSong(number) | BindingAction::PadBank(number) => {
+                write!(f, "{} {}", self.kind(), number)
+            }
+            BindingAction::RunMacro(name) => write!(f, "{} {}", self.kind(), name),
+            _ => f.write_str(&self.kind()),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Binding {
+    pub source: BindingSource,
+    pub action: BindingAction,
+}
+
+impl Default for Binding {
+    fn default() -> Self {
+        Self {
+            source: BindingSource::kinds()[0].clone(),
+            action: BindingAction::Start,
+        }
+    }
+}
+
+/// A message received and what it did, for the bindings window.
+pub struct ReceivedInput {
+    pub time: chrono::DateTime<chrono::Local>,
+    pub event: InputEvent,
+    /// What the message triggered, or None if nothing used it.
+    pub outcome: Option<String>,
+}
+
+/// For each binding, the positions of the other bindings that a message
+/// could trigger at the same time.
+pub fn conflicts(bindings: &[Binding]) -> Vec<Vec<usize>> {
+    bindings
+        .iter()
+        .enumerate()
+        .map(|(i, binding)| {
+            bindings
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| j != i && binding.source.overlaps(&other.source))
+                .map(|(j, _)| j)
+                .collect()
+        })
+        .collect()
+}
+
+impl HaloApp {
+    /// Runs the actions bound to `event`, or fills in the source of the
+    /// binding being learned. Returns whether anything used the event.
+    pub(crate) fn handle_input_event(&mut self, event: InputEvent) -> bool {
+        if let Some(index) = self.learn_binding.take() {
+            if let Some(binding) = self.show.bindings.get_mut(index) {
+                binding.source = BindingSource::learned(&event);
+                self.log
+                    .info(tr!("binding-learned-event", event = event.to_string()));
+                self.record_input(event, Some(tr!("binding-learned")));
+                return true;
+            }
+        }
+        let actions: Vec<BindingAction> = self
+            .show
+            .bindings
+            .iter()
+            .filter(|binding| binding.source.matches(&event))
+            .map(|binding| binding.action.clone())
+            .collect();
+        let outcome = (!actions.is_empty()).then(|| {
+            actions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+        self.record_input(event.clone(), outcome);
+        for action in &actions {
+            self.log.info(format!("{}: {}", event, action));
+            self.run_binding_action(action.clone());
+        }
+        !actions.is_empty()
+    }
+
+    fn record_input(&mut self, event: InputEvent, outcome: Option<String>) {
+        if self.received_inputs.len() == MAX_RECEIVED {
+            self.received_inputs.pop_front();
+        }
+        self.received_inputs.push_back(ReceivedInput {
+            time: chrono::Local::now(),
+            event,
+            outcome,
+        });
+    }
+
+    /// Records what the last message received did when something other
+    /// than a binding used it.
+    pub(crate) fn set_input_outcome(&mut self, outcome: String) {
+        if let Some(received) = self.received_inputs.back_mut() {
+            received.outcome = Some(outcome);
+        }
+    }
+
+    pub(crate) fn run_binding_action(&mut self, action: BindingAction) {
+        let action = match action {
+            BindingAction::FireCue(number) => MacroAction::FireCue(number),
+            BindingAction::Go => MacroAction::Go,
+            BindingAction::Back => MacroAction::Back,
+            BindingAction::TogglePad(label) => {
+                let Some(pad) = self.show.pads.iter().find(|pad| pad.label == label) else {
+                    self.log.warn(tr!("binding-no-pad", pad = label.as_str()));
+                    return;
+                };
+                MacroAction::SetPad {
+                    active: !pad.active,
+                    pad: label,
+                }
+            }
+            BindingAction::Start => MacroAction::Start,
+            BindingAction::Stop => MacroAction::Stop,
+            BindingAction::Pause => MacroAction::Pause,
+            BindingAction::Reset => MacroAction::Reset,
+            BindingAction::NextSong => MacroAction::NextSong,
+            BindingAction::PreviousSong => MacroAction::PreviousSong,
+            BindingAction::GoToSong(number) => MacroAction::GoToSong(number),
+            BindingAction::PadBank(number) => MacroAction::PadBank(number),
+            BindingAction::Assert => MacroAction::Assert,
+            BindingAction::RunMacro(name) => MacroAction::RunMacro(name),
+            BindingAction::RecordLook => {
+                self.record_look();
+                return;
+            }
+        };
+        self.perform(action);
+    }
+
+    /// Starts or stops the OSC listener to match the current preferences.
+    pub(crate) fn apply_osc_preferences(&mut self, ctx: &egui::Context) {
+        // Drop the old listener first so its port is free to rebind.
+        self.osc_input = None;
+        self.osc_error = None;
+        if !self.preferences.osc_input_enabled {
+            return;
+        }
+        let port = self.preferences.osc_input_port;
+        let address = self.bind_address(Protocol::Osc);
+        match OscListener::start(address, port, self.osc_sender.clone(), ctx.clone()) {
+            Ok(listener) => self.osc_input = Some(listener),
+            Err(err) => {
+                self.osc_error = Some(tr!("listen-failed", port = port, error = err.to_string()))
+            }
+        }
+    }
+
+    /// Handles the OSC messages received since the last frame.
+    pub(crate) fn handle_osc_messages(&mut self) {
+        let messages: Vec<(SocketAddr, OscMessage)> = self.osc_messages.try_iter().collect();
+        for (from, message) in messages {
+            self.osc_monitor
+                .record(Direction::In, from.to_string(), &message);
+            if self.set_variable_from_osc(&message) {
+                continue;
+            }
+            self.handle_input_event(InputEvent::Osc(message.address));
+        }
+    }
+}
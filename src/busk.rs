@@ -0,0 +1,176 @@
+//! Busk mode: generative, beat-synced looks for gigs with no cue list. Every
+//! few beats a new look is picked from the enabled templates and the palette,
+//! and rendered over the cue output on the chosen fixture groups.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::pixel::Rgb;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BuskTemplate {
+    /// Every fixture in one colour.
+    Wash,
+    /// Alternate fixtures in two colours.
+    Split,
+    /// One fixture at a time, stepping on each beat.
+    Chase,
+    /// Everything flashes on the beat and decays.
+    Pulse,
+    /// Moving heads sweep in a slow circle.
+    Sweep,
+}
+
+impl BuskTemplate {
+    pub const ALL: [BuskTemplate; 5] = [
+        BuskTemplate::Wash,
+        BuskTemplate::Split,
+        BuskTemplate::Chase,
+        BuskTemplate::Pulse,
+        BuskTemplate::Sweep,
+    ];
+}
+
+impl fmt::Display for BuskTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            BuskTemplate::Wash => tr!("busk-template-wash"),
+            BuskTemplate::Split => tr!("busk-template-split"),
+            BuskTemplate::Chase => tr!("busk-template-chase"),
+            BuskTemplate::Pulse => tr!("busk-template-pulse"),
+            BuskTemplate::Sweep => tr!("busk-template-sweep"),
+        })
+    }
+}
+
+/// What busk mode plays with, saved with the show.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuskSettings {
+    /// Fixture groups to play on, by profile name.
+    pub groups: Vec<String>,
+    pub palette: Vec<Rgb>,
+    pub templates: Vec<BuskTemplate>,
+    /// Master level of the generated looks, from 0 to 1.
+    pub intensity: f32,
+    /// 0 walks through templates and colours in order; 1 picks them at
+    /// random.
+    pub variation: f32,
+    pub beats_per_look: u32,
+    /// The speed master scaling the looks' beat clock, by position.
+    pub speed_master: Option<usize>,
+}
+
+impl Default for BuskSettings {
+    fn default() -> Self {
+        Self {
+            groups: Vec::new(),
+            palette: vec![[255, 0, 64], [0, 96, 255], [255, 160, 0], [0, 255, 128]],
+            templates: BuskTemplate::ALL.to_vec(),
+            intensity: 1.0,
+            variation: 0.5,
+            beats_per_look: 8,
+            speed_master: None,
+        }
+    }
+}
+
+/// One generated look.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Look {
+    pub number: u64,
+    pub template: BuskTemplate,
+    pub colors: [Rgb; 2],
+}
+
+/// SplitMix64, enough to pick looks reproducibly from the look number.
+fn random(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// A number from 0 up to 1, the same every time for the same seed.
+pub fn chance(seed: u64) -> f32 {
+    (random(seed) >> 40) as f32 / (1u64 << 24) as f32
+}
+
+impl BuskSettings {
+    /// The look playing `beats` beats in, or nothing if there is no template
+    /// or colour to make one from.
+    pub fn look_at(&self, beats: f32) -> Option<Look> {
+        if self.templates.is_empty() || self.palette.is_empty() {
+            return None;
+        }
+        let number = (beats.max(0.0) / self.beats_per_look.max(1) as f32) as u64;
+        let seed = number.wrapping_mul(4);
+        let pick = |len: usize, step: u64, salt: u64| {
+            if chance(seed + salt) < self.variation {
+                random(seed + salt + 1) as usize % len
+            } else {
+                (number.wrapping_mul(step) % len as u64) as usize
+            }
+        };
+        let template = self.templates[pick(self.templates.len(), 1, 0)];
+        let first = pick(self.palette.len(), 1, 2);
+        let second = (first + 1 + pick(self.palette.len().max(2) - 1, 1, 3)) % self.palette.len();
+        Some(Look {
+            number,
+            template,
+            colors: [self.palette[first], self.palette[second]],
+        })
+    }
+
+    /// Writes the look playing `beats` beats in over `frame`.
+    pub fn render(&self, fixtures: &[Fixture], beats: f32, frame: &mut DmxFrame) {
+        let Some(look) = self.look_at(beats) else {
+            return;
+        };
+        let targets: Vec<&Fixture> = fixtures
+            .iter()
+            .filter(|fixture| self.groups.contains(&fixture.profile.name))
+            .collect();
+        let count = targets.len();
+        let phase = beats.fract();
+        let master = self.intensity.clamp(0.0, 1.0);
+        let [a, b] = look.colors;
+
+        for (i, fixture) in targets.into_iter().enumerate() {
+            let (color, level) = match look.template {
+                BuskTemplate::Wash | BuskTemplate::Sweep => (a, 1.0),
+                BuskTemplate::Split => (if i % 2 == 0 { a } else { b }, 1.0),
+                BuskTemplate::Chase => {
+                    let lit = beats as usize % count == i;
+                    (a, if lit { 1.0 } else { 0.0 })
+                }
+                BuskTemplate::Pulse => (a, 1.0 - phase),
+            };
+            let level = level * master;
+
+            let mut set = |attribute, value: f32| {
+                if let Some(channel) = fixture.channel_for(attribute) {
+                    frame.set(fixture.universe, channel, value.round() as u8);
+                }
+            };
+            if fixture.channel_for(Attribute::Red).is_some() {
+                set(Attribute::Red, color[0] as f32);
+                set(Attribute::Green, color[1] as f32);
+                set(Attribute::Blue, color[2] as f32);
+                set(Attribute::Intensity, 255.0 * level);
+            } else {
+                // Plain dimmers follow the brightness of the colour.
+                let brightness = color.into_iter().max().unwrap_or(0) as f32;
+                set(Attribute::Intensity, brightness * level);
+            }
+            if look.template == BuskTemplate::Sweep {
+                let angle = TAU * (beats / 8.0 + i as f32 / count as f32);
+                set(Attribute::Pan, 128.0 + 100.0 * angle.sin());
+                set(Attribute::Tilt, 128.0 + 60.0 * angle.cos());
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Colour temperature for tunable white: one Kelvin value worked out for
+//! whatever each fixture has, so a mixed rig can be set to 3200K at once.
+//! A CCT channel is scaled across the profile's range, warm and cool white
+//! LEDs are mixed, and RGB fixtures take the colour of a black body at that
+//! temperature.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use serde::{Deserialize, Serialize};
+
+/// The range the colour temperature tools cover, in Kelvin.
+pub const WARMEST: f32 = 2700.0;
+pub const COOLEST: f32 = 6500.0;
+
+/// A profile's white points: the temperatures of its warm and cool LEDs, or
+/// of its CCT channel at 0 and at full.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WhiteRange {
+    pub warm: u32,
+    pub cool: u32,
+}
+
+impl Default for WhiteRange {
+    fn default() -> Self {
+        Self {
+            warm: WARMEST as u32,
+            cool: COOLEST as u32,
+        }
+    }
+}
+
+fn range(fixture: &Fixture) -> (f32, f32) {
+    let range = fixture.profile.white.unwrap_or_default();
+    let (warm, cool) = (range.warm.max(1000) as f32, range.cool.max(1000) as f32);
+    (warm.min(cool), warm.max(cool))
+}
+
+/// How far towards the warm end `kelvin` is, from 0 to 1, in mireds so the
+/// mix looks even to the eye.
+fn warmth(kelvin: f32, warm: f32, cool: f32) -> f32 {
+    if warm == cool {
+        return 0.5;
+    }
+    let mired = |k: f32| 1_000_000.0 / k;
+    ((mired(kelvin) - mired(cool)) / (mired(warm) - mired(cool))).clamp(0.0, 1.0)
+}
+
+/// Whether `fixture` can be set to a colour temperature.
+pub fn supports(fixture: &Fixture) -> bool {
+    let has = |attribute| fixture.channel_for(attribute).is_some();
+    has(Attribute::Cct)
+        || (has(Attribute::WarmWhite) && has(Attribute::CoolWhite))
+        || (has(Attribute::Red) && has(Attribute::Green) && has(Attribute::Blue))
+}
+
+/// The levels that put `fixture` at `kelvin`, as near as its range allows.
+pub fn values(fixture: &Fixture, kelvin: f32) -> Vec<(Attribute, u8)> {
+    let has = |attribute| fixture.channel_for(attribute).is_some();
+    let (warm, cool) = range(fixture);
+    let kelvin = kelvin.clamp(warm, cool);
+    let level = |f: f32| (f * 255.0).round() as u8;
+    if has(Attribute::Cct) {
+        let t = if cool > warm {
+            (kelvin - warm) / (cool - warm)
+        } else {
+            0.5
+        };
+        vec![(Attribute::Cct, level(t))]
+    } else if has(Attribute::WarmWhite) && has(Attribute::CoolWhite) {
+        // Whichever LED leads runs at full, so the white stays as bright
+        // as it can across the range.
+        let w = warmth(kelvin, warm, cool);
+        let scale = w.max(1.0 - w);
+        vec![
+            (Attribute::WarmWhite, level(w / scale)),
+            (Attribute::CoolWhite, level((1.0 - w) / scale)),
+        ]
+    } else if supports(fixture) {
+        let [r, g, b] = blackbody(kelvin);
+        vec![
+            (Attribute::Red, r),
+            (Attribute::Green, g),
+            (Attribute::Blue, b),
+        ]
+    } else {
+        Vec::new()
+    }
+}
+
+/// The colour temperature a tunable white fixture is at in `frame`. RGB
+/// fixtures aren't read back, as most of their colours have none.
+pub fn kelvin(fixture: &Fixture, frame: &DmxFrame) -> Option<f32> {
+    let level = |attribute| {
+        fixture
+            .channel_for(attribute)
+            .map(|channel| frame.get(fixture.universe, channel) as f32 / 255.0)
+    };
+    let (warm, cool) = range(fixture);
+    if let Some(t) = level(Attribute::Cct) {
+        return Some(warm + (cool - warm) * t);
+    }
+    let (w, c) = (level(Attribute::WarmWhite)?, level(Attribute::CoolWhite)?);
+    if w + c == 0.0 {
+        return None;
+    }
+    // Mix the two white points in mireds, weighted by their levels.
+    let mired = (w * 1_000_000.0 / warm + c * 1_000_000.0 / cool) / (w + c);
+    Some(1_000_000.0 / mired)
+}
+
+/// The colour of a black body at `kelvin`, after Tanner Helland's fit to
+/// the CIE tables.
+pub fn blackbody(kelvin: f32) -> [u8; 3] {
+    let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+    let red = if t <= 66.0 {
+        255.0
+    } else {
+        329.698_73 * (t - 60.0).powf(-0.133_204_76)
+    };
+    let green = if t <= 66.0 {
+        99.470_8 * t.ln() - 161.119_57
+    } else {
+        288.122_16 * (t - 60.0).powf(-0.075_514_85)
+    };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.517_73 * (t - 10.0).ln() - 305.044_8
+    };
+    [red, green, blue].map(|c| c.round().clamp(0.0, 255.0) as u8)
+}
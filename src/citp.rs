@@ -0,0 +1,483 @@
+//! CITP for pre-visualisers such as Capture and L8. Halo announces itself as
+//! a lighting console on the CITP multicast group and accepts the
+//! visualiser's connection, then keeps it in step: the patch goes out as
+//! FPTC patch messages whenever it changes, and fixture selection goes both
+//! ways over FSEL, so picking fixtures in the visualiser selects their
+//! channels for the command line and quick console, and the other way round.
+//!
+//! Halo's patch is the one that counts: patch changes made in the visualiser
+//! are not taken back. Media server thumbnails (MSEX) are for media servers,
+//! which Halo neither is nor drives.
+
+use crate::fixture::Fixture;
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const MULTICAST_GROUP: Ipv4Addr = Ipv4Addr::new(239, 224, 0, 180);
+const MULTICAST_PORT: u16 = 4809;
+const HEADER_SIZE: usize = 20;
+/// The largest message accepted from a peer.
+const MAX_MESSAGE: usize = 1 << 20;
+/// How often Halo announces where it listens.
+const ANNOUNCE_INTERVAL: Duration = Duration::from_secs(1);
+/// How long a message may take to arrive once it starts.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
+const COOKIE: &[u8; 4] = b"CITP";
+const PINF: &[u8; 4] = b"PINF";
+const FPTC: &[u8; 4] = b"FPTC";
+const FSEL: &[u8; 4] = b"FSEL";
+const PEER_NAME: &[u8; 4] = b"PNam";
+const PEER_LOCATION: &[u8; 4] = b"PLoc";
+const PATCH: &[u8; 4] = b"Ptch";
+const UNPATCH: &[u8; 4] = b"UPtc";
+const SEND_PATCH: &[u8; 4] = b"SPtc";
+const SELECT: &[u8; 4] = b"Sele";
+const DESELECT: &[u8; 4] = b"DeSe";
+
+/// A patched fixture as CITP describes it.
+#[derive(Clone, PartialEq)]
+pub struct PatchEntry {
+    pub id: u16,
+    /// From 0, as CITP counts.
+    pub universe: u8,
+    /// From 0, as CITP counts.
+    pub channel: u16,
+    pub channel_count: u16,
+    pub make: String,
+    pub name: String,
+}
+
+impl PatchEntry {
+    fn from_fixture(fixture: &Fixture) -> Self {
+        Self {
+            id: fixture.id as u16,
+            universe: fixture.universe.saturating_sub(1) as u8,
+            channel: fixture.address.saturating_sub(1),
+            channel_count: fixture.profile.channels.len() as u16,
+            make: fixture.profile.manufacturer().to_string(),
+            name: fixture.profile.model().to_string(),
+        }
+    }
+}
+
+/// Selection changes from a visualiser, by fixture id.
+pub enum CitpEvent {
+    /// These fixtures are selected; with `complete`, only these are.
+    Select {
+        complete: bool,
+        ids: Vec<u16>,
+    },
+    Deselect(Vec<u16>),
+}
+
+/// A whole message: the CITP header for `layer`, then `body`.
+fn message(layer: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(HEADER_SIZE + body.len());
+    packet.extend(COOKIE);
+    packet.extend([1, 0]);
+    packet.extend(0u16.to_le_bytes());
+    packet.extend(((HEADER_SIZE + body.len()) as u32).to_le_bytes());
+    packet.extend(1u16.to_le_bytes());
+    packet.extend(0u16.to_le_bytes());
+    packet.extend(layer);
+    packet.extend(body);
+    packet
+}
+
+/// A null-terminated single-byte string.
+fn push_string(body: &mut Vec<u8>, text: &str) {
+    body.extend(text.bytes().filter(u8::is_ascii));
+    body.push(0);
+}
+
+fn announcement(port: u16, name: &str) -> Vec<u8> {
+    let mut body = PEER_LOCATION.to_vec();
+    body.extend(port.to_le_bytes());
+    push_string(&mut body, "LightingConsole");
+    push_string(&mut body, name);
+    push_string(&mut body, "Running");
+    message(PINF, &body)
+}
+
+fn peer_name(name: &str) -> Vec<u8> {
+    let mut body = PEER_NAME.to_vec();
+    push_string(&mut body, name);
+    message(PINF, &body)
+}
+
+fn patch_message(entry: &PatchEntry) -> Vec<u8> {
+    let mut body = PATCH.to_vec();
+    // Content hint: none.
+    body.extend(0u32.to_le_bytes());
+    body.extend(entry.id.to_le_bytes());
+    body.push(entry.universe);
+    body.push(0);
+    body.extend(entry.channel.to_le_bytes());
+    body.extend(entry.channel_count.to_le_bytes());
+    push_string(&mut body, &entry.make);
+    push_string(&mut body, &entry.name);
+    message(FPTC, &body)
+}
+
+fn unpatch_message(ids: &[u16]) -> Vec<u8> {
+    let mut body = UNPATCH.to_vec();
+    body.extend(0u32.to_le_bytes());
+    body.extend((ids.len() as u16).to_le_bytes());
+    for id in ids {
+        body.extend(id.to_le_bytes());
+    }
+    message(FPTC, &body)
+}
+
+fn select_message(ids: &[u16]) -> Vec<u8> {
+    let mut body = SELECT.to_vec();
+    // Complete: the selection is exactly these.
+    body.extend([1, 0]);
+    body.extend((ids.len() as u16).to_le_bytes());
+    for id in ids {
+        body.extend(id.to_le_bytes());
+    }
+    message(FSEL, &body)
+}
+
+/// Fixture ids as a count followed by the ids, from `data`.
+fn read_ids(data: &[u8]) -> Option<Vec<u16>> {
+    let count = u16::from_le_bytes(data.get(..2)?.try_into().ok()?) as usize;
+    let ids = data.get(2..2 + count * 2)?;
+    Some(
+        ids.chunks_exact(2)
+            .map(|id| u16::from_le_bytes([id[0], id[1]]))
+            .collect(),
+    )
+}
+
+/// Reads one message from `stream`, returning its layer and the body after
+/// the header.
+fn read_message(stream: &mut TcpStream) -> io::Result<([u8; 4], Vec<u8>)> {
+    let mut header = [0u8; HEADER_SIZE];
+    stream.read_exact(&mut header)?;
+    if &header[..4] != COOKIE {
+        return Err(io::Error::other("not a CITP message"));
+    }
+    let size = u32::from_le_bytes(header[8..12].try_into().unwrap()) as usize;
+    if !(HEADER_SIZE..=MAX_MESSAGE).contains(&size) {
+        return Err(io::Error::other("bad CITP message size"));
+    }
+    let mut body = vec![0u8; size - HEADER_SIZE];
+    stream.read_exact(&mut body)?;
+    Ok((header[16..20].try_into().unwrap(), body))
+}
+
+/// What the connected visualisers have been sent, to send them only
+/// changes.
+#[derive(Default)]
+struct Shared {
+    peers: Vec<(SocketAddr, TcpStream, String)>,
+    patch: Vec<PatchEntry>,
+    selection: Vec<u16>,
+}
+
+impl Shared {
+    fn broadcast(&mut self, packet: &[u8]) {
+        self.peers
+            .retain_mut(|(_, stream, _)| stream.write_all(packet).is_ok());
+    }
+}
+
+/// Announces Halo and serves visualiser connections on background threads
+/// until dropped.
+pub struct CitpServer {
+    shared: Arc<Mutex<Shared>>,
+    shutdown: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+    events: Receiver<CitpEvent>,
+}
+
+impl CitpServer {
+    pub fn start(name: &str, ctx: egui::Context) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_multicast_ttl_v4(1)?;
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (sender, events) = mpsc::channel();
+
+        let stop = shutdown.clone();
+        let packet = announcement(port, name);
+        let announce = thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let _ = socket.send_to(&packet, (MULTICAST_GROUP, MULTICAST_PORT));
+                thread::sleep(ANNOUNCE_INTERVAL);
+            }
+        });
+
+        let stop = shutdown.clone();
+        let peers = shared.clone();
+        let name = name.to_string();
+        let accept = thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let Ok((stream, from)) = listener.accept() else {
+                    thread::sleep(Duration::from_millis(100));
+                    continue;
+                };
+                let peers = peers.clone();
+                let (stop, sender, ctx) = (stop.clone(), sender.clone(), ctx.clone());
+                let name = name.clone();
+                thread::spawn(move || {
+                    let _ = serve_peer(stream, from, &name, &peers, &stop, &sender, &ctx);
+                    if let Ok(mut shared) = peers.lock() {
+                        shared.peers.retain(|(address, _, _)| *address != from);
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            shared,
+            shutdown,
+            threads: vec![announce, accept],
+            events,
+        })
+    }
+
+    /// Sends the patch to the visualisers, if it changed: fixtures that
+    /// went are unpatched, and new or changed ones patched.
+    pub fn publish_patch(&self, patch: Vec<PatchEntry>) {
+        let Ok(mut shared) = self.shared.lock() else {
+            return;
+        };
+        if shared.patch == patch {
+            return;
+        }
+        let gone: Vec<u16> = shared
+            .patch
+            .iter()
+            .filter(|old| patch.iter().all(|entry| entry.id != old.id))
+            .map(|old| old.id)
+            .collect();
+        if !gone.is_empty() {
+            shared.broadcast(&unpatch_message(&gone));
+        }
+        let changed: Vec<Vec<u8>> = patch
+            .iter()
+            .filter(|entry| !shared.patch.contains(entry))
+            .map(patch_message)
+            .collect();
+        for packet in changed {
+            shared.broadcast(&packet);
+        }
+        shared.patch = patch;
+    }
+
+    /// Sends Halo's fixture selection to the visualisers, if it changed.
+    pub fn publish_selection(&self, ids: Vec<u16>) {
+        let Ok(mut shared) = self.shared.lock() else {
+            return;
+        };
+        if shared.selection != ids {
+            shared.broadcast(&select_message(&ids));
+            shared.selection = ids;
+        }
+    }
+
+    /// The connected visualisers, by name.
+    pub fn peers(&self) -> Vec<String> {
+        self.shared.lock().map_or(Vec::new(), |shared| {
+            shared
+                .peers
+                .iter()
+                .map(|(address, _, name)| {
+                    if name.is_empty() {
+                        address.to_string()
+                    } else {
+                        format!("{} ({})", name, address.ip())
+                    }
+                })
+                .collect()
+        })
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = CitpEvent> + '_ {
+        self.events.try_iter()
+    }
+}
+
+impl Drop for CitpServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.peers.clear();
+        }
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Talks to one visualiser until it disconnects or the server stops.
+fn serve_peer(
+    mut stream: TcpStream,
+    from: SocketAddr,
+    name: &str,
+    shared: &Mutex<Shared>,
+    stop: &AtomicBool,
+    events: &Sender<CitpEvent>,
+    ctx: &egui::Context,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_nodelay(true)?;
+    stream.write_all(&peer_name(name))?;
+    {
+        let mut shared = shared.lock().map_err(|_| io::Error::other("poisoned"))?;
+        for entry in &shared.patch {
+            stream.write_all(&patch_message(entry))?;
+        }
+        stream.write_all(&select_message(&shared.selection))?;
+        shared
+            .peers
+            .push((from, stream.try_clone()?, String::new()));
+    }
+    while !stop.load(Ordering::Relaxed) {
+        // Wait for a message a little at a time, so the thread exits once
+        // stopped, then give the whole message longer to arrive.
+        stream.set_read_timeout(Some(Duration::from_millis(250)))?;
+        match stream.peek(&mut [0]) {
+            Ok(0) => return Ok(()),
+            Ok(_) => {}
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                continue;
+            }
+            Err(err) => return Err(err),
+        }
+        stream.set_read_timeout(Some(MESSAGE_TIMEOUT))?;
+        let (layer, body) = read_message(&mut stream)?;
+        let Some((kind, data)) = body.split_first_chunk::<4>() else {
+            continue;
+        };
+        match (&layer, kind) {
+            (PINF, PEER_NAME) => {
+                let peer = String::from_utf8_lossy(data.split(|&b| b == 0).next().unwrap_or(&[]))
+                    .to_string();
+                if let Ok(mut shared) = shared.lock() {
+                    if let Some(entry) = shared.peers.iter_mut().find(|(a, _, _)| *a == from) {
+                        entry.2 = peer;
+                    }
+                }
+                ctx.request_repaint();
+            }
+            (FPTC, SEND_PATCH) => {
+                // After the content hint, the fixtures wanted; none means all.
+                let ids = data.get(4..).and_then(read_ids).unwrap_or_default();
+                let packets: Vec<Vec<u8>> = shared.lock().map_or(Vec::new(), |shared| {
+                    shared
+                        .patch
+                        .iter()
+                        .filter(|entry| ids.is_empty() || ids.contains(&entry.id))
+                        .map(patch_message)
+                        .collect()
+                });
+                for packet in packets {
+                    stream.write_all(&packet)?;
+                }
+            }
+            (FSEL, SELECT) => {
+                if let Some(ids) = data.get(2..).and_then(read_ids) {
+                    let complete = data[0] != 0;
+                    let _ = events.send(CitpEvent::Select { complete, ids });
+                    ctx.request_repaint();
+                }
+            }
+            (FSEL, DESELECT) => {
+                if let Some(ids) = read_ids(data) {
+                    let _ = events.send(CitpEvent::Deselect(ids));
+                    ctx.request_repaint();
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+impl HaloApp {
+    /// Starts or stops CITP to match the current preferences.
+    pub(crate) fn apply_citp_preferences(&mut self, ctx: &egui::Context) {
+        self.citp = None;
+        self.citp_error = None;
+        if !self.preferences.citp_enabled {
+            return;
+        }
+        match CitpServer::start("Halo", ctx.clone()) {
+            Ok(server) => self.citp = Some(server),
+            Err(err) => {
+                self.log.error(tr!("citp-failed", error = err.to_string()));
+                self.citp_error = Some(err.to_string());
+            }
+        }
+    }
+
+    /// Takes selections from the visualisers, and sends them the patch and
+    /// Halo's selection when they change.
+    pub(crate) fn update_citp(&mut self) {
+        let Some(citp) = &self.citp else {
+            return;
+        };
+        // Selections are kept as channel numbers: positions in the patch.
+        let channel = |id: u16| {
+            self.show
+                .fixtures
+                .iter()
+                .position(|fixture| fixture.id == id as usize)
+                .map(|index| index + 1)
+        };
+        let mut selection = self.quick_selection.clone();
+        for event in citp.events() {
+            match event {
+                CitpEvent::Select { complete, ids } => {
+                    if complete {
+                        selection.clear();
+                    }
+                    for channel in ids.into_iter().filter_map(channel) {
+                        if !selection.contains(&channel) {
+                            selection.push(channel);
+                        }
+                    }
+                }
+                CitpEvent::Deselect(ids) => {
+                    let gone: Vec<usize> = ids.into_iter().filter_map(channel).collect();
+                    selection.retain(|channel| !gone.contains(channel));
+                }
+            }
+        }
+        let ids: Vec<u16> = selection
+            .iter()
+            .filter_map(|&channel| self.show.fixtures.get(channel - 1))
+            .map(|fixture| fixture.id as u16)
+            .collect();
+        citp.publish_patch(
+            self.show
+                .fixtures
+                .iter()
+                .map(PatchEntry::from_fixture)
+                .collect(),
+        );
+        citp.publish_selection(ids);
+        self.quick_selection = selection;
+    }
+}
@@ -0,0 +1,225 @@
+//! The metronome click: a short tone on every beat, louder and higher on the
+//! first beat of the bar, played on an audio output of its own so it can go
+//! to a drummer's or the operator's in-ear mix and not the PA. The output
+//! thread counts beats at the show tempo itself, so clicks land on time
+//! however often the UI repaints, and the UI lines it up with the beat
+//! indicator on Start, Reset and each beat of incoming MIDI clock.
+
+use crate::error::{Device, HaloError};
+use crate::i18n::tr;
+use crate::HaloApp;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+use std::f32::consts::TAU;
+use std::sync::{Arc, Mutex};
+
+/// How long each click rings for.
+const CLICK_SECS: f32 = 0.03;
+const PITCH: f32 = 1000.0;
+const ACCENT_PITCH: f32 = 1500.0;
+
+pub fn output_devices() -> Vec<String> {
+    let Ok(devices) = cpal::default_host().output_devices() else {
+        return Vec::new();
+    };
+    devices.filter_map(|device| device.name().ok()).collect()
+}
+
+#[derive(Default)]
+struct ClickState {
+    bpm: f32,
+    beats_per_bar: u32,
+    /// Count beats at `bpm`; otherwise only click when synced, as with MIDI
+    /// clock.
+    running: bool,
+    volume: f32,
+    accent: bool,
+    /// The beat that falls right now, waiting for the output thread.
+    sync: Option<u32>,
+    error: Option<String>,
+}
+
+/// The tempo and position the click follows, shared with the output thread.
+#[derive(Clone, Default)]
+pub struct ClickClock(Arc<Mutex<ClickState>>);
+
+impl ClickClock {
+    pub fn set(&self, bpm: f32, beats_per_bar: u32, running: bool, volume: f32, accent: bool) {
+        if let Ok(mut state) = self.0.lock() {
+            state.bpm = bpm;
+            state.beats_per_bar = beats_per_bar.max(1);
+            state.running = running;
+            state.volume = volume;
+            state.accent = accent;
+        }
+    }
+
+    /// Clicks beat `beat`, counted from zero, now and counts on from there.
+    pub fn sync(&self, beat: u32) {
+        if let Ok(mut state) = self.0.lock() {
+            state.sync = Some(beat);
+        }
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.0.lock().ok().and_then(|state| state.error.clone())
+    }
+}
+
+/// An open output device playing the click. It falls silent when dropped.
+pub struct ClickOutput {
+    _stream: cpal::Stream,
+}
+
+impl ClickOutput {
+    pub fn start(device_name: &str, clock: ClickClock) -> Result<Self, String> {
+        let host = cpal::default_host();
+        let device = host
+            .output_devices()
+            .map_err(|err| err.to_string())?
+            .find(|device| device.name().is_ok_and(|name| name == device_name))
+            .ok_or_else(|| tr!("playback-device-missing", device = device_name))?;
+        let supported = device
+            .default_output_config()
+            .map_err(|err| err.to_string())?;
+        let config = supported.config();
+        let stream = match supported.sample_format() {
+            cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config, clock),
+            cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config, clock),
+            cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config, clock),
+            cpal::SampleFormat::I32 => build_stream::<i32>(&device, &config, clock),
+            format => return Err(tr!("audio-unsupported-format", format = format.to_string())),
+        }?;
+        stream.play().map_err(|err| err.to_string())?;
+        Ok(Self { _stream: stream })
+    }
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    clock: ClickClock,
+) -> Result<cpal::Stream, String>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let channels = config.channels.max(1) as usize;
+    let mut generator = Generator::new(config.sample_rate.0 as f32);
+    let errors = clock.clone();
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _| {
+                let Ok(mut state) = clock.0.lock() else {
+                    return;
+                };
+                if let Some(beat) = state.sync.take() {
+                    generator.sync(beat);
+                }
+                let state = &*state;
+                for frame in data.chunks_mut(channels) {
+                    let sample = T::from_sample(generator.next(state));
+                    frame.fill(sample);
+                }
+            },
+            move |err| {
+                if let Ok(mut state) = errors.0.lock() {
+                    state.error = Some(err.to_string());
+                }
+            },
+            None,
+        )
+        .map_err(|err| err.to_string())
+}
+
+/// Runs on the output thread, counting beats in samples.
+struct Generator {
+    sample_rate: f32,
+    /// The next beat to click, counted from zero.
+    beat: u32,
+    /// Samples until it falls.
+    until_beat: f32,
+    /// Samples into the click sounding, and its pitch and level.
+    click: Option<(u32, f32, f32)>,
+    /// Click the next sample, having just been synced.
+    synced: bool,
+}
+
+impl Generator {
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            beat: 0,
+            until_beat: 0.0,
+            click: None,
+            synced: false,
+        }
+    }
+
+    fn sync(&mut self, beat: u32) {
+        self.beat = beat;
+        self.until_beat = 0.0;
+        self.synced = true;
+    }
+
+    fn next(&mut self, state: &ClickState) -> f32 {
+        if (state.running || self.synced) && state.bpm > 0.0 {
+            if self.until_beat <= 0.0 || self.synced {
+                self.synced = false;
+                let downbeat = self.beat.is_multiple_of(state.beats_per_bar.max(1));
+                self.click = Some(if downbeat && state.accent {
+                    (0, ACCENT_PITCH, 1.0)
+                } else {
+                    (0, PITCH, 0.6)
+                });
+                self.beat = self.beat.wrapping_add(1);
+                self.until_beat += self.sample_rate * 60.0 / state.bpm;
+            }
+            if state.running {
+                self.until_beat -= 1.0;
+            }
+        }
+        let Some((position, pitch, level)) = &mut self.click else {
+            return 0.0;
+        };
+        let t = *position as f32 / self.sample_rate;
+        if t >= CLICK_SECS {
+            self.click = None;
+            return 0.0;
+        }
+        *position += 1;
+        let envelope = (1.0 - t / CLICK_SECS).powi(2);
+        (TAU * *pitch * t).sin() * envelope * *level * state.volume
+    }
+}
+
+impl HaloApp {
+    /// Opens or closes the click output to match the current preferences.
+    pub(crate) fn apply_click_preferences(&mut self) {
+        self.click_output = None;
+        self.click_error = None;
+        if let Some(device) = self.preferences.click_output.clone() {
+            match ClickOutput::start(&device, self.click_clock.clone()) {
+                Ok(output) => self.click_output = Some(output),
+                Err(err) => {
+                    self.report_error(HaloError::device(Device::Click, device, &err));
+                    self.click_error = Some(err);
+                }
+            }
+        }
+    }
+
+    /// Passes the tempo and transport on to the click.
+    pub(crate) fn update_click(&self) {
+        if self.click_output.is_none() {
+            return;
+        }
+        self.click_clock.set(
+            self.bpm,
+            self.show.time_signature.beats_per_bar(),
+            self.transport.is_running() && !self.follows_midi_clock(),
+            self.preferences.click_volume,
+            self.preferences.accent_downbeat,
+        );
+    }
+}
@@ -0,0 +1,570 @@
+//! Clock sharing between a main and a backup Halo. The main answers clock
+//! requests over UDP straight from the engine's playhead, and the backup asks
+//! ten times a second, NTP-style: it times each round trip, takes the main's
+//! playhead to have moved on by half of it, and trusts the quickest recent
+//! exchange most. The backup then starts, stops and seeks its own transport
+//! to stay within a few milliseconds of the main, showing how far apart the
+//! two clocks were measured to be.
+//!
+//! A tracking backup also mirrors the main's show, fetched over TCP on the
+//! same port whenever the main's copy changes, and holds its own output
+//! while the main answers. When the main stops answering for a second the
+//! backup takes over the output from the same cues at the same playhead, and
+//! keeps it until the operator hands it back.
+
+use crate::engine::Playhead;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::show::Show;
+use crate::transport::Transport;
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often the backup asks for the main's clock.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// How many recent exchanges to pick the quickest from.
+const SAMPLES: usize = 8;
+/// The main counts as lost after this long without a reply.
+const LOST_AFTER: Duration = Duration::from_secs(1);
+/// A backup counts as gone after this long without a request.
+const BACKUP_TIMEOUT: Duration = Duration::from_secs(2);
+/// How far the backup's playhead may drift from the main's before it is
+/// moved back into line.
+const TOLERANCE: f64 = 0.005;
+/// A move in the main's clock bigger than this is a seek, so exchanges from
+/// before it are thrown away.
+const JUMP: f64 = 0.05;
+/// How often the main checks its show for changes to pass on.
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ClockRole {
+    #[default]
+    Off,
+    /// Serves its clock to backups.
+    Main,
+    /// Follows a main's clock.
+    Backup,
+}
+
+impl ClockRole {
+    pub const ALL: [ClockRole; 3] = [ClockRole::Off, ClockRole::Main, ClockRole::Backup];
+}
+
+impl fmt::Display for ClockRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            ClockRole::Off => tr!("clock-role-off"),
+            ClockRole::Main => tr!("clock-role-main"),
+            ClockRole::Backup => tr!("clock-role-backup"),
+        })
+    }
+}
+
+/// Where a tracking backup's output stands.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum BackupState {
+    /// Holding output, not having heard from the main yet.
+    #[default]
+    Waiting,
+    /// Holding output while the main answers.
+    Standby,
+    /// Sending output in place of the main.
+    TakenOver,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ClockRequest {
+    seq: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ClockReply {
+    seq: u32,
+    /// The main's playhead when it answered, in microseconds.
+    position_us: u64,
+    running: bool,
+    /// Counts changes to the main's show, for tracking backups; 0 before
+    /// there is one to fetch.
+    #[serde(default)]
+    show_revision: u64,
+}
+
+/// The main's show as last published, for tracking backups to fetch.
+#[derive(Default)]
+struct PublishedShow {
+    revision: u64,
+    json: Arc<String>,
+}
+
+/// Answers clock requests from backups, and serves the show to tracking
+/// backups, on background threads until dropped.
+pub struct ClockServer {
+    /// When each backup last asked, by address.
+    backups: Arc<Mutex<HashMap<SocketAddr, Instant>>>,
+    show: Arc<Mutex<PublishedShow>>,
+    shutdown: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl ClockServer {
+    pub fn start(port: u16, playhead: Playhead) -> io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        // Wake up periodically so the thread exits once stopped.
+        socket.set_read_timeout(Some(Duration::from_millis(250)))?;
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        listener.set_nonblocking(true)?;
+        let backups = Arc::new(Mutex::new(HashMap::new()));
+        let show = Arc::new(Mutex::new(PublishedShow::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let published = show.clone();
+        let stop = shutdown.clone();
+        let serve_show = thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let Ok((stream, _)) = listener.accept() else {
+                    thread::sleep(Duration::from_millis(100));
+                    continue;
+                };
+                let json = published.lock().map(|show| show.json.clone());
+                if let Ok(json) = json {
+                    let _ = send_show(stream, &json);
+                }
+            }
+        });
+
+        let seen = backups.clone();
+        let published = show.clone();
+        let stop = shutdown.clone();
+        let answer_clock = thread::spawn(move || {
+            let mut buf = [0u8; 512];
+            while !stop.load(Ordering::Relaxed) {
+                let Ok((len, from)) = socket.recv_from(&mut buf) else {
+                    continue;
+                };
+                let Ok(request) = serde_json::from_slice::<ClockRequest>(&buf[..len]) else {
+                    continue;
+                };
+                let (position, running) = playhead.position();
+                let reply = ClockReply {
+                    seq: request.seq,
+                    position_us: position.as_micros() as u64,
+                    running,
+                    show_revision: published.lock().map_or(0, |show| show.revision),
+                };
+                if let Ok(packet) = serde_json::to_vec(&reply) {
+                    let _ = socket.send_to(&packet, from);
+                }
+                if let Ok(mut seen) = seen.lock() {
+                    seen.insert(from, Instant::now());
+                }
+            }
+        });
+        Ok(Self {
+            backups,
+            show,
+            shutdown,
+            threads: vec![answer_clock, serve_show],
+        })
+    }
+
+    /// Passes on the show as saved, if it has changed.
+    fn publish_show(&self, json: String) {
+        if let Ok(mut show) = self.show.lock() {
+            if *show.json != json {
+                show.revision += 1;
+                show.json = Arc::new(json);
+            }
+        }
+    }
+
+    /// Backups that have asked for the clock lately.
+    pub fn backups(&self) -> Vec<SocketAddr> {
+        let Ok(backups) = self.backups.lock() else {
+            return Vec::new();
+        };
+        let mut addresses: Vec<SocketAddr> = backups
+            .iter()
+            .filter(|(_, seen)| seen.elapsed() < BACKUP_TIMEOUT)
+            .map(|(address, _)| *address)
+            .collect();
+        addresses.sort();
+        addresses
+    }
+}
+
+impl Drop for ClockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Wait for the sockets to close so the port can be bound again.
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn send_show(mut stream: TcpStream, json: &str) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+    stream.write_all(json.as_bytes())
+}
+
+/// Reads the main's show from the port it answers clock requests on.
+fn fetch_show(main: SocketAddr) -> io::Result<Show> {
+    let mut stream = TcpStream::connect_timeout(&main, Duration::from_secs(2))?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut json = String::new();
+    stream.read_to_string(&mut json)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// The main's clock as measured by one exchange.
+#[derive(Clone, Copy)]
+pub struct ClockEstimate {
+    /// The main's playhead at `at`.
+    pub position: Duration,
+    pub running: bool,
+    pub at: Instant,
+    pub round_trip: Duration,
+}
+
+impl ClockEstimate {
+    /// Where the main's playhead is at `now`.
+    pub fn position_at(&self, now: Instant) -> Duration {
+        if self.running {
+            self.position + now.saturating_duration_since(self.at)
+        } else {
+            self.position
+        }
+    }
+}
+
+#[derive(Default)]
+struct ClientShared {
+    samples: VecDeque<ClockEstimate>,
+    error: Option<String>,
+    /// A newer copy of the main's show, waiting for the UI.
+    show: Option<Show>,
+    /// The revision of the main's show last fetched.
+    show_revision: u64,
+    /// A fetch is under way; it runs on a thread of its own so a big show
+    /// doesn't hold up the clock.
+    fetching: bool,
+    show_error: Option<String>,
+}
+
+/// The backup's connection to the main's clock. Polls on a background thread
+/// until dropped.
+pub struct ClockClient {
+    shared: Arc<Mutex<ClientShared>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ClockClient {
+    /// Follows the clock of `main`, and with `track` its show too.
+    pub fn connect(main: &str, track: bool, ctx: egui::Context) -> Self {
+        let shared = Arc::new(Mutex::new(ClientShared::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let main = main.to_string();
+        let thread_shared = shared.clone();
+        let stop = shutdown.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                if let Err(err) = poll(&main, track, &thread_shared, &ctx, &stop) {
+                    if let Ok(mut shared) = thread_shared.lock() {
+                        shared.samples.clear();
+                        shared.error = Some(format!("{}: {}", main, err));
+                    }
+                    ctx.request_repaint();
+                    thread::sleep(Duration::from_secs(1));
+                }
+            }
+        });
+        Self { shared, shutdown }
+    }
+
+    /// The main's clock from the quickest recent exchange, unless the main
+    /// has stopped answering.
+    pub fn estimate(&self) -> Option<ClockEstimate> {
+        let shared = self.shared.lock().ok()?;
+        let latest = shared.samples.back()?;
+        if latest.at.elapsed() > LOST_AFTER {
+            return None;
+        }
+        shared
+            .samples
+            .iter()
+            .min_by_key(|sample| sample.round_trip)
+            .copied()
+    }
+
+    /// The main's show, if it has changed since last taken.
+    pub fn take_show(&self) -> Option<Show> {
+        self.shared.lock().ok()?.show.take()
+    }
+
+    pub fn error(&self) -> Option<String> {
+        let shared = self.shared.lock().ok()?;
+        match shared.samples.back() {
+            Some(latest) if latest.at.elapsed() > LOST_AFTER => Some(tr!("clock-main-silent")),
+            _ => shared.error.clone().or_else(|| shared.show_error.clone()),
+        }
+    }
+}
+
+impl Drop for ClockClient {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Exchanges clock packets with the main until the socket fails or the
+/// client is dropped.
+fn poll(
+    main: &str,
+    track: bool,
+    shared: &Arc<Mutex<ClientShared>>,
+    ctx: &egui::Context,
+    stop: &AtomicBool,
+) -> io::Result<()> {
+    let addr = main
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::other("no such host"))?;
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.connect(addr)?;
+    socket.set_read_timeout(Some(POLL_INTERVAL))?;
+    let mut seq: u32 = 0;
+    let mut buf = [0u8; 512];
+    while !stop.load(Ordering::Relaxed) {
+        seq = seq.wrapping_add(1);
+        let sent = Instant::now();
+        socket.send(&serde_json::to_vec(&ClockRequest { seq })?)?;
+        // Replies to earlier requests that arrive late are skipped.
+        let reply = loop {
+            match socket.recv(&mut buf) {
+                Ok(len) => match serde_json::from_slice::<ClockReply>(&buf[..len]) {
+                    Ok(reply) if reply.seq == seq => break Some(reply),
+                    _ => continue,
+                },
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    break None
+                }
+                // Nothing listening on the main's port yet.
+                Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => break None,
+                Err(err) => return Err(err),
+            }
+        };
+        let at = Instant::now();
+        if let Some(reply) = reply {
+            let round_trip = at - sent;
+            let mut position = Duration::from_micros(reply.position_us);
+            if reply.running {
+                position += round_trip / 2;
+            }
+            let estimate = ClockEstimate {
+                position,
+                running: reply.running,
+                at,
+                round_trip,
+            };
+            let mut fetch = false;
+            if let Ok(mut shared) = shared.lock() {
+                // Earlier exchanges say nothing about a clock that has
+                // since been started, stopped or moved.
+                let jumped = shared.samples.back().is_some_and(|last| {
+                    last.running != estimate.running
+                        || (last.position_at(at).as_secs_f64() - position.as_secs_f64()).abs()
+                            > JUMP
+                });
+                if jumped {
+                    shared.samples.clear();
+                }
+                shared.samples.push_back(estimate);
+                if shared.samples.len() > SAMPLES {
+                    shared.samples.pop_front();
+                }
+                shared.error = None;
+                fetch = track
+                    && reply.show_revision != 0
+                    && reply.show_revision != shared.show_revision
+                    && !shared.fetching;
+                shared.fetching |= fetch;
+            }
+            if fetch {
+                let shared = shared.clone();
+                let revision = reply.show_revision;
+                let ctx = ctx.clone();
+                thread::spawn(move || {
+                    let result = fetch_show(addr);
+                    if let Ok(mut shared) = shared.lock() {
+                        shared.fetching = false;
+                        match result {
+                            Ok(show) => {
+                                shared.show = Some(show);
+                                shared.show_revision = revision;
+                                shared.show_error = None;
+                            }
+                            Err(err) => {
+                                shared.show_error =
+                                    Some(tr!("clock-show-fetch-failed", error = err.to_string()));
+                            }
+                        }
+                    }
+                    ctx.request_repaint();
+                });
+            }
+            ctx.request_repaint();
+        }
+        thread::sleep(POLL_INTERVAL.saturating_sub(at - sent));
+    }
+    Ok(())
+}
+
+impl HaloApp {
+    /// Serves or follows the clock to match the current preferences.
+    pub(crate) fn apply_clock_sync_preferences(&mut self, ctx: &egui::Context) {
+        self.clock_server = None;
+        self.clock_client = None;
+        self.clock_sync_error = None;
+        self.clock_offset = None;
+        match self.preferences.clock_role {
+            ClockRole::Off => {}
+            ClockRole::Main => {
+                match ClockServer::start(self.preferences.clock_port, self.engine.playhead()) {
+                    Ok(server) => self.clock_server = Some(server),
+                    Err(err) => {
+                        self.clock_sync_error = Some(err.to_string());
+                        self.log.error(tr!(
+                            "clock-listen-failed",
+                            port = self.preferences.clock_port,
+                            error = err.to_string()
+                        ));
+                    }
+                }
+            }
+            ClockRole::Backup => {
+                self.clock_client = Some(ClockClient::connect(
+                    &self.preferences.clock_main,
+                    self.preferences.tracking_backup,
+                    ctx.clone(),
+                ));
+            }
+        }
+    }
+
+    /// Whether this is a tracking backup holding its output for the main.
+    pub(crate) fn holds_output_for_main(&self) -> bool {
+        self.preferences.clock_role == ClockRole::Backup
+            && self.preferences.tracking_backup
+            && self.backup_state != BackupState::TakenOver
+    }
+
+    /// Sends this Halo's output in place of the main's.
+    pub(crate) fn take_over_output(&mut self) {
+        self.backup_state = BackupState::TakenOver;
+        self.clock_offset = None;
+    }
+
+    /// Holds output again and goes back to following the main.
+    pub(crate) fn hand_back_output(&mut self) {
+        self.backup_state = BackupState::Waiting;
+        self.log.info(tr!("clock-handed-back"));
+    }
+
+    /// Passes the show on to backups, keeps a backup's transport and show in
+    /// line with the main's, and takes over from a main that has gone.
+    pub(crate) fn update_clock_sync(&mut self) {
+        if let Some(server) = &self.clock_server {
+            if self.show_published.elapsed() >= PUBLISH_INTERVAL && !server.backups().is_empty() {
+                server.publish_show(self.show.snapshot());
+                self.show_published = Instant::now();
+            }
+        }
+        // A backup that has taken over runs by itself until handed back.
+        if self.backup_state == BackupState::TakenOver {
+            return;
+        }
+        let Some(client) = &self.clock_client else {
+            return;
+        };
+        let show = client.take_show();
+        let Some(main) = client.estimate() else {
+            self.clock_offset = None;
+            if self.preferences.tracking_backup && self.backup_state == BackupState::Standby {
+                self.take_over_output();
+                self.notify(
+                    LogLevel::Error,
+                    tr!(
+                        "clock-taken-over",
+                        main = self.preferences.clock_main.as_str()
+                    ),
+                );
+            }
+            return;
+        };
+        if self.backup_state == BackupState::Waiting {
+            self.backup_state = BackupState::Standby;
+            self.log.info(tr!(
+                "clock-following",
+                main = self.preferences.clock_main.as_str()
+            ));
+        }
+        if let Some(show) = show {
+            self.mirror_show(show);
+        }
+        // Wait for the engine to catch up with the last correction before
+        // measuring again.
+        if self.engine.is_behind() || self.panic.is_some() {
+            return;
+        }
+        let target = main.position_at(Instant::now());
+        let (local, running) = self.engine.playhead().position();
+        let offset = local.as_secs_f64() - target.as_secs_f64();
+        self.clock_offset = Some(offset);
+        if running == main.running && offset.abs() <= TOLERANCE {
+            return;
+        }
+        if running == main.running {
+            self.clock_corrections += 1;
+        }
+        self.elapsed = target;
+        self.engine.seek(target);
+        if self.transport.is_running() != main.running {
+            self.set_transport(if main.running {
+                Transport::Running
+            } else {
+                Transport::Stopped
+            });
+        }
+    }
+
+    /// Replaces the show with the main's copy, keeping the selection where
+    /// it still fits.
+    fn mirror_show(&mut self, show: Show) {
+        self.show = show;
+        // The main's copy is the one saved.
+        self.saved_show = self.show.snapshot();
+        self.selected_cue = self
+            .selected_cue
+            .filter(|&index| index < self.show.cues.len());
+        self.selected_pixel_map = self
+            .selected_pixel_map
+            .filter(|&index| index < self.show.pixel_maps.len());
+        self.load_pixel_media();
+    }
+}
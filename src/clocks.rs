@@ -0,0 +1,153 @@
+//! Clocks for changeover scheduling alongside the timecode: the time of day
+//! in other time zones, for a festival crew working to another country's
+//! schedule, and countdowns to the times the show turns on, such as doors
+//! and show start. They are set up with the show, and the big LED readout
+//! steps through them.
+//!
+//! Zones are fixed offsets from UTC, so a zone that changes for daylight
+//! saving needs its offset moving with it.
+
+use crate::i18n::tr;
+use chrono::{DateTime, FixedOffset, Local, NaiveTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The time of day somewhere else.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneClock {
+    pub name: String,
+    /// Minutes ahead of UTC; negative behind it.
+    pub utc_offset: i32,
+}
+
+impl ZoneClock {
+    pub fn new(name: &str, utc_offset: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            utc_offset,
+        }
+    }
+
+    /// The time there at `now`, as the readout shows the time of day.
+    pub fn format(&self, now: DateTime<Utc>) -> String {
+        let offset = FixedOffset::east_opt(self.utc_offset * 60)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).expect("UTC is a valid offset"));
+        now.with_timezone(&offset)
+            .format("%H:%M:%S.%3f")
+            .to_string()
+    }
+
+    /// The offset as UTC+hh:mm.
+    pub fn offset_label(&self) -> String {
+        let sign = if self.utc_offset < 0 { '-' } else { '+' };
+        let minutes = self.utc_offset.unsigned_abs();
+        format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+/// A countdown to a time of day, local to this computer.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Countdown {
+    pub name: String,
+    pub at: NaiveTime,
+}
+
+impl Countdown {
+    pub fn new(name: &str, at: NaiveTime) -> Self {
+        Self {
+            name: name.to_string(),
+            at,
+        }
+    }
+
+    /// How long until the time comes round at `now`, negative once it has
+    /// passed. Within twelve hours either way, so doors at 00:30 are still
+    /// ahead at 23:00, and a show start at 20:00 has passed at 23:00.
+    pub fn remaining(&self, now: DateTime<Local>) -> TimeDelta {
+        let day = TimeDelta::days(1);
+        let mut remaining = self.at - now.time();
+        if remaining > TimeDelta::hours(12) {
+            remaining -= day;
+        } else if remaining <= TimeDelta::hours(-12) {
+            remaining += day;
+        }
+        remaining
+    }
+
+    /// The countdown at `now` as the readout shows it: counting down with a
+    /// minus sign, then up from the time without one.
+    pub fn format(&self, now: DateTime<Local>) -> String {
+        let remaining = self.remaining(now);
+        let secs = remaining.num_seconds().unsigned_abs();
+        let clock = format!(
+            "{:02}:{:02}:{:02}",
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60
+        );
+        if remaining > TimeDelta::zero() {
+            format!("-{}", clock)
+        } else {
+            clock
+        }
+    }
+
+    /// "Doors in" or "Doors since", to go with [`Self::format`].
+    pub fn caption(&self, now: DateTime<Local>) -> String {
+        if self.remaining(now) > TimeDelta::zero() {
+            tr!("clocks-caption-in", name = self.name.as_str())
+        } else {
+            tr!("clocks-caption-since", name = self.name.as_str())
+        }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Clocks {
+    pub zones: Vec<ZoneClock>,
+    pub countdowns: Vec<Countdown>,
+}
+
+impl Clocks {
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty() && self.countdowns.is_empty()
+    }
+}
+
+/// What the big LED readout shows.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum Readout {
+    #[default]
+    Timecode,
+    /// The time of day here.
+    TimeOfDay,
+    Zone(usize),
+    Countdown(usize),
+}
+
+impl Readout {
+    /// The readout after this one, going through the show's clocks before
+    /// coming back round to the timecode.
+    pub fn next(self, clocks: &Clocks) -> Self {
+        let next = match self {
+            Readout::Timecode => Readout::TimeOfDay,
+            Readout::TimeOfDay => Readout::Zone(0),
+            Readout::Zone(i) => Readout::Zone(i + 1),
+            Readout::Countdown(i) => Readout::Countdown(i + 1),
+        };
+        match next {
+            Readout::Zone(i) if i >= clocks.zones.len() => Readout::Countdown(0).valid(clocks),
+            other => other.valid(clocks),
+        }
+    }
+
+    /// This readout, or the timecode if it is a clock the show no longer
+    /// has.
+    pub fn valid(self, clocks: &Clocks) -> Self {
+        match self {
+            Readout::Zone(i) if i >= clocks.zones.len() => Readout::Timecode,
+            Readout::Countdown(i) if i >= clocks.countdowns.len() => Readout::Timecode,
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,441 @@
+//! The command line: console-style syntax typed at the keyboard, which an
+//! experienced programmer gets through far faster than with the mouse.
+//!
+//! - `1 thru 12 @ 75`, `1 + 3 + 5 @ full`, `1 thru 10 - 4 @ out` and
+//!   `@ rel` set quick console levels by channel; see `quick_console`.
+//! - `1 thru 4 @ +10` and `@ -5` move them by percentage points, and
+//!   `@ +20%` and `@ -50%` scale them, keeping their proportions.
+//! - `group rgb par @ 50` does the same for every fixture of a type.
+//! - `cue 5` goes to a cue, and `cue 5 time 3` sets its fade time.
+//! - `record cue 7` records what's on stage into a cue, adding it if needed.
+//! - `go`, `stop`, `reset`, `song 2`, `bpm 128`, `macro Intro` and
+//!   `release all` do what they say.
+//!
+//! Words can be shortened to any unambiguous start, so `rec 7` records.
+
+use crate::cue::{Cue, CueNumber};
+use crate::i18n::tr;
+use crate::macros::MacroAction;
+use crate::quick_console::Relative;
+use crate::snapshot;
+use crate::HaloApp;
+use std::time::Duration;
+
+/// How many commands the history keeps.
+const HISTORY: usize = 100;
+
+const KEYWORDS: [&str; 20] = [
+    "all", "assert", "back", "bpm", "cue", "full", "go", "group", "macro", "out", "pause",
+    "record", "release", "reset", "song", "stop", "thru", "time", "@", "+",
+];
+
+/// What a level command does to the channels it selects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Level {
+    /// A level from 0 to 255.
+    Set(u8),
+    /// Back to whatever the cues give.
+    Release,
+    /// Moved from wherever each channel is.
+    Relative(Relative),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Selection {
+    /// Channel numbers, from 1, in the order given.
+    Channels(Vec<usize>),
+    /// Every fixture whose type starts with this, in any case.
+    Group(String),
+    /// Whatever the last level command selected.
+    Last,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Level {
+        selection: Selection,
+        level: Level,
+    },
+    CueTime {
+        cue: CueNumber,
+        time: Duration,
+    },
+    Record(CueNumber),
+    ReleaseAll,
+    /// Anything a macro step can do.
+    Action(MacroAction),
+}
+
+/// The full keyword `word` starts, if it starts exactly one.
+fn keyword(word: &str) -> Option<&'static str> {
+    if let Some(exact) = KEYWORDS.iter().find(|keyword| **keyword == word) {
+        return Some(exact);
+    }
+    let mut matches = KEYWORDS.iter().filter(|keyword| keyword.starts_with(word));
+    match (matches.next(), matches.next()) {
+        (Some(keyword), None) => Some(keyword),
+        _ => None,
+    }
+}
+
+/// Splits a command into lower-case words, with `@`, `+` and `-` as words
+/// of their own.
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .replace('@', " @ ")
+        .replace('+', " + ")
+        .replace('-', " - ")
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn parse(text: &str) -> Result<Command, String> {
+    let words = words(text);
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    if let Some(at) = words.iter().position(|word| *word == "@") {
+        let level = parse_level(&words[at + 1..])?;
+        let selection = match &words[..at] {
+            [] => Selection::Last,
+            [group, name @ ..] if keyword(group) == Some("group") => {
+                if name.is_empty() {
+                    return Err(tr!("command-group-type"));
+                }
+                Selection::Group(name.join(" "))
+            }
+            channels => Selection::Channels(parse_channels(channels)?),
+        };
+        return Ok(Command::Level { selection, level });
+    }
+
+    let first = words.first().ok_or_else(|| tr!("command-empty"))?;
+    let command = match (keyword(first), &words[1..]) {
+        (Some("cue"), [number]) => Command::Action(MacroAction::FireCue(cue_number(number)?)),
+        (Some("cue"), [number, time, seconds]) if keyword(time) == Some("time") => {
+            Command::CueTime {
+                cue: cue_number(number)?,
+                time: Duration::from_secs_f32(number_in(seconds, 0.0..=3600.0)?),
+            }
+        }
+        (Some("record"), [cue, number]) if keyword(cue) == Some("cue") => {
+            Command::Record(cue_number(number)?)
+        }
+        (Some("record"), [number]) => Command::Record(cue_number(number)?),
+        (Some("go"), []) => Command::Action(MacroAction::Start),
+        (Some("stop"), []) => Command::Action(MacroAction::Stop),
+        (Some("pause"), []) => Command::Action(MacroAction::Pause),
+        (Some("reset"), []) => Command::Action(MacroAction::Reset),
+        (Some("assert"), []) => Command::Action(MacroAction::Assert),
+        (Some("back"), []) => Command::Action(MacroAction::Back),
+        (Some("song"), [number]) => Command::Action(MacroAction::GoToSong(number_in(
+            number,
+            1.0..=999.0,
+        )? as usize)),
+        (Some("bpm"), [bpm]) => Command::Action(MacroAction::SetBpm(number_in(bpm, 20.0..=300.0)?)),
+        (Some("macro"), [_, ..]) => {
+            // Macro names keep their case.
+            let name = text
+                .trim()
+                .split_once(char::is_whitespace)
+                .map_or("", |(_, name)| name);
+            Command::Action(MacroAction::RunMacro(name.trim().to_string()))
+        }
+        (Some("release"), [all]) if keyword(all) == Some("all") => Command::ReleaseAll,
+        (Some(word), _) => return Err(tr!("command-unsure", word = word)),
+        (None, _) => return Err(tr!("command-unknown", word = *first)),
+    };
+    Ok(command)
+}
+
+fn parse_level(words: &[&str]) -> Result<Level, String> {
+    match words {
+        [word] => match keyword(word) {
+            Some("full") => Ok(Level::Set(255)),
+            Some("out") => Ok(Level::Set(0)),
+            Some("release") => Ok(Level::Release),
+            _ => Ok(Level::Set(
+                (number_in(word, 0.0..=100.0)? * 2.55).round() as u8
+            )),
+        },
+        [sign @ ("+" | "-"), amount] => {
+            let up = *sign == "+";
+            Ok(Level::Relative(match amount.strip_suffix('%') {
+                Some(percent) => {
+                    let most = if up { 1000.0 } else { 100.0 };
+                    let percent = number_in(percent, 0.0..=most)?;
+                    Relative::Scale(1.0 + if up { percent } else { -percent } / 100.0)
+                }
+                None => {
+                    let points = number_in(amount, 0.0..=100.0)?;
+                    Relative::By(if up { points } else { -points })
+                }
+            }))
+        }
+        [] => Err(tr!("command-level-missing")),
+        _ => Err(tr!("command-level-extra")),
+    }
+}
+
+/// Channel numbers from a selection like `1 thru 10 - 4 + 12`.
+fn parse_channels(words: &[&str]) -> Result<Vec<usize>, String> {
+    let mut channels: Vec<usize> = Vec::new();
+    let mut adding = true;
+    let mut words = words.iter().peekable();
+    while let Some(&word) = words.next() {
+        match word {
+            "+" => adding = true,
+            "-" => adding = false,
+            _ => {
+                let first = channel(word)?;
+                let last = if words
+                    .next_if(|word| keyword(word) == Some("thru"))
+                    .is_some()
+                {
+                    channel(words.next().ok_or_else(|| tr!("command-thru-channel"))?)?
+                } else {
+                    first
+                };
+                let range = first.min(last)..=first.max(last);
+                if adding {
+                    for channel in range {
+                        if !channels.contains(&channel) {
+                            channels.push(channel);
+                        }
+                    }
+                } else {
+                    channels.retain(|channel| !range.contains(channel));
+                }
+            }
+        }
+    }
+    if channels.is_empty() {
+        return Err(tr!("command-no-channels"));
+    }
+    Ok(channels)
+}
+
+fn channel(word: &str) -> Result<usize, String> {
+    word.parse()
+        .ok()
+        .filter(|&channel| channel > 0)
+        .ok_or_else(|| tr!("command-not-channel", word = word))
+}
+
+fn cue_number(word: &str) -> Result<CueNumber, String> {
+    CueNumber::parse(word).ok_or_else(|| tr!("command-not-cue-number", word = word))
+}
+
+fn number_in(word: &str, range: std::ops::RangeInclusive<f32>) -> Result<f32, String> {
+    let number: f32 = word
+        .parse()
+        .map_err(|_| tr!("command-not-number", word = word))?;
+    if !range.contains(&number) {
+        return Err(tr!(
+            "command-out-of-range",
+            word = word,
+            start = range.start().to_string(),
+            end = range.end().to_string()
+        ));
+    }
+    Ok(number)
+}
+
+/// Ways to finish the last word of `text`: keywords, and fixture types or
+/// macro names where those come next. Each is the whole line completed.
+pub fn complete(text: &str, groups: &[String], macros: &[String]) -> Vec<String> {
+    let (head, last) = match text.rfind(char::is_whitespace) {
+        Some(at) => text.split_at(at + 1),
+        None => ("", text),
+    };
+    let previous = words(head).last().and_then(|word| keyword(word));
+    let names = match previous {
+        Some("group") => groups,
+        Some("macro") => macros,
+        _ => &[],
+    };
+    if last.is_empty() && names.is_empty() {
+        return Vec::new();
+    }
+    let lower = last.to_lowercase();
+    let mut completions: Vec<String> = names
+        .iter()
+        .map(String::as_str)
+        .chain(KEYWORDS.iter().copied().filter(|keyword| keyword.len() > 1))
+        .filter(|word| word.to_lowercase().starts_with(&lower) && word.len() > last.len())
+        .map(|word| format!("{}{} ", head, word))
+        .collect();
+    completions.dedup();
+    completions
+}
+
+/// What the command line has been given: the line being typed, earlier
+/// commands and how the last one went.
+#[derive(Default)]
+pub struct CommandLine {
+    pub text: String,
+    pub history: Vec<String>,
+    /// Which history entry is shown while stepping back through them.
+    pub recalled: Option<usize>,
+    pub result: Option<Result<String, String>>,
+}
+
+impl CommandLine {
+    /// Shows the previous command, or the next one when `back` is false.
+    pub fn recall(&mut self, back: bool) {
+        let last = self.history.len().checked_sub(1);
+        self.recalled = match (self.recalled, back) {
+            (None, true) => last,
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => Some(index + 1).filter(|&next| Some(next) <= last),
+        };
+        self.text = self
+            .recalled
+            .map_or_else(String::new, |index| self.history[index].clone());
+    }
+
+    fn remember(&mut self, command: String) {
+        self.history.retain(|earlier| *earlier != command);
+        self.history.push(command);
+        if self.history.len() > HISTORY {
+            self.history.remove(0);
+        }
+        self.recalled = None;
+    }
+}
+
+impl HaloApp {
+    /// Runs the line typed into the command line, keeping it in the
+    /// history.
+    pub(crate) fn run_command_line(&mut self) {
+        let text = self.command_line.text.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let result = parse(&text).and_then(|command| self.run_command(command));
+        if result.is_ok() {
+            self.command_line.remember(text);
+            self.command_line.text.clear();
+        }
+        self.command_line.result = Some(result);
+    }
+
+    /// Carries out a parsed command, saying what it did.
+    fn run_command(&mut self, command: Command) -> Result<String, String> {
+        match command {
+            Command::Level { selection, level } => {
+                let ids = self.select_fixtures(selection)?;
+                match level {
+                    Level::Set(level) => {
+                        for id in &ids {
+                            self.quick_levels.insert(*id, level);
+                        }
+                    }
+                    Level::Release => {
+                        for id in &ids {
+                            self.quick_levels.remove(id);
+                        }
+                    }
+                    Level::Relative(change) => {
+                        let levels = self.quick_levels_of(&ids);
+                        self.set_quick_levels(&levels, change);
+                        return Ok(tr!("command-moved", count = levels.len()));
+                    }
+                }
+                Ok(tr!("command-set", count = ids.len()))
+            }
+            Command::CueTime { cue, time } => {
+                let found = self.show.cues.iter_mut().find(|c| c.number == cue);
+                let found = found.ok_or_else(|| tr!("command-no-cue", number = cue.to_string()))?;
+                found.duration = time;
+                Ok(tr!(
+                    "command-cue-time",
+                    number = cue.to_string(),
+                    seconds = format!("{:.1}", time.as_secs_f32())
+                ))
+            }
+            Command::Record(number) => Ok(self.record_cue(number)),
+            Command::ReleaseAll => {
+                self.quick_levels.clear();
+                Ok(tr!("command-released"))
+            }
+            Command::Action(action) => {
+                if let MacroAction::FireCue(number) = action {
+                    if !self.show.cues.iter().any(|cue| cue.number == number) {
+                        return Err(tr!("command-no-cue", number = number.to_string()));
+                    }
+                }
+                let done = action.to_string();
+                self.perform(action);
+                Ok(done)
+            }
+        }
+    }
+
+    /// The ids of the fixtures a level command selects, remembering channel
+    /// selections for the next command.
+    fn select_fixtures(&mut self, selection: Selection) -> Result<Vec<usize>, String> {
+        let channels = match selection {
+            Selection::Group(name) => {
+                let ids: Vec<usize> = self
+                    .show
+                    .fixtures
+                    .iter()
+                    .filter(|fixture| fixture.profile.name.to_lowercase().starts_with(&name))
+                    .map(|fixture| fixture.id)
+                    .collect();
+                if ids.is_empty() {
+                    return Err(tr!("command-no-group", name = name));
+                }
+                return Ok(ids);
+            }
+            Selection::Channels(channels) => {
+                self.quick_selection = channels.clone();
+                channels
+            }
+            Selection::Last if self.quick_selection.is_empty() => {
+                return Err(tr!("command-select-first"));
+            }
+            Selection::Last => self.quick_selection.clone(),
+        };
+        Ok(channels
+            .iter()
+            .filter_map(|&channel| self.show.fixtures.get(channel - 1))
+            .map(|fixture| fixture.id)
+            .collect())
+    }
+
+    /// Records everything on stage into the cue numbered `number`, adding
+    /// it at the playhead if there's no such cue.
+    fn record_cue(&mut self, number: CueNumber) -> String {
+        let values = snapshot::look(&self.programmed_output, &self.show.fixtures);
+        if let Some(index) = self.show.cues.iter().position(|cue| cue.number == number) {
+            self.show.cues[index].values = values;
+            self.selected_cue = Some(index);
+            return tr!("command-recorded", number = number.to_string());
+        }
+        let mut cue = Cue::new(
+            number,
+            &tr!("cue-number", number = number.to_string()),
+            0,
+            1,
+        );
+        cue.start_time = self.elapsed;
+        cue.values = values;
+        let index = self
+            .show
+            .cues
+            .iter()
+            .position(|cue| cue.number > number)
+            .unwrap_or(self.show.cues.len());
+        self.show.cues.insert(index, cue);
+        let song = index
+            .checked_sub(1)
+            .and_then(|previous| self.show.cues[previous].song);
+        if song.is_some() {
+            self.show.set_cue_song(index, song);
+        }
+        self.selected_cue = Some(index);
+        tr!("command-added", number = number.to_string())
+    }
+}
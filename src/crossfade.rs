@@ -0,0 +1,202 @@
+//! Manual A/B crossfade: a pair of faders the operator rides to move from the
+//! current cue to the next by hand, alongside the timed fades. The fader at
+//! the top takes the current cue out and the one at the bottom brings the
+//! next cue in, so the pair swaps roles after each crossfade, as on a
+//! two-preset desk.
+
+use crate::cue::Cue;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+
+/// A MIDI controller driving one fader. A channel of 0 matches any channel.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FaderControl {
+    pub channel: u8,
+    pub controller: u8,
+}
+
+impl FaderControl {
+    pub fn matches(&self, channel: u8, controller: u8) -> bool {
+        self.controller == controller && (self.channel == 0 || self.channel == channel)
+    }
+}
+
+/// The MIDI controllers assigned to the pair, kept with the show.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrossfadeSettings {
+    pub a: Option<FaderControl>,
+    pub b: Option<FaderControl>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Fader {
+    A,
+    B,
+}
+
+/// Where the two faders are, from 0 at the bottom to 1 at the top.
+pub struct CrossfadePair {
+    pub a: f32,
+    pub b: f32,
+    /// Set when B is the fader taking the current cue out.
+    swapped: bool,
+}
+
+impl CrossfadePair {
+    pub fn new() -> Self {
+        Self {
+            a: 1.0,
+            b: 0.0,
+            swapped: false,
+        }
+    }
+
+    /// The fader taking the current cue out.
+    pub fn outgoing(&self) -> Fader {
+        if self.swapped {
+            Fader::B
+        } else {
+            Fader::A
+        }
+    }
+
+    pub fn level(&self, fader: Fader) -> f32 {
+        match fader {
+            Fader::A => self.a,
+            Fader::B => self.b,
+        }
+    }
+
+    pub fn level_mut(&mut self, fader: Fader) -> &mut f32 {
+        match fader {
+            Fader::A => &mut self.a,
+            Fader::B => &mut self.b,
+        }
+    }
+
+    /// How far up the current cue and the next cue are.
+    fn levels(&self) -> (f32, f32) {
+        if self.swapped {
+            (self.b, self.a)
+        } else {
+            (self.a, self.b)
+        }
+    }
+
+    /// Whether either fader has left its starting end.
+    pub fn is_active(&self) -> bool {
+        let (outgoing, incoming) = self.levels();
+        outgoing < 1.0 || incoming > 0.0
+    }
+
+    fn is_complete(&self) -> bool {
+        let (outgoing, incoming) = self.levels();
+        outgoing <= 0.0 && incoming >= 1.0
+    }
+}
+
+/// A crossfade in progress, as the output engine applies it to the cues.
+#[derive(Clone, PartialEq)]
+pub struct ManualFade {
+    /// The cue going out, if the playhead has reached one.
+    pub from: Option<usize>,
+    pub to: usize,
+    pub outgoing: f32,
+    pub incoming: f32,
+}
+
+impl ManualFade {
+    /// The cues the crossfade moves.
+    pub fn cues(&self) -> impl Iterator<Item = usize> {
+        self.from.into_iter().chain([self.to])
+    }
+
+    /// Scales the current cue down and brings the next one in at least as
+    /// far as the faders say, on top of the timed fades.
+    pub fn apply(&self, cues: &mut [Cue]) {
+        if let Some(cue) = self.from.and_then(|index| cues.get_mut(index)) {
+            cue.scale_progress(self.outgoing);
+        }
+        if let Some(cue) = cues.get_mut(self.to) {
+            cue.raise_progress(self.incoming);
+        }
+    }
+}
+
+impl HaloApp {
+    pub(crate) fn cue_index(&self, cue: &Cue) -> Option<usize> {
+        self.show
+            .cues
+            .iter()
+            .position(|other| std::ptr::eq(other, cue))
+    }
+
+    /// The crossfade for the engine, while the faders are away from rest.
+    pub(crate) fn manual_fade(&self) -> Option<ManualFade> {
+        if !self.crossfade.is_active() {
+            return None;
+        }
+        let to = self.next_cue().and_then(|cue| self.cue_index(cue))?;
+        let (outgoing, incoming) = self.crossfade.levels();
+        Some(ManualFade {
+            from: self.current_cue().and_then(|cue| self.cue_index(cue)),
+            to,
+            outgoing,
+            incoming,
+        })
+    }
+
+    /// Sets a fader from a MIDI controller, or assigns the controller to the
+    /// fader being learned.
+    pub(crate) fn receive_controller(&mut self, channel: u8, controller: u8, value: u8) {
+        if let Some(fader) = self.learn_fader.take() {
+            let control = Some(FaderControl {
+                channel,
+                controller,
+            });
+            match fader {
+                Fader::A => self.show.crossfade.a = control,
+                Fader::B => self.show.crossfade.b = control,
+            }
+            self.log.info(tr!(
+                "crossfade-learned",
+                controller = controller,
+                channel = channel,
+                fader = if fader == Fader::A { "A" } else { "B" }
+            ));
+            return;
+        }
+        let level = value as f32 / 127.0;
+        let settings = &self.show.crossfade;
+        if settings.a.is_some_and(|a| a.matches(channel, controller)) {
+            self.crossfade.a = level;
+        }
+        if settings.b.is_some_and(|b| b.matches(channel, controller)) {
+            self.crossfade.b = level;
+        }
+    }
+
+    /// Once both faders reach the far end, leaves the playhead at the end of
+    /// the next cue's fade, runs what the cue runs when it fires, and swaps
+    /// the faders' roles for the next crossfade.
+    pub(crate) fn update_crossfade(&mut self) {
+        if !self.crossfade.is_complete() {
+            return;
+        }
+        let Some(index) = self.next_cue().and_then(|cue| self.cue_index(cue)) else {
+            return;
+        };
+        self.crossfade.swapped = !self.crossfade.swapped;
+        self.go_to_cue(index);
+        self.locate(self.elapsed + self.show.cues[index].duration);
+        self.run_cue_actions(index, None);
+        let number = self.show.cues[index].number;
+        self.notify(
+            LogLevel::Info,
+            tr!("crossfade-done", number = number.to_string()),
+        );
+    }
+}
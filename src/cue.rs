@@ -0,0 +1,727 @@
+use crate::fade_curve::FadeCurve;
+use crate::fixture::{Attribute, AttributeGroup};
+use crate::hue_shift::HueShift;
+use crate::i18n::tr;
+use crate::tag::Tag;
+use crate::time_signature::BarBeat;
+use crate::trigger::CueTrigger;
+use crate::variable::Condition;
+use crate::variation::Variation;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Range;
+use std::time::Duration;
+
+/// A cue number such as `1`, `2.5` or `10.25`, stored in thousandths so point
+/// cues can be inserted between whole numbers without floating point drift.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CueNumber(u32);
+
+impl CueNumber {
+    const SCALE: u32 = 1000;
+    /// Cue 0, for looks that sit before the cue list.
+    pub const ZERO: CueNumber = CueNumber(0);
+    /// Cue 1, where numbering starts.
+    pub const FIRST: CueNumber = CueNumber(Self::SCALE);
+    /// The highest number a cue can have.
+    pub const MAX: CueNumber = CueNumber(u32::MAX);
+
+    /// Cue `number`, or `None` past [`Self::MAX`].
+    pub fn from_whole(number: u32) -> Option<Self> {
+        number.checked_mul(Self::SCALE).map(Self)
+    }
+
+    pub fn from_f32(number: f32) -> Self {
+        Self((number.max(0.0) * Self::SCALE as f32).round() as u32)
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (whole, frac) = text.split_once('.').unwrap_or((text, ""));
+        if frac.len() > 3 || (whole.is_empty() && frac.is_empty()) {
+            return None;
+        }
+        let whole: u32 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().ok()?
+        };
+        let frac: u32 = if frac.is_empty() {
+            0
+        } else {
+            format!("{:0<3}", frac).parse().ok()?
+        };
+        Some(Self(whole.checked_mul(Self::SCALE)?.checked_add(frac)?))
+    }
+
+    /// Picks the simplest number strictly between `self` and `next`, preferring
+    /// whole numbers, then halves, tenths and so on. With no following cue the
+    /// next whole number is used. `None` if there is no room, or the next
+    /// whole number would be past [`Self::MAX`].
+    pub fn point_after(self, next: Option<CueNumber>) -> Option<Self> {
+        let Some(next) = next else {
+            return Self::from_whole(self.0 / Self::SCALE + 1);
+        };
+        [1000, 500, 100, 50, 10, 5, 1]
+            .into_iter()
+            .filter_map(|step| (self.0 / step).checked_add(1)?.checked_mul(step))
+            .find(|&candidate| candidate < next.0)
+            .map(Self)
+    }
+}
+
+impl fmt::Display for CueNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / Self::SCALE;
+        let frac = self.0 % Self::SCALE;
+        if frac == 0 {
+            write!(f, "{}", whole)
+        } else {
+            let frac = format!("{:03}", frac);
+            write!(f, "{}.{}", whole, frac.trim_end_matches('0'))
+        }
+    }
+}
+
+// Cue numbers are written to show files as text ("2.5") so they read the
+// same as they do on screen.
+impl Serialize for CueNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CueNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        CueNumber::parse(&text)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid cue number {:?}", text)))
+    }
+}
+
+/// A level stored in a cue for one attribute of one patched fixture.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CueValue {
+    pub fixture_id: usize,
+    pub attribute: Attribute,
+    pub value: u8,
+}
+
+/// A set of fixtures within a cue that fades in its own time, such as the
+/// backlight taking 8s while the fronts take the cue's 3s.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct CuePart {
+    pub name: String,
+    /// Ids of the fixtures the part times.
+    pub fixtures: Vec<usize>,
+    /// How long after the cue fires the part starts to fade.
+    pub delay: Duration,
+    pub fade: Duration,
+}
+
+/// How far through a fade of `fade` after `delay` is at `elapsed` into the
+/// cue.
+fn delayed_progress(delay: Duration, fade: Duration, elapsed: Duration) -> f32 {
+    let Some(into) = elapsed.checked_sub(delay) else {
+        return 0.0;
+    };
+    if fade.is_zero() {
+        1.0
+    } else {
+        (into.as_secs_f32() / fade.as_secs_f32()).min(1.0)
+    }
+}
+
+impl CuePart {
+    /// How far through its fade the part is at `elapsed` into the cue.
+    fn progress(&self, elapsed: Duration) -> f32 {
+        delayed_progress(self.delay, self.fade, elapsed)
+    }
+}
+
+/// One group of attributes fading in its own time within a cue, such as
+/// colour taking 5s while intensity takes the cue's 2s.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GroupTiming {
+    pub group: AttributeGroup,
+    /// How long after the cue fires the group starts to fade.
+    pub delay: Duration,
+    pub fade: Duration,
+}
+
+impl GroupTiming {
+    fn progress(&self, elapsed: Duration) -> f32 {
+        delayed_progress(self.delay, self.fade, elapsed)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Cue {
+    pub number: CueNumber,
+    pub name: String,
+    /// When the cue fires, from the start of its song if it has one and from
+    /// the start of the show otherwise.
+    pub start_time: Duration,
+    pub duration: Duration,
+    pub values: Vec<CueValue>,
+    /// The shape of the fade on intensity channels.
+    #[serde(default, skip_serializing_if = "FadeCurve::is_linear")]
+    pub intensity_curve: FadeCurve,
+    /// The shape of the fade on colour, position and every other channel.
+    #[serde(default, skip_serializing_if = "FadeCurve::is_linear")]
+    pub attribute_curve: FadeCurve,
+    /// Fixtures that fade in their own time; the rest take `duration`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<CuePart>,
+    /// Attribute groups that fade in their own time; the rest take
+    /// `duration`. A part's timing comes before these for its fixtures.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timing: Vec<GroupTiming>,
+    /// Id of the song the cue belongs to; see `song::Song`.
+    #[serde(default)]
+    pub song: Option<u32>,
+    /// Standby notes shown to the operator and in exported cue sheets.
+    #[serde(default)]
+    pub notes: String,
+    /// Starts a new section of the cue list, such as an act or song, which
+    /// runs until the next cue with a section label.
+    #[serde(default)]
+    pub section: String,
+    /// Rhai script run when the cue fires; see `script` for the API.
+    #[serde(default)]
+    pub script: String,
+    /// Macro replayed when the cue fires.
+    #[serde(default)]
+    pub run_macro: Option<String>,
+    /// MIDI, OSC and webhooks sent when the cue starts or ends.
+    #[serde(default)]
+    pub triggers: Vec<CueTrigger>,
+    /// Left out of playback, without deleting it, until armed again.
+    #[serde(default)]
+    pub disarmed: bool,
+    /// Fires when the musical timeline reaches this bar and beat rather than
+    /// when the clock reaches `start_time`; see `beat_cue`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at_beat: Option<BarBeat>,
+    /// Plays only while this holds of the show variables; see `variable`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Condition>,
+    /// Holds every value that tracks into the cue; see `tracking`.
+    #[serde(default)]
+    pub block: bool,
+    /// Colour and icon shown against the cue in the list.
+    #[serde(default, skip_serializing_if = "Tag::is_empty")]
+    pub tag: Tag,
+    /// Turns the colours the cue gives round the wheel; see `hue_shift`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hue_shift: Option<HueShift>,
+    /// Moves some levels by a random amount each time the cue fires; see
+    /// `variation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variation: Option<Variation>,
+    /// Set when the cue's script called `skip()`; cleared when the playhead
+    /// moves back before the cue.
+    #[serde(skip)]
+    pub skipped: bool,
+    #[serde(skip)]
+    pub is_playing: bool,
+    #[serde(skip)]
+    pub progress: f32,
+    /// How far each of `parts` has faded, filled in alongside `progress`.
+    #[serde(skip)]
+    pub part_progress: Vec<f32>,
+    /// How far each of `timing` has faded, likewise.
+    #[serde(skip)]
+    pub timing_progress: Vec<f32>,
+}
+
+/// Compares what was programmed, ignoring the playback state the output
+/// engine fills in.
+impl PartialEq for Cue {
+    fn eq(&self, other: &Self) -> bool {
+        self.start_time == other.start_time
+            && self.disarmed == other.disarmed
+            && self.eq_off_timeline(other)
+    }
+}
+
+impl Cue {
+    /// Compares everything but the start time and whether the cue is
+    /// armed, which `Show::timeline_cues` changes on the way to the engine.
+    pub fn eq_off_timeline(&self, other: &Self) -> bool {
+        self.number == other.number
+            && self.name == other.name
+            && self.duration == other.duration
+            && self.values == other.values
+            && self.intensity_curve == other.intensity_curve
+            && self.attribute_curve == other.attribute_curve
+            && self.parts == other.parts
+            && self.timing == other.timing
+            && self.song == other.song
+            && self.notes == other.notes
+            && self.section == other.section
+            && self.script == other.script
+            && self.run_macro == other.run_macro
+            && self.triggers == other.triggers
+            && self.at_beat == other.at_beat
+            && self.condition == other.condition
+            && self.block == other.block
+            && self.tag == other.tag
+            && self.hue_shift == other.hue_shift
+            && self.variation == other.variation
+            && self.skipped == other.skipped
+    }
+}
+
+impl Cue {
+    pub fn new(number: CueNumber, name: &str, start_time_secs: u64, duration_secs: u64) -> Self {
+        Self {
+            number,
+            name: name.to_string(),
+            start_time: Duration::from_secs(start_time_secs),
+            duration: Duration::from_secs(duration_secs),
+            values: Vec::new(),
+            intensity_curve: FadeCurve::Linear,
+            attribute_curve: FadeCurve::Linear,
+            parts: Vec::new(),
+            timing: Vec::new(),
+            song: None,
+            notes: String::new(),
+            section: String::new(),
+            script: String::new(),
+            run_macro: None,
+            triggers: Vec::new(),
+            disarmed: false,
+            at_beat: None,
+            condition: None,
+            block: false,
+            tag: Tag::default(),
+            hue_shift: None,
+            variation: None,
+            skipped: false,
+            is_playing: false,
+            progress: 0.0,
+            part_progress: Vec::new(),
+            timing_progress: Vec::new(),
+        }
+    }
+
+    pub fn with_value(mut self, fixture_id: usize, attribute: Attribute, value: u8) -> Self {
+        self.values.push(CueValue {
+            fixture_id,
+            attribute,
+            value,
+        });
+        self
+    }
+
+    /// Sets what the cue gives one attribute of a fixture, replacing any
+    /// value it had.
+    pub fn set_value(&mut self, fixture_id: usize, attribute: Attribute, value: u8) {
+        match self
+            .values
+            .iter_mut()
+            .find(|v| v.fixture_id == fixture_id && v.attribute == attribute)
+        {
+            Some(existing) => existing.value = value,
+            None => self.values.push(CueValue {
+                fixture_id,
+                attribute,
+                value,
+            }),
+        }
+    }
+
+    /// Drops what the cue gives one attribute of a fixture, so it tracks
+    /// from the cues before.
+    pub fn clear_value(&mut self, fixture_id: usize, attribute: Attribute) {
+        self.values
+            .retain(|v| v.fixture_id != fixture_id || v.attribute != attribute);
+    }
+
+    /// Whether the cue's number, name or notes contain `query`, which is
+    /// expected to already be lower case.
+    pub fn matches(&self, query: &str) -> bool {
+        query.is_empty()
+            || self.number.to_string().contains(query)
+            || self.name.to_lowercase().contains(query)
+            || self.notes.to_lowercase().contains(query)
+    }
+
+    /// Whether playback takes the cue: it is armed and its script hasn't
+    /// skipped it.
+    pub fn is_live(&self) -> bool {
+        !self.disarmed && !self.skipped
+    }
+
+    /// How far the fade on one attribute of a fixture has got, in the time
+    /// of the first part the fixture is in, or else of the attribute's
+    /// group, and following its curve.
+    pub fn fade_level(&self, fixture_id: usize, attribute: Attribute) -> f32 {
+        let curve = match attribute {
+            Attribute::Intensity => self.intensity_curve,
+            _ => self.attribute_curve,
+        };
+        let part = self
+            .parts
+            .iter()
+            .position(|part| part.fixtures.contains(&fixture_id))
+            .and_then(|index| self.part_progress.get(index));
+        let group = || {
+            self.timing
+                .iter()
+                .position(|timing| timing.group == attribute.group())
+                .and_then(|index| self.timing_progress.get(index))
+        };
+        let progress = part.or_else(group).copied().unwrap_or(self.progress);
+        if attribute.is_wheel() {
+            return if progress > 0.0 { 1.0 } else { 0.0 };
+        }
+        curve.apply(progress)
+    }
+
+    /// Whether any of the cue has started to fade.
+    pub fn has_started(&self) -> bool {
+        self.progress > 0.0
+            || self
+                .part_progress
+                .iter()
+                .chain(&self.timing_progress)
+                .any(|&progress| progress > 0.0)
+    }
+
+    /// How long after it fires the cue's fades are all done: the end of its
+    /// own fade or of its longest part or group, whichever is later.
+    pub fn settles_after(&self) -> Duration {
+        let parts = self.parts.iter().map(|part| part.delay + part.fade);
+        let groups = self.timing.iter().map(|timing| timing.delay + timing.fade);
+        parts.chain(groups).fold(self.duration, Duration::max)
+    }
+
+    /// The cue's fade time, followed by any group's own, such as
+    /// `3.0s C5.0s P8.0s after 2.0s` for colour over 5s and position over
+    /// 8s after waiting 2s.
+    pub fn timing_label(&self) -> String {
+        let mut label = format!("{:.1}s", self.duration.as_secs_f32());
+        for timing in &self.timing {
+            label.push_str(&format!(
+                " {}{:.1}s",
+                timing.group.initial(),
+                timing.fade.as_secs_f32()
+            ));
+            if !timing.delay.is_zero() {
+                label.push_str(&tr!(
+                    "cue-timing-after",
+                    seconds = format!("{:.1}", timing.delay.as_secs_f32())
+                ));
+            }
+        }
+        label
+    }
+
+    /// The delay and fade of `group`: its own, or none and the cue's.
+    pub fn group_timing(&self, group: AttributeGroup) -> (Duration, Duration) {
+        self.timing
+            .iter()
+            .find(|timing| timing.group == group)
+            .map_or((Duration::ZERO, self.duration), |timing| {
+                (timing.delay, timing.fade)
+            })
+    }
+
+    /// Gives `group` its own delay and fade, or takes it back to the cue's
+    /// when they are the cue's anyway.
+    pub fn set_group_timing(&mut self, group: AttributeGroup, delay: Duration, fade: Duration) {
+        self.timing.retain(|timing| timing.group != group);
+        if delay.is_zero() && fade == self.duration {
+            return;
+        }
+        self.timing.push(GroupTiming { group, delay, fade });
+        self.timing.sort_by_key(|timing| {
+            AttributeGroup::ALL
+                .iter()
+                .position(|other| *other == timing.group)
+        });
+    }
+
+    /// Scales every part's progress by `f`, for an outgoing crossfade.
+    pub fn scale_progress(&mut self, f: f32) {
+        self.progress *= f;
+        for progress in self
+            .part_progress
+            .iter_mut()
+            .chain(&mut self.timing_progress)
+        {
+            *progress *= f;
+        }
+    }
+
+    /// Brings every part at least as far as `level`, for an incoming
+    /// crossfade.
+    pub fn raise_progress(&mut self, level: f32) {
+        self.progress = self.progress.max(level);
+        for progress in self
+            .part_progress
+            .iter_mut()
+            .chain(&mut self.timing_progress)
+        {
+            *progress = progress.max(level);
+        }
+    }
+
+    pub fn update(&mut self, current_time: Duration) {
+        let elapsed = current_time.checked_sub(self.start_time);
+        self.part_progress = self
+            .parts
+            .iter()
+            .map(|part| elapsed.map_or(0.0, |elapsed| part.progress(elapsed)))
+            .collect();
+        self.timing_progress = self
+            .timing
+            .iter()
+            .map(|timing| elapsed.map_or(0.0, |elapsed| timing.progress(elapsed)))
+            .collect();
+        if current_time >= self.start_time {
+            let elapsed_in_cue = current_time - self.start_time;
+            if self.duration.is_zero() {
+                // A zero-time cue snaps straight to its look.
+                self.is_playing = false;
+                self.progress = 1.0;
+            } else if elapsed_in_cue <= self.duration {
+                self.is_playing = true;
+                self.progress = elapsed_in_cue.as_secs_f32() / self.duration.as_secs_f32();
+            } else {
+                self.is_playing = false;
+                self.progress = 1.0;
+            }
+        } else {
+            self.is_playing = false;
+            self.progress = 0.0;
+        }
+    }
+}
+
+/// Inserts a point cue after the cue at `index`, numbered between it and the
+/// following cue and starting where the selected cue ends. Returns the index
+/// of the new cue, or `None` if there is no room between the two numbers.
+pub fn insert_point_cue(cues: &mut Vec<Cue>, index: usize) -> Option<usize> {
+    let current = cues.get(index)?;
+    let number = current
+        .number
+        .point_after(cues.get(index + 1).map(|cue| cue.number))?;
+    let start_time = current.start_time + current.duration;
+
+    let mut cue = Cue::new(
+        number,
+        &tr!("cue-number", number = number.to_string()),
+        0,
+        5,
+    );
+    cue.start_time = start_time;
+    cues.insert(index + 1, cue);
+    Some(index + 1)
+}
+
+/// Inserts copies of `copied` after the cue at `after`, or at the end of the
+/// list, with their start times moved by `offset` seconds. They are numbered
+/// between their neighbours where there is room, and after the last cue
+/// otherwise. Returns the indices of the new cues, or `None`, leaving the
+/// cues as they were, if numbering them would run past [`CueNumber::MAX`].
+pub fn paste_cues(
+    cues: &mut Vec<Cue>,
+    after: Option<usize>,
+    copied: &[Cue],
+    offset: f32,
+) -> Option<Range<usize>> {
+    let mut at = after.map_or(cues.len(), |index| (index + 1).min(cues.len()));
+    let next = cues.get(at).map(|cue| cue.number);
+    let mut previous = at.checked_sub(1).map(|index| cues[index].number);
+    let mut numbers = Vec::new();
+    for _ in copied {
+        let number = match previous {
+            Some(previous) => previous.point_after(next),
+            None => next.map_or(Some(CueNumber::FIRST), |next| {
+                CueNumber::ZERO.point_after(Some(next))
+            }),
+        };
+        let Some(number) = number else {
+            break;
+        };
+        numbers.push(number);
+        previous = Some(number);
+    }
+    if numbers.len() < copied.len() {
+        at = cues.len();
+        let last = cues.iter().map(|cue| cue.number).max();
+        let whole = last.map_or(1, |last| last.0 / CueNumber::SCALE + 1);
+        numbers = (0..copied.len())
+            .map(|i| CueNumber::from_whole(whole.checked_add(u32::try_from(i).ok()?)?))
+            .collect::<Option<_>>()?;
+    }
+
+    let pasted = copied.iter().zip(numbers).map(|(cue, number)| {
+        let start = (cue.start_time.as_secs_f32() + offset).max(0.0);
+        Cue {
+            number,
+            start_time: Duration::from_secs_f32(start),
+            skipped: false,
+            is_playing: false,
+            progress: 0.0,
+            part_progress: Vec::new(),
+            timing_progress: Vec::new(),
+            ..cue.clone()
+        }
+    });
+    cues.splice(at..at, pasted);
+    Some(at..at + copied.len())
+}
+
+/// Splits the cue list into sections at each cue with a section label. Cues
+/// before the first label form an untitled section.
+pub fn sections(cues: &[Cue]) -> Vec<(Option<String>, Range<usize>)> {
+    let mut sections: Vec<(Option<String>, Range<usize>)> = Vec::new();
+    for (i, cue) in cues.iter().enumerate() {
+        match sections.last_mut() {
+            Some((_, range)) if cue.section.is_empty() => range.end = i + 1,
+            _ => {
+                let title = (!cue.section.is_empty()).then(|| cue.section.clone());
+                sections.push((title, i..i + 1));
+            }
+        }
+    }
+    sections
+}
+
+/// Rewrites every cue number in list order, starting at `start` and stepping
+/// by `interval`. Returns the last cue's new number, or `None`, leaving the
+/// cues as they were, if the numbers would run past [`CueNumber::MAX`].
+pub fn renumber(cues: &mut [Cue], start: CueNumber, interval: CueNumber) -> Option<CueNumber> {
+    let interval = interval.0.max(1);
+    let steps = u32::try_from(cues.len().saturating_sub(1)).ok()?;
+    let last = CueNumber(start.0.checked_add(interval.checked_mul(steps)?)?);
+    for (i, cue) in cues.iter_mut().enumerate() {
+        cue.number = CueNumber(start.0 + interval * i as u32);
+    }
+    Some(last)
+}
+
+pub fn default_cues() -> Vec<Cue> {
+    let pars = 0..4;
+    let spots = 4..6;
+    let fronts = 7..13;
+
+    let mut opening = Cue::new(CueNumber::FIRST, "Opening", 2, 5);
+    for id in fronts.clone() {
+        opening = opening.with_value(id, Attribute::Intensity, 153);
+    }
+    opening = opening.with_value(6, Attribute::Haze, 64);
+
+    let mut verse = Cue::new(CueNumber(2 * CueNumber::SCALE), "First Verse", 8, 10);
+    for id in pars.clone() {
+        verse =
+            verse
+                .with_value(id, Attribute::Intensity, 255)
+                .with_value(id, Attribute::Blue, 255);
+    }
+
+    let mut chorus = Cue::new(CueNumber(3 * CueNumber::SCALE), "Chorus", 19, 8);
+    for id in pars.clone() {
+        chorus = chorus
+            .with_value(id, Attribute::Red, 255)
+            .with_value(id, Attribute::Blue, 0);
+    }
+    for id in spots.clone() {
+        chorus = chorus
+            .with_value(id, Attribute::Intensity, 255)
+            .with_value(id, Attribute::Pan, 96)
+            .with_value(id, Attribute::Tilt, 160);
+    }
+
+    let mut bridge = Cue::new(CueNumber(4 * CueNumber::SCALE), "Bridge", 28, 12);
+    for id in pars.clone() {
+        bridge = bridge.with_value(id, Attribute::Intensity, 76);
+    }
+    for id in spots.clone() {
+        bridge = bridge
+            .with_value(id, Attribute::Pan, 160)
+            .with_value(id, Attribute::Green, 255);
+    }
+
+    let mut finale = Cue::new(CueNumber(5 * CueNumber::SCALE), "Finale", 41, 6);
+    for id in pars.chain(spots).chain(fronts) {
+        finale = finale.with_value(id, Attribute::Intensity, 255);
+    }
+
+    vec![opening, verse, chorus, bridge, finale]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_time_cue_snaps_in() {
+        let mut cue = Cue::new(CueNumber::FIRST, "", 5, 0);
+        for (at, progress) in [(4_999, 0.0), (5_000, 1.0), (5_001, 1.0)] {
+            cue.update(Duration::from_millis(at));
+            assert_eq!(cue.progress, progress, "at {}ms", at);
+            assert!(!cue.is_playing);
+        }
+    }
+
+    #[test]
+    fn renumber_refuses_to_overflow() {
+        let mut cues: Vec<Cue> = (1..=3)
+            .map(|n| Cue::new(CueNumber::from_whole(n).unwrap(), "", 0, 1))
+            .collect();
+        let numbers = |cues: &[Cue]| cues.iter().map(|cue| cue.number).collect::<Vec<_>>();
+        let before = numbers(&cues);
+        let start = CueNumber(CueNumber::MAX.0 - 1500);
+        assert_eq!(renumber(&mut cues, start, CueNumber::FIRST), None);
+        assert_eq!(numbers(&cues), before);
+
+        let last = renumber(
+            &mut cues,
+            CueNumber::from_whole(10).unwrap(),
+            CueNumber(500),
+        );
+        assert_eq!(last, CueNumber::parse("11"));
+        assert_eq!(numbers(&cues)[1], CueNumber(10_500));
+    }
+
+    #[test]
+    fn numbers_past_the_highest_are_refused() {
+        assert_eq!(CueNumber::parse("4294967.295"), Some(CueNumber::MAX));
+        assert_eq!(CueNumber::parse("4294967.999"), None);
+        assert_eq!(CueNumber::parse("4294968"), None);
+        assert_eq!(
+            CueNumber::from_whole(4_294_967),
+            Some(CueNumber(4_294_967_000))
+        );
+        assert_eq!(CueNumber::from_whole(4_294_968), None);
+
+        let last = CueNumber::parse("4294967").unwrap();
+        assert_eq!(last.point_after(None), None);
+        assert_eq!(
+            last.point_after(Some(CueNumber::MAX)),
+            CueNumber::parse("4294967.1")
+        );
+        assert_eq!(CueNumber::MAX.point_after(None), None);
+        assert_eq!(CueNumber::MAX.point_after(Some(CueNumber::MAX)), None);
+    }
+
+    #[test]
+    fn paste_refuses_to_number_past_the_highest_cue() {
+        let mut cues: Vec<Cue> = [CueNumber(1000), CueNumber(1001), CueNumber::MAX]
+            .into_iter()
+            .map(|number| Cue::new(number, "", 0, 1))
+            .collect();
+        let copied = [Cue::new(CueNumber(5000), "", 0, 1)];
+        assert_eq!(paste_cues(&mut cues, Some(0), &copied, 0.0), None);
+        assert_eq!(cues.len(), 3);
+
+        cues.pop();
+        assert_eq!(paste_cues(&mut cues, Some(0), &copied, 0.0), Some(2..3));
+        assert_eq!(cues[2].number, CueNumber(2000));
+    }
+}
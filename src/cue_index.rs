@@ -0,0 +1,240 @@
+//! Cues indexed by time, so each engine tick only evaluates the cues whose
+//! fades could have moved since the last: a cue is settled at nothing before
+//! it starts and at its full look once every part and attribute group has
+//! finished, so only the cues whose fade overlaps the stretch of time the
+//! playhead crossed need updating. Shows with thousands of cues then cost
+//! about as much per frame as the handful fading at the time.
+
+use crate::cue::Cue;
+use std::ops::Bound;
+use std::time::Duration;
+
+/// When a cue's fades are all done.
+fn settled_at(cue: &Cue) -> Duration {
+    cue.start_time + cue.settles_after()
+}
+
+/// The indices of `times`, sorted by time, that fall within `range`.
+fn within(
+    times: &[(Duration, usize)],
+    range: (Bound<Duration>, Bound<Duration>),
+) -> &[(Duration, usize)] {
+    let start = match range.0 {
+        Bound::Included(from) => times.partition_point(|&(t, _)| t < from),
+        Bound::Excluded(from) => times.partition_point(|&(t, _)| t <= from),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.1 {
+        Bound::Included(to) => times.partition_point(|&(t, _)| t <= to),
+        Bound::Excluded(to) => times.partition_point(|&(t, _)| t < to),
+        Bound::Unbounded => times.len(),
+    };
+    &times[start..end.max(start)]
+}
+
+#[derive(Default)]
+pub struct CueIndex {
+    /// Every cue by start time.
+    starts: Vec<(Duration, usize)>,
+    /// Armed cues by start time, for firing.
+    armed_starts: Vec<(Duration, usize)>,
+    /// Armed cues by the end of their own fade, for ending.
+    armed_ends: Vec<(Duration, usize)>,
+    /// Longest any cue takes from starting to settling.
+    longest: Duration,
+    /// The time the cues were last brought up to, or `None` if they must
+    /// all be updated.
+    updated: Option<Duration>,
+}
+
+impl CueIndex {
+    pub fn new(cues: &[Cue]) -> Self {
+        let mut starts: Vec<(Duration, usize)> = cues
+            .iter()
+            .enumerate()
+            .map(|(i, cue)| (cue.start_time, i))
+            .collect();
+        starts.sort();
+        let armed = |&&(_, i): &&(Duration, usize)| !cues[i].disarmed;
+        let armed_starts = starts.iter().filter(armed).copied().collect();
+        let mut armed_ends: Vec<(Duration, usize)> = starts
+            .iter()
+            .filter(armed)
+            .map(|&(start, i)| (start + cues[i].duration, i))
+            .collect();
+        armed_ends.sort();
+        Self {
+            starts,
+            armed_starts,
+            armed_ends,
+            longest: cues
+                .iter()
+                .map(|cue| settled_at(cue) - cue.start_time)
+                .max()
+                .unwrap_or_default(),
+            updated: None,
+        }
+    }
+
+    /// Brings every cue's progress up to `elapsed`, evaluating only those
+    /// that could have changed since the last update.
+    pub fn update(&mut self, cues: &mut [Cue], elapsed: Duration) {
+        match self.updated {
+            None => {
+                for cue in cues.iter_mut() {
+                    cue.update(elapsed);
+                }
+            }
+            Some(updated) => {
+                let (from, to) = (updated.min(elapsed), updated.max(elapsed));
+                // Cues that started after `to` were at nothing either side,
+                // and ones that settled by `from` at their full look.
+                let window = (
+                    Bound::Included(from.saturating_sub(self.longest)),
+                    Bound::Included(to),
+                );
+                for &(_, i) in within(&self.starts, window) {
+                    if settled_at(&cues[i]) >= from {
+                        cues[i].update(elapsed);
+                    }
+                }
+            }
+        }
+        self.updated = Some(elapsed);
+    }
+
+    /// Armed cues whose start the playhead crossed going from `previous` to
+    /// `elapsed`, in list order. Cues at zero fire when starting from zero.
+    pub fn fired(&self, previous: Duration, elapsed: Duration) -> Vec<usize> {
+        let from = if previous.is_zero() {
+            Bound::Included(previous)
+        } else {
+            Bound::Excluded(previous)
+        };
+        let mut fired: Vec<usize> = within(&self.armed_starts, (from, Bound::Included(elapsed)))
+            .iter()
+            .map(|&(_, i)| i)
+            .collect();
+        fired.sort_unstable();
+        fired
+    }
+
+    /// Armed cues whose end the playhead crossed going from `previous` to
+    /// `elapsed`, in list order.
+    pub fn ended(&self, previous: Duration, elapsed: Duration) -> Vec<usize> {
+        let mut ended: Vec<usize> = within(
+            &self.armed_ends,
+            (Bound::Excluded(previous), Bound::Included(elapsed)),
+        )
+        .iter()
+        .map(|&(_, i)| i)
+        .collect();
+        ended.sort_unstable();
+        ended
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cue::{CueNumber, CuePart, GroupTiming};
+    use crate::fixture::AttributeGroup;
+
+    /// A small xorshift, so the cues are the same on every run.
+    struct Random(u64);
+
+    impl Random {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+
+        fn millis(&mut self, n: u64) -> Duration {
+            Duration::from_millis(self.below(n))
+        }
+    }
+
+    /// Ten thousand overlapping cues, some with parts or group timing that
+    /// outlast the cue's own fade, some zero-time and some disarmed.
+    fn cues() -> Vec<Cue> {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+        (0..10_000)
+            .map(|i| {
+                let mut cue = Cue::new(CueNumber::from_whole(i + 1).unwrap(), "", 0, 0);
+                cue.start_time = random.millis(3_600_000);
+                cue.duration = match random.below(50) {
+                    0 => Duration::ZERO,
+                    _ => Duration::from_millis(1 + random.below(20_000)),
+                };
+                if random.below(4) == 0 {
+                    cue.parts.push(CuePart {
+                        name: String::new(),
+                        fixtures: vec![1],
+                        delay: random.millis(10_000),
+                        fade: random.millis(60_000),
+                    });
+                }
+                if random.below(4) == 0 {
+                    cue.timing.push(GroupTiming {
+                        group: AttributeGroup::Color,
+                        delay: random.millis(5_000),
+                        fade: random.millis(30_000),
+                    });
+                }
+                cue.disarmed = random.below(10) == 0;
+                cue
+            })
+            .collect()
+    }
+
+    fn assert_same(indexed: &[Cue], full: &[Cue], at: Duration) {
+        for (a, b) in indexed.iter().zip(full) {
+            assert!(
+                a.progress == b.progress
+                    && a.is_playing == b.is_playing
+                    && a.part_progress == b.part_progress
+                    && a.timing_progress == b.timing_progress,
+                "cue {} differs at {:?}",
+                a.number,
+                at
+            );
+        }
+    }
+
+    #[test]
+    fn indexed_update_matches_full_update() {
+        let mut indexed = cues();
+        let mut full = indexed.clone();
+        let mut index = CueIndex::new(&indexed);
+        let mut random = Random(42);
+
+        // Playing forward frame by frame, seeking both ways, jumping back a
+        // little as Back does, and going back to the top.
+        let mut times = Vec::new();
+        let mut at = Duration::ZERO;
+        for _ in 0..2_000 {
+            at += Duration::from_millis(23);
+            times.push(at);
+        }
+        for _ in 0..500 {
+            at = match random.below(4) {
+                0 => random.millis(3_700_000),
+                1 => at.saturating_sub(random.millis(30_000)),
+                2 => at + random.millis(90_000),
+                _ => at + Duration::from_millis(23),
+            };
+            times.push(at);
+        }
+        times.extend([Duration::ZERO, Duration::from_secs(4_000), Duration::ZERO]);
+
+        for at in times {
+            index.update(&mut indexed, at);
+            for cue in &mut full {
+                cue.update(at);
+            }
+            assert_same(&indexed, &full, at);
+        }
+    }
+}
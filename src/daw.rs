@@ -0,0 +1,352 @@
+//! Cue times exchanged with a DAW as marker lists, so the lighting timeline
+//! and the audio session stay in step when the music edit changes. Cues go
+//! out as REAPER marker CSV or a Pro Tools marker listing, named `Q<number>
+//! <name>`; a list coming back moves each named cue to its marker, and
+//! markers that don't name a cue become new cues.
+
+use crate::cue::{self, Cue, CueNumber};
+use crate::i18n::tr;
+use crate::show::Show;
+use crate::HaloApp;
+use std::fmt::{self, Write};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Sample rate Pro Tools time references are written at.
+const SAMPLE_RATE: u64 = 48_000;
+const PRO_TOOLS_TITLE: &str = "M A R K E R S  L I S T I N G";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DawFormat {
+    /// REAPER's Region/Marker Manager CSV.
+    Reaper,
+    /// The markers section of a Pro Tools session info text export.
+    ProTools,
+}
+
+impl DawFormat {
+    pub const ALL: [DawFormat; 2] = [DawFormat::Reaper, DawFormat::ProTools];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            DawFormat::Reaper => "csv",
+            DawFormat::ProTools => "txt",
+        }
+    }
+}
+
+impl fmt::Display for DawFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            DawFormat::Reaper => tr!("daw-format-reaper"),
+            DawFormat::ProTools => tr!("daw-format-pro-tools"),
+        })
+    }
+}
+
+/// A marker read from a DAW's list, at its time on the show timeline.
+#[derive(Clone)]
+pub struct DawMarker {
+    pub time: Duration,
+    pub name: String,
+}
+
+impl DawMarker {
+    /// The cue a marker's name refers to, as `Q<number>` at its start.
+    pub fn cue_number(&self) -> Option<CueNumber> {
+        let first = self.name.split_whitespace().next()?;
+        let number = first.strip_prefix(['Q', 'q'])?;
+        CueNumber::parse(number)
+    }
+
+    /// The name without its leading cue number, for a new cue.
+    fn cue_name(&self) -> &str {
+        match self.cue_number() {
+            Some(_) => self
+                .name
+                .trim()
+                .split_once(char::is_whitespace)
+                .map_or("", |(_, name)| name.trim()),
+            None => self.name.trim(),
+        }
+    }
+}
+
+/// `m:ss.mmm`, as REAPER shows minutes and seconds.
+fn minutes_seconds(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}.{:03}", secs / 60, secs % 60, time.subsec_millis())
+}
+
+/// `hh:mm:ss:ff` at `fps`.
+fn timecode(time: Duration, fps: u32) -> String {
+    let secs = time.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        time.subsec_millis() * fps / 1000
+    )
+}
+
+/// Reads `hh:mm:ss:ff` timecode at `fps`, `[h:]m:ss[.mmm]` or plain seconds.
+/// Whether it was timecode is returned too, as timecode counts from the
+/// show's start timecode.
+fn parse_time(text: &str, fps: u32) -> Option<(Duration, bool)> {
+    let text = text.trim();
+    let parts: Vec<&str> = text.split([':', ';']).collect();
+    if parts.len() == 4 {
+        let numbers: Vec<u64> = parts
+            .iter()
+            .map(|part| part.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        let secs = numbers[0]
+            .checked_mul(3600)?
+            .checked_add(numbers[1].checked_mul(60)?)?
+            .checked_add(numbers[2])?;
+        let frames = Duration::try_from_secs_f64(numbers[3] as f64 / fps as f64).ok()?;
+        return Some((Duration::from_secs(secs).checked_add(frames)?, true));
+    }
+    let mut seconds = 0.0;
+    for part in &parts {
+        let value: f64 = part.trim().parse().ok()?;
+        seconds = seconds * 60.0 + value;
+    }
+    // Infinite, NaN and negative times, and those too long for a Duration,
+    // are no time at all.
+    let time = Duration::try_from_secs_f64(seconds).ok()?;
+    (parts.len() <= 3).then_some((time, false))
+}
+
+/// Splits a CSV line, honouring double quotes.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+fn csv_quote(text: &str) -> String {
+    if text.contains([',', '"']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// The name a cue goes out under.
+fn marker_name(cue: &Cue) -> String {
+    if cue.name.is_empty() {
+        format!("Q{}", cue.number)
+    } else {
+        format!("Q{} {}", cue.number, cue.name)
+    }
+}
+
+/// The show's cues as a marker list in `format`. Pro Tools locations are
+/// timecode at `fps`, from the show's start timecode.
+pub fn export(show: &Show, format: DawFormat, fps: u32) -> String {
+    let mut cues: Vec<(Duration, &Cue)> = show
+        .cues
+        .iter()
+        .map(|cue| (show.cue_start(cue), cue))
+        .collect();
+    cues.sort_by_key(|&(start, _)| start);
+    let mut text = String::new();
+    match format {
+        DawFormat::Reaper => {
+            text.push_str("#,Name,Start,End,Length\n");
+            for (i, (start, cue)) in cues.iter().enumerate() {
+                let _ = writeln!(
+                    text,
+                    "M{},{},{},,",
+                    i + 1,
+                    csv_quote(&marker_name(cue)),
+                    minutes_seconds(*start)
+                );
+            }
+        }
+        DawFormat::ProTools => {
+            let _ = writeln!(text, "{}", PRO_TOOLS_TITLE);
+            text.push_str("#   \tLOCATION     \tTIME REFERENCE    \tUNITS    \tNAME\tCOMMENTS\n");
+            for (i, (start, cue)) in cues.iter().enumerate() {
+                let location = show.timecode.start + *start;
+                let _ = writeln!(
+                    text,
+                    "{}\t{}\t{}\tSamples\t{}\t",
+                    i + 1,
+                    timecode(location, fps),
+                    (location.as_secs_f64() * SAMPLE_RATE as f64).round() as u64,
+                    marker_name(cue)
+                );
+            }
+        }
+    }
+    text
+}
+
+/// Reads a REAPER marker CSV or a Pro Tools marker listing, going by the
+/// contents. Timecode locations are taken at `fps` and counted from the
+/// show's start timecode `start`.
+pub fn load(path: &Path, fps: u32, start: Duration) -> io::Result<Vec<DawMarker>> {
+    let text = fs::read_to_string(path)?;
+    let markers = if text.contains(PRO_TOOLS_TITLE) || text.contains("\tLOCATION") {
+        parse_pro_tools(&text, fps, start)
+    } else {
+        parse_reaper(&text, fps, start)
+    }?;
+    if markers.is_empty() {
+        return Err(io::Error::other(tr!("daw-no-markers")));
+    }
+    Ok(markers)
+}
+
+fn show_time((time, is_timecode): (Duration, bool), start: Duration) -> Duration {
+    if is_timecode {
+        time.saturating_sub(start)
+    } else {
+        time
+    }
+}
+
+fn parse_reaper(text: &str, fps: u32, start: Duration) -> io::Result<Vec<DawMarker>> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = csv_fields(lines.next().unwrap_or_default());
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|field| field.trim().eq_ignore_ascii_case(name))
+            .ok_or_else(|| io::Error::other(tr!("daw-no-column", name = name)))
+    };
+    let (name, time) = (column("Name")?, column("Start")?);
+    let mut markers = Vec::new();
+    for (n, line) in lines.enumerate() {
+        let fields = csv_fields(line);
+        let Some(field) = fields.get(time) else {
+            continue;
+        };
+        let parsed = parse_time(field, fps)
+            .ok_or_else(|| io::Error::other(tr!("daw-bad-time", row = n + 2, time = field)))?;
+        markers.push(DawMarker {
+            time: show_time(parsed, start),
+            name: fields.get(name).cloned().unwrap_or_default(),
+        });
+    }
+    Ok(markers)
+}
+
+fn parse_pro_tools(text: &str, fps: u32, start: Duration) -> io::Result<Vec<DawMarker>> {
+    let mut markers = Vec::new();
+    let mut header: Option<Vec<String>> = None;
+    for line in text.lines() {
+        let fields: Vec<String> = line.split('\t').map(|f| f.trim().to_string()).collect();
+        let Some(header) = &header else {
+            if fields.iter().any(|field| field == "LOCATION") {
+                header = Some(fields);
+            }
+            continue;
+        };
+        // The listing ends at the first blank line.
+        if line.trim().is_empty() {
+            break;
+        }
+        let find = |name: &str| header.iter().position(|field| field == name);
+        let (Some(location), Some(name)) = (find("LOCATION"), find("NAME")) else {
+            return Err(io::Error::other(tr!("daw-no-location")));
+        };
+        let Some(parsed) = fields
+            .get(location)
+            .and_then(|field| parse_time(field, fps))
+        else {
+            return Err(io::Error::other(tr!(
+                "daw-bad-location",
+                line = line.trim()
+            )));
+        };
+        markers.push(DawMarker {
+            time: show_time(parsed, start),
+            name: fields.get(name).cloned().unwrap_or_default(),
+        });
+    }
+    Ok(markers)
+}
+
+impl HaloApp {
+    /// Moves each cue a marker names to the marker's time, and adds a cue
+    /// for every other marker. Returns how many cues moved and were added.
+    pub(crate) fn apply_daw_markers(&mut self, markers: &[DawMarker]) -> (usize, usize) {
+        let (mut moved, mut added) = (0, 0);
+        for marker in markers {
+            let existing = marker
+                .cue_number()
+                .and_then(|number| self.show.cues.iter().position(|cue| cue.number == number));
+            if let Some(index) = existing {
+                let song_start =
+                    |show: &Show| show.cue_start(&show.cues[index]) - show.cues[index].start_time;
+                // A marker before the start of the cue's song takes it out.
+                if song_start(&self.show) > marker.time {
+                    self.show.set_cue_song(index, None);
+                }
+                let start_time = marker.time - song_start(&self.show);
+                let cue = &mut self.show.cues[index];
+                if cue.start_time != start_time {
+                    cue.start_time = start_time;
+                    moved += 1;
+                }
+                continue;
+            }
+            let mut new = Cue::new(CueNumber::FIRST, marker.cue_name(), 0, 0);
+            new.start_time = marker.time;
+            let after = self
+                .show
+                .cues
+                .iter()
+                .rposition(|cue| self.show.cue_start(cue) <= marker.time);
+            let Some(pasted) = cue::paste_cues(&mut self.show.cues, after, &[new], 0.0) else {
+                self.warn_no_room(1);
+                continue;
+            };
+            let index = pasted.start;
+            if let Some(song) = after.and_then(|i| self.show.cues[i].song) {
+                self.show.set_cue_song(index, Some(song));
+            }
+            added += 1;
+        }
+        (moved, added)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn times_too_large_for_a_duration_are_refused() {
+        assert_eq!(
+            parse_time("1:02.5", 25),
+            Some((Duration::from_millis(62_500), false))
+        );
+        assert_eq!(
+            parse_time("01:00:00:05", 25),
+            Some((Duration::from_millis(3_600_200), true))
+        );
+        for text in ["inf", "1e400", "-1", "NaN", "1e300"] {
+            assert_eq!(parse_time(text, 25), None, "{}", text);
+        }
+        assert_eq!(parse_time("18446744073709551615:00:00:00", 25), None);
+    }
+}
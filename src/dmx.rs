@@ -0,0 +1,168 @@
+use crate::cue::Cue;
+use crate::fixture::{Attribute, Fixture};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub const UNIVERSE_SIZE: usize = 512;
+
+/// Rate DMX frames are meant to go out at; the usual ceiling for a full
+/// universe on the wire.
+pub const OUTPUT_HZ: f32 = 44.0;
+
+/// One frame of output across every patched universe, keyed by universe number.
+#[derive(Clone, Default, PartialEq)]
+pub struct DmxFrame {
+    universes: BTreeMap<u16, [u8; UNIVERSE_SIZE]>,
+}
+
+impl DmxFrame {
+    /// Returns the level of a 1-based channel, or zero if the universe is not
+    /// present in this frame.
+    pub fn get(&self, universe: u16, channel: u16) -> u8 {
+        self.universes
+            .get(&universe)
+            .and_then(|data| data.get((channel as usize).checked_sub(1)?))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn set(&mut self, universe: u16, channel: u16, value: u8) {
+        if (1..=UNIVERSE_SIZE as u16).contains(&channel) {
+            self.universe_mut(universe)[channel as usize - 1] = value;
+        }
+    }
+
+    pub fn universe(&self, universe: u16) -> Option<&[u8; UNIVERSE_SIZE]> {
+        self.universes.get(&universe)
+    }
+
+    pub fn remove_universe(&mut self, universe: u16) {
+        self.universes.remove(&universe);
+    }
+
+    pub fn universe_mut(&mut self, universe: u16) -> &mut [u8; UNIVERSE_SIZE] {
+        self.universes.entry(universe).or_insert([0; UNIVERSE_SIZE])
+    }
+
+    pub fn universe_numbers(&self) -> impl Iterator<Item = u16> + '_ {
+        self.universes.keys().copied()
+    }
+}
+
+// Frames are written to show files as one hex string per universe, which
+// keeps a captured snapshot to a line instead of 512 numbers.
+impl Serialize for DmxFrame {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.universes.iter().map(|(universe, data)| {
+            let hex: String = data.iter().map(|level| format!("{:02x}", level)).collect();
+            (universe, hex)
+        }))
+    }
+}
+
+impl<'de> Deserialize<'de> for DmxFrame {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let written = BTreeMap::<u16, String>::deserialize(deserializer)?;
+        let mut frame = DmxFrame::default();
+        for (universe, hex) in written {
+            let invalid =
+                || serde::de::Error::custom(format!("invalid levels for universe {}", universe));
+            if hex.len() != UNIVERSE_SIZE * 2 || !hex.is_ascii() {
+                return Err(invalid());
+            }
+            let data = frame.universe_mut(universe);
+            for (level, pair) in data.iter_mut().zip(hex.as_bytes().chunks(2)) {
+                let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+                *level = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+            }
+        }
+        Ok(frame)
+    }
+}
+
+/// Raw channel check: one channel forced to a test level, for tracking down
+/// mystery dimmers.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ChannelCheck {
+    pub universe: u16,
+    /// 1-based channel.
+    pub channel: u16,
+    pub level: u8,
+    /// Black out everything else so only the checked channel is lit.
+    pub solo: bool,
+}
+
+impl ChannelCheck {
+    pub fn new(universe: u16) -> Self {
+        Self {
+            universe,
+            channel: 1,
+            level: 255,
+            solo: true,
+        }
+    }
+
+    /// Moves to the next or previous channel, wrapping within the universe
+    /// and stepping over the `skipped` ones, such as inhibited fixtures'.
+    pub fn step(&mut self, forward: bool, skipped: &HashSet<(u16, u16)>) {
+        let size = UNIVERSE_SIZE as u16;
+        for _ in 0..size {
+            self.channel = if forward {
+                self.channel % size + 1
+            } else {
+                (self.channel + size - 2) % size + 1
+            };
+            if !skipped.contains(&(self.universe, self.channel)) {
+                break;
+            }
+        }
+    }
+
+    /// Applies the check on top of everything else in `frame`.
+    pub fn apply(&self, frame: &mut DmxFrame) {
+        if self.solo {
+            for universe in frame.universes.values_mut() {
+                universe.fill(0);
+            }
+        }
+        frame.set(self.universe, self.channel, self.level);
+    }
+}
+
+/// Computes the output for the current playback state. Cues are applied in
+/// start-time order with latest-takes-precedence, each crossfading from the
+/// level left by the cues before it according to its progress, or its parts',
+/// along its fade curves.
+pub fn render(fixtures: &[Fixture], cues: &[Cue]) -> DmxFrame {
+    let mut order: Vec<&Cue> = cues
+        .iter()
+        .filter(|cue| cue.has_started() && cue.is_live())
+        .collect();
+    order.sort_by_key(|cue| cue.start_time);
+
+    let mut levels: HashMap<(usize, Attribute), f32> = HashMap::new();
+    for cue in order {
+        for value in &cue.values {
+            let level = levels
+                .entry((value.fixture_id, value.attribute))
+                .or_insert(0.0);
+            *level +=
+                (value.value as f32 - *level) * cue.fade_level(value.fixture_id, value.attribute);
+        }
+    }
+
+    let mut frame = DmxFrame::default();
+    for fixture in fixtures {
+        frame.universe_mut(fixture.universe);
+        for attribute in &fixture.profile.channels {
+            let level = levels
+                .get(&(fixture.id, *attribute))
+                .copied()
+                .unwrap_or(0.0);
+            if let Some(channel) = fixture.channel_for(*attribute) {
+                frame.set(fixture.universe, channel, level.round() as u8);
+            }
+        }
+    }
+    frame
+}
@@ -0,0 +1,194 @@
+//! Fader wings over DMX. A cheap wing on an Art-Net or sACN input node can
+//! move the grand master and the group and speed masters, with no MIDI
+//! hardware: each master can follow one channel of the universes read for
+//! faders, picked by moving the fader while learning. A master only follows
+//! its channel when the channel changes, so it can still be moved on screen.
+
+use crate::dmx::UNIVERSE_SIZE;
+use crate::i18n::tr;
+use crate::speed_master::MAX_RATE;
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// A channel has moved, for learning, once it changes by this much.
+const LEARN_THRESHOLD: u8 = 16;
+/// How often the wing is read while anything follows it.
+const POLL: Duration = Duration::from_millis(40);
+
+/// The channel of an incoming universe a fader sends on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DmxFader {
+    pub universe: u16,
+    /// From 1.
+    pub channel: u16,
+}
+
+impl fmt::Display for DmxFader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.universe, self.channel)
+    }
+}
+
+/// What a fader on the wing drives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FaderTarget {
+    /// The grand master.
+    Grand,
+    /// The group master at this position.
+    Group(usize),
+    /// The speed master at this position.
+    Speed(usize),
+}
+
+/// A fader being learned, with each fader universe as it was when learning
+/// started, to tell which channel moves.
+pub struct DmxLearn {
+    pub target: FaderTarget,
+    baseline: HashMap<u16, [u8; UNIVERSE_SIZE]>,
+}
+
+impl HaloApp {
+    /// Starts learning the fader for `target`, or stops if it is already
+    /// being learned.
+    pub(crate) fn toggle_dmx_learn(&mut self, target: FaderTarget) {
+        if self
+            .learn_dmx_fader
+            .as_ref()
+            .is_some_and(|learn| learn.target == target)
+        {
+            self.learn_dmx_fader = None;
+            return;
+        }
+        if self.preferences.network_input.fader_universes.is_empty() {
+            self.log.warn(tr!("masters-no-fader-universe"));
+            return;
+        }
+        self.learn_dmx_fader = Some(DmxLearn {
+            target,
+            baseline: HashMap::new(),
+        });
+    }
+
+    fn dmx_fader_mut(&mut self, target: FaderTarget) -> Option<&mut Option<DmxFader>> {
+        match target {
+            FaderTarget::Grand => Some(&mut self.show.grand_master_fader),
+            FaderTarget::Group(index) => {
+                self.show.group_masters.get_mut(index).map(|m| &mut m.fader)
+            }
+            FaderTarget::Speed(index) => {
+                self.show.speed_masters.get_mut(index).map(|m| &mut m.fader)
+            }
+        }
+    }
+
+    fn set_fader_target(&mut self, target: FaderTarget, value: u8) {
+        let level = value as f32 / 255.0;
+        match target {
+            FaderTarget::Grand => self.grand_master = level,
+            FaderTarget::Group(index) => {
+                if let Some(master) = self.show.group_masters.get_mut(index) {
+                    master.level = level;
+                }
+            }
+            FaderTarget::Speed(index) => {
+                if let Some(master) = self.show.speed_masters.get_mut(index) {
+                    master.rate = level * MAX_RATE;
+                }
+            }
+        }
+    }
+
+    /// Assigns the first channel to move to the fader being learned, and
+    /// moves the masters whose channels changed since the last frame.
+    pub(crate) fn update_dmx_faders(&mut self, ctx: &egui::Context) {
+        if self.network_input.is_none() {
+            return;
+        }
+        let mapped = self.show.grand_master_fader.is_some()
+            || self.show.group_masters.iter().any(|m| m.fader.is_some())
+            || self.show.speed_masters.iter().any(|m| m.fader.is_some());
+        if !mapped && self.learn_dmx_fader.is_none() {
+            return;
+        }
+        // Incoming DMX doesn't wake the UI, so look again shortly.
+        ctx.request_repaint_after(POLL);
+        let universes = self.preferences.network_input.fader_universes.clone();
+        let levels: Vec<(u16, [u8; UNIVERSE_SIZE])> = universes
+            .iter()
+            .filter_map(|&universe| Some((universe, self.dmx_input.universe(universe)?)))
+            .collect();
+
+        if let Some(learn) = &mut self.learn_dmx_fader {
+            let mut moved = None;
+            for (universe, data) in &levels {
+                let baseline = learn.baseline.entry(*universe).or_insert(*data);
+                if let Some(channel) = data
+                    .iter()
+                    .zip(baseline.iter())
+                    .position(|(now, then)| now.abs_diff(*then) >= LEARN_THRESHOLD)
+                {
+                    moved = Some(DmxFader {
+                        universe: *universe,
+                        channel: channel as u16 + 1,
+                    });
+                    break;
+                }
+            }
+            if let Some(fader) = moved {
+                let target = learn.target;
+                self.learn_dmx_fader = None;
+                let name = match target {
+                    FaderTarget::Grand => Some(tr!("masters-the-grand-master")),
+                    FaderTarget::Group(index) => {
+                        self.show.group_masters.get(index).map(|m| m.name.clone())
+                    }
+                    FaderTarget::Speed(index) => {
+                        self.show.speed_masters.get(index).map(|m| m.name.clone())
+                    }
+                };
+                if let (Some(slot), Some(name)) = (self.dmx_fader_mut(target), name) {
+                    *slot = Some(fader);
+                    self.log.info(tr!(
+                        "masters-learned-dmx",
+                        fader = fader.to_string(),
+                        name = name
+                    ));
+                }
+            }
+            return;
+        }
+
+        let mut mapped = Vec::new();
+        if let Some(fader) = self.show.grand_master_fader {
+            mapped.push((fader, FaderTarget::Grand));
+        }
+        for (index, master) in self.show.group_masters.iter().enumerate() {
+            if let Some(fader) = master.fader {
+                mapped.push((fader, FaderTarget::Group(index)));
+            }
+        }
+        for (index, master) in self.show.speed_masters.iter().enumerate() {
+            if let Some(fader) = master.fader {
+                mapped.push((fader, FaderTarget::Speed(index)));
+            }
+        }
+        for (fader, target) in mapped {
+            let Some((_, data)) = levels
+                .iter()
+                .find(|(universe, _)| *universe == fader.universe)
+            else {
+                continue;
+            };
+            let Some(&value) = data.get(fader.channel as usize - 1) else {
+                continue;
+            };
+            if self.dmx_fader_values.insert(fader, value) != Some(value) {
+                self.set_fader_target(target, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,522 @@
+//! Art-Net and sACN input, merged with Halo's own output so a fader wing or
+//! another console can ride levels on top of playback. Packets are received on
+//! background threads into a shared [`DmxInput`], which the output engine
+//! merges into each frame on the selected universes.
+//!
+//! Universes are numbered as in the rest of Halo, from 1. sACN universes map
+//! straight across; Art-Net port-address 0 is Halo universe 1.
+//!
+//! Sources on the same universe are arbitrated address by address, as E1.31
+//! receivers do: each address goes to the highest priority sending it, from
+//! the per-address priority of sACN sources that send one (0xDD) and the
+//! universe priority of the rest, and sources at the same priority merge
+//! highest level wins.
+
+use crate::art_net::{ArtNetListener, ART_NET_ID, OP_DMX};
+use crate::dmx::{DmxFrame, UNIVERSE_SIZE};
+use crate::i18n::tr;
+use crate::interfaces::{NetworkInterface, Protocol};
+use crate::packet_inspector::PacketInspector;
+use crate::sacn::{self, PRIORITY_START_CODE, SACN_PORT};
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const ACN_ID: &[u8] = b"ASC-E1.17\0\0\0";
+/// How long receive threads wait for a packet before checking whether
+/// they've been stopped.
+const RECEIVE_TIMEOUT: Duration = Duration::from_millis(250);
+/// Sources that stop sending are dropped after this long, as in E1.31.
+const SOURCE_TIMEOUT: Duration = Duration::from_millis(2500);
+/// sACN priority assumed for Art-Net, which has none.
+const DEFAULT_PRIORITY: u8 = 100;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MergeMode {
+    /// Highest level wins on every channel.
+    Htp,
+    /// The input wins each address it sends at a higher priority than
+    /// Halo's, and loses it at a lower one; equal priorities merge highest
+    /// level wins.
+    Priority,
+}
+
+impl MergeMode {
+    pub const ALL: [MergeMode; 2] = [MergeMode::Htp, MergeMode::Priority];
+}
+
+impl fmt::Display for MergeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            MergeMode::Htp => "HTP".to_string(),
+            MergeMode::Priority => tr!("merge-mode-priority"),
+        })
+    }
+}
+
+/// What to listen for and how to merge it, kept in the preferences since it
+/// depends on the desk rather than the show.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputSettings {
+    pub art_net: bool,
+    pub sacn: bool,
+    /// Universes to merge; anything else received is ignored.
+    pub universes: Vec<u16>,
+    pub mode: MergeMode,
+    /// Priority Halo's own output is merged at, in the sACN range 0 to 200.
+    pub priority: u8,
+    /// Universes read for fader wings driving masters; see `dmx_fader`.
+    /// They are not merged into the output.
+    pub fader_universes: Vec<u16>,
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
+        Self {
+            art_net: false,
+            sacn: false,
+            universes: vec![1],
+            mode: MergeMode::Htp,
+            priority: DEFAULT_PRIORITY,
+            fader_universes: Vec::new(),
+        }
+    }
+}
+
+impl InputSettings {
+    pub fn enabled(&self) -> bool {
+        (self.art_net || self.sacn) && !self.listened().is_empty()
+    }
+
+    /// Every universe received: those merged and those read for faders.
+    fn listened(&self) -> Vec<u16> {
+        let mut universes = self.universes.clone();
+        universes.extend(&self.fader_universes);
+        universes.sort_unstable();
+        universes.dedup();
+        universes
+    }
+}
+
+/// A console or wing currently sending on one universe.
+#[derive(Clone)]
+pub struct Source {
+    pub universe: u16,
+    pub protocol: &'static str,
+    /// The sACN source name, or the sender's address for Art-Net.
+    pub name: String,
+    pub priority: u8,
+    /// Packets a second, smoothed over the last few seconds.
+    pub rate: f32,
+    /// The packet's sequence number; 0 from Art-Net senders that don't
+    /// number their packets.
+    pub sequence: u8,
+    data: [u8; UNIVERSE_SIZE],
+    received: Instant,
+    /// The per-address priorities the source last sent, and when.
+    address_priorities: Option<(Instant, Box<[u8; UNIVERSE_SIZE]>)>,
+}
+
+impl Source {
+    /// Whether the source is sending per-address priority.
+    pub fn per_address(&self) -> bool {
+        self.address_priorities
+            .as_ref()
+            .is_some_and(|(received, _)| received.elapsed() < SOURCE_TIMEOUT)
+    }
+
+    /// The priority the source sends `address` at: its per-address priority
+    /// while it sends one, else its universe priority. None where its
+    /// per-address priority is 0, meaning it isn't sending the address.
+    fn priority_at(&self, address: usize) -> Option<u8> {
+        match &self.address_priorities {
+            Some((received, priorities)) if received.elapsed() < SOURCE_TIMEOUT => {
+                Some(priorities[address]).filter(|&priority| priority > 0)
+            }
+            _ => Some(self.priority),
+        }
+    }
+}
+
+/// The levels of `sources` on one universe, arbitrated address by address,
+/// with the priority that won each address, or None where none sends it.
+fn arbitrate(sources: &[&Source]) -> ([u8; UNIVERSE_SIZE], [Option<u8>; UNIVERSE_SIZE]) {
+    let mut levels = [0u8; UNIVERSE_SIZE];
+    let mut winners = [None; UNIVERSE_SIZE];
+    for source in sources {
+        for (address, (level, winner)) in levels.iter_mut().zip(&mut winners).enumerate() {
+            let Some(priority) = source.priority_at(address) else {
+                continue;
+            };
+            let value = source.data[address];
+            match *winner {
+                Some(top) if top > priority => {}
+                Some(top) if top == priority => *level = (*level).max(value),
+                _ => {
+                    *winner = Some(priority);
+                    *level = value;
+                }
+            }
+        }
+    }
+    (levels, winners)
+}
+
+/// Latest data from every live source, shared between the receive threads
+/// and the output engine.
+#[derive(Clone, Default)]
+pub struct DmxInput(Arc<Mutex<HashMap<(u16, SocketAddr), Source>>>);
+
+impl DmxInput {
+    fn receive(&self, from: SocketAddr, mut source: Source) {
+        if let Ok(mut sources) = self.0.lock() {
+            if let Some(previous) = sources.get_mut(&(source.universe, from)) {
+                let interval = source.received - previous.received;
+                source.rate = previous.rate * 0.9 + 0.1 / interval.as_secs_f32().max(0.001);
+                source.address_priorities = previous.address_priorities.take();
+            }
+            sources.insert((source.universe, from), source);
+        }
+    }
+
+    /// Notes the per-address priorities a source sent, carried in place of
+    /// its levels. Those from a source not yet sending levels are dropped,
+    /// as they come round again within a second.
+    fn receive_priorities(&self, from: SocketAddr, priorities: &Source) {
+        if let Ok(mut sources) = self.0.lock() {
+            if let Some(source) = sources.get_mut(&(priorities.universe, from)) {
+                source.address_priorities = Some((priorities.received, Box::new(priorities.data)));
+            }
+        }
+    }
+
+    fn remove(&self, universe: u16, from: SocketAddr) {
+        if let Ok(mut sources) = self.0.lock() {
+            sources.remove(&(universe, from));
+        }
+    }
+
+    fn clear(&self) {
+        if let Ok(mut sources) = self.0.lock() {
+            sources.clear();
+        }
+    }
+
+    /// Live sources, ordered by universe.
+    pub fn sources(&self) -> Vec<Source> {
+        let Ok(mut sources) = self.0.lock() else {
+            return Vec::new();
+        };
+        sources.retain(|_, source| source.received.elapsed() < SOURCE_TIMEOUT);
+        let mut live: Vec<Source> = sources.values().cloned().collect();
+        live.sort_by(|a, b| (a.universe, &a.name).cmp(&(b.universe, &b.name)));
+        live
+    }
+
+    /// The live sources on `universe`, arbitrated.
+    pub fn universe(&self, universe: u16) -> Option<[u8; UNIVERSE_SIZE]> {
+        let sources = self.sources();
+        let sending: Vec<&Source> = sources
+            .iter()
+            .filter(|source| source.universe == universe)
+            .collect();
+        (!sending.is_empty()).then(|| arbitrate(&sending).0)
+    }
+
+    /// Every universe being received, each arbitrated as [`Self::universe`]
+    /// arbitrates it.
+    pub fn frame(&self) -> DmxFrame {
+        let sources = self.sources();
+        let mut universes: Vec<u16> = sources.iter().map(|source| source.universe).collect();
+        universes.dedup();
+        let mut frame = DmxFrame::default();
+        for universe in universes {
+            let sending: Vec<&Source> = sources
+                .iter()
+                .filter(|source| source.universe == universe)
+                .collect();
+            *frame.universe_mut(universe) = arbitrate(&sending).0;
+        }
+        frame
+    }
+
+    /// Merges the live sources into `frame` on the selected universes.
+    pub fn merge(&self, settings: &InputSettings, frame: &mut DmxFrame) {
+        let sources = self.sources();
+        for &universe in &settings.universes {
+            let sending: Vec<&Source> = sources
+                .iter()
+                .filter(|source| source.universe == universe)
+                .collect();
+            if sending.is_empty() {
+                continue;
+            }
+            // Sources on the same universe arbitrate among themselves first.
+            let (input, winners) = arbitrate(&sending);
+
+            let output = frame.universe_mut(universe);
+            for ((level, value), winner) in output.iter_mut().zip(input).zip(winners) {
+                let Some(priority) = winner else {
+                    continue;
+                };
+                match settings.mode {
+                    MergeMode::Priority if priority > settings.priority => *level = value,
+                    MergeMode::Priority if priority < settings.priority => {}
+                    _ => *level = (*level).max(value),
+                }
+            }
+        }
+    }
+}
+
+/// Where a receive thread gets its packets from: its own socket, or the
+/// Art-Net port it shares.
+enum Receiving {
+    Socket(UdpSocket),
+    ArtNet(ArtNetListener),
+}
+
+impl Receiving {
+    /// The next packet, or None if none came within `RECEIVE_TIMEOUT`.
+    fn next(&self) -> Option<(SocketAddr, Vec<u8>)> {
+        match self {
+            Receiving::Socket(socket) => {
+                let mut buf = [0u8; 1024];
+                let (len, from) = socket.recv_from(&mut buf).ok()?;
+                Some((from, buf[..len].to_vec()))
+            }
+            Receiving::ArtNet(listener) => listener.recv_timeout(RECEIVE_TIMEOUT),
+        }
+    }
+}
+
+/// Receives Art-Net and sACN on background threads until dropped.
+pub struct NetworkInput {
+    shutdown: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+    input: DmxInput,
+}
+
+impl NetworkInput {
+    /// Listens for Art-Net on `art_net_port`, when given, and sACN if the
+    /// settings ask for it, from the subnets of `art_net` and `sacn`
+    /// respectively, or from anywhere when they are None.
+    pub fn start(
+        settings: &InputSettings,
+        input: DmxInput,
+        inspector: PacketInspector,
+        art_net_port: Option<ArtNetListener>,
+        art_net: Option<NetworkInterface>,
+        sacn: Option<NetworkInterface>,
+    ) -> io::Result<Self> {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let universes = settings.listened();
+        let mut receivers = Vec::new();
+        if let Some(listener) = art_net_port {
+            receivers.push((
+                Receiving::ArtNet(listener),
+                parse_art_net as Parser,
+                art_net,
+            ));
+        }
+        if settings.sacn {
+            let socket = UdpSocket::bind(("0.0.0.0", SACN_PORT))
+                .map_err(|err| io::Error::other(format!("sACN port: {}", err)))?;
+            let interface = sacn
+                .as_ref()
+                .map_or(Ipv4Addr::UNSPECIFIED, |interface| interface.address);
+            for &universe in &universes {
+                socket.join_multicast_v4(&sacn::multicast_group(universe), &interface)?;
+            }
+            // Wake up periodically so the thread exits once stopped.
+            socket.set_read_timeout(Some(RECEIVE_TIMEOUT))?;
+            receivers.push((Receiving::Socket(socket), parse_sacn as Parser, sacn));
+        }
+
+        let mut threads = Vec::new();
+        for (receiving, parse, network) in receivers {
+            let stop = shutdown.clone();
+            let input = input.clone();
+            let inspector = inspector.clone();
+            let universes = universes.clone();
+            threads.push(thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let Some((from, packet)) = receiving.next() else {
+                        continue;
+                    };
+                    // The port is shared by every interface, so drop what
+                    // arrives from other networks.
+                    if network
+                        .as_ref()
+                        .is_some_and(|network| !network.contains(from.ip()))
+                    {
+                        continue;
+                    }
+                    let Some(packet) = parse(&packet, from) else {
+                        continue;
+                    };
+                    inspector.record(from, &packet);
+                    match packet {
+                        Packet::Dmx(source) if universes.contains(&source.universe) => {
+                            input.receive(from, *source)
+                        }
+                        Packet::Priorities(source) if universes.contains(&source.universe) => {
+                            input.receive_priorities(from, &source)
+                        }
+                        Packet::Terminated(universe) => input.remove(universe, from),
+                        _ => {}
+                    }
+                }
+            }));
+        }
+
+        Ok(Self {
+            shutdown,
+            threads,
+            input,
+        })
+    }
+}
+
+impl Drop for NetworkInput {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Wait for the sockets to close, and the Art-Net port to be let go
+        // of, so the ports can be bound again.
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+        self.input.clear();
+    }
+}
+
+pub(crate) enum Packet {
+    Dmx(Box<Source>),
+    /// A sACN source's per-address priorities, carried in place of levels.
+    Priorities(Box<Source>),
+    /// The source on this universe said it is stopping.
+    Terminated(u16),
+}
+
+type Parser = fn(&[u8], SocketAddr) -> Option<Packet>;
+
+fn parse_art_net(packet: &[u8], from: SocketAddr) -> Option<Packet> {
+    if packet.len() < 18 || !packet.starts_with(ART_NET_ID) {
+        return None;
+    }
+    if u16::from_le_bytes([packet[8], packet[9]]) != OP_DMX {
+        return None;
+    }
+    let port_address = u16::from_le_bytes([packet[14], packet[15]]) & 0x7FFF;
+    let length = u16::from_be_bytes([packet[16], packet[17]]) as usize;
+    let data = packet.get(18..18 + length.min(UNIVERSE_SIZE))?;
+    Some(Packet::Dmx(Box::new(source(
+        port_address + 1,
+        "Art-Net",
+        from.ip().to_string(),
+        DEFAULT_PRIORITY,
+        packet[12],
+        data,
+    ))))
+}
+
+fn parse_sacn(packet: &[u8], _from: SocketAddr) -> Option<Packet> {
+    if packet.len() < 126 || packet.get(4..16)? != ACN_ID {
+        return None;
+    }
+    // Root vector: E1.31 data; framing vector: DMX data; DMP start code 0.
+    let root = u32::from_be_bytes(packet[18..22].try_into().ok()?);
+    let framing = u32::from_be_bytes(packet[40..44].try_into().ok()?);
+    let start_code = packet[125];
+    if root != 4 || framing != 2 || (start_code != 0 && start_code != PRIORITY_START_CODE) {
+        return None;
+    }
+    let options = packet[112];
+    let universe = u16::from_be_bytes([packet[113], packet[114]]);
+    if options & 0x40 != 0 {
+        return Some(Packet::Terminated(universe));
+    }
+    if options & 0x80 != 0 {
+        // Preview data is for visualisers, not the rig.
+        return None;
+    }
+    let name = String::from_utf8_lossy(&packet[44..108])
+        .trim_end_matches('\0')
+        .to_string();
+    let count = u16::from_be_bytes([packet[123], packet[124]]) as usize;
+    let data = packet.get(126..126 + count.saturating_sub(1).min(UNIVERSE_SIZE))?;
+    let source = Box::new(source(
+        universe,
+        "sACN",
+        name,
+        packet[108],
+        packet[111],
+        data,
+    ));
+    Some(if start_code == PRIORITY_START_CODE {
+        Packet::Priorities(source)
+    } else {
+        Packet::Dmx(source)
+    })
+}
+
+fn source(
+    universe: u16,
+    protocol: &'static str,
+    name: String,
+    priority: u8,
+    sequence: u8,
+    data: &[u8],
+) -> Source {
+    let mut levels = [0u8; UNIVERSE_SIZE];
+    levels[..data.len()].copy_from_slice(data);
+    Source {
+        universe,
+        protocol,
+        name,
+        priority,
+        rate: 0.0,
+        sequence,
+        data: levels,
+        received: Instant::now(),
+        address_priorities: None,
+    }
+}
+
+impl HaloApp {
+    /// Opens or closes the network input to match the current preferences.
+    pub(crate) fn apply_network_input_preferences(&mut self) {
+        self.network_input = None;
+        self.network_input_error = None;
+        if self.preferences.network_input.enabled() {
+            match self.start_network_input() {
+                Ok(input) => self.network_input = Some(input),
+                Err(err) => self.network_input_error = Some(err.to_string()),
+            }
+        }
+    }
+
+    fn start_network_input(&mut self) -> io::Result<NetworkInput> {
+        let art_net_port = if self.preferences.network_input.art_net {
+            Some(self.art_net_port()?.listen(&[OP_DMX]))
+        } else {
+            None
+        };
+        NetworkInput::start(
+            &self.preferences.network_input,
+            self.dmx_input.clone(),
+            self.packet_inspector.clone(),
+            art_net_port,
+            self.bound_interface(Protocol::ArtNet).cloned(),
+            self.bound_interface(Protocol::Sacn).cloned(),
+        )
+    }
+}
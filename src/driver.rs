@@ -0,0 +1,377 @@
+//! Fixtures that aren't DMX: an OSC lamp, a device with an HTTP API or a
+//! hazer on MIDI. A driven fixture is patched like any other, and its
+//! address is where its levels sit in Halo's frame so cues, effects and
+//! processing work on it as usual, but those channels stay out of the DMX
+//! output. The driver turns the fixture's levels into its own protocol from
+//! a thread of its own, sending only what has changed, so a slow device
+//! never holds up the engine.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use crate::midi;
+use crate::osc::{self, OscArg};
+use crate::smart_bulb;
+use midir::MidiOutputConnection;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::net::UdpSocket;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const OSC_INTERVAL: Duration = Duration::from_millis(33);
+/// Most devices with an HTTP API manage about ten requests a second.
+const HTTP_INTERVAL: Duration = Duration::from_millis(100);
+const CONTROL_CHANGE: u8 = 0xB0;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FixtureDriver {
+    /// One OSC message per attribute to `target` (`host:port`), at
+    /// `<prefix>/<attribute>` with the level from 0 to 1.
+    Osc { target: String, prefix: String },
+    /// A request to `host` whenever the fixture changes. `{intensity}`,
+    /// `{red}` and the other attribute names in braces in the path and body
+    /// become levels from 0 to 255, and `{hex}` the output colour as RRGGBB.
+    Http {
+        host: String,
+        method: String,
+        path: String,
+        body: String,
+    },
+    /// Control changes on a MIDI output port, one controller per channel of
+    /// the profile counting from `controller`, with the level halved.
+    Midi {
+        port: String,
+        channel: u8,
+        controller: u8,
+    },
+}
+
+impl FixtureDriver {
+    pub fn kinds() -> [FixtureDriver; 3] {
+        [
+            FixtureDriver::Osc {
+                target: String::new(),
+                prefix: "/light".to_string(),
+            },
+            FixtureDriver::Http {
+                host: String::new(),
+                method: "PUT".to_string(),
+                path: "/".to_string(),
+                body: "{\"brightness\": {intensity}}".to_string(),
+            },
+            FixtureDriver::Midi {
+                port: String::new(),
+                channel: 1,
+                controller: 1,
+            },
+        ]
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FixtureDriver::Osc { .. } => "OSC",
+            FixtureDriver::Http { .. } => "HTTP",
+            FixtureDriver::Midi { .. } => "MIDI",
+        }
+    }
+}
+
+impl fmt::Display for FixtureDriver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixtureDriver::Osc { target, prefix } => write!(f, "OSC {}{}", target, prefix),
+            FixtureDriver::Http { host, path, .. } => write!(f, "HTTP {}{}", host, path),
+            FixtureDriver::Midi {
+                port,
+                channel,
+                controller,
+            } => write!(f, "MIDI {} ch {} CC {}", port, channel, controller),
+        }
+    }
+}
+
+/// `frame` with the channels of driven fixtures dark, for the DMX outputs.
+pub fn dmx_only<'a>(fixtures: &[Fixture], frame: &'a DmxFrame) -> Cow<'a, DmxFrame> {
+    let mut driven = fixtures
+        .iter()
+        .filter(|fixture| fixture.driver.is_some())
+        .peekable();
+    if driven.peek().is_none() {
+        return Cow::Borrowed(frame);
+    }
+    let mut frame = frame.clone();
+    for fixture in driven {
+        for attribute in &fixture.profile.channels {
+            if let Some(channel) = fixture.channel_for(*attribute) {
+                frame.set(fixture.universe, channel, 0);
+            }
+        }
+    }
+    Cow::Owned(frame)
+}
+
+/// What one driven fixture is putting out.
+#[derive(Clone, PartialEq)]
+struct Output {
+    name: String,
+    driver: FixtureDriver,
+    levels: Vec<(Attribute, u8)>,
+    color: [u8; 3],
+}
+
+impl Output {
+    /// `text` with the placeholders filled in.
+    fn fill(&self, text: &str) -> String {
+        let mut text = text.replace(
+            "{hex}",
+            &format!(
+                "{:02X}{:02X}{:02X}",
+                self.color[0], self.color[1], self.color[2]
+            ),
+        );
+        for (attribute, level) in &self.levels {
+            let name = format!("{{{}}}", attribute.to_string().to_lowercase());
+            text = text.replace(&name, &level.to_string());
+        }
+        text
+    }
+}
+
+struct Shared {
+    outputs: Vec<Output>,
+    /// The last failure, and which fixture it was for.
+    error: Option<(String, String)>,
+}
+
+/// Sends the latest levels of every driven fixture from its own thread,
+/// until dropped.
+pub struct DriverBridge {
+    shared: Arc<Mutex<Shared>>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DriverBridge {
+    pub fn start() -> Self {
+        let shared = Arc::new(Mutex::new(Shared {
+            outputs: Vec::new(),
+            error: None,
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let shared = shared.clone();
+            let shutdown = shutdown.clone();
+            thread::Builder::new()
+                .name("halo-drivers".to_string())
+                .spawn(move || run(shared, shutdown))
+                .ok()
+        };
+        Self {
+            shared,
+            shutdown,
+            thread,
+        }
+    }
+
+    /// Hands the bridge the levels each driven fixture has in `frame`.
+    pub fn update(&self, fixtures: &[Fixture], frame: &DmxFrame) {
+        let outputs = fixtures
+            .iter()
+            .filter(|fixture| !fixture.inhibited)
+            .filter_map(|fixture| {
+                let driver = fixture.driver.clone()?;
+                let levels = fixture
+                    .profile
+                    .channels
+                    .iter()
+                    .filter_map(|&attribute| {
+                        let channel = fixture.channel_for(attribute)?;
+                        Some((attribute, frame.get(fixture.universe, channel)))
+                    })
+                    .collect();
+                Some(Output {
+                    name: fixture.name.clone(),
+                    driver,
+                    levels,
+                    color: fixture.output_color(frame),
+                })
+            })
+            .collect();
+        self.shared.lock().unwrap().outputs = outputs;
+    }
+
+    pub fn error(&self) -> Option<String> {
+        let shared = self.shared.lock().unwrap();
+        let (fixture, error) = shared.error.as_ref()?;
+        Some(format!("{}: {}", fixture, error))
+    }
+}
+
+impl Drop for DriverBridge {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// What was last sent to one fixture, and when.
+#[derive(Clone, Default)]
+struct Sent {
+    levels: Option<Vec<(Attribute, u8)>>,
+    at: Option<Instant>,
+}
+
+fn run(shared: Arc<Mutex<Shared>>, shutdown: Arc<AtomicBool>) {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok();
+    let mut drivers: Vec<FixtureDriver> = Vec::new();
+    let mut sent: Vec<Sent> = Vec::new();
+    let mut midi_ports: HashMap<String, MidiOutputConnection> = HashMap::new();
+    // HTTP requests go out on threads of their own, one at a time per
+    // fixture, so a device that doesn't answer only holds up itself.
+    let mut requests: Vec<Arc<AtomicBool>> = Vec::new();
+
+    while !shutdown.load(Ordering::Relaxed) {
+        thread::sleep(Duration::from_millis(10));
+        let outputs = shared.lock().unwrap().outputs.clone();
+        let current: Vec<FixtureDriver> = outputs.iter().map(|o| o.driver.clone()).collect();
+        if current != drivers {
+            drivers = current;
+            sent = vec![Sent::default(); drivers.len()];
+            requests = (0..drivers.len())
+                .map(|_| Arc::new(AtomicBool::new(false)))
+                .collect();
+            midi_ports.clear();
+        }
+        let now = Instant::now();
+        let mut results = Vec::new();
+
+        for (i, output) in outputs.iter().enumerate() {
+            let last = &sent[i].levels;
+            if last.as_ref() == Some(&output.levels) {
+                continue;
+            }
+            let since = sent[i].at.map(|at| now.saturating_duration_since(at));
+            match &output.driver {
+                FixtureDriver::Osc { target, prefix } => {
+                    if since.is_some_and(|since| since < OSC_INTERVAL) || target.is_empty() {
+                        continue;
+                    }
+                    let Some(socket) = &socket else {
+                        continue;
+                    };
+                    for (attribute, level) in &output.levels {
+                        if last
+                            .as_ref()
+                            .is_some_and(|last| last.contains(&(*attribute, *level)))
+                        {
+                            continue;
+                        }
+                        let address = format!(
+                            "{}/{}",
+                            prefix.trim_end_matches('/'),
+                            attribute.to_string().to_lowercase()
+                        );
+                        let packet = osc::encode(&address, &[OscArg::Float(*level as f32 / 255.0)]);
+                        let result = socket.send_to(&packet, target.as_str()).map(drop);
+                        results.push((i, result.map_err(|err| err.to_string())));
+                    }
+                }
+                FixtureDriver::Http {
+                    host,
+                    method,
+                    path,
+                    body,
+                } => {
+                    if since.is_some_and(|since| since < HTTP_INTERVAL)
+                        || host.is_empty()
+                        || requests[i].swap(true, Ordering::Relaxed)
+                    {
+                        continue;
+                    }
+                    let busy = requests[i].clone();
+                    let shared = shared.clone();
+                    let (host, method) = (host.clone(), method.clone());
+                    let (path, body) = (output.fill(path), output.fill(body));
+                    let name = output.name.clone();
+                    thread::spawn(move || {
+                        let result = smart_bulb::http(&host, &method, &path, &body);
+                        let mut shared = shared.lock().unwrap();
+                        match result {
+                            Err(err) => shared.error = Some((name, err.to_string())),
+                            Ok(_) if shared.error.as_ref().is_some_and(|(f, _)| *f == name) => {
+                                shared.error = None;
+                            }
+                            Ok(_) => {}
+                        }
+                        busy.store(false, Ordering::Relaxed);
+                    });
+                }
+                FixtureDriver::Midi {
+                    port,
+                    channel,
+                    controller,
+                } => {
+                    if port.is_empty() {
+                        continue;
+                    }
+                    if !midi_ports.contains_key(port) {
+                        match midi::connect_output(port, "halo-driver") {
+                            Ok(connection) => {
+                                midi_ports.insert(port.clone(), connection);
+                            }
+                            Err(err) => {
+                                // Try again once the levels change.
+                                results.push((i, Err(err)));
+                                sent[i] = Sent {
+                                    levels: Some(output.levels.clone()),
+                                    at: Some(now),
+                                };
+                                continue;
+                            }
+                        }
+                    }
+                    let connection = midi_ports.get_mut(port).expect("connected above");
+                    let status = CONTROL_CHANGE | (channel.clamp(&1, &16) - 1);
+                    for (offset, (attribute, level)) in output.levels.iter().enumerate() {
+                        if last
+                            .as_ref()
+                            .is_some_and(|last| last.contains(&(*attribute, *level)))
+                        {
+                            continue;
+                        }
+                        let number = (*controller as usize + offset).min(127) as u8;
+                        let result = connection
+                            .send(&[status, number, level >> 1])
+                            .map_err(|err| err.to_string());
+                        results.push((i, result));
+                    }
+                }
+            }
+            sent[i] = Sent {
+                levels: Some(output.levels.clone()),
+                at: Some(now),
+            };
+        }
+
+        if !results.is_empty() {
+            let mut shared = shared.lock().unwrap();
+            for (i, result) in results {
+                let name = &outputs[i].name;
+                match result {
+                    Err(err) => shared.error = Some((name.clone(), err)),
+                    Ok(()) if shared.error.as_ref().is_some_and(|(f, _)| f == name) => {
+                        shared.error = None;
+                    }
+                    Ok(()) => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Effect presets: a pixel map's effect — its content, colours, speed,
+//! brightness and audio response — saved under a name, to reuse in another
+//! show or hand to someone else. The library keeps one small JSON file per
+//! preset in the config directory, and any preset file can be imported into
+//! it or exported out of it. Size, wiring and media files stay with the map,
+//! as they belong to the rig rather than the effect.
+
+use crate::pixel::{AudioModulation, Generator, PixelMap, Rgb};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EffectPreset {
+    pub name: String,
+    /// Heading the preset is filed under in the library, such as "Chases".
+    pub category: String,
+    pub generator: Generator,
+    pub colors: [Rgb; 2],
+    /// Cycles per second for the moving generators.
+    pub speed: f32,
+    pub brightness: f32,
+    /// Playback rate of media, where 1 is normal speed.
+    pub playback_speed: f32,
+    pub looping: bool,
+    /// The generator plugin drawing the effect, by name.
+    pub plugin: String,
+    pub modulation: Option<AudioModulation>,
+}
+
+impl Default for EffectPreset {
+    fn default() -> Self {
+        Self::from_map("Effect", &PixelMap::default())
+    }
+}
+
+impl EffectPreset {
+    /// The effect `map` is playing, as a preset called `name`.
+    pub fn from_map(name: &str, map: &PixelMap) -> Self {
+        Self {
+            name: name.to_string(),
+            category: String::new(),
+            generator: map.generator,
+            colors: map.colors,
+            speed: map.speed,
+            brightness: map.brightness,
+            playback_speed: map.playback_speed,
+            looping: map.looping,
+            plugin: map.plugin.clone(),
+            modulation: map.modulation,
+        }
+    }
+
+    /// Sets `map` playing the preset's effect.
+    pub fn apply(&self, map: &mut PixelMap) {
+        map.generator = self.generator;
+        map.colors = self.colors;
+        map.speed = self.speed;
+        map.brightness = self.brightness;
+        map.playback_speed = self.playback_speed;
+        map.looping = self.looping;
+        map.plugin = self.plugin.clone();
+        map.modulation = self.modulation;
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let preset = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        Ok(preset)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), self)?;
+        Ok(())
+    }
+
+    /// File name for the preset in the library, derived from its name.
+    pub fn file_name(&self) -> String {
+        let stem: String = self
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("{}.json", stem)
+    }
+}
+
+/// Directory holding the effect preset library, one JSON file each.
+pub fn preset_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("halo").join("effects"))
+}
+
+/// Every preset in `preset_dir()`, by category and then name. Unreadable
+/// files are skipped.
+pub fn preset_library() -> Vec<EffectPreset> {
+    let Some(entries) = preset_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut presets: Vec<EffectPreset> = entries
+        .flatten()
+        .filter_map(|entry| EffectPreset::load(&entry.path()).ok())
+        .collect();
+    presets.sort_by(|a, b| (&a.category, &a.name).cmp(&(&b.category, &b.name)));
+    presets
+}
+
+/// Saves `preset` into the library, replacing any preset stored under the
+/// same name.
+pub fn install_preset(preset: &EffectPreset) -> io::Result<()> {
+    let dir = preset_dir().ok_or_else(|| io::Error::other("no config directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    preset.save(&dir.join(preset.file_name()))
+}
+
+/// Deletes `preset` from the library.
+pub fn remove_preset(preset: &EffectPreset) -> io::Result<()> {
+    let dir = preset_dir().ok_or_else(|| io::Error::other("no config directory"))?;
+    std::fs::remove_file(dir.join(preset.file_name()))
+}
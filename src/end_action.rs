@@ -0,0 +1,97 @@
+//! What happens when playback reaches the end of the cue list, or of a song
+//! in setlist mode: carry on, stop, loop back to a chosen cue, or chain into
+//! a song. Looping a walk-in song keeps it going until the operator moves on
+//! to the show proper.
+
+use crate::cue::CueNumber;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::transport::Transport;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum EndAction {
+    /// The clock runs on past the last cue, or holds at the end of a song in
+    /// setlist mode.
+    #[default]
+    Continue,
+    Stop,
+    /// Back to the cue with this number, or the start of the list or song.
+    Loop(Option<CueNumber>),
+    /// On into the song with this id.
+    Chain(u32),
+}
+
+impl EndAction {
+    pub fn is_continue(&self) -> bool {
+        *self == EndAction::Continue
+    }
+
+    /// The actions with their targets left at the start, for pickers.
+    pub fn kinds(first_song: Option<u32>) -> Vec<EndAction> {
+        let mut kinds = vec![EndAction::Continue, EndAction::Stop, EndAction::Loop(None)];
+        kinds.extend(first_song.map(EndAction::Chain));
+        kinds
+    }
+
+    /// Whether `other` is the same kind of action, whatever its target.
+    pub fn same_kind(&self, other: &EndAction) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl fmt::Display for EndAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            EndAction::Continue => tr!("end-action-continue"),
+            EndAction::Stop => tr!("end-action-stop"),
+            EndAction::Loop(_) => tr!("end-action-loop"),
+            EndAction::Chain(_) => tr!("end-action-chain"),
+        })
+    }
+}
+
+impl HaloApp {
+    /// Carries out `action` at the end of the cue list or of the song with
+    /// id `song`, leaving playback running unless it stops.
+    pub(crate) fn run_end_action(&mut self, action: EndAction, song: Option<u32>) {
+        match action {
+            EndAction::Continue => return,
+            EndAction::Stop => {
+                self.stop_playback();
+                self.notify(LogLevel::Info, tr!("end-stopped"));
+                return;
+            }
+            EndAction::Loop(cue) => {
+                let index = cue
+                    .and_then(|number| self.show.cues.iter().position(|cue| cue.number == number));
+                match (index, song) {
+                    (Some(index), _) => self.go_to_cue(index),
+                    (None, Some(id)) => match self.show.songs.iter().position(|s| s.id == id) {
+                        Some(index) => self.go_to_song(index),
+                        None => self.reset(),
+                    },
+                    (None, None) => self.reset(),
+                }
+                self.log.info(tr!("end-looped"));
+            }
+            EndAction::Chain(id) => {
+                let Some(index) = self.show.songs.iter().position(|song| song.id == id) else {
+                    self.notify(LogLevel::Warning, tr!("end-chain-missing"));
+                    self.stop_playback();
+                    return;
+                };
+                self.go_to_song(index);
+                self.log.info(tr!(
+                    "end-chained",
+                    song = self.show.songs[index].name.as_str()
+                ));
+            }
+        }
+        if !self.transport.is_running() {
+            self.set_transport(Transport::Running);
+        }
+    }
+}
@@ -0,0 +1,1644 @@
+//! The output engine: a worker thread that owns the show clock and renders
+//! DMX frames on a fixed-rate ticker, so neither cue timing nor output depends
+//! on how often the UI repaints. The UI sends it a new [`Program`] whenever
+//! the show changes, drives the transport with commands, and picks up the
+//! latest [`EngineSnapshot`] and any [`EngineEvent`]s each frame.
+
+use crate::art_net::{ArtNetOutput, ArtNetSender, OutputRate};
+use crate::audio::{AudioLevels, AudioMeter};
+use crate::back::BackFade;
+use crate::busk::BuskSettings;
+use crate::crossfade::ManualFade;
+use crate::cue::{Cue, CueNumber, CueValue};
+use crate::cue_index::CueIndex;
+use crate::dmx::{self, ChannelCheck, DmxFrame, OUTPUT_HZ, UNIVERSE_SIZE};
+use crate::dmx_input::{DmxInput, InputSettings};
+use crate::driver::{self, DriverBridge};
+use crate::fixture::{Attribute, Fixture};
+use crate::fixture_control::{self, ControlLevel};
+use crate::flash::{self, LiveFlash};
+use crate::follow_spot::{FollowAims, FollowSpot};
+use crate::group_master::{self, GroupMaster};
+use crate::haze::{self, HazeSettings};
+use crate::hue_shift::HueShift;
+use crate::i18n::tr;
+use crate::inhibit;
+use crate::interfaces::{NetworkInterface, Protocol};
+use crate::log::LogLevel;
+use crate::move_in_black::MoveInBlack;
+use crate::ownership::{self, Owner, Owners};
+use crate::panic::Panic;
+use crate::pixel::{PixelClock, PixelMap};
+use crate::pixel_stream::{PixelOutput, PixelStreams};
+use crate::plugin::{PluginHost, PluginOutput, PluginSource};
+use crate::processing;
+use crate::psn::Trackers;
+use crate::quick_console;
+use crate::recording::{Player, RecordSource, Recorder};
+use crate::sacn::{SacnOutput, SacnSender};
+use crate::smart_bulb::{BulbBridge, SmartBulbSettings};
+use crate::speed_master::EffectClocks;
+use crate::stats::{FrameTimer, StageClock, StageTime, StageTimes};
+use crate::test_pattern::{self, TestPattern};
+use crate::trigger::TriggerWhen;
+use crate::ui::script_editor::ScriptTarget;
+use crate::HaloApp;
+use eframe::egui;
+use std::collections::{BTreeMap, HashMap};
+use std::net::UdpSocket;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy)]
+enum Transport {
+    /// Playing, with the playhead at zero at `origin`.
+    Running {
+        origin: Instant,
+    },
+    Stopped {
+        elapsed: Duration,
+    },
+}
+
+impl Transport {
+    fn elapsed_at(&self, now: Instant) -> Duration {
+        match *self {
+            Transport::Running { origin } => now.saturating_duration_since(origin),
+            Transport::Stopped { elapsed } => elapsed,
+        }
+    }
+}
+
+/// The engine's clock as of its last frame, for threads that need the
+/// playhead between snapshots, such as the clock sync server.
+#[derive(Clone)]
+pub struct Playhead(Arc<Mutex<Transport>>);
+
+impl Playhead {
+    /// Where the playhead is now, and whether it is running.
+    pub fn position(&self) -> (Duration, bool) {
+        let Ok(transport) = self.0.lock() else {
+            return (Duration::ZERO, false);
+        };
+        (
+            transport.elapsed_at(Instant::now()),
+            matches!(*transport, Transport::Running { .. }),
+        )
+    }
+
+    fn set(&self, transport: Transport) {
+        if let Ok(mut shared) = self.0.lock() {
+            *shared = transport;
+        }
+    }
+}
+
+/// Everything the engine needs to render output.
+#[derive(Clone)]
+pub struct Program {
+    pub cues: Vec<Cue>,
+    pub fixtures: Vec<Fixture>,
+    pub pixel_maps: Vec<PixelMap>,
+    pub move_in_black_lead: Duration,
+    /// Busk mode settings while it is on.
+    pub busk: Option<BuskSettings>,
+    /// How to merge Art-Net and sACN input, while it is on.
+    pub input: Option<InputSettings>,
+    /// Overrides everything else while a channel check is running.
+    pub channel_check: Option<ChannelCheck>,
+    /// Replaces one universe while commissioning.
+    pub test_pattern: Option<TestPattern>,
+    /// Universes left out of the DMX outputs.
+    pub muted_universes: Vec<u16>,
+    /// The A/B crossfade, while the faders are away from rest.
+    pub crossfade: Option<ManualFade>,
+    /// Replaces the output, fading from what was on air, while panicked.
+    pub panic: Option<Panic>,
+    /// Replaces the output the same way while stopped, per the preferences.
+    pub release: Option<Panic>,
+    pub bpm: f32,
+    pub beats_per_bar: u32,
+    /// Where Art-Net output goes, while it is on.
+    pub art_net: Option<ArtNetOutput>,
+    /// The interface Art-Net output goes out on, if one is chosen and up.
+    pub art_net_interface: Option<NetworkInterface>,
+    /// How sACN output goes out, while it is on.
+    pub sacn: Option<SacnOutput>,
+    /// The interface sACN output goes out on, if one is chosen and up.
+    pub sacn_interface: Option<NetworkInterface>,
+    /// The haze schedule, while it is on.
+    pub haze: Option<HazeSettings>,
+    /// How far on the Smoke pad is, overriding the haze schedule above 0.
+    pub smoke: f32,
+    /// The rate of each speed master, by position.
+    pub speed_rates: Vec<f32>,
+    /// Holds every effect where it is.
+    pub effects_frozen: bool,
+    pub group_masters: Vec<GroupMaster>,
+    /// Scales every fixture's intensity, from 0 to 1.
+    pub grand_master: f32,
+    pub plugins: Vec<PluginSource>,
+    /// Plugin output protocols that are switched on.
+    pub plugin_outputs: Vec<PluginOutput>,
+    /// The smart bulb bridge, while it is on.
+    pub smart_bulbs: Option<SmartBulbSettings>,
+    /// Whether pixel maps may send over DDP and WLED.
+    pub pixel_streams: bool,
+    /// Whether fixtures with drivers may send over their own protocols.
+    pub drivers: bool,
+    /// Intensities from the quick console, by fixture id.
+    pub quick_levels: BTreeMap<usize, u8>,
+    /// Levels held by lamp and reset macros.
+    pub controls: Vec<ControlLevel>,
+    /// Follow spots to aim, while PSN input is on.
+    pub follow_spots: Vec<FollowSpot>,
+    /// The flash pads that are up.
+    pub flashes: Vec<LiveFlash>,
+    /// Whether to work out what set each channel, while the UI is showing
+    /// it. Otherwise the snapshots' owners are left empty.
+    pub owners: bool,
+}
+
+impl Default for Program {
+    /// Nothing to render: the program the engine starts on.
+    fn default() -> Self {
+        Self {
+            cues: Vec::new(),
+            fixtures: Vec::new(),
+            pixel_maps: Vec::new(),
+            move_in_black_lead: Duration::ZERO,
+            busk: None,
+            input: None,
+            channel_check: None,
+            test_pattern: None,
+            muted_universes: Vec::new(),
+            crossfade: None,
+            panic: None,
+            release: None,
+            bpm: 120.0,
+            beats_per_bar: 4,
+            art_net: None,
+            art_net_interface: None,
+            sacn: None,
+            sacn_interface: None,
+            haze: None,
+            smoke: 0.0,
+            speed_rates: Vec::new(),
+            effects_frozen: false,
+            group_masters: Vec::new(),
+            grand_master: 1.0,
+            plugins: Vec::new(),
+            plugin_outputs: Vec::new(),
+            smart_bulbs: None,
+            pixel_streams: false,
+            drivers: false,
+            quick_levels: BTreeMap::new(),
+            controls: Vec::new(),
+            follow_spots: Vec::new(),
+            flashes: Vec::new(),
+            owners: false,
+        }
+    }
+}
+
+enum EngineCommand {
+    Program(Box<Program>),
+    Start,
+    Stop,
+    /// Moves the playhead without firing the cues in between.
+    Seek(Duration),
+    /// Jumps to a position like a seek, then fires the cues that started
+    /// within the window before it once the clock runs.
+    Join {
+        position: Duration,
+        window: Duration,
+    },
+    /// Stops the clock when the playhead reaches this position.
+    HoldAt(Option<Duration>),
+    /// Sends the next frame in full to every output, changed or not.
+    Assert,
+    /// Crossfades from what is on air to the output over this long.
+    BackFade(Duration),
+    /// Starts recording the output, or stops with None.
+    Record(Option<Box<Recorder>>),
+    /// Plays a recording out in place of the show, or stops with None.
+    Play(Option<Box<Player>>),
+    /// Puts out one last frame, with any release finished, and exits.
+    Shutdown,
+}
+
+/// Things that happened on the engine clock that the UI must act on.
+pub enum EngineEvent {
+    /// The playhead crossed the start of the cue at this index, and was
+    /// this far past it by then.
+    CueFired(usize, Duration),
+    /// The playhead crossed the end of the cue at this index.
+    CueEnded(usize),
+    /// The clock stopped at the hold position.
+    Held,
+    /// A restarted engine took over the outputs and any recording from the
+    /// thread it replaced; see [`Engine::restart`].
+    TookOver,
+}
+
+/// One rendered frame, sent back to the UI.
+pub struct EngineSnapshot {
+    /// The program revision this frame was rendered from.
+    pub revision: u64,
+    pub frame: DmxFrame,
+    /// The frame as programmed, before group masters and output processing,
+    /// for recording looks that play back the same.
+    pub programmed: DmxFrame,
+    /// What set each channel of `frame`.
+    pub owners: Owners,
+    /// The playhead position the frame was rendered at.
+    pub elapsed: Duration,
+    /// Whether each cue is fading and how far, in cue list order.
+    pub cues: Vec<(bool, f32)>,
+    pub output_frames: FrameTimer,
+    /// How long each stage of the output frame is taking.
+    pub stage_times: Vec<StageTime>,
+    /// How often each Art-Net destination is getting frames.
+    pub art_net_rates: Vec<OutputRate>,
+    /// Errors from the plugins, by name.
+    pub plugin_errors: Vec<(String, String)>,
+    /// The smart bulb bridge's last failure.
+    pub smart_bulb_error: Option<String>,
+    /// The last failure sending a driven fixture.
+    pub driver_error: Option<String>,
+    /// Why a pixel map couldn't be sent over the network this frame.
+    pub pixel_stream_error: Option<String>,
+    /// How long the output has been recorded for, while it is.
+    pub recording: Option<Duration>,
+    /// How far into the recording being played back, and its length.
+    pub playback: Option<(Duration, Duration)>,
+    /// Why the last recording or playback stopped early.
+    pub recording_error: Option<String>,
+}
+
+/// How long the engine can go without rendering a frame before the
+/// watchdog counts it as stalled.
+pub const STALL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Why the watchdog thinks the engine has stopped.
+pub struct Stall {
+    /// Time since the engine last rendered a frame.
+    pub since_frame: Duration,
+    /// The thread exited, most likely by panicking.
+    pub exited: bool,
+    /// Commands the engine had yet to catch up with.
+    pub pending: u64,
+}
+
+/// What the engine thread works from besides its commands.
+#[derive(Clone)]
+struct Sources {
+    /// Zero point of the pixel map clock.
+    origin: Instant,
+    audio: AudioMeter,
+    input: DmxInput,
+    trackers: Trackers,
+    /// Milliseconds from `origin` to the last frame rendered, written by the
+    /// engine thread for the watchdog.
+    heartbeat: Arc<AtomicU64>,
+    playhead: Playhead,
+    /// What an engine thread left behind by a restart hands to the one that
+    /// replaced it, as it exits.
+    handover: Arc<Mutex<Option<Sessions>>>,
+}
+
+/// What the engine thread holds open from frame to frame: the output
+/// sockets and drivers, and any recording or playback. Plugins are compiled
+/// for one thread, so a new thread compiles its own from the program.
+#[derive(Default)]
+struct Sessions {
+    art_net: Option<ArtNetSender>,
+    sacn: Option<SacnSender>,
+    plugin_socket: Option<UdpSocket>,
+    bulbs: Option<BulbBridge>,
+    drivers: Option<DriverBridge>,
+    streams: PixelStreams,
+    recorder: Option<Box<Recorder>>,
+    player: Option<Box<Player>>,
+}
+
+/// The channels and thread of one run of the engine.
+struct Worker {
+    commands: Sender<(u64, EngineCommand)>,
+    snapshots: Receiver<EngineSnapshot>,
+    events: Receiver<EngineEvent>,
+    thread: thread::JoinHandle<()>,
+}
+
+pub struct Engine {
+    commands: Sender<(u64, EngineCommand)>,
+    snapshots: Receiver<EngineSnapshot>,
+    events: Receiver<EngineEvent>,
+    sent: Program,
+    /// Counts the commands sent, so a snapshot can be matched to them.
+    revision: u64,
+    received: u64,
+    /// Revision of the last transport command.
+    clock_revision: u64,
+    hold: Option<Duration>,
+    thread: Option<thread::JoinHandle<()>>,
+    sources: Sources,
+}
+
+impl Engine {
+    /// Starts the engine thread with nothing to render until the first
+    /// program arrives. `origin` is the zero point of the pixel map clock,
+    /// shared with the UI previews, `audio` feeds audio-reactive maps,
+    /// `input` holds the network input to merge and `trackers` the PSN
+    /// trackers follow spots aim at. The thread exits once the engine is
+    /// dropped.
+    pub fn start(origin: Instant, audio: AudioMeter, input: DmxInput, trackers: Trackers) -> Self {
+        let program = Program::default();
+        let sources = Sources {
+            origin,
+            audio,
+            input,
+            trackers,
+            heartbeat: Arc::new(AtomicU64::new(origin.elapsed().as_millis() as u64)),
+            playhead: Playhead(Arc::new(Mutex::new(Transport::Stopped {
+                elapsed: Duration::ZERO,
+            }))),
+            handover: Arc::default(),
+        };
+        let worker = spawn(sources.clone(), program.clone(), None);
+        Self {
+            commands: worker.commands,
+            snapshots: worker.snapshots,
+            events: worker.events,
+            sent: program,
+            revision: 0,
+            received: 0,
+            clock_revision: 0,
+            hold: None,
+            thread: Some(worker.thread),
+            sources,
+        }
+    }
+
+    /// Whether the engine has stopped rendering, by going quiet for longer
+    /// than [`STALL_TIMEOUT`] or by its thread exiting. Never after
+    /// [`Engine::shutdown`].
+    pub fn stall(&self) -> Option<Stall> {
+        let thread = self.thread.as_ref()?;
+        let sources = &self.sources;
+        let last_frame = Duration::from_millis(sources.heartbeat.load(Ordering::Relaxed));
+        let since_frame = sources.origin.elapsed().saturating_sub(last_frame);
+        let exited = thread.is_finished();
+        (exited || since_frame > STALL_TIMEOUT).then(|| Stall {
+            since_frame,
+            exited,
+            pending: self.revision - self.received,
+        })
+    }
+
+    /// Abandons the current engine thread and starts a new one on the last
+    /// program sent, with the clock stopped at zero. A stuck thread is left
+    /// to exit by itself once it notices its channels are gone. It still
+    /// holds the output sockets, serial drivers and any recording, so the
+    /// new thread renders without sending anything until the old one has
+    /// exited and handed them over, then sends [`EngineEvent::TookOver`].
+    pub fn restart(&mut self) {
+        let Some(previous) = self.thread.take() else {
+            return;
+        };
+        let sources = &self.sources;
+        sources.heartbeat.store(
+            sources.origin.elapsed().as_millis() as u64,
+            Ordering::Relaxed,
+        );
+        let worker = spawn(sources.clone(), self.sent.clone(), Some(previous));
+        self.commands = worker.commands;
+        self.snapshots = worker.snapshots;
+        self.events = worker.events;
+        self.thread = Some(worker.thread);
+        // The new thread starts at revision 0, so count from there again.
+        self.revision = 0;
+        self.received = 0;
+        self.clock_revision = 0;
+        self.hold = None;
+    }
+
+    fn command(&mut self, command: EngineCommand) {
+        self.revision += 1;
+        let _ = self.commands.send((self.revision, command));
+    }
+
+    /// The clock as the engine last rendered it, shared across restarts.
+    pub fn playhead(&self) -> Playhead {
+        self.sources.playhead.clone()
+    }
+
+    pub fn sent(&self) -> &Program {
+        &self.sent
+    }
+
+    pub fn send(&mut self, program: Program) {
+        self.command(EngineCommand::Program(Box::new(program.clone())));
+        self.sent = program;
+    }
+
+    pub fn start_clock(&mut self) {
+        self.command(EngineCommand::Start);
+        self.clock_revision = self.revision;
+    }
+
+    pub fn stop_clock(&mut self) {
+        self.command(EngineCommand::Stop);
+        self.clock_revision = self.revision;
+    }
+
+    pub fn seek(&mut self, position: Duration) {
+        self.command(EngineCommand::Seek(position));
+        self.clock_revision = self.revision;
+    }
+
+    /// Has the next frame sent in full, for outputs that only send changes.
+    pub fn assert(&mut self) {
+        self.command(EngineCommand::Assert);
+    }
+
+    /// Fades the output from what is on air now over `fade`; see `back`.
+    pub fn back_fade(&mut self, fade: Duration) {
+        self.command(EngineCommand::BackFade(fade));
+    }
+
+    /// Records every frame sent to `recorder`, or stops recording.
+    pub fn record(&mut self, recorder: Option<Recorder>) {
+        self.command(EngineCommand::Record(recorder.map(Box::new)));
+    }
+
+    /// Sends `player`'s recording in place of the show, or goes back to it.
+    pub fn play(&mut self, player: Option<Player>) {
+        self.command(EngineCommand::Play(player.map(Box::new)));
+    }
+
+    /// Joins the show at `position`, firing the cues that started up to
+    /// `window` before it; see [`Renderer::join`].
+    pub fn join(&mut self, position: Duration, window: Duration) {
+        self.command(EngineCommand::Join { position, window });
+        self.clock_revision = self.revision;
+    }
+
+    /// Sets where the clock should stop by itself, if anywhere. Cues starting
+    /// exactly there are left for whoever moves the playhead on.
+    pub fn hold_at(&mut self, hold: Option<Duration>) {
+        if hold != self.hold {
+            self.hold = hold;
+            self.command(EngineCommand::HoldAt(hold));
+        }
+    }
+
+    /// Whether `snapshot` was rendered after the last transport command, so
+    /// its clock can replace the position the UI assumed.
+    pub fn has_clock(&self, snapshot: &EngineSnapshot) -> bool {
+        snapshot.revision >= self.clock_revision
+    }
+
+    /// Whether the engine has yet to render a frame reflecting every command
+    /// sent so far.
+    pub fn is_behind(&self) -> bool {
+        self.received != self.revision
+    }
+
+    pub fn latest_snapshot(&mut self) -> Option<EngineSnapshot> {
+        let snapshot = self.snapshots.try_iter().last()?;
+        self.received = snapshot.revision;
+        Some(snapshot)
+    }
+
+    pub fn events(&self) -> Vec<EngineEvent> {
+        self.events.try_iter().collect()
+    }
+
+    /// Stops the engine thread after its final frame and waits for it to
+    /// exit. Commands sent afterwards are ignored.
+    pub fn shutdown(&mut self) {
+        let Some(thread) = self.thread.take() else {
+            return;
+        };
+        self.command(EngineCommand::Shutdown);
+        let _ = thread.join();
+    }
+}
+
+/// Starts an engine thread. `previous` is the thread it replaces, whose
+/// sessions it takes over once that has exited.
+fn spawn(sources: Sources, program: Program, previous: Option<thread::JoinHandle<()>>) -> Worker {
+    let (commands, command_rx) = mpsc::channel();
+    // Only the newest frame matters, so a full channel just means the UI
+    // hasn't caught up and the frame can be dropped. Events must all
+    // arrive, and are rare enough to queue.
+    let (snapshot_tx, snapshots) = mpsc::sync_channel(1);
+    let (event_tx, events) = mpsc::channel();
+    let thread = thread::Builder::new()
+        .name("halo-engine".to_string())
+        .spawn(move || {
+            run(
+                sources,
+                program,
+                previous,
+                command_rx,
+                snapshot_tx,
+                event_tx,
+            )
+        })
+        .expect("failed to spawn the engine thread");
+    Worker {
+        commands,
+        snapshots,
+        events,
+        thread,
+    }
+}
+
+/// A frame from [`Renderer::render`], before it goes anywhere.
+pub struct Rendered {
+    pub frame: DmxFrame,
+    /// The frame before group masters and output processing.
+    pub programmed: DmxFrame,
+    pub owners: Owners,
+    /// The playhead position the frame was rendered at.
+    pub elapsed: Duration,
+    /// What happened on the clock since the last frame.
+    pub events: Vec<EngineEvent>,
+    /// Pixel data for each network pixel map, by index, to stream.
+    pub streamed: Vec<(usize, Vec<u8>)>,
+    /// How long each stage of rendering took.
+    pub stages: Vec<(&'static str, Duration)>,
+}
+
+/// The engine's clock and everything it carries from frame to frame. It
+/// never reads the time itself: each call is given `now`, so the same
+/// program stepped through the same times always renders the same frames.
+/// The engine thread steps it with the wall clock, and `simulate` with a
+/// virtual one. (The stages are timed with the wall clock, but only to be
+/// reported.)
+pub struct Renderer {
+    /// Zero point of the pixel map clock.
+    origin: Instant,
+    transport: Transport,
+    /// Where the playhead was on the last tick, to find the cues crossed
+    /// since.
+    previous: Duration,
+    hold: Option<Duration>,
+    /// The last frame rendered, and the one on air when a panic or release
+    /// started.
+    last_frame: DmxFrame,
+    fade_from: Option<(Instant, DmxFrame)>,
+    back_fade: Option<BackFade>,
+    move_in_black: MoveInBlack,
+    /// Effect time runs with the pixel map clock until the effects are
+    /// frozen, and each speed master's runs at its own rate from there.
+    effect_time: f32,
+    effect_clocks: EffectClocks,
+    last_tick: Instant,
+    cue_index: CueIndex,
+    follow_aims: FollowAims,
+    /// Where to fire cues from on the next tick, after joining late.
+    join_from: Option<Duration>,
+    /// The levels each cue with variation rolled when it last fired, by
+    /// index, swapped in for the merge.
+    varied: HashMap<usize, Vec<CueValue>>,
+    /// How many times each cue with variation has fired, for its seed.
+    firings: HashMap<CueNumber, u64>,
+}
+
+impl Renderer {
+    pub fn new(origin: Instant, program: &Program, now: Instant) -> Self {
+        Self {
+            origin,
+            transport: Transport::Stopped {
+                elapsed: Duration::ZERO,
+            },
+            previous: Duration::ZERO,
+            hold: None,
+            last_frame: DmxFrame::default(),
+            fade_from: None,
+            back_fade: None,
+            move_in_black: MoveInBlack::default(),
+            effect_time: now.saturating_duration_since(origin).as_secs_f32(),
+            effect_clocks: EffectClocks::default(),
+            last_tick: now,
+            cue_index: CueIndex::new(&program.cues),
+            follow_aims: FollowAims::default(),
+            join_from: None,
+            varied: HashMap::new(),
+            firings: HashMap::new(),
+        }
+    }
+
+    /// Rolls the levels `cue`, at `index`, plays this time it fires, if it
+    /// has variation.
+    fn vary(&mut self, cue: &Cue, index: usize) {
+        let Some(variation) = &cue.variation else {
+            return;
+        };
+        let firing = self.firings.entry(cue.number).or_default();
+        self.varied
+            .insert(index, variation.roll(&cue.values, *firing));
+        *firing += 1;
+    }
+
+    /// Swaps the varied cues' rolled levels with their programmed ones.
+    fn swap_varied(&mut self, cues: &mut [Cue]) {
+        for (&index, values) in &mut self.varied {
+            if let Some(cue) = cues.get_mut(index) {
+                std::mem::swap(&mut cue.values, values);
+            }
+        }
+    }
+
+    /// Takes up a new program in place of `old`.
+    pub fn load(&mut self, old: &Program, new: &Program) {
+        if new.cues != old.cues {
+            self.move_in_black.clear();
+            self.varied.clear();
+        }
+        self.cue_index = CueIndex::new(&new.cues);
+    }
+
+    pub fn start(&mut self, now: Instant) {
+        if let Transport::Stopped { elapsed } = self.transport {
+            self.transport = Transport::Running {
+                origin: now - elapsed,
+            };
+            self.previous = elapsed;
+        }
+    }
+
+    /// Fades from the last frame rendered to the output over `fade`.
+    pub fn back_fade(&mut self, fade: Duration, now: Instant) {
+        self.back_fade = Some(BackFade::new(self.last_frame.clone(), fade, now));
+    }
+
+    pub fn stop(&mut self, now: Instant) {
+        self.transport = Transport::Stopped {
+            elapsed: self.transport.elapsed_at(now),
+        };
+    }
+
+    /// Moves the playhead without firing the cues in between.
+    pub fn seek(&mut self, position: Duration, now: Instant) {
+        self.transport = match self.transport {
+            Transport::Running { .. } => Transport::Running {
+                origin: now - position,
+            },
+            Transport::Stopped { .. } => Transport::Stopped { elapsed: position },
+        };
+        self.previous = position;
+        self.join_from = None;
+    }
+
+    /// Moves the playhead to `position` like a seek, for joining a show
+    /// already under way. The look is always that of `position`, fades
+    /// part-way included; cues that started up to `window` before it, and
+    /// after where the playhead was, also fire their scripts, triggers and
+    /// macros once the clock runs, as they would have if Halo had been
+    /// playing.
+    pub fn join(&mut self, position: Duration, window: Duration, now: Instant) {
+        let from = self
+            .transport
+            .elapsed_at(now)
+            .max(position.saturating_sub(window));
+        self.seek(position, now);
+        self.join_from = (!window.is_zero() && from < position).then_some(from);
+    }
+
+    pub fn hold_at(&mut self, hold: Option<Duration>) {
+        self.hold = hold;
+    }
+
+    pub fn elapsed_at(&self, now: Instant) -> Duration {
+        self.transport.elapsed_at(now)
+    }
+
+    /// The frame to leave on air when shutting down, with any panic or
+    /// release run to its end.
+    pub fn final_frame(&mut self, program: &Program, now: Instant) -> DmxFrame {
+        match program.panic.as_ref().or(program.release.as_ref()) {
+            Some(fade) => fade_frame(
+                fade,
+                &mut self.fade_from,
+                &self.last_frame,
+                now + fade.remaining(now),
+            ),
+            None => self.last_frame.clone(),
+        }
+    }
+
+    /// Renders the frame for `now`, with `audio` the levels for audio-reactive
+    /// pixel maps and `input` the network input to merge.
+    pub fn render(
+        &mut self,
+        program: &mut Program,
+        plugins: &PluginHost,
+        audio: AudioLevels,
+        input: &DmxInput,
+        trackers: &Trackers,
+        now: Instant,
+    ) -> Rendered {
+        let mut stages = StageClock::start();
+        let mut events = Vec::new();
+        let mut elapsed = self.transport.elapsed_at(now);
+        if let Transport::Running { .. } = self.transport {
+            let previous = self.previous;
+            let held = self.hold.filter(|&hold| previous < hold && elapsed >= hold);
+            if let Some(hold) = held {
+                elapsed = hold;
+                self.transport = Transport::Stopped { elapsed };
+            }
+            let fire_from = self.join_from.take().unwrap_or(previous);
+            for index in self.cue_index.fired(fire_from, elapsed) {
+                if held.is_some() && program.cues[index].start_time == elapsed {
+                    continue;
+                }
+                self.vary(&program.cues[index], index);
+                let past = elapsed.saturating_sub(program.cues[index].start_time);
+                events.push(EngineEvent::CueFired(index, past));
+            }
+            for index in self.cue_index.ended(previous, elapsed) {
+                events.push(EngineEvent::CueEnded(index));
+            }
+            if held.is_some() {
+                events.push(EngineEvent::Held);
+            }
+            self.previous = elapsed;
+        }
+        self.cue_index.update(&mut program.cues, elapsed);
+        if let Some(crossfade) = &program.crossfade {
+            // The crossfade scales the cues' progress, so they start from
+            // their timed fades afresh each tick.
+            for index in crossfade.cues() {
+                if let Some(cue) = program.cues.get_mut(index) {
+                    cue.update(elapsed);
+                }
+            }
+            crossfade.apply(&mut program.cues);
+        }
+        stages.lap("Cues");
+        self.swap_varied(&mut program.cues);
+        let mut frame = dmx::render(&program.fixtures, &program.cues);
+        self.swap_varied(&mut program.cues);
+        stages.lap("Merge");
+        let mut owners = if program.owners {
+            ownership::cue_owners(&program.fixtures, &program.cues)
+        } else {
+            Owners::untracked()
+        };
+        stages.lap("Ownership");
+        self.move_in_black.apply(
+            &program.fixtures,
+            &program.cues,
+            elapsed,
+            program.move_in_black_lead,
+            &mut frame,
+        );
+        for (id, index) in self.move_in_black.marks() {
+            let Some(fixture) = program.fixtures.iter().find(|fixture| fixture.id == id) else {
+                continue;
+            };
+            for value in program.cues[index]
+                .values
+                .iter()
+                .filter(|value| value.fixture_id == id && value.attribute != Attribute::Intensity)
+            {
+                owners.claim_attribute(fixture, value.attribute, Owner::MoveInBlack(index));
+            }
+        }
+        let tick = if program.effects_frozen {
+            0.0
+        } else {
+            now.saturating_duration_since(self.last_tick).as_secs_f32()
+        };
+        self.last_tick = now;
+        self.effect_time += tick;
+        let effect_time = self.effect_time;
+        self.effect_clocks
+            .advance(&program.speed_rates, tick, effect_time);
+        // Shifts follow the cue the fixture's colour comes from, unless it
+        // is being pre-positioned for the next one.
+        if program.cues.iter().any(|cue| cue.hue_shift.is_some()) {
+            let mut colours = ownership::attribute_cues(&program.cues, Attribute::Red);
+            for (id, index) in self.move_in_black.marks() {
+                if program.cues[index]
+                    .values
+                    .iter()
+                    .any(|value| value.fixture_id == id && value.attribute == Attribute::Red)
+                {
+                    colours.remove(&id);
+                }
+            }
+            for fixture in &program.fixtures {
+                if fixture.channel_for(Attribute::Red).is_none() {
+                    continue;
+                }
+                let Some(&index) = colours.get(&fixture.id) else {
+                    continue;
+                };
+                if let Some(shift) = &program.cues[index].hue_shift {
+                    let time = self.effect_clocks.time(shift.speed_master, effect_time);
+                    HueShift::apply(shift.phase(time), fixture, &mut frame);
+                }
+            }
+        }
+        let clock = PixelClock::at(
+            now.saturating_duration_since(self.origin).as_secs_f32(),
+            program.bpm,
+            program.beats_per_bar,
+        )
+        .with_audio(audio);
+        if let Some(busk) = &program.busk {
+            let beats =
+                self.effect_clocks.time(busk.speed_master, effect_time) * program.bpm / 60.0;
+            busk.render(&program.fixtures, beats, &mut frame);
+            owners.claim_busk(busk, &program.fixtures, beats);
+        }
+        // Network pixel maps bypass the frame, so they wait for the panic and
+        // release handling below.
+        let mut streamed = Vec::new();
+        for (index, map) in program.pixel_maps.iter().enumerate() {
+            let clock =
+                clock.with_effect_time(self.effect_clocks.time(map.speed_master, effect_time));
+            match map.output {
+                PixelOutput::Dmx => {
+                    map.render(clock, plugins, &mut frame);
+                    if map.enabled {
+                        owners.claim_pixel_map(map, index);
+                    }
+                }
+                _ if map.enabled && program.pixel_streams => {
+                    streamed.push((index, map.wired(clock, plugins).concat()));
+                }
+                _ => {}
+            }
+        }
+        stages.lap("Effects and pixel maps");
+        if let Some(settings) = &program.haze {
+            let output = match self.transport {
+                Transport::Running { .. } => {
+                    settings.output_at(elapsed, haze::show_end(&program.cues), program.smoke)
+                }
+                Transport::Stopped { .. } => {
+                    (program.smoke > 0.0).then_some(settings.pad_output * program.smoke)
+                }
+            };
+            if let Some(output) = output {
+                settings.render(&program.fixtures, output, &mut frame);
+                let owner = if program.smoke > 0.0 {
+                    Owner::SmokePad
+                } else {
+                    Owner::HazeSchedule
+                };
+                for fixture in program
+                    .fixtures
+                    .iter()
+                    .filter(|fixture| settings.fixtures.contains(&fixture.id))
+                {
+                    owners.claim_attribute(fixture, Attribute::Haze, owner);
+                }
+            }
+        }
+        if let Some(settings) = &program.input {
+            let before = frame.clone();
+            input.merge(settings, &mut frame);
+            owners.claim_changed(&before, &frame, Owner::Input);
+        }
+        quick_console::apply(&program.quick_levels, &program.fixtures, &mut frame);
+        for fixture in program
+            .fixtures
+            .iter()
+            .filter(|fixture| program.quick_levels.contains_key(&fixture.id))
+        {
+            owners.claim_attribute(fixture, Attribute::Intensity, Owner::QuickConsole);
+        }
+        self.follow_aims.apply(
+            &program.follow_spots,
+            trackers,
+            &program.fixtures,
+            &mut frame,
+            now,
+        );
+        self.follow_aims.claim(&program.fixtures, &mut owners);
+        stages.lap("Haze, input and follow spots");
+        let programmed = frame.clone();
+        flash::apply(&program.flashes, &program.fixtures, &mut frame, &mut owners);
+        group_master::apply(&program.group_masters, &program.fixtures, &mut frame);
+        owners.claim_group_masters(&program.group_masters, &program.fixtures);
+        group_master::apply_grand_master(program.grand_master, &program.fixtures, &mut frame);
+        if program.grand_master < 1.0 {
+            for fixture in &program.fixtures {
+                owners.claim_attribute(fixture, Attribute::Intensity, Owner::GrandMaster);
+            }
+        }
+        stages.lap("Masters");
+        processing::apply(&program.fixtures, &mut frame);
+        owners.claim_processing(&program.fixtures);
+        let inhibited = inhibit::channels(&program.fixtures);
+        inhibit::apply(&inhibited, &mut frame);
+        owners.claim_inhibited(&inhibited);
+        fixture_control::apply(
+            &program.controls,
+            &program.fixtures,
+            &mut frame,
+            &mut owners,
+        );
+        if self
+            .back_fade
+            .as_ref()
+            .is_some_and(|fade| !fade.apply(&mut frame, now))
+        {
+            self.back_fade = None;
+        }
+        if let Some(check) = &program.channel_check {
+            check.apply(&mut frame);
+            if check.solo {
+                owners.claim_all(&frame, Owner::ChannelCheck);
+            }
+            owners.claim(check.universe, check.channel, Owner::ChannelCheck);
+        }
+        if let Some(test) = &program.test_pattern {
+            test.apply(
+                &mut frame,
+                now.saturating_duration_since(self.origin).as_secs_f32(),
+            );
+            for channel in 1..=UNIVERSE_SIZE as u16 {
+                owners.claim(test.universe, channel, Owner::TestPattern);
+            }
+        }
+        // A panic takes over from a release in progress.
+        match program.panic.as_ref().or(program.release.as_ref()) {
+            Some(fade) => {
+                frame = fade_frame(fade, &mut self.fade_from, &self.last_frame, now);
+                let owner = if program.panic.is_some() {
+                    Owner::Panic
+                } else {
+                    Owner::Release
+                };
+                owners.claim_all(&frame, owner);
+            }
+            None => self.fade_from = None,
+        }
+        self.last_frame = frame.clone();
+        stages.lap("Processing and overrides");
+        Rendered {
+            frame,
+            programmed,
+            owners,
+            elapsed,
+            events,
+            streamed,
+            stages: stages.finish(),
+        }
+    }
+}
+
+fn run(
+    sources: Sources,
+    mut program: Program,
+    mut previous: Option<thread::JoinHandle<()>>,
+    commands: Receiver<(u64, EngineCommand)>,
+    snapshots: SyncSender<EngineSnapshot>,
+    events: Sender<EngineEvent>,
+) {
+    let Sources {
+        origin,
+        audio,
+        input,
+        trackers,
+        heartbeat,
+        playhead,
+        handover,
+    } = sources;
+    let period = Duration::from_secs_f32(1.0 / OUTPUT_HZ);
+    let mut revision = 0;
+    let mut output_frames = FrameTimer::new(Some(OUTPUT_HZ));
+    let mut stage_times = StageTimes::default();
+    let mut next_tick = Instant::now();
+    let mut renderer = Renderer::new(origin, &program, next_tick);
+    let mut art_net: Option<ArtNetSender> = None;
+    let mut sacn: Option<SacnSender> = None;
+    let mut plugins = PluginHost::default();
+    plugins.load(&program.plugins);
+    let mut plugin_socket: Option<UdpSocket> = None;
+    let mut bulbs: Option<BulbBridge> = None;
+    let mut drivers: Option<DriverBridge> = None;
+    let mut streams = PixelStreams::default();
+    let mut assert = false;
+    let mut recorder: Option<Box<Recorder>> = None;
+    let mut player: Option<Box<Player>> = None;
+    let mut recording_error = None;
+
+    loop {
+        // Wait out the rest of the period, applying commands as they come so
+        // transport changes take effect at the moment they were made.
+        loop {
+            let now = Instant::now();
+            if now >= next_tick {
+                break;
+            }
+            let (new_revision, command) = match commands.recv_timeout(next_tick - now) {
+                Ok(received) => received,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    // Left behind by a restart: the thread that replaced
+                    // this one carries on with what it had open.
+                    if let Ok(mut handover) = handover.lock() {
+                        *handover = Some(Sessions {
+                            art_net,
+                            sacn,
+                            plugin_socket,
+                            bulbs,
+                            drivers,
+                            streams,
+                            recorder,
+                            player,
+                        });
+                    }
+                    return;
+                }
+            };
+            revision = new_revision;
+            let now = Instant::now();
+            match command {
+                EngineCommand::Program(new_program) => {
+                    renderer.load(&program, &new_program);
+                    program = *new_program;
+                    plugins.load(&program.plugins);
+                }
+                EngineCommand::Start => renderer.start(now),
+                EngineCommand::Stop => renderer.stop(now),
+                EngineCommand::Seek(position) => renderer.seek(position, now),
+                EngineCommand::Join { position, window } => renderer.join(position, window, now),
+                EngineCommand::HoldAt(position) => renderer.hold_at(position),
+                EngineCommand::Assert => assert = true,
+                EngineCommand::BackFade(fade) => renderer.back_fade(fade, now),
+                EngineCommand::Record(new) => {
+                    recording_error = None;
+                    if let Some(old) = std::mem::replace(&mut recorder, new) {
+                        if let Err(err) = old.finish() {
+                            recording_error =
+                                Some(tr!("engine-recording-failed", error = err.to_string()));
+                        }
+                    }
+                }
+                EngineCommand::Play(new) => {
+                    recording_error = None;
+                    player = new;
+                }
+                EngineCommand::Shutdown => {
+                    if let Some(recorder) = recorder.take() {
+                        let _ = recorder.finish();
+                    }
+                    let frame = renderer.final_frame(&program, now);
+                    if let (Some(settings), Some(sender)) = (&program.art_net, &mut art_net) {
+                        sender.flush(settings, &driver::dmx_only(&program.fixtures, &frame));
+                    }
+                    if let (Some(settings), Some(sender)) = (&program.sacn, &mut sacn) {
+                        let dmx = driver::dmx_only(&program.fixtures, &frame);
+                        sender.send(settings, &program.fixtures, &dmx);
+                    }
+                    let _ = snapshots.try_send(EngineSnapshot {
+                        revision,
+                        programmed: frame.clone(),
+                        frame,
+                        owners: Owners::default(),
+                        elapsed: renderer.elapsed_at(now),
+                        cues: Vec::new(),
+                        output_frames,
+                        stage_times: Vec::new(),
+                        art_net_rates: Vec::new(),
+                        plugin_errors: Vec::new(),
+                        smart_bulb_error: None,
+                        driver_error: None,
+                        pixel_stream_error: None,
+                        recording: None,
+                        playback: None,
+                        recording_error: None,
+                    });
+                    return;
+                }
+            }
+        }
+
+        if previous.as_ref().is_some_and(|thread| thread.is_finished()) {
+            let _ = previous.take().map(thread::JoinHandle::join);
+            // A thread that panicked leaves nothing, and everything is
+            // opened afresh.
+            let carried = handover
+                .lock()
+                .ok()
+                .and_then(|mut handover| handover.take());
+            if let Some(carried) = carried {
+                Sessions {
+                    art_net,
+                    sacn,
+                    plugin_socket,
+                    bulbs,
+                    drivers,
+                    streams,
+                    recorder,
+                    player,
+                } = carried;
+            }
+            assert = true;
+            let _ = events.send(EngineEvent::TookOver);
+        }
+
+        let now = Instant::now();
+        let Rendered {
+            mut frame,
+            programmed,
+            mut owners,
+            elapsed,
+            events: happened,
+            streamed,
+            mut stages,
+        } = renderer.render(
+            &mut program,
+            &plugins,
+            audio.levels(),
+            &input,
+            &trackers,
+            now,
+        );
+        let sending = Instant::now();
+        // A panic or release still takes over from a recording.
+        if let Some(playing) = player
+            .as_mut()
+            .filter(|_| program.panic.is_none() && program.release.is_none())
+        {
+            match playing.frame(now) {
+                Ok(played) => {
+                    frame = played.clone();
+                    owners.clear();
+                    owners.claim_all(&frame, Owner::Recording);
+                }
+                Err(err) => {
+                    recording_error = Some(tr!("engine-playback-failed", error = err.to_string()));
+                    player = None;
+                }
+            }
+        }
+        if player.as_ref().is_some_and(|player| player.is_finished()) {
+            player = None;
+        }
+        for event in happened {
+            let _ = events.send(event);
+        }
+        playhead.set(renderer.transport);
+        let dmx = test_pattern::unmuted(
+            &program.muted_universes,
+            driver::dmx_only(&program.fixtures, &frame),
+        );
+        let mut stream_error = None;
+        // Until a thread left behind by a restart lets go of the outputs,
+        // opening them again could fail or send twice.
+        if previous.is_none() {
+            if let Some(settings) = &program.art_net {
+                if art_net
+                    .as_ref()
+                    .is_none_or(|sender| sender.interface() != program.art_net_interface.as_ref())
+                {
+                    art_net = ArtNetSender::open(program.art_net_interface.clone()).ok();
+                }
+                if let Some(sender) = &mut art_net {
+                    if std::mem::take(&mut assert) {
+                        sender.flush(settings, &dmx);
+                    } else {
+                        sender.send(settings, &dmx);
+                    }
+                }
+            }
+            // sACN sends every universe every frame, so there is nothing to
+            // assert, and dropping the sender tells receivers it stopped.
+            match &program.sacn {
+                Some(settings) => {
+                    if sacn
+                        .as_ref()
+                        .is_none_or(|sender| sender.interface() != program.sacn_interface.as_ref())
+                    {
+                        sacn = SacnSender::open(program.sacn_interface.clone()).ok();
+                    }
+                    if let Some(sender) = &mut sacn {
+                        sender.send(settings, &program.fixtures, &dmx);
+                    }
+                }
+                None => sacn = None,
+            }
+            if let Some(recording) = &mut recorder {
+                let recorded = match recording.source() {
+                    RecordSource::Output => recording.record(&dmx, now),
+                    RecordSource::Input => recording.record(&input.frame(), now),
+                };
+                if let Err(err) = recorded {
+                    recording_error = Some(tr!("engine-recording-failed", error = err.to_string()));
+                    recorder = None;
+                }
+            }
+            if !program.plugin_outputs.is_empty() {
+                if plugin_socket.is_none() {
+                    plugin_socket = UdpSocket::bind("0.0.0.0:0").ok();
+                }
+                if let Some(socket) = &plugin_socket {
+                    send_plugin_outputs(&plugins, &program.plugin_outputs, socket, &dmx);
+                }
+            }
+            for (index, mut data) in streamed {
+                let map = &program.pixel_maps[index];
+                // There's no safe look for pixels to fade to, so they go dark.
+                if program.panic.is_some() || program.release.is_some() {
+                    data.fill(0);
+                }
+                if let Err(err) = streams.send(&map.host, map.output, &data) {
+                    stream_error = Some(format!("{}: {}", map.name, err));
+                }
+            }
+            match &program.smart_bulbs {
+                Some(settings) => bulbs.get_or_insert_with(BulbBridge::start).update(
+                    settings,
+                    &program.fixtures,
+                    &frame,
+                ),
+                None => bulbs = None,
+            }
+            if program.drivers {
+                drivers
+                    .get_or_insert_with(DriverBridge::start)
+                    .update(&program.fixtures, &frame);
+            } else {
+                drivers = None;
+            }
+        }
+        stages.push(("Sending", sending.elapsed()));
+        stage_times.record(&stages);
+        output_frames.tick(now);
+        heartbeat.store(
+            now.saturating_duration_since(origin).as_millis() as u64,
+            Ordering::Relaxed,
+        );
+
+        let _ = snapshots.try_send(EngineSnapshot {
+            revision,
+            frame,
+            programmed,
+            owners,
+            elapsed,
+            cues: program
+                .cues
+                .iter()
+                .map(|cue| (cue.is_playing, cue.progress))
+                .collect(),
+            output_frames: output_frames.clone(),
+            stage_times: stage_times.summary(),
+            art_net_rates: match (&program.art_net, &art_net) {
+                (Some(_), Some(sender)) => sender.rates(),
+                _ => Vec::new(),
+            },
+            plugin_errors: plugins.errors(),
+            smart_bulb_error: bulbs.as_ref().and_then(BulbBridge::error),
+            driver_error: drivers.as_ref().and_then(DriverBridge::error),
+            pixel_stream_error: stream_error,
+            recording: recorder.as_ref().map(|recorder| recorder.length()),
+            playback: player
+                .as_ref()
+                .map(|player| (player.position(), player.length())),
+            recording_error: recording_error.clone(),
+        });
+
+        // Keep to the fixed schedule, but after a stall start afresh rather
+        // than rushing out the missed frames back to back.
+        next_tick += period;
+        if now.saturating_duration_since(next_tick) > period {
+            next_tick = now + period;
+        }
+    }
+}
+
+/// Sends every universe in `frame` through each plugin output protocol.
+fn send_plugin_outputs(
+    plugins: &PluginHost,
+    outputs: &[PluginOutput],
+    socket: &UdpSocket,
+    frame: &DmxFrame,
+) {
+    for output in outputs {
+        for universe in frame.universe_numbers() {
+            let Some(data) = frame.universe(universe) else {
+                continue;
+            };
+            if let Some(packet) = plugins.packet(&output.plugin, universe, data) {
+                let _ = socket.send_to(&packet, output.target.as_str());
+            }
+        }
+    }
+}
+
+/// The output `fade` puts out at `now`, fading from the frame that was on air
+/// when it started, which `from` keeps.
+fn fade_frame(
+    fade: &Panic,
+    from: &mut Option<(Instant, DmxFrame)>,
+    on_air: &DmxFrame,
+    now: Instant,
+) -> DmxFrame {
+    if from
+        .as_ref()
+        .is_some_and(|(started, _)| *started != fade.started)
+    {
+        *from = None;
+    }
+    let (_, from) = from.get_or_insert_with(|| (fade.started, on_air.clone()));
+    fade.frame(from, now)
+}
+
+impl HaloApp {
+    /// Merge settings for the engine while network input is running.
+    fn merge_settings(&self) -> Option<&InputSettings> {
+        self.network_input
+            .is_some()
+            .then_some(&self.preferences.network_input)
+    }
+
+    fn program(&self) -> Program {
+        Program {
+            cues: self.show.timeline_cues(),
+            fixtures: self.rig().into_owned(),
+            pixel_maps: self.show.pixel_maps.clone(),
+            move_in_black_lead: self.show.move_in_black_lead,
+            busk: self.busk_enabled.then(|| self.show.busk.clone()),
+            input: self.merge_settings().cloned(),
+            channel_check: self.channel_check,
+            test_pattern: self.test_pattern,
+            muted_universes: self.muted_universes.clone(),
+            crossfade: self.manual_fade(),
+            panic: self.panic.clone(),
+            release: self.release.clone(),
+            bpm: self.bpm,
+            beats_per_bar: self.show.time_signature.beats_per_bar(),
+            art_net: self.art_net_output().cloned(),
+            art_net_interface: self.bound_interface(Protocol::ArtNet).cloned(),
+            sacn: self.sacn_output().cloned(),
+            sacn_interface: self.bound_interface(Protocol::Sacn).cloned(),
+            haze: self.haze_settings().cloned(),
+            smoke: self.smoke_level(),
+            speed_rates: self.speed_rates(),
+            effects_frozen: self.effects_frozen,
+            group_masters: self.show.group_masters.clone(),
+            grand_master: self.grand_master,
+            plugins: self.plugins.clone(),
+            plugin_outputs: self.plugin_outputs(),
+            smart_bulbs: self.smart_bulbs().cloned(),
+            pixel_streams: !self.holds_output_for_main(),
+            drivers: self.drives_fixtures(),
+            quick_levels: self.quick_levels.clone(),
+            controls: self.fixture_controls.levels(Instant::now()),
+            follow_spots: self.follow_spots().to_vec(),
+            flashes: self.live_flashes(),
+            owners: self.owners_shown.get(),
+        }
+    }
+
+    /// The follow spots the engine aims, while PSN input is on.
+    fn follow_spots(&self) -> &[FollowSpot] {
+        if self.psn.is_some() {
+            &self.show.follow_spots
+        } else {
+            &[]
+        }
+    }
+
+    fn smart_bulbs(&self) -> Option<&SmartBulbSettings> {
+        let bulbs = &self.show.smart_bulbs;
+        (bulbs.enabled && !bulbs.bulbs.is_empty() && !self.holds_output_for_main()).then_some(bulbs)
+    }
+
+    /// Whether any fixture has a driver and the output isn't held for the
+    /// main.
+    fn drives_fixtures(&self) -> bool {
+        !self.holds_output_for_main()
+            && self
+                .show
+                .fixtures
+                .iter()
+                .any(|fixture| fixture.driver.is_some())
+    }
+
+    fn plugin_outputs(&self) -> Vec<PluginOutput> {
+        if self.holds_output_for_main() {
+            return Vec::new();
+        }
+        self.preferences
+            .plugin_outputs
+            .iter()
+            .filter(|output| output.enabled && !output.target.is_empty())
+            .cloned()
+            .collect()
+    }
+
+    fn art_net_output(&self) -> Option<&ArtNetOutput> {
+        let output = &self.preferences.art_net_output;
+        (output.enabled && !self.holds_output_for_main()).then_some(output)
+    }
+
+    fn sacn_output(&self) -> Option<&SacnOutput> {
+        let output = &self.preferences.sacn_output;
+        (output.enabled && !self.holds_output_for_main()).then_some(output)
+    }
+
+    fn haze_settings(&self) -> Option<&HazeSettings> {
+        let haze = &self.show.haze;
+        haze.enabled.then_some(haze)
+    }
+
+    fn speed_rates(&self) -> Vec<f32> {
+        self.show
+            .speed_masters
+            .iter()
+            .map(|master| master.rate)
+            .collect()
+    }
+
+    /// Restarts the engine if it has stalled, picking up the show where the
+    /// UI last saw it: the same program, playhead and transport.
+    pub(crate) fn check_engine(&mut self) {
+        let Some(stall) = self.engine.stall() else {
+            return;
+        };
+        let details = tr!(
+            "engine-stall-details",
+            since = stall.since_frame.as_millis() as u64,
+            pending = stall.pending,
+            playhead = Self::format_duration(self.elapsed),
+            transport = self.transport.label().to_lowercase()
+        );
+        self.log.error(if stall.exited {
+            tr!("engine-exited", details = details)
+        } else {
+            tr!("engine-stalled", details = details)
+        });
+        let recording = &self.output_recording;
+        if !stall.exited {
+            // The new thread can't open the outputs while the stuck one
+            // holds them; see `Engine::restart`.
+            self.log.warn(tr!("engine-outputs-waiting"));
+        } else if recording.recording.is_some() || recording.playback.is_some() {
+            self.log.warn(tr!("engine-recording-lost"));
+        }
+        self.engine.restart();
+        self.engine.send(self.program());
+        self.engine.seek(self.elapsed);
+        if self.transport.is_running() {
+            self.engine.start_clock();
+        }
+        self.engine.hold_at(self.setlist_hold());
+        self.engine_restarts += 1;
+        self.engine_restarted = Some(Instant::now());
+        self.notify(LogLevel::Error, tr!("engine-restarted"));
+    }
+
+    /// Takes the latest frame and clock from the engine and acts on the cues
+    /// it fired since the last UI frame.
+    pub(crate) fn receive_engine_output(&mut self) {
+        if let Some(snapshot) = self.engine.latest_snapshot() {
+            if self.engine.has_clock(&snapshot) {
+                self.elapsed = snapshot.elapsed;
+            }
+            if snapshot.cues.len() == self.show.cues.len() {
+                for (cue, (is_playing, progress)) in self.show.cues.iter_mut().zip(snapshot.cues) {
+                    cue.is_playing = is_playing;
+                    cue.progress = progress;
+                }
+            }
+            self.output = snapshot.frame;
+            self.programmed_output = snapshot.programmed;
+            self.output_owners = snapshot.owners;
+            self.output_frames = snapshot.output_frames;
+            self.stage_times = snapshot.stage_times;
+            self.art_net_rates = snapshot.art_net_rates;
+            self.plugin_errors = snapshot.plugin_errors;
+            self.smart_bulb_error = snapshot.smart_bulb_error;
+            self.driver_error = snapshot.driver_error;
+            self.pixel_stream_error = snapshot.pixel_stream_error;
+            self.output_recording.recording = snapshot.recording;
+            self.output_recording.playback = snapshot.playback;
+            if snapshot.recording_error.is_some() {
+                self.output_recording.error = snapshot.recording_error;
+            }
+        }
+
+        for event in self.engine.events() {
+            match event {
+                EngineEvent::CueFired(index, past) => {
+                    // Chasing, the cue is as late as it went past its time
+                    // on incoming timecode, which Halo's clock leads by the
+                    // drift.
+                    let late = past.as_secs_f64() - self.timecode_drift().unwrap_or(0.0);
+                    self.run_cue_actions(index, Some(late));
+                }
+                EngineEvent::CueEnded(index) => {
+                    self.run_cue_triggers(index, TriggerWhen::End);
+                    let last = self
+                        .show
+                        .cues
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, cue)| self.show.plays(cue))
+                        .max_by_key(|(_, cue)| self.show.cue_start(cue) + cue.duration)
+                        .map(|(i, _)| i);
+                    if last == Some(index) {
+                        self.notify(LogLevel::Info, tr!("engine-end-of-cues"));
+                        if self.show.haze.enabled {
+                            self.log.info(tr!("engine-hazers-off"));
+                        }
+                        if !self.setlist_mode {
+                            self.run_end_action(self.show.end_action, None);
+                        }
+                    }
+                }
+                EngineEvent::Held => {
+                    // The engine stopped its own clock; the output stays.
+                    self.transport = crate::transport::Transport::Stopped;
+                    let song = self.setlist_song().map(|song| (song.id, song.end_action));
+                    match song {
+                        Some((id, action)) if !action.is_continue() => {
+                            self.run_end_action(action, Some(id));
+                        }
+                        _ => self.notify(LogLevel::Info, tr!("engine-held")),
+                    }
+                }
+                EngineEvent::TookOver => self.log.info(tr!("engine-took-over")),
+            }
+        }
+    }
+
+    /// Runs the script, start triggers and macro of the cue at `index`, as
+    /// it fires, `late` seconds after its time when the clock fired it.
+    pub(crate) fn run_cue_actions(&mut self, index: usize, late: Option<f64>) {
+        if index >= self.show.cues.len() {
+            return;
+        }
+        self.record_cue_fire(index, late);
+        self.run_script(ScriptTarget::Cue(index));
+        self.run_cue_triggers(index, TriggerWhen::Start);
+        if let Some(name) = self.show.cues[index].run_macro.clone() {
+            self.start_macro(&name);
+        }
+    }
+
+    /// Sends the engine whatever changed in the show this frame.
+    pub(crate) fn sync_engine(&mut self, ctx: &egui::Context) {
+        let sent = self.engine.sent();
+        if !self.show.is_timeline(&sent.cues)
+            || sent.fixtures[..] != *self.rig()
+            || sent.pixel_maps != self.show.pixel_maps
+            || sent.move_in_black_lead != self.show.move_in_black_lead
+            || sent.busk.as_ref() != self.busk_enabled.then_some(&self.show.busk)
+            || sent.input.as_ref() != self.merge_settings()
+            || sent.channel_check != self.channel_check
+            || sent.test_pattern != self.test_pattern
+            || sent.muted_universes != self.muted_universes
+            || sent.crossfade != self.manual_fade()
+            || sent.panic != self.panic
+            || sent.release != self.release
+            || sent.bpm != self.bpm
+            || sent.beats_per_bar != self.show.time_signature.beats_per_bar()
+            || sent.art_net.as_ref() != self.art_net_output()
+            || sent.art_net_interface.as_ref() != self.bound_interface(Protocol::ArtNet)
+            || sent.sacn.as_ref() != self.sacn_output()
+            || sent.sacn_interface.as_ref() != self.bound_interface(Protocol::Sacn)
+            || sent.haze.as_ref() != self.haze_settings()
+            || sent.smoke != self.smoke_level()
+            || sent.speed_rates != self.speed_rates()
+            || sent.effects_frozen != self.effects_frozen
+            || sent.group_masters != self.show.group_masters
+            || sent.grand_master != self.grand_master
+            || sent.plugins != self.plugins
+            || sent.plugin_outputs != self.plugin_outputs()
+            || sent.smart_bulbs.as_ref() != self.smart_bulbs()
+            || sent.pixel_streams == self.holds_output_for_main()
+            || sent.drivers != self.drives_fixtures()
+            || sent.quick_levels != self.quick_levels
+            || sent.controls != self.fixture_controls.levels(Instant::now())
+            || sent.follow_spots != self.follow_spots()
+            || sent.flashes != self.live_flashes()
+            || sent.owners != self.owners_shown.get()
+        {
+            self.engine.send(self.program());
+        }
+        // Asked for again by whatever shows them next frame.
+        self.owners_shown.set(false);
+        self.engine.hold_at(self.setlist_hold());
+        if self.engine.is_behind() {
+            // Come back for the frame that shows the change.
+            ctx.request_repaint_after(Duration::from_secs_f32(1.0 / OUTPUT_HZ));
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Failures the operator has to do something about: a show file that won't
+//! open, or a device chosen in Preferences that won't. They go in the log
+//! like everything else, and also up in front of the operator with a way
+//! forward, rather than leaving them to notice a red line in Preferences or
+//! a device that is quietly doing nothing.
+
+use crate::i18n::tr;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A device Halo opens from the preferences.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Device {
+    AudioInput,
+    Click,
+    Playback,
+    MidiInput,
+    MidiOutput,
+    Buttons,
+}
+
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&match self {
+            Device::AudioInput => tr!("device-audio-input"),
+            Device::Click => tr!("device-click"),
+            Device::Playback => tr!("device-playback"),
+            Device::MidiInput => tr!("device-midi-input"),
+            Device::MidiOutput => tr!("device-midi-output"),
+            Device::Buttons => tr!("device-buttons"),
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum HaloError {
+    /// A show that couldn't be read, or wasn't a show.
+    ShowFile { path: PathBuf, message: String },
+    /// A device that couldn't be opened, by the name it was chosen by.
+    Device {
+        device: Device,
+        name: String,
+        message: String,
+    },
+}
+
+impl HaloError {
+    pub fn show_file(path: impl Into<PathBuf>, error: impl fmt::Display) -> Self {
+        HaloError::ShowFile {
+            path: path.into(),
+            message: error.to_string(),
+        }
+    }
+
+    pub fn device(device: Device, name: impl Into<String>, error: impl fmt::Display) -> Self {
+        HaloError::Device {
+            device,
+            name: name.into(),
+            message: error.to_string(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            HaloError::ShowFile { .. } => tr!("error-title-show"),
+            HaloError::Device { device, .. } => match device {
+                Device::AudioInput => tr!("error-title-audio-input"),
+                Device::Click => tr!("error-title-click"),
+                Device::Playback => tr!("error-title-playback"),
+                Device::MidiInput => tr!("error-title-midi-input"),
+                Device::MidiOutput => tr!("error-title-midi-output"),
+                Device::Buttons => tr!("error-title-buttons"),
+            },
+        }
+    }
+
+    /// The label of the button for trying something else instead.
+    pub fn alternative(&self) -> String {
+        match self {
+            HaloError::ShowFile { .. } => tr!("error-another-file"),
+            HaloError::Device { .. } => tr!("error-another-device"),
+        }
+    }
+}
+
+impl fmt::Display for HaloError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HaloError::ShowFile { path, message } => f.write_str(&tr!(
+                "error-open-show",
+                path = path.display().to_string(),
+                message = message.as_str()
+            )),
+            HaloError::Device {
+                device,
+                name,
+                message,
+            } => f.write_str(&tr!(
+                "error-open-device",
+                device = device.to_string(),
+                name = name.as_str(),
+                message = message.as_str()
+            )),
+        }
+    }
+}
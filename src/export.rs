@@ -0,0 +1,715 @@
+use crate::fixture::Fixture;
+use crate::marker;
+use crate::power;
+use crate::session::{format_late, Session};
+use crate::shortcuts::SHORTCUTS;
+use crate::show::Show;
+use std::fmt;
+use std::fmt::Write;
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Multi-line free text keeps its line breaks in the rendered page.
+fn paragraph(text: &str) -> String {
+    if text.trim().is_empty() {
+        return "<p class=\"empty\">None recorded.</p>".to_string();
+    }
+    format!("<p>{}</p>", escape(text.trim()).replace('\n', "<br>"))
+}
+
+fn format_time(time: std::time::Duration) -> String {
+    let secs = time.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+        time.subsec_millis()
+    )
+}
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#111}\
+h1{margin-bottom:0}table{border-collapse:collapse;margin-bottom:1.5em}\
+th,td{border:1px solid #999;padding:4px 8px;text-align:left;vertical-align:top}\
+th{background:#eee}.pads td{width:6em;height:3em;text-align:center;font-weight:bold}\
+.empty{color:#888;font-style:italic}tr.marker td{color:#a60}\
+kbd{border:1px solid #999;border-radius:3px;padding:0 4px}\
+@media print{section{page-break-inside:avoid}}";
+
+/// Cue sheet rows for the markers just before the cue at `index`.
+fn html_markers(html: &mut String, show: &Show, slots: &[usize], index: usize) {
+    for (_, marker) in marker::before(show, slots, index) {
+        let _ = writeln!(
+            html,
+            "<tr class=\"marker\"><td>◆</td><td><em>{}</em></td><td>{}</td><td></td><td></td></tr>",
+            escape(&marker.text),
+            format_time(marker.time)
+        );
+    }
+}
+
+/// Builds a single self-contained HTML page with everything a substitute
+/// operator needs to cover the show: contacts, standby notes, the cue sheet,
+/// the pad layout and the keyboard shortcut map.
+pub fn handoff_html(show: &Show) -> String {
+    let mut html = String::new();
+    let title = escape(&show.name);
+    let generated = chrono::Local::now().format("%Y-%m-%d %H:%M");
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title} - Operator Handoff</title>\
+         <style>{STYLE}</style></head><body>\n\
+         <h1>{title}</h1><p>Operator handoff generated {generated}</p>\n"
+    );
+
+    let _ = write!(
+        html,
+        "<section><h2>Contacts</h2>{}</section>\n<section><h2>Standby Notes</h2>{}</section>\n",
+        paragraph(&show.contacts),
+        paragraph(&show.notes)
+    );
+
+    html.push_str(
+        "<section><h2>Cue Sheet</h2><table><tr><th>Cue</th><th>Name</th><th>Timecode</th>\
+         <th>Fade</th><th>Notes</th></tr>\n",
+    );
+    let slots = marker::slots(show);
+    for (i, cue) in show.cues.iter().enumerate() {
+        if !cue.section.is_empty() {
+            let _ = writeln!(
+                html,
+                "<tr><th colspan=\"5\">{}</th></tr>",
+                escape(&cue.section)
+            );
+        }
+        html_markers(&mut html, show, &slots, i);
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            cue.number,
+            escape(&cue.name),
+            if cue.disarmed { " (disarmed)" } else { "" },
+            format_time(show.cue_start(cue)),
+            cue.timing_label(),
+            escape(&cue.notes).replace('\n', "<br>")
+        );
+    }
+    html_markers(&mut html, show, &slots, show.cues.len());
+    html.push_str("</table></section>\n");
+
+    html.push_str("<section><h2>Override Pads</h2><table class=\"pads\">\n");
+    let layout = &show.pad_layout;
+    for (i, bank) in layout.banks.iter().enumerate() {
+        if layout.banks.len() > 1 {
+            let _ = writeln!(
+                html,
+                "<tr><th colspan=\"{}\">{}</th></tr>",
+                layout.columns,
+                escape(&bank.name)
+            );
+        }
+        for row in layout.rows(&show.pads, i) {
+            html.push_str("<tr>");
+            for pad in row.iter().map(|&i| &show.pads[i]) {
+                let _ = write!(
+                    html,
+                    "<td colspan=\"{}\">{}</td>",
+                    pad.width.clamp(1, layout.columns.max(1)),
+                    escape(&pad.label)
+                );
+            }
+            html.push_str("</tr>\n");
+        }
+    }
+    html.push_str("</table></section>\n");
+
+    html.push_str("<section><h2>Keyboard Shortcuts</h2><table>\n");
+    for shortcut in SHORTCUTS {
+        let _ = writeln!(
+            html,
+            "<tr><td><kbd>{}</kbd></td><td>{}</td></tr>",
+            shortcut.name(),
+            escape(&shortcut.description())
+        );
+    }
+    html.push_str("</table></section>\n</body></html>\n");
+    html
+}
+
+/// The formats the show report can be exported in.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+    Pdf,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 3] = [
+        ReportFormat::Markdown,
+        ReportFormat::Html,
+        ReportFormat::Pdf,
+    ];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+            ReportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ReportFormat::Markdown => "Markdown",
+            ReportFormat::Html => "HTML",
+            ReportFormat::Pdf => "PDF",
+        })
+    }
+}
+
+/// The fixture's type and channel count, as the patch report lists it.
+fn fixture_mode(fixture: &Fixture) -> String {
+    format!(
+        "{} ({}ch)",
+        fixture.profile.name,
+        fixture.profile.channels.len()
+    )
+}
+
+fn fixture_address(fixture: &Fixture) -> String {
+    let channels = fixture.profile.channels.len() as u16;
+    if channels > 1 {
+        format!(
+            "{}.{}-{}",
+            fixture.universe,
+            fixture.address,
+            fixture.address + channels - 1
+        )
+    } else {
+        format!("{}.{}", fixture.universe, fixture.address)
+    }
+}
+
+/// Builds the cue sheet and patch report for the production book.
+pub fn show_report(show: &Show, format: ReportFormat) -> Vec<u8> {
+    match format {
+        ReportFormat::Markdown => report_markdown(show).into_bytes(),
+        ReportFormat::Html => report_html(show).into_bytes(),
+        ReportFormat::Pdf => pdf(&format!("{} - Show Report", show.name), &report_lines(show)),
+    }
+}
+
+/// The power section's summary and each cue's peak draw, where any fixture
+/// type has a wattage.
+fn power_rows(show: &Show) -> Option<(String, Vec<[String; 3]>)> {
+    if show
+        .fixtures
+        .iter()
+        .all(|fixture| fixture.profile.power.is_none())
+    {
+        return None;
+    }
+    let limit = show.circuit_limit;
+    let summary = match limit {
+        Some(limit) => format!(
+            "Estimated peak draw of each cue on its busiest circuit, against breakers of {}.",
+            power::format_watts(limit)
+        ),
+        None => "Estimated peak draw of each cue. No circuit limit is set.".to_string(),
+    };
+    let rows = show
+        .cues
+        .iter()
+        .zip(power::cue_peaks(
+            &show.fixtures,
+            &show.cues,
+            &show.playback_order(),
+        ))
+        .map(|(cue, peak)| {
+            [
+                cue.number.to_string(),
+                cue.name.clone(),
+                if peak.is_over(limit) {
+                    format!("{} OVER LIMIT", power::format_watts(peak.worst_circuit()))
+                } else {
+                    power::format_watts(peak.worst_circuit())
+                },
+            ]
+        })
+        .collect();
+    Some((summary, rows))
+}
+
+/// Keeps a cell on one row of a Markdown table.
+fn markdown_cell(text: &str) -> String {
+    text.trim().replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn report_markdown(show: &Show) -> String {
+    let mut md = String::new();
+    let generated = chrono::Local::now().format("%Y-%m-%d %H:%M");
+    let _ = writeln!(
+        md,
+        "# {}\n\nShow report generated {}\n",
+        show.name, generated
+    );
+
+    md.push_str(
+        "## Cue Sheet\n\n| Cue | Name | Timecode | Fade | Notes |\n|---|---|---|---|---|\n",
+    );
+    let slots = marker::slots(show);
+    let markers = |md: &mut String, index| {
+        for (_, marker) in marker::before(show, &slots, index) {
+            let _ = writeln!(
+                md,
+                "| ◆ | *{}* | {} | | |",
+                markdown_cell(&marker.text),
+                format_time(marker.time)
+            );
+        }
+    };
+    for (i, cue) in show.cues.iter().enumerate() {
+        if !cue.section.is_empty() {
+            let _ = writeln!(md, "| **{}** | | | | |", markdown_cell(&cue.section));
+        }
+        markers(&mut md, i);
+        let _ = writeln!(
+            md,
+            "| {} | {}{} | {} | {} | {} |",
+            cue.number,
+            markdown_cell(&cue.name),
+            if cue.disarmed { " (disarmed)" } else { "" },
+            format_time(show.cue_start(cue)),
+            cue.timing_label(),
+            markdown_cell(&cue.notes)
+        );
+    }
+    markers(&mut md, show.cues.len());
+
+    md.push_str("\n## Patch\n\n| Id | Fixture | Type | Address |\n|---|---|---|---|\n");
+    for fixture in &show.fixtures {
+        let _ = writeln!(
+            md,
+            "| {} | {} | {} | {} |",
+            fixture.id,
+            markdown_cell(&fixture.name),
+            markdown_cell(&fixture_mode(fixture)),
+            fixture_address(fixture)
+        );
+    }
+
+    if let Some((summary, rows)) = power_rows(show) {
+        let _ = writeln!(
+            md,
+            "\n## Power\n\n{}\n\n| Cue | Name | Peak Draw |\n|---|---|---|",
+            summary
+        );
+        for [cue, name, peak] in &rows {
+            let _ = writeln!(md, "| {} | {} | {} |", cue, markdown_cell(name), peak);
+        }
+    }
+    md
+}
+
+fn report_html(show: &Show) -> String {
+    let mut html = String::new();
+    let title = escape(&show.name);
+    let generated = chrono::Local::now().format("%Y-%m-%d %H:%M");
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title} - Show Report</title>\
+         <style>{STYLE}</style></head><body>\n\
+         <h1>{title}</h1><p>Show report generated {generated}</p>\n"
+    );
+
+    html.push_str(
+        "<section><h2>Cue Sheet</h2><table><tr><th>Cue</th><th>Name</th><th>Timecode</th>\
+         <th>Fade</th><th>Notes</th></tr>\n",
+    );
+    let slots = marker::slots(show);
+    for (i, cue) in show.cues.iter().enumerate() {
+        if !cue.section.is_empty() {
+            let _ = writeln!(
+                html,
+                "<tr><th colspan=\"5\">{}</th></tr>",
+                escape(&cue.section)
+            );
+        }
+        html_markers(&mut html, show, &slots, i);
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            cue.number,
+            escape(&cue.name),
+            if cue.disarmed { " (disarmed)" } else { "" },
+            format_time(show.cue_start(cue)),
+            cue.timing_label(),
+            escape(&cue.notes).replace('\n', "<br>")
+        );
+    }
+    html_markers(&mut html, show, &slots, show.cues.len());
+    html.push_str("</table></section>\n");
+
+    html.push_str(
+        "<section><h2>Patch</h2><table><tr><th>Id</th><th>Fixture</th><th>Type</th>\
+         <th>Address</th></tr>\n",
+    );
+    for fixture in &show.fixtures {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            fixture.id,
+            escape(&fixture.name),
+            escape(&fixture_mode(fixture)),
+            fixture_address(fixture)
+        );
+    }
+    html.push_str("</table></section>\n");
+
+    if let Some((summary, rows)) = power_rows(show) {
+        let _ = writeln!(
+            html,
+            "<section><h2>Power</h2><p>{}</p><table><tr><th>Cue</th><th>Name</th>\
+             <th>Peak Draw</th></tr>",
+            escape(&summary)
+        );
+        for [cue, name, peak] in &rows {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                cue,
+                escape(name),
+                peak
+            );
+        }
+        html.push_str("</table></section>\n");
+    }
+    html.push_str("</body></html>\n");
+    html
+}
+
+/// Signed seconds, as cue drift reads in the session report.
+fn format_drift(seconds: f64) -> String {
+    format!("{:+.1}s", seconds)
+}
+
+/// The post-show report: how long the show ran and when each cue went
+/// against its programmed time, with the cues that went late on timecode,
+/// for the stage manager's show report.
+pub fn session_report(show: &Show, session: &Session, format: ReportFormat) -> Vec<u8> {
+    let generated = chrono::Local::now().format("%Y-%m-%d %H:%M");
+    let threshold = show.timecode.late_cue;
+    let began = session.run_started.map_or_else(
+        || "not started".to_string(),
+        |(_, at)| at.format("%H:%M:%S").to_string(),
+    );
+    let summary = [
+        ("Run began", began),
+        ("Played for", format_time(session.play_time())),
+        ("Cues fired", session.fires.len().to_string()),
+        ("Cues late", session.late_count(threshold).to_string()),
+    ];
+    let rows: Vec<[String; 7]> = session
+        .fires
+        .iter()
+        .map(|fire| {
+            [
+                fire.number.to_string(),
+                fire.name.clone(),
+                format_time(fire.programmed),
+                format_time(fire.actual),
+                format_drift(fire.drift()),
+                fire.late
+                    .filter(|_| fire.is_late(threshold))
+                    .map(format_late)
+                    .unwrap_or_default(),
+                fire.at.format("%H:%M:%S").to_string(),
+            ]
+        })
+        .collect();
+    let headings = [
+        "Cue",
+        "Name",
+        "Programmed",
+        "Actual",
+        "Drift",
+        "Late",
+        "Went at",
+    ];
+
+    match format {
+        ReportFormat::Markdown => {
+            let mut md = String::new();
+            let _ = writeln!(
+                md,
+                "# {}\n\nPost-show report generated {}\n",
+                show.name, generated
+            );
+            for (label, value) in &summary {
+                let _ = writeln!(md, "- **{}:** {}", label, value);
+            }
+            let _ = writeln!(
+                md,
+                "\n## Cues Fired\n\n| {} |\n|{}",
+                headings.join(" | "),
+                "---|".repeat(headings.len())
+            );
+            for row in &rows {
+                let cells: Vec<String> = row.iter().map(|cell| markdown_cell(cell)).collect();
+                let _ = writeln!(md, "| {} |", cells.join(" | "));
+            }
+            md.into_bytes()
+        }
+        ReportFormat::Html => {
+            let mut html = String::new();
+            let title = escape(&show.name);
+            let _ = write!(
+                html,
+                "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title} - Post-Show Report</title>\
+                 <style>{STYLE}</style></head><body>\n\
+                 <h1>{title}</h1><p>Post-show report generated {generated}</p>\n<table>\n"
+            );
+            for (label, value) in &summary {
+                let _ = writeln!(
+                    html,
+                    "<tr><th>{}</th><td>{}</td></tr>",
+                    label,
+                    escape(value)
+                );
+            }
+            html.push_str("</table>\n<section><h2>Cues Fired</h2><table><tr>");
+            for heading in headings {
+                let _ = write!(html, "<th>{}</th>", heading);
+            }
+            html.push_str("</tr>\n");
+            for row in &rows {
+                html.push_str("<tr>");
+                for cell in row {
+                    let _ = write!(html, "<td>{}</td>", escape(cell));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table></section>\n</body></html>\n");
+            html.into_bytes()
+        }
+        ReportFormat::Pdf => {
+            let mut lines = vec![
+                show.name.clone(),
+                format!("Post-show report generated {}", generated),
+                String::new(),
+            ];
+            for (label, value) in &summary {
+                lines.push(format!("{}: {}", label, value));
+            }
+            lines.extend([String::new(), "CUES FIRED".to_string(), String::new()]);
+            let widths = [7, 20, 13, 13, 9, 13, 9];
+            let line = |cells: &[String]| {
+                cells
+                    .iter()
+                    .zip(widths)
+                    .map(|(cell, width)| column(cell, width))
+                    .collect::<String>()
+            };
+            lines.push(line(&headings.map(str::to_string)));
+            lines.push("-".repeat(90));
+            for row in &rows {
+                lines.push(line(row));
+            }
+            pdf(&format!("{} - Post-Show Report", show.name), &lines)
+        }
+    }
+}
+
+/// Cuts `text` to `width` characters, padded so the next column lines up.
+fn column(text: &str, width: usize) -> String {
+    let text: String = text.chars().take(width).collect();
+    format!("{:<width$} ", text)
+}
+
+/// The report as fixed-width lines for the PDF.
+fn report_lines(show: &Show) -> Vec<String> {
+    let mut lines = vec![
+        show.name.clone(),
+        format!(
+            "Show report generated {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M")
+        ),
+        String::new(),
+        "CUE SHEET".to_string(),
+        String::new(),
+    ];
+    let cue_row = |cue: &str, name: &str, time: &str, fade: &str, notes: &str| {
+        [
+            column(cue, 7),
+            column(name, 24),
+            column(time, 12),
+            column(fade, 6),
+        ]
+        .concat()
+            + notes
+    };
+    lines.push(cue_row("Cue", "Name", "Timecode", "Fade", "Notes"));
+    lines.push("-".repeat(90));
+    let slots = marker::slots(show);
+    let markers = |lines: &mut Vec<String>, index| {
+        for (_, marker) in marker::before(show, &slots, index) {
+            lines.push(cue_row(
+                "*",
+                &marker.text,
+                &format_time(marker.time),
+                "",
+                "",
+            ));
+        }
+    };
+    for (i, cue) in show.cues.iter().enumerate() {
+        if !cue.section.is_empty() {
+            lines.push(format!("[{}]", cue.section));
+        }
+        markers(&mut lines, i);
+        let name = if cue.disarmed {
+            format!("{} (disarmed)", cue.name)
+        } else {
+            cue.name.clone()
+        };
+        let mut notes = cue.notes.lines();
+        lines.push(cue_row(
+            &cue.number.to_string(),
+            &name,
+            &format_time(show.cue_start(cue)),
+            &cue.timing_label(),
+            notes.next().unwrap_or_default(),
+        ));
+        for note in notes {
+            lines.push(cue_row("", "", "", "", note));
+        }
+    }
+    markers(&mut lines, show.cues.len());
+
+    lines.extend([String::new(), "PATCH".to_string(), String::new()]);
+    let patch_row = |id: &str, name: &str, mode: &str, address: &str| {
+        [column(id, 5), column(name, 24), column(mode, 24)].concat() + address
+    };
+    lines.push(patch_row("Id", "Fixture", "Type", "Address"));
+    lines.push("-".repeat(90));
+    for fixture in &show.fixtures {
+        lines.push(patch_row(
+            &fixture.id.to_string(),
+            &fixture.name,
+            &fixture_mode(fixture),
+            &fixture_address(fixture),
+        ));
+    }
+
+    if let Some((summary, rows)) = power_rows(show) {
+        lines.extend([String::new(), "POWER".to_string(), String::new(), summary]);
+        lines.push(String::new());
+        let power_row =
+            |cue: &str, name: &str, peak: &str| [column(cue, 7), column(name, 24)].concat() + peak;
+        lines.push(power_row("Cue", "Name", "Peak Draw"));
+        lines.push("-".repeat(90));
+        for [cue, name, peak] in &rows {
+            lines.push(power_row(cue, name, peak));
+        }
+    }
+    lines
+}
+
+const PDF_LINES_PER_PAGE: usize = 64;
+
+/// Escapes a line for a PDF string, with anything outside printable ASCII
+/// replaced since the built-in Courier font only covers Latin text.
+fn pdf_text(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    for c in line.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                text.push('\\');
+                text.push(c);
+            }
+            ' '..='~' => text.push(c),
+            _ => text.push('?'),
+        }
+    }
+    text
+}
+
+/// Lays `lines` out as a plain A4 PDF in 9 point Courier, so the report
+/// prints without a browser.
+fn pdf(title: &str, lines: &[String]) -> Vec<u8> {
+    let pages: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(PDF_LINES_PER_PAGE).collect()
+    };
+    // Objects: 1 catalog, 2 page tree, 3 font, 4 info, then a page and its
+    // content stream for each page.
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len())
+                .map(|i| format!("{} 0 R", 5 + i * 2))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        format!("<< /Title ({}) /Producer (Halo) >>", pdf_text(title)),
+    ];
+    for (i, page) in pages.iter().enumerate() {
+        let mut stream = String::from("BT /F1 9 Tf 11 TL 40 800 Td\n");
+        for line in *page {
+            let _ = writeln!(stream, "({}) '", pdf_text(line));
+        }
+        let _ = writeln!(stream, "0 -11 Td (Page {} of {}) '", i + 1, pages.len());
+        stream.push_str("ET");
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            6 + i * 2
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            stream.len(),
+            stream
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+    }
+    let xref = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R /Info 4 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    pdf.into_bytes()
+}
@@ -0,0 +1,90 @@
+//! The shape of a fade: how far a channel has moved towards its new level at
+//! each point of the cue's time. A linear fade looks like it snaps at the
+//! bottom on intensity, so cues can ease in and out instead, separately for
+//! intensity and for colour and position.
+
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum FadeCurve {
+    #[default]
+    Linear,
+    /// Starts slowly and finishes quickly.
+    EaseIn,
+    /// Starts quickly and settles slowly.
+    EaseOut,
+    /// Eases in and out.
+    SCurve,
+    /// A cubic bezier from (0, 0) to (1, 1) through two control points, as
+    /// in CSS `cubic-bezier()`.
+    Bezier { x1: f32, y1: f32, x2: f32, y2: f32 },
+}
+
+impl FadeCurve {
+    pub const PRESETS: [FadeCurve; 4] = [
+        FadeCurve::Linear,
+        FadeCurve::EaseIn,
+        FadeCurve::EaseOut,
+        FadeCurve::SCurve,
+    ];
+
+    /// What a custom curve starts from.
+    pub const CUSTOM: FadeCurve = FadeCurve::Bezier {
+        x1: 0.25,
+        y1: 0.1,
+        x2: 0.25,
+        y2: 1.0,
+    };
+
+    pub fn is_linear(&self) -> bool {
+        *self == FadeCurve::Linear
+    }
+
+    /// How far along the fade is at `progress` through its time, both from
+    /// 0 to 1.
+    pub fn apply(&self, progress: f32) -> f32 {
+        let p = progress.clamp(0.0, 1.0);
+        match *self {
+            FadeCurve::Linear => p,
+            FadeCurve::EaseIn => p * p,
+            FadeCurve::EaseOut => 1.0 - (1.0 - p) * (1.0 - p),
+            FadeCurve::SCurve => p * p * (3.0 - 2.0 * p),
+            FadeCurve::Bezier { x1, y1, x2, y2 } => {
+                let x1 = x1.clamp(0.0, 1.0);
+                let x2 = x2.clamp(0.0, 1.0);
+                // x(t) only rises with the control points held in 0..=1, so
+                // bisect for the t that gives `p`.
+                let (mut low, mut high) = (0.0, 1.0);
+                for _ in 0..24 {
+                    let t = (low + high) / 2.0;
+                    if cubic(x1, x2, t) < p {
+                        low = t;
+                    } else {
+                        high = t;
+                    }
+                }
+                cubic(y1, y2, (low + high) / 2.0).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+/// One coordinate of a cubic bezier from 0 to 1 through `a` and `b` at `t`.
+fn cubic(a: f32, b: f32, t: f32) -> f32 {
+    let u = 1.0 - t;
+    3.0 * u * u * t * a + 3.0 * u * t * t * b + t * t * t
+}
+
+impl fmt::Display for FadeCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            FadeCurve::Linear => tr!("curve-linear"),
+            FadeCurve::EaseIn => tr!("curve-ease-in"),
+            FadeCurve::EaseOut => tr!("curve-ease-out"),
+            FadeCurve::SCurve => tr!("curve-s"),
+            FadeCurve::Bezier { .. } => tr!("curve-custom"),
+        })
+    }
+}
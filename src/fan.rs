@@ -0,0 +1,213 @@
+//! Fanning an attribute across several fixtures: spreading it from one level
+//! to another over the selection in one go, instead of setting each fixture
+//! by hand.
+
+use crate::busk;
+use crate::cct;
+use crate::cue::Cue;
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use eframe::egui::epaint::Hsva;
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FanMode {
+    /// From the first fixture to the last.
+    Linear,
+    /// From the middle out to both ends.
+    Symmetrical,
+    /// Scattered between the two levels, the same way for the same seed.
+    Random,
+}
+
+impl FanMode {
+    pub const ALL: [FanMode; 3] = [FanMode::Linear, FanMode::Symmetrical, FanMode::Random];
+
+    /// How far between the two levels each of `count` fixtures lands, from 0
+    /// to 1. A wrapped linear fan stops a step short of the end, for a fan
+    /// that carries on round from the last fixture to the first.
+    pub fn positions(self, count: usize, seed: u64, wrap: bool) -> Vec<f32> {
+        let last = count.saturating_sub(1).max(1) as f32;
+        (0..count)
+            .map(|i| match self {
+                FanMode::Linear if wrap => i as f32 / count as f32,
+                FanMode::Linear => i as f32 / last,
+                FanMode::Symmetrical => {
+                    let middle = last / 2.0;
+                    if middle == 0.0 {
+                        0.0
+                    } else {
+                        (i as f32 - middle).abs() / middle
+                    }
+                }
+                FanMode::Random => busk::chance(seed.wrapping_mul(1000).wrapping_add(i as u64)),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for FanMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            FanMode::Linear => tr!("fan-mode-linear"),
+            FanMode::Symmetrical => tr!("fan-mode-symmetrical"),
+            FanMode::Random => tr!("fan-mode-random"),
+        })
+    }
+}
+
+/// What to fan: one attribute, the colour of RGB fixtures round the colour
+/// wheel, or the colour temperature of whatever can make white.
+#[derive(Clone, Copy, PartialEq)]
+pub enum FanTarget {
+    Attribute(Attribute),
+    /// Red, green and blue together, from a hue in degrees.
+    Hue,
+    /// Red, green and blue together, mixed from one colour to another.
+    Gradient,
+    /// A colour temperature in Kelvin, set through each fixture's own
+    /// channels; see `cct`.
+    WhitePoint,
+}
+
+impl FanTarget {
+    /// The levels the target goes between.
+    pub fn range(self) -> std::ops::RangeInclusive<f32> {
+        match self {
+            FanTarget::Attribute(_) => 0.0..=255.0,
+            FanTarget::Hue => 0.0..=360.0,
+            FanTarget::Gradient => 0.0..=1.0,
+            FanTarget::WhitePoint => cct::WARMEST..=cct::COOLEST,
+        }
+    }
+
+    /// Whether `fixture` has the channels for the target.
+    pub fn applies_to(self, fixture: &Fixture) -> bool {
+        match self {
+            FanTarget::Attribute(attribute) => fixture.profile.channels.contains(&attribute),
+            FanTarget::Hue | FanTarget::Gradient => {
+                [Attribute::Red, Attribute::Green, Attribute::Blue]
+                    .iter()
+                    .all(|attribute| fixture.profile.channels.contains(attribute))
+            }
+            FanTarget::WhitePoint => cct::supports(fixture),
+        }
+    }
+}
+
+impl fmt::Display for FanTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FanTarget::Attribute(attribute) => attribute.fmt(f),
+            FanTarget::Hue => f.write_str(&tr!("fan-target-hue")),
+            FanTarget::Gradient => f.write_str(&tr!("fan-target-gradient")),
+            FanTarget::WhitePoint => f.write_str(&tr!("fan-target-white-point")),
+        }
+    }
+}
+
+/// The order to fan the selection in.
+#[derive(Clone, Copy, PartialEq)]
+pub enum FanOrder {
+    /// By universe and address.
+    Patch,
+    /// Fixture type by fixture type, each in patch order.
+    Group,
+}
+
+impl FanOrder {
+    pub const ALL: [FanOrder; 2] = [FanOrder::Patch, FanOrder::Group];
+
+    pub fn sort(self, fixtures: &mut [&Fixture]) {
+        match self {
+            FanOrder::Patch => fixtures.sort_by_key(|fixture| (fixture.universe, fixture.address)),
+            FanOrder::Group => fixtures.sort_by(|a, b| {
+                (&a.profile.name, a.universe, a.address).cmp(&(
+                    &b.profile.name,
+                    b.universe,
+                    b.address,
+                ))
+            }),
+        }
+    }
+}
+
+impl fmt::Display for FanOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            FanOrder::Patch => tr!("fan-order-patch"),
+            FanOrder::Group => tr!("fan-order-group"),
+        })
+    }
+}
+
+pub struct Fan {
+    pub target: FanTarget,
+    pub mode: FanMode,
+    pub order: FanOrder,
+    /// Levels at the start and end of the fan, within [`FanTarget::range`].
+    pub from: f32,
+    pub to: f32,
+    /// The colours at the start and end of a gradient.
+    pub colors: [[u8; 3]; 2],
+    pub seed: u64,
+    /// Runs the fan from the last fixture back to the first.
+    pub reverse: bool,
+    /// Makes a linear fan a loop: a hue fan stops a step short of `to`, so a
+    /// full rainbow doesn't repeat a colour, and anything else goes out to
+    /// `to` and back; either way it joins up when its hue is shifted.
+    pub wrap: bool,
+}
+
+impl Fan {
+    /// Writes the fan into `cue` for the `fixtures` that have the target.
+    /// Returns how many fixtures it set.
+    pub fn apply(&self, cue: &mut Cue, fixtures: &[&Fixture]) -> usize {
+        let mut fixtures: Vec<&Fixture> = fixtures
+            .iter()
+            .copied()
+            .filter(|fixture| self.target.applies_to(fixture))
+            .collect();
+        self.order.sort(&mut fixtures);
+        if self.reverse {
+            fixtures.reverse();
+        }
+        let positions = self.mode.positions(fixtures.len(), self.seed, self.wrap);
+        let loops = self.wrap && self.mode == FanMode::Linear && self.target != FanTarget::Hue;
+        for (fixture, t) in fixtures.iter().zip(positions) {
+            // Out and back, so the end meets the start again.
+            let t = if loops {
+                1.0 - (1.0 - 2.0 * t).abs()
+            } else {
+                t
+            };
+            let level = self.from + (self.to - self.from) * t;
+            match self.target {
+                FanTarget::Attribute(attribute) => {
+                    cue.set_value(fixture.id, attribute, level.round().clamp(0.0, 255.0) as u8)
+                }
+                FanTarget::Hue => {
+                    let [r, g, b] = Hsva::new(level / 360.0, 1.0, 1.0, 1.0).to_srgb();
+                    cue.set_value(fixture.id, Attribute::Red, r);
+                    cue.set_value(fixture.id, Attribute::Green, g);
+                    cue.set_value(fixture.id, Attribute::Blue, b);
+                }
+                FanTarget::Gradient => {
+                    let [from, to] = self.colors;
+                    let mix = |i: usize| {
+                        (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t).round() as u8
+                    };
+                    cue.set_value(fixture.id, Attribute::Red, mix(0));
+                    cue.set_value(fixture.id, Attribute::Green, mix(1));
+                    cue.set_value(fixture.id, Attribute::Blue, mix(2));
+                }
+                FanTarget::WhitePoint => {
+                    for (attribute, value) in cct::values(fixture, level) {
+                        cue.set_value(fixture.id, attribute, value);
+                    }
+                }
+            }
+        }
+        fixtures.len()
+    }
+}
@@ -0,0 +1,631 @@
+use crate::cct::{self, WhiteRange};
+use crate::dmx::DmxFrame;
+use crate::driver::FixtureDriver;
+use crate::i18n::tr;
+use crate::power::PowerRating;
+use crate::processing::{ChannelProcessing, PositionAdjust};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Attribute {
+    Intensity,
+    Red,
+    Green,
+    Blue,
+    Pan,
+    Tilt,
+    Haze,
+    Gobo,
+    ColorWheel,
+    WarmWhite,
+    CoolWhite,
+    Cct,
+    /// Lamp, reset and other settings, driven by the profile's control
+    /// macros; see `fixture_control`.
+    Control,
+}
+
+impl Attribute {
+    /// Whether the attribute picks a wheel slot, so it snaps to the new
+    /// slot as its cue starts rather than spinning through the ones between.
+    pub fn is_wheel(&self) -> bool {
+        matches!(self, Attribute::Gobo | Attribute::ColorWheel)
+    }
+
+    pub fn group(&self) -> AttributeGroup {
+        match self {
+            Attribute::Intensity => AttributeGroup::Intensity,
+            Attribute::Red
+            | Attribute::Green
+            | Attribute::Blue
+            | Attribute::ColorWheel
+            | Attribute::WarmWhite
+            | Attribute::CoolWhite
+            | Attribute::Cct => AttributeGroup::Color,
+            Attribute::Pan | Attribute::Tilt => AttributeGroup::Position,
+            Attribute::Gobo | Attribute::Haze | Attribute::Control => AttributeGroup::Beam,
+        }
+    }
+}
+
+/// The kinds of attribute a cue can time separately, as on a theatre
+/// console: colour can take its time while intensity snaps, or movers wait
+/// for the lights to go out before they move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttributeGroup {
+    Intensity,
+    Color,
+    Position,
+    /// Gobos and everything else.
+    Beam,
+}
+
+impl AttributeGroup {
+    pub const ALL: [AttributeGroup; 4] = [
+        AttributeGroup::Intensity,
+        AttributeGroup::Color,
+        AttributeGroup::Position,
+        AttributeGroup::Beam,
+    ];
+}
+
+impl AttributeGroup {
+    /// The group's letter in a cue's timing, as consoles write split times.
+    pub fn initial(&self) -> char {
+        match self {
+            AttributeGroup::Intensity => 'I',
+            AttributeGroup::Color => 'C',
+            AttributeGroup::Position => 'P',
+            AttributeGroup::Beam => 'B',
+        }
+    }
+}
+
+impl fmt::Display for AttributeGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            AttributeGroup::Intensity => tr!("attribute-group-intensity"),
+            AttributeGroup::Color => tr!("attribute-group-colour"),
+            AttributeGroup::Position => tr!("attribute-group-position"),
+            AttributeGroup::Beam => tr!("attribute-group-beam"),
+        })
+    }
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Attribute::Intensity => "Intensity",
+            Attribute::Red => "Red",
+            Attribute::Green => "Green",
+            Attribute::Blue => "Blue",
+            Attribute::Pan => "Pan",
+            Attribute::Tilt => "Tilt",
+            Attribute::Haze => "Haze",
+            Attribute::Gobo => "Gobo",
+            Attribute::ColorWheel => "Colour Wheel",
+            Attribute::WarmWhite => "Warm White",
+            Attribute::CoolWhite => "Cool White",
+            Attribute::Cct => "CCT",
+            Attribute::Control => "Control",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A position on a gobo or colour wheel.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct WheelSlot {
+    pub name: String,
+    /// The lowest DMX value that selects the slot.
+    pub value: u8,
+    /// The colour a colour wheel slot gives, for the visualizer and smart
+    /// bulbs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[u8; 3]>,
+    /// A picture of the slot for the picker, as a path to a PNG or GIF.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<PathBuf>,
+}
+
+impl WheelSlot {
+    pub fn new(name: &str, value: u8) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            color: None,
+            image: None,
+        }
+    }
+
+    pub fn with_color(mut self, color: [u8; 3]) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// The named slots on one of a profile's wheels, in DMX value order.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wheel {
+    pub attribute: Attribute,
+    pub slots: Vec<WheelSlot>,
+}
+
+/// What a control macro does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlKind {
+    LampOn,
+    LampOff,
+    Reset,
+}
+
+impl fmt::Display for ControlKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            ControlKind::LampOn => tr!("control-lamp-on"),
+            ControlKind::LampOff => tr!("control-lamp-off"),
+            ControlKind::Reset => tr!("control-reset"),
+        })
+    }
+}
+
+/// One level a control macro holds a channel at, for as long as the
+/// fixture needs to see it.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ControlStep {
+    #[serde(default = "ControlStep::default_attribute")]
+    pub attribute: Attribute,
+    pub value: u8,
+    pub hold: Duration,
+}
+
+impl ControlStep {
+    fn default_attribute() -> Attribute {
+        Attribute::Control
+    }
+
+    pub fn new(value: u8, hold: Duration) -> Self {
+        Self {
+            attribute: Attribute::Control,
+            value,
+            hold,
+        }
+    }
+}
+
+/// Lamp on, lamp off or reset as the fixture takes it: channel levels held
+/// one after another, then let go.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlMacro {
+    pub kind: ControlKind,
+    pub steps: Vec<ControlStep>,
+    /// How long after each other fixtures run the macro when several do at
+    /// once, such as discharge lamps striking one by one rather than all
+    /// drawing their strike current together.
+    #[serde(default, skip_serializing_if = "Duration::is_zero")]
+    pub stagger: Duration,
+}
+
+impl ControlMacro {
+    /// How long the steps take.
+    pub fn length(&self) -> Duration {
+        self.steps.iter().map(|step| step.hold).sum()
+    }
+}
+
+/// Describes the channel layout of a fixture type, one attribute per DMX
+/// channel starting at the fixture's address.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixtureProfile {
+    pub name: String,
+    /// Who makes the fixture, for the library browser; empty for generic
+    /// profiles.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub manufacturer: String,
+    /// The model, where `name` also carries the mode.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub model: String,
+    /// The DMX mode, such as "16ch Extended", where the model has several.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub mode: String,
+    pub channels: Vec<Attribute>,
+    /// Slot names for the gobo and colour wheels, where the profile has them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wheels: Vec<Wheel>,
+    /// The white points of a tunable white profile, where they differ from
+    /// 2700K to 6500K.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub white: Option<WhiteRange>,
+    /// What the fixture draws, for the power estimate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerRating>,
+    /// Lamp on, lamp off and reset, where the fixture has them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub controls: Vec<ControlMacro>,
+}
+
+impl FixtureProfile {
+    pub fn new(name: &str, channels: &[Attribute]) -> Self {
+        Self {
+            name: name.to_string(),
+            manufacturer: String::new(),
+            model: String::new(),
+            mode: String::new(),
+            channels: channels.to_vec(),
+            wheels: Vec::new(),
+            white: None,
+            power: None,
+            controls: Vec::new(),
+        }
+    }
+
+    pub fn dimmer() -> Self {
+        Self::new("Generic Dimmer", &[Attribute::Intensity])
+    }
+
+    pub fn rgb_par() -> Self {
+        Self::new(
+            "RGB Par",
+            &[
+                Attribute::Intensity,
+                Attribute::Red,
+                Attribute::Green,
+                Attribute::Blue,
+            ],
+        )
+    }
+
+    pub fn moving_head() -> Self {
+        Self::new(
+            "Moving Head",
+            &[
+                Attribute::Pan,
+                Attribute::Tilt,
+                Attribute::Intensity,
+                Attribute::Red,
+                Attribute::Green,
+                Attribute::Blue,
+            ],
+        )
+    }
+
+    pub fn hazer() -> Self {
+        Self::new("Hazer", &[Attribute::Haze])
+    }
+
+    pub fn gobo_spot() -> Self {
+        let mut profile = Self::new(
+            "Gobo Spot",
+            &[
+                Attribute::Pan,
+                Attribute::Tilt,
+                Attribute::Intensity,
+                Attribute::ColorWheel,
+                Attribute::Gobo,
+            ],
+        );
+        profile.wheels = vec![
+            Wheel {
+                attribute: Attribute::ColorWheel,
+                slots: vec![
+                    WheelSlot::new("Open", 0).with_color([255, 255, 255]),
+                    WheelSlot::new("Red", 16).with_color([255, 0, 0]),
+                    WheelSlot::new("Orange", 32).with_color([255, 128, 0]),
+                    WheelSlot::new("Yellow", 48).with_color([255, 230, 0]),
+                    WheelSlot::new("Green", 64).with_color([0, 255, 0]),
+                    WheelSlot::new("Cyan", 80).with_color([0, 255, 255]),
+                    WheelSlot::new("Blue", 96).with_color([0, 0, 255]),
+                    WheelSlot::new("Magenta", 112).with_color([255, 0, 255]),
+                ],
+            },
+            Wheel {
+                attribute: Attribute::Gobo,
+                slots: vec![
+                    WheelSlot::new("Open", 0),
+                    WheelSlot::new("Dots", 16),
+                    WheelSlot::new("Breakup", 32),
+                    WheelSlot::new("Star", 48),
+                    WheelSlot::new("Triangle", 64),
+                    WheelSlot::new("Cone", 80),
+                ],
+            },
+        ];
+        profile
+    }
+
+    /// A discharge spot, whose lamp is struck and doused from its control
+    /// channel and only takes the level after seeing it for a few seconds.
+    pub fn discharge_spot() -> Self {
+        let mut profile = Self::gobo_spot();
+        profile.name = "Discharge Spot".to_string();
+        profile.channels.push(Attribute::Control);
+        let hold = Duration::from_secs(5);
+        profile.controls = vec![
+            ControlMacro {
+                kind: ControlKind::LampOn,
+                steps: vec![ControlStep::new(230, hold)],
+                stagger: Duration::from_secs(2),
+            },
+            ControlMacro {
+                kind: ControlKind::LampOff,
+                steps: vec![ControlStep::new(250, hold)],
+                stagger: Duration::ZERO,
+            },
+            ControlMacro {
+                kind: ControlKind::Reset,
+                steps: vec![ControlStep::new(200, hold)],
+                stagger: Duration::from_secs(1),
+            },
+        ];
+        profile
+    }
+
+    pub fn tunable_white() -> Self {
+        Self::new(
+            "Tunable White",
+            &[
+                Attribute::Intensity,
+                Attribute::WarmWhite,
+                Attribute::CoolWhite,
+            ],
+        )
+    }
+
+    pub fn cct_panel() -> Self {
+        Self::new("CCT Panel", &[Attribute::Intensity, Attribute::Cct])
+    }
+
+    pub fn builtin() -> Vec<Self> {
+        vec![
+            Self::dimmer(),
+            Self::rgb_par(),
+            Self::moving_head(),
+            Self::hazer(),
+            Self::gobo_spot(),
+            Self::discharge_spot(),
+            Self::tunable_white(),
+            Self::cct_panel(),
+        ]
+    }
+
+    /// The slots on the wheel for `attribute`, if the profile names any.
+    pub fn slots(&self, attribute: Attribute) -> &[WheelSlot] {
+        self.wheels
+            .iter()
+            .find(|wheel| wheel.attribute == attribute)
+            .map_or(&[], |wheel| wheel.slots.as_slice())
+    }
+
+    /// The slot a level on the wheel for `attribute` selects.
+    pub fn slot_at(&self, attribute: Attribute, level: u8) -> Option<&WheelSlot> {
+        self.slots(attribute)
+            .iter()
+            .filter(|slot| slot.value <= level)
+            .max_by_key(|slot| slot.value)
+    }
+
+    /// The profile's macro for `kind`, if it has one.
+    pub fn control(&self, kind: ControlKind) -> Option<&ControlMacro> {
+        self.controls.iter().find(|control| control.kind == kind)
+    }
+
+    /// The manufacturer as the library browser groups by it.
+    pub fn manufacturer(&self) -> &str {
+        if self.manufacturer.is_empty() {
+            "Generic"
+        } else {
+            &self.manufacturer
+        }
+    }
+
+    pub fn model(&self) -> &str {
+        if self.model.is_empty() {
+            &self.name
+        } else {
+            &self.model
+        }
+    }
+
+    /// The mode, or the channel count where the profile doesn't name one.
+    pub fn mode(&self) -> String {
+        if self.mode.is_empty() {
+            format!("{}ch", self.channels.len())
+        } else {
+            self.mode.clone()
+        }
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        Self::builtin().contains(self)
+    }
+
+    /// File name used when storing the profile in the library or a package.
+    pub fn file_name(&self) -> String {
+        let stem: String = self
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("{}.json", stem)
+    }
+}
+
+/// Directory holding user-defined fixture profiles, one JSON file each.
+pub fn profile_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("halo").join("profiles"))
+}
+
+/// The built-in profiles followed by any user profiles found in
+/// `profile_dir()`. Unreadable files are skipped.
+pub fn profile_library() -> Vec<FixtureProfile> {
+    let mut profiles = FixtureProfile::builtin();
+    let Some(entries) = profile_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return profiles;
+    };
+    for entry in entries.flatten() {
+        let Ok(file) = std::fs::File::open(entry.path()) else {
+            continue;
+        };
+        if let Ok(profile) = serde_json::from_reader::<_, FixtureProfile>(io::BufReader::new(file))
+        {
+            if !profiles.iter().any(|p| p.name == profile.name) {
+                profiles.push(profile);
+            }
+        }
+    }
+    profiles
+}
+
+/// Saves `profile` into the user library, replacing any profile stored under
+/// the same name.
+pub fn install_profile(profile: &FixtureProfile) -> io::Result<()> {
+    let dir = profile_dir().ok_or_else(|| io::Error::other("no config directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    let file = std::fs::File::create(dir.join(profile.file_name()))?;
+    serde_json::to_writer_pretty(io::BufWriter::new(file), profile)?;
+    Ok(())
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub id: usize,
+    pub name: String,
+    pub profile: FixtureProfile,
+    pub universe: u16,
+    /// 1-based DMX start address.
+    pub address: u16,
+    /// Invert, limits and curves for the channels that have any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub processing: Vec<ChannelProcessing>,
+    /// Pre-position while dark for the next cue; see `move_in_black`.
+    #[serde(default)]
+    pub move_in_black: bool,
+    /// Pan and tilt swap, invert and offsets, for units hung differently.
+    #[serde(default, skip_serializing_if = "PositionAdjust::is_default")]
+    pub position: PositionAdjust,
+    /// Sends the fixture over OSC, HTTP or MIDI instead of DMX; see `driver`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub driver: Option<FixtureDriver>,
+    /// Left out of the output for the night; see `inhibit`.
+    #[serde(default)]
+    pub inhibited: bool,
+    /// The power circuit the fixture is plugged into, for checking the draw
+    /// on each; see `power`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub circuit: String,
+}
+
+impl Fixture {
+    pub fn new(
+        id: usize,
+        name: &str,
+        profile: FixtureProfile,
+        universe: u16,
+        address: u16,
+    ) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            profile,
+            universe,
+            address,
+            processing: Vec::new(),
+            move_in_black: false,
+            position: PositionAdjust::default(),
+            driver: None,
+            inhibited: false,
+            circuit: String::new(),
+        }
+    }
+
+    /// Returns the 1-based channel carrying `attribute`, if the profile has it.
+    pub fn channel_for(&self, attribute: Attribute) -> Option<u16> {
+        self.profile
+            .channels
+            .iter()
+            .position(|a| *a == attribute)
+            .map(|offset| self.address + offset as u16)
+    }
+
+    /// The colour the fixture is putting out in `frame`: its RGB mix or
+    /// colour wheel slot if it has one, scaled by its intensity channel if it
+    /// has one.
+    pub fn output_color(&self, frame: &DmxFrame) -> [u8; 3] {
+        let level = |attribute| {
+            self.channel_for(attribute)
+                .map(|channel| frame.get(self.universe, channel))
+        };
+        let rgb = match (
+            level(Attribute::Red),
+            level(Attribute::Green),
+            level(Attribute::Blue),
+        ) {
+            (Some(r), Some(g), Some(b)) => [r, g, b],
+            _ => match (level(Attribute::ColorWheel), level(Attribute::Haze)) {
+                (Some(wheel), _) => self
+                    .profile
+                    .slot_at(Attribute::ColorWheel, wheel)
+                    .and_then(|slot| slot.color)
+                    .unwrap_or([255; 3]),
+                (None, Some(haze)) => [haze / 2; 3],
+                (None, None) => cct::kelvin(self, frame).map_or([255; 3], cct::blackbody),
+            },
+        };
+        let intensity = level(Attribute::Intensity).unwrap_or(255) as u16;
+        rgb.map(|c| (c as u16 * intensity / 255) as u8)
+    }
+
+    /// Returns the attribute patched at `channel` in `universe`, if any.
+    pub fn attribute_at(&self, universe: u16, channel: u16) -> Option<Attribute> {
+        if universe != self.universe || channel < self.address {
+            return None;
+        }
+        self.profile
+            .channels
+            .get((channel - self.address) as usize)
+            .copied()
+    }
+}
+
+pub fn default_rig() -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+    for i in 0..4 {
+        fixtures.push(Fixture::new(
+            fixtures.len(),
+            &format!("Par {}", i + 1),
+            FixtureProfile::rgb_par(),
+            1,
+            1 + i * 4,
+        ));
+    }
+    for i in 0..2 {
+        fixtures.push(Fixture::new(
+            fixtures.len(),
+            &format!("Spot {}", i + 1),
+            FixtureProfile::moving_head(),
+            1,
+            17 + i * 6,
+        ));
+    }
+    fixtures.push(Fixture::new(
+        fixtures.len(),
+        "Hazer",
+        FixtureProfile::hazer(),
+        1,
+        29,
+    ));
+    for i in 0..6 {
+        fixtures.push(Fixture::new(
+            fixtures.len(),
+            &format!("Front {}", i + 1),
+            FixtureProfile::dimmer(),
+            2,
+            1 + i,
+        ));
+    }
+    fixtures
+}
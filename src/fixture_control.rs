@@ -0,0 +1,139 @@
+//! Fixture control macros: lamp on, lamp off and reset, as each fixture's
+//! profile defines them. A macro holds the profile's levels on its control
+//! channel one after another, for as long as the fixture needs to see each,
+//! then lets go. Run on several fixtures at once, each starts the profile's
+//! stagger after the one before, so discharge lamps don't all strike
+//! together.
+//!
+//! The levels sit over everything but the channel check, test pattern and
+//! safe state, including inhibit, so an inhibited fixture's lamp can still
+//! be doused.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, ControlKind, ControlStep, Fixture};
+use crate::ownership::{Owner, Owners};
+use std::time::{Duration, Instant};
+
+/// A level a running macro is holding on one fixture.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ControlLevel {
+    pub fixture_id: usize,
+    pub kind: ControlKind,
+    pub attribute: Attribute,
+    pub value: u8,
+}
+
+/// A macro running on one fixture.
+struct RunningControl {
+    fixture_id: usize,
+    kind: ControlKind,
+    steps: Vec<ControlStep>,
+    started: Instant,
+}
+
+impl RunningControl {
+    /// The level the macro holds at `now`, or None before it starts and
+    /// once it is done.
+    fn level(&self, now: Instant) -> Option<ControlLevel> {
+        let mut at = now.checked_duration_since(self.started)?;
+        for step in &self.steps {
+            if at < step.hold {
+                return Some(ControlLevel {
+                    fixture_id: self.fixture_id,
+                    kind: self.kind,
+                    attribute: step.attribute,
+                    value: step.value,
+                });
+            }
+            at -= step.hold;
+        }
+        None
+    }
+
+    fn is_done(&self, now: Instant) -> bool {
+        let length: Duration = self.steps.iter().map(|step| step.hold).sum();
+        now.saturating_duration_since(self.started) >= length
+    }
+}
+
+/// Every macro running, on the UI side.
+#[derive(Default)]
+pub struct FixtureControls {
+    running: Vec<RunningControl>,
+}
+
+impl FixtureControls {
+    /// Runs `kind` on each of `fixtures` whose profile has it, staggered,
+    /// in place of any macro already running on them. Returns how many
+    /// took it.
+    pub fn run(&mut self, kind: ControlKind, fixtures: &[&Fixture]) -> usize {
+        let now = Instant::now();
+        let mut count = 0;
+        for fixture in fixtures {
+            let Some(control) = fixture.profile.control(kind) else {
+                continue;
+            };
+            self.running
+                .retain(|running| running.fixture_id != fixture.id);
+            self.running.push(RunningControl {
+                fixture_id: fixture.id,
+                kind,
+                steps: control.steps.clone(),
+                started: now + control.stagger * count as u32,
+            });
+            count += 1;
+        }
+        count
+    }
+
+    /// Lets go of every fixture straight away.
+    pub fn stop(&mut self) {
+        self.running.clear();
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.running.is_empty()
+    }
+
+    /// The macro running on `fixture_id`, if any.
+    pub fn running_on(&self, fixture_id: usize) -> Option<ControlKind> {
+        self.running
+            .iter()
+            .find(|running| running.fixture_id == fixture_id)
+            .map(|running| running.kind)
+    }
+
+    /// Drops the macros that are done by `now`.
+    pub fn prune(&mut self, now: Instant) {
+        self.running.retain(|running| !running.is_done(now));
+    }
+
+    /// The levels held at `now`.
+    pub fn levels(&self, now: Instant) -> Vec<ControlLevel> {
+        self.running
+            .iter()
+            .filter_map(|running| running.level(now))
+            .collect()
+    }
+}
+
+/// Sets each held level on its fixture's channel.
+pub fn apply(
+    levels: &[ControlLevel],
+    fixtures: &[Fixture],
+    frame: &mut DmxFrame,
+    owners: &mut Owners,
+) {
+    for level in levels {
+        let Some(fixture) = fixtures
+            .iter()
+            .find(|fixture| fixture.id == level.fixture_id)
+        else {
+            continue;
+        };
+        if let Some(channel) = fixture.channel_for(level.attribute) {
+            frame.set(fixture.universe, channel, level.value);
+            owners.claim(fixture.universe, channel, Owner::Control(level.kind));
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Find and replace for fixtures: every place the show programs or uses one
+//! fixture, handed over to another, such as when Spot 5 is moved to Spot 9's
+//! position and the show should follow it.
+//!
+//! Cues, cue parts, pad flashes, the haze schedule and follow spots are
+//! searched. In a cue the new fixture takes the old one's levels for the
+//! attributes it has, over any of its own, and the rest are dropped.
+//!
+//! A replacement remembers what each place held before, so it can be undone
+//! for as long as nothing it changed has been edited since.
+
+use crate::cue::{CueNumber, CuePart, CueValue};
+use crate::flash::Flash;
+use crate::follow_spot::SpotFixture;
+use crate::i18n::tr;
+use crate::show::Show;
+
+/// A place in the show that can refer to fixtures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Place {
+    Cue(CueNumber),
+    /// A pad's flash, by the pad's position.
+    Pad(usize),
+    Haze,
+    /// By the spot's position.
+    FollowSpot(usize),
+}
+
+/// A place that refers to the fixture searched for.
+pub struct Found {
+    pub place: Place,
+    /// What the place is and how it uses the fixture, for the preview.
+    pub description: String,
+}
+
+/// What a place holds that refers to fixtures.
+#[derive(Clone, PartialEq)]
+enum Held {
+    Cue(Vec<CueValue>, Vec<CuePart>),
+    Pad(Option<Flash>),
+    Haze(Vec<usize>),
+    FollowSpot(Vec<SpotFixture>),
+}
+
+fn held(show: &Show, place: Place) -> Option<Held> {
+    Some(match place {
+        Place::Cue(number) => {
+            let cue = show.cues.iter().find(|cue| cue.number == number)?;
+            Held::Cue(cue.values.clone(), cue.parts.clone())
+        }
+        Place::Pad(index) => Held::Pad(show.pads.get(index)?.flash.clone()),
+        Place::Haze => Held::Haze(show.haze.fixtures.clone()),
+        Place::FollowSpot(index) => {
+            Held::FollowSpot(show.follow_spots.get(index)?.fixtures.clone())
+        }
+    })
+}
+
+fn put(show: &mut Show, place: Place, held: Held) {
+    match (place, held) {
+        (Place::Cue(number), Held::Cue(values, parts)) => {
+            if let Some(cue) = show.cues.iter_mut().find(|cue| cue.number == number) {
+                cue.values = values;
+                cue.parts = parts;
+            }
+        }
+        (Place::Pad(index), Held::Pad(flash)) => {
+            if let Some(pad) = show.pads.get_mut(index) {
+                pad.flash = flash;
+            }
+        }
+        (Place::Haze, Held::Haze(fixtures)) => show.haze.fixtures = fixtures,
+        (Place::FollowSpot(index), Held::FollowSpot(fixtures)) => {
+            if let Some(spot) = show.follow_spots.get_mut(index) {
+                spot.fixtures = fixtures;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every place that refers to fixture `id`, in show order.
+pub fn find(show: &Show, id: usize) -> Vec<Found> {
+    let mut found = Vec::new();
+    for cue in &show.cues {
+        let levels = cue.values.iter().filter(|v| v.fixture_id == id).count();
+        let parts: Vec<&str> = cue
+            .parts
+            .iter()
+            .filter(|part| part.fixtures.contains(&id))
+            .map(|part| part.name.as_str())
+            .collect();
+        let mut uses = Vec::new();
+        if levels > 0 {
+            uses.push(tr!("replace-levels", count = levels));
+        }
+        if !parts.is_empty() {
+            uses.push(tr!("replace-part", parts = parts.join(", ")));
+        }
+        if !uses.is_empty() {
+            found.push(Found {
+                place: Place::Cue(cue.number),
+                description: tr!(
+                    "replace-cue",
+                    number = cue.number.to_string(),
+                    name = cue.name.as_str(),
+                    uses = uses.join("; ")
+                ),
+            });
+        }
+    }
+    for (index, pad) in show.pads.iter().enumerate() {
+        if pad
+            .flash
+            .as_ref()
+            .is_some_and(|flash| flash.fixtures.contains(&id))
+        {
+            found.push(Found {
+                place: Place::Pad(index),
+                description: tr!("replace-pad", pad = pad.label.as_str()),
+            });
+        }
+    }
+    if show.haze.fixtures.contains(&id) {
+        found.push(Found {
+            place: Place::Haze,
+            description: tr!("replace-haze"),
+        });
+    }
+    for (index, spot) in show.follow_spots.iter().enumerate() {
+        if spot.fixtures.iter().any(|fixture| fixture.fixture == id) {
+            found.push(Found {
+                place: Place::FollowSpot(index),
+                description: tr!("replace-follow-spot", name = spot.name.as_str()),
+            });
+        }
+    }
+    found
+}
+
+/// Swaps `from` for `to` in `ids`, keeping each id once.
+fn swap_id(ids: &mut Vec<usize>, from: usize, to: usize) {
+    for id in ids.iter_mut() {
+        if *id == from {
+            *id = to;
+        }
+    }
+    let mut seen = Vec::new();
+    ids.retain(|id| {
+        let first = !seen.contains(id);
+        seen.push(*id);
+        first
+    });
+}
+
+/// What a replacement changed: each place as it was before and as the
+/// replacement left it.
+pub struct Replacement {
+    pub from: usize,
+    pub to: usize,
+    changed: Vec<(Place, Held, Held)>,
+    /// Cue levels for attributes the new fixture doesn't have.
+    pub dropped: usize,
+}
+
+impl Replacement {
+    pub fn places(&self) -> usize {
+        self.changed.len()
+    }
+
+    /// Puts back what the replacement changed, except where a place has
+    /// been edited since. Returns how many places were put back and how
+    /// many were left because they had changed.
+    pub fn undo(self, show: &mut Show) -> (usize, usize) {
+        let mut restored = 0;
+        let mut kept = 0;
+        for (place, before, after) in self.changed {
+            if held(show, place).as_ref() == Some(&after) {
+                put(show, place, before);
+                restored += 1;
+            } else {
+                kept += 1;
+            }
+        }
+        (restored, kept)
+    }
+}
+
+/// Hands every place in `places` over from fixture `from` to fixture `to`.
+pub fn replace(show: &mut Show, from: usize, to: usize, places: &[Place]) -> Replacement {
+    let channels = show
+        .fixtures
+        .iter()
+        .find(|fixture| fixture.id == to)
+        .map(|fixture| fixture.profile.channels.clone())
+        .unwrap_or_default();
+    let mut replacement = Replacement {
+        from,
+        to,
+        changed: Vec::new(),
+        dropped: 0,
+    };
+    for &place in places {
+        let Some(before) = held(show, place) else {
+            continue;
+        };
+        let after = match before.clone() {
+            Held::Cue(mut values, mut parts) => {
+                let moved: Vec<CueValue> = values
+                    .iter()
+                    .filter(|value| value.fixture_id == from)
+                    .map(|value| CueValue {
+                        fixture_id: to,
+                        ..*value
+                    })
+                    .collect();
+                values.retain(|value| {
+                    value.fixture_id != from
+                        && !(value.fixture_id == to
+                            && moved.iter().any(|m| m.attribute == value.attribute))
+                });
+                for value in moved {
+                    if channels.contains(&value.attribute) {
+                        values.push(value);
+                    } else {
+                        replacement.dropped += 1;
+                    }
+                }
+                for part in &mut parts {
+                    swap_id(&mut part.fixtures, from, to);
+                }
+                Held::Cue(values, parts)
+            }
+            Held::Pad(mut flash) => {
+                if let Some(flash) = &mut flash {
+                    swap_id(&mut flash.fixtures, from, to);
+                }
+                Held::Pad(flash)
+            }
+            Held::Haze(mut fixtures) => {
+                swap_id(&mut fixtures, from, to);
+                Held::Haze(fixtures)
+            }
+            Held::FollowSpot(mut fixtures) => {
+                for fixture in &mut fixtures {
+                    if fixture.fixture == from {
+                        fixture.fixture = to;
+                    }
+                }
+                Held::FollowSpot(fixtures)
+            }
+        };
+        if after != before {
+            put(show, place, after.clone());
+            replacement.changed.push((place, before, after));
+        }
+    }
+    replacement
+}
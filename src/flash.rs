@@ -0,0 +1,127 @@
+//! Flash pads: a pad can bump a set of fixtures to full while it is on,
+//! fading with the pad's own fade-out. In add mode the flash sits on top of
+//! the cues, highest level winning; in solo mode everything else goes dark
+//! for as long as the flash is up, so one fixture can be picked out. The
+//! flash master scales every flash, so the same pads serve a stadium
+//! anthem and a quiet acoustic number.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::ownership::{Owner, Owners};
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Instant;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlashMode {
+    /// On top of the cues, highest level winning.
+    #[default]
+    Add,
+    /// Only the flashed fixtures stay lit.
+    Solo,
+}
+
+impl fmt::Display for FlashMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            FlashMode::Add => tr!("flash-mode-add"),
+            FlashMode::Solo => tr!("flash-mode-solo"),
+        })
+    }
+}
+
+/// What a pad flashes.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Flash {
+    /// Fixture ids.
+    pub fixtures: Vec<usize>,
+    pub mode: FlashMode,
+}
+
+/// A flash on air, as the engine plays it.
+#[derive(Clone, PartialEq)]
+pub struct LiveFlash {
+    /// The pad, by position.
+    pub pad: usize,
+    pub flash: Flash,
+    /// The pad's level times the flash master, from 0 to 1.
+    pub level: f32,
+}
+
+/// Puts the flashes up in `frame`: solo flashes dim every fixture they
+/// don't include by their level first, then each flash raises its fixtures'
+/// intensity to its level.
+pub fn apply(
+    flashes: &[LiveFlash],
+    fixtures: &[Fixture],
+    frame: &mut DmxFrame,
+    owners: &mut Owners,
+) {
+    let flashed = |fixture: &Fixture, mode: Option<FlashMode>| {
+        flashes.iter().any(|live| {
+            live.flash.fixtures.contains(&fixture.id)
+                && mode.is_none_or(|mode| live.flash.mode == mode)
+        })
+    };
+    let solo = flashes
+        .iter()
+        .filter(|live| live.flash.mode == FlashMode::Solo)
+        .map(|live| live.level)
+        .fold(0.0_f32, f32::max);
+    if solo > 0.0 {
+        for fixture in fixtures
+            .iter()
+            .filter(|fixture| !flashed(fixture, Some(FlashMode::Solo)))
+        {
+            let Some(channel) = fixture.channel_for(Attribute::Intensity) else {
+                continue;
+            };
+            let value = frame.get(fixture.universe, channel) as f32 * (1.0 - solo);
+            frame.set(fixture.universe, channel, value.round() as u8);
+        }
+    }
+    for live in flashes {
+        let level = (live.level.clamp(0.0, 1.0) * 255.0).round() as u8;
+        for fixture in fixtures
+            .iter()
+            .filter(|f| live.flash.fixtures.contains(&f.id))
+        {
+            let Some(channel) = fixture.channel_for(Attribute::Intensity) else {
+                continue;
+            };
+            if level > frame.get(fixture.universe, channel) {
+                frame.set(fixture.universe, channel, level);
+                owners.claim(fixture.universe, channel, Owner::Flash(live.pad));
+            }
+        }
+    }
+    if solo > 0.0 {
+        for fixture in fixtures.iter().filter(|fixture| !flashed(fixture, None)) {
+            owners.claim_attribute(fixture, Attribute::Intensity, Owner::FlashSolo);
+        }
+    }
+}
+
+impl HaloApp {
+    /// The flash pads that are up, scaled by the flash master.
+    pub(crate) fn live_flashes(&self) -> Vec<LiveFlash> {
+        let now = Instant::now();
+        self.show
+            .pads
+            .iter()
+            .enumerate()
+            .filter_map(|(pad, p)| {
+                let flash = p.flash.as_ref()?;
+                let level = p.level(now) * self.flash_master;
+                (level > 0.0 && !flash.fixtures.is_empty()).then(|| LiveFlash {
+                    pad,
+                    flash: flash.clone(),
+                    level,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,326 @@
+//! Dual-operator sessions. A second Halo can follow this one as a read-only
+//! designer view over the remote control port, seeing the timecode, cue state
+//! and output levels live. The operator can grant a designer view control of
+//! chosen sections of the cue list; anything else it asks to fire is refused,
+//! so the two positions can't step on each other.
+
+use crate::cue::{self, CueNumber};
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::macros::MacroAction;
+use crate::remote::{RemoteResponse, RemoteState};
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a designer view asks for the operator's state.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// A designer view that hasn't polled for this long is shown as gone.
+const FOLLOWER_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Everything a designer view shows, sent in answer to each poll.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FollowState {
+    #[serde(flatten)]
+    pub state: RemoteState,
+    pub cues: Vec<FollowCue>,
+    pub levels: Vec<FixtureLevel>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FollowCue {
+    pub number: CueNumber,
+    pub name: String,
+    /// The section the cue sits in, empty before the first section label.
+    pub section: String,
+    /// The asking designer view may fire this cue.
+    pub granted: bool,
+    pub playing: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FixtureLevel {
+    pub name: String,
+    /// Intensity, or the brightest colour channel for fixtures without one.
+    pub level: u8,
+}
+
+impl FixtureLevel {
+    fn of(fixture: &Fixture, frame: &DmxFrame) -> Self {
+        let get = |attribute| {
+            fixture
+                .channel_for(attribute)
+                .map(|channel| frame.get(fixture.universe, channel))
+        };
+        let level = get(Attribute::Intensity).unwrap_or_else(|| {
+            [Attribute::Red, Attribute::Green, Attribute::Blue]
+                .into_iter()
+                .filter_map(get)
+                .max()
+                .unwrap_or(0)
+        });
+        Self {
+            name: fixture.name.clone(),
+            level,
+        }
+    }
+}
+
+/// A designer view seen by the operator, and what it may control.
+pub struct Follower {
+    pub name: String,
+    pub last_seen: Instant,
+    /// Sections of the cue list this view may fire cues in.
+    pub granted: Vec<String>,
+}
+
+impl Follower {
+    pub fn connected(&self) -> bool {
+        self.last_seen.elapsed() < FOLLOWER_TIMEOUT
+    }
+}
+
+/// The reply to a follow request, as read back by the designer view.
+#[derive(Deserialize)]
+struct FollowReply {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    follow: Option<FollowState>,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Option<FollowState>,
+    /// Why the connection is down.
+    error: Option<String>,
+    /// Why the operator refused the last go, cleared by the next that works.
+    refused: Option<String>,
+}
+
+/// The designer view's connection to an operator's Halo. Polls on a
+/// background thread until dropped.
+pub struct FollowClient {
+    shared: Arc<Mutex<Shared>>,
+    go: Sender<CueNumber>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl FollowClient {
+    pub fn connect(address: &str, name: &str, ctx: egui::Context) -> Self {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (go, requests) = mpsc::channel();
+
+        let address = address.to_string();
+        let name = name.to_string();
+        let thread_shared = shared.clone();
+        let stop = shutdown.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let result = follow(&address, &name, &requests, &thread_shared, &ctx, &stop);
+                if let Err(err) = result {
+                    if let Ok(mut shared) = thread_shared.lock() {
+                        shared.state = None;
+                        shared.error = Some(format!("{}: {}", address, err));
+                    }
+                    ctx.request_repaint();
+                    thread::sleep(Duration::from_secs(1));
+                }
+            }
+        });
+
+        Self {
+            shared,
+            go,
+            shutdown,
+        }
+    }
+
+    pub fn state(&self) -> Option<FollowState> {
+        self.shared
+            .lock()
+            .ok()
+            .and_then(|shared| shared.state.clone())
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.shared
+            .lock()
+            .ok()
+            .and_then(|shared| shared.error.clone())
+    }
+
+    pub fn refused(&self) -> Option<String> {
+        self.shared
+            .lock()
+            .ok()
+            .and_then(|shared| shared.refused.clone())
+    }
+
+    /// Asks the operator's Halo to fire `cue`; refused unless it is granted.
+    pub fn go(&self, cue: CueNumber) {
+        let _ = self.go.send(cue);
+    }
+}
+
+impl Drop for FollowClient {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Polls one connection until it fails or the client is dropped.
+fn follow(
+    address: &str,
+    name: &str,
+    go: &Receiver<CueNumber>,
+    shared: &Mutex<Shared>,
+    ctx: &egui::Context,
+    stop: &AtomicBool,
+) -> io::Result<()> {
+    let addr = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::other("no such host"))?;
+    let stream = TcpStream::connect_timeout(&addr, Duration::from_secs(2))?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let mut request = |request: serde_json::Value| -> io::Result<FollowReply> {
+        serde_json::to_writer(&mut writer, &request)?;
+        writer.write_all(b"\n")?;
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::other("the operator closed the connection"));
+        }
+        Ok(serde_json::from_str(&line)?)
+    };
+
+    while !stop.load(Ordering::Relaxed) {
+        for cue in go.try_iter() {
+            let reply = request(serde_json::json!({
+                "command": "follow_go",
+                "name": name,
+                "cue": cue.to_string(),
+            }))?;
+            if let Ok(mut shared) = shared.lock() {
+                shared.refused = reply.error;
+            }
+        }
+
+        let reply = request(serde_json::json!({"command": "follow", "name": name}))?;
+        if !reply.ok {
+            return Err(io::Error::other(reply.error.unwrap_or_default()));
+        }
+        if let Ok(mut shared) = shared.lock() {
+            shared.state = reply.follow;
+            shared.error = None;
+        }
+        ctx.request_repaint();
+        thread::sleep(POLL_INTERVAL);
+    }
+    Ok(())
+}
+
+impl HaloApp {
+    /// Records a poll from the designer view `name` and answers it.
+    pub(crate) fn follow_state(&mut self, name: &str) -> RemoteResponse {
+        let index = match self.followers.iter().position(|f| f.name == name) {
+            Some(index) if self.followers[index].connected() => index,
+            found => {
+                self.log.info(tr!("designer-connected", name = name));
+                found.unwrap_or_else(|| {
+                    self.followers.push(Follower {
+                        name: name.to_string(),
+                        last_seen: Instant::now(),
+                        granted: Vec::new(),
+                    });
+                    self.followers.len() - 1
+                })
+            }
+        };
+        self.followers[index].last_seen = Instant::now();
+
+        let granted = &self.followers[index].granted;
+        let mut cues = Vec::new();
+        for (title, range) in cue::sections(&self.show.cues) {
+            let section = title.unwrap_or_default();
+            for cue in &self.show.cues[range] {
+                cues.push(FollowCue {
+                    number: cue.number,
+                    name: cue.name.clone(),
+                    section: section.clone(),
+                    granted: granted.contains(&section),
+                    playing: cue.is_playing,
+                });
+            }
+        }
+        RemoteResponse {
+            follow: Some(FollowState {
+                state: self.remote_state(),
+                cues,
+                levels: self
+                    .show
+                    .fixtures
+                    .iter()
+                    .map(|fixture| FixtureLevel::of(fixture, &self.output))
+                    .collect(),
+            }),
+            ..RemoteResponse::ok()
+        }
+    }
+
+    /// Fires `cue` for the designer view `name` if it has been granted the
+    /// cue's section.
+    pub(crate) fn follower_go(&mut self, name: &str, cue: &str) -> RemoteResponse {
+        let Some(number) = CueNumber::parse(cue) else {
+            return RemoteResponse::error(format!("invalid cue number {:?}", cue));
+        };
+        let section = cue::sections(&self.show.cues)
+            .into_iter()
+            .find(|(_, range)| {
+                self.show.cues[range.clone()]
+                    .iter()
+                    .any(|c| c.number == number)
+            })
+            .map(|(title, _)| title.unwrap_or_default());
+        let Some(section) = section else {
+            return RemoteResponse::error(format!("no cue {}", number));
+        };
+        let granted = self
+            .followers
+            .iter()
+            .any(|f| f.name == name && f.granted.contains(&section));
+        if !granted {
+            return RemoteResponse::error(format!("not granted control of cue {}", number));
+        }
+        self.log.info(tr!(
+            "designer-fired",
+            name = name,
+            number = number.to_string()
+        ));
+        self.perform(MacroAction::FireCue(number));
+        RemoteResponse::ok()
+    }
+
+    /// Connects to or disconnects from the operator's Halo as a designer view.
+    pub(crate) fn set_following(&mut self, ctx: &egui::Context, follow: bool) {
+        self.follow_client = follow.then(|| {
+            FollowClient::connect(
+                &self.preferences.follow_address,
+                &self.preferences.follow_name,
+                ctx.clone(),
+            )
+        });
+    }
+}
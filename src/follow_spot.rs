@@ -0,0 +1,167 @@
+//! Follow spots: moving heads aimed at a PSN tracker, so they follow a
+//! performer round the stage. Each spot knows where its fixtures hang, in
+//! the tracking system's coordinates, and works out the pan and tilt that
+//! points each one at the tracker, lifted to the performer's chest and
+//! trimmed by hand. The aim is eased so tracker jitter doesn't shake the
+//! beams, and holds where it was when a tracker drops out.
+//!
+//! Pan and tilt are worked out for a fixture hung pointing straight down,
+//! pan centred towards +z. Heads hung any other way are turned round by
+//! their position adjustment in the patch, which applies afterwards.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use crate::ownership::{Owner, Owners};
+use crate::psn::Trackers;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// A moving head on a follow spot, and where it hangs.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpotFixture {
+    pub fixture: usize,
+    /// x, y and z in metres, y up, as the tracking system measures.
+    pub position: [f32; 3],
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FollowSpot {
+    pub name: String,
+    /// The PSN tracker followed.
+    pub tracker: u16,
+    pub enabled: bool,
+    pub fixtures: Vec<SpotFixture>,
+    /// How far the heads pan and tilt end to end, in degrees.
+    pub pan_range: f32,
+    pub tilt_range: f32,
+    /// How long the aim takes to catch up, in seconds; 0 follows at once.
+    pub smoothing: f32,
+    /// Metres above the tracker to aim at, so the beam lands on the face
+    /// rather than the belt pack.
+    pub height: f32,
+    /// Metres to shift the aim across (x) and up or downstage (z).
+    pub trim: [f32; 2],
+}
+
+impl Default for FollowSpot {
+    fn default() -> Self {
+        Self {
+            name: "Follow Spot".to_string(),
+            tracker: 1,
+            enabled: true,
+            fixtures: Vec::new(),
+            pan_range: 540.0,
+            tilt_range: 270.0,
+            smoothing: 0.2,
+            height: 1.2,
+            trim: [0.0, 0.0],
+        }
+    }
+}
+
+impl FollowSpot {
+    /// Where the spot aims when its tracker is at `tracker`.
+    fn target(&self, tracker: [f32; 3]) -> [f32; 3] {
+        [
+            tracker[0] + self.trim[0],
+            tracker[1] + self.height,
+            tracker[2] + self.trim[1],
+        ]
+    }
+
+    /// The pan and tilt, as DMX levels, pointing a head hung at `from` at
+    /// `target`.
+    fn aim(&self, from: [f32; 3], target: [f32; 3]) -> [f32; 2] {
+        let [x, y, z] = [
+            target[0] - from[0],
+            target[1] - from[1],
+            target[2] - from[2],
+        ];
+        let pan = x.atan2(z).to_degrees();
+        // From straight down.
+        let tilt = x.hypot(z).atan2(-y).to_degrees();
+        let level = |angle: f32, range: f32| (0.5 + angle / range.max(1.0)).clamp(0.0, 1.0) * 255.0;
+        [level(pan, self.pan_range), level(tilt, self.tilt_range)]
+    }
+}
+
+/// The eased aim of every follow spot fixture, by spot and fixture id, kept
+/// by the output engine between frames.
+#[derive(Default)]
+pub struct FollowAims {
+    aims: HashMap<(usize, usize), [f32; 2]>,
+    last_tick: Option<Instant>,
+}
+
+impl FollowAims {
+    /// Eases each enabled spot's fixtures towards their tracker and sets
+    /// their pan and tilt in `frame`. Fixtures whose tracker has never been
+    /// heard from are left alone.
+    pub fn apply(
+        &mut self,
+        spots: &[FollowSpot],
+        trackers: &Trackers,
+        fixtures: &[Fixture],
+        frame: &mut DmxFrame,
+        now: Instant,
+    ) {
+        let tick = self.last_tick.map_or(0.0, |last| {
+            now.saturating_duration_since(last).as_secs_f32()
+        });
+        self.last_tick = Some(now);
+        self.aims.retain(|&(index, id), _| {
+            spots.get(index).is_some_and(|spot| {
+                spot.enabled && spot.fixtures.iter().any(|hung| hung.fixture == id)
+            })
+        });
+        for (index, spot) in spots.iter().enumerate().filter(|(_, spot)| spot.enabled) {
+            let target = trackers.position(spot.tracker).map(|at| spot.target(at));
+            let ease = if spot.smoothing > 0.0 {
+                1.0 - (-tick / spot.smoothing).exp()
+            } else {
+                1.0
+            };
+            for hung in &spot.fixtures {
+                let Some(fixture) = fixtures.iter().find(|f| f.id == hung.fixture) else {
+                    continue;
+                };
+                let (Some(pan), Some(tilt)) = (
+                    fixture.channel_for(Attribute::Pan),
+                    fixture.channel_for(Attribute::Tilt),
+                ) else {
+                    continue;
+                };
+                let key = (index, fixture.id);
+                let aim = match (target, self.aims.get(&key)) {
+                    (Some(target), Some(&[from_pan, from_tilt])) => {
+                        let [to_pan, to_tilt] = spot.aim(hung.position, target);
+                        [
+                            from_pan + (to_pan - from_pan) * ease,
+                            from_tilt + (to_tilt - from_tilt) * ease,
+                        ]
+                    }
+                    (Some(target), None) => spot.aim(hung.position, target),
+                    // Lost trackers hold where they were.
+                    (None, Some(&aim)) => aim,
+                    (None, None) => continue,
+                };
+                self.aims.insert(key, aim);
+                frame.set(fixture.universe, pan, aim[0].round() as u8);
+                frame.set(fixture.universe, tilt, aim[1].round() as u8);
+            }
+        }
+    }
+
+    /// Gives each spot the pan and tilt of the fixtures it is aiming.
+    pub fn claim(&self, fixtures: &[Fixture], owners: &mut Owners) {
+        for &(index, id) in self.aims.keys() {
+            if let Some(fixture) = fixtures.iter().find(|fixture| fixture.id == id) {
+                owners.claim_attribute(fixture, Attribute::Pan, Owner::FollowSpot(index));
+                owners.claim_attribute(fixture, Attribute::Tilt, Owner::FollowSpot(index));
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Group masters: inhibitive faders that cap the intensity of the fixture
+//! groups assigned to them. Unlike a cue or a pad they never add light; at
+//! full they leave the output alone and below it they scale it down, so the
+//! blinders can be held to 70% for a TV recording without touching a cue.
+//! Fixtures in more than one master's groups follow the lowest of them.
+//! The grand master does the same for every fixture at once.
+
+use crate::crossfade::FaderControl;
+use crate::dmx::DmxFrame;
+use crate::dmx_fader::DmxFader;
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GroupMaster {
+    pub name: String,
+    /// Fixture groups it caps, by profile name.
+    pub groups: Vec<String>,
+    /// The most the groups may put out, from 0 to 1.
+    pub level: f32,
+    /// The MIDI controller that moves the fader.
+    pub control: Option<FaderControl>,
+    /// The DMX wing channel that moves the fader.
+    pub fader: Option<DmxFader>,
+}
+
+impl Default for GroupMaster {
+    fn default() -> Self {
+        Self {
+            name: "Group".to_string(),
+            groups: Vec::new(),
+            level: 1.0,
+            control: None,
+            fader: None,
+        }
+    }
+}
+
+impl GroupMaster {
+    /// Whether the master caps anything below full.
+    pub fn is_inhibiting(&self) -> bool {
+        self.level < 1.0 && !self.groups.is_empty()
+    }
+}
+
+/// Scales the intensity of every fixture under a master by the lowest
+/// master it is under.
+pub fn apply(masters: &[GroupMaster], fixtures: &[Fixture], frame: &mut DmxFrame) {
+    for fixture in fixtures {
+        let level = masters
+            .iter()
+            .filter(|master| master.groups.contains(&fixture.profile.name))
+            .map(|master| master.level.clamp(0.0, 1.0))
+            .fold(1.0_f32, f32::min);
+        if level >= 1.0 {
+            continue;
+        }
+        if let Some(channel) = fixture.channel_for(Attribute::Intensity) {
+            let value = frame.get(fixture.universe, channel);
+            frame.set(
+                fixture.universe,
+                channel,
+                (value as f32 * level).round() as u8,
+            );
+        }
+    }
+}
+
+/// Scales the intensity of every fixture by the grand master `level`.
+pub fn apply_grand_master(level: f32, fixtures: &[Fixture], frame: &mut DmxFrame) {
+    let level = level.clamp(0.0, 1.0);
+    if level >= 1.0 {
+        return;
+    }
+    for fixture in fixtures {
+        if let Some(channel) = fixture.channel_for(Attribute::Intensity) {
+            let value = frame.get(fixture.universe, channel);
+            frame.set(
+                fixture.universe,
+                channel,
+                (value as f32 * level).round() as u8,
+            );
+        }
+    }
+}
+
+impl HaloApp {
+    /// Sets group masters from a MIDI controller, or assigns the controller
+    /// to the master being learned.
+    pub(crate) fn receive_group_controller(&mut self, channel: u8, controller: u8, value: u8) {
+        if let Some(index) = self.learn_group_master.take() {
+            if let Some(master) = self.show.group_masters.get_mut(index) {
+                master.control = Some(FaderControl {
+                    channel,
+                    controller,
+                });
+                self.log.info(tr!(
+                    "learned-cc",
+                    controller = controller,
+                    channel = channel,
+                    name = master.name.as_str()
+                ));
+            }
+            return;
+        }
+        for master in &mut self.show.group_masters {
+            if master
+                .control
+                .is_some_and(|control| control.matches(channel, controller))
+            {
+                master.level = value as f32 / 127.0;
+            }
+        }
+    }
+}
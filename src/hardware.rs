@@ -0,0 +1,116 @@
+//! Serial ports, USB relay boards and GPIO pins, written to by cue triggers
+//! for the things on a show that aren't lights: confetti cannons, motors,
+//! door strikes.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// The rates a serial trigger can run at.
+pub const BAUD_RATES: [u32; 8] = [1200, 2400, 4800, 9600, 19200, 38400, 57600, 115200];
+
+/// Parses trigger text into bytes: plain text, with `\r`, `\n`, `\t`, `\\`
+/// and `\xNN` for anything else.
+pub fn parse_bytes(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('r') => bytes.push(b'\r'),
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some('\\') => bytes.push(b'\\'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let byte = u8::from_str_radix(&hex, 16)
+                    .map_err(|_| format!("\\x{} is not a hex byte", hex))?;
+                bytes.push(byte);
+            }
+            Some(other) => return Err(format!("unknown escape \\{}", other)),
+            None => return Err("trailing \\".to_string()),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Opens a serial port for writing, raw at `baud`.
+#[cfg(unix)]
+fn open_serial(port: &str, baud: u32) -> io::Result<File> {
+    use nix::sys::termios::{self, BaudRate, SetArg};
+
+    let rate = match baud {
+        1200 => BaudRate::B1200,
+        2400 => BaudRate::B2400,
+        4800 => BaudRate::B4800,
+        9600 => BaudRate::B9600,
+        19200 => BaudRate::B19200,
+        38400 => BaudRate::B38400,
+        57600 => BaudRate::B57600,
+        115200 => BaudRate::B115200,
+        _ => return Err(io::Error::other(format!("unsupported baud rate {}", baud))),
+    };
+    let file = OpenOptions::new().read(true).write(true).open(port)?;
+    let mut settings = termios::tcgetattr(&file)?;
+    termios::cfmakeraw(&mut settings);
+    termios::cfsetspeed(&mut settings, rate)?;
+    termios::tcsetattr(&file, SetArg::TCSANOW, &settings)?;
+    Ok(file)
+}
+
+/// Opens a serial port for writing. Windows keeps the port's own settings,
+/// as set in Device Manager or with `mode`.
+#[cfg(not(unix))]
+fn open_serial(port: &str, _baud: u32) -> io::Result<File> {
+    let path = if port.starts_with(r"\\.\") {
+        port.to_string()
+    } else {
+        format!(r"\\.\{}", port)
+    };
+    OpenOptions::new().write(true).open(path)
+}
+
+/// Writes `bytes` to a serial port and waits for them to go out.
+pub fn send_serial(port: &str, baud: u32, bytes: &[u8]) -> io::Result<()> {
+    let mut file = open_serial(port, baud)?;
+    file.write_all(bytes)?;
+    file.flush()?;
+    #[cfg(unix)]
+    nix::sys::termios::tcdrain(&file)?;
+    Ok(())
+}
+
+/// Switches one relay on a USB serial relay board of the common CH340 kind
+/// (LCUS-1 and its multi-channel siblings), which take four-byte commands at
+/// 9600 baud. Relays count from 1.
+pub fn set_relay(port: &str, relay: u8, on: bool) -> io::Result<()> {
+    let state = on as u8;
+    let command = [
+        0xA0,
+        relay,
+        state,
+        0xA0u8.wrapping_add(relay).wrapping_add(state),
+    ];
+    send_serial(port, 9600, &command)
+}
+
+/// Drives a GPIO pin through Linux's sysfs interface, exporting it first if
+/// need be.
+pub fn set_gpio(pin: u32, high: bool) -> io::Result<()> {
+    let dir = format!("/sys/class/gpio/gpio{}", pin);
+    if !Path::new(&dir).exists() {
+        std::fs::write("/sys/class/gpio/export", pin.to_string())?;
+    }
+    std::fs::write(format!("{}/direction", dir), "out")?;
+    std::fs::write(format!("{}/value", dir), if high { "1" } else { "0" })
+}
+
+/// Where a new serial trigger points.
+#[cfg(unix)]
+pub const DEFAULT_PORT: &str = "/dev/ttyUSB0";
+#[cfg(not(unix))]
+pub const DEFAULT_PORT: &str = "COM3";
@@ -0,0 +1,106 @@
+//! Haze automation: runs the hazers on a duty cycle while the show plays, so
+//! the room keeps an even haze without anyone riding the haze channel. The
+//! Smoke pad overrides the schedule with a full blast, and the hazers shut
+//! off by themselves once the last cue has played.
+
+use crate::cue::Cue;
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// The haze schedule, saved with the show.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HazeSettings {
+    pub enabled: bool,
+    /// Ids of the patched fixtures the schedule drives, on their Haze
+    /// channel.
+    pub fixtures: Vec<usize>,
+    /// Haze output during a burst, from 0 to 1.
+    pub output: f32,
+    /// How long each burst lasts.
+    pub burst: Duration,
+    /// Time from the start of one burst to the start of the next.
+    pub interval: Duration,
+    /// Label of the pad that overrides the schedule while it is on.
+    pub pad: String,
+    /// Haze output while the pad is on, from 0 to 1.
+    pub pad_output: f32,
+}
+
+impl Default for HazeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fixtures: Vec::new(),
+            output: 0.2,
+            burst: Duration::from_secs(30),
+            interval: Duration::from_secs(180),
+            pad: "Smoke".to_string(),
+            pad_output: 1.0,
+        }
+    }
+}
+
+impl HazeSettings {
+    /// Whether the schedule is in a burst `elapsed` into the show. Bursts
+    /// start with the show and then every interval.
+    pub fn is_bursting(&self, elapsed: Duration) -> bool {
+        let interval = self.interval.max(self.burst).max(Duration::from_secs(1));
+        Duration::from_nanos((elapsed.as_nanos() % interval.as_nanos()) as u64) < self.burst
+    }
+
+    /// The haze output `elapsed` into a show ending at `end`, or the Smoke
+    /// pad's output scaled by its level `smoke` while that is above 0.
+    /// `None` leaves the haze channels to the cues.
+    pub fn output_at(&self, elapsed: Duration, end: Duration, smoke: f32) -> Option<f32> {
+        if smoke > 0.0 {
+            Some(self.pad_output * smoke)
+        } else if elapsed >= end {
+            Some(0.0)
+        } else {
+            self.is_bursting(elapsed).then_some(self.output)
+        }
+    }
+
+    /// Writes `output` to the Haze channel of each hazer the schedule
+    /// drives.
+    pub fn render(&self, fixtures: &[Fixture], output: f32, frame: &mut DmxFrame) {
+        let value = (output.clamp(0.0, 1.0) * 255.0).round() as u8;
+        for fixture in fixtures
+            .iter()
+            .filter(|fixture| self.fixtures.contains(&fixture.id))
+        {
+            if let Some(channel) = fixture.channel_for(Attribute::Haze) {
+                frame.set(fixture.universe, channel, value);
+            }
+        }
+    }
+}
+
+/// When the last live cue in `cues` finishes, with start times on the show
+/// timeline.
+pub fn show_end(cues: &[Cue]) -> Duration {
+    cues.iter()
+        .filter(|cue| cue.is_live())
+        .map(|cue| cue.start_time + cue.duration)
+        .max()
+        .unwrap_or_default()
+}
+
+impl HaloApp {
+    /// How far on the pad that overrides the haze schedule is, 0 when it
+    /// is off and done fading.
+    pub(crate) fn smoke_level(&self) -> f32 {
+        let label = &self.show.haze.pad;
+        let now = Instant::now();
+        self.show
+            .pads
+            .iter()
+            .filter(|pad| &pad.label == label)
+            .map(|pad| pad.level(now))
+            .fold(0.0, f32::max)
+    }
+}
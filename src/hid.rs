@@ -0,0 +1,199 @@
+//! USB buttons and footswitches as trigger binding sources. Most of them,
+//! like the keyboards many present as, turn up on Linux as input event
+//! devices, read here on a background thread whether or not Halo has focus.
+//! Each key press becomes a [`InputEvent::HidKey`], so a big red button
+//! is bound to GO like any MIDI note. Presses of a key closer together than
+//! the debounce time count once, for switches that bounce.
+//!
+//! [`InputEvent::HidKey`]: crate::binding::InputEvent::HidKey
+
+use crate::binding::InputEvent;
+use crate::error::{Device, HaloError};
+use crate::HaloApp;
+use eframe::egui;
+use std::io;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// How long an unplugged device waits before being opened again.
+#[cfg(target_os = "linux")]
+const REOPEN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// An input device that might be a button.
+#[derive(Clone, PartialEq)]
+pub struct HidDevice {
+    pub path: PathBuf,
+    pub name: String,
+}
+
+/// The input event devices present, by name, under their `/dev/input/by-id`
+/// path where they have one so the choice survives being plugged into
+/// another socket.
+#[cfg(target_os = "linux")]
+pub fn devices() -> Vec<HidDevice> {
+    let by_id: Vec<(PathBuf, PathBuf)> = std::fs::read_dir("/dev/input/by-id")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| Some((entry.path().canonicalize().ok()?, entry.path())))
+        .collect();
+    let mut devices: Vec<HidDevice> = std::fs::read_dir("/sys/class/input")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("event"))
+        .map(|entry| {
+            let node = Path::new("/dev/input").join(entry.file_name());
+            let name = std::fs::read_to_string(entry.path().join("device/name")).map_or_else(
+                |_| node.display().to_string(),
+                |name| name.trim().to_string(),
+            );
+            let path = by_id
+                .iter()
+                .find(|(target, _)| *target == node)
+                .map_or(node, |(_, link)| link.clone());
+            HidDevice { path, name }
+        })
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn devices() -> Vec<HidDevice> {
+    Vec::new()
+}
+
+/// The key pressed in an `input_event` record, if it is a press: the time
+/// as two longs, then the type, code and value.
+#[cfg(target_os = "linux")]
+fn key_press(record: &[u8]) -> Option<u16> {
+    const EV_KEY: u16 = 1;
+    let at = std::mem::size_of::<usize>() * 2;
+    let kind = u16::from_ne_bytes(record.get(at..at + 2)?.try_into().ok()?);
+    let code = u16::from_ne_bytes(record.get(at + 2..at + 4)?.try_into().ok()?);
+    let value = i32::from_ne_bytes(record.get(at + 4..at + 8)?.try_into().ok()?);
+    // 1 is a press, 0 a release and 2 auto-repeat.
+    (kind == EV_KEY && value == 1).then_some(code)
+}
+
+/// Reads key presses from `path` until `stop` is set, opening it again
+/// whenever it goes away.
+#[cfg(target_os = "linux")]
+fn read_keys(
+    path: &Path,
+    debounce: Duration,
+    stop: &AtomicBool,
+    events: &Sender<u16>,
+    ctx: &egui::Context,
+) {
+    use std::collections::HashMap;
+    use std::io::Read;
+    use std::time::Instant;
+
+    let record = std::mem::size_of::<usize>() * 2 + 8;
+    let mut pressed: HashMap<u16, Instant> = HashMap::new();
+    while !stop.load(Ordering::Relaxed) {
+        let Ok(mut device) = std::fs::File::open(path) else {
+            thread::sleep(REOPEN_INTERVAL);
+            continue;
+        };
+        let mut buf = vec![0u8; record * 64];
+        while let Ok(len) = device.read(&mut buf) {
+            if len == 0 || stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let now = Instant::now();
+            for code in buf[..len].chunks_exact(record).filter_map(key_press) {
+                if pressed
+                    .get(&code)
+                    .is_some_and(|&last| now.duration_since(last) < debounce)
+                {
+                    continue;
+                }
+                pressed.insert(code, now);
+                if events.send(code).is_err() {
+                    return;
+                }
+                ctx.request_repaint();
+            }
+        }
+    }
+}
+
+/// Reads a button device on a background thread until dropped.
+pub struct HidListener {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl HidListener {
+    #[cfg(target_os = "linux")]
+    pub fn start(
+        path: PathBuf,
+        debounce: Duration,
+        events: Sender<u16>,
+        ctx: egui::Context,
+    ) -> io::Result<Self> {
+        // Open it once here so a missing device or permissions are reported
+        // straight away.
+        std::fs::File::open(&path)
+            .map_err(|err| io::Error::other(format!("{}: {}", path.display(), err)))?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = shutdown.clone();
+        thread::spawn(move || read_keys(&path, debounce, &stop, &events, &ctx));
+        Ok(Self { shutdown })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn start(
+        _path: PathBuf,
+        _debounce: Duration,
+        _events: Sender<u16>,
+        _ctx: egui::Context,
+    ) -> io::Result<Self> {
+        Err(io::Error::other("USB buttons are only read on Linux"))
+    }
+}
+
+impl Drop for HidListener {
+    fn drop(&mut self) {
+        // Reads block, so the thread finishes at the device's next event
+        // rather than being waited for here.
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+impl HaloApp {
+    /// Starts or stops reading the button device to match the current
+    /// preferences.
+    pub(crate) fn apply_hid_preferences(&mut self, ctx: &egui::Context) {
+        self.hid = None;
+        self.hid_error = None;
+        let Some(path) = self.preferences.hid_device.clone() else {
+            return;
+        };
+        let debounce = Duration::from_millis(self.preferences.hid_debounce_ms);
+        let name = path.display().to_string();
+        match HidListener::start(path, debounce, self.hid_sender.clone(), ctx.clone()) {
+            Ok(listener) => self.hid = Some(listener),
+            Err(err) => {
+                self.report_error(HaloError::device(Device::Buttons, name, &err));
+                self.hid_error = Some(err.to_string());
+            }
+        }
+    }
+
+    /// Runs the bindings of the keys pressed since the last frame.
+    pub(crate) fn handle_hid_keys(&mut self) {
+        let keys: Vec<u16> = self.hid_keys.try_iter().collect();
+        for key in keys {
+            self.handle_input_event(InputEvent::HidKey(key));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Hot-plugging: a background thread watches which MIDI ports are present,
+//! so a controller unplugged mid-show is noticed, and bound again when it
+//! comes back, without restarting Halo.
+
+use crate::error::Device;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::midi;
+use crate::HaloApp;
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How often the ports are listed.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The MIDI ports present at one moment.
+#[derive(Clone, PartialEq)]
+pub struct MidiPorts {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+impl MidiPorts {
+    fn list() -> Self {
+        Self {
+            inputs: midi::input_ports(),
+            outputs: midi::output_ports(),
+        }
+    }
+}
+
+/// Lists the MIDI ports until dropped, sending the list whenever it changes.
+pub struct DeviceWatcher {
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+    changes: Receiver<MidiPorts>,
+}
+
+impl DeviceWatcher {
+    pub fn start(ctx: egui::Context) -> Self {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (sender, changes) = mpsc::channel();
+        let stop = shutdown.clone();
+        let thread = thread::Builder::new()
+            .name("halo-hotplug".to_string())
+            .spawn(move || {
+                let mut last = MidiPorts::list();
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(POLL_INTERVAL);
+                    let ports = MidiPorts::list();
+                    if ports == last {
+                        continue;
+                    }
+                    last = ports.clone();
+                    if sender.send(ports).is_err() {
+                        return;
+                    }
+                    ctx.request_repaint();
+                }
+            })
+            .ok();
+        Self {
+            shutdown,
+            thread,
+            changes,
+        }
+    }
+
+    /// The latest change to the ports, if there has been one.
+    pub fn latest(&self) -> Option<MidiPorts> {
+        self.changes.try_iter().last()
+    }
+}
+
+impl Drop for DeviceWatcher {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl HaloApp {
+    /// Drops MIDI ports that were unplugged and reconnects chosen ports that
+    /// came back, telling the operator either way.
+    pub(crate) fn handle_device_changes(&mut self, ctx: &egui::Context) {
+        let Some(ports) = self.device_watcher.as_ref().and_then(DeviceWatcher::latest) else {
+            return;
+        };
+        if let Some(port) = self.preferences.midi_input.clone() {
+            let present = ports.inputs.iter().any(|name| midi::same_port(name, &port));
+            if self.midi_in.is_some() && !present {
+                self.midi_in = None;
+                self.midi_error = Some(tr!("hotplug-input-disconnected", port = port.as_str()));
+                self.notify(
+                    LogLevel::Warning,
+                    tr!("hotplug-input-unplugged", port = port.as_str()),
+                );
+            } else if self.midi_in.is_none() && present {
+                self.midi_error = None;
+                self.connect_midi_input(ctx);
+                if self.midi_in.is_some() {
+                    self.clear_device_error(Device::MidiInput);
+                    self.notify(
+                        LogLevel::Info,
+                        tr!("hotplug-input-reconnected", port = port.as_str()),
+                    );
+                }
+            }
+        }
+        if let Some(port) = self.preferences.midi_output.clone() {
+            let present = ports
+                .outputs
+                .iter()
+                .any(|name| midi::same_port(name, &port));
+            if self.midi_out.is_some() && !present {
+                self.midi_out = None;
+                self.midi_error = Some(tr!("hotplug-output-disconnected", port = port.as_str()));
+                self.notify(
+                    LogLevel::Warning,
+                    tr!("hotplug-output-unplugged", port = port.as_str()),
+                );
+            } else if self.midi_out.is_none() && present {
+                self.midi_error = None;
+                self.connect_midi_output();
+                if self.midi_out.is_some() {
+                    self.clear_device_error(Device::MidiOutput);
+                    self.notify(
+                        LogLevel::Info,
+                        tr!("hotplug-output-reconnected", port = port.as_str()),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Minimal HTTP endpoints for Bitfocus Companion and Stream Deck. Every route
+//! answers with JSON; actions accept GET as well as POST so Stream Deck's
+//! "System: Website" action can trigger them.
+
+use crate::remote::{self, RemoteMessage, RemoteRequest, RemoteResponse};
+use eframe::egui;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Duration;
+
+enum Route {
+    /// Query the state and reply with the value at this JSON pointer.
+    Query(&'static str),
+    Action(RemoteRequest),
+}
+
+/// Decodes the `%XX` escapes in a path segment, or None if they aren't
+/// valid UTF-8.
+fn percent_decode(segment: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut rest = segment.as_bytes();
+    while let Some((&byte, after)) = rest.split_first() {
+        rest = after;
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        let hex = std::str::from_utf8(rest.get(..2)?).ok()?;
+        bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        rest = &rest[2..];
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// The route for `path`, whose segments are percent-decoded so names with
+/// spaces or accents in them can be used.
+fn route(method: &str, path: &str) -> Option<Route> {
+    let decoded: Vec<String> = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect::<Option<_>>()?;
+    let segments: Vec<&str> = decoded.iter().map(String::as_str).collect();
+    let is_action_method = method == "POST" || method == "GET";
+
+    match (method, segments.as_slice()) {
+        ("GET", ["api", "state"]) => Some(Route::Query("")),
+        ("GET", ["api", "timecode"]) => Some(Route::Query("/timecode")),
+        ("GET", ["api", "cue"]) => Some(Route::Query("/current_cue")),
+        ("GET", ["api", "pads"]) => Some(Route::Query("/pads")),
+        (_, ["api", "cue", cue, "fire"]) if is_action_method => {
+            Some(Route::Action(RemoteRequest::FireCue {
+                cue: cue.to_string(),
+            }))
+        }
+        (_, ["api", "pads", pad, "toggle"]) if is_action_method => pad
+            .parse()
+            .ok()
+            .map(|pad| Route::Action(RemoteRequest::TogglePad { pad })),
+        (_, ["api", "bpm", bpm]) if is_action_method => bpm
+            .parse()
+            .ok()
+            .map(|bpm| Route::Action(RemoteRequest::SetBpm { bpm })),
+        (_, ["api", "trigger", name]) if is_action_method => {
+            Some(Route::Action(RemoteRequest::Trigger {
+                name: name.to_string(),
+            }))
+        }
+        (_, ["api", "transport", action]) if is_action_method => match *action {
+            "start" => Some(Route::Action(RemoteRequest::Start)),
+            "stop" => Some(Route::Action(RemoteRequest::Stop)),
+            "pause" => Some(Route::Action(RemoteRequest::Pause)),
+            "reset" => Some(Route::Action(RemoteRequest::Reset)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn write_json(stream: &mut TcpStream, status: &str, body: &Value) -> io::Result<()> {
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+fn response_json(response: &RemoteResponse) -> Value {
+    serde_json::to_value(response).unwrap_or_else(|_| json!({ "ok": false }))
+}
+
+pub fn handle_connection(
+    stream: TcpStream,
+    sender: Sender<RemoteMessage>,
+    ctx: egui::Context,
+    stop: Arc<AtomicBool>,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return write_json(
+            &mut writer,
+            "400 Bad Request",
+            &json!({ "ok": false, "error": "malformed request" }),
+        );
+    };
+
+    // Skip the headers, draining any body so the client sees a clean close.
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length.min(64 * 1024)];
+    reader.read_exact(&mut body)?;
+
+    // The server was switched off while the request came in.
+    if stop.load(Ordering::Relaxed) {
+        return write_json(
+            &mut writer,
+            "503 Service Unavailable",
+            &json!({ "ok": false, "error": "the HTTP server is stopping" }),
+        );
+    }
+
+    let path = target.split('?').next().unwrap_or(target);
+    match route(method, path) {
+        Some(Route::Query(pointer)) => {
+            let response = remote::dispatch(RemoteRequest::State, &sender, &ctx);
+            let value = response_json(&response);
+            let body = value
+                .pointer(&format!("/state{}", pointer))
+                .cloned()
+                .unwrap_or(value);
+            write_json(&mut writer, "200 OK", &body)
+        }
+        Some(Route::Action(request)) => {
+            let response = remote::dispatch(request, &sender, &ctx);
+            let status = if response.ok {
+                "200 OK"
+            } else {
+                "400 Bad Request"
+            };
+            write_json(&mut writer, status, &response_json(&response))
+        }
+        None => write_json(
+            &mut writer,
+            "404 Not Found",
+            &json!({ "ok": false, "error": format!("no route for {} {}", method, path) }),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_segments_are_percent_decoded() {
+        assert_eq!(percent_decode("Walk%20In").as_deref(), Some("Walk In"));
+        assert_eq!(percent_decode("Canci%C3%B3n").as_deref(), Some("Canción"));
+        assert_eq!(percent_decode("50%25").as_deref(), Some("50%"));
+        assert_eq!(percent_decode("plain").as_deref(), Some("plain"));
+        assert_eq!(percent_decode("bad%2"), None);
+        assert_eq!(percent_decode("bad%zz"), None);
+        assert_eq!(percent_decode("%FF"), None);
+
+        let Some(Route::Action(RemoteRequest::Trigger { name })) =
+            route("GET", "/api/trigger/Walk%20In")
+        else {
+            panic!("no trigger route");
+        };
+        assert_eq!(name, "Walk In");
+    }
+}
@@ -0,0 +1,61 @@
+//! Hue shift: a cue effect that turns the colours of the fixtures it gives
+//! red, green and blue round the colour wheel, so a fanned rainbow or
+//! gradient chases along the rig. Every fixture turns by the same amount,
+//! keeping the fan's spread as its phase.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use eframe::egui::epaint::Hsva;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HueShift {
+    /// Seconds to go once round the wheel.
+    pub period: f32,
+    /// Turns the other way round the wheel.
+    pub reverse: bool,
+    /// The speed master running the shift, if any.
+    pub speed_master: Option<usize>,
+}
+
+impl Default for HueShift {
+    fn default() -> Self {
+        Self {
+            period: 8.0,
+            reverse: false,
+            speed_master: None,
+        }
+    }
+}
+
+impl HueShift {
+    /// How far round the wheel the shift has turned at effect time `time`,
+    /// from 0 to 1.
+    pub fn phase(&self, time: f32) -> f32 {
+        let turns = (time / self.period.max(0.1)).fract();
+        if self.reverse {
+            1.0 - turns
+        } else {
+            turns
+        }
+    }
+
+    /// Turns `fixture`'s colour in `frame` by `phase` of the way round.
+    pub fn apply(phase: f32, fixture: &Fixture, frame: &mut DmxFrame) {
+        let (Some(red), Some(green), Some(blue)) = (
+            fixture.channel_for(Attribute::Red),
+            fixture.channel_for(Attribute::Green),
+            fixture.channel_for(Attribute::Blue),
+        ) else {
+            return;
+        };
+        let channels = [red, green, blue];
+        let mut color =
+            Hsva::from_srgb(channels.map(|channel| frame.get(fixture.universe, channel)));
+        color.h = (color.h + phase).fract();
+        for (channel, level) in channels.into_iter().zip(color.to_srgb()) {
+            frame.set(fixture.universe, channel, level);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Translations of the interface. Each language is a Fluent file in
+//! `assets/locales`, built into the binary, and text is looked up by id
+//! with [`tr!`]. Anything a language doesn't translate yet falls back to
+//! English, so a translation can grow a window at a time.
+//!
+//! The chosen language is global rather than passed to every window: it
+//! only changes from the preferences, and only the UI thread reads it.
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use unic_langid::LanguageIdentifier;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locales/en.ftl"),
+            Language::Spanish => include_str!("../assets/locales/es.ftl"),
+        }
+    }
+}
+
+/// Each language by its own name, so it can be found by someone who can't
+/// read the current one.
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        })
+    }
+}
+
+/// The chosen language, by its place in [`Language::ALL`].
+static LANGUAGE: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Bundles are built the first time a language is used.
+    static BUNDLES: RefCell<HashMap<Language, FluentBundle<FluentResource>>> =
+        RefCell::new(HashMap::new());
+}
+
+pub fn set_language(language: Language) {
+    let index = Language::ALL
+        .iter()
+        .position(|&l| l == language)
+        .unwrap_or(0);
+    LANGUAGE.store(index, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL[LANGUAGE.load(Ordering::Relaxed)]
+}
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = language.code().parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new(vec![id]);
+    // egui draws the bidi isolation marks Fluent puts round arguments as
+    // boxes.
+    bundle.set_use_isolating(false);
+    // The files are built in, so errors are mistakes in them; whatever did
+    // parse is still used.
+    let resource = FluentResource::try_new(language.source().to_string())
+        .unwrap_or_else(|(resource, _)| resource);
+    let _ = bundle.add_resource(resource);
+    bundle
+}
+
+/// `id` in `language`, if it has it.
+fn format(language: Language, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    BUNDLES.with(|bundles| {
+        let mut bundles = bundles.borrow_mut();
+        let bundle = bundles.entry(language).or_insert_with(|| bundle(language));
+        let pattern = bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        Some(
+            bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned(),
+        )
+    })
+}
+
+/// `id` in the chosen language, or in English if it isn't translated. Ids
+/// missing from English too are shown as they are, so they stand out.
+pub fn translate(id: &str, args: Option<&FluentArgs>) -> String {
+    format(language(), id, args)
+        .or_else(|| format(Language::English, id, args))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// The text with id `$id` in the chosen language, with any arguments given
+/// as `name = value`.
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::translate($id, None)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($id, Some(&args))
+    }};
+}
+pub(crate) use tr;
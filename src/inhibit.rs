@@ -0,0 +1,36 @@
+//! Inhibited fixtures: units left out of the show for the night — broken, or
+//! a rental that never turned up — without losing their programming. Their
+//! channels go out dark whatever the cues ask for, channel check steps past
+//! them, and clearing the flag puts them straight back.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::Fixture;
+use std::collections::HashSet;
+
+/// The channels of inhibited fixtures that no working fixture shares, by
+/// universe and 1-based channel, so a stand-in patched over a missing unit
+/// still plays.
+pub fn channels(fixtures: &[Fixture]) -> HashSet<(u16, u16)> {
+    let footprint = |fixture: &Fixture| {
+        let (universe, address) = (fixture.universe, fixture.address);
+        (0..fixture.profile.channels.len() as u16).map(move |offset| (universe, address + offset))
+    };
+    let working: HashSet<(u16, u16)> = fixtures
+        .iter()
+        .filter(|fixture| !fixture.inhibited)
+        .flat_map(footprint)
+        .collect();
+    fixtures
+        .iter()
+        .filter(|fixture| fixture.inhibited)
+        .flat_map(footprint)
+        .filter(|channel| !working.contains(channel))
+        .collect()
+}
+
+/// Puts the inhibited fixtures' channels out dark.
+pub fn apply(channels: &HashSet<(u16, u16)>, frame: &mut DmxFrame) {
+    for &(universe, channel) in channels {
+        frame.set(universe, channel, 0);
+    }
+}
@@ -0,0 +1,246 @@
+//! Network interface selection, so that on a machine with several networks,
+//! such as a FOH laptop on venue Wi-Fi and a lighting VLAN, Art-Net, sACN and
+//! OSC use the right one. Halo keeps a list of the machine's IPv4 interfaces
+//! up to date and warns when an interface a protocol is bound to goes down.
+
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::{Duration, Instant};
+
+/// How often the list of interfaces is refreshed.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// An IPv4 address on one of the machine's network interfaces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub address: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    /// The interface is up with a link.
+    pub up: bool,
+}
+
+impl NetworkInterface {
+    /// Whether `ip` is on this interface's subnet.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let IpAddr::V4(ip) = ip else {
+            return false;
+        };
+        let mask = u32::from(self.netmask);
+        u32::from(ip) & mask == u32::from(self.address) & mask
+    }
+
+    /// The broadcast address of this interface's subnet.
+    pub fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from(u32::from(self.address) | !u32::from(self.netmask))
+    }
+}
+
+impl fmt::Display for NetworkInterface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.address)?;
+        if !self.up {
+            f.write_str(&tr!("interfaces-down-suffix"))?;
+        }
+        Ok(())
+    }
+}
+
+/// The machine's IPv4 interfaces, in the order the system lists them.
+#[cfg(unix)]
+pub fn list() -> Vec<NetworkInterface> {
+    use nix::ifaddrs::getifaddrs;
+    use nix::net::if_::InterfaceFlags;
+
+    let Ok(addresses) = getifaddrs() else {
+        return Vec::new();
+    };
+    addresses
+        .filter_map(|interface| {
+            let address = interface.address?.as_sockaddr_in()?.ip();
+            let netmask = interface
+                .netmask
+                .and_then(|netmask| netmask.as_sockaddr_in().map(|netmask| netmask.ip()))
+                .unwrap_or(Ipv4Addr::BROADCAST);
+            let up = interface
+                .flags
+                .contains(InterfaceFlags::IFF_UP | InterfaceFlags::IFF_RUNNING);
+            Some(NetworkInterface {
+                name: interface.interface_name,
+                address,
+                netmask,
+                up,
+            })
+        })
+        .collect()
+}
+
+/// Interfaces can only be listed on Unix for now; elsewhere every protocol
+/// uses all interfaces.
+#[cfg(not(unix))]
+pub fn list() -> Vec<NetworkInterface> {
+    Vec::new()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Protocol {
+    ArtNet,
+    Sacn,
+    Osc,
+}
+
+impl Protocol {
+    pub const ALL: [Protocol; 3] = [Protocol::ArtNet, Protocol::Sacn, Protocol::Osc];
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Protocol::ArtNet => "Art-Net",
+            Protocol::Sacn => "sACN",
+            Protocol::Osc => "OSC",
+        })
+    }
+}
+
+/// The interface each protocol is bound to, by name, so the choice survives
+/// the address changing. None uses all interfaces.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InterfaceSettings {
+    pub art_net: Option<String>,
+    pub sacn: Option<String>,
+    pub osc: Option<String>,
+}
+
+impl InterfaceSettings {
+    pub fn get(&self, protocol: Protocol) -> Option<&str> {
+        match protocol {
+            Protocol::ArtNet => self.art_net.as_deref(),
+            Protocol::Sacn => self.sacn.as_deref(),
+            Protocol::Osc => self.osc.as_deref(),
+        }
+    }
+
+    pub fn get_mut(&mut self, protocol: Protocol) -> &mut Option<String> {
+        match protocol {
+            Protocol::ArtNet => &mut self.art_net,
+            Protocol::Sacn => &mut self.sacn,
+            Protocol::Osc => &mut self.osc,
+        }
+    }
+}
+
+impl HaloApp {
+    /// The interface `protocol` is bound to, if it has one and it is up.
+    pub(crate) fn bound_interface(&self, protocol: Protocol) -> Option<&NetworkInterface> {
+        let name = self.preferences.interfaces.get(protocol)?;
+        self.interfaces
+            .iter()
+            .find(|interface| interface.name == name && interface.up)
+    }
+
+    /// The address to bind `protocol`'s sockets to. While its interface is
+    /// down this falls back to all interfaces, so the protocol keeps working
+    /// on whatever network is left.
+    pub(crate) fn bind_address(&self, protocol: Protocol) -> Ipv4Addr {
+        self.bound_interface(protocol)
+            .map_or(Ipv4Addr::UNSPECIFIED, |interface| interface.address)
+    }
+
+    /// Whether anything is using `protocol`, so its interface matters.
+    fn protocol_in_use(&self, protocol: Protocol) -> bool {
+        let input = &self.preferences.network_input;
+        match protocol {
+            Protocol::ArtNet => self.preferences.art_net_output.enabled || input.art_net,
+            Protocol::Sacn => self.preferences.sacn_output.enabled || input.sacn,
+            Protocol::Osc => self.preferences.osc_input_enabled,
+        }
+    }
+
+    /// The protocols in use whose chosen interface is missing or down.
+    pub(crate) fn interfaces_down(&self) -> Vec<(Protocol, &str)> {
+        Protocol::ALL
+            .into_iter()
+            .filter(|&protocol| self.protocol_in_use(protocol))
+            .filter_map(|protocol| {
+                let name = self.preferences.interfaces.get(protocol)?;
+                self.bound_interface(protocol)
+                    .is_none()
+                    .then_some((protocol, name))
+            })
+            .collect()
+    }
+
+    /// Refreshes the interface list every couple of seconds, warning when a
+    /// chosen interface goes down and rebinding the listeners when their
+    /// address changes.
+    pub(crate) fn check_interfaces(&mut self, ctx: &egui::Context) {
+        if self
+            .interfaces_checked
+            .is_some_and(|checked| checked.elapsed() < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        // Warn about an interface that is already down at startup.
+        let first = self.interfaces_checked.is_none();
+        self.interfaces_checked = Some(Instant::now());
+        ctx.request_repaint_after(REFRESH_INTERVAL);
+
+        let before: Vec<Option<Ipv4Addr>> = Protocol::ALL
+            .into_iter()
+            .map(|protocol| self.bound_interface(protocol).map(|i| i.address))
+            .collect();
+        self.interfaces = list();
+        for (protocol, was) in Protocol::ALL.into_iter().zip(before.iter().copied()) {
+            let Some(name) = self
+                .preferences
+                .interfaces
+                .get(protocol)
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            let now = self.bound_interface(protocol).map(|i| i.address);
+            if !self.protocol_in_use(protocol) {
+                continue;
+            }
+            let message = match (was.is_some() || first, now.is_some()) {
+                (true, false) => tr!(
+                    "interfaces-down",
+                    protocol = protocol.to_string(),
+                    name = name.as_str()
+                ),
+                (false, true) => tr!(
+                    "interfaces-up",
+                    protocol = protocol.to_string(),
+                    name = name.as_str()
+                ),
+                _ => continue,
+            };
+            let level = if now.is_some() {
+                LogLevel::Info
+            } else {
+                LogLevel::Warning
+            };
+            self.notify(level, message);
+        }
+
+        let changed: Vec<bool> = Protocol::ALL
+            .into_iter()
+            .zip(before)
+            .map(|(protocol, was)| self.bound_interface(protocol).map(|i| i.address) != was)
+            .collect();
+        if changed[Protocol::ArtNet as usize] || changed[Protocol::Sacn as usize] {
+            self.apply_network_input_preferences();
+        }
+        if changed[Protocol::Osc as usize] {
+            self.apply_osc_preferences(ctx);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Learn timing: a rehearsal mode for turning a called show into a timecode
+//! one. The operator plays the audio or timecode and presses GO where each
+//! cue should fire, and the playhead position at each GO becomes that cue's
+//! start time. Cues waiting for their GO are left out of playback so they
+//! don't fire early at their old times.
+
+use crate::cue::CueNumber;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::HaloApp;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub struct LearnTiming {
+    /// Cues still to be called, in cue list order.
+    pending: VecDeque<CueNumber>,
+    /// Cues called so far, with the start time each had before, for undo
+    /// and cancel.
+    learned: Vec<(CueNumber, Duration)>,
+}
+
+impl LearnTiming {
+    pub fn next(&self) -> Option<CueNumber> {
+        self.pending.front().copied()
+    }
+
+    pub fn learned(&self) -> usize {
+        self.learned.len()
+    }
+
+    pub fn total(&self) -> usize {
+        self.learned.len() + self.pending.len()
+    }
+}
+
+impl HaloApp {
+    fn cue_position(&self, number: CueNumber) -> Option<usize> {
+        self.show.cues.iter().position(|cue| cue.number == number)
+    }
+
+    /// Starts learning the armed cues from the playhead on.
+    pub(crate) fn start_learn_timing(&mut self) {
+        let pending: VecDeque<CueNumber> = self
+            .show
+            .cues
+            .iter()
+            .filter(|cue| self.show.plays(cue) && self.show.cue_start(cue) >= self.elapsed)
+            .map(|cue| cue.number)
+            .collect();
+        if pending.is_empty() {
+            self.log.warn(tr!("learn-no-cues"));
+            return;
+        }
+        self.notify(LogLevel::Info, tr!("learn-started", count = pending.len()));
+        self.learn_timing = Some(LearnTiming {
+            pending,
+            learned: Vec::new(),
+        });
+        self.hold_learn_timing();
+    }
+
+    /// Keeps the cues waiting for their GO out of playback, as moving the
+    /// playhead brings skipped cues back.
+    pub(crate) fn hold_learn_timing(&mut self) {
+        let Some(learn) = &self.learn_timing else {
+            return;
+        };
+        for cue in &mut self.show.cues {
+            if learn.pending.contains(&cue.number) {
+                cue.skipped = true;
+            }
+        }
+    }
+
+    /// Gives the next cue the playhead position as its start time, and lets
+    /// it play from there.
+    pub(crate) fn learn_go(&mut self) {
+        let Some(number) = self
+            .learn_timing
+            .as_mut()
+            .and_then(|learn| learn.pending.pop_front())
+        else {
+            return;
+        };
+        let Some(index) = self.cue_position(number) else {
+            self.log
+                .warn(tr!("learn-cue-gone", number = number.to_string()));
+            return;
+        };
+        let offset = self.show.cue_start(&self.show.cues[index]) - self.show.cues[index].start_time;
+        let cue = &mut self.show.cues[index];
+        let before = cue.start_time;
+        cue.start_time = self.elapsed.saturating_sub(offset);
+        cue.skipped = false;
+        if let Some(learn) = &mut self.learn_timing {
+            learn.learned.push((number, before));
+        }
+        self.log.info(tr!(
+            "learn-learned-cue",
+            number = number.to_string(),
+            time = self.format_timecode()
+        ));
+        self.run_cue_actions(index, None);
+
+        if self
+            .learn_timing
+            .as_ref()
+            .is_some_and(|learn| learn.pending.is_empty())
+        {
+            self.finish_learn_timing(true);
+        }
+    }
+
+    /// Takes back the last GO, putting the cue back to its old time.
+    pub(crate) fn undo_learn_go(&mut self) {
+        let Some((number, before)) = self
+            .learn_timing
+            .as_mut()
+            .and_then(|learn| learn.learned.pop())
+        else {
+            return;
+        };
+        if let Some(index) = self.cue_position(number) {
+            self.show.cues[index].start_time = before;
+        }
+        if let Some(learn) = &mut self.learn_timing {
+            learn.pending.push_front(number);
+        }
+        self.hold_learn_timing();
+    }
+
+    /// Leaves learn timing, keeping the times learned or putting every cue
+    /// back as it was.
+    pub(crate) fn finish_learn_timing(&mut self, keep: bool) {
+        let Some(learn) = self.learn_timing.take() else {
+            return;
+        };
+        for cue in &mut self.show.cues {
+            if learn.pending.contains(&cue.number) {
+                cue.skipped = false;
+            }
+        }
+        if keep {
+            self.notify(
+                LogLevel::Info,
+                tr!("learn-finished", count = learn.learned.len()),
+            );
+            return;
+        }
+        for (number, before) in learn.learned.into_iter().rev() {
+            if let Some(index) = self.cue_position(number) {
+                self.show.cues[index].start_time = before;
+            }
+        }
+        self.log.info(tr!("learn-cancelled"));
+    }
+}
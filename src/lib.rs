@@ -0,0 +1,1868 @@
+mod art_net;
+mod assert;
+pub mod audio;
+mod awake;
+mod back;
+mod backup;
+mod beat_cue;
+mod binding;
+pub mod busk;
+mod cct;
+mod citp;
+mod click;
+mod clock_sync;
+mod clocks;
+mod command_line;
+mod crossfade;
+pub mod cue;
+pub mod cue_index;
+mod daw;
+pub mod dmx;
+mod dmx_fader;
+pub mod dmx_input;
+pub mod driver;
+mod effect_preset;
+mod end_action;
+pub mod engine;
+mod error;
+mod export;
+mod fade_curve;
+mod fan;
+pub mod fixture;
+mod fixture_control;
+mod fixture_replace;
+mod flash;
+mod follow;
+mod follow_spot;
+mod group_master;
+mod hardware;
+mod haze;
+mod hid;
+mod hotplug;
+mod http;
+mod hue_shift;
+mod i18n;
+mod inhibit;
+mod interfaces;
+mod learn_timing;
+mod log;
+mod macros;
+mod marker;
+mod media;
+mod midi;
+mod midi_monitor;
+mod move_in_black;
+mod osc;
+mod osc_monitor;
+pub mod ownership;
+mod package;
+mod packet_inspector;
+mod pad_bank;
+mod pad_release;
+mod panic;
+mod patch_import;
+pub mod pixel;
+mod pixel_stream;
+mod playback;
+pub mod plugin;
+mod power;
+mod preferences;
+pub mod processing;
+pub mod psn;
+mod quick_console;
+mod rdm;
+mod recording;
+mod remote;
+mod ripple;
+mod sacn;
+mod schedule;
+mod schema;
+mod script;
+mod session;
+mod setlist;
+mod shortcuts;
+mod show;
+mod show_diff;
+mod show_lock;
+mod show_merge;
+mod simulate;
+mod smart_bulb;
+mod snap;
+mod snapshot;
+mod song;
+mod spectrogram;
+mod speed_master;
+mod stage_display;
+mod startup;
+pub mod stats;
+mod stream_import;
+mod tag;
+mod test_pattern;
+mod theme;
+mod time_signature;
+mod timecode;
+mod tracking;
+mod transport;
+mod trigger;
+mod ui;
+mod update;
+mod variable;
+mod variation;
+mod venue;
+
+use art_net::{ArtNetDiscovery, ArtNetPort, OutputRate};
+use audio::{AudioInput, AudioMeter};
+use binding::ReceivedInput;
+use citp::CitpServer;
+use click::{ClickClock, ClickOutput};
+use clock_sync::{BackupState, ClockClient, ClockServer};
+use clocks::Readout;
+use command_line::CommandLine;
+use crossfade::{CrossfadePair, Fader};
+use cue::Cue;
+use dmx::{ChannelCheck, DmxFrame};
+use dmx_fader::{DmxFader, DmxLearn};
+use dmx_input::{DmxInput, NetworkInput};
+use eframe::egui;
+use engine::Engine;
+use error::HaloError;
+use export::ReportFormat;
+use fixture::FixtureProfile;
+use follow::{FollowClient, Follower};
+use hid::HidListener;
+use hotplug::DeviceWatcher;
+use i18n::tr;
+use interfaces::NetworkInterface;
+use learn_timing::LearnTiming;
+use log::{Log, LogLevel};
+use macros::{MacroAction, MacroPlayback, MacroRecorder};
+use midi::{MidiEvent, MidiIn, MidiOut};
+use midi_monitor::{MidiMonitor, MonitorFilter};
+use osc::{OscListener, OscMessage};
+use osc_monitor::OscMonitor;
+use ownership::Owners;
+use packet_inspector::PacketInspector;
+use panic::Panic;
+use pixel::PixelClock;
+use playback::{Playback, PlaybackClock};
+use plugin::{PluginHost, PluginSource};
+use preferences::Preferences;
+use psn::{PsnListener, Trackers};
+use rdm::Rdm;
+use remote::{RemoteMessage, RemoteServer};
+use script::Scripting;
+use session::Session;
+use show::Show;
+use stats::{FrameTimer, StageTime};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Weak;
+use std::time::{Duration, Instant};
+use test_pattern::TestPattern;
+use time_signature::TimeSignature;
+use timecode::Timecode;
+use tracking::EditMode;
+use transport::Transport;
+use ui::command_palette::CommandPalette;
+use ui::daw::DawTool;
+use ui::effect_presets::EffectPresetTool;
+use ui::fan::FanTool;
+use ui::osc_monitor::OscMonitorTab;
+use ui::packet_inspector::InspectorView;
+use ui::patch::PatchTools;
+use ui::patch_import::PatchImport;
+use ui::preferences::PreferencesSection;
+use ui::profile_browser::ProfileBrowser;
+use ui::recording::OutputRecording;
+use ui::script_editor::ScriptTarget;
+use ui::show_diff::ShowDiff;
+use ui::show_file::{FileAction, FileDialog};
+use ui::show_merge::ShowMerge;
+use ui::stage_display::StageDisplay;
+use ui::toasts::Toasts;
+use ui::tracksheet::CellEdit;
+use ui::venue::VenueTool;
+use ui::viewports::Panel;
+use update::{Release, UpdateCheck};
+use venue::VenueMap;
+
+struct BeatIndicator {
+    /// Beats counted since the last reset, or the last Start of an external
+    /// clock.
+    beat: u32,
+    last_beat_time: Instant,
+    beat_duration: Duration,
+}
+
+impl BeatIndicator {
+    fn new() -> Self {
+        Self {
+            beat: 0,
+            last_beat_time: Instant::now(),
+            beat_duration: Duration::from_secs_f32(60.0 / 120.0), // Default 120 BPM
+        }
+    }
+
+    /// Lines the indicator up with beat `beat` of an external clock.
+    fn sync(&mut self, beat: u32) {
+        self.beat = beat;
+        self.last_beat_time = Instant::now();
+    }
+
+    fn update(&mut self, bpm: f32) {
+        self.beat_duration = Duration::from_secs_f32(60.0 / bpm);
+        if self.last_beat_time.elapsed() >= self.beat_duration {
+            self.beat = self.beat.wrapping_add(1);
+            // Count from when the beat fell rather than when it was noticed,
+            // so the indicator keeps time with the click; after a stall,
+            // start afresh.
+            self.last_beat_time += self.beat_duration;
+            if self.last_beat_time.elapsed() >= self.beat_duration {
+                self.last_beat_time = Instant::now();
+            }
+        }
+    }
+}
+
+enum AppView {
+    Timeline,
+    Patch,
+}
+
+struct HaloApp {
+    current_view: AppView,
+    /// Locked show view with editing hidden; see `ui::performance`.
+    performance_mode: bool,
+    performance_prompt: bool,
+    /// The passcode typed to leave performance mode in a locked show.
+    passcode_entry: String,
+    passcode_wrong: bool,
+    /// Enter performance mode on the next frame, for a show that opened
+    /// locked.
+    enter_locked: bool,
+    /// The passcode being set in Show Properties, and again to confirm it.
+    lock_passcode: String,
+    lock_confirm: String,
+    /// Play the show song by song; see `setlist`.
+    setlist_mode: bool,
+    current_song: usize,
+    /// The pad bank the pad grid shows.
+    pad_bank: usize,
+    /// The bank the current song or section last brought up; see
+    /// `pad_bank`.
+    followed_pad_bank: Option<usize>,
+    /// The momentary pad held down on screen.
+    held_pad: Option<usize>,
+    /// The beat whose beat cues last fired; see `beat_cue`.
+    fired_beat: Option<u32>,
+    transport: Transport,
+    /// Set while the rig is held in the safe state; see `panic`.
+    panic: Option<Panic>,
+    /// What the output fades to while stopped, per the preferences.
+    release: Option<Panic>,
+    /// Whether to start playing again on resuming from a panic.
+    resume_running: bool,
+    show_safe_state: bool,
+    elapsed: Duration,
+    /// What the big LED readout shows.
+    readout: Readout,
+    show: Show,
+    log: Log,
+    toasts: Toasts,
+    show_notifications: bool,
+    show_about: bool,
+    update_check: Option<UpdateCheck>,
+    /// What the last update check found: a newer release, if any.
+    update_status: Option<Result<Option<Release>, String>>,
+    show_log: bool,
+    scripting: Scripting,
+    script_target: Option<ScriptTarget>,
+    script_error: Option<String>,
+    macro_recorder: Option<MacroRecorder>,
+    macro_playbacks: Vec<MacroPlayback>,
+    show_macros: bool,
+    show_songs: bool,
+    fan_tool: FanTool,
+    show_fan: bool,
+    crossfade: CrossfadePair,
+    show_crossfade: bool,
+    /// The crossfade fader waiting for a MIDI controller to assign.
+    learn_fader: Option<Fader>,
+    /// Set while learning cue times from GOs; see `learn_timing`.
+    learn_timing: Option<LearnTiming>,
+    show_learn_timing: bool,
+    show_speed_masters: bool,
+    show_group_masters: bool,
+    show_quick_console: bool,
+    /// Intensities set in the quick console, by fixture id, held over the
+    /// cues until released.
+    quick_levels: BTreeMap<usize, u8>,
+    /// Lamp and reset macros running on the rig.
+    fixture_controls: fixture_control::FixtureControls,
+    /// A lamp or reset macro waiting to be confirmed.
+    control_prompt: Option<ui::fixture_controls::ControlPrompt>,
+    /// The channels the last level command selected, or picked in the
+    /// quick console.
+    quick_selection: Vec<usize>,
+    quick_encoder: ui::quick_console::QuickEncoder,
+    show_command_line: bool,
+    command_line: CommandLine,
+    /// Pictures of gobo and colour wheel slots, loaded as pickers need them.
+    slot_images: ui::wheel::SlotImages,
+    show_spectrogram: bool,
+    show_variables: bool,
+    /// The name and value typed for a new show variable.
+    new_variable: (String, String),
+    spectrogram: ui::spectrogram::SpectrogramView,
+    show_art_net: bool,
+    show_sacn: bool,
+    /// Polling for Art-Net nodes, while the Art-Net window asks for it.
+    art_net_discovery: Option<ArtNetDiscovery>,
+    art_net_error: Option<String>,
+    /// The Art-Net port, while network input, discovery or RDM listens on it.
+    art_net_port: Weak<ArtNetPort>,
+    /// How often each Art-Net destination is getting frames, from the engine.
+    art_net_rates: Vec<OutputRate>,
+    /// The machine's network interfaces, refreshed by `check_interfaces`.
+    interfaces: Vec<NetworkInterface>,
+    interfaces_checked: Option<Instant>,
+    /// When the watchdog last restarted the output engine, for the warning
+    /// banner.
+    engine_restarted: Option<Instant>,
+    engine_restarts: u32,
+    show_schedule: bool,
+    /// When the scheduled triggers were last checked; see `schedule`.
+    schedule_checked: Option<chrono::DateTime<chrono::Local>>,
+    show_haze: bool,
+    /// The speed master waiting for a MIDI controller to assign.
+    learn_speed_master: Option<usize>,
+    /// The group master waiting for a MIDI controller to assign.
+    learn_group_master: Option<usize>,
+    /// The master whose DMX wing fader is being learned; see `dmx_fader`.
+    learn_dmx_fader: Option<DmxLearn>,
+    /// What each DMX wing fader last read, to follow only its changes.
+    dmx_fader_values: HashMap<DmxFader, u8>,
+    /// Scales every fixture's intensity, from 0 to 1. Full at every launch.
+    grand_master: f32,
+    /// Scales every flash pad, from 0 to 1.
+    flash_master: f32,
+    /// Holds every effect where it is; see `speed_master`.
+    effects_frozen: bool,
+    show_busk: bool,
+    /// Generated looks are playing over the cues; see `busk`.
+    busk_enabled: bool,
+    macro_name: String,
+    preferences: Preferences,
+    show_preferences: bool,
+    /// The Preferences section to scroll to when it next opens.
+    preferences_focus: Option<PreferencesSection>,
+    remote_sender: Sender<RemoteMessage>,
+    remote_requests: Receiver<RemoteMessage>,
+    /// Analysis of the audio input, read by audio-reactive pixel maps.
+    audio_meter: AudioMeter,
+    audio_input: Option<AudioInput>,
+    audio_error: Option<String>,
+    /// The metronome click; see `click`.
+    click_clock: ClickClock,
+    click_output: Option<ClickOutput>,
+    click_error: Option<String>,
+    /// The show audio on its outputs; see `playback`.
+    playback: Playback,
+    playback_clock: PlaybackClock,
+    playback_error: Option<String>,
+    /// Clock sharing with a backup or main; see `clock_sync`.
+    clock_server: Option<ClockServer>,
+    clock_client: Option<ClockClient>,
+    clock_sync_error: Option<String>,
+    /// How far ahead of the main this backup's playhead was last measured, in
+    /// seconds.
+    clock_offset: Option<f64>,
+    clock_corrections: u32,
+    backup_state: BackupState,
+    /// When the show was last passed on to tracking backups.
+    show_published: Instant,
+    /// The plugins found at startup or on the last reload.
+    plugins: Vec<PluginSource>,
+    /// Plugin files that couldn't be read.
+    plugin_read_errors: Vec<(PathBuf, String)>,
+    /// The plugins compiled for the UI's previews.
+    plugin_host: PluginHost,
+    /// Errors the engine's plugins raised, by name.
+    plugin_errors: Vec<(String, String)>,
+    show_plugins: bool,
+    show_smart_bulbs: bool,
+    show_effect_presets: bool,
+    show_merge_window: bool,
+    command_palette: CommandPalette,
+    show_shortcut_help: bool,
+    show_merge: ShowMerge,
+    show_diff_window: bool,
+    show_diff: ShowDiff,
+    /// Moving a cue takes the later cues with it; see `ripple`.
+    ripple_edit: bool,
+    effect_presets: EffectPresetTool,
+    /// Why a pixel map couldn't be sent over the network, from the engine.
+    pixel_stream_error: Option<String>,
+    /// The smart bulb bridge's last failure, from the engine.
+    smart_bulb_error: Option<String>,
+    /// The last failure sending a driven fixture, from the engine.
+    driver_error: Option<String>,
+    /// Waiting for the Hue bridge to answer a pairing request.
+    hue_pairing: Option<Receiver<Result<String, String>>>,
+    /// Latest Art-Net and sACN received, merged into the output by the engine.
+    dmx_input: DmxInput,
+    network_input: Option<NetworkInput>,
+    network_input_error: Option<String>,
+    /// Talking RDM to a gateway, while switched on in the patch view.
+    rdm: Option<Rdm>,
+    rdm_error: Option<String>,
+    midi_in: Option<MidiIn>,
+    midi_out: Option<MidiOut>,
+    midi_error: Option<String>,
+    midi_sender: Sender<MidiEvent>,
+    midi_events: Receiver<MidiEvent>,
+    /// Watches for MIDI ports being unplugged and plugged back in.
+    device_watcher: Option<DeviceWatcher>,
+    /// Failures reported by cue triggers sent off the UI thread.
+    trigger_sender: Sender<String>,
+    trigger_results: Receiver<String>,
+    osc_input: Option<OscListener>,
+    osc_error: Option<String>,
+    /// Visualisers sharing the patch and selection.
+    citp: Option<CitpServer>,
+    citp_error: Option<String>,
+    /// PSN tracker positions, shared with the engine.
+    trackers: Trackers,
+    psn: Option<PsnListener>,
+    psn_error: Option<String>,
+    show_follow_spots: bool,
+    /// OSC messages received, and where from.
+    osc_sender: Sender<(SocketAddr, OscMessage)>,
+    osc_messages: Receiver<(SocketAddr, OscMessage)>,
+    hid: Option<HidListener>,
+    hid_error: Option<String>,
+    /// Keys pressed on the USB button device.
+    hid_sender: Sender<u16>,
+    hid_keys: Receiver<u16>,
+    show_bindings: bool,
+    /// The last timecode received, and when.
+    timecode_in: Option<(Timecode, Instant)>,
+    show_timecode: bool,
+    /// The binding whose source is set by the next message received.
+    learn_binding: Option<usize>,
+    /// MIDI messages received and sent, for the MIDI monitor.
+    midi_monitor: MidiMonitor,
+    show_midi_monitor: bool,
+    midi_monitor_filter: MonitorFilter,
+    osc_monitor: OscMonitor,
+    packet_inspector: PacketInspector,
+    show_packet_inspector: bool,
+    inspector_view: InspectorView,
+    show_osc_monitor: bool,
+    osc_monitor_tab: OscMonitorTab,
+    show_tracksheet: bool,
+    /// The fixture the tracksheet shows, by id, or None for all of them.
+    tracksheet_fixture: Option<usize>,
+    tracksheet_edit: Option<CellEdit>,
+    /// Whether edits to cue values track on; see `tracking`.
+    edit_mode: EditMode,
+    /// The latest messages bindings were offered, newest last.
+    received_inputs: VecDeque<ReceivedInput>,
+    remote: Option<RemoteServer>,
+    /// Designer views that have polled this Halo; see `follow`.
+    followers: Vec<Follower>,
+    /// Set while this Halo follows another as a designer view.
+    follow_client: Option<FollowClient>,
+    show_designer_view: bool,
+    remote_error: Option<String>,
+    http: Option<RemoteServer>,
+    http_error: Option<String>,
+    show_path: Option<PathBuf>,
+    /// The show as last saved or loaded, to spot unsaved changes.
+    saved_show: String,
+    /// The format of the show just opened, when a newer Halo saved it.
+    newer_show: Option<u32>,
+    /// Failures waiting for the operator to deal with, oldest first.
+    errors: VecDeque<HaloError>,
+    /// When to start playing, as the startup preferences ask.
+    startup_playback: Option<Instant>,
+    /// Keeping the display on, and when to dim to the clock.
+    awake: awake::Awake,
+    /// Asking whether to save before quitting.
+    quit_prompt: bool,
+    /// Quit once the save the quit prompt started has gone through.
+    quit_after_save: bool,
+    /// Quit has been confirmed, so the next close request goes through.
+    quitting: bool,
+    /// When to close, once the output has faded out for quit.
+    quit_at: Option<Instant>,
+    file_dialog: Option<FileDialog>,
+    show_properties: bool,
+    show_backups: bool,
+    profile_library: Vec<FixtureProfile>,
+    new_fixture_profile: usize,
+    show_profile_browser: bool,
+    profile_browser: ProfileBrowser,
+    patch_tools: PatchTools,
+    patch_import: PatchImport,
+    show_patch_import: bool,
+    /// The venue mapping output goes through, if one is loaded.
+    venue: Option<VenueMap>,
+    show_venue: bool,
+    venue_tool: VenueTool,
+    show_daw: bool,
+    daw_tool: DawTool,
+    new_fixture_name: String,
+    new_fixture_universe: u16,
+    new_fixture_address: u16,
+    selected_cue: Option<usize>,
+    cue_filter: String,
+    show_renumber: bool,
+    show_fixture_replace: bool,
+    fixture_replace: ui::fixture_replace::FixtureReplaceTool,
+    /// Cues copied with everything they store, for pasting.
+    cue_clipboard: Vec<Cue>,
+    show_paste_cues: bool,
+    /// Seconds added to the start of pasted cues.
+    paste_offset: f32,
+    renumber_start: f32,
+    renumber_interval: f32,
+    show_cue_editor: bool,
+    show_handoff: bool,
+    handoff_path: String,
+    handoff_status: Option<Result<String, String>>,
+    show_report: bool,
+    report_format: ReportFormat,
+    report_path: String,
+    report_status: Option<Result<String, String>>,
+    /// Run time, saves and cue fires this session; see `session`.
+    session: Session,
+    show_session: bool,
+    session_report_format: ReportFormat,
+    session_report_path: String,
+    session_report_status: Option<Result<String, String>>,
+    engine: Engine,
+    output: DmxFrame,
+    /// The output before group masters and processing; see `EngineSnapshot`.
+    programmed_output: DmxFrame,
+    /// What set each channel of `output`, from the engine.
+    output_owners: Owners,
+    /// Something on screen this frame shows `output_owners`, so the engine
+    /// should work them out.
+    owners_shown: Cell<bool>,
+    show_output_monitor: bool,
+    monitor_universe: u16,
+    /// The channel clicked in the output monitor, to show what set it.
+    monitor_channel: Option<u16>,
+    channel_check: Option<ChannelCheck>,
+    test_pattern: Option<TestPattern>,
+    /// Universes kept off the output, in order.
+    muted_universes: Vec<u16>,
+    show_visualizer: bool,
+    detached: Vec<Panel>,
+    launched: Instant,
+    show_pixel_maps: bool,
+    selected_pixel_map: Option<usize>,
+    pixel_media_error: Option<String>,
+    show_snapshots: bool,
+    show_recording: bool,
+    show_power: bool,
+    show_stage_display_settings: bool,
+    show_clocks: bool,
+    stage_display: StageDisplay,
+    output_recording: OutputRecording,
+    snapshot_name: String,
+    diff_before: Option<usize>,
+    diff_after: Option<usize>,
+    link_enabled: bool,
+    bpm: f32,
+    ui_frames: FrameTimer,
+    output_frames: FrameTimer,
+    show_frame_graph: bool,
+    /// How long each stage of the output frame is taking.
+    stage_times: Vec<StageTime>,
+    show_frame_timings: bool,
+    beat_indicator: BeatIndicator,
+}
+
+impl Default for HaloApp {
+    fn default() -> Self {
+        let (remote_sender, remote_requests) = mpsc::channel();
+        let (midi_sender, midi_events) = mpsc::channel();
+        let (trigger_sender, trigger_results) = mpsc::channel();
+        let (osc_sender, osc_messages) = mpsc::channel();
+        let (hid_sender, hid_keys) = mpsc::channel();
+        let launched = Instant::now();
+        let audio_meter = AudioMeter::default();
+        let dmx_input = DmxInput::default();
+        let trackers = Trackers::default();
+        Self {
+            current_view: AppView::Timeline,
+            performance_mode: false,
+            performance_prompt: false,
+            passcode_entry: String::new(),
+            passcode_wrong: false,
+            enter_locked: false,
+            lock_passcode: String::new(),
+            lock_confirm: String::new(),
+            setlist_mode: false,
+            current_song: 0,
+            pad_bank: 0,
+            followed_pad_bank: None,
+            held_pad: None,
+            fired_beat: None,
+            transport: Transport::Stopped,
+            panic: None,
+            release: None,
+            resume_running: false,
+            show_safe_state: false,
+            elapsed: Duration::from_secs(0),
+            readout: Readout::default(),
+            show: Show::default(),
+            log: Log::default(),
+            toasts: Toasts::default(),
+            show_notifications: false,
+            show_about: false,
+            update_check: None,
+            update_status: None,
+            show_log: false,
+            scripting: Scripting::new(),
+            script_target: None,
+            script_error: None,
+            macro_recorder: None,
+            macro_playbacks: Vec::new(),
+            show_macros: false,
+            show_songs: false,
+            fan_tool: FanTool::default(),
+            show_fan: false,
+            crossfade: CrossfadePair::new(),
+            show_crossfade: false,
+            learn_fader: None,
+            learn_timing: None,
+            show_learn_timing: false,
+            show_speed_masters: false,
+            show_group_masters: false,
+            show_quick_console: false,
+            quick_levels: BTreeMap::new(),
+            fixture_controls: Default::default(),
+            control_prompt: None,
+            quick_selection: Vec::new(),
+            quick_encoder: Default::default(),
+            show_command_line: false,
+            command_line: CommandLine::default(),
+            slot_images: Default::default(),
+            show_spectrogram: false,
+            show_variables: false,
+            new_variable: Default::default(),
+            spectrogram: Default::default(),
+            show_art_net: false,
+            show_sacn: false,
+            art_net_discovery: None,
+            art_net_error: None,
+            art_net_port: Weak::new(),
+            art_net_rates: Vec::new(),
+            interfaces: interfaces::list(),
+            interfaces_checked: None,
+            engine_restarted: None,
+            engine_restarts: 0,
+            show_schedule: false,
+            schedule_checked: None,
+            show_haze: false,
+            learn_speed_master: None,
+            learn_group_master: None,
+            learn_dmx_fader: None,
+            dmx_fader_values: HashMap::new(),
+            grand_master: 1.0,
+            flash_master: 1.0,
+            effects_frozen: false,
+            show_busk: false,
+            busk_enabled: false,
+            macro_name: String::new(),
+            preferences: Preferences::load(),
+            show_preferences: false,
+            preferences_focus: None,
+            remote_sender,
+            remote_requests,
+            engine: Engine::start(
+                launched,
+                audio_meter.clone(),
+                dmx_input.clone(),
+                trackers.clone(),
+            ),
+            audio_meter,
+            audio_input: None,
+            audio_error: None,
+            click_clock: ClickClock::default(),
+            click_output: None,
+            click_error: None,
+            playback: Playback::default(),
+            playback_clock: PlaybackClock::default(),
+            playback_error: None,
+            clock_server: None,
+            clock_client: None,
+            clock_sync_error: None,
+            clock_offset: None,
+            clock_corrections: 0,
+            backup_state: BackupState::Waiting,
+            show_published: Instant::now(),
+            plugins: Vec::new(),
+            plugin_read_errors: Vec::new(),
+            plugin_host: PluginHost::default(),
+            plugin_errors: Vec::new(),
+            show_plugins: false,
+            show_smart_bulbs: false,
+            show_effect_presets: false,
+            show_merge_window: false,
+            command_palette: CommandPalette::default(),
+            show_shortcut_help: false,
+            show_merge: ShowMerge::default(),
+            show_diff_window: false,
+            show_diff: ShowDiff::default(),
+            ripple_edit: false,
+            effect_presets: EffectPresetTool::default(),
+            pixel_stream_error: None,
+            smart_bulb_error: None,
+            driver_error: None,
+            hue_pairing: None,
+            dmx_input,
+            network_input: None,
+            network_input_error: None,
+            rdm: None,
+            rdm_error: None,
+            midi_in: None,
+            midi_out: None,
+            midi_error: None,
+            midi_sender,
+            midi_events,
+            device_watcher: None,
+            trigger_sender,
+            trigger_results,
+            osc_input: None,
+            osc_error: None,
+            citp: None,
+            citp_error: None,
+            trackers,
+            psn: None,
+            psn_error: None,
+            show_follow_spots: false,
+            osc_sender,
+            osc_messages,
+            hid: None,
+            hid_error: None,
+            hid_sender,
+            hid_keys,
+            show_bindings: false,
+            timecode_in: None,
+            show_timecode: false,
+            learn_binding: None,
+            midi_monitor: MidiMonitor::default(),
+            show_midi_monitor: false,
+            midi_monitor_filter: MonitorFilter::default(),
+            osc_monitor: OscMonitor::default(),
+            packet_inspector: PacketInspector::default(),
+            show_packet_inspector: false,
+            inspector_view: InspectorView::default(),
+            show_osc_monitor: false,
+            osc_monitor_tab: OscMonitorTab::default(),
+            show_tracksheet: false,
+            tracksheet_fixture: None,
+            tracksheet_edit: None,
+            edit_mode: EditMode::default(),
+            received_inputs: VecDeque::new(),
+            remote: None,
+            followers: Vec::new(),
+            follow_client: None,
+            show_designer_view: false,
+            remote_error: None,
+            http: None,
+            http_error: None,
+            show_path: None,
+            saved_show: Show::default().snapshot(),
+            newer_show: None,
+            errors: VecDeque::new(),
+            startup_playback: None,
+            awake: Default::default(),
+            quit_prompt: false,
+            quit_after_save: false,
+            quitting: false,
+            quit_at: None,
+            file_dialog: None,
+            show_properties: false,
+            show_backups: false,
+            profile_library: fixture::profile_library(),
+            new_fixture_profile: 0,
+            show_profile_browser: false,
+            profile_browser: ProfileBrowser::default(),
+            patch_tools: PatchTools::default(),
+            patch_import: PatchImport::default(),
+            show_patch_import: false,
+            venue: None,
+            show_venue: false,
+            venue_tool: VenueTool::default(),
+            show_daw: false,
+            daw_tool: DawTool::default(),
+            new_fixture_name: String::new(),
+            new_fixture_universe: 1,
+            new_fixture_address: 1,
+            selected_cue: None,
+            cue_filter: String::new(),
+            show_renumber: false,
+            show_fixture_replace: false,
+            fixture_replace: Default::default(),
+            cue_clipboard: Vec::new(),
+            show_paste_cues: false,
+            paste_offset: 0.0,
+            renumber_start: 1.0,
+            renumber_interval: 1.0,
+            show_cue_editor: false,
+            show_handoff: false,
+            handoff_path: "handoff.html".to_string(),
+            handoff_status: None,
+            show_report: false,
+            report_format: ReportFormat::Markdown,
+            report_path: "show-report.md".to_string(),
+            report_status: None,
+            session: Session::default(),
+            show_session: false,
+            session_report_format: ReportFormat::Markdown,
+            session_report_path: "post-show-report.md".to_string(),
+            session_report_status: None,
+            output: DmxFrame::default(),
+            programmed_output: DmxFrame::default(),
+            output_owners: Owners::default(),
+            owners_shown: Cell::new(false),
+            show_output_monitor: false,
+            monitor_universe: 1,
+            monitor_channel: None,
+            channel_check: None,
+            test_pattern: None,
+            muted_universes: Vec::new(),
+            show_visualizer: false,
+            detached: Vec::new(),
+            launched,
+            show_pixel_maps: false,
+            selected_pixel_map: None,
+            pixel_media_error: None,
+            show_snapshots: false,
+            show_recording: false,
+            show_power: false,
+            show_stage_display_settings: false,
+            show_clocks: false,
+            stage_display: StageDisplay::default(),
+            output_recording: OutputRecording::default(),
+            snapshot_name: String::new(),
+            diff_before: None,
+            diff_after: None,
+            link_enabled: false,
+            bpm: 120.0,
+            ui_frames: FrameTimer::new(None),
+            output_frames: FrameTimer::new(Some(dmx::OUTPUT_HZ)),
+            show_frame_graph: false,
+            stage_times: Vec::new(),
+            show_frame_timings: false,
+            beat_indicator: BeatIndicator::new(),
+        }
+    }
+}
+
+impl HaloApp {
+    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+        // Load custom fonts
+        let mut fonts = egui::FontDefinitions::default();
+
+        // Add the LED font
+        fonts.font_data.insert(
+            "matrix".to_owned(),
+            std::sync::Arc::new(egui::FontData::from_static(include_bytes!(
+                "../assets/digital-7-mono.ttf"
+            ))),
+        );
+
+        fonts.families.insert(
+            egui::FontFamily::Name("matrix".into()),
+            vec!["matrix".into()],
+        );
+
+        _cc.egui_ctx.set_fonts(fonts);
+
+        let mut app = Self::default();
+        app.apply_remote_preferences(&_cc.egui_ctx);
+        app.connect_startup_midi();
+        app.apply_midi_preferences(&_cc.egui_ctx);
+        app.device_watcher = Some(DeviceWatcher::start(_cc.egui_ctx.clone()));
+        app.apply_osc_preferences(&_cc.egui_ctx);
+        app.apply_citp_preferences(&_cc.egui_ctx);
+        app.apply_psn_preferences(&_cc.egui_ctx);
+        app.apply_hid_preferences(&_cc.egui_ctx);
+        app.apply_audio_preferences();
+        app.apply_click_preferences();
+        app.apply_playback_preferences();
+        app.apply_venue_preferences();
+        app.venue_tool = VenueTool::new(app.preferences.venue_file.as_ref());
+        app.apply_clock_sync_preferences(&_cc.egui_ctx);
+        app.load_plugins();
+        app.apply_network_input_preferences();
+        app.apply_startup_preferences();
+        i18n::set_language(app.preferences.language);
+        app.apply_theme(&_cc.egui_ctx);
+        app.apply_ui_scale(&_cc.egui_ctx);
+        if app.preferences.check_for_updates {
+            app.check_for_updates(&_cc.egui_ctx);
+        }
+        app
+    }
+
+    fn format_duration(duration: Duration) -> String {
+        let total_secs = duration.as_secs();
+        let minutes = total_secs / 60;
+        let seconds = total_secs % 60;
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+
+    /// A cell per beat of the bar, with the current one lit, followed by the
+    /// bar and beat.
+    fn draw_beat_indicator(&mut self, ui: &mut egui::Ui) {
+        let size = 24.0;
+        let spacing = 2.0;
+        let cell = egui::vec2(10.0, size - spacing * 2.0);
+        let signature = self.show.time_signature;
+        let beats = signature.beats_per_bar();
+        let (bar, beat) = signature.bar_beat(self.beat_indicator.beat);
+
+        let width = spacing + beats as f32 * (cell.x + spacing);
+        let (_, rect) = ui.allocate_space(egui::vec2(width, size));
+        let painter = ui.painter();
+        let led = self.preferences.led_color;
+
+        // Draw outer frame
+        painter.rect_stroke(
+            rect,
+            0.0,
+            egui::Stroke::new(1.0, led.lit()),
+            egui::StrokeKind::Outside,
+        );
+
+        // Draw a cell per beat
+        for i in 0..beats {
+            let pos = rect.min + egui::vec2(spacing + i as f32 * (cell.x + spacing), spacing);
+            let color = match i + 1 {
+                lit if lit != beat => led.unlit(),
+                1 if self.preferences.accent_downbeat => {
+                    led.lit().lerp_to_gamma(egui::Color32::WHITE, 0.6)
+                }
+                _ => led.lit(),
+            };
+            painter.rect_filled(egui::Rect::from_min_size(pos, cell), 0.0, color);
+        }
+        ui.label(
+            egui::RichText::new(format!("{}.{}", bar, beat))
+                .monospace()
+                .color(led.lit()),
+        );
+    }
+
+    fn reset(&mut self) {
+        self.beat_indicator = BeatIndicator::new();
+        self.fired_beat = None;
+        if self.transport.is_running() && !self.follows_midi_clock() {
+            self.click_clock.sync(0);
+        }
+        self.release = None;
+        if self.setlist_mode && !self.show.songs.is_empty() {
+            self.go_to_song(0);
+            return;
+        }
+        self.locate(Duration::ZERO);
+        // Reset all cues
+        for cue in &mut self.show.cues {
+            cue.is_playing = false;
+            cue.progress = 0.0;
+            cue.skipped = false;
+        }
+    }
+
+    /// Moves the playhead to the start of the cue at `index`.
+    fn go_to_cue(&mut self, index: usize) {
+        let Some(cue) = self.show.cues.get(index) else {
+            return;
+        };
+        if let Some(song) = cue
+            .song
+            .and_then(|id| self.show.songs.iter().position(|song| song.id == id))
+        {
+            self.current_song = song;
+        }
+        self.locate(self.show.cue_start(cue));
+        self.release = None;
+        for i in 0..self.show.cues.len() {
+            if self.show.cue_start(&self.show.cues[i]) >= self.elapsed {
+                self.show.cues[i].skipped = false;
+            }
+        }
+    }
+
+    /// The most recently started armed cue at the current playhead position.
+    fn current_cue(&self) -> Option<&Cue> {
+        self.show
+            .cues
+            .iter()
+            .filter(|cue| self.show.plays(cue) && self.show.cue_start(cue) <= self.elapsed)
+            .max_by_key(|cue| self.show.cue_start(cue))
+    }
+
+    /// The armed cue before the current one, by index.
+    fn previous_cue(&self) -> Option<usize> {
+        let current = self.show.cue_start(self.current_cue()?);
+        let previous = self
+            .show
+            .cues
+            .iter()
+            .filter(|cue| self.show.plays(cue) && self.show.cue_start(cue) < current)
+            .max_by_key(|cue| self.show.cue_start(cue))?;
+        self.cue_index(previous)
+    }
+
+    /// The next armed cue the playhead will reach.
+    fn next_cue(&self) -> Option<&Cue> {
+        self.show
+            .cues
+            .iter()
+            .filter(|cue| self.show.plays(cue) && self.show.cue_start(cue) > self.elapsed)
+            .min_by_key(|cue| self.show.cue_start(cue))
+    }
+
+    fn pixel_clock(&self) -> PixelClock {
+        PixelClock::since(
+            self.launched,
+            self.bpm,
+            self.show.time_signature.beats_per_bar(),
+        )
+        .with_audio(self.audio_meter.levels())
+    }
+
+    /// Decodes the media of every pixel map that plays some, logging the
+    /// ones that fail.
+    fn load_pixel_media(&mut self) {
+        let clock = self.pixel_clock();
+        let mut failures = Vec::new();
+        for map in &mut self.show.pixel_maps {
+            if map.media.is_none() {
+                continue;
+            }
+            if let Err(err) = map.load_media(clock) {
+                failures.push(tr!(
+                    "pixel-media-failed",
+                    name = map.name.as_str(),
+                    error = err.to_string()
+                ));
+            }
+        }
+        for failure in failures {
+            self.notify(LogLevel::Error, failure);
+        }
+    }
+
+    fn toggle_view(&mut self) {
+        self.current_view = match self.current_view {
+            AppView::Timeline => AppView::Patch,
+            AppView::Patch => AppView::Timeline,
+        };
+    }
+
+    /// Handles the keys listed in `shortcuts::SHORTCUTS`, unless a text field
+    /// has keyboard focus.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let pressed = |key| ctx.input(|i| i.key_pressed(key));
+        // Panic works even while typing.
+        if pressed(egui::Key::F12) {
+            self.engage_panic();
+        }
+        // Taken first so K alone doesn't also pause.
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K)) {
+            self.toggle_command_palette();
+        }
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        if pressed(egui::Key::Questionmark) {
+            self.show_shortcut_help = !self.show_shortcut_help;
+        }
+        // With a control focused from the keyboard, Space presses it
+        // instead; Escape lets go of the focus.
+        let focused = ctx.memory(|memory| memory.focused().is_some());
+        if pressed(egui::Key::Space) && !focused {
+            self.perform(if self.rolling() {
+                MacroAction::Stop
+            } else {
+                MacroAction::Start
+            });
+        }
+        if pressed(egui::Key::K) {
+            self.toggle_pause();
+        }
+        if pressed(egui::Key::Backspace) && !self.performance_mode {
+            self.perform(MacroAction::Reset);
+        }
+        if pressed(egui::Key::T) {
+            self.cycle_readout();
+        }
+        if pressed(egui::Key::P) && !self.performance_mode {
+            self.toggle_view();
+        }
+        if pressed(egui::Key::N) && self.setlist_mode {
+            self.perform(MacroAction::NextSong);
+        }
+        if pressed(egui::Key::B) {
+            self.perform(MacroAction::Back);
+        }
+        if pressed(egui::Key::L) {
+            self.performance_prompt = true;
+        }
+        if pressed(egui::Key::G) && self.learn_timing.is_some() {
+            self.learn_go();
+        }
+        if !self.performance_mode && !self.quick_selection.is_empty() {
+            let fine = ctx.input(|i| i.modifiers.shift);
+            let step = if fine { 1.0 } else { 5.0 };
+            if pressed(egui::Key::PageUp) {
+                self.nudge_quick_selection(step);
+            }
+            if pressed(egui::Key::PageDown) {
+                self.nudge_quick_selection(-step);
+            }
+        }
+        if !self.performance_mode {
+            let frame = self.nudge_frame();
+            for (key, seconds) in [
+                (egui::Key::OpenBracket, -1.0),
+                (egui::Key::CloseBracket, 1.0),
+                (egui::Key::Comma, -frame),
+                (egui::Key::Period, frame),
+            ] {
+                if pressed(key) {
+                    self.nudge_selected_cue(seconds);
+                }
+            }
+        }
+    }
+}
+
+impl eframe::App for HaloApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.ui_frames.tick(Instant::now());
+        self.check_engine();
+        self.check_interfaces(ctx);
+        self.receive_engine_output();
+        self.update_awake(ctx);
+        self.handle_shortcuts(ctx);
+        self.handle_remote_requests();
+        self.handle_device_changes(ctx);
+        self.handle_midi_events();
+        self.update_click();
+        self.update_beat_cues();
+        self.update_pad_bank();
+        self.update_pads(ctx);
+        self.update_dmx_faders(ctx);
+        self.update_citp();
+        self.update_playback(ctx);
+        self.check_timecode_dropout();
+        self.update_clock_sync();
+        self.update_preroll();
+        self.update_startup(ctx);
+        self.update_fixture_controls(ctx);
+        self.remember_show_path();
+        self.update_session();
+        self.handle_osc_messages();
+        self.handle_hid_keys();
+        self.handle_trigger_results();
+        self.update_macros();
+        self.update_crossfade();
+        self.update_schedule(ctx);
+        self.hold_learn_timing();
+        self.handle_update_check();
+        self.handle_quit(ctx);
+        if std::mem::take(&mut self.enter_locked) && !self.performance_mode {
+            self.set_performance_mode(ctx, true);
+        }
+
+        if !self.performance_mode {
+            egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+                egui::menu::bar(ui, |ui| {
+                    ui.menu_button(tr!("menu-halo"), |ui| {
+                        if ui.button(tr!("menu-about")).clicked() {
+                            self.show_about = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-load-show")).clicked() {
+                            self.file_dialog =
+                                Some(FileDialog::new(FileAction::Load, self.show_path.as_deref()));
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-save-show")).clicked() {
+                            self.save_show();
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-save-show-as")).clicked() {
+                            self.file_dialog =
+                                Some(FileDialog::new(FileAction::Save, self.show_path.as_deref()));
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-save-package")).clicked() {
+                            self.file_dialog = Some(FileDialog::new(
+                                FileAction::SavePackage,
+                                self.show_path.as_deref(),
+                            ));
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-merge")).clicked() {
+                            self.show_merge_window = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-compare-shows")).clicked() {
+                            self.show_diff_window = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.show_path.is_some(),
+                                egui::Button::new(tr!("menu-revert")),
+                            )
+                            .clicked()
+                        {
+                            self.show_backups = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-preferences")).clicked() {
+                            self.show_preferences = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-properties")).clicked() {
+                            self.show_properties = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-handoff")).clicked() {
+                            self.show_handoff = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-report")).clicked() {
+                            self.show_report = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-session")).clicked() {
+                            self.show_session = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-quit")).clicked() {
+                            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+                            ui.close_menu();
+                        }
+                    });
+                    ui.menu_button(tr!("menu-cues"), |ui| {
+                        if ui
+                            .add_enabled(
+                                self.selected_cue.is_some(),
+                                egui::Button::new(tr!("menu-insert-point-cue")),
+                            )
+                            .clicked()
+                        {
+                            if let Some(index) = self.selected_cue {
+                                if let Some(new_index) =
+                                    cue::insert_point_cue(&mut self.show.cues, index)
+                                {
+                                    self.selected_cue = Some(new_index);
+                                }
+                            }
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.selected_cue.is_some(),
+                                egui::Button::new(tr!("menu-duplicate-at-playhead")),
+                            )
+                            .clicked()
+                        {
+                            self.duplicate_cue_at_playhead();
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        if ui
+                            .add_enabled(
+                                self.selected_cue.is_some(),
+                                egui::Button::new(tr!("menu-copy-cue")),
+                            )
+                            .clicked()
+                        {
+                            self.copy_cues(false);
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.selected_cue.is_some(),
+                                egui::Button::new(tr!("menu-copy-section")),
+                            )
+                            .on_hover_text(tr!("menu-copy-section-hint"))
+                            .clicked()
+                        {
+                            self.copy_cues(true);
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add_enabled(
+                                !self.cue_clipboard.is_empty(),
+                                egui::Button::new(tr!("menu-paste-cues")),
+                            )
+                            .clicked()
+                        {
+                            self.show_paste_cues = true;
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        if ui
+                            .add_enabled(
+                                self.selected_cue.is_some(),
+                                egui::Button::new(tr!("menu-edit-cue")),
+                            )
+                            .clicked()
+                        {
+                            self.show_cue_editor = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.selected_cue.is_some(),
+                                egui::Button::new(tr!("menu-edit-script")),
+                            )
+                            .clicked()
+                        {
+                            self.script_target = self.selected_cue.map(ScriptTarget::Cue);
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-fan")).clicked() {
+                            self.show_fan = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-renumber")).clicked() {
+                            self.show_renumber = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-fixture-replace")).clicked() {
+                            self.show_fixture_replace = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-songs")).clicked() {
+                            self.show_songs = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-crossfade")).clicked() {
+                            self.show_crossfade = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-variables")).clicked() {
+                            self.show_variables = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-spectrogram")).clicked() {
+                            self.show_spectrogram = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-learn-timing")).clicked() {
+                            self.show_learn_timing = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-tracksheet")).clicked() {
+                            self.show_tracksheet = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-follow-spots")).clicked() {
+                            self.show_follow_spots = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-daw")).clicked() {
+                            self.show_daw = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-schedule")).clicked() {
+                            self.show_schedule = true;
+                            ui.close_menu();
+                        }
+                    });
+                    ui.menu_button(tr!("menu-view"), |ui| {
+                        if ui.button(tr!("menu-performance-mode")).clicked() {
+                            self.performance_prompt = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-busk")).clicked() {
+                            self.show_busk = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-designer-view")).clicked() {
+                            self.show_designer_view = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-visualizer")).clicked() {
+                            self.show_visualizer = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-quick-console")).clicked() {
+                            self.show_quick_console = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-command-line")).clicked() {
+                            self.show_command_line = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-command-palette")).clicked() {
+                            self.command_palette.open = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-shortcuts")).clicked() {
+                            self.show_shortcut_help = true;
+                            ui.close_menu();
+                        }
+                        let cue_list_detached = self.is_detached(Panel::CueList);
+                        if ui
+                            .button(if cue_list_detached {
+                                tr!("menu-dock-cue-list")
+                            } else {
+                                tr!("menu-detach-cue-list")
+                            })
+                            .clicked()
+                        {
+                            self.set_detached(Panel::CueList, !cue_list_detached);
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-macros")).clicked() {
+                            self.show_macros = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-bindings")).clicked() {
+                            self.show_bindings = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-midi-monitor")).clicked() {
+                            self.show_midi_monitor = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-osc-monitor")).clicked() {
+                            self.show_osc_monitor = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-packet-inspector")).clicked() {
+                            self.show_packet_inspector = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-log")).clicked() {
+                            self.show_log = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-frame-timings")).clicked() {
+                            self.show_frame_timings = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-clocks")).clicked() {
+                            self.show_clocks = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-stage-display")).clicked() {
+                            self.show_stage_display_settings = true;
+                            ui.close_menu();
+                        }
+                    });
+                    ui.menu_button(tr!("menu-output"), |ui| {
+                        if ui.button(tr!("menu-output-monitor")).clicked() {
+                            self.show_output_monitor = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-art-net")).clicked() {
+                            self.show_art_net = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-sacn")).clicked() {
+                            self.show_sacn = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-pixel-maps")).clicked() {
+                            self.show_pixel_maps = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-effect-presets")).clicked() {
+                            self.show_effect_presets = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-smart-bulbs")).clicked() {
+                            self.show_smart_bulbs = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-plugins")).clicked() {
+                            self.show_plugins = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-speed-masters")).clicked() {
+                            self.show_speed_masters = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-group-masters")).clicked() {
+                            self.show_group_masters = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-haze")).clicked() {
+                            self.show_haze = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-snapshots")).clicked() {
+                            self.show_snapshots = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-safe-state")).clicked() {
+                            self.show_safe_state = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-recording")).clicked() {
+                            self.show_recording = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr!("menu-power")).clicked() {
+                            self.show_power = true;
+                            ui.close_menu();
+                        }
+                    });
+                });
+            });
+        }
+
+        self.draw_engine_warning(ctx);
+        egui::TopBottomPanel::top("header").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                self.draw_beat_indicator(ui);
+                ui.add_space(8.0);
+
+                if ui
+                    .button(if self.link_enabled {
+                        "Link ●"
+                    } else {
+                        "Link ○"
+                    })
+                    .clicked()
+                {
+                    self.link_enabled = !self.link_enabled;
+                    // Here you would add the actual Ableton Link connection logic
+                }
+                ui.add_space(8.0);
+                ui.label(tr!("header-bpm"));
+                let mut bpm = self.bpm;
+                if ui
+                    .add_enabled(
+                        !self.performance_mode && !self.follows_midi_clock(),
+                        egui::DragValue::new(&mut bpm)
+                            .speed(0.1)
+                            .range(20.0..=300.0)
+                            .fixed_decimals(1),
+                    )
+                    .changed()
+                {
+                    self.perform(MacroAction::SetBpm(bpm));
+                }
+                ui.add_enabled_ui(!self.performance_mode, |ui| {
+                    egui::ComboBox::from_id_salt("time_signature")
+                        .width(56.0)
+                        .selected_text(self.show.time_signature.to_string())
+                        .show_ui(ui, |ui| {
+                            for signature in TimeSignature::PRESETS {
+                                ui.selectable_value(
+                                    &mut self.show.time_signature,
+                                    signature,
+                                    signature.to_string(),
+                                );
+                            }
+                        })
+                });
+                ui.add_space(8.0);
+                self.draw_setlist_controls(ui);
+
+                // Right side elements
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    self.draw_panic_button(ui);
+                    ui.toggle_value(&mut self.effects_frozen, tr!("header-freeze"))
+                        .on_hover_text(tr!("header-freeze-hint"));
+                    if ui
+                        .button(tr!("header-record-look"))
+                        .on_hover_text(tr!("header-record-look-hint"))
+                        .clicked()
+                    {
+                        self.record_look();
+                    }
+                    if self.performance_mode {
+                        if ui.button(tr!("header-leave-performance")).clicked() {
+                            self.performance_prompt = true;
+                        }
+                    } else if ui.button(tr!("header-patch")).clicked() {
+                        self.toggle_view();
+                    }
+                });
+            });
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if self.performance_mode {
+                self.draw_performance_view(ui);
+                return;
+            }
+            match self.current_view {
+                AppView::Timeline => {
+                    // Use large text for the timecode display
+                    let font_id = egui::FontId::new(120.0, egui::FontFamily::Name("matrix".into()));
+
+                    ui.spacing_mut().item_spacing.y = 20.0;
+
+                    ui.vertical_centered(|ui| {
+                        ui.add_space(20.0);
+                        // Add toggle button here
+                        if ui.button(self.next_readout_label()).clicked() {
+                            self.cycle_readout();
+                        }
+
+                        ui.label(
+                            egui::RichText::new(self.format_readout())
+                                .font(font_id)
+                                .color(self.preferences.led_color.lit()),
+                        );
+                        self.draw_dual_timecode(ui, 20.0);
+                        self.draw_clock_strip(ui, 20.0);
+                    });
+
+                    // Add some space before the buttons
+                    ui.add_space(20.0);
+
+                    // Center-align the buttons
+                    ui.vertical_centered(|ui| {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(if self.rolling() {
+                                    tr!("transport-stop")
+                                } else {
+                                    tr!("transport-start")
+                                })
+                                .clicked()
+                            {
+                                self.perform(if self.rolling() {
+                                    MacroAction::Stop
+                                } else {
+                                    MacroAction::Start
+                                });
+                            }
+
+                            if ui
+                                .add_enabled(
+                                    matches!(
+                                        self.transport,
+                                        Transport::Running | Transport::Paused
+                                    ),
+                                    egui::Button::new(if self.transport == Transport::Paused {
+                                        tr!("transport-resume")
+                                    } else {
+                                        tr!("transport-pause")
+                                    }),
+                                )
+                                .on_hover_text(tr!("transport-pause-hint"))
+                                .clicked()
+                            {
+                                self.toggle_pause();
+                            }
+
+                            if ui.button(tr!("transport-reset")).clicked() {
+                                self.perform(MacroAction::Reset);
+                            }
+
+                            if ui
+                                .button(tr!("transport-go"))
+                                .on_hover_text(tr!("transport-go-hint"))
+                                .clicked()
+                            {
+                                self.perform(MacroAction::Go);
+                            }
+                            if ui
+                                .button(tr!("transport-back"))
+                                .on_hover_text(tr!(
+                                    "transport-back-hint",
+                                    seconds = format!("{:.1}", self.show.back_fade.as_secs_f32())
+                                ))
+                                .clicked()
+                            {
+                                self.perform(MacroAction::Back);
+                            }
+                            let mut go_to = None;
+                            egui::ComboBox::from_id_salt("go_to_cue")
+                                .selected_text(tr!("transport-go-to-cue"))
+                                .show_ui(ui, |ui| {
+                                    for cue in self.show.cues.iter().filter(|cue| !cue.disarmed) {
+                                        if ui
+                                            .selectable_label(
+                                                false,
+                                                format!("{} {}", cue.number, cue.name),
+                                            )
+                                            .clicked()
+                                        {
+                                            go_to = Some(cue.number);
+                                        }
+                                    }
+                                });
+                            if let Some(number) = go_to {
+                                self.perform(MacroAction::FireCue(number));
+                            }
+
+                            if ui
+                                .button(tr!("transport-assert"))
+                                .on_hover_text(tr!("transport-assert-hint"))
+                                .clicked()
+                            {
+                                self.perform(MacroAction::Assert);
+                            }
+
+                            if ui.button(tr!("transport-timecode")).clicked() {
+                                self.show_timecode = true;
+                            }
+                        });
+                    });
+
+                    ui.add_space(20.0);
+
+                    if self.is_detached(Panel::CueList) {
+                        ui.label(tr!("transport-cue-list-detached"));
+                    } else {
+                        self.draw_cue_list(ui);
+                    }
+
+                    ui.add_space(20.0);
+                    ui.label(tr!("transport-override-pads"));
+                    ui.add_space(10.0);
+
+                    self.draw_pads(ui, true);
+                }
+                AppView::Patch => {
+                    egui::ScrollArea::vertical().show(ui, |ui| self.draw_patch_view(ui));
+                }
+            }
+        });
+
+        if !self.performance_mode {
+            self.draw_renumber_window(ctx);
+            self.draw_fixture_replace_window(ctx);
+            self.draw_paste_cues_window(ctx);
+            self.draw_snapshot_window(ctx);
+            self.draw_cue_editor(ctx);
+            self.draw_handoff_window(ctx);
+            self.draw_report_window(ctx);
+            self.draw_session_window(ctx);
+            self.draw_file_dialog(ctx);
+            self.draw_show_merge_window(ctx);
+            self.draw_show_diff_window(ctx);
+            self.draw_show_properties(ctx);
+            self.draw_backups_window(ctx);
+            self.draw_preferences(ctx);
+            self.draw_script_editor(ctx);
+            self.draw_log_console(ctx);
+            self.draw_macros_window(ctx);
+            self.draw_bindings_window(ctx);
+            self.draw_songs_window(ctx);
+            self.draw_busk_window(ctx);
+            self.draw_pixel_map_window(ctx);
+            self.draw_effect_presets_window(ctx);
+            self.draw_safe_state_window(ctx);
+            self.draw_recording_window(ctx);
+            self.draw_power_window(ctx);
+            self.draw_stage_display_window(ctx);
+            self.draw_clocks_window(ctx);
+            self.draw_patch_import_window(ctx);
+            self.draw_venue_window(ctx);
+            self.draw_daw_window(ctx);
+            self.draw_follow_spots_window(ctx);
+            self.draw_profile_browser_window(ctx);
+            self.draw_timecode_window(ctx);
+            self.draw_crossfade_window(ctx);
+            self.draw_learn_timing_window(ctx);
+            self.draw_fan_window(ctx);
+            self.draw_spectrogram_window(ctx);
+            self.draw_variables_window(ctx);
+            self.draw_speed_masters_window(ctx);
+            self.draw_group_masters_window(ctx);
+            self.draw_quick_console_window(ctx);
+            self.draw_command_line_window(ctx);
+            self.draw_art_net_window(ctx);
+            self.draw_sacn_window(ctx);
+            self.draw_plugins_window(ctx);
+            self.draw_smart_bulbs_window(ctx);
+            self.draw_schedule_window(ctx);
+            self.draw_haze_window(ctx);
+            self.draw_midi_monitor(ctx);
+            self.draw_osc_monitor(ctx);
+            self.draw_packet_inspector(ctx);
+            self.draw_tracksheet(ctx);
+        }
+        self.draw_performance_prompt(ctx);
+        self.draw_designer_view(ctx);
+        self.draw_frame_graph(ctx);
+        self.draw_frame_timings(ctx);
+        self.draw_panel_windows(ctx);
+        self.draw_detached_panels(ctx);
+        self.draw_stage_display(ctx);
+        self.draw_toasts(ctx);
+        self.draw_notifications_window(ctx);
+        self.draw_about_window(ctx);
+        self.draw_quit_prompt(ctx);
+        self.draw_control_prompt(ctx);
+        self.draw_newer_show_prompt(ctx);
+        self.draw_error_dialog(ctx);
+        self.draw_shortcut_help(ctx);
+        self.draw_command_palette(ctx);
+        ui::accessibility::draw_focus_ring(ctx);
+        self.draw_dimmed(ctx);
+
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| self.draw_status_bar(ui));
+        });
+
+        self.sync_engine(ctx);
+        self.sync_midi_clock();
+
+        // Request continuous repaint while running
+        if self.rolling()
+            || self.readout != Readout::Timecode
+            || !self.macro_playbacks.is_empty()
+            || self.busk_enabled
+            || self.show.pixel_maps.iter().any(|map| map.enabled)
+            || self.show_frame_graph
+            || self.panic.is_some()
+        {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Runs Halo: the simulation when asked for on the command line, and the
+/// app window otherwise.
+pub fn run() -> eframe::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(at) = args.iter().position(|arg| arg == "--simulate") {
+        std::process::exit(simulate::main(&args[at + 1..]));
+    }
+    let native_options = eframe::NativeOptions {
+        // initial_window_size: Some(egui::vec2(400.0, 200.0)),
+        // min_window_size: Some(egui::vec2(300.0, 150.0)),
+        viewport: eframe::egui::ViewportBuilder {
+            title: Some(String::from("Halo")),
+            app_id: Some(String::from("io.github.robmorgan.halo")),
+            maximized: Some(true),
+            ..eframe::egui::ViewportBuilder::default()
+        },
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "Halo",
+        native_options,
+        Box::new(|cc| Ok(Box::new(HaloApp::new(cc)))),
+    )
+}
@@ -0,0 +1,56 @@
+use std::collections::VecDeque;
+
+/// Oldest entries are dropped once the console holds this many.
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LogLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+pub struct LogEntry {
+    pub time: chrono::DateTime<chrono::Local>,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// In-app log shown in the log console window.
+#[derive(Default)]
+pub struct Log {
+    entries: VecDeque<LogEntry>,
+}
+
+impl Log {
+    pub fn push(&mut self, level: LogLevel, message: impl Into<String>) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            time: chrono::Local::now(),
+            level,
+            message: message.into(),
+        });
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(LogLevel::Info, message);
+    }
+
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.push(LogLevel::Warning, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(LogLevel::Error, message);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
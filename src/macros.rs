@@ -0,0 +1,228 @@
+//! Operator macros: a recorded sequence of actions with their relative
+//! timing, replayable from a pad or a cue.
+
+use crate::cue::CueNumber;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::ui::script_editor::ScriptTarget;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Consecutive BPM changes closer together than this are merged into one
+/// step, so dragging the BPM field doesn't record every intermediate value.
+const BPM_MERGE_WINDOW: Duration = Duration::from_millis(250);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MacroAction {
+    FireCue(CueNumber),
+    /// Jumps to the next cue.
+    Go,
+    /// Fades back to the cue before the current one; see `back`.
+    Back,
+    SetPad {
+        pad: String,
+        active: bool,
+    },
+    SetBpm(f32),
+    Start,
+    Stop,
+    /// Freezes playback with the output held; Start carries on.
+    Pause,
+    Reset,
+    RunMacro(String),
+    NextSong,
+    PreviousSong,
+    /// Cues up a song by its 1-based position in the setlist.
+    GoToSong(usize),
+    /// Shows the pad bank at this 1-based position.
+    PadBank(usize),
+    /// Drops the overrides on top of the cues; see `assert`.
+    Assert,
+}
+
+impl fmt::Display for MacroAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MacroAction::FireCue(number) => {
+                f.write_str(&tr!("macro-fire-cue", number = number.to_string()))
+            }
+            MacroAction::Go => f.write_str(&tr!("transport-go")),
+            MacroAction::Back => f.write_str(&tr!("transport-back")),
+            MacroAction::SetPad { pad, active } => f.write_str(&if *active {
+                tr!("macro-pad-on", pad = pad.as_str())
+            } else {
+                tr!("macro-pad-off", pad = pad.as_str())
+            }),
+            MacroAction::SetBpm(bpm) => write!(f, "BPM {:.1}", bpm),
+            MacroAction::Start => f.write_str(&tr!("transport-start")),
+            MacroAction::Stop => f.write_str(&tr!("transport-stop")),
+            MacroAction::Pause => f.write_str(&tr!("transport-pause")),
+            MacroAction::Reset => f.write_str(&tr!("transport-reset")),
+            MacroAction::RunMacro(name) => write!(f, "{} {}", tr!("action-run-macro"), name),
+            MacroAction::NextSong => f.write_str(&tr!("action-next-song")),
+            MacroAction::PreviousSong => f.write_str(&tr!("action-previous-song")),
+            MacroAction::GoToSong(number) => f.write_str(&tr!("macro-song", number = *number)),
+            MacroAction::PadBank(number) => write!(f, "{} {}", tr!("action-pad-bank"), number),
+            MacroAction::Assert => f.write_str(&tr!("transport-assert")),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MacroStep {
+    /// Time since the start of the macro.
+    pub offset: Duration,
+    pub action: MacroAction,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Macro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
+
+pub struct MacroRecorder {
+    pub name: String,
+    started: Instant,
+    steps: Vec<MacroStep>,
+}
+
+impl MacroRecorder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            started: Instant::now(),
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, action: MacroAction) {
+        let offset = self.started.elapsed();
+        if let (MacroAction::SetBpm(_), Some(last)) = (&action, self.steps.last_mut()) {
+            if matches!(last.action, MacroAction::SetBpm(_))
+                && offset - last.offset < BPM_MERGE_WINDOW
+            {
+                last.action = action;
+                return;
+            }
+        }
+        self.steps.push(MacroStep { offset, action });
+    }
+
+    pub fn step_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn finish(self) -> Macro {
+        Macro {
+            name: self.name,
+            steps: self.steps,
+        }
+    }
+}
+
+pub struct MacroPlayback {
+    steps: Vec<MacroStep>,
+    started: Instant,
+    next: usize,
+}
+
+/// Guards against macros that run themselves, directly or indirectly.
+const MAX_RUNNING_MACROS: usize = 16;
+
+impl HaloApp {
+    /// Applies an operator action, recording it if a macro is being recorded.
+    pub(crate) fn perform(&mut self, action: MacroAction) {
+        if let Some(recorder) = &mut self.macro_recorder {
+            recorder.record(action.clone());
+        }
+        self.apply_action(action);
+    }
+
+    fn apply_action(&mut self, action: MacroAction) {
+        match action {
+            MacroAction::FireCue(number) => {
+                match self.show.cues.iter().position(|cue| cue.number == number) {
+                    Some(index) => self.go_to_cue(index),
+                    None => self
+                        .log
+                        .warn(tr!("macros-no-cue", number = number.to_string())),
+                }
+            }
+            MacroAction::Go => match self.next_cue().and_then(|cue| self.cue_index(cue)) {
+                Some(index) => self.go_to_cue(index),
+                None => self.log.warn(tr!("macros-no-next-cue")),
+            },
+            MacroAction::Back => self.back(),
+            MacroAction::SetPad { pad, active } => {
+                let Some(index) = self.show.pads.iter().position(|p| p.label == pad) else {
+                    self.log.warn(tr!("macros-no-pad", pad = pad));
+                    return;
+                };
+                if self.latch_pad(index, active) {
+                    self.run_script(ScriptTarget::Pad(index));
+                    if let Some(name) = self.show.pads[index].run_macro.clone() {
+                        self.start_macro(&name);
+                    }
+                }
+            }
+            MacroAction::SetBpm(bpm) => self.bpm = bpm.clamp(20.0, 300.0),
+            MacroAction::Start => self.start_playback(),
+            MacroAction::Stop => self.stop_playback(),
+            MacroAction::Pause => self.pause_playback(),
+            MacroAction::Reset => self.reset(),
+            MacroAction::RunMacro(name) => self.start_macro(&name),
+            MacroAction::NextSong => self.next_song(),
+            MacroAction::PreviousSong => self.previous_song(),
+            MacroAction::GoToSong(number) => self.go_to_song(number.saturating_sub(1)),
+            MacroAction::PadBank(number) => {
+                if (1..=self.show.pad_layout.banks.len()).contains(&number) {
+                    self.pad_bank = number - 1;
+                } else {
+                    self.log.warn(tr!("macros-no-bank", number = number));
+                }
+            }
+            MacroAction::Assert => self.assert_cues(),
+        }
+    }
+
+    pub(crate) fn start_macro(&mut self, name: &str) {
+        let Some(recorded) = self.show.macros.iter().find(|m| m.name == name) else {
+            self.log.warn(tr!("macros-no-macro", name = name));
+            return;
+        };
+        if self.macro_playbacks.len() >= MAX_RUNNING_MACROS {
+            self.notify(LogLevel::Error, tr!("macros-too-many", name = name));
+            return;
+        }
+        self.macro_playbacks.push(MacroPlayback {
+            steps: recorded.steps.clone(),
+            started: Instant::now(),
+            next: 0,
+        });
+    }
+
+    /// Applies the steps of running macros that have come due. Replayed
+    /// actions are not recorded again.
+    pub(crate) fn update_macros(&mut self) {
+        let mut due = Vec::new();
+        for playback in &mut self.macro_playbacks {
+            let elapsed = playback.started.elapsed();
+            while let Some(step) = playback.steps.get(playback.next) {
+                if step.offset > elapsed {
+                    break;
+                }
+                due.push(step.action.clone());
+                playback.next += 1;
+            }
+        }
+        self.macro_playbacks
+            .retain(|playback| playback.next < playback.steps.len());
+
+        for action in due {
+            self.apply_action(action);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Timeline markers: notes pinned to a point in the show, such as lyrics,
+//! "pyro here" or "band entrance". They sit among the cues in the cue list,
+//! count down in performance mode and print on the cue sheet, but never
+//! touch the output.
+
+use crate::show::Show;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Marker {
+    /// When it falls on the show timeline.
+    pub time: Duration,
+    pub text: String,
+}
+
+impl Marker {
+    pub fn matches(&self, query: &str) -> bool {
+        query.is_empty() || self.text.to_lowercase().contains(query)
+    }
+}
+
+/// Where each marker goes among the cues: the index of the first cue starting
+/// at or after it, or the number of cues if it comes after them all.
+pub fn slots(show: &Show) -> Vec<usize> {
+    show.markers
+        .iter()
+        .map(|marker| {
+            show.cues
+                .iter()
+                .position(|cue| show.cue_start(cue) >= marker.time)
+                .unwrap_or(show.cues.len())
+        })
+        .collect()
+}
+
+/// The markers that go just before the cue at `index`, with their indices.
+pub fn before<'a>(
+    show: &'a Show,
+    slots: &'a [usize],
+    index: usize,
+) -> impl Iterator<Item = (usize, &'a Marker)> {
+    show.markers
+        .iter()
+        .enumerate()
+        .filter(move |(i, _)| slots[*i] == index)
+}
+
+/// The first marker still to come after `elapsed`.
+pub fn next(show: &Show, elapsed: Duration) -> Option<&Marker> {
+    show.markers
+        .iter()
+        .filter(|marker| marker.time > elapsed)
+        .min_by_key(|marker| marker.time)
+}
@@ -0,0 +1,140 @@
+//! Decodes animated GIFs and short video files into frames sampled at a pixel
+//! map's resolution. GIFs are decoded in-process; anything else is handed to
+//! an `ffmpeg` binary on the `PATH`.
+
+use crate::pixel::Rgb;
+use image::codecs::gif::GifDecoder;
+use image::imageops::{self, FilterType};
+use image::{AnimationDecoder, RgbaImage};
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Frame rate video is resampled to before it reaches the pixel map.
+const VIDEO_FPS: u32 = 30;
+
+/// Longest stretch of video decoded, as every frame is held in memory.
+const MAX_VIDEO_SECONDS: u32 = 120;
+
+#[derive(PartialEq, Eq)]
+pub struct MediaFrame {
+    pub pixels: Vec<Rgb>,
+    pub duration: Duration,
+}
+
+#[derive(PartialEq, Eq)]
+pub struct MediaClip {
+    pub width: u16,
+    pub height: u16,
+    pub frames: Vec<MediaFrame>,
+    pub length: Duration,
+}
+
+impl MediaClip {
+    pub fn load(path: &Path, width: u16, height: u16) -> io::Result<Self> {
+        let is_gif = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"));
+        let frames = if is_gif {
+            load_gif(path, width, height)?
+        } else {
+            load_video(path, width, height)?
+        };
+        if frames.is_empty() {
+            return Err(io::Error::other("no frames decoded"));
+        }
+        let length = frames.iter().map(|frame| frame.duration).sum();
+        Ok(Self {
+            width,
+            height,
+            frames,
+            length,
+        })
+    }
+
+    /// The frame showing `position` into the clip, holding the last frame
+    /// once the clip has ended unless `looping`.
+    pub fn frame_at(&self, position: Duration, looping: bool) -> &MediaFrame {
+        let position = if looping && !self.length.is_zero() {
+            Duration::from_nanos((position.as_nanos() % self.length.as_nanos()) as u64)
+        } else {
+            position
+        };
+        let mut start = Duration::ZERO;
+        for frame in &self.frames {
+            start += frame.duration;
+            if position < start {
+                return frame;
+            }
+        }
+        &self.frames[self.frames.len() - 1]
+    }
+}
+
+fn sample(image: &RgbaImage, width: u16, height: u16) -> Vec<Rgb> {
+    let scaled = imageops::resize(image, width as u32, height as u32, FilterType::Triangle);
+    scaled
+        .pixels()
+        .map(|pixel| {
+            // Composite transparent areas onto black.
+            let alpha = pixel[3] as u16;
+            [0, 1, 2].map(|i| (pixel[i] as u16 * alpha / 255) as u8)
+        })
+        .collect()
+}
+
+fn load_gif(path: &Path, width: u16, height: u16) -> io::Result<Vec<MediaFrame>> {
+    let decoder = GifDecoder::new(BufReader::new(File::open(path)?)).map_err(io::Error::other)?;
+    let mut frames = Vec::new();
+    for frame in decoder.into_frames() {
+        let frame = frame.map_err(io::Error::other)?;
+        let duration = Duration::from(frame.delay());
+        frames.push(MediaFrame {
+            pixels: sample(frame.buffer(), width, height),
+            // Browsers treat a zero delay as "as fast as reasonable".
+            duration: duration.max(Duration::from_millis(20)),
+        });
+    }
+    Ok(frames)
+}
+
+fn load_video(path: &Path, width: u16, height: u16) -> io::Result<Vec<MediaFrame>> {
+    let mut child = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(path)
+        .args(["-t", &MAX_VIDEO_SECONDS.to_string()])
+        .args(["-vf", &format!("scale={}:{}", width, height)])
+        .args(["-r", &VIDEO_FPS.to_string()])
+        .args(["-f", "rawvideo", "-pix_fmt", "rgb24", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| io::Error::new(err.kind(), format!("could not run ffmpeg: {}", err)))?;
+
+    let mut raw = Vec::new();
+    child
+        .stdout
+        .take()
+        .expect("stdout is piped")
+        .read_to_end(&mut raw)?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!(
+            "ffmpeg failed: {}",
+            message.trim()
+        )));
+    }
+
+    let frame_size = width as usize * height as usize * 3;
+    Ok(raw
+        .chunks_exact(frame_size)
+        .map(|chunk| MediaFrame {
+            pixels: chunk.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect(),
+            duration: Duration::from_secs(1) / VIDEO_FPS,
+        })
+        .collect())
+}
@@ -0,0 +1,586 @@
+//! MIDI in and out. Halo can follow the tempo of a drum machine or sequencer
+//! sending MIDI clock, chase MIDI timecode, take program changes to pick setlist songs, pass notes,
+//! controllers and program changes to trigger bindings, send its
+//! own tempo as MIDI clock for other gear to follow, and send the notes and
+//! program changes cues trigger.
+
+use crate::binding::InputEvent;
+use crate::error::{Device, HaloError};
+use crate::i18n::tr;
+use crate::macros::MacroAction;
+use crate::midi_monitor::{Direction, MidiMonitor};
+use crate::timecode::{self, MtcDecoder, Timecode};
+use crate::HaloApp;
+use eframe::egui;
+use midir::{Ignore, MidiInput, MidiInputConnection, MidiOutput, MidiOutputConnection};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// MIDI clock pulses per quarter note.
+const PPQN: u32 = 24;
+
+const QUARTER_FRAME: u8 = 0xF1;
+const CLOCK: u8 = 0xF8;
+const START: u8 = 0xFA;
+const STOP: u8 = 0xFC;
+const NOTE_ON: u8 = 0x90;
+const CONTROL_CHANGE: u8 = 0xB0;
+const PROGRAM_CHANGE: u8 = 0xC0;
+const SYSEX: u8 = 0xF0;
+const SYSEX_END: u8 = 0xF7;
+/// Universal real-time sysex, and its MMC command sub-ID.
+const REAL_TIME: u8 = 0x7F;
+const MMC_COMMAND: u8 = 0x06;
+
+/// Clock gaps longer than this mean the sender stopped, not that it slowed.
+const CLOCK_TIMEOUT: Duration = Duration::from_secs(1);
+
+const CLIENT_NAME: &str = "Halo";
+
+/// The MMC device ID that addresses every device.
+pub const ALL_CALL: u8 = 0x7F;
+const MMC_STOP: u8 = 0x01;
+const MMC_PLAY: u8 = 0x02;
+const MMC_DEFERRED_PLAY: u8 = 0x03;
+const MMC_PAUSE: u8 = 0x09;
+const MMC_LOCATE: u8 = 0x44;
+/// Locate's byte count and its "target" sub-command.
+const LOCATE_TARGET: [u8; 2] = [0x06, 0x01];
+/// Frame rate Locate is sent at, as the hours byte's rate bits: 25 fps.
+const LOCATE_RATE: u8 = 1;
+const LOCATE_FPS: u32 = 25;
+
+/// MIDI Machine Control transport commands, received and sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MachineCommand {
+    Stop,
+    Play,
+    Pause,
+    /// Move to this timecode.
+    Locate(Duration),
+}
+
+impl MachineCommand {
+    /// The device ID and command of an MMC sysex message.
+    fn parse(message: &[u8]) -> Option<(u8, Self)> {
+        let command = match message {
+            [SYSEX, REAL_TIME, _, MMC_COMMAND, command, SYSEX_END] => match *command {
+                MMC_STOP => MachineCommand::Stop,
+                // Deferred play is play once the machine is ready, which
+                // Halo always is.
+                MMC_PLAY | MMC_DEFERRED_PLAY => MachineCommand::Play,
+                MMC_PAUSE => MachineCommand::Pause,
+                _ => return None,
+            },
+            [SYSEX, REAL_TIME, _, MMC_COMMAND, MMC_LOCATE, count, target, hours, minutes, seconds, frames, _subframes, SYSEX_END]
+                if [*count, *target] == LOCATE_TARGET =>
+            {
+                let timecode = Timecode::new(*hours, *minutes, *seconds, *frames, false);
+                MachineCommand::Locate(timecode.position)
+            }
+            _ => return None,
+        };
+        Some((message[2], command))
+    }
+
+    /// The sysex message sending this command to `device`.
+    pub fn message(self, device: u8) -> Vec<u8> {
+        let mut message = vec![SYSEX, REAL_TIME, device, MMC_COMMAND];
+        match self {
+            MachineCommand::Stop => message.push(MMC_STOP),
+            MachineCommand::Play => message.push(MMC_PLAY),
+            MachineCommand::Pause => message.push(MMC_PAUSE),
+            MachineCommand::Locate(position) => {
+                let secs = position.as_secs();
+                let frames = position.subsec_millis() * LOCATE_FPS / 1000;
+                message.push(MMC_LOCATE);
+                message.extend(LOCATE_TARGET);
+                message.extend([
+                    (LOCATE_RATE << 5) | (secs / 3600 % 24) as u8,
+                    (secs / 60 % 60) as u8,
+                    (secs % 60) as u8,
+                    frames as u8,
+                    0,
+                ]);
+            }
+        }
+        message.push(SYSEX_END);
+        message
+    }
+}
+
+impl fmt::Display for MachineCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MachineCommand::Stop => f.write_str(&tr!("transport-stop")),
+            MachineCommand::Play => f.write_str(&tr!("mmc-play")),
+            MachineCommand::Pause => f.write_str(&tr!("transport-pause")),
+            MachineCommand::Locate(position) => {
+                f.write_str(&tr!("mmc-locate", position = timecode::format(*position)))
+            }
+        }
+    }
+}
+
+pub enum MidiEvent {
+    /// Tempo measured from incoming clock, sent once a beat.
+    Tempo(f32),
+    /// A beat of incoming clock, counted from the last Start.
+    Beat(u32),
+    /// MIDI timecode, once per frame pair while it runs.
+    Timecode(Timecode),
+    /// A note on, controller press or program change, for trigger bindings
+    /// and setlist song selection.
+    Input(InputEvent),
+    /// Every controller change, with its value, for the crossfade faders.
+    Controller {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    /// A MIDI Machine Control transport command for `device`.
+    Machine { device: u8, command: MachineCommand },
+}
+
+pub fn input_ports() -> Vec<String> {
+    let Ok(input) = MidiInput::new(CLIENT_NAME) else {
+        return Vec::new();
+    };
+    input
+        .ports()
+        .iter()
+        .filter_map(|port| input.port_name(port).ok())
+        .collect()
+}
+
+pub fn output_ports() -> Vec<String> {
+    let Ok(output) = MidiOutput::new(CLIENT_NAME) else {
+        return Vec::new();
+    };
+    output
+        .ports()
+        .iter()
+        .filter_map(|port| output.port_name(port).ok())
+        .collect()
+}
+
+/// Whether port `name` is the port saved as `wanted`. On Linux port names end
+/// in ALSA client and port numbers, which can change when a device is plugged
+/// back in, so those are ignored.
+pub fn same_port(name: &str, wanted: &str) -> bool {
+    fn device(name: &str) -> &str {
+        match name.rsplit_once(' ') {
+            Some((device, id))
+                if id.split(':').count() == 2
+                    && id.split(':').all(|n| n.parse::<u32>().is_ok()) =>
+            {
+                device
+            }
+            _ => name,
+        }
+    }
+    name == wanted || device(name) == device(wanted)
+}
+
+/// Turns incoming clock pulses into tempo and beat events, and timecode
+/// into positions.
+struct ClockFollower {
+    events: Sender<MidiEvent>,
+    ctx: egui::Context,
+    monitor: MidiMonitor,
+    port: String,
+    mtc: MtcDecoder,
+    pulses: u32,
+    last_pulse: Option<Instant>,
+    intervals: VecDeque<Duration>,
+}
+
+impl ClockFollower {
+    fn send(&self, event: MidiEvent) {
+        let _ = self.events.send(event);
+        self.ctx.request_repaint();
+    }
+
+    fn receive(&mut self, message: &[u8]) {
+        self.monitor.record(Direction::In, &self.port, message);
+        match message {
+            [CLOCK] => self.pulse(Instant::now()),
+            [START] => self.pulses = 0,
+            [QUARTER_FRAME, data] => {
+                if let Some(timecode) = self.mtc.quarter_frame(*data) {
+                    self.send(MidiEvent::Timecode(timecode));
+                }
+            }
+            [status, note, velocity] if status & 0xF0 == NOTE_ON && *velocity > 0 => {
+                self.send(MidiEvent::Input(InputEvent::MidiNote {
+                    channel: channel(*status),
+                    note: *note,
+                }));
+            }
+            [status, controller, value] if status & 0xF0 == CONTROL_CHANGE => {
+                self.send(MidiEvent::Controller {
+                    channel: channel(*status),
+                    controller: *controller,
+                    value: *value,
+                });
+                // Controllers count as pressed when they cross the halfway
+                // point, so buttons sending 127 then 0 trigger once.
+                if *value >= 64 {
+                    self.send(MidiEvent::Input(InputEvent::MidiControl {
+                        channel: channel(*status),
+                        controller: *controller,
+                    }));
+                }
+            }
+            [status, program] if status & 0xF0 == PROGRAM_CHANGE => {
+                self.send(MidiEvent::Input(InputEvent::MidiProgram {
+                    channel: channel(*status),
+                    program: *program,
+                }));
+            }
+            message => {
+                if let Some(timecode) = timecode::full_frame(message) {
+                    self.send(MidiEvent::Timecode(timecode));
+                } else if let Some((device, command)) = MachineCommand::parse(message) {
+                    self.send(MidiEvent::Machine { device, command });
+                }
+            }
+        }
+    }
+
+    fn pulse(&mut self, now: Instant) {
+        if let Some(last) = self.last_pulse {
+            let interval = now - last;
+            if interval > CLOCK_TIMEOUT {
+                self.intervals.clear();
+            } else {
+                self.intervals.push_back(interval);
+                if self.intervals.len() > PPQN as usize {
+                    self.intervals.pop_front();
+                }
+            }
+        }
+        self.last_pulse = Some(now);
+
+        if self.pulses.is_multiple_of(PPQN) {
+            self.send(MidiEvent::Beat(self.pulses / PPQN));
+            // Average over a whole beat to smooth out USB jitter.
+            if self.intervals.len() == PPQN as usize {
+                let beat: Duration = self.intervals.iter().sum();
+                let bpm = 60.0 / beat.as_secs_f32();
+                self.send(MidiEvent::Tempo((bpm * 10.0).round() / 10.0));
+            }
+        }
+        self.pulses = self.pulses.wrapping_add(1);
+    }
+}
+
+/// The 1-based channel of a channel message.
+fn channel(status: u8) -> u8 {
+    (status & 0x0F) + 1
+}
+
+/// A connection to the MIDI input port Halo listens to. Closed when dropped.
+pub struct MidiIn {
+    _connection: MidiInputConnection<ClockFollower>,
+}
+
+impl MidiIn {
+    pub fn connect(
+        port_name: &str,
+        events: Sender<MidiEvent>,
+        monitor: MidiMonitor,
+        ctx: egui::Context,
+    ) -> Result<Self, String> {
+        let mut input = MidiInput::new(CLIENT_NAME).map_err(|err| err.to_string())?;
+        // Sysex is needed for MTC full frame messages.
+        input.ignore(Ignore::ActiveSense);
+        let port = input
+            .ports()
+            .into_iter()
+            .find(|port| {
+                input
+                    .port_name(port)
+                    .is_ok_and(|name| same_port(&name, port_name))
+            })
+            .ok_or_else(|| tr!("midi-input-missing", port = port_name))?;
+        let follower = ClockFollower {
+            events,
+            ctx,
+            monitor,
+            port: port_name.to_string(),
+            mtc: MtcDecoder::default(),
+            pulses: 0,
+            last_pulse: None,
+            intervals: VecDeque::new(),
+        };
+        let connection = input
+            .connect(
+                &port,
+                "halo-in",
+                |_, message, follower| follower.receive(message),
+                follower,
+            )
+            .map_err(|err| err.to_string())?;
+        Ok(Self {
+            _connection: connection,
+        })
+    }
+}
+
+/// Opens the MIDI output port named `port_name`, calling the connection
+/// `connection_name`.
+pub fn connect_output(
+    port_name: &str,
+    connection_name: &str,
+) -> Result<MidiOutputConnection, String> {
+    let output = MidiOutput::new(CLIENT_NAME).map_err(|err| err.to_string())?;
+    let port = output
+        .ports()
+        .into_iter()
+        .find(|port| {
+            output
+                .port_name(port)
+                .is_ok_and(|name| same_port(&name, port_name))
+        })
+        .ok_or_else(|| tr!("midi-output-missing", port = port_name))?;
+    output
+        .connect(&port, connection_name)
+        .map_err(|err| err.to_string())
+}
+
+enum OutCommand {
+    Bpm(f32),
+    Start,
+    Stop,
+    Message(Vec<u8>),
+}
+
+/// The MIDI output port, driven from a thread of its own. Sends clock at
+/// Halo's tempo when `clock` is set, along with Start and Stop as the
+/// transport changes, and any message passed to [`MidiOut::send`]. The thread
+/// exits when this is dropped.
+pub struct MidiOut {
+    commands: Sender<OutCommand>,
+    bpm: f32,
+    running: bool,
+}
+
+impl MidiOut {
+    pub fn connect(
+        port_name: &str,
+        bpm: f32,
+        clock: bool,
+        monitor: MidiMonitor,
+    ) -> Result<Self, String> {
+        let mut connection = connect_output(port_name, "halo-out")?;
+        let (commands, command_rx) = mpsc::channel();
+        let port = port_name.to_string();
+        thread::Builder::new()
+            .name("halo-midi-out".to_string())
+            .spawn(move || {
+                let mut send = |message: &[u8]| {
+                    let _ = connection.send(message);
+                    monitor.record(Direction::Out, &port, message);
+                };
+                send_output(&mut send, clock.then_some(bpm), command_rx)
+            })
+            .map_err(|err| err.to_string())?;
+        Ok(Self {
+            commands,
+            bpm,
+            running: false,
+        })
+    }
+
+    pub fn set_bpm(&mut self, bpm: f32) {
+        if bpm != self.bpm {
+            self.bpm = bpm;
+            let _ = self.commands.send(OutCommand::Bpm(bpm));
+        }
+    }
+
+    pub fn set_running(&mut self, running: bool) {
+        if running != self.running {
+            self.running = running;
+            let _ = self.commands.send(if running {
+                OutCommand::Start
+            } else {
+                OutCommand::Stop
+            });
+        }
+    }
+
+    pub fn send(&self, message: &[u8]) {
+        let _ = self.commands.send(OutCommand::Message(message.to_vec()));
+    }
+}
+
+fn pulse_period(bpm: f32) -> Duration {
+    Duration::from_secs_f32(60.0 / bpm.max(1.0) / PPQN as f32)
+}
+
+/// Runs the output thread; `bpm` is `None` when clock is off.
+fn send_output(send: &mut impl FnMut(&[u8]), bpm: Option<f32>, commands: Receiver<OutCommand>) {
+    let clock = bpm.is_some();
+    let mut period = pulse_period(bpm.unwrap_or(120.0));
+    let mut next_pulse = Instant::now();
+    loop {
+        let command = if clock {
+            let timeout = next_pulse.saturating_duration_since(Instant::now());
+            commands.recv_timeout(timeout)
+        } else {
+            commands.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        };
+        match command {
+            Ok(OutCommand::Bpm(bpm)) => period = pulse_period(bpm),
+            Ok(OutCommand::Start) if clock => send(&[START]),
+            Ok(OutCommand::Stop) if clock => send(&[STOP]),
+            Ok(OutCommand::Start | OutCommand::Stop) => {}
+            Ok(OutCommand::Message(message)) => send(&message),
+            Err(RecvTimeoutError::Timeout) => {
+                send(&[CLOCK]);
+                let now = Instant::now();
+                next_pulse += period;
+                // After a stall, carry on from now rather than bursting.
+                if next_pulse < now {
+                    next_pulse = now + period;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+impl HaloApp {
+    /// Opens or closes the MIDI ports to match the current preferences.
+    pub(crate) fn apply_midi_preferences(&mut self, ctx: &egui::Context) {
+        self.midi_in = None;
+        self.midi_out = None;
+        self.midi_error = None;
+        self.connect_midi_input(ctx);
+        self.connect_midi_output();
+    }
+
+    /// Opens the chosen MIDI input port, if there is one.
+    pub(crate) fn connect_midi_input(&mut self, ctx: &egui::Context) {
+        if let Some(port) = self.preferences.midi_input.clone() {
+            match MidiIn::connect(
+                &port,
+                self.midi_sender.clone(),
+                self.midi_monitor.clone(),
+                ctx.clone(),
+            ) {
+                Ok(input) => self.midi_in = Some(input),
+                Err(err) => {
+                    self.report_error(HaloError::device(Device::MidiInput, port, &err));
+                    self.midi_error = Some(err);
+                }
+            }
+        }
+    }
+
+    /// Opens the chosen MIDI output port, if there is one.
+    pub(crate) fn connect_midi_output(&mut self) {
+        if let Some(port) = self.preferences.midi_output.clone() {
+            match MidiOut::connect(
+                &port,
+                self.bpm,
+                self.preferences.midi_send_clock,
+                self.midi_monitor.clone(),
+            ) {
+                Ok(output) => self.midi_out = Some(output),
+                Err(err) => {
+                    self.report_error(HaloError::device(Device::MidiOutput, port, &err));
+                    self.midi_error = Some(err);
+                }
+            }
+        }
+    }
+
+    /// Whether the tempo and beat come from incoming MIDI clock.
+    pub(crate) fn follows_midi_clock(&self) -> bool {
+        self.midi_in.is_some() && self.preferences.midi_clock_in
+    }
+
+    pub(crate) fn handle_midi_events(&mut self) {
+        let events: Vec<MidiEvent> = self.midi_events.try_iter().collect();
+        for event in events {
+            match event {
+                MidiEvent::Tempo(bpm) if self.follows_midi_clock() => {
+                    self.bpm = bpm.clamp(20.0, 300.0);
+                }
+                MidiEvent::Beat(beat) if self.follows_midi_clock() => {
+                    self.beat_indicator.sync(beat);
+                    self.click_clock.sync(beat);
+                }
+                MidiEvent::Timecode(timecode) => self.receive_timecode(timecode),
+                MidiEvent::Machine { device, command } if self.answers_mmc(device) => {
+                    self.log.info(format!("MMC {}", command));
+                    match command {
+                        MachineCommand::Stop => self.perform(MacroAction::Stop),
+                        MachineCommand::Play => self.perform(MacroAction::Start),
+                        MachineCommand::Pause => self.perform(MacroAction::Pause),
+                        MachineCommand::Locate(position) => {
+                            self.locate(self.show.timecode.show_position(position));
+                        }
+                    }
+                }
+                MidiEvent::Controller {
+                    channel,
+                    controller,
+                    value,
+                } => {
+                    self.receive_controller(channel, controller, value);
+                    self.receive_speed_controller(channel, controller, value);
+                    self.receive_group_controller(channel, controller, value);
+                }
+                MidiEvent::Input(event) => {
+                    // Bound program changes take precedence over songs.
+                    let bound = self.handle_input_event(event.clone());
+                    if let InputEvent::MidiProgram { channel, program } = event {
+                        let song_channel = self.preferences.midi_song_channel;
+                        if !bound
+                            && self.setlist_mode
+                            && (song_channel == 0 || song_channel == channel)
+                        {
+                            let song = program as usize + 1;
+                            self.set_input_outcome(tr!("macro-song", number = song));
+                            self.perform(MacroAction::GoToSong(song));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether MMC for `device` is followed: with MMC in on, for Halo's
+    /// device ID or all devices.
+    fn answers_mmc(&self, device: u8) -> bool {
+        let wanted = self.preferences.midi_mmc_device;
+        self.preferences.midi_mmc_in
+            && (device == ALL_CALL || wanted == ALL_CALL || device == wanted)
+    }
+
+    /// Sends an MMC command on the output port, if MMC out is on.
+    pub(crate) fn send_machine_command(&self, command: MachineCommand) {
+        if !self.preferences.midi_mmc_out {
+            return;
+        }
+        if let Some(output) = &self.midi_out {
+            output.send(&command.message(self.preferences.midi_mmc_device));
+        }
+    }
+
+    /// Keeps outgoing MIDI clock in step with the tempo and transport.
+    pub(crate) fn sync_midi_clock(&mut self) {
+        if let Some(output) = &mut self.midi_out {
+            output.set_bpm(self.bpm);
+            output.set_running(self.transport.is_running());
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! A record of the MIDI messages Halo receives and sends, for the MIDI
+//! monitor window. Clock pulses and timecode quarter frames arrive dozens of
+//! times a second and would push everything else out, so they are only kept
+//! while asked for.
+
+use crate::i18n::tr;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// The monitor keeps this many messages, dropping the oldest.
+const MAX_MESSAGES: usize = 500;
+
+const QUARTER_FRAME: u8 = 0xF1;
+const CLOCK: u8 = 0xF8;
+const ACTIVE_SENSE: u8 = 0xFE;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Direction {
+    In,
+    Out,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            Direction::In => tr!("direction-in"),
+            Direction::Out => tr!("direction-out"),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct MidiMessage {
+    pub time: chrono::DateTime<chrono::Local>,
+    pub direction: Direction,
+    pub port: String,
+    pub bytes: Vec<u8>,
+}
+
+impl MidiMessage {
+    /// The 1-based channel of a channel message.
+    pub fn channel(&self) -> Option<u8> {
+        let status = *self.bytes.first()?;
+        (0x80..0xF0)
+            .contains(&status)
+            .then_some((status & 0x0F) + 1)
+    }
+
+    pub fn kind(&self) -> &'static str {
+        let Some(&status) = self.bytes.first() else {
+            return "Empty";
+        };
+        match (status & 0xF0, self.bytes.get(2)) {
+            (0x90, Some(0)) | (0x80, _) => "Note off",
+            (0x90, _) => "Note on",
+            (0xA0, _) => "Aftertouch",
+            (0xB0, _) => "Control change",
+            (0xC0, _) => "Program change",
+            (0xD0, _) => "Channel pressure",
+            (0xE0, _) => "Pitch bend",
+            _ => match status {
+                0xF0 => "SysEx",
+                QUARTER_FRAME => "MTC quarter frame",
+                0xF2 => "Song position",
+                0xF3 => "Song select",
+                CLOCK => "Clock",
+                0xFA => "Start",
+                0xFB => "Continue",
+                0xFC => "Stop",
+                ACTIVE_SENSE => "Active sensing",
+                0xFF => "Reset",
+                _ => "Unknown",
+            },
+        }
+    }
+
+    /// The message's data, read for its type.
+    pub fn data(&self) -> String {
+        let byte = |i: usize| self.bytes.get(i).copied().unwrap_or(0);
+        match self.bytes.first().map(|status| status & 0xF0) {
+            Some(0x80 | 0x90) => format!("note {} velocity {}", byte(1), byte(2)),
+            Some(0xA0) => format!("note {} pressure {}", byte(1), byte(2)),
+            Some(0xB0) => format!("CC {} = {}", byte(1), byte(2)),
+            Some(0xC0) => format!("program {}", byte(1)),
+            Some(0xD0) => format!("pressure {}", byte(1)),
+            Some(0xE0) => {
+                let bend = (byte(2) as i32) << 7 | byte(1) as i32;
+                format!("{:+}", bend - 8192)
+            }
+            _ if self.bytes.first() == Some(&0xF0) => format!("{} bytes", self.bytes.len()),
+            _ => self.bytes[1.min(self.bytes.len())..]
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// The raw bytes in hex.
+    pub fn hex(&self) -> String {
+        self.bytes
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Clock, timecode quarter frames and active sensing, which repeat many
+    /// times a second.
+    fn is_timing(&self) -> bool {
+        matches!(
+            self.bytes.first(),
+            Some(&(CLOCK | QUARTER_FRAME | ACTIVE_SENSE))
+        )
+    }
+}
+
+#[derive(Default)]
+struct Recording {
+    messages: VecDeque<MidiMessage>,
+    paused: bool,
+    timing: bool,
+}
+
+/// Messages recorded by the MIDI input callback and output thread, shared
+/// with the monitor window.
+#[derive(Clone, Default)]
+pub struct MidiMonitor(Arc<Mutex<Recording>>);
+
+impl MidiMonitor {
+    pub fn record(&self, direction: Direction, port: &str, bytes: &[u8]) {
+        let Ok(mut recording) = self.0.lock() else {
+            return;
+        };
+        if recording.paused {
+            return;
+        }
+        let message = MidiMessage {
+            time: chrono::Local::now(),
+            direction,
+            port: port.to_string(),
+            bytes: bytes.to_vec(),
+        };
+        if message.is_timing() && !recording.timing {
+            return;
+        }
+        if recording.messages.len() == MAX_MESSAGES {
+            recording.messages.pop_front();
+        }
+        recording.messages.push_back(message);
+    }
+
+    /// The recorded messages, oldest first.
+    pub fn messages(&self) -> Vec<MidiMessage> {
+        self.0
+            .lock()
+            .map(|recording| recording.messages.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut recording) = self.0.lock() {
+            recording.messages.clear();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.0.lock().is_ok_and(|recording| recording.paused)
+    }
+
+    /// Stops recording while paused, so what is listed holds still.
+    pub fn set_paused(&self, paused: bool) {
+        if let Ok(mut recording) = self.0.lock() {
+            recording.paused = paused;
+        }
+    }
+
+    pub fn records_timing(&self) -> bool {
+        self.0.lock().is_ok_and(|recording| recording.timing)
+    }
+
+    /// Whether to record clock, quarter frames and active sensing.
+    pub fn set_timing(&self, timing: bool) {
+        if let Ok(mut recording) = self.0.lock() {
+            recording.timing = timing;
+        }
+    }
+}
+
+/// What the monitor window lists.
+pub struct MonitorFilter {
+    pub incoming: bool,
+    pub outgoing: bool,
+    /// 0 lists every channel, and messages with none.
+    pub channel: u8,
+}
+
+impl Default for MonitorFilter {
+    fn default() -> Self {
+        Self {
+            incoming: true,
+            outgoing: true,
+            channel: 0,
+        }
+    }
+}
+
+impl MonitorFilter {
+    pub fn matches(&self, message: &MidiMessage) -> bool {
+        let direction = match message.direction {
+            Direction::In => self.incoming,
+            Direction::Out => self.outgoing,
+        };
+        direction && (self.channel == 0 || message.channel() == Some(self.channel))
+    }
+}
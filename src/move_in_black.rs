@@ -0,0 +1,88 @@
+//! Move in black: fixtures that are dark get their position and colour for
+//! their next cue ahead of time, so they don't visibly move or change colour
+//! as they fade up. Run by the output engine, which keeps the marks between
+//! frames.
+
+use crate::cue::Cue;
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Fixtures currently marked, by id, with the index of the cue they were
+/// pre-positioned for.
+#[derive(Default)]
+pub struct MoveInBlack {
+    marks: HashMap<usize, usize>,
+}
+
+impl MoveInBlack {
+    /// Forgets the marks, for when the cue list changes under them.
+    pub fn clear(&mut self) {
+        self.marks.clear();
+    }
+
+    /// Marked fixtures by id, each with the index of its cue.
+    pub fn marks(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.marks.iter().map(|(&fixture, &cue)| (fixture, cue))
+    }
+
+    /// Marks dark fixtures whose next cue is less than `lead` away, and holds
+    /// marked fixtures at that cue's values until it has finished fading.
+    /// `frame` is the cue output at `elapsed`.
+    pub fn apply(
+        &mut self,
+        fixtures: &[Fixture],
+        cues: &[Cue],
+        elapsed: Duration,
+        lead: Duration,
+        frame: &mut DmxFrame,
+    ) {
+        for fixture in fixtures.iter().filter(|fixture| fixture.move_in_black) {
+            let Some(intensity) = fixture.channel_for(Attribute::Intensity) else {
+                continue;
+            };
+            let held = self.marks.get(&fixture.id).and_then(|&index| {
+                let cue = cues.get(index)?;
+                let marked_from = cue.start_time.saturating_sub(lead);
+                (elapsed >= marked_from && elapsed < cue.start_time + cue.duration).then_some(index)
+            });
+            let mark = held.or_else(|| {
+                if frame.get(fixture.universe, intensity) > 0 {
+                    return None;
+                }
+                let (index, next) = cues
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, cue)| {
+                        cue.start_time > elapsed
+                            && cue.is_live()
+                            && cue.values.iter().any(|v| v.fixture_id == fixture.id)
+                    })
+                    .min_by_key(|(_, cue)| cue.start_time)?;
+                let fades_up = next.values.iter().any(|v| {
+                    v.fixture_id == fixture.id && v.attribute == Attribute::Intensity && v.value > 0
+                });
+                (fades_up && next.start_time - elapsed <= lead).then_some(index)
+            });
+
+            match mark {
+                Some(index) => {
+                    self.marks.insert(fixture.id, index);
+                    for value in &cues[index].values {
+                        if value.fixture_id != fixture.id || value.attribute == Attribute::Intensity
+                        {
+                            continue;
+                        }
+                        if let Some(channel) = fixture.channel_for(value.attribute) {
+                            frame.set(fixture.universe, channel, value.value);
+                        }
+                    }
+                }
+                None => {
+                    self.marks.remove(&fixture.id);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Just enough OSC 1.0 to send messages over UDP, and to read incoming ones
+//! for trigger bindings and the OSC monitor.
+
+use eframe::egui;
+use std::fmt;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+    Str(String),
+}
+
+impl fmt::Display for OscArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OscArg::Int(value) => write!(f, "{}", value),
+            OscArg::Float(value) => write!(f, "{:?}", value),
+            OscArg::Str(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+/// A message read from a packet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+fn push_padded_str(buf: &mut Vec<u8>, text: &str) {
+    buf.extend_from_slice(text.as_bytes());
+    // Strings are null-terminated and padded to a multiple of four bytes.
+    let padding = 4 - text.len() % 4;
+    buf.extend(std::iter::repeat_n(0, padding));
+}
+
+pub fn encode(address: &str, args: &[OscArg]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    push_padded_str(&mut buf, address);
+
+    let tags: String = std::iter::once(',')
+        .chain(args.iter().map(|arg| match arg {
+            OscArg::Int(_) => 'i',
+            OscArg::Float(_) => 'f',
+            OscArg::Str(_) => 's',
+        }))
+        .collect();
+    push_padded_str(&mut buf, &tags);
+
+    for arg in args {
+        match arg {
+            OscArg::Int(value) => buf.extend_from_slice(&value.to_be_bytes()),
+            OscArg::Float(value) => buf.extend_from_slice(&value.to_be_bytes()),
+            OscArg::Str(value) => push_padded_str(&mut buf, value),
+        }
+    }
+    buf
+}
+
+/// Sends a single message to `target` (`host:port`).
+pub fn send(target: &str, address: &str, args: &[OscArg]) -> io::Result<()> {
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.send_to(&encode(address, args), target)?;
+    Ok(())
+}
+
+/// Reads space-separated arguments as typed into the UI: whole numbers become
+/// ints, other numbers floats, and anything else a string.
+pub fn parse_args(text: &str) -> Vec<OscArg> {
+    text.split_whitespace()
+        .map(|word| {
+            if let Ok(int) = word.parse() {
+                OscArg::Int(int)
+            } else if let Ok(float) = word.parse() {
+                OscArg::Float(float)
+            } else {
+                OscArg::Str(word.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Reads a null-terminated, padded string, returning it and the rest.
+fn read_padded_str(buf: &[u8]) -> Option<(&str, &[u8])> {
+    let end = buf.iter().position(|&b| b == 0)?;
+    let text = std::str::from_utf8(&buf[..end]).ok()?;
+    let next = (end / 4 + 1) * 4;
+    Some((text, buf.get(next..).unwrap_or_default()))
+}
+
+/// The messages in a packet, looking inside bundles. Arguments of types
+/// other than int, float and string end the argument list.
+pub fn decode(packet: &[u8]) -> Vec<OscMessage> {
+    let mut messages = Vec::new();
+    if let Some(mut elements) = packet.strip_prefix(b"#bundle\0") {
+        // Skip the time tag; each element is prefixed with its size.
+        elements = elements.get(8..).unwrap_or_default();
+        while let Some((size, rest)) = elements.split_first_chunk::<4>() {
+            let size = u32::from_be_bytes(*size) as usize;
+            let Some(element) = rest.get(..size) else {
+                break;
+            };
+            messages.extend(decode(element));
+            elements = &rest[size..];
+        }
+    } else if let Some((address, rest)) = read_padded_str(packet) {
+        if address.starts_with('/') {
+            messages.push(OscMessage {
+                address: address.to_string(),
+                args: decode_args(rest),
+            });
+        }
+    }
+    messages
+}
+
+fn decode_args(buf: &[u8]) -> Vec<OscArg> {
+    let Some((tags, mut data)) = read_padded_str(buf) else {
+        return Vec::new();
+    };
+    let mut args = Vec::new();
+    for tag in tags.chars().skip_while(|&c| c == ',') {
+        let arg = match tag {
+            'i' | 'f' => {
+                let Some((bytes, rest)) = data.split_first_chunk::<4>() else {
+                    break;
+                };
+                data = rest;
+                if tag == 'i' {
+                    OscArg::Int(i32::from_be_bytes(*bytes))
+                } else {
+                    OscArg::Float(f32::from_be_bytes(*bytes))
+                }
+            }
+            's' => {
+                let Some((text, rest)) = read_padded_str(data) else {
+                    break;
+                };
+                data = rest;
+                OscArg::Str(text.to_string())
+            }
+            _ => break,
+        };
+        args.push(arg);
+    }
+    args
+}
+
+/// Receives OSC on a background thread until dropped, passing on every
+/// message with where it came from.
+pub struct OscListener {
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl OscListener {
+    /// Listens on `port` at `address`, which is unspecified for all
+    /// interfaces.
+    pub fn start(
+        address: Ipv4Addr,
+        port: u16,
+        messages: Sender<(SocketAddr, OscMessage)>,
+        ctx: egui::Context,
+    ) -> io::Result<Self> {
+        let socket = UdpSocket::bind((address, port))?;
+        // Wake up periodically so the thread exits once stopped.
+        socket.set_read_timeout(Some(Duration::from_millis(250)))?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = shutdown.clone();
+        let thread = thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while !stop.load(Ordering::Relaxed) {
+                let Ok((len, from)) = socket.recv_from(&mut buf) else {
+                    continue;
+                };
+                for message in decode(&buf[..len]) {
+                    let _ = messages.send((from, message));
+                }
+                ctx.request_repaint();
+            }
+        });
+        Ok(Self {
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for OscListener {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Wait for the socket to close so the port can be bound again.
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
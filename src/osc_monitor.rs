@@ -0,0 +1,145 @@
+//! The OSC monitor: recent OSC traffic in and out, and the addresses Halo
+//! answers to or sends, so integrators can see what to send without leaving
+//! the app.
+
+use crate::binding::BindingSource;
+use crate::i18n::tr;
+use crate::midi_monitor::Direction;
+use crate::osc::{self, OscArg, OscMessage};
+use crate::trigger::TriggerAction;
+use crate::HaloApp;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io;
+
+/// The monitor keeps this many messages, dropping the oldest.
+const MAX_MESSAGES: usize = 500;
+
+#[derive(Clone)]
+pub struct OscTraffic {
+    pub time: chrono::DateTime<chrono::Local>,
+    pub direction: Direction,
+    /// Where the message came from, or where it went.
+    pub peer: String,
+    pub message: OscMessage,
+}
+
+#[derive(Default)]
+pub struct OscMonitor {
+    traffic: VecDeque<OscTraffic>,
+    /// The last arguments seen at each address, in or out.
+    values: HashMap<String, (Vec<OscArg>, chrono::DateTime<chrono::Local>)>,
+    pub paused: bool,
+}
+
+impl OscMonitor {
+    pub fn record(&mut self, direction: Direction, peer: String, message: &OscMessage) {
+        let time = chrono::Local::now();
+        self.values
+            .insert(message.address.clone(), (message.args.clone(), time));
+        if self.paused {
+            return;
+        }
+        if self.traffic.len() == MAX_MESSAGES {
+            self.traffic.pop_front();
+        }
+        self.traffic.push_back(OscTraffic {
+            time,
+            direction,
+            peer,
+            message: message.clone(),
+        });
+    }
+
+    /// Recent messages, oldest first.
+    pub fn traffic(&self) -> impl Iterator<Item = &OscTraffic> {
+        self.traffic.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.traffic.clear();
+        self.values.clear();
+    }
+}
+
+/// One level of the address tree: the notes on this address and the
+/// addresses below it.
+#[derive(Default)]
+pub struct AddressNode {
+    pub notes: Vec<String>,
+    pub children: BTreeMap<String, AddressNode>,
+}
+
+impl AddressNode {
+    fn insert(&mut self, address: &str, note: String) {
+        let mut node = self;
+        for part in address.split('/').filter(|part| !part.is_empty()) {
+            node = node.children.entry(part.to_string()).or_default();
+        }
+        if !node.notes.contains(&note) {
+            node.notes.push(note);
+        }
+    }
+}
+
+impl HaloApp {
+    /// Sends an OSC message, recording it in the monitor.
+    pub(crate) fn send_osc(
+        &mut self,
+        target: &str,
+        address: &str,
+        args: &[OscArg],
+    ) -> io::Result<()> {
+        self.osc_monitor.record(
+            Direction::Out,
+            target.to_string(),
+            &OscMessage {
+                address: address.to_string(),
+                args: args.to_vec(),
+            },
+        );
+        osc::send(target, address, args)
+    }
+
+    /// Every address Halo answers to through a trigger binding, sends from a
+    /// cue, or has seen, with what it does and its last value.
+    pub(crate) fn osc_address_tree(&self) -> AddressNode {
+        let mut root = AddressNode::default();
+        for binding in &self.show.bindings {
+            if let BindingSource::Osc { address } = &binding.source {
+                root.insert(
+                    address,
+                    tr!("osc-monitor-bound", action = binding.action.to_string()),
+                );
+            }
+        }
+        for cue in &self.show.cues {
+            for trigger in &cue.triggers {
+                if let TriggerAction::Osc {
+                    target, address, ..
+                } = &trigger.action
+                {
+                    root.insert(
+                        address,
+                        tr!(
+                            "osc-monitor-sent",
+                            target = target.as_str(),
+                            number = cue.number.to_string()
+                        ),
+                    );
+                }
+            }
+        }
+        for (address, (args, time)) in &self.osc_monitor.values {
+            let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+            root.insert(
+                address,
+                tr!(
+                    "osc-monitor-last",
+                    args = args.join(", "),
+                    time = time.format("%H:%M:%S").to_string()
+                ),
+            );
+        }
+        root
+    }
+}
@@ -0,0 +1,281 @@
+//! Which parts of the output pipeline put the level on each channel, worked
+//! out by the engine as it renders so the UI can explain a level nobody
+//! expected: a cue still fading, busk mode, a pixel map, a wing on the
+//! network input or a group master holding it down. Each channel keeps the
+//! chain of stages that touched it, in pipeline order.
+//!
+//! Working the chains out costs more than the rest of a frame on a big rig,
+//! so the engine only does it while the UI is showing them.
+
+use crate::busk::{BuskSettings, BuskTemplate};
+use crate::cue::Cue;
+use crate::dmx::{DmxFrame, UNIVERSE_SIZE};
+use crate::fixture::{Attribute, ControlKind, Fixture};
+use crate::group_master::GroupMaster;
+use crate::pixel::PixelMap;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Owner {
+    /// The cue at this position in the cue list.
+    Cue(usize),
+    /// Pre-positioned while dark for the cue at this position.
+    MoveInBlack(usize),
+    Busk,
+    /// The pixel map at this position.
+    PixelMap(usize),
+    HazeSchedule,
+    SmokePad,
+    /// Art-Net or sACN merged in from the network.
+    Input,
+    QuickConsole,
+    /// The follow spot at this position, aiming pan and tilt.
+    FollowSpot(usize),
+    /// The flash on the pad at this position.
+    Flash(usize),
+    /// Dimmed by a solo flash.
+    FlashSolo,
+    /// The group master at this position, scaling the level down.
+    GroupMaster(usize),
+    GrandMaster,
+    /// The fixture's invert, limits, curve or position adjustment.
+    Processing,
+    /// Dark because the fixture is inhibited.
+    Inhibited,
+    /// Held by a running lamp or reset macro.
+    Control(ControlKind),
+    ChannelCheck,
+    TestPattern,
+    Panic,
+    Release,
+    /// Played back from a recording of the output.
+    Recording,
+}
+
+impl Owner {
+    /// Whether the stage sets the level rather than adjusting what an
+    /// earlier stage set.
+    pub fn sets_level(&self) -> bool {
+        !matches!(
+            self,
+            Owner::GroupMaster(_) | Owner::GrandMaster | Owner::Processing
+        )
+    }
+}
+
+/// The stages that touched every channel that has any, by universe and
+/// 1-based channel.
+#[derive(Clone, Default)]
+pub struct Owners {
+    chains: HashMap<(u16, u16), Vec<Owner>>,
+    /// Claims are ignored, for frames nobody is looking at the owners of.
+    untracked: bool,
+}
+
+impl Owners {
+    /// Owners that stay empty whatever claims them.
+    pub fn untracked() -> Self {
+        Self {
+            chains: HashMap::new(),
+            untracked: true,
+        }
+    }
+
+    /// Forgets every chain, for a stage that replaces the whole output.
+    pub fn clear(&mut self) {
+        self.chains.clear();
+    }
+
+    /// Every stage that touched a channel, earliest first.
+    pub fn chain(&self, universe: u16, channel: u16) -> &[Owner] {
+        self.chains
+            .get(&(universe, channel))
+            .map_or(&[], |chain| chain.as_slice())
+    }
+
+    /// The last stage to set a channel's level, ignoring the ones that only
+    /// adjusted it.
+    pub fn get(&self, universe: u16, channel: u16) -> Option<Owner> {
+        self.chain(universe, channel)
+            .iter()
+            .rev()
+            .find(|owner| owner.sets_level())
+            .copied()
+    }
+
+    /// The owner of one attribute of `fixture`, if it has that attribute and
+    /// anything set it.
+    pub fn attribute(&self, fixture: &Fixture, attribute: Attribute) -> Option<Owner> {
+        self.get(fixture.universe, fixture.channel_for(attribute)?)
+    }
+
+    /// Adds `owner` to the end of a channel's chain.
+    pub fn claim(&mut self, universe: u16, channel: u16, owner: Owner) {
+        if self.untracked {
+            return;
+        }
+        let chain = self.chains.entry((universe, channel)).or_default();
+        if chain.last() != Some(&owner) {
+            chain.push(owner);
+        }
+    }
+
+    pub fn claim_attribute(&mut self, fixture: &Fixture, attribute: Attribute, owner: Owner) {
+        if let Some(channel) = fixture.channel_for(attribute) {
+            self.claim(fixture.universe, channel, owner);
+        }
+    }
+
+    /// Gives `owner` every channel whose level differs between `before` and
+    /// `after`, for stages that only sometimes win a channel.
+    pub fn claim_changed(&mut self, before: &DmxFrame, after: &DmxFrame, owner: Owner) {
+        if self.untracked {
+            return;
+        }
+        for universe in after.universe_numbers() {
+            let Some(data) = after.universe(universe) else {
+                continue;
+            };
+            for (index, &level) in data.iter().enumerate() {
+                let channel = index as u16 + 1;
+                if before.get(universe, channel) != level {
+                    self.claim(universe, channel, owner);
+                }
+            }
+        }
+    }
+
+    /// Gives `owner` every channel in `frame`, for stages that replace the
+    /// whole output.
+    pub fn claim_all(&mut self, frame: &DmxFrame, owner: Owner) {
+        if self.untracked {
+            return;
+        }
+        for universe in frame.universe_numbers() {
+            for channel in 1..=UNIVERSE_SIZE as u16 {
+                self.claim(universe, channel, owner);
+            }
+        }
+    }
+
+    /// Gives busk mode what its look playing `beats` beats in sets, as
+    /// `BuskSettings::render` does.
+    pub fn claim_busk(&mut self, busk: &BuskSettings, fixtures: &[Fixture], beats: f32) {
+        let Some(look) = busk.look_at(beats) else {
+            return;
+        };
+        for fixture in fixtures
+            .iter()
+            .filter(|fixture| busk.groups.contains(&fixture.profile.name))
+        {
+            let mut attributes = vec![Attribute::Intensity];
+            if fixture.channel_for(Attribute::Red).is_some() {
+                attributes.extend([Attribute::Red, Attribute::Green, Attribute::Blue]);
+            }
+            if look.template == BuskTemplate::Sweep {
+                attributes.extend([Attribute::Pan, Attribute::Tilt]);
+            }
+            for attribute in attributes {
+                self.claim_attribute(fixture, attribute, Owner::Busk);
+            }
+        }
+    }
+
+    /// Adds the group master holding each fixture's intensity down, as
+    /// `group_master::apply` picks it.
+    pub fn claim_group_masters(&mut self, masters: &[GroupMaster], fixtures: &[Fixture]) {
+        for fixture in fixtures {
+            let lowest = masters
+                .iter()
+                .enumerate()
+                .filter(|(_, master)| master.groups.contains(&fixture.profile.name))
+                .min_by(|(_, a), (_, b)| a.level.total_cmp(&b.level))
+                .filter(|(_, master)| master.level < 1.0);
+            if let Some((index, _)) = lowest {
+                self.claim_attribute(fixture, Attribute::Intensity, Owner::GroupMaster(index));
+            }
+        }
+    }
+
+    /// Adds the channels each fixture's output processing touches.
+    pub fn claim_processing(&mut self, fixtures: &[Fixture]) {
+        for fixture in fixtures {
+            if !fixture.position.is_default() {
+                self.claim_attribute(fixture, Attribute::Pan, Owner::Processing);
+                self.claim_attribute(fixture, Attribute::Tilt, Owner::Processing);
+            }
+            for processing in &fixture.processing {
+                self.claim_attribute(fixture, processing.attribute, Owner::Processing);
+            }
+        }
+    }
+
+    /// Gives the inhibited fixtures' channels to `Owner::Inhibited`.
+    pub fn claim_inhibited(&mut self, channels: &HashSet<(u16, u16)>) {
+        for &(universe, channel) in channels {
+            self.claim(universe, channel, Owner::Inhibited);
+        }
+    }
+
+    /// Gives the pixel map at `index` every channel it is wired to.
+    pub fn claim_pixel_map(&mut self, map: &PixelMap, index: usize) {
+        for pixel in 0..map.pixel_count() {
+            let (universe, channel) = map.pixel_address(pixel);
+            for offset in 0..3 {
+                self.claim(universe, channel + offset, Owner::PixelMap(index));
+            }
+        }
+    }
+}
+
+/// The live cues behind each fixture attribute, with the same
+/// latest-takes-precedence order as `dmx::render`: the last cue to finish
+/// fading it, then any still fading from there. `cues` are in list order.
+pub fn cue_owners(fixtures: &[Fixture], cues: &[Cue]) -> Owners {
+    let mut chains: HashMap<(usize, Attribute), Vec<usize>> = HashMap::new();
+    for (index, cue) in live_order(cues) {
+        for value in &cue.values {
+            let chain = chains
+                .entry((value.fixture_id, value.attribute))
+                .or_default();
+            // A finished fade hides everything before it.
+            if cue.fade_level(value.fixture_id, value.attribute) >= 1.0 {
+                chain.clear();
+            }
+            chain.push(index);
+        }
+    }
+
+    let mut owners = Owners::default();
+    for fixture in fixtures {
+        for attribute in &fixture.profile.channels {
+            for &index in chains.get(&(fixture.id, *attribute)).into_iter().flatten() {
+                owners.claim_attribute(fixture, *attribute, Owner::Cue(index));
+            }
+        }
+    }
+    owners
+}
+
+/// The live cues that have started, by position, in start-time order.
+fn live_order(cues: &[Cue]) -> Vec<(usize, &Cue)> {
+    let mut order: Vec<(usize, &Cue)> = cues
+        .iter()
+        .enumerate()
+        .filter(|(_, cue)| cue.has_started() && cue.is_live())
+        .collect();
+    order.sort_by_key(|(_, cue)| cue.start_time);
+    order
+}
+
+/// The cue each fixture's `attribute` comes from, by fixture id: the one
+/// `cue_owners` would leave owning it, without working out the chains.
+pub fn attribute_cues(cues: &[Cue], attribute: Attribute) -> HashMap<usize, usize> {
+    let mut owning = HashMap::new();
+    for (index, cue) in live_order(cues) {
+        for value in cue.values.iter().filter(|v| v.attribute == attribute) {
+            owning.insert(value.fixture_id, index);
+        }
+    }
+    owning
+}
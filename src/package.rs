@@ -0,0 +1,130 @@
+//! `.haloshow` packages: a zip archive holding the show JSON together with its
+//! audio files and any custom fixture profiles, so a show can move between
+//! machines without broken file paths.
+
+use crate::fixture::{self, FixtureProfile};
+use crate::schema;
+use crate::show::Show;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub const EXTENSION: &str = "haloshow";
+
+const SHOW_ENTRY: &str = "show.json";
+const AUDIO_DIR: &str = "audio/";
+const PROFILE_DIR: &str = "profiles/";
+
+/// Sniffs the zip signature rather than trusting the file extension.
+pub fn is_package(path: &Path) -> io::Result<bool> {
+    let mut magic = [0u8; 4];
+    let mut file = File::open(path)?;
+    match file.read_exact(&mut magic) {
+        Ok(()) => Ok(&magic == b"PK\x03\x04"),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn save(show: &Show, path: &Path) -> io::Result<()> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    // The packaged show points at the archive entries instead of the local
+    // paths.
+    let mut packaged = show.clone();
+    let mut written = HashSet::new();
+    if let Some(audio) = &mut packaged.audio_file {
+        *audio = pack_audio(&mut zip, options, audio, &mut written)?;
+    }
+    for song in &mut packaged.songs {
+        if let Some(audio) = &mut song.audio_file {
+            *audio = pack_audio(&mut zip, options, audio, &mut written)?;
+        }
+    }
+
+    for profile in show.custom_profiles() {
+        zip.start_file(format!("{}{}", PROFILE_DIR, profile.file_name()), options)?;
+        serde_json::to_writer_pretty(&mut zip, profile)?;
+    }
+
+    zip.start_file(SHOW_ENTRY, options)?;
+    serde_json::to_writer_pretty(&mut zip, &packaged)?;
+    zip.finish()?;
+    Ok(())
+}
+
+/// Writes an audio file into the package once, however many songs share it,
+/// returning its entry name.
+fn pack_audio(
+    zip: &mut ZipWriter<File>,
+    options: SimpleFileOptions,
+    audio: &Path,
+    written: &mut HashSet<String>,
+) -> io::Result<PathBuf> {
+    let name = audio
+        .file_name()
+        .ok_or_else(|| io::Error::other("audio file has no file name"))?;
+    let entry = format!("{}{}", AUDIO_DIR, name.to_string_lossy());
+    if written.insert(entry.clone()) {
+        zip.start_file(entry.as_str(), options)?;
+        io::copy(&mut File::open(audio)?, zip)?;
+    }
+    Ok(PathBuf::from(entry))
+}
+
+/// Unpacks the audio file at `entry` of the package at `path`, returning
+/// where it went.
+fn unpack_audio(archive: &mut ZipArchive<File>, entry: &Path, path: &Path) -> io::Result<PathBuf> {
+    let entry = entry.to_string_lossy().replace('\\', "/");
+    let mut source = archive.by_name(&entry)?;
+    let target = source
+        .enclosed_name()
+        .map(|name| extract_dir(path).map(|dir| dir.join(name)))
+        .ok_or_else(|| io::Error::other(format!("unsafe entry name {}", entry)))??;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    io::copy(&mut source, &mut File::create(&target)?)?;
+    Ok(target)
+}
+
+/// Where the media of a package is unpacked: a per-package folder in the
+/// local data directory, so re-opening the same package reuses it.
+fn extract_dir(path: &Path) -> io::Result<PathBuf> {
+    let stem = path
+        .file_stem()
+        .ok_or_else(|| io::Error::other("package has no file name"))?;
+    let base = dirs::data_local_dir().ok_or_else(|| io::Error::other("no data directory"))?;
+    Ok(base.join("halo").join("packages").join(stem))
+}
+
+/// Opens a package, unpacking its audio next to the other extracted packages
+/// and installing its custom profiles into the user library. Also the
+/// version of the show format it was saved in.
+pub fn load(path: &Path) -> io::Result<(Show, u32)> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let (mut show, version) = schema::read(archive.by_name(SHOW_ENTRY)?)?;
+
+    if let Some(audio) = &mut show.audio_file {
+        *audio = unpack_audio(&mut archive, audio, path)?;
+    }
+    for song in &mut show.songs {
+        if let Some(audio) = &mut song.audio_file {
+            *audio = unpack_audio(&mut archive, audio, path)?;
+        }
+    }
+
+    for i in 0..archive.len() {
+        let file = archive.by_index(i)?;
+        if file.is_file() && file.name().starts_with(PROFILE_DIR) {
+            let profile: FixtureProfile = serde_json::from_reader(file)?;
+            fixture::install_profile(&profile)?;
+        }
+    }
+
+    Ok((show, version))
+}
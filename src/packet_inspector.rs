@@ -0,0 +1,255 @@
+//! The packet inspector: every Art-Net and sACN DMX packet the network input
+//! receives, by source and universe, with the sequence numbers checked for
+//! packets lost or arriving out of order, so a flaky switch or two consoles
+//! sending the same universe can be found at FOH without Wireshark.
+//!
+//! It sees whatever reaches the input's ports, including universes the
+//! input isn't merging, and only while it is capturing, so it costs nothing
+//! when its window is closed.
+
+use crate::dmx_input::{Packet, Source};
+use crate::i18n::tr;
+use std::collections::VecDeque;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The log keeps this many packets, dropping the oldest, and as many again
+/// of those with something wrong, which would otherwise scroll away in
+/// seconds at 44 packets a second a universe.
+const MAX_PACKETS: usize = 500;
+
+/// A stream that has sent nothing for this long is shown as gone quiet.
+const QUIET: Duration = Duration::from_millis(2500);
+
+/// E1.31's window for a sequence number that went backwards: within it the
+/// packet is late, beyond it the sender has started again.
+const REORDER_WINDOW: i16 = 20;
+
+/// What the sequence number said about a packet.
+#[derive(Clone, Copy, PartialEq)]
+pub enum SequenceEvent {
+    /// This many packets before it never arrived.
+    Gap(u8),
+    /// It arrived after a later one.
+    OutOfOrder,
+    /// The sender said it is stopping.
+    Terminated,
+}
+
+impl fmt::Display for SequenceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SequenceEvent::Gap(lost) => f.write_str(&tr!("sequence-lost", count = *lost)),
+            SequenceEvent::OutOfOrder => f.write_str(&tr!("sequence-out-of-order")),
+            SequenceEvent::Terminated => f.write_str(&tr!("sequence-terminated")),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PacketRecord {
+    pub time: chrono::DateTime<chrono::Local>,
+    pub from: SocketAddr,
+    pub protocol: &'static str,
+    pub universe: u16,
+    /// 0 for Art-Net senders that don't number their packets.
+    pub sequence: u8,
+    pub event: Option<SequenceEvent>,
+}
+
+/// One sender's packets on one universe.
+#[derive(Clone)]
+pub struct Stream {
+    pub universe: u16,
+    pub from: IpAddr,
+    pub protocol: &'static str,
+    /// The sACN source name, or the sender's address for Art-Net.
+    pub name: String,
+    pub priority: u8,
+    pub packets: u64,
+    pub lost: u64,
+    pub out_of_order: u64,
+    /// Packets a second, smoothed over the last few seconds.
+    pub rate: f32,
+    last_sequence: u8,
+    last: Instant,
+}
+
+impl Stream {
+    pub fn is_quiet(&self) -> bool {
+        self.last.elapsed() > QUIET
+    }
+
+    pub fn since_last(&self) -> Duration {
+        self.last.elapsed()
+    }
+}
+
+/// How far `new` is on from `last`. Art-Net counts 1 to 255 and back to 1,
+/// skipping the 0 that means it isn't counting; sACN counts through 0.
+fn step(protocol: &str, last: u8, new: u8) -> i16 {
+    let mut step = new.wrapping_sub(last) as i8 as i16;
+    if protocol == "Art-Net" && new < last && step > 0 {
+        step -= 1;
+    }
+    step
+}
+
+#[derive(Default)]
+struct Log {
+    streams: Vec<Stream>,
+    packets: VecDeque<PacketRecord>,
+    problems: VecDeque<PacketRecord>,
+}
+
+impl Log {
+    fn dmx(&mut self, from: SocketAddr, source: &Source) -> Option<SequenceEvent> {
+        let now = Instant::now();
+        let found = self
+            .streams
+            .iter_mut()
+            .find(|s| s.universe == source.universe && s.from == from.ip());
+        let Some(stream) = found else {
+            self.streams.push(Stream {
+                universe: source.universe,
+                from: from.ip(),
+                protocol: source.protocol,
+                name: source.name.clone(),
+                priority: source.priority,
+                packets: 1,
+                lost: 0,
+                out_of_order: 0,
+                rate: 0.0,
+                last_sequence: source.sequence,
+                last: now,
+            });
+            self.streams.sort_by_key(|s| (s.universe, s.from));
+            return None;
+        };
+        let interval = now - stream.last;
+        stream.rate = stream.rate * 0.9 + 0.1 / interval.as_secs_f32().max(0.001);
+        stream.packets += 1;
+        stream.last = now;
+        stream.name.clone_from(&source.name);
+        stream.priority = source.priority;
+        let counting = source.sequence != 0 && stream.last_sequence != 0;
+        let step = step(source.protocol, stream.last_sequence, source.sequence);
+        let event = match step {
+            _ if !counting => None,
+            1 => None,
+            step if step <= 0 && step > -REORDER_WINDOW => {
+                stream.out_of_order += 1;
+                return Some(SequenceEvent::OutOfOrder);
+            }
+            step if step > 1 => {
+                stream.lost += step as u64 - 1;
+                Some(SequenceEvent::Gap(step as u8 - 1))
+            }
+            // Far enough back that the sender has started counting again.
+            _ => None,
+        };
+        stream.last_sequence = source.sequence;
+        event
+    }
+}
+
+/// The inspector's capture, shared between the receive threads and the UI.
+#[derive(Clone, Default)]
+pub struct PacketInspector {
+    capturing: Arc<AtomicBool>,
+    log: Arc<Mutex<Log>>,
+}
+
+impl PacketInspector {
+    pub fn set_capturing(&self, capturing: bool) {
+        self.capturing.store(capturing, Ordering::Relaxed);
+    }
+
+    /// Notes a packet the network input received.
+    pub(crate) fn record(&self, from: SocketAddr, packet: &Packet) {
+        if !self.capturing.load(Ordering::Relaxed) {
+            return;
+        }
+        let Ok(mut log) = self.log.lock() else {
+            return;
+        };
+        let (protocol, universe, sequence, event) = match packet {
+            Packet::Dmx(source) | Packet::Priorities(source) => (
+                source.protocol,
+                source.universe,
+                source.sequence,
+                log.dmx(from, source),
+            ),
+            Packet::Terminated(universe) => {
+                log.streams
+                    .retain(|s| !(s.universe == *universe && s.from == from.ip()));
+                ("sACN", *universe, 0, Some(SequenceEvent::Terminated))
+            }
+        };
+        let record = PacketRecord {
+            time: chrono::Local::now(),
+            from,
+            protocol,
+            universe,
+            sequence,
+            event,
+        };
+        if event.is_some() {
+            if log.problems.len() == MAX_PACKETS {
+                log.problems.pop_front();
+            }
+            log.problems.push_back(record.clone());
+        }
+        if log.packets.len() == MAX_PACKETS {
+            log.packets.pop_front();
+        }
+        log.packets.push_back(record);
+    }
+
+    /// Every stream seen since the capture was cleared, by universe.
+    pub fn streams(&self) -> Vec<Stream> {
+        self.log
+            .lock()
+            .map(|log| log.streams.clone())
+            .unwrap_or_default()
+    }
+
+    /// Recent packets, or only those with something wrong, oldest first.
+    pub fn packets(&self, problems_only: bool) -> Vec<PacketRecord> {
+        let Ok(log) = self.log.lock() else {
+            return Vec::new();
+        };
+        let packets = if problems_only {
+            &log.problems
+        } else {
+            &log.packets
+        };
+        packets.iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut log) = self.log.lock() {
+            *log = Log::default();
+        }
+    }
+}
+
+/// Universes more than one sender is sending right now.
+pub fn duplicated(streams: &[Stream]) -> Vec<u16> {
+    let mut universes: Vec<u16> = streams
+        .iter()
+        .filter(|stream| !stream.is_quiet())
+        .map(|stream| stream.universe)
+        .collect();
+    universes.sort_unstable();
+    let mut duplicated: Vec<u16> = universes
+        .windows(2)
+        .filter(|pair| pair[0] == pair[1])
+        .map(|pair| pair[0])
+        .collect();
+    duplicated.dedup();
+    duplicated
+}
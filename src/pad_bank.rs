@@ -0,0 +1,54 @@
+//! Pad banks that follow the show: a bank linked to a setlist song or a cue
+//! list section comes up when playback gets there, so that song's specials
+//! are already under the operator's fingers. The operator can still switch
+//! banks by hand; the linked bank only comes back when the song or section
+//! changes again.
+
+use crate::cue;
+use crate::HaloApp;
+
+impl HaloApp {
+    /// The section label of the cue list section the current cue is in.
+    fn current_section(&self) -> Option<String> {
+        let current = self.current_cue()?;
+        let index = self
+            .show
+            .cues
+            .iter()
+            .position(|cue| std::ptr::eq(cue, current))?;
+        cue::sections(&self.show.cues)
+            .into_iter()
+            .find(|(_, range)| range.contains(&index))?
+            .0
+    }
+
+    /// The bank linked to the current section or, in setlist mode, song.
+    /// A section's bank wins over its song's.
+    fn linked_pad_bank(&self) -> Option<usize> {
+        let banks = &self.show.pad_layout.banks;
+        if banks.iter().any(|bank| !bank.sections.is_empty()) {
+            if let Some(section) = self.current_section() {
+                if let Some(index) = banks
+                    .iter()
+                    .position(|bank| bank.sections.contains(&section))
+                {
+                    return Some(index);
+                }
+            }
+        }
+        let song = self.setlist_song()?.id;
+        banks.iter().position(|bank| bank.songs.contains(&song))
+    }
+
+    /// Brings up the linked bank when the song or section changes.
+    pub(crate) fn update_pad_bank(&mut self) {
+        let linked = self.linked_pad_bank();
+        if linked == self.followed_pad_bank {
+            return;
+        }
+        self.followed_pad_bank = linked;
+        if let Some(bank) = linked {
+            self.pad_bank = bank;
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! What pads do when let go of. A pad can latch on and off, stay on only
+//! while held, or go off by itself a number of seconds or bars after going
+//! on, and can fade out over a set time rather than snap off. The fade shows
+//! in the pad's level, which the Smoke pad's haze output follows.
+
+use crate::show::PadRelease;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+impl HaloApp {
+    /// Switches the pad at `index` on or off, starting its timed release or
+    /// its fade-out. Returns whether it went on from off, for the caller to
+    /// run what the pad triggers.
+    pub(crate) fn latch_pad(&mut self, index: usize, active: bool) -> bool {
+        let beats_per_bar = self.show.time_signature.beats_per_bar();
+        let bar = Duration::from_secs_f32(60.0 / self.bpm.max(1.0) * beats_per_bar as f32);
+        let Some(pad) = self.show.pads.get_mut(index) else {
+            return false;
+        };
+        let was_active = pad.active;
+        pad.active = active;
+        let now = Instant::now();
+        if active && !was_active {
+            pad.released_at = None;
+            pad.release_at = match pad.release {
+                PadRelease::Seconds(seconds) => {
+                    Some(now + Duration::from_secs_f32(seconds.max(0.0)))
+                }
+                PadRelease::Bars(bars) => Some(now + bar * bars.max(1)),
+                PadRelease::Toggle | PadRelease::Momentary => None,
+            };
+        } else if !active && was_active {
+            pad.release_at = None;
+            pad.released_at = Some(now);
+        }
+        active && !was_active
+    }
+
+    /// Lets timed pads go once their time is up, and keeps the screen
+    /// drawing while a pad fades out.
+    pub(crate) fn update_pads(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        let due: Vec<usize> = (0..self.show.pads.len())
+            .filter(|&i| {
+                let pad = &self.show.pads[i];
+                pad.active && pad.release_at.is_some_and(|at| now >= at)
+            })
+            .collect();
+        for index in due {
+            self.latch_pad(index, false);
+        }
+        let fading = self.show.pads.iter().any(|pad| {
+            !pad.active
+                && pad
+                    .released_at
+                    .is_some_and(|at| now.duration_since(at) < pad.fade_out)
+        });
+        let timed = self.show.pads.iter().any(|pad| pad.release_at.is_some());
+        if fading {
+            ctx.request_repaint();
+        } else if timed {
+            ctx.request_repaint_after(Duration::from_millis(50));
+        }
+    }
+}
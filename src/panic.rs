@@ -0,0 +1,160 @@
+//! The panic safe state: a look kept with the show (house lights up, effects
+//! off) that the Panic button fades the rig to, stopping playback until the
+//! operator resumes.
+
+use crate::cue::{Cue, CueNumber, CueValue};
+use crate::dmx::{self, DmxFrame, UNIVERSE_SIZE};
+use crate::fixture::Fixture;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::processing;
+use crate::transport::Transport;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafeState {
+    /// Levels of the safe look; everything else goes to zero.
+    pub values: Vec<CueValue>,
+    pub fade: Duration,
+}
+
+impl Default for SafeState {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            fade: Duration::from_secs(3),
+        }
+    }
+}
+
+impl SafeState {
+    /// The safe look rendered for `fixtures`.
+    fn render(&self, fixtures: &[Fixture]) -> DmxFrame {
+        let mut look = Cue::new(CueNumber::ZERO, "Safe state", 0, 0);
+        look.values = self.values.clone();
+        look.progress = 1.0;
+        let mut frame = dmx::render(fixtures, &[look]);
+        processing::apply(fixtures, &mut frame);
+        frame
+    }
+}
+
+/// What the output does when playback stops, a show is loaded or Halo quits.
+/// Stopping a rig dead can strobe it, so it can fade out instead.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StopOutput {
+    /// Leave the last frame on air.
+    Hold,
+    /// Fade everything to zero over the stop fade time.
+    FadeOut,
+    /// Cut to the show's safe state.
+    SafeState,
+}
+
+impl StopOutput {
+    pub const ALL: [StopOutput; 3] = [StopOutput::Hold, StopOutput::FadeOut, StopOutput::SafeState];
+}
+
+impl fmt::Display for StopOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            StopOutput::Hold => tr!("stop-output-hold"),
+            StopOutput::FadeOut => tr!("stop-output-fade"),
+            StopOutput::SafeState => tr!("stop-output-safe-state"),
+        })
+    }
+}
+
+/// The safe state, or any other look the output engine fades to from what
+/// was on air, as the engine plays it.
+#[derive(Clone, PartialEq)]
+pub struct Panic {
+    look: DmxFrame,
+    fade: Duration,
+    pub started: Instant,
+}
+
+impl Panic {
+    /// How much of the fade is left at `now`.
+    pub fn remaining(&self, now: Instant) -> Duration {
+        (self.started + self.fade).saturating_duration_since(now)
+    }
+
+    /// The output `fade` into the panic, crossfading from `from`, the frame
+    /// on air when the panic started.
+    pub fn frame(&self, from: &DmxFrame, now: Instant) -> DmxFrame {
+        let t = if self.fade.is_zero() {
+            1.0
+        } else {
+            (now.saturating_duration_since(self.started).as_secs_f32() / self.fade.as_secs_f32())
+                .min(1.0)
+        };
+        let mut frame = self.look.clone();
+        for universe in from.universe_numbers() {
+            for channel in 1..=UNIVERSE_SIZE as u16 {
+                let start = from.get(universe, channel) as f32;
+                let end = self.look.get(universe, channel) as f32;
+                frame.set(universe, channel, (start + (end - start) * t).round() as u8);
+            }
+        }
+        frame
+    }
+}
+
+impl HaloApp {
+    /// Releases the output as set in the preferences, once playback has
+    /// stopped. Returns how long the release takes. It lasts until playback
+    /// starts or the playhead is moved.
+    pub(crate) fn release_output(&mut self) -> Duration {
+        let (look, fade) = match self.preferences.stop_output {
+            StopOutput::Hold => return Duration::ZERO,
+            StopOutput::FadeOut => (DmxFrame::default(), self.preferences.stop_fade),
+            StopOutput::SafeState => (
+                self.show.safe_state.render(&self.show.fixtures),
+                Duration::ZERO,
+            ),
+        };
+        self.release = Some(Panic {
+            look,
+            fade,
+            started: Instant::now(),
+        });
+        fade
+    }
+
+    /// Fades the rig to the safe state and stops playback.
+    pub(crate) fn engage_panic(&mut self) {
+        if self.panic.is_some() {
+            return;
+        }
+        let safe = &self.show.safe_state;
+        self.panic = Some(Panic {
+            look: safe.render(&self.show.fixtures),
+            fade: safe.fade,
+            started: Instant::now(),
+        });
+        let message = tr!(
+            "safe-panic-fading",
+            seconds = format!("{:.1}", safe.fade.as_secs_f32())
+        );
+        self.notify(LogLevel::Warning, message);
+        self.resume_running = self.transport.is_running();
+        self.set_transport(Transport::Stopped);
+    }
+
+    /// Leaves the safe state, carrying on playing if the show was running
+    /// when the panic started.
+    pub(crate) fn resume_from_panic(&mut self) {
+        if self.panic.take().is_none() {
+            return;
+        }
+        self.log.info(tr!("safe-resumed"));
+        if self.resume_running {
+            self.set_transport(Transport::Running);
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Importing a patch from site paperwork: MVR files from a visualiser or
+//! console, or the CSV patch exports of grandMA and ETC consoles. Fixture
+//! types are matched to Halo profiles by name where possible, and the rest
+//! are left for the operator to reconcile.
+
+use crate::dmx::UNIVERSE_SIZE;
+use crate::fixture::FixtureProfile;
+use crate::i18n::tr;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use zip::ZipArchive;
+
+const SCENE_ENTRY: &str = "GeneralSceneDescription.xml";
+
+/// A fixture as described by the imported file.
+#[derive(Clone)]
+pub struct ImportedFixture {
+    pub name: String,
+    /// The console's or GDTF's name for the fixture type, and mode if known.
+    pub fixture_type: String,
+    pub universe: u16,
+    pub address: u16,
+}
+
+/// Reads an MVR file or a CSV patch export, going by the file's contents.
+pub fn load(path: &Path) -> io::Result<Vec<ImportedFixture>> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    let fixtures = if bytes.starts_with(b"PK\x03\x04") {
+        parse_mvr(&bytes)?
+    } else {
+        parse_csv(&String::from_utf8_lossy(&bytes))?
+    };
+    if fixtures.is_empty() {
+        return Err(io::Error::other(tr!("import-patch-none-found")));
+    }
+    Ok(fixtures)
+}
+
+/// Reads an address written as `universe.address` (or with `/`, `:` or `-`),
+/// or as an absolute address counting on through the universes. None if it
+/// isn't one, or is past the last universe.
+fn parse_address(text: &str, universe: Option<u16>) -> Option<(u16, u16)> {
+    let text = text.trim();
+    if let Some((u, a)) = text.split_once(['.', '/', ':', '-']) {
+        let universe = u.trim().parse().ok()?;
+        let address = a.trim().parse().ok()?;
+        return (1..=UNIVERSE_SIZE as u16)
+            .contains(&address)
+            .then_some((universe, address));
+    }
+    let absolute: u32 = text.parse().ok()?;
+    if absolute == 0 {
+        return None;
+    }
+    match universe {
+        Some(universe) if absolute <= UNIVERSE_SIZE as u32 => Some((universe, absolute as u16)),
+        _ => {
+            let size = UNIVERSE_SIZE as u32;
+            let universe = u16::try_from((absolute - 1) / size + 1).ok()?;
+            Some((universe, ((absolute - 1) % size + 1) as u16))
+        }
+    }
+}
+
+fn parse_mvr(bytes: &[u8]) -> io::Result<Vec<ImportedFixture>> {
+    let mut archive = ZipArchive::new(io::Cursor::new(bytes))?;
+    let mut xml = String::new();
+    archive
+        .by_name(SCENE_ENTRY)
+        .map_err(|_| io::Error::other(tr!("import-patch-not-mvr", entry = SCENE_ENTRY)))?
+        .read_to_string(&mut xml)?;
+    parse_scene(&xml).map_err(|err| io::Error::other(format!("{}: {}", SCENE_ENTRY, err)))
+}
+
+/// A fixture being read from a scene: name, GDTF spec, mode and address.
+type SceneFixture = (String, String, String, Option<(u16, u16)>);
+
+/// Collects the `Fixture` nodes of an MVR scene with their GDTF type, mode
+/// and first DMX address.
+fn parse_scene(xml: &str) -> Result<Vec<ImportedFixture>, quick_xml::Error> {
+    let mut reader = Reader::from_str(xml);
+    let mut fixtures = Vec::new();
+    let mut current: Option<SceneFixture> = None;
+    // The element whose text is being read, inside the current fixture.
+    let mut field = String::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                let tag = String::from_utf8_lossy(element.local_name().as_ref()).to_string();
+                if tag == "Fixture" {
+                    let name = element
+                        .try_get_attribute("name")?
+                        .map(|name| name.unescape_value().map(|v| v.to_string()))
+                        .transpose()?
+                        .unwrap_or_default();
+                    current = Some((name, String::new(), String::new(), None));
+                }
+                field = tag;
+            }
+            Event::Text(text) => {
+                let Some((_, spec, mode, address)) = &mut current else {
+                    continue;
+                };
+                let text = text.unescape()?.trim().to_string();
+                match field.as_str() {
+                    "GDTFSpec" => {
+                        *spec = text.trim_end_matches(".gdtf").replace('@', " ");
+                    }
+                    "GDTFMode" => *mode = text,
+                    "Address" if address.is_none() => *address = parse_address(&text, None),
+                    _ => {}
+                }
+            }
+            Event::End(element) => {
+                if element.local_name().as_ref() == b"Fixture" {
+                    if let Some((name, spec, mode, Some((universe, address)))) = current.take() {
+                        let fixture_type = if mode.is_empty() {
+                            spec
+                        } else {
+                            format!("{} ({})", spec, mode)
+                        };
+                        fixtures.push(ImportedFixture {
+                            name,
+                            fixture_type,
+                            universe,
+                            address,
+                        });
+                    }
+                }
+                field.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(fixtures)
+}
+
+/// Splits one CSV line on `separator`, honouring double-quoted fields.
+fn split_csv_line(line: &str, separator: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// Header names used by grandMA and ETC exports for each column Halo needs.
+const NAME_HEADERS: &[&str] = &["name", "label", "fixture name"];
+const TYPE_HEADERS: &[&str] = &[
+    "type",
+    "fixture type",
+    "fixturetype",
+    "instrument type",
+    "model",
+];
+const ADDRESS_HEADERS: &[&str] = &["patch", "address", "dmx", "dmx address", "addr"];
+const UNIVERSE_HEADERS: &[&str] = &["universe", "univ", "dmx universe"];
+const ID_HEADERS: &[&str] = &["fixture id", "fixtureid", "fid", "channel", "chan", "id"];
+
+fn parse_csv(text: &str) -> io::Result<Vec<ImportedFixture>> {
+    let mut lines = text
+        .lines()
+        .map(|line| line.trim_start_matches('\u{feff}'))
+        .filter(|line| !line.trim().is_empty());
+    // ETC exports start with a few lines of metadata before the header row,
+    // so take the first line that names an address column.
+    // Some consoles write semicolons rather than commas.
+    let (header, separator) = lines
+        .by_ref()
+        .find_map(|line| {
+            let separator = if line.matches(';').count() > line.matches(',').count() {
+                ';'
+            } else {
+                ','
+            };
+            let header: Vec<String> = split_csv_line(line, separator)
+                .into_iter()
+                .map(|h| h.to_lowercase())
+                .collect();
+            header
+                .iter()
+                .any(|h| ADDRESS_HEADERS.contains(&h.as_str()))
+                .then_some((header, separator))
+        })
+        .ok_or_else(|| io::Error::other(tr!("import-patch-no-address")))?;
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+    let address_column = column(ADDRESS_HEADERS);
+    let universe_column = column(UNIVERSE_HEADERS);
+    let name_column = column(NAME_HEADERS);
+    let type_column = column(TYPE_HEADERS);
+    let id_column = column(ID_HEADERS);
+
+    let mut fixtures = Vec::new();
+    for line in lines {
+        let fields = split_csv_line(line, separator);
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|c| fields.get(c))
+                .map(String::as_str)
+                .unwrap_or("")
+        };
+        let universe = field(universe_column).parse().ok();
+        let Some((universe, address)) = parse_address(field(address_column), universe) else {
+            continue;
+        };
+        let fixture_type = field(type_column).to_string();
+        let mut name = field(name_column).to_string();
+        if name.is_empty() {
+            name = format!("{} {}", fixture_type, field(id_column))
+                .trim()
+                .to_string();
+        }
+        fixtures.push(ImportedFixture {
+            name,
+            fixture_type,
+            universe,
+            address,
+        });
+    }
+    Ok(fixtures)
+}
+
+/// The Halo profile most likely meant by an imported type name: one with the
+/// same name, or else one picked by common words in fixture type names.
+pub fn guess_profile(fixture_type: &str, profiles: &[FixtureProfile]) -> Option<usize> {
+    let lower = fixture_type.to_lowercase();
+    if let Some(index) = profiles
+        .iter()
+        .position(|profile| profile.name.to_lowercase() == lower)
+    {
+        return Some(index);
+    }
+    let guesses: [(&[&str], &str); 4] = [
+        (&["haze", "hazer", "fog", "smoke"], "Hazer"),
+        (
+            &["spot", "wash", "beam", "profile", "moving", "head"],
+            "Moving Head",
+        ),
+        (&["par", "rgb", "led"], "RGB Par"),
+        (
+            &["dimmer", "dim", "conventional", "fresnel"],
+            "Generic Dimmer",
+        ),
+    ];
+    guesses
+        .iter()
+        .find(|(words, _)| words.iter().any(|word| lower.contains(word)))
+        .and_then(|(_, name)| profiles.iter().position(|profile| profile.name == *name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_addresses_past_the_last_universe_are_refused() {
+        assert_eq!(parse_address("513", None), Some((2, 1)));
+        assert_eq!(parse_address("33553920", None), Some((65535, 512)));
+        assert_eq!(parse_address("33553921", None), None);
+        assert_eq!(parse_address("4294967295", None), None);
+        assert_eq!(parse_address("3.17", None), Some((3, 17)));
+    }
+}
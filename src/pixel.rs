@@ -0,0 +1,403 @@
+//! Pixel mapping for LED strips and matrices. Each map is a grid of RGB
+//! pixels patched contiguously from a start address, spilling into the
+//! following universes, and driven by a built-in content generator.
+
+use crate::audio::{AudioBand, AudioLevels};
+use crate::dmx::{DmxFrame, UNIVERSE_SIZE};
+use crate::i18n::tr;
+use crate::media::MediaClip;
+use crate::pixel_stream::PixelOutput;
+use crate::plugin::PluginHost;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Whole RGB pixels that fit in one universe (510 of 512 channels).
+pub const PIXELS_PER_UNIVERSE: usize = UNIVERSE_SIZE / 3;
+
+pub type Rgb = [u8; 3];
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Generator {
+    /// Static blend from the first colour to the second across the map.
+    Gradient,
+    /// A band of the first colour moving over the second.
+    Chase,
+    /// Classic sine plasma blending between the two colours.
+    Plasma,
+    /// The whole map flashes the first colour on each beat and decays.
+    BeatPulse,
+    /// Like Beat Pulse, but once a bar, decaying over the whole bar.
+    BarPulse,
+    /// An animated GIF or video file scaled onto the grid.
+    Media,
+    /// A generator plugin; see `plugin`.
+    Plugin,
+}
+
+impl Generator {
+    pub const ALL: [Generator; 7] = [
+        Generator::Gradient,
+        Generator::Chase,
+        Generator::Plasma,
+        Generator::BeatPulse,
+        Generator::BarPulse,
+        Generator::Media,
+        Generator::Plugin,
+    ];
+}
+
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            Generator::Gradient => tr!("generator-gradient"),
+            Generator::Chase => tr!("generator-chase"),
+            Generator::Plasma => tr!("generator-plasma"),
+            Generator::BeatPulse => tr!("generator-beat-pulse"),
+            Generator::BarPulse => tr!("generator-bar-pulse"),
+            Generator::Media => tr!("generator-media"),
+            Generator::Plugin => tr!("generator-plugin"),
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ColorOrder {
+    Rgb,
+    Grb,
+    Bgr,
+}
+
+/// The generator parameter an audio band drives.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ModulationTarget {
+    Brightness,
+    /// Width of the chase band.
+    Size,
+    /// Rate of the moving generators.
+    Speed,
+}
+
+impl ModulationTarget {
+    pub const ALL: [ModulationTarget; 3] = [
+        ModulationTarget::Brightness,
+        ModulationTarget::Size,
+        ModulationTarget::Speed,
+    ];
+}
+
+impl fmt::Display for ModulationTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            ModulationTarget::Brightness => tr!("modulation-brightness"),
+            ModulationTarget::Size => tr!("modulation-size"),
+            ModulationTarget::Speed => tr!("modulation-speed"),
+        })
+    }
+}
+
+/// Drives a generator parameter from the energy in one audio band.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioModulation {
+    pub band: AudioBand,
+    pub target: ModulationTarget,
+    /// How far the band moves the parameter, from 0 to 1.
+    pub depth: f32,
+}
+
+impl Default for AudioModulation {
+    fn default() -> Self {
+        Self {
+            band: AudioBand::Bass,
+            target: ModulationTarget::Brightness,
+            depth: 1.0,
+        }
+    }
+}
+
+/// Share of the map covered by the chase band.
+const CHASE_SIZE: f32 = 0.25;
+/// Extra cycles per second a full-scale band adds at full depth.
+const MODULATED_SPEED: f32 = 4.0;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PixelMap {
+    pub name: String,
+    pub enabled: bool,
+    /// Pixels per row; a strip is a map with a height of one.
+    pub width: u16,
+    pub height: u16,
+    /// DMX, or straight to a network pixel controller.
+    pub output: PixelOutput,
+    /// The controller's address for DDP and WLED output, as `host` or
+    /// `host:port`.
+    pub host: String,
+    pub universe: u16,
+    /// 1-based DMX address of the first pixel.
+    pub address: u16,
+    pub color_order: ColorOrder,
+    /// Alternate rows run in opposite directions, as most matrices are wired.
+    pub serpentine: bool,
+    pub generator: Generator,
+    pub colors: [Rgb; 2],
+    /// Cycles per second for the moving generators.
+    pub speed: f32,
+    pub brightness: f32,
+    /// GIF or video played by the media generator.
+    pub media: Option<PathBuf>,
+    /// Playback rate of the media, where 1 is normal speed.
+    pub playback_speed: f32,
+    pub looping: bool,
+    /// The generator plugin drawing the map, by name.
+    pub plugin: String,
+    pub modulation: Option<AudioModulation>,
+    /// The speed master scaling the generator's speed, by position.
+    pub speed_master: Option<usize>,
+    /// The media decoded at the map's size, loaded by [`PixelMap::load_media`].
+    #[serde(skip)]
+    pub clip: Option<Arc<MediaClip>>,
+    /// Clock time the media was (re)started at.
+    #[serde(skip)]
+    media_started: f32,
+}
+
+impl Default for PixelMap {
+    fn default() -> Self {
+        Self {
+            name: "Pixel Map".to_string(),
+            enabled: true,
+            width: 16,
+            height: 1,
+            output: PixelOutput::Dmx,
+            host: String::new(),
+            universe: 10,
+            address: 1,
+            color_order: ColorOrder::Rgb,
+            serpentine: true,
+            generator: Generator::Chase,
+            colors: [[255, 0, 128], [0, 0, 64]],
+            speed: 0.5,
+            brightness: 1.0,
+            media: None,
+            playback_speed: 1.0,
+            looping: true,
+            plugin: String::new(),
+            modulation: None,
+            speed_master: None,
+            clip: None,
+            media_started: 0.0,
+        }
+    }
+}
+
+/// Timing inputs shared by every generator for one frame.
+#[derive(Clone, Copy)]
+pub struct PixelClock {
+    /// Free-running seconds, independent of the show transport.
+    pub time: f32,
+    /// Seconds of effect movement: `time` as run by the speed master driving
+    /// the effect.
+    pub effect_time: f32,
+    /// Position within the current beat, from 0 to 1.
+    pub beat_phase: f32,
+    /// Position within the current bar, from 0 to 1.
+    pub bar_phase: f32,
+    pub audio: AudioLevels,
+}
+
+impl PixelClock {
+    /// The clock as of now, counting time, beats and bars from `origin`.
+    pub fn since(origin: Instant, bpm: f32, beats_per_bar: u32) -> Self {
+        Self::at(origin.elapsed().as_secs_f32(), bpm, beats_per_bar)
+    }
+
+    /// The clock `time` seconds from its zero point.
+    pub fn at(time: f32, bpm: f32, beats_per_bar: u32) -> Self {
+        let beats = time * bpm / 60.0;
+        Self {
+            time,
+            effect_time: time,
+            beat_phase: beats.fract(),
+            bar_phase: (beats / beats_per_bar.max(1) as f32).fract(),
+            audio: AudioLevels::default(),
+        }
+    }
+
+    pub fn with_audio(self, audio: AudioLevels) -> Self {
+        Self { audio, ..self }
+    }
+
+    pub fn with_effect_time(self, effect_time: f32) -> Self {
+        Self {
+            effect_time,
+            ..self
+        }
+    }
+}
+
+pub fn mix(a: Rgb, b: Rgb, t: f32) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    [0, 1, 2].map(|i| (a[i] as f32 + (b[i] as f32 - a[i] as f32) * t).round() as u8)
+}
+
+impl PixelMap {
+    pub fn pixel_count(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    /// Decodes the media file at the map's current size and plays it from
+    /// the start.
+    pub fn load_media(&mut self, clock: PixelClock) -> io::Result<()> {
+        let path = self
+            .media
+            .as_ref()
+            .ok_or_else(|| io::Error::other("no media file set"))?;
+        self.clip = Some(Arc::new(MediaClip::load(path, self.width, self.height)?));
+        self.restart_media(clock);
+        Ok(())
+    }
+
+    pub fn restart_media(&mut self, clock: PixelClock) {
+        self.media_started = clock.time;
+    }
+
+    /// Whether the loaded clip was decoded at a different size than the map
+    /// now has.
+    pub fn media_needs_reload(&self) -> bool {
+        self.clip
+            .as_ref()
+            .is_some_and(|clip| clip.width != self.width || clip.height != self.height)
+    }
+
+    fn media_pixels(&self, clock: PixelClock) -> Vec<Rgb> {
+        match &self.clip {
+            Some(clip) if !self.media_needs_reload() => {
+                let seconds = (clock.time - self.media_started) * self.playback_speed;
+                let position = Duration::from_secs_f32(seconds.max(0.0));
+                clip.frame_at(position, self.looping).pixels.clone()
+            }
+            _ => vec![[0, 0, 0]; self.pixel_count()],
+        }
+    }
+
+    /// Computes the colour of every pixel in row-major order.
+    pub fn pixels(&self, clock: PixelClock, plugins: &PluginHost) -> Vec<Rgb> {
+        let mut brightness = self.brightness;
+        let mut size = CHASE_SIZE;
+        let mut phase = clock.effect_time * self.speed;
+        if let Some(modulation) = self.modulation {
+            let depth = modulation.depth.clamp(0.0, 1.0);
+            let level = clock.audio.level(modulation.band);
+            match modulation.target {
+                ModulationTarget::Brightness => brightness *= 1.0 - depth + depth * level,
+                ModulationTarget::Size => size += (1.0 - CHASE_SIZE) * depth * level,
+                ModulationTarget::Speed => {
+                    phase += MODULATED_SPEED * depth * clock.audio.integral(modulation.band)
+                }
+            }
+        }
+        let brightness = brightness.clamp(0.0, 1.0);
+        if self.generator == Generator::Media {
+            return self
+                .media_pixels(clock)
+                .into_iter()
+                .map(|color| color.map(|c| (c as f32 * brightness) as u8))
+                .collect();
+        }
+
+        let width = self.width.max(1) as f32;
+        let height = self.height.max(1) as f32;
+        let [a, b] = self.colors;
+
+        let mut pixels = Vec::with_capacity(self.pixel_count());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let u = x as f32 / width;
+                let v = y as f32 / height;
+                let color = match self.generator {
+                    Generator::Gradient => mix(a, b, u),
+                    Generator::Chase => {
+                        let distance = (u - phase.rem_euclid(1.0)).rem_euclid(1.0);
+                        mix(a, b, distance / size)
+                    }
+                    Generator::Plasma => {
+                        let value = (u * 6.0 + phase * TAU).sin()
+                            + (v * 6.0 + phase * TAU * 0.7).sin()
+                            + ((u + v) * 4.0 + phase * TAU * 1.3).sin();
+                        mix(a, b, (value / 3.0 + 1.0) / 2.0)
+                    }
+                    Generator::BeatPulse => mix(a, b, clock.beat_phase),
+                    Generator::BarPulse => mix(a, b, clock.bar_phase),
+                    Generator::Plugin => plugins.pixel(
+                        &self.plugin,
+                        (u, v, phase, clock.beat_phase, clock.bar_phase),
+                        self.colors,
+                    ),
+                    Generator::Media => unreachable!("media is sampled above"),
+                };
+                pixels.push(color.map(|c| (c as f32 * brightness) as u8));
+            }
+        }
+        pixels
+    }
+
+    /// Maps a grid position to its index along the wiring.
+    fn wiring_index(&self, x: u16, y: u16) -> usize {
+        let x = if self.serpentine && y % 2 == 1 {
+            self.width - 1 - x
+        } else {
+            x
+        };
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// The universe and 1-based channel of the first byte of the pixel at
+    /// position `index` along the wiring. Pixels never straddle universes.
+    pub fn pixel_address(&self, index: usize) -> (u16, u16) {
+        let first = (self.address.max(1) as usize - 1) / 3;
+        let slot = first + index;
+        let universe = self.universe as usize + slot / PIXELS_PER_UNIVERSE;
+        let offset = slot % PIXELS_PER_UNIVERSE;
+        let base = if slot < PIXELS_PER_UNIVERSE {
+            self.address as usize + index * 3
+        } else {
+            offset * 3 + 1
+        };
+        (universe as u16, base as u16)
+    }
+
+    /// The map's pixels in wiring order, each in the map's colour order.
+    pub fn wired(&self, clock: PixelClock, plugins: &PluginHost) -> Vec<[u8; 3]> {
+        let pixels = self.pixels(clock, plugins);
+        let mut wired = vec![[0; 3]; pixels.len()];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let color = pixels[y as usize * self.width as usize + x as usize];
+                wired[self.wiring_index(x, y)] = match self.color_order {
+                    ColorOrder::Rgb => color,
+                    ColorOrder::Grb => [color[1], color[0], color[2]],
+                    ColorOrder::Bgr => [color[2], color[1], color[0]],
+                };
+            }
+        }
+        wired
+    }
+
+    /// Writes the map into `frame`, overriding whatever the cues set there.
+    pub fn render(&self, clock: PixelClock, plugins: &PluginHost, frame: &mut DmxFrame) {
+        if !self.enabled {
+            return;
+        }
+        for (index, bytes) in self.wired(clock, plugins).into_iter().enumerate() {
+            let (universe, channel) = self.pixel_address(index);
+            for (i, byte) in bytes.into_iter().enumerate() {
+                frame.set(universe, channel + i as u16, byte);
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! DDP and WLED realtime output for pixel maps, so strips on WLED and other
+//! network pixel controllers can be driven straight from a map without an
+//! Art-Net-to-SPI bridge.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+pub const DDP_PORT: u16 = 4048;
+pub const WLED_PORT: u16 = 21324;
+/// RGB bytes per DDP packet, a whole number of pixels within a standard
+/// Ethernet frame.
+const DDP_CHUNK: usize = 480 * 3;
+/// LEDs per WLED DNRGB packet.
+const WLED_CHUNK: usize = 489;
+/// Seconds WLED holds the last frame before going back to its own effects.
+const WLED_TIMEOUT: u8 = 2;
+/// How long to wait before looking up a host that didn't resolve again.
+const RETRY_LOOKUP: Duration = Duration::from_secs(5);
+
+/// Where a pixel map's pixels go.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum PixelOutput {
+    /// Into the DMX frame at the map's universe and address.
+    #[default]
+    Dmx,
+    /// DDP packets to a controller.
+    Ddp,
+    /// WLED's UDP realtime protocol.
+    Wled,
+}
+
+impl PixelOutput {
+    pub const ALL: [PixelOutput; 3] = [PixelOutput::Dmx, PixelOutput::Ddp, PixelOutput::Wled];
+
+    fn port(self) -> u16 {
+        match self {
+            PixelOutput::Dmx => 0,
+            PixelOutput::Ddp => DDP_PORT,
+            PixelOutput::Wled => WLED_PORT,
+        }
+    }
+}
+
+impl fmt::Display for PixelOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PixelOutput::Dmx => "DMX",
+            PixelOutput::Ddp => "DDP",
+            PixelOutput::Wled => "WLED UDP",
+        })
+    }
+}
+
+/// DDP packets carrying `data`, RGB bytes in wiring order. The last packet
+/// has the push flag set, so the controller shows the frame once it is all
+/// there.
+fn ddp_packets(data: &[u8]) -> Vec<Vec<u8>> {
+    let chunks: Vec<&[u8]> = data.chunks(DDP_CHUNK).collect();
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            let push = if i + 1 == chunks.len() { 0x01 } else { 0x00 };
+            let offset = (i * DDP_CHUNK) as u32;
+            // Version 1, no sequence numbers, RGB at 8 bits, to the default
+            // output device.
+            let mut packet = vec![0x40 | push, 0x00, 0x0B, 0x01];
+            packet.extend_from_slice(&offset.to_be_bytes());
+            packet.extend_from_slice(&(chunk.len() as u16).to_be_bytes());
+            packet.extend_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// WLED DNRGB packets carrying `data`, each starting at its first LED.
+fn wled_packets(data: &[u8]) -> Vec<Vec<u8>> {
+    data.chunks(WLED_CHUNK * 3)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let start = (i * WLED_CHUNK) as u16;
+            let mut packet = vec![4, WLED_TIMEOUT];
+            packet.extend_from_slice(&start.to_be_bytes());
+            packet.extend_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// Sends pixel maps over the network from the engine thread, remembering
+/// where each controller's name resolved to so a frame never waits on a
+/// lookup it has already done.
+#[derive(Default)]
+pub struct PixelStreams {
+    socket: Option<UdpSocket>,
+    addresses: HashMap<(String, PixelOutput), (Instant, Option<SocketAddr>)>,
+}
+
+impl PixelStreams {
+    /// Sends one frame of RGB bytes to `host` over `output`.
+    pub fn send(&mut self, host: &str, output: PixelOutput, data: &[u8]) -> io::Result<()> {
+        if output == PixelOutput::Dmx || host.is_empty() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let key = (host.to_string(), output);
+        let stale = self
+            .addresses
+            .get(&key)
+            .is_none_or(|(at, addr)| addr.is_none() && now.duration_since(*at) > RETRY_LOOKUP);
+        if stale {
+            let addr = resolve(host, output.port());
+            self.addresses.insert(key.clone(), (now, addr));
+        }
+        let Some(addr) = self.addresses[&key].1 else {
+            return Err(io::Error::other(format!("can't find {}", host)));
+        };
+        if self.socket.is_none() {
+            self.socket = Some(UdpSocket::bind("0.0.0.0:0")?);
+        }
+        let socket = self.socket.as_ref().expect("bound above");
+        let packets = match output {
+            PixelOutput::Ddp => ddp_packets(data),
+            _ => wled_packets(data),
+        };
+        for packet in packets {
+            socket.send_to(&packet, addr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves `host`, adding `port` unless it names one.
+fn resolve(host: &str, port: u16) -> Option<SocketAddr> {
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:{}", host, port)
+    };
+    address.to_socket_addrs().ok()?.next()
+}
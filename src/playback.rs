@@ -0,0 +1,423 @@
+//! Plays the show's audio in time with the show clock, split across audio
+//! outputs by channel. A multichannel file can carry the music on channels
+//! 1-2 for front of house and a click and guide track on 3-4 for the band,
+//! each sent only to its own device. Songs can have files of their own,
+//! played from the song's start in place of the show's file, each with its
+//! own gain trim. Files are decoded by an `ffmpeg` binary on the `PATH` and
+//! held in memory; each output thread keeps its own place and jumps back
+//! into line when it drifts from the show clock, as on a locate.
+
+use crate::end_action::EndAction;
+use crate::error::{Device, HaloError};
+use crate::i18n::tr;
+use crate::HaloApp;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Sample rate the file is decoded at; outputs at other rates resample it.
+const SAMPLE_RATE: u32 = 48_000;
+/// Longest stretch of audio decoded, as every sample is held in memory.
+const MAX_SECONDS: u32 = 3 * 60 * 60;
+/// How far an output may wander from the show clock before it jumps.
+const MAX_DRIFT: f64 = 0.04;
+
+/// An audio output and the channels of the show audio it plays.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioRoute {
+    pub device: String,
+    /// Channels of the file, counted from 1, sent to the device's outputs
+    /// in order. A single channel goes to every output.
+    pub channels: Vec<u16>,
+    /// Level, from 0 to 1.
+    pub level: f32,
+}
+
+impl AudioRoute {
+    pub fn new(device: &str) -> Self {
+        Self {
+            device: device.to_string(),
+            channels: vec![1, 2],
+            level: 1.0,
+        }
+    }
+}
+
+/// The show audio, decoded.
+pub struct ShowAudio {
+    pub channels: u16,
+    /// Interleaved samples at [`SAMPLE_RATE`].
+    samples: Vec<i16>,
+}
+
+impl ShowAudio {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let channels = channel_count(path)?;
+        let mut child = Command::new("ffmpeg")
+            .args(["-v", "error", "-i"])
+            .arg(path)
+            .args(["-t", &MAX_SECONDS.to_string()])
+            .args(["-vn", "-ar", &SAMPLE_RATE.to_string()])
+            .args(["-f", "s16le", "-"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| io::Error::new(err.kind(), format!("could not run ffmpeg: {}", err)))?;
+        let mut raw = Vec::new();
+        child
+            .stdout
+            .take()
+            .expect("stdout is piped")
+            .read_to_end(&mut raw)?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!(
+                "ffmpeg failed: {}",
+                message.trim()
+            )));
+        }
+        Ok(Self {
+            channels,
+            samples: raw
+                .chunks_exact(2)
+                .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+                .collect(),
+        })
+    }
+
+    fn frames(&self) -> usize {
+        self.samples.len() / self.channels.max(1) as usize
+    }
+
+    /// Channel `channel` (from 0) at `position` in frames, or silence past
+    /// either end or for a channel the file doesn't have.
+    fn sample(&self, position: f64, channel: u16) -> f32 {
+        if position < 0.0 || channel >= self.channels {
+            return 0.0;
+        }
+        let frame = position as usize;
+        if frame >= self.frames() {
+            return 0.0;
+        }
+        self.samples[frame * self.channels as usize + channel as usize] as f32 / 32768.0
+    }
+}
+
+/// Asks `ffprobe` how many channels the file's first audio stream has.
+fn channel_count(path: &Path) -> io::Result<u16> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-select_streams", "a:0"])
+        .args(["-show_entries", "stream=channels", "-of", "csv=p=0"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| io::Error::new(err.kind(), format!("could not run ffprobe: {}", err)))?;
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()
+        .filter(|&channels| channels > 0)
+        .ok_or_else(|| io::Error::other("no audio stream found"))
+}
+
+/// A file placed on the show timeline.
+struct Track {
+    audio: Arc<ShowAudio>,
+    /// Where the file starts playing and where it gives way, in seconds of
+    /// show time.
+    start: f64,
+    end: f64,
+    /// Linear gain from the file's trim.
+    gain: f32,
+}
+
+/// What the playlist is made of, to notice when it changes: each file with
+/// its start, end and trim in dB.
+type PlaylistKey = Vec<(PathBuf, Duration, Option<Duration>, f32)>;
+
+#[derive(Default)]
+struct PlaybackState {
+    /// Songs' own files first, then the show's file under everything else.
+    tracks: Vec<Track>,
+    /// In setlist mode, where the current song ends and where the song it
+    /// chains to starts, so the outputs carry straight on into it.
+    follow: Option<(f64, f64)>,
+    /// Where the show clock was, in seconds, and when.
+    position: f64,
+    at: Option<Instant>,
+    playing: bool,
+    error: Option<String>,
+}
+
+impl PlaybackState {
+    /// Where the show clock is now, with a chain to the next song already
+    /// taken.
+    fn now(&self) -> f64 {
+        let now = match self.at {
+            Some(at) if self.playing => self.position + at.elapsed().as_secs_f64(),
+            _ => self.position,
+        };
+        match self.follow {
+            Some((end, next)) if now >= end => next + (now - end),
+            _ => now,
+        }
+    }
+
+    /// Channel `channel` of whatever plays at `time` seconds.
+    fn sample(&self, time: f64, channel: u16) -> f32 {
+        self.tracks
+            .iter()
+            .find(|track| track.start <= time && time < track.end)
+            .map_or(0.0, |track| {
+                let position = (time - track.start) * SAMPLE_RATE as f64;
+                track.audio.sample(position, channel) * track.gain
+            })
+    }
+}
+
+/// The audio and clock the outputs play from, shared with their threads.
+#[derive(Clone, Default)]
+pub struct PlaybackClock(Arc<Mutex<PlaybackState>>);
+
+impl PlaybackClock {
+    pub fn set(&self, position: Duration, playing: bool, follow: Option<(Duration, Duration)>) {
+        if let Ok(mut state) = self.0.lock() {
+            state.position = position.as_secs_f64();
+            state.at = Some(Instant::now());
+            state.playing = playing;
+            state.follow = follow.map(|(end, next)| (end.as_secs_f64(), next.as_secs_f64()));
+        }
+    }
+
+    fn set_tracks(&self, tracks: Vec<Track>) {
+        if let Ok(mut state) = self.0.lock() {
+            state.tracks = tracks;
+        }
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.0.lock().ok().and_then(|state| state.error.clone())
+    }
+}
+
+/// The open outputs, and the files loading or loaded for them. Everything
+/// falls silent when this is dropped.
+#[derive(Default)]
+pub struct Playback {
+    outputs: Vec<cpal::Stream>,
+    /// Files decoded so far, or `None` where decoding failed.
+    files: HashMap<PathBuf, Option<Arc<ShowAudio>>>,
+    loading: Option<(PathBuf, Receiver<io::Result<ShowAudio>>)>,
+    /// The playlist the outputs have, and whether a file has loaded since.
+    playlist: PlaylistKey,
+    stale: bool,
+}
+
+fn start_output(route: &AudioRoute, clock: PlaybackClock) -> Result<cpal::Stream, String> {
+    let host = cpal::default_host();
+    let device = host
+        .output_devices()
+        .map_err(|err| err.to_string())?
+        .find(|device| device.name().is_ok_and(|name| name == route.device))
+        .ok_or_else(|| tr!("playback-device-missing", device = route.device.as_str()))?;
+    let supported = device
+        .default_output_config()
+        .map_err(|err| err.to_string())?;
+    let config = supported.config();
+    let stream = match supported.sample_format() {
+        cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config, route, clock),
+        cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config, route, clock),
+        cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config, route, clock),
+        cpal::SampleFormat::I32 => build_stream::<i32>(&device, &config, route, clock),
+        format => return Err(tr!("playback-sample-format", format = format.to_string())),
+    }?;
+    stream.play().map_err(|err| err.to_string())?;
+    Ok(stream)
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    route: &AudioRoute,
+    clock: PlaybackClock,
+) -> Result<cpal::Stream, String>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let outputs = config.channels.max(1) as usize;
+    // Seconds per output frame.
+    let step = 1.0 / config.sample_rate.0 as f64;
+    let channels: Vec<u16> = route.channels.iter().map(|c| c.saturating_sub(1)).collect();
+    let level = route.level;
+    let mut time = 0.0_f64;
+    let errors = clock.clone();
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _| {
+                let Ok(state) = clock.0.lock() else {
+                    return;
+                };
+                if !state.playing || state.tracks.is_empty() {
+                    data.fill(T::from_sample(0.0));
+                    return;
+                }
+                let target = state.now();
+                if (time - target).abs() > MAX_DRIFT {
+                    time = target;
+                }
+                for frame in data.chunks_mut(outputs) {
+                    for (output, sample) in frame.iter_mut().enumerate() {
+                        let channel = match channels.as_slice() {
+                            [only] => Some(*only),
+                            channels => channels.get(output).copied(),
+                        };
+                        let value = channel.map_or(0.0, |c| state.sample(time, c) * level);
+                        *sample = T::from_sample(value);
+                    }
+                    time += step;
+                    if let Some((end, next)) = state.follow {
+                        if time >= end && time - step < end {
+                            time = next + (time - end);
+                        }
+                    }
+                }
+            },
+            move |err| {
+                if let Ok(mut state) = errors.0.lock() {
+                    state.error = Some(err.to_string());
+                }
+            },
+            None,
+        )
+        .map_err(|err| err.to_string())
+}
+
+/// Decibels to linear gain.
+fn gain(trim: f32) -> f32 {
+    10f32.powf(trim / 20.0)
+}
+
+impl HaloApp {
+    /// Opens the audio outputs to match the current preferences.
+    pub(crate) fn apply_playback_preferences(&mut self) {
+        self.playback.outputs.clear();
+        self.playback_error = None;
+        if self.preferences.audio_routes.is_empty() {
+            // Nothing to play on, so don't hold the audio in memory.
+            self.playback = Playback::default();
+            self.playback_clock.set_tracks(Vec::new());
+        }
+        for route in self.preferences.audio_routes.clone() {
+            match start_output(&route, self.playback_clock.clone()) {
+                Ok(stream) => self.playback.outputs.push(stream),
+                Err(err) => {
+                    self.report_error(HaloError::device(Device::Playback, route.device, &err));
+                    self.playback_error = Some(err);
+                }
+            }
+        }
+    }
+
+    /// The files the show plays, song files first.
+    fn playlist(&self) -> PlaylistKey {
+        let songs = self.show.songs.iter().filter_map(|song| {
+            let path = song.audio_file.clone()?;
+            Some((path, song.start_time, Some(song.end()), song.audio_trim))
+        });
+        let show = self
+            .show
+            .audio_file
+            .clone()
+            .map(|path| (path, Duration::ZERO, None, self.show.audio_trim));
+        songs.chain(show).collect()
+    }
+
+    /// In setlist mode, the end of the current song and the start of the
+    /// song it chains to.
+    fn follow(&self) -> Option<(Duration, Duration)> {
+        let song = self.setlist_song()?;
+        let EndAction::Chain(id) = song.end_action else {
+            return None;
+        };
+        Some((song.end(), self.show.song(id)?.start_time))
+    }
+
+    /// Decodes the show's audio files as they change, one at a time, and
+    /// keeps the outputs on the show clock.
+    pub(crate) fn update_playback(&mut self, ctx: &egui::Context) {
+        if self.preferences.audio_routes.is_empty() {
+            return;
+        }
+        let playlist = self.playlist();
+        let playback = &mut self.playback;
+        if let Some((path, receiver)) = &playback.loading {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    if let Err(err) = &result {
+                        self.playback_error = Some(tr!(
+                            "playback-load-failed",
+                            path = path.display().to_string(),
+                            error = err.to_string()
+                        ));
+                    }
+                    playback
+                        .files
+                        .insert(path.clone(), result.ok().map(Arc::new));
+                    playback.loading = None;
+                    playback.stale = true;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => playback.loading = None,
+            }
+        }
+        playback
+            .files
+            .retain(|path, _| playlist.iter().any(|(wanted, ..)| wanted == path));
+        if playback.loading.is_none() {
+            let next = playlist
+                .iter()
+                .map(|(path, ..)| path)
+                .find(|path| !playback.files.contains_key(*path));
+            if let Some(path) = next.cloned() {
+                let (sender, receiver) = mpsc::channel();
+                let ctx = ctx.clone();
+                let file = path.clone();
+                thread::spawn(move || {
+                    let _ = sender.send(ShowAudio::load(&file));
+                    ctx.request_repaint();
+                });
+                playback.loading = Some((path, receiver));
+            }
+        }
+        if playback.stale || playback.playlist != playlist {
+            let tracks = playlist
+                .iter()
+                .filter_map(|(path, start, end, trim)| {
+                    Some(Track {
+                        audio: playback.files.get(path)?.clone()?,
+                        start: start.as_secs_f64(),
+                        end: end.map_or(f64::INFINITY, |end| end.as_secs_f64()),
+                        gain: gain(*trim),
+                    })
+                })
+                .collect();
+            self.playback_clock.set_tracks(tracks);
+            playback.playlist = playlist;
+            playback.stale = false;
+        }
+        self.playback_clock
+            .set(self.elapsed, self.transport.is_running(), self.follow());
+    }
+}
@@ -0,0 +1,305 @@
+//! Plugins: Rhai scripts dropped into the `plugins` folder beside the
+//! preferences, loaded at startup. A plugin is an effect generator, an
+//! output protocol or both, depending on which functions it defines:
+//!
+//! | Function | Kind |
+//! | --- | --- |
+//! | `pixel(u, v, t, beat, bar)` | Pixel map generator. `u` and `v` are the pixel's position across and down the map and `t` the effect time times the map's speed; `beat` and `bar` run from 0 to 1 through each beat and bar. Returns how far to blend from the map's first colour to its second, from 0 to 1, or an `[r, g, b]` array. |
+//! | `packet(universe, data)` | Output protocol. Called each output frame for every universe with its 512 levels as a blob, it returns the bytes of a UDP packet to send to the output's target, or `()` to send nothing. |
+//!
+//! Scripts are sandboxed like cue scripts and capped in how much work each
+//! call may do. Rhai engines can't move between threads, so the output
+//! engine and the UI each compile the plugins for themselves.
+
+use crate::i18n::tr;
+use crate::pixel::{self, Rgb};
+use crate::HaloApp;
+use rhai::{Array, CallFnOptions, Dynamic, Engine, Scope, AST};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Caps the work one call may do, so a plugin can't stall a frame.
+const MAX_OPERATIONS: u64 = 10_000;
+
+/// A plugin as read from disk.
+#[derive(Clone, PartialEq)]
+pub struct PluginSource {
+    /// The file name without its extension.
+    pub name: String,
+    pub path: PathBuf,
+    pub source: Arc<str>,
+}
+
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("halo").join("plugins"))
+}
+
+/// Reads every `.rhai` file in the plugins folder, with the files that
+/// couldn't be read and why.
+pub fn discover() -> (Vec<PluginSource>, Vec<(PathBuf, String)>) {
+    let Some(dir) = plugins_dir() else {
+        return (Vec::new(), Vec::new());
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    paths.sort();
+    let mut plugins = Vec::new();
+    let mut errors = Vec::new();
+    for path in paths {
+        match std::fs::read_to_string(&path) {
+            Ok(source) => plugins.push(PluginSource {
+                name: name_of(&path),
+                source: source.into(),
+                path,
+            }),
+            Err(err) => errors.push((path, err.to_string())),
+        }
+    }
+    (plugins, errors)
+}
+
+fn name_of(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Sends a plugin protocol's packets to a target.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginOutput {
+    pub plugin: String,
+    /// Where to send its packets, as `host:port`.
+    pub target: String,
+    pub enabled: bool,
+}
+
+/// What a plugin offers, for the plugin manager.
+pub struct PluginInfo {
+    pub name: String,
+    pub path: PathBuf,
+    pub generator: bool,
+    pub output: bool,
+    /// Why it failed to compile, or its last error while running.
+    pub error: Option<String>,
+}
+
+struct Loaded {
+    source: PluginSource,
+    ast: Option<AST>,
+    generator: bool,
+    output: bool,
+}
+
+/// The plugins compiled for one thread.
+pub struct PluginHost {
+    engine: Engine,
+    plugins: Vec<Loaded>,
+    /// Compile errors and the last error each plugin raised, by name.
+    errors: RefCell<HashMap<String, String>>,
+}
+
+impl Default for PluginHost {
+    fn default() -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.on_print(|_| {});
+        Self {
+            engine,
+            plugins: Vec::new(),
+            errors: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl PluginHost {
+    /// Compiles `sources`, keeping any already compiled from the same text.
+    pub fn load(&mut self, sources: &[PluginSource]) {
+        if self.plugins.len() == sources.len()
+            && self
+                .plugins
+                .iter()
+                .zip(sources)
+                .all(|(loaded, source)| loaded.source == *source)
+        {
+            return;
+        }
+        let errors = self.errors.get_mut();
+        errors.clear();
+        self.plugins = sources
+            .iter()
+            .map(|source| {
+                let ast = match self.engine.compile(&*source.source) {
+                    Ok(ast) => Some(ast),
+                    Err(err) => {
+                        errors.insert(source.name.clone(), err.to_string());
+                        None
+                    }
+                };
+                let defines = |name: &str| {
+                    ast.as_ref()
+                        .is_some_and(|ast| ast.iter_functions().any(|f| f.name == name))
+                };
+                Loaded {
+                    generator: defines("pixel"),
+                    output: defines("packet"),
+                    source: source.clone(),
+                    ast,
+                }
+            })
+            .collect();
+        for loaded in &self.plugins {
+            if loaded.ast.is_some() && !loaded.generator && !loaded.output {
+                errors.insert(loaded.source.name.clone(), tr!("plugins-no-entry-point"));
+            }
+        }
+    }
+
+    pub fn info(&self) -> Vec<PluginInfo> {
+        self.plugins
+            .iter()
+            .map(|loaded| PluginInfo {
+                name: loaded.source.name.clone(),
+                path: loaded.source.path.clone(),
+                generator: loaded.generator,
+                output: loaded.output,
+                error: self.error(&loaded.source.name),
+            })
+            .collect()
+    }
+
+    pub fn generators(&self) -> Vec<String> {
+        self.plugins
+            .iter()
+            .filter(|loaded| loaded.generator)
+            .map(|loaded| loaded.source.name.clone())
+            .collect()
+    }
+
+    pub fn outputs(&self) -> Vec<String> {
+        self.plugins
+            .iter()
+            .filter(|loaded| loaded.output)
+            .map(|loaded| loaded.source.name.clone())
+            .collect()
+    }
+
+    pub fn error(&self, name: &str) -> Option<String> {
+        self.errors.borrow().get(name).cloned()
+    }
+
+    /// Every plugin's error, by name.
+    pub fn errors(&self) -> Vec<(String, String)> {
+        let mut errors: Vec<(String, String)> = self
+            .errors
+            .borrow()
+            .iter()
+            .map(|(name, error)| (name.clone(), error.clone()))
+            .collect();
+        errors.sort();
+        errors
+    }
+
+    fn call(&self, name: &str, function: &str, args: impl rhai::FuncArgs) -> Option<Dynamic> {
+        let loaded = self
+            .plugins
+            .iter()
+            .find(|loaded| loaded.source.name == name)?;
+        let ast = loaded.ast.as_ref()?;
+        let options = CallFnOptions::new().eval_ast(false);
+        match self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            ast,
+            function,
+            args,
+        ) {
+            Ok(result) => Some(result),
+            Err(err) => {
+                self.errors
+                    .borrow_mut()
+                    .insert(name.to_string(), format!("{}(): {}", function, err));
+                None
+            }
+        }
+    }
+
+    /// The colour of one pixel from the generator plugin `name`, blending
+    /// `colors` if it returns a number.
+    pub fn pixel(
+        &self,
+        name: &str,
+        (u, v, t, beat, bar): (f32, f32, f32, f32, f32),
+        [a, b]: [Rgb; 2],
+    ) -> Rgb {
+        let args = (u as f64, v as f64, t as f64, beat as f64, bar as f64);
+        let Some(result) = self.call(name, "pixel", args) else {
+            return [0, 0, 0];
+        };
+        if let Ok(amount) = result.as_float() {
+            return pixel::mix(a, b, amount as f32);
+        }
+        if let Ok(amount) = result.as_int() {
+            return pixel::mix(a, b, amount as f32);
+        }
+        match result.try_cast::<Array>() {
+            Some(array) if array.len() == 3 => {
+                let channel = |i: usize| array[i].as_int().unwrap_or(0).clamp(0, 255) as u8;
+                [channel(0), channel(1), channel(2)]
+            }
+            _ => [0, 0, 0],
+        }
+    }
+
+    /// The packet the output plugin `name` makes of one universe, if any.
+    pub fn packet(&self, name: &str, universe: u16, data: &[u8]) -> Option<Vec<u8>> {
+        let result = self.call(name, "packet", (universe as i64, data.to_vec()))?;
+        if result.is_unit() {
+            return None;
+        }
+        if result.is_blob() {
+            return result.into_blob().ok();
+        }
+        let array = result.try_cast::<Array>()?;
+        Some(
+            array
+                .iter()
+                .map(|byte| byte.as_int().unwrap_or(0) as u8)
+                .collect(),
+        )
+    }
+}
+
+impl HaloApp {
+    /// Reads the plugins folder again and compiles what it finds.
+    pub(crate) fn load_plugins(&mut self) {
+        let (plugins, read_errors) = discover();
+        self.plugin_host.load(&plugins);
+        for (path, err) in &read_errors {
+            self.log.warn(tr!(
+                "plugins-read-failed",
+                path = path.display().to_string(),
+                error = err.as_str()
+            ));
+        }
+        for (name, err) in self.plugin_host.errors() {
+            self.log.warn(tr!(
+                "plugins-error",
+                name = name.as_str(),
+                error = err.as_str()
+            ));
+        }
+        if !plugins.is_empty() {
+            self.log.info(tr!("plugins-loaded", count = plugins.len()));
+        }
+        self.plugins = plugins;
+        self.plugin_read_errors = read_errors;
+    }
+}
@@ -0,0 +1,161 @@
+//! Power estimates: roughly how much the rig is drawing, from the wattage in
+//! each fixture's profile and the levels it is being sent, so a show on a
+//! few small-venue circuits can be checked against their breakers before it
+//! trips one.
+//!
+//! A fixture draws its idle wattage dark and its full wattage at full, in
+//! proportion to its intensity in between. Where it has colour or white
+//! emitters their mix counts too, so a par in deep red draws about a third
+//! of what it does in open white. Profiles without a wattage aren't counted.
+
+use crate::cue::Cue;
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use crate::tracking;
+use serde::{Deserialize, Serialize};
+
+/// The channels that drive the light source itself.
+const EMITTERS: [Attribute; 5] = [
+    Attribute::Red,
+    Attribute::Green,
+    Attribute::Blue,
+    Attribute::WarmWhite,
+    Attribute::CoolWhite,
+];
+
+/// What a fixture type draws, from its spec sheet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PowerRating {
+    /// At full, in watts.
+    pub full: f32,
+    /// Dark but powered: electronics, fans, or a discharge lamp left struck.
+    #[serde(default)]
+    pub idle: f32,
+}
+
+/// How hard `fixture` is working, from 0 dark to 1 at full, given the level
+/// of each of its attributes.
+fn load(fixture: &Fixture, level: impl Fn(Attribute) -> u8) -> f32 {
+    let channels = &fixture.profile.channels;
+    let intensity = if channels.contains(&Attribute::Intensity) {
+        level(Attribute::Intensity) as f32 / 255.0
+    } else {
+        1.0
+    };
+    let emitters: Vec<f32> = EMITTERS
+        .iter()
+        .filter(|attribute| channels.contains(attribute))
+        .map(|attribute| level(*attribute) as f32 / 255.0)
+        .collect();
+    let mix = if emitters.is_empty() {
+        1.0
+    } else {
+        emitters.iter().sum::<f32>() / emitters.len() as f32
+    };
+    // A fixture with neither channel, a hazer say, is on whenever patched.
+    intensity * mix
+}
+
+/// Watts `fixture` draws at the given levels, if its profile has a rating.
+fn fixture_draw(fixture: &Fixture, level: impl Fn(Attribute) -> u8) -> Option<f32> {
+    let rating = fixture.profile.power?;
+    let load = if fixture.inhibited {
+        0.0
+    } else {
+        load(fixture, level)
+    };
+    Some(rating.idle + (rating.full - rating.idle).max(0.0) * load)
+}
+
+/// The draw of a whole look, in total and by circuit.
+#[derive(Clone, Default)]
+pub struct Estimate {
+    pub total: f32,
+    /// Each circuit fixtures are assigned to, in patch order, with its draw.
+    pub circuits: Vec<(String, f32)>,
+    /// Patched fixtures whose profiles have no wattage.
+    pub unrated: usize,
+}
+
+impl Estimate {
+    fn of(fixtures: &[Fixture], level: impl Fn(&Fixture, Attribute) -> u8) -> Self {
+        let mut estimate = Self::default();
+        for fixture in fixtures {
+            let Some(watts) = fixture_draw(fixture, |attribute| level(fixture, attribute)) else {
+                estimate.unrated += 1;
+                continue;
+            };
+            estimate.total += watts;
+            if fixture.circuit.is_empty() {
+                continue;
+            }
+            match estimate
+                .circuits
+                .iter_mut()
+                .find(|(name, _)| *name == fixture.circuit)
+            {
+                Some((_, draw)) => *draw += watts,
+                None => estimate.circuits.push((fixture.circuit.clone(), watts)),
+            }
+        }
+        estimate
+    }
+
+    /// The highest draw on any one circuit, or the whole rig's where no
+    /// circuits are assigned, since then it is all on one as far as Halo
+    /// knows. Fixtures left off a circuit otherwise count only in the total.
+    pub fn worst_circuit(&self) -> f32 {
+        self.circuits
+            .iter()
+            .map(|(_, draw)| *draw)
+            .reduce(f32::max)
+            .unwrap_or(self.total)
+    }
+
+    /// Whether any circuit is over `limit` watts.
+    pub fn is_over(&self, limit: Option<f32>) -> bool {
+        limit.is_some_and(|limit| self.worst_circuit() > limit)
+    }
+}
+
+/// The draw of the output `frame`.
+pub fn estimate(fixtures: &[Fixture], frame: &DmxFrame) -> Estimate {
+    Estimate::of(fixtures, |fixture, attribute| {
+        fixture
+            .channel_for(attribute)
+            .map_or(0, |channel| frame.get(fixture.universe, channel))
+    })
+}
+
+/// The most each cue draws, by list position: at the end of its fade or
+/// just before it, whichever is more, since levels move in a straight line
+/// between the two. Cues are taken as played in `order` at full, without
+/// masters, effects or busking.
+pub fn cue_peaks(fixtures: &[Fixture], cues: &[Cue], order: &[usize]) -> Vec<Estimate> {
+    let history = tracking::history(cues, order);
+    let mut before = Estimate::of(fixtures, |_, _| 0);
+    let mut peaks = vec![before.clone(); cues.len()];
+    for &index in order {
+        let after = Estimate::of(fixtures, |fixture, attribute| {
+            history[index]
+                .get(&(fixture.id, attribute))
+                .map_or(0, |tracked| tracked.value)
+        });
+        peaks[index] = if after.worst_circuit() >= before.worst_circuit() {
+            after.clone()
+        } else {
+            before.clone()
+        };
+        before = after;
+    }
+    peaks
+}
+
+/// Watts for display, in kilowatts from 1000.
+pub fn format_watts(watts: f32) -> String {
+    if watts >= 1000.0 {
+        format!("{:.2} kW", watts / 1000.0)
+    } else {
+        format!("{:.0} W", watts)
+    }
+}
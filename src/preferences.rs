@@ -0,0 +1,214 @@
+use crate::art_net::ArtNetOutput;
+use crate::awake::AwakeSettings;
+use crate::clock_sync::ClockRole;
+use crate::dmx_input::InputSettings;
+use crate::i18n::Language;
+use crate::interfaces::InterfaceSettings;
+use crate::midi;
+use crate::panic::StopOutput;
+use crate::playback::AudioRoute;
+use crate::plugin::PluginOutput;
+use crate::sacn::SacnOutput;
+use crate::stage_display::StageDisplaySettings;
+use crate::startup::StartupSettings;
+use crate::theme::{LedColor, Theme};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Machine-wide settings, kept separately from the show file so they stay
+/// with the FOH computer rather than travelling with the show.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    /// Accept remote control connections (see the README for the protocol).
+    pub remote_enabled: bool,
+    pub remote_port: u16,
+    /// Serve the Companion / Stream Deck HTTP endpoints.
+    pub http_enabled: bool,
+    pub http_port: u16,
+    /// Listen for OSC messages to run trigger bindings.
+    pub osc_input_enabled: bool,
+    pub osc_input_port: u16,
+    /// Share the patch and fixture selection with visualisers over CITP.
+    pub citp_enabled: bool,
+    /// Receive PosiStageNet trackers for follow spots.
+    pub psn_enabled: bool,
+    /// The USB button or footswitch whose keys trigger bindings.
+    pub hid_device: Option<PathBuf>,
+    /// Presses of a button closer together than this count once.
+    pub hid_debounce_ms: u64,
+    /// The operator's Halo to follow as a designer view, as `host:port`.
+    pub follow_address: String,
+    /// The name this Halo gives the operator when following.
+    pub follow_name: String,
+    /// The language of the interface.
+    pub language: Language,
+    pub theme: Theme,
+    /// Colour of the LED timecode and beat indicator.
+    pub led_color: LedColor,
+    /// Light the first beat of each bar brighter in the beat indicator.
+    pub accent_downbeat: bool,
+    /// Zoom factor applied to the whole interface.
+    pub ui_scale: f32,
+    /// Larger hit targets, with long-press instead of hover for hints.
+    pub touch_mode: bool,
+    /// MIDI port to take clock and program changes from.
+    pub midi_input: Option<String>,
+    /// Follow the tempo of MIDI clock on the input port.
+    pub midi_clock_in: bool,
+    /// Chase MIDI timecode on the input port.
+    pub midi_timecode_in: bool,
+    /// Take Play, Stop, Pause and Locate from MIDI Machine Control on the
+    /// input port.
+    pub midi_mmc_in: bool,
+    /// Send MMC Play, Stop, Pause and Locate on the output port as the
+    /// transport moves.
+    pub midi_mmc_out: bool,
+    /// The MMC device ID answered to and sent to; 127 is all devices.
+    pub midi_mmc_device: u8,
+    /// The channel whose program changes select setlist songs; 0 is any.
+    pub midi_song_channel: u8,
+    /// MIDI port for clock and the messages cues send.
+    #[serde(alias = "midi_clock_output")]
+    pub midi_output: Option<String>,
+    /// Send Halo's tempo as MIDI clock on the output port.
+    pub midi_send_clock: bool,
+    /// Audio device analysed for audio-reactive pixel maps.
+    pub audio_input: Option<String>,
+    /// Audio device the metronome click plays on.
+    pub click_output: Option<String>,
+    /// Click level, from 0 to 1.
+    pub click_volume: f32,
+    /// Where the channels of the show audio play.
+    pub audio_routes: Vec<AudioRoute>,
+    /// The network interface Art-Net, sACN and OSC each use.
+    pub interfaces: InterfaceSettings,
+    /// Art-Net and sACN input merged with the output.
+    pub network_input: InputSettings,
+    /// Where to send RDM requests: an Art-Net gateway, or a broadcast address
+    /// to reach them all.
+    pub rdm_gateway: String,
+    /// Where the output goes over Art-Net.
+    pub art_net_output: ArtNetOutput,
+    /// How the output goes out over sACN.
+    pub sacn_output: SacnOutput,
+    /// Plugin output protocols and where they send.
+    pub plugin_outputs: Vec<PluginOutput>,
+    /// How many past saves of the show to keep as backups; 0 turns them off.
+    pub backup_count: usize,
+    /// What the output does when playback stops.
+    pub stop_output: StopOutput,
+    /// How long [`StopOutput::FadeOut`] takes.
+    pub stop_fade: Duration,
+    /// Look for a newer release on GitHub at startup.
+    pub check_for_updates: bool,
+    /// Give each look recorded with Record Look a pad that fires it.
+    pub look_pads: bool,
+    /// Whether this Halo serves its clock to a backup or follows a main.
+    pub clock_role: ClockRole,
+    /// The UDP port a main answers clock requests on.
+    pub clock_port: u16,
+    /// The main a backup follows, as `host:port`.
+    pub clock_main: String,
+    /// As a backup, mirror the main's show and take over its output if it
+    /// is lost.
+    pub tracking_backup: bool,
+    /// The venue mapping output goes through; see `venue`.
+    pub venue_file: Option<PathBuf>,
+    /// Profile names starred in the fixture library browser.
+    pub favorite_profiles: Vec<String>,
+    /// Profiles most recently patched, newest first.
+    pub recent_profiles: Vec<String>,
+    /// The stage display's window and what it shows.
+    pub stage_display: StageDisplaySettings,
+    /// What happens when Halo starts, for unattended installations.
+    pub startup: StartupSettings,
+    /// Keeping the display on, and dimming to a clock.
+    pub awake: AwakeSettings,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            remote_enabled: false,
+            remote_port: 7700,
+            http_enabled: false,
+            http_port: 7701,
+            osc_input_enabled: false,
+            osc_input_port: 7702,
+            citp_enabled: false,
+            psn_enabled: false,
+            hid_device: None,
+            hid_debounce_ms: 150,
+            follow_address: "localhost:7700".to_string(),
+            follow_name: "Designer".to_string(),
+            language: Language::default(),
+            theme: Theme::Dark,
+            led_color: LedColor::Green,
+            accent_downbeat: true,
+            ui_scale: 1.0,
+            touch_mode: false,
+            midi_input: None,
+            midi_clock_in: true,
+            midi_timecode_in: false,
+            midi_mmc_in: false,
+            midi_mmc_out: false,
+            midi_mmc_device: midi::ALL_CALL,
+            midi_song_channel: 0,
+            midi_output: None,
+            midi_send_clock: true,
+            audio_input: None,
+            click_output: None,
+            click_volume: 0.8,
+            audio_routes: Vec::new(),
+            interfaces: InterfaceSettings::default(),
+            network_input: InputSettings::default(),
+            rdm_gateway: "255.255.255.255".to_string(),
+            art_net_output: ArtNetOutput::default(),
+            sacn_output: SacnOutput::default(),
+            plugin_outputs: Vec::new(),
+            backup_count: 10,
+            stop_output: StopOutput::Hold,
+            stop_fade: Duration::from_secs(3),
+            check_for_updates: true,
+            look_pads: false,
+            clock_role: ClockRole::Off,
+            clock_port: 7703,
+            clock_main: "localhost:7703".to_string(),
+            tracking_backup: false,
+            venue_file: None,
+            favorite_profiles: Vec::new(),
+            recent_profiles: Vec::new(),
+            stage_display: StageDisplaySettings::default(),
+            startup: StartupSettings::default(),
+            awake: AwakeSettings::default(),
+        }
+    }
+}
+
+impl Preferences {
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("halo").join("preferences.json"))
+    }
+
+    /// Loads saved preferences, falling back to defaults if there are none or
+    /// the file can't be read.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| File::open(path).ok())
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or_else(|| io::Error::other("no config directory"))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), self)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+//! Per-channel output processing: invert, limits and dimmer curves set on a
+//! fixture in the patch, applied to the finished frame just before it goes
+//! out, so they hold whatever the cues, pixel maps or network input ask for.
+//! Moving heads can also have pan and tilt swapped, inverted and offset, so
+//! a unit hung the other way round moves like the rest of its group.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum Curve {
+    Linear,
+    /// Eases in and out, for dimmers that jump at the bottom or top.
+    SShaped,
+    /// Output levels at evenly spaced input levels from 0 to full, with
+    /// straight lines in between.
+    Custom(Vec<u8>),
+}
+
+impl Curve {
+    /// One of each, for picking a curve in the patch view.
+    pub fn kinds() -> [Curve; 3] {
+        [
+            Curve::Linear,
+            Curve::SShaped,
+            Curve::Custom(vec![0, 64, 128, 191, 255]),
+        ]
+    }
+
+    fn apply(&self, value: u8) -> f32 {
+        let x = value as f32 / 255.0;
+        match self {
+            Curve::Linear => value as f32,
+            Curve::SShaped => x * x * (3.0 - 2.0 * x) * 255.0,
+            Curve::Custom(points) => match points.len() {
+                0 => value as f32,
+                1 => points[0] as f32,
+                len => {
+                    let position = x * (len - 1) as f32;
+                    let below = (position.floor() as usize).min(len - 2);
+                    let t = position - below as f32;
+                    let (a, b) = (points[below] as f32, points[below + 1] as f32);
+                    a + (b - a) * t
+                }
+            },
+        }
+    }
+}
+
+impl fmt::Display for Curve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            Curve::Linear => tr!("curve-linear"),
+            Curve::SShaped => tr!("curve-s"),
+            Curve::Custom(_) => tr!("curve-custom"),
+        })
+    }
+}
+
+/// How one channel of a fixture is processed on the way out.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelProcessing {
+    pub attribute: Attribute,
+    pub curve: Curve,
+    pub invert: bool,
+    /// Output limits in percent, applied last.
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ChannelProcessing {
+    /// Processing that leaves the channel as it is.
+    pub fn new(attribute: Attribute) -> Self {
+        Self {
+            attribute,
+            curve: Curve::Linear,
+            invert: false,
+            min: 0.0,
+            max: 100.0,
+        }
+    }
+
+    pub fn is_passthrough(&self) -> bool {
+        *self == Self::new(self.attribute)
+    }
+
+    pub fn apply(&self, value: u8) -> u8 {
+        let mut level = self.curve.apply(value);
+        if self.invert {
+            level = 255.0 - level;
+        }
+        let min = self.min * 2.55;
+        let max = (self.max * 2.55).max(min);
+        level.clamp(min, max).round() as u8
+    }
+}
+
+/// How a fixture's pan and tilt are turned round on the way out. The
+/// programmed pan and tilt are swapped first; inverting and offsetting then
+/// apply to the fixture's own pan and tilt channels.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PositionAdjust {
+    pub swap: bool,
+    pub invert_pan: bool,
+    pub invert_tilt: bool,
+    /// Added to the output, in DMX steps, to line up the home position.
+    pub pan_offset: i16,
+    pub tilt_offset: i16,
+}
+
+impl PositionAdjust {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn apply(&self, fixture: &Fixture, frame: &mut DmxFrame) {
+        let (Some(pan), Some(tilt)) = (
+            fixture.channel_for(Attribute::Pan),
+            fixture.channel_for(Attribute::Tilt),
+        ) else {
+            return;
+        };
+        let mut values = (
+            frame.get(fixture.universe, pan),
+            frame.get(fixture.universe, tilt),
+        );
+        if self.swap {
+            values = (values.1, values.0);
+        }
+        let adjust = |value: u8, invert: bool, offset: i16| {
+            let value = if invert { 255 - value } else { value };
+            (value as i16 + offset).clamp(0, 255) as u8
+        };
+        frame.set(
+            fixture.universe,
+            pan,
+            adjust(values.0, self.invert_pan, self.pan_offset),
+        );
+        frame.set(
+            fixture.universe,
+            tilt,
+            adjust(values.1, self.invert_tilt, self.tilt_offset),
+        );
+    }
+}
+
+/// Applies every fixture's processing to `frame`.
+pub fn apply(fixtures: &[Fixture], frame: &mut DmxFrame) {
+    for fixture in fixtures {
+        if !fixture.position.is_default() {
+            fixture.position.apply(fixture, frame);
+        }
+        for processing in &fixture.processing {
+            if let Some(channel) = fixture.channel_for(processing.attribute) {
+                let value = frame.get(fixture.universe, channel);
+                frame.set(fixture.universe, channel, processing.apply(value));
+            }
+        }
+    }
+}
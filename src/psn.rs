@@ -0,0 +1,196 @@
+//! PosiStageNet input: tracker positions from a tracking system such as
+//! BlackTrax or Stage Precision, received on a background thread into a
+//! shared [`Trackers`] the output engine aims follow spots from.
+//!
+//! PSN sends little-endian chunks to a multicast group: data packets carry
+//! each tracker's position in metres, and info packets its name.
+
+use crate::HaloApp;
+use eframe::egui;
+use std::collections::HashMap;
+use std::io;
+use std::net::{Ipv4Addr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const MULTICAST_GROUP: Ipv4Addr = Ipv4Addr::new(236, 10, 10, 10);
+const PORT: u16 = 56565;
+const DATA_PACKET: u16 = 0x6755;
+const INFO_PACKET: u16 = 0x6756;
+/// In both packets, the chunk holding the trackers.
+const DATA_TRACKER_LIST: u16 = 0x0001;
+const INFO_TRACKER_LIST: u16 = 0x0002;
+const TRACKER_POSITION: u16 = 0x0000;
+const TRACKER_NAME: u16 = 0x0000;
+/// Trackers that stop sending are dropped from the list after this long.
+const TRACKER_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A tracker as last received.
+#[derive(Clone)]
+pub struct Tracker {
+    pub id: u16,
+    pub name: String,
+    /// x, y and z in metres, y up.
+    pub position: Option<[f32; 3]>,
+    received: Instant,
+}
+
+/// The chunks in `data`, as their id, whether they hold chunks of their own,
+/// and their data.
+fn chunks(mut data: &[u8]) -> impl Iterator<Item = (u16, bool, &[u8])> {
+    std::iter::from_fn(move || {
+        let header = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+        let length = ((header >> 16) & 0x7fff) as usize;
+        let chunk = data.get(4..4 + length)?;
+        data = &data[4 + length..];
+        Some((header as u16, header & 0x8000_0000 != 0, chunk))
+    })
+}
+
+/// The tracker list chunk `list` in the root chunk of `packet`, as each
+/// tracker's id and chunks.
+fn tracker_chunks(packet: &[u8], root: u16, list: u16) -> Vec<(u16, &[u8])> {
+    chunks(packet)
+        .filter(|&(id, _, _)| id == root)
+        .flat_map(|(_, _, data)| chunks(data))
+        .filter(|&(id, children, _)| id == list && children)
+        .flat_map(|(_, _, data)| chunks(data))
+        .map(|(id, _, data)| (id, data))
+        .collect()
+}
+
+fn position(data: &[u8]) -> Option<[f32; 3]> {
+    let value = |i: usize| Some(f32::from_le_bytes(data.get(i..i + 4)?.try_into().ok()?));
+    Some([value(0)?, value(4)?, value(8)?])
+}
+
+/// Tracker `id`, heard from at `now`.
+fn heard(trackers: &mut HashMap<u16, Tracker>, id: u16, now: Instant) -> &mut Tracker {
+    let tracker = trackers.entry(id).or_insert_with(|| Tracker {
+        id,
+        name: String::new(),
+        position: None,
+        received: now,
+    });
+    tracker.received = now;
+    tracker
+}
+
+/// Latest position and name of every tracker heard from, shared between the
+/// receive thread and the output engine.
+#[derive(Clone, Default)]
+pub struct Trackers(Arc<Mutex<HashMap<u16, Tracker>>>);
+
+impl Trackers {
+    fn receive(&self, packet: &[u8], now: Instant) {
+        let Ok(mut trackers) = self.0.lock() else {
+            return;
+        };
+        for (id, data) in tracker_chunks(packet, DATA_PACKET, DATA_TRACKER_LIST) {
+            if let Some(found) = chunks(data)
+                .find(|&(chunk, _, _)| chunk == TRACKER_POSITION)
+                .and_then(|(_, _, data)| position(data))
+            {
+                heard(&mut trackers, id, now).position = Some(found);
+            }
+        }
+        for (id, data) in tracker_chunks(packet, INFO_PACKET, INFO_TRACKER_LIST) {
+            if let Some((_, _, name)) = chunks(data).find(|&(chunk, _, _)| chunk == TRACKER_NAME) {
+                heard(&mut trackers, id, now).name = String::from_utf8_lossy(name).to_string();
+            }
+        }
+    }
+
+    fn clear(&self) {
+        if let Ok(mut trackers) = self.0.lock() {
+            trackers.clear();
+        }
+    }
+
+    /// Where tracker `id` is, if it is still sending.
+    pub fn position(&self, id: u16) -> Option<[f32; 3]> {
+        let trackers = self.0.lock().ok()?;
+        let tracker = trackers.get(&id)?;
+        (tracker.received.elapsed() < TRACKER_TIMEOUT)
+            .then_some(tracker.position)
+            .flatten()
+    }
+
+    /// Trackers still sending, by id.
+    pub fn live(&self) -> Vec<Tracker> {
+        let Ok(mut trackers) = self.0.lock() else {
+            return Vec::new();
+        };
+        trackers.retain(|_, tracker| tracker.received.elapsed() < TRACKER_TIMEOUT);
+        let mut live: Vec<Tracker> = trackers.values().cloned().collect();
+        live.sort_by_key(|tracker| tracker.id);
+        live
+    }
+}
+
+/// Receives PSN on a background thread until dropped.
+pub struct PsnListener {
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+    trackers: Trackers,
+}
+
+impl PsnListener {
+    pub fn start(trackers: Trackers, ctx: egui::Context) -> io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, PORT))
+            .map_err(|err| io::Error::other(format!("PSN port: {}", err)))?;
+        socket.join_multicast_v4(&MULTICAST_GROUP, &Ipv4Addr::UNSPECIFIED)?;
+        // Wake up periodically so the thread exits once stopped.
+        socket.set_read_timeout(Some(Duration::from_millis(250)))?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = shutdown.clone();
+        let shared = trackers.clone();
+        let thread = thread::spawn(move || {
+            let mut buf = [0u8; 1500];
+            let mut repainted = Instant::now();
+            while !stop.load(Ordering::Relaxed) {
+                let Ok(len) = socket.recv(&mut buf) else {
+                    continue;
+                };
+                shared.receive(&buf[..len], Instant::now());
+                // Trackers send fast; the tracker list needn't keep up.
+                if repainted.elapsed() > Duration::from_millis(200) {
+                    ctx.request_repaint();
+                    repainted = Instant::now();
+                }
+            }
+        });
+        Ok(Self {
+            shutdown,
+            thread: Some(thread),
+            trackers,
+        })
+    }
+}
+
+impl Drop for PsnListener {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        self.trackers.clear();
+    }
+}
+
+impl HaloApp {
+    /// Starts or stops PSN input to match the current preferences.
+    pub(crate) fn apply_psn_preferences(&mut self, ctx: &egui::Context) {
+        self.psn = None;
+        self.psn_error = None;
+        if !self.preferences.psn_enabled {
+            return;
+        }
+        match PsnListener::start(self.trackers.clone(), ctx.clone()) {
+            Ok(listener) => self.psn = Some(listener),
+            Err(err) => self.psn_error = Some(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! The quick console: intensity levels set by channel number, the way a
+//! theatre desk works, without opening the cue editor. Channels are the
+//! fixtures' places in the patch, from 1. Levels sit over the cues and
+//! effects until released, and **Record Look** picks them up like anything
+//! else on stage. Levels are set from the faders or the command line; see
+//! `command_line`. A selection can also be moved relative to where it is,
+//! by the encoder, the keyboard or `@ +10` on the command line.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::{Attribute, Fixture};
+use std::collections::BTreeMap;
+
+/// A change to levels relative to where they are.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Relative {
+    /// Up or down by this many percentage points.
+    By(f32),
+    /// Multiplied by this, so the levels keep their proportions.
+    Scale(f32),
+}
+
+impl Relative {
+    /// `level` changed, and kept in range.
+    pub fn apply(self, level: u8) -> u8 {
+        let level = match self {
+            Relative::By(points) => level as f32 + points * 2.55,
+            Relative::Scale(factor) => level as f32 * factor.max(0.0),
+        };
+        level.round().clamp(0.0, 255.0) as u8
+    }
+}
+
+/// Sets the intensity of each fixture with a quick level, by fixture id.
+pub fn apply(levels: &BTreeMap<usize, u8>, fixtures: &[Fixture], frame: &mut DmxFrame) {
+    for fixture in fixtures {
+        let (Some(&level), Some(channel)) = (
+            levels.get(&fixture.id),
+            fixture.channel_for(Attribute::Intensity),
+        ) else {
+            continue;
+        };
+        frame.set(fixture.universe, channel, level);
+    }
+}
@@ -0,0 +1,455 @@
+//! RDM over Art-Net gateways: discover the responders on a universe, read
+//! their device info and set their DMX start address from the patch, so
+//! fixtures can be re-addressed without a ladder. Requests run one at a time
+//! on a background thread and results are published to a shared
+//! [`RdmState`].
+//!
+//! Only Art-Net gateways are supported; USB widgets such as the Enttec DMX
+//! USB Pro Mk2 need a serial driver Halo doesn't have yet.
+
+use crate::art_net::{art_net_header, opcode, ArtNetListener, ArtNetPort, ART_NET_PORT};
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use std::fmt;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const OP_TOD_REQUEST: u16 = 0x8000;
+const OP_TOD_DATA: u16 = 0x8100;
+const OP_TOD_CONTROL: u16 = 0x8200;
+const OP_RDM: u16 = 0x8300;
+/// TodControl command asking the gateway to run a full discovery again.
+const ATC_FLUSH: u8 = 0x01;
+
+const RDM_SUB_START_CODE: u8 = 0x01;
+const GET_COMMAND: u8 = 0x20;
+const SET_COMMAND: u8 = 0x30;
+const RESPONSE_ACK: u8 = 0x00;
+
+const PID_DEVICE_MODEL_DESCRIPTION: u16 = 0x0080;
+const PID_MANUFACTURER_LABEL: u16 = 0x0081;
+const PID_DEVICE_LABEL: u16 = 0x0082;
+const PID_DEVICE_INFO: u16 = 0x0060;
+const PID_DMX_START_ADDRESS: u16 = 0x00F0;
+const PID_IDENTIFY_DEVICE: u16 = 0x1000;
+
+/// How long to collect discovery replies from gateways.
+const DISCOVERY_TIME: Duration = Duration::from_secs(3);
+const REPLY_TIMEOUT: Duration = Duration::from_millis(800);
+const RETRIES: usize = 2;
+
+/// Halo's own RDM UID, in the range set aside for prototyping.
+const CONTROLLER_UID: Uid = Uid {
+    manufacturer: 0x7FF0,
+    device: 0x4841_4C4F,
+};
+
+/// A responder's unique id: manufacturer and device number.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Uid {
+    pub manufacturer: u16,
+    pub device: u32,
+}
+
+impl Uid {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            manufacturer: u16::from_be_bytes([bytes[0], bytes[1]]),
+            device: u32::from_be_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]),
+        }
+    }
+
+    fn to_bytes(self) -> [u8; 6] {
+        let [m0, m1] = self.manufacturer.to_be_bytes();
+        let [d0, d1, d2, d3] = self.device.to_be_bytes();
+        [m0, m1, d0, d1, d2, d3]
+    }
+}
+
+impl fmt::Display for Uid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04X}:{:08X}", self.manufacturer, self.device)
+    }
+}
+
+/// A discovered responder and what it told us about itself.
+#[derive(Clone)]
+pub struct RdmDevice {
+    pub universe: u16,
+    pub uid: Uid,
+    /// The gateway the responder was found behind.
+    gateway: SocketAddr,
+    pub manufacturer: String,
+    pub model: String,
+    pub label: String,
+    /// DMX footprint in the current personality.
+    pub footprint: u16,
+    /// 1 to 512, or `None` for responders without a DMX address.
+    pub address: Option<u16>,
+    pub identifying: bool,
+}
+
+#[derive(Default)]
+struct RdmState {
+    devices: Vec<RdmDevice>,
+    busy: Option<String>,
+    error: Option<String>,
+}
+
+enum RdmRequest {
+    Discover(u16),
+    SetAddress(Uid, u16),
+    Identify(Uid, bool),
+}
+
+/// A background worker talking RDM to the gateways, until dropped.
+pub struct Rdm {
+    state: Arc<Mutex<RdmState>>,
+    requests: Sender<RdmRequest>,
+}
+
+impl Rdm {
+    /// Sends requests from the Art-Net port, which gateways answer on, to
+    /// `gateway` (`host` or `host:port`, usually a broadcast address).
+    pub fn start(gateway: &str, port: &Arc<ArtNetPort>, ctx: egui::Context) -> io::Result<Self> {
+        let target = if gateway.contains(':') {
+            gateway.to_string()
+        } else {
+            format!("{}:{}", gateway, ART_NET_PORT)
+        };
+        let target = target
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::other("no such gateway"))?;
+        let replies = port.listen(&[OP_TOD_DATA, OP_RDM]);
+
+        let state = Arc::new(Mutex::new(RdmState::default()));
+        let (requests, receiver) = mpsc::channel();
+        let shared = state.clone();
+        thread::Builder::new()
+            .name("halo-rdm".to_string())
+            .spawn(move || {
+                let mut worker = Worker {
+                    port: replies,
+                    target,
+                    state: shared,
+                    transaction: 0,
+                    ctx,
+                };
+                worker.run(receiver);
+            })?;
+        Ok(Self { state, requests })
+    }
+
+    pub fn devices(&self) -> Vec<RdmDevice> {
+        self.state
+            .lock()
+            .map(|state| state.devices.clone())
+            .unwrap_or_default()
+    }
+
+    /// What the worker is doing, if anything.
+    pub fn busy(&self) -> Option<String> {
+        self.state.lock().ok().and_then(|state| state.busy.clone())
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.state.lock().ok().and_then(|state| state.error.clone())
+    }
+
+    /// Finds the responders on `universe`, replacing the device list.
+    pub fn discover(&self, universe: u16) {
+        let _ = self.requests.send(RdmRequest::Discover(universe));
+    }
+
+    pub fn set_address(&self, uid: Uid, address: u16) {
+        let _ = self.requests.send(RdmRequest::SetAddress(uid, address));
+    }
+
+    pub fn identify(&self, uid: Uid, on: bool) {
+        let _ = self.requests.send(RdmRequest::Identify(uid, on));
+    }
+}
+
+struct Worker {
+    port: ArtNetListener,
+    target: SocketAddr,
+    state: Arc<Mutex<RdmState>>,
+    transaction: u8,
+    ctx: egui::Context,
+}
+
+impl Worker {
+    fn run(&mut self, requests: Receiver<RdmRequest>) {
+        // Ends when the `Rdm` handle is dropped.
+        for request in requests {
+            let result = match request {
+                RdmRequest::Discover(universe) => self.discover(universe),
+                RdmRequest::SetAddress(uid, address) => self.set_address(uid, address),
+                RdmRequest::Identify(uid, on) => self.identify(uid, on),
+            };
+            self.update(|state| {
+                state.busy = None;
+                state.error = result.err().map(|err| err.to_string());
+            });
+        }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut RdmState)) {
+        if let Ok(mut state) = self.state.lock() {
+            change(&mut state);
+        }
+        self.ctx.request_repaint();
+    }
+
+    fn device(&self, uid: Uid) -> io::Result<RdmDevice> {
+        self.state
+            .lock()
+            .ok()
+            .and_then(|state| state.devices.iter().find(|d| d.uid == uid).cloned())
+            .ok_or_else(|| io::Error::other(tr!("rdm-not-listed", uid = uid.to_string())))
+    }
+
+    fn discover(&mut self, universe: u16) -> io::Result<()> {
+        self.update(|state| {
+            state.devices.clear();
+            state.busy = Some(tr!("rdm-discovering", universe = universe));
+        });
+        let port_address = universe.saturating_sub(1);
+        let [net, sub_uni] = port_address.to_be_bytes();
+
+        let mut flush = art_net_header(OP_TOD_CONTROL);
+        flush.extend_from_slice(&[0; 9]);
+        flush.extend_from_slice(&[net, ATC_FLUSH, sub_uni]);
+        self.port.send_to(&flush, self.target)?;
+
+        let mut request = art_net_header(OP_TOD_REQUEST);
+        request.extend_from_slice(&[0; 9]);
+        request.extend_from_slice(&[net, 0, 1, sub_uni]);
+        request.resize(request.len() + 31, 0);
+        self.port.send_to(&request, self.target)?;
+
+        // Gateways may send several blocks, and send again once the flush
+        // finishes, so collect for a while.
+        let mut found: Vec<(Uid, SocketAddr)> = Vec::new();
+        let deadline = Instant::now() + DISCOVERY_TIME;
+        while let Some(wait) = deadline.checked_duration_since(Instant::now()) {
+            let Some((from, packet)) = self.port.recv_timeout(wait) else {
+                break;
+            };
+            if opcode(&packet) != Some(OP_TOD_DATA) || packet.len() < 28 {
+                continue;
+            }
+            if [packet[21], packet[23]] != [net, sub_uni] {
+                continue;
+            }
+            let count = packet[27] as usize;
+            for chunk in packet[28..].chunks_exact(6).take(count) {
+                let uid = Uid::from_bytes(chunk);
+                if !found.iter().any(|(known, _)| *known == uid) {
+                    found.push((uid, from));
+                }
+            }
+        }
+
+        for (i, (uid, gateway)) in found.iter().enumerate() {
+            self.update(|state| {
+                state.busy = Some(tr!("rdm-reading", number = i + 1, count = found.len()));
+            });
+            let device = self.read_device(universe, *uid, *gateway);
+            self.update(|state| state.devices.push(device));
+        }
+        Ok(())
+    }
+
+    /// Reads what a responder will tell about itself. Anything it doesn't
+    /// answer is left blank rather than dropping the device.
+    fn read_device(&mut self, universe: u16, uid: Uid, gateway: SocketAddr) -> RdmDevice {
+        let mut device = RdmDevice {
+            universe,
+            uid,
+            gateway,
+            manufacturer: String::new(),
+            model: String::new(),
+            label: String::new(),
+            footprint: 0,
+            address: None,
+            identifying: false,
+        };
+        let Ok(info) = self.get(&device, PID_DEVICE_INFO) else {
+            return device;
+        };
+        if info.len() >= 19 {
+            device.footprint = u16::from_be_bytes([info[10], info[11]]);
+            let address = u16::from_be_bytes([info[14], info[15]]);
+            device.address = (1..=512).contains(&address).then_some(address);
+        }
+        let text = |data: io::Result<Vec<u8>>| {
+            data.map(|data| {
+                String::from_utf8_lossy(&data)
+                    .trim_end_matches('\0')
+                    .to_string()
+            })
+            .unwrap_or_default()
+        };
+        device.manufacturer = text(self.get(&device, PID_MANUFACTURER_LABEL));
+        device.model = text(self.get(&device, PID_DEVICE_MODEL_DESCRIPTION));
+        device.label = text(self.get(&device, PID_DEVICE_LABEL));
+        device
+    }
+
+    fn set_address(&mut self, uid: Uid, address: u16) -> io::Result<()> {
+        let device = self.device(uid)?;
+        self.update(|state| {
+            state.busy = Some(tr!("rdm-addressing", uid = uid.to_string()));
+        });
+        self.set(&device, PID_DMX_START_ADDRESS, &address.to_be_bytes())?;
+        // Read it back rather than trusting the ack.
+        let data = self.get(&device, PID_DMX_START_ADDRESS)?;
+        let confirmed = (data.len() >= 2).then(|| u16::from_be_bytes([data[0], data[1]]));
+        self.update(|state| {
+            if let Some(device) = state.devices.iter_mut().find(|d| d.uid == uid) {
+                device.address = confirmed;
+            }
+        });
+        Ok(())
+    }
+
+    fn identify(&mut self, uid: Uid, on: bool) -> io::Result<()> {
+        let device = self.device(uid)?;
+        self.set(&device, PID_IDENTIFY_DEVICE, &[on as u8])?;
+        self.update(|state| {
+            if let Some(device) = state.devices.iter_mut().find(|d| d.uid == uid) {
+                device.identifying = on;
+            }
+        });
+        Ok(())
+    }
+
+    fn get(&mut self, device: &RdmDevice, pid: u16) -> io::Result<Vec<u8>> {
+        self.transact(device, GET_COMMAND, pid, &[])
+    }
+
+    fn set(&mut self, device: &RdmDevice, pid: u16, data: &[u8]) -> io::Result<()> {
+        self.transact(device, SET_COMMAND, pid, data).map(|_| ())
+    }
+
+    /// Sends one RDM request through the device's gateway and waits for the
+    /// matching response, returning its parameter data.
+    fn transact(
+        &mut self,
+        device: &RdmDevice,
+        command: u8,
+        pid: u16,
+        data: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        let [net, sub_uni] = device.universe.saturating_sub(1).to_be_bytes();
+        for _ in 0..RETRIES {
+            self.transaction = self.transaction.wrapping_add(1);
+            let mut packet = art_net_header(OP_RDM);
+            packet.extend_from_slice(&[1, 0]);
+            packet.extend_from_slice(&[0; 7]);
+            packet.extend_from_slice(&[net, 0, sub_uni]);
+            packet.extend_from_slice(&rdm_request(
+                device.uid,
+                self.transaction,
+                command,
+                pid,
+                data,
+            ));
+            self.port.send_to(&packet, device.gateway)?;
+
+            let deadline = Instant::now() + REPLY_TIMEOUT;
+            while let Some(wait) = deadline.checked_duration_since(Instant::now()) {
+                let Some((_, packet)) = self.port.recv_timeout(wait) else {
+                    break;
+                };
+                if opcode(&packet) != Some(OP_RDM) || packet.len() < 24 {
+                    continue;
+                }
+                if let Some(reply) = rdm_response(&packet[24..], device.uid, self.transaction, pid)
+                {
+                    return reply;
+                }
+            }
+        }
+        Err(io::Error::other(tr!(
+            "rdm-no-answer",
+            uid = device.uid.to_string()
+        )))
+    }
+}
+
+/// An RDM request without the start code, as carried in ArtRdm.
+fn rdm_request(to: Uid, transaction: u8, command: u8, pid: u16, data: &[u8]) -> Vec<u8> {
+    let mut packet = vec![RDM_SUB_START_CODE, 24 + data.len() as u8];
+    packet.extend_from_slice(&to.to_bytes());
+    packet.extend_from_slice(&CONTROLLER_UID.to_bytes());
+    // Transaction, port, message count and the root sub-device.
+    packet.extend_from_slice(&[transaction, 1, 0, 0, 0, command]);
+    packet.extend_from_slice(&pid.to_be_bytes());
+    packet.push(data.len() as u8);
+    packet.extend_from_slice(data);
+    // The checksum covers the start code too.
+    let sum = packet
+        .iter()
+        .fold(0xCCu16, |sum, &b| sum.wrapping_add(b as u16));
+    packet.extend_from_slice(&sum.to_be_bytes());
+    packet
+}
+
+/// Reads an RDM response (without the start code) answering our
+/// `transaction`, or `None` if it is for something else.
+fn rdm_response(
+    packet: &[u8],
+    from: Uid,
+    transaction: u8,
+    pid: u16,
+) -> Option<io::Result<Vec<u8>>> {
+    if packet.len() < 23 || packet[0] != RDM_SUB_START_CODE {
+        return None;
+    }
+    if Uid::from_bytes(&packet[8..14]) != from || packet[14] != transaction {
+        return None;
+    }
+    if u16::from_be_bytes([packet[20], packet[21]]) != pid {
+        return None;
+    }
+    let length = packet[22] as usize;
+    let data = packet.get(23..23 + length)?.to_vec();
+    if packet[15] != RESPONSE_ACK {
+        let reason = if data.len() >= 2 {
+            tr!(
+                "rdm-refused-reason",
+                uid = from.to_string(),
+                reason = format!("{:#06X}", u16::from_be_bytes([data[0], data[1]]))
+            )
+        } else {
+            tr!("rdm-refused", uid = from.to_string())
+        };
+        return Some(Err(io::Error::other(reason)));
+    }
+    Some(Ok(data))
+}
+
+impl HaloApp {
+    /// Starts or stops talking RDM to the gateway in the preferences.
+    pub(crate) fn set_rdm_enabled(&mut self, ctx: &egui::Context, enabled: bool) {
+        self.rdm = None;
+        self.rdm_error = None;
+        if enabled {
+            let started = self
+                .art_net_port()
+                .and_then(|port| Rdm::start(&self.preferences.rdm_gateway, &port, ctx.clone()));
+            match started {
+                Ok(rdm) => self.rdm = Some(rdm),
+                Err(err) => self.rdm_error = Some(err.to_string()),
+            }
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! Recording the DMX output to a file and playing a recording back out.
+//!
+//! A recording is every frame sent, as the changes from the frame before, so
+//! a look that holds costs a few bytes a frame. After a header, each frame
+//! is its time in milliseconds from the start and the number of universes
+//! that changed; each universe is its number and the number of runs of
+//! changed channels, and each run its first channel, its length and the
+//! levels. Everything is little-endian. A universe seen for the first time
+//! changed from all zeros, and is there from then on. A file cut off part
+//! way through a frame, as a crash while recording leaves it, ends with the
+//! frame before.
+//!
+//! The engine records on its own thread, so the file has every frame rather
+//! than the ones the UI got round to drawing. It can record the Art-Net and
+//! sACN input instead, to capture another console's show for
+//! [`stream_import`](crate::stream_import) to turn into cues. Played back, a
+//! recording takes over the output on its own clock, whether or not the show
+//! is playing.
+
+use crate::dmx::{DmxFrame, UNIVERSE_SIZE};
+use crate::i18n::tr;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// The file extension recordings are given.
+pub const EXTENSION: &str = "halodmx";
+
+const MAGIC: &[u8; 8] = b"HALODMX1";
+
+/// Unchanged channels between two changed ones cost less inside a run than
+/// the four bytes of starting another.
+const RUN_GAP: usize = 4;
+
+/// How often a recording is written out to disk, so a crash loses at most
+/// this much of it.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+fn read_u16(reader: &mut impl Read) -> io::Result<u16> {
+    let mut bytes = [0; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+/// The next frame's time, or None at the end of the file.
+fn read_time(reader: &mut impl Read) -> io::Result<Option<Duration>> {
+    let mut bytes = [0; 4];
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(
+            Duration::from_millis(u32::from_le_bytes(bytes) as u64),
+        )),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Applies a frame's changes, after its time, to `frame`.
+fn read_changes(reader: &mut impl Read, frame: &mut DmxFrame) -> io::Result<()> {
+    for _ in 0..read_u16(reader)? {
+        let universe = frame.universe_mut(read_u16(reader)?);
+        for _ in 0..read_u16(reader)? {
+            let first = read_u16(reader)? as usize;
+            let len = read_u16(reader)? as usize;
+            let run = first
+                .checked_sub(1)
+                .and_then(|start| universe.get_mut(start..start + len))
+                .ok_or_else(|| invalid("channel run past the end of the universe"))?;
+            reader.read_exact(run)?;
+        }
+    }
+    Ok(())
+}
+
+/// The runs of channels that differ between `old` and `new`, as 0-based
+/// start and end.
+fn changed_runs(old: &[u8; UNIVERSE_SIZE], new: &[u8; UNIVERSE_SIZE]) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for channel in (0..UNIVERSE_SIZE).filter(|&i| old[i] != new[i]) {
+        match runs.last_mut() {
+            Some((_, end)) if channel - *end <= RUN_GAP => *end = channel + 1,
+            _ => runs.push((channel, channel + 1)),
+        }
+    }
+    runs
+}
+
+/// What a recording captures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecordSource {
+    /// Every frame Halo sends.
+    #[default]
+    Output,
+    /// What is coming in over Art-Net and sACN, every universe.
+    Input,
+}
+
+impl RecordSource {
+    pub const ALL: [RecordSource; 2] = [RecordSource::Output, RecordSource::Input];
+}
+
+impl fmt::Display for RecordSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&match self {
+            RecordSource::Output => tr!("record-source-output"),
+            RecordSource::Input => tr!("record-source-input"),
+        })
+    }
+}
+
+/// Writes frames to a recording as they are sent or received.
+pub struct Recorder {
+    writer: BufWriter<File>,
+    source: RecordSource,
+    started: Option<Instant>,
+    flushed: Option<Instant>,
+    last: DmxFrame,
+    length: Duration,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, source: RecordSource) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        Ok(Self {
+            writer,
+            source,
+            started: None,
+            flushed: None,
+            last: DmxFrame::default(),
+            length: Duration::ZERO,
+        })
+    }
+
+    /// Adds `frame`, sent at `now`. The recording starts with the first.
+    pub fn record(&mut self, frame: &DmxFrame, now: Instant) -> io::Result<()> {
+        let at = now.saturating_duration_since(*self.started.get_or_insert(now));
+        let blank = [0; UNIVERSE_SIZE];
+        let changed: Vec<(u16, Vec<(usize, usize)>)> = frame
+            .universe_numbers()
+            .filter_map(|number| {
+                let new = frame.universe(number)?;
+                let old = self.last.universe(number);
+                let runs = changed_runs(old.unwrap_or(&blank), new);
+                // A new universe is written even when dark, so it is sent.
+                (!runs.is_empty() || old.is_none()).then_some((number, runs))
+            })
+            .collect();
+        let out = &mut self.writer;
+        out.write_all(&(at.as_millis() as u32).to_le_bytes())?;
+        out.write_all(&(changed.len() as u16).to_le_bytes())?;
+        for (number, runs) in changed {
+            let Some(levels) = frame.universe(number) else {
+                continue;
+            };
+            out.write_all(&number.to_le_bytes())?;
+            out.write_all(&(runs.len() as u16).to_le_bytes())?;
+            for (start, end) in runs {
+                out.write_all(&(start as u16 + 1).to_le_bytes())?;
+                out.write_all(&((end - start) as u16).to_le_bytes())?;
+                out.write_all(&levels[start..end])?;
+            }
+        }
+        self.last = frame.clone();
+        self.length = at;
+        let flushed = *self.flushed.get_or_insert(now);
+        if now.saturating_duration_since(flushed) >= FLUSH_INTERVAL {
+            self.writer.flush()?;
+            self.flushed = Some(now);
+        }
+        Ok(())
+    }
+
+    pub fn source(&self) -> RecordSource {
+        self.source
+    }
+
+    /// How long has been recorded.
+    pub fn length(&self) -> Duration {
+        self.length
+    }
+
+    /// Writes out what is still buffered.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Reads a recording through a frame at a time.
+pub struct Reader {
+    reader: BufReader<File>,
+    frame: DmxFrame,
+    /// When the next frame in the file is due, or None at the end.
+    next: Option<Duration>,
+}
+
+impl Reader {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a Halo DMX recording"));
+        }
+        let next = read_time(&mut reader)?;
+        Ok(Self {
+            reader,
+            frame: DmxFrame::default(),
+            next,
+        })
+    }
+
+    /// The next frame and its time, or None at the end.
+    pub fn next_frame(&mut self) -> io::Result<Option<(Duration, &DmxFrame)>> {
+        let Some(at) = self.next else {
+            return Ok(None);
+        };
+        // Read into a copy, so a frame the file cuts off isn't half played.
+        let mut frame = self.frame.clone();
+        match read_changes(&mut self.reader, &mut frame) {
+            Ok(()) => self.frame = frame,
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                self.next = None;
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        }
+        self.next = read_time(&mut self.reader)?;
+        Ok(Some((at, &self.frame)))
+    }
+
+    /// When the next frame is due, or None at the end.
+    pub fn next_at(&self) -> Option<Duration> {
+        self.next
+    }
+
+    /// The last frame read.
+    pub fn frame(&self) -> &DmxFrame {
+        &self.frame
+    }
+
+    /// Goes back to before the first frame.
+    pub fn rewind(&mut self) -> io::Result<()> {
+        self.reader.seek(SeekFrom::Start(MAGIC.len() as u64))?;
+        self.next = read_time(&mut self.reader)?;
+        self.frame = DmxFrame::default();
+        Ok(())
+    }
+}
+
+/// Plays a recording back a frame at a time.
+pub struct Player {
+    reader: Reader,
+    started: Option<Instant>,
+    position: Duration,
+    length: Duration,
+    looping: bool,
+    finished: bool,
+}
+
+impl Player {
+    /// Opens a recording, reading it through once for its length.
+    pub fn open(path: &Path, looping: bool) -> io::Result<Self> {
+        let mut reader = Reader::open(path)?;
+        let mut length = Duration::ZERO;
+        while let Some((at, _)) = reader.next_frame()? {
+            length = at;
+        }
+        reader.rewind()?;
+        Ok(Self {
+            reader,
+            started: None,
+            position: Duration::ZERO,
+            length,
+            looping,
+            finished: false,
+        })
+    }
+
+    /// The frame due at `now`. Playback starts with the first call.
+    pub fn frame(&mut self, now: Instant) -> io::Result<&DmxFrame> {
+        let started = *self.started.get_or_insert(now);
+        self.position = now.saturating_duration_since(started);
+        // Looping holds the last frame until the tick after it, then starts
+        // again from the top.
+        if self.looping && self.reader.next_at().is_none() && self.position > self.length {
+            self.reader.rewind()?;
+            self.started = Some(now);
+            self.position = Duration::ZERO;
+        }
+        while self.reader.next_at().is_some_and(|at| at <= self.position) {
+            self.reader.next_frame()?;
+        }
+        self.finished = !self.looping && self.reader.next_at().is_none();
+        Ok(self.reader.frame())
+    }
+
+    /// How far into the recording playback is.
+    pub fn position(&self) -> Duration {
+        self.position
+    }
+
+    pub fn length(&self) -> Duration {
+        self.length
+    }
+
+    /// Whether the last frame has been played, without looping.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn frame(level: u8) -> DmxFrame {
+        let mut frame = DmxFrame::default();
+        frame.universe_mut(1)[..4].fill(level);
+        frame
+    }
+
+    #[test]
+    fn recording_cut_off_mid_frame_ends_at_the_frame_before() {
+        let path =
+            std::env::temp_dir().join(format!("halo-cut-off-{}.halodmx", std::process::id()));
+        let start = Instant::now();
+        let mut recorder = Recorder::create(&path, RecordSource::Output).unwrap();
+        for (i, level) in [10, 20, 30].into_iter().enumerate() {
+            let now = start + Duration::from_millis(100 * i as u64);
+            recorder.record(&frame(level), now).unwrap();
+        }
+        recorder.finish().unwrap();
+        let whole = fs::read(&path).unwrap();
+        // The last frame's time, universe count and universe number, but
+        // not its runs.
+        fs::write(&path, &whole[..whole.len() - 8]).unwrap();
+
+        let mut player = Player::open(&path, false).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(player.length(), Duration::from_millis(100));
+        player.frame(start).unwrap();
+        let last = player.frame(start + Duration::from_secs(1)).unwrap();
+        assert_eq!(last.universe(1), frame(20).universe(1));
+        assert!(player.is_finished());
+    }
+}
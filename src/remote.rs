@@ -0,0 +1,374 @@
+//! Remote control over TCP. Each connection sends one JSON object per line
+//! and receives one JSON object per line in reply; the protocol is documented
+//! in the README. The HTTP endpoints in `http` feed the same requests.
+
+use crate::binding::InputEvent;
+use crate::cue::CueNumber;
+use crate::follow::FollowState;
+use crate::http;
+use crate::i18n::tr;
+use crate::macros::MacroAction;
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum RemoteRequest {
+    FireCue {
+        cue: String,
+    },
+    SetBpm {
+        bpm: f32,
+    },
+    /// Toggles the override pad at the 1-based position `pad`.
+    TogglePad {
+        pad: usize,
+    },
+    Start,
+    Stop,
+    Pause,
+    Reset,
+    /// Runs whatever is bound to the HTTP trigger `name`; see `binding`.
+    Trigger {
+        name: String,
+    },
+    State,
+    /// A designer view polling for state; see `follow`.
+    Follow {
+        name: String,
+    },
+    /// A designer view asking to fire a cue in a section it was granted.
+    FollowGo {
+        name: String,
+        cue: String,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CueState {
+    pub number: CueNumber,
+    pub name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PadState {
+    pub pad: usize,
+    pub label: String,
+    pub active: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RemoteState {
+    pub running: bool,
+    pub timecode: String,
+    pub bpm: f32,
+    pub current_cue: Option<CueState>,
+    pub pads: Vec<PadState>,
+}
+
+#[derive(Serialize)]
+pub struct RemoteResponse {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<RemoteState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follow: Option<FollowState>,
+}
+
+impl RemoteResponse {
+    pub fn ok() -> Self {
+        Self {
+            ok: true,
+            error: None,
+            state: None,
+            follow: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            error: Some(message.into()),
+            state: None,
+            follow: None,
+        }
+    }
+}
+
+/// A request handed to the UI thread along with where to send the answer.
+pub struct RemoteMessage {
+    pub request: RemoteRequest,
+    pub reply: Sender<RemoteResponse>,
+}
+
+/// Serves one client connection, forwarding its requests to the UI thread
+/// until the client disconnects or `stop` is set.
+pub type ConnectionHandler =
+    fn(TcpStream, Sender<RemoteMessage>, egui::Context, Arc<AtomicBool>) -> io::Result<()>;
+
+/// Listens for remote connections on a background thread until dropped.
+pub struct RemoteServer {
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RemoteServer {
+    pub fn start(
+        port: u16,
+        sender: Sender<RemoteMessage>,
+        ctx: egui::Context,
+        handler: ConnectionHandler,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let stop = shutdown.clone();
+        let thread = thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let sender = sender.clone();
+                        let ctx = ctx.clone();
+                        let stop = stop.clone();
+                        thread::spawn(move || {
+                            let _ = handler(stream, sender, ctx, stop);
+                        });
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(50));
+                    }
+                    Err(_) => thread::sleep(Duration::from_millis(250)),
+                }
+            }
+        });
+
+        Ok(Self {
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Wait for the listener to close so the port can be bound again.
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Forwards a request to the UI thread and waits for its answer.
+pub fn dispatch(
+    request: RemoteRequest,
+    sender: &Sender<RemoteMessage>,
+    ctx: &egui::Context,
+) -> RemoteResponse {
+    let (reply, answer) = mpsc::channel();
+    if sender.send(RemoteMessage { request, reply }).is_err() {
+        return RemoteResponse::error("Halo is shutting down");
+    }
+    ctx.request_repaint();
+    answer
+        .recv_timeout(Duration::from_secs(2))
+        .unwrap_or_else(|_| RemoteResponse::error("timed out waiting for Halo"))
+}
+
+fn handle_json_connection(
+    stream: TcpStream,
+    sender: Sender<RemoteMessage>,
+    ctx: egui::Context,
+    stop: Arc<AtomicBool>,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    // Wake up periodically so connections close when the server is stopped.
+    stream.set_read_timeout(Some(Duration::from_millis(500)))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(err)
+                if err.kind() == io::ErrorKind::WouldBlock
+                    || err.kind() == io::ErrorKind::TimedOut =>
+            {
+                continue;
+            }
+            Err(err) => return Err(err),
+        }
+        if line.trim().is_empty() {
+            line.clear();
+            continue;
+        }
+
+        let response = match serde_json::from_str::<RemoteRequest>(line.trim()) {
+            Ok(request) => dispatch(request, &sender, &ctx),
+            Err(err) => RemoteResponse::error(format!("invalid request: {}", err)),
+        };
+        line.clear();
+
+        serde_json::to_writer(&mut writer, &response)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+impl HaloApp {
+    /// Starts, restarts or stops the TCP and HTTP servers to match the current
+    /// preferences.
+    pub(crate) fn apply_remote_preferences(&mut self, ctx: &egui::Context) {
+        self.apply_json_remote_preferences(ctx);
+        self.apply_http_preferences(ctx);
+    }
+
+    /// Starts, restarts or stops the TCP server to match the current
+    /// preferences.
+    pub(crate) fn apply_json_remote_preferences(&mut self, ctx: &egui::Context) {
+        // Drop the old server first so its port is free to rebind.
+        self.remote = None;
+        let (enabled, port) = (
+            self.preferences.remote_enabled,
+            self.preferences.remote_port,
+        );
+        (self.remote, self.remote_error) =
+            self.start_server(enabled, port, handle_json_connection, ctx);
+    }
+
+    /// Starts, restarts or stops the HTTP server to match the current
+    /// preferences.
+    pub(crate) fn apply_http_preferences(&mut self, ctx: &egui::Context) {
+        self.http = None;
+        let (enabled, port) = (self.preferences.http_enabled, self.preferences.http_port);
+        (self.http, self.http_error) =
+            self.start_server(enabled, port, http::handle_connection, ctx);
+    }
+
+    fn start_server(
+        &self,
+        enabled: bool,
+        port: u16,
+        handler: ConnectionHandler,
+        ctx: &egui::Context,
+    ) -> (Option<RemoteServer>, Option<String>) {
+        if !enabled {
+            return (None, None);
+        }
+        match RemoteServer::start(port, self.remote_sender.clone(), ctx.clone(), handler) {
+            Ok(server) => (Some(server), None),
+            Err(err) => (
+                None,
+                Some(tr!("listen-failed", port = port, error = err.to_string())),
+            ),
+        }
+    }
+
+    pub(crate) fn handle_remote_requests(&mut self) {
+        let messages: Vec<RemoteMessage> = self.remote_requests.try_iter().collect();
+        for message in messages {
+            let response = self.handle_remote_request(message.request);
+            let _ = message.reply.send(response);
+        }
+    }
+
+    fn handle_remote_request(&mut self, request: RemoteRequest) -> RemoteResponse {
+        match request {
+            RemoteRequest::FireCue { cue } => {
+                let Some(number) = CueNumber::parse(&cue) else {
+                    return RemoteResponse::error(format!("invalid cue number {:?}", cue));
+                };
+                if !self.show.cues.iter().any(|cue| cue.number == number) {
+                    return RemoteResponse::error(format!("no cue {}", number));
+                }
+                self.perform(MacroAction::FireCue(number));
+                RemoteResponse::ok()
+            }
+            RemoteRequest::SetBpm { bpm } => {
+                if !(20.0..=300.0).contains(&bpm) {
+                    return RemoteResponse::error("bpm must be between 20 and 300");
+                }
+                self.perform(MacroAction::SetBpm(bpm));
+                RemoteResponse::ok()
+            }
+            RemoteRequest::TogglePad { pad } => {
+                match pad.checked_sub(1).and_then(|i| self.show.pads.get(i)) {
+                    Some(pad) => {
+                        self.perform(MacroAction::SetPad {
+                            pad: pad.label.clone(),
+                            active: !pad.active,
+                        });
+                        RemoteResponse::ok()
+                    }
+                    None => RemoteResponse::error(format!("no pad {}", pad)),
+                }
+            }
+            RemoteRequest::Start => {
+                self.perform(MacroAction::Start);
+                RemoteResponse::ok()
+            }
+            RemoteRequest::Stop => {
+                self.perform(MacroAction::Stop);
+                RemoteResponse::ok()
+            }
+            RemoteRequest::Pause => {
+                self.perform(MacroAction::Pause);
+                RemoteResponse::ok()
+            }
+            RemoteRequest::Reset => {
+                self.perform(MacroAction::Reset);
+                RemoteResponse::ok()
+            }
+            RemoteRequest::Trigger { name } => {
+                if self.handle_input_event(InputEvent::Http(name.clone())) {
+                    RemoteResponse::ok()
+                } else {
+                    RemoteResponse::error(format!("nothing is bound to trigger {:?}", name))
+                }
+            }
+            RemoteRequest::State => RemoteResponse {
+                state: Some(self.remote_state()),
+                ..RemoteResponse::ok()
+            },
+            RemoteRequest::Follow { name } => self.follow_state(&name),
+            RemoteRequest::FollowGo { name, cue } => self.follower_go(&name, &cue),
+        }
+    }
+
+    pub(crate) fn remote_state(&self) -> RemoteState {
+        RemoteState {
+            running: self.transport.is_running(),
+            timecode: self.format_timecode(),
+            bpm: self.bpm,
+            current_cue: self.current_cue().map(|cue| CueState {
+                number: cue.number,
+                name: cue.name.clone(),
+            }),
+            pads: self
+                .show
+                .pads
+                .iter()
+                .enumerate()
+                .map(|(i, pad)| PadState {
+                    pad: i + 1,
+                    label: pad.label.clone(),
+                    active: pad.active,
+                })
+                .collect(),
+        }
+    }
+}
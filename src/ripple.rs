@@ -0,0 +1,75 @@
+//! Moving cues in time, one at a time or rippling: in ripple mode, moving a
+//! cue moves every later cue in the same song (or, for a cue outside any
+//! song, every later cue outside songs) by the same amount, so a song edit
+//! that adds or cuts a few bars mid-production is one move rather than
+//! dozens. Selected cues can also be nudged from the keyboard a second or a
+//! timecode frame at a time.
+
+use crate::i18n::tr;
+use crate::show::Show;
+use crate::HaloApp;
+use std::time::Duration;
+
+/// Frame rate for frame nudges when no timecode is coming in.
+const DEFAULT_FPS: f32 = 25.0;
+
+impl Show {
+    /// Moves the cue at `index` to start at `start`, within its song if it
+    /// has one, taking the later cues with it if `ripple`. Returns the
+    /// indices of the other cues moved.
+    pub fn move_cue(&mut self, index: usize, start: Duration, ripple: bool) -> Vec<usize> {
+        let Some(cue) = self.cues.get(index) else {
+            return Vec::new();
+        };
+        let (from, song) = (cue.start_time, cue.song);
+        let later: Vec<usize> = if ripple {
+            self.cues
+                .iter()
+                .enumerate()
+                .filter(|&(i, other)| i != index && other.song == song && other.start_time > from)
+                .map(|(i, _)| i)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        self.cues[index].start_time = start;
+        for &i in &later {
+            let cue = &mut self.cues[i];
+            // Later cues keep their distance from the moved one.
+            cue.start_time = start + (cue.start_time - from);
+        }
+        later
+    }
+}
+
+impl HaloApp {
+    /// Moves the selected cue `seconds` later, or earlier if negative, no
+    /// further back than its song's start.
+    pub(crate) fn nudge_selected_cue(&mut self, seconds: f32) {
+        let Some(index) = self.selected_cue else {
+            return;
+        };
+        let Some(cue) = self.show.cues.get(index) else {
+            return;
+        };
+        let from = cue.start_time.as_secs_f32();
+        let start = Duration::from_secs_f32((from + seconds).max(0.0));
+        let number = cue.number;
+        let moved = self.show.move_cue(index, start, self.ripple_edit);
+        self.log.info(tr!(
+            "ripple-nudged",
+            number = number.to_string(),
+            count = moved.len(),
+            seconds = format!("{:+.3}", start.as_secs_f32() - from)
+        ));
+    }
+
+    /// One frame of the incoming timecode, or of 25 fps without any.
+    pub(crate) fn nudge_frame(&self) -> f32 {
+        let fps = self
+            .timecode_in
+            .as_ref()
+            .map_or(DEFAULT_FPS, |(timecode, _)| timecode.fps);
+        1.0 / fps
+    }
+}
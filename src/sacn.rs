@@ -0,0 +1,300 @@
+//! sACN (E1.31) output: every universe in the frame multicast to its sACN
+//! group, at a priority set per universe, for rigs that share universes
+//! with a house system or another console.
+//!
+//! A universe can also carry per-address priority, the 0xDD start code
+//! that receivers supporting it arbitrate slot by slot: Halo claims only the
+//! addresses its fixtures are patched at, at the universe's priority, and
+//! sends 0 for the rest so the house system keeps them.
+//!
+//! Universes are numbered from 1 as in the rest of Halo and go out on the
+//! same sACN universe.
+
+use crate::art_net::KEEP_ALIVE;
+use crate::dmx::{DmxFrame, UNIVERSE_SIZE};
+use crate::fixture::Fixture;
+use crate::interfaces::NetworkInterface;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub const SACN_PORT: u16 = 5568;
+const ACN_ID: &[u8] = b"ASC-E1.17\0\0\0";
+const ROOT_DATA: u32 = 4;
+const FRAMING_DATA: u32 = 2;
+/// The start code of per-address priority packets.
+pub const PRIORITY_START_CODE: u8 = 0xDD;
+/// Options bit: the source is stopping and receivers should let go now.
+const TERMINATED: u8 = 0x40;
+/// The priority sACN sources send at unless set otherwise.
+pub const DEFAULT_PRIORITY: u8 = 100;
+/// E1.31 priorities run from 0 to 200.
+pub const MAX_PRIORITY: u8 = 200;
+
+/// How one universe goes out.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SacnUniverse {
+    pub universe: u16,
+    pub priority: u8,
+    /// Claim only the patched addresses, with per-address priority.
+    pub per_address: bool,
+}
+
+impl SacnUniverse {
+    pub fn new(universe: u16) -> Self {
+        Self {
+            universe,
+            priority: DEFAULT_PRIORITY,
+            per_address: false,
+        }
+    }
+}
+
+/// Where the output goes over sACN, kept in the preferences since it depends
+/// on the network rather than the show.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SacnOutput {
+    pub enabled: bool,
+    /// The name receivers list Halo under.
+    pub source_name: String,
+    /// Halo's component id, which receivers tell sources apart by. Made up
+    /// once and kept, so a restart is the same source to them.
+    pub cid: [u8; 16],
+    /// The priority of universes not listed in `universes`.
+    pub priority: u8,
+    /// Universes with their own priority.
+    pub universes: Vec<SacnUniverse>,
+}
+
+impl Default for SacnOutput {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source_name: "Halo".to_string(),
+            cid: new_cid(),
+            priority: DEFAULT_PRIORITY,
+            universes: Vec::new(),
+        }
+    }
+}
+
+impl SacnOutput {
+    /// How `universe` goes out: as listed, or at the default priority.
+    pub fn universe(&self, universe: u16) -> SacnUniverse {
+        self.universes
+            .iter()
+            .find(|listed| listed.universe == universe)
+            .copied()
+            .unwrap_or(SacnUniverse {
+                priority: self.priority,
+                ..SacnUniverse::new(universe)
+            })
+    }
+}
+
+/// A random version 4 UUID, from the standard library's randomly keyed
+/// hasher and the time.
+fn new_cid() -> [u8; 16] {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_nanos());
+    let mut cid = [0u8; 16];
+    for half in cid.chunks_mut(8) {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(now);
+        half.copy_from_slice(&hasher.finish().to_be_bytes());
+    }
+    cid[6] = (cid[6] & 0x0F) | 0x40;
+    cid[8] = (cid[8] & 0x3F) | 0x80;
+    cid
+}
+
+/// The multicast group a universe is sent to.
+pub fn multicast_group(universe: u16) -> Ipv4Addr {
+    let [hi, lo] = universe.to_be_bytes();
+    Ipv4Addr::new(239, 255, hi, lo)
+}
+
+/// An E1.31 data packet with `start_code` and a full universe of slots.
+fn data_packet(
+    settings: &SacnOutput,
+    universe: SacnUniverse,
+    sequence: u8,
+    options: u8,
+    start_code: u8,
+    data: &[u8; UNIVERSE_SIZE],
+) -> Vec<u8> {
+    let length = 126 + UNIVERSE_SIZE;
+    // Each layer's flags and length: 0x7 then the length from its start.
+    let flags_length = |start: usize| (0x7000 | (length - start) as u16).to_be_bytes();
+    let mut packet = Vec::with_capacity(length);
+    // Root layer.
+    packet.extend_from_slice(&[0x00, 0x10, 0x00, 0x00]);
+    packet.extend_from_slice(ACN_ID);
+    packet.extend_from_slice(&flags_length(16));
+    packet.extend_from_slice(&ROOT_DATA.to_be_bytes());
+    packet.extend_from_slice(&settings.cid);
+    // Framing layer.
+    packet.extend_from_slice(&flags_length(38));
+    packet.extend_from_slice(&FRAMING_DATA.to_be_bytes());
+    let mut name = [0u8; 64];
+    let bytes = settings.source_name.as_bytes();
+    let len = bytes.len().min(63);
+    name[..len].copy_from_slice(&bytes[..len]);
+    packet.extend_from_slice(&name);
+    packet.push(universe.priority.min(MAX_PRIORITY));
+    // No synchronisation universe.
+    packet.extend_from_slice(&[0, 0]);
+    packet.push(sequence);
+    packet.push(options);
+    packet.extend_from_slice(&universe.universe.to_be_bytes());
+    // DMP layer: set property, one byte each from address 0.
+    packet.extend_from_slice(&flags_length(115));
+    packet.extend_from_slice(&[0x02, 0xA1, 0x00, 0x00, 0x00, 0x01]);
+    packet.extend_from_slice(&(UNIVERSE_SIZE as u16 + 1).to_be_bytes());
+    packet.push(start_code);
+    packet.extend_from_slice(data);
+    packet
+}
+
+/// The addresses of `universe` that fixtures are patched at, at `priority`,
+/// and 0 everywhere else.
+fn address_priorities(fixtures: &[Fixture], universe: u16, priority: u8) -> [u8; UNIVERSE_SIZE] {
+    let mut priorities = [0u8; UNIVERSE_SIZE];
+    for fixture in fixtures
+        .iter()
+        .filter(|fixture| fixture.universe == universe && fixture.driver.is_none())
+    {
+        let start = (fixture.address as usize).saturating_sub(1);
+        let end = (start + fixture.profile.channels.len()).min(UNIVERSE_SIZE);
+        if start < end {
+            priorities[start..end].fill(priority);
+        }
+    }
+    priorities
+}
+
+/// What was last sent on one universe.
+struct Sent {
+    settings: SacnUniverse,
+    sequence: u8,
+    priorities: Option<(Instant, [u8; UNIVERSE_SIZE])>,
+}
+
+/// Sends frames from the output engine, and tells receivers it has stopped
+/// when dropped.
+pub struct SacnSender {
+    socket: UdpSocket,
+    interface: Option<NetworkInterface>,
+    settings: SacnOutput,
+    universes: HashMap<u16, Sent>,
+}
+
+impl SacnSender {
+    /// Opens a socket on `interface`, or on all interfaces.
+    pub fn open(interface: Option<NetworkInterface>) -> io::Result<Self> {
+        let address = interface
+            .as_ref()
+            .map_or(Ipv4Addr::UNSPECIFIED, |interface| interface.address);
+        let socket = UdpSocket::bind((address, 0))?;
+        // Halo's own sACN input would otherwise merge its output back in.
+        socket.set_multicast_loop_v4(false)?;
+        Ok(Self {
+            socket,
+            interface,
+            settings: SacnOutput::default(),
+            universes: HashMap::new(),
+        })
+    }
+
+    pub fn interface(&self) -> Option<&NetworkInterface> {
+        self.interface.as_ref()
+    }
+
+    fn send_packet(
+        &mut self,
+        universe: SacnUniverse,
+        options: u8,
+        start_code: u8,
+        data: &[u8; UNIVERSE_SIZE],
+    ) {
+        let sent = self
+            .universes
+            .entry(universe.universe)
+            .or_insert_with(|| Sent {
+                settings: universe,
+                sequence: 0,
+                priorities: None,
+            });
+        sent.settings = universe;
+        sent.sequence = sent.sequence.wrapping_add(1);
+        let packet = data_packet(
+            &self.settings,
+            universe,
+            sent.sequence,
+            options,
+            start_code,
+            data,
+        );
+        let address = SocketAddr::from((multicast_group(universe.universe), SACN_PORT));
+        let _ = self.socket.send_to(&packet, address);
+    }
+
+    /// Sends every universe in `frame` at its priority, with the per-address
+    /// priorities of those that claim only their patched addresses, again
+    /// at least every [`KEEP_ALIVE`] or as soon as the patch changes them.
+    pub fn send(&mut self, settings: &SacnOutput, fixtures: &[Fixture], frame: &DmxFrame) {
+        if settings.cid != self.settings.cid || settings.source_name != self.settings.source_name {
+            // Receivers would see a new source alongside the old one.
+            self.terminate_all();
+        }
+        self.settings.clone_from(settings);
+        let now = Instant::now();
+        for number in frame.universe_numbers() {
+            let Some(data) = frame.universe(number) else {
+                continue;
+            };
+            let universe = settings.universe(number);
+            if universe.per_address {
+                let priorities = address_priorities(fixtures, number, universe.priority);
+                let due = self.universes.get(&number).is_none_or(|sent| {
+                    sent.priorities.is_none_or(|(at, sent)| {
+                        sent != priorities || now.saturating_duration_since(at) >= KEEP_ALIVE
+                    })
+                });
+                if due {
+                    self.send_packet(universe, 0, PRIORITY_START_CODE, &priorities);
+                    if let Some(sent) = self.universes.get_mut(&number) {
+                        sent.priorities = Some((now, priorities));
+                    }
+                }
+            }
+            self.send_packet(universe, 0, 0, data);
+        }
+    }
+
+    /// Tells receivers every universe sent has stopped, three times over as
+    /// E1.31 asks, so they let go straight away rather than holding the
+    /// last look until they time out.
+    fn terminate_all(&mut self) {
+        let universes: Vec<SacnUniverse> =
+            self.universes.values().map(|sent| sent.settings).collect();
+        for universe in universes {
+            for _ in 0..3 {
+                self.send_packet(universe, TERMINATED, 0, &[0; UNIVERSE_SIZE]);
+            }
+        }
+        self.universes.clear();
+    }
+}
+
+impl Drop for SacnSender {
+    fn drop(&mut self) {
+        self.terminate_all();
+    }
+}
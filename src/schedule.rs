@@ -0,0 +1,200 @@
+//! Scheduled triggers: actions run at a wall-clock time or at sunrise or
+//! sunset, so Halo can run architectural and installation lighting
+//! unattended.
+
+use crate::binding::BindingAction;
+use crate::i18n::tr;
+use crate::HaloApp;
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt;
+use std::time::Duration;
+
+/// Where the venue is, for working out sunrise and sunset. North and east
+/// are positive.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Self {
+            latitude: 51.5,
+            longitude: -0.1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScheduleTime {
+    TimeOfDay {
+        hour: u32,
+        minute: u32,
+    },
+    /// Minutes after sunrise, or before it when negative.
+    Sunrise {
+        offset_minutes: i32,
+    },
+    /// Minutes after sunset, or before it when negative.
+    Sunset {
+        offset_minutes: i32,
+    },
+}
+
+impl ScheduleTime {
+    /// One of each kind, with defaults, for picking a kind in the editor.
+    pub fn kinds() -> [ScheduleTime; 3] {
+        [
+            ScheduleTime::TimeOfDay {
+                hour: 18,
+                minute: 0,
+            },
+            ScheduleTime::Sunrise { offset_minutes: 0 },
+            ScheduleTime::Sunset { offset_minutes: 0 },
+        ]
+    }
+
+    pub fn kind(&self) -> String {
+        match self {
+            ScheduleTime::TimeOfDay { .. } => tr!("schedule-time-of-day"),
+            ScheduleTime::Sunrise { .. } => tr!("schedule-sunrise"),
+            ScheduleTime::Sunset { .. } => tr!("schedule-sunset"),
+        }
+    }
+
+    /// When the trigger goes off on `date`, or `None` if the sun doesn't
+    /// rise or set there that day.
+    pub fn on(&self, date: NaiveDate, location: Location) -> Option<DateTime<Local>> {
+        match *self {
+            ScheduleTime::TimeOfDay { hour, minute } => {
+                let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+                Local.from_local_datetime(&date.and_time(time)).earliest()
+            }
+            ScheduleTime::Sunrise { offset_minutes } => sun_event(date, location, true)
+                .map(|time| time + ChronoDuration::minutes(offset_minutes as i64)),
+            ScheduleTime::Sunset { offset_minutes } => sun_event(date, location, false)
+                .map(|time| time + ChronoDuration::minutes(offset_minutes as i64)),
+        }
+    }
+}
+
+impl fmt::Display for ScheduleTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ScheduleTime::TimeOfDay { hour, minute } => write!(f, "{:02}:{:02}", hour, minute),
+            ScheduleTime::Sunrise { offset_minutes } => f.write_str(&match offset_minutes {
+                0 => tr!("schedule-sunrise"),
+                m if m > 0 => tr!("schedule-after-sunrise", minutes = m),
+                m => tr!("schedule-before-sunrise", minutes = -m),
+            }),
+            ScheduleTime::Sunset { offset_minutes } => f.write_str(&match offset_minutes {
+                0 => tr!("schedule-sunset"),
+                m if m > 0 => tr!("schedule-after-sunset", minutes = m),
+                m => tr!("schedule-before-sunset", minutes = -m),
+            }),
+        }
+    }
+}
+
+/// Sunrise or sunset on `date` at `location`, from the NOAA solar
+/// equations, which are good to a minute or so.
+fn sun_event(date: NaiveDate, location: Location, sunrise: bool) -> Option<DateTime<Local>> {
+    use chrono::Datelike;
+    let gamma = 2.0 * PI / 365.0 * (date.ordinal0() as f64);
+    let equation_of_time = 229.18
+        * (0.000075 + 0.001868 * gamma.cos()
+            - 0.032077 * gamma.sin()
+            - 0.014615 * (2.0 * gamma).cos()
+            - 0.040849 * (2.0 * gamma).sin());
+    let declination = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+        - 0.006758 * (2.0 * gamma).cos()
+        + 0.000907 * (2.0 * gamma).sin()
+        - 0.002697 * (3.0 * gamma).cos()
+        + 0.00148 * (3.0 * gamma).sin();
+    let latitude = location.latitude.to_radians();
+    let cos_hour_angle = 90.833_f64.to_radians().cos() / (latitude.cos() * declination.cos())
+        - latitude.tan() * declination.tan();
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        // Midnight sun or polar night.
+        return None;
+    }
+    let hour_angle = cos_hour_angle.acos().to_degrees();
+    let hour_angle = if sunrise { hour_angle } else { -hour_angle };
+    let minutes_utc = 720.0 - 4.0 * (location.longitude + hour_angle) - equation_of_time;
+    let midnight = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?);
+    let time = midnight + ChronoDuration::seconds((minutes_utc * 60.0).round() as i64);
+    Some(time.with_timezone(&Local))
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledTrigger {
+    pub enabled: bool,
+    pub when: ScheduleTime,
+    pub action: BindingAction,
+}
+
+impl Default for ScheduledTrigger {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            when: ScheduleTime::kinds()[0],
+            action: BindingAction::Start,
+        }
+    }
+}
+
+impl ScheduledTrigger {
+    /// The next time the trigger goes off after `now`, looking a couple of
+    /// days ahead.
+    pub fn next_after(&self, now: DateTime<Local>, location: Location) -> Option<DateTime<Local>> {
+        (0..3)
+            .filter_map(|days| {
+                self.when
+                    .on(now.date_naive() + ChronoDuration::days(days), location)
+            })
+            .find(|&time| time > now)
+    }
+}
+
+impl HaloApp {
+    /// Runs the scheduled triggers whose time came since the last frame,
+    /// and keeps the UI ticking while any are on so they go off unattended.
+    pub(crate) fn update_schedule(&mut self, ctx: &egui::Context) {
+        let now = Local::now();
+        let Some(last) = self.schedule_checked.replace(now) else {
+            return;
+        };
+        if !self.show.schedule.iter().any(|trigger| trigger.enabled) {
+            return;
+        }
+        ctx.request_repaint_after(Duration::from_secs(1));
+
+        let location = self.show.location;
+        let due: Vec<(ScheduleTime, BindingAction)> = self
+            .show
+            .schedule
+            .iter()
+            .filter(|trigger| trigger.enabled)
+            .filter(|trigger| {
+                trigger
+                    .next_after(last, location)
+                    .is_some_and(|time| time <= now)
+            })
+            .map(|trigger| (trigger.when, trigger.action.clone()))
+            .collect();
+        for (when, action) in due {
+            self.log.info(tr!(
+                "schedule-ran",
+                when = when.to_string(),
+                action = action.to_string()
+            ));
+            self.run_binding_action(action);
+        }
+    }
+}
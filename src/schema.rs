@@ -0,0 +1,168 @@
+//! Show file format versions. Every show is saved with the version of the
+//! format it was written in, and loading runs the migrations between that
+//! and the current version over the raw JSON before it becomes a [`Show`],
+//! so a show from an older Halo loads without serde tripping over fields
+//! that have since changed shape.
+//!
+//! Each migration can also be undone, for saving a copy an older Halo can
+//! open; whatever the newer format added is lost on the way down.
+//!
+//! Changing the shape of a saved field means bumping [`CURRENT`] and adding
+//! a migration here. Adding a field with a default doesn't need one.
+
+use crate::show::Show;
+use serde_json::{Map, Value};
+use std::io::{self, Read};
+
+/// The format this Halo writes.
+pub const CURRENT: u32 = 2;
+
+/// Files written before the version was saved.
+const UNVERSIONED: u32 = 1;
+
+const VERSION_KEY: &str = "schema_version";
+
+type Object = Map<String, Value>;
+
+struct Migration {
+    /// The version this migration brings a show up to.
+    version: u32,
+    /// The locale id of what changed, for the format picker.
+    change: &'static str,
+    up: fn(&mut Object),
+    down: fn(&mut Object),
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 2,
+    change: "schema-pad-banks",
+    up: pad_banks_up,
+    down: pad_banks_down,
+}];
+
+/// The pad layout's banks, if the show has any.
+fn pad_banks(show: &mut Object) -> Option<&mut Vec<Value>> {
+    show.get_mut("pad_layout")?.get_mut("banks")?.as_array_mut()
+}
+
+/// Banks were just their names.
+fn pad_banks_up(show: &mut Object) {
+    for bank in pad_banks(show).into_iter().flatten() {
+        if let Value::String(name) = bank {
+            *bank = serde_json::json!({ "name": name });
+        }
+    }
+}
+
+fn pad_banks_down(show: &mut Object) {
+    for bank in pad_banks(show).into_iter().flatten() {
+        if let Some(name) = bank.get("name").cloned() {
+            *bank = name;
+        }
+    }
+}
+
+/// The versions a show can be saved as, newest first, with the locale id
+/// of what each changed.
+pub fn versions() -> impl Iterator<Item = (u32, &'static str)> {
+    MIGRATIONS
+        .iter()
+        .rev()
+        .map(|migration| (migration.version, migration.change))
+        .chain([(UNVERSIONED, "schema-unversioned")])
+}
+
+/// Reads a show saved by any version of Halo, bringing an older one up to
+/// date, along with the version it was saved in. A show from a newer Halo
+/// is read as far as this one understands it.
+pub fn read(reader: impl Read) -> io::Result<(Show, u32)> {
+    let mut value: Value = serde_json::from_reader(reader)?;
+    let show = value
+        .as_object_mut()
+        .ok_or_else(|| io::Error::other("not a Halo show file"))?;
+    let version = show
+        .get(VERSION_KEY)
+        .and_then(Value::as_u64)
+        .map_or(UNVERSIONED, |version| version as u32);
+    for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+        (migration.up)(show);
+    }
+    show.insert(VERSION_KEY.to_string(), CURRENT.into());
+    Ok((serde_json::from_value(value)?, version))
+}
+
+/// `show` as `version` of the format has it, for an older Halo to open.
+pub fn downgrade(show: &Show, version: u32) -> io::Result<Value> {
+    let mut value = serde_json::to_value(show)?;
+    let Some(object) = value.as_object_mut() else {
+        return Ok(value);
+    };
+    for migration in MIGRATIONS.iter().rev().filter(|m| m.version > version) {
+        (migration.down)(object);
+    }
+    if version == UNVERSIONED {
+        object.remove(VERSION_KEY);
+    } else {
+        object.insert(VERSION_KEY.to_string(), version.into());
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn banks(show: &Show) -> Vec<&str> {
+        show.pad_layout
+            .banks
+            .iter()
+            .map(|bank| bank.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn unversioned_pad_banks_are_brought_up() {
+        let file = json!({ "pad_layout": { "banks": ["Verse", "Chorus"] } });
+        let (show, version) = read(file.to_string().as_bytes()).unwrap();
+        assert_eq!(version, UNVERSIONED);
+        assert_eq!(banks(&show), ["Verse", "Chorus"]);
+        assert_eq!(show.schema_version, CURRENT);
+    }
+
+    #[test]
+    fn pad_banks_round_trip_through_the_unversioned_format() {
+        let file = json!({ "pad_layout": { "banks": ["Verse", "Chorus"] } });
+        let (show, _) = read(file.to_string().as_bytes()).unwrap();
+
+        let old = downgrade(&show, UNVERSIONED).unwrap();
+        assert_eq!(old["pad_layout"]["banks"], json!(["Verse", "Chorus"]));
+        assert!(old.get(VERSION_KEY).is_none());
+
+        let (again, version) = read(old.to_string().as_bytes()).unwrap();
+        assert_eq!(version, UNVERSIONED);
+        assert_eq!(banks(&again), ["Verse", "Chorus"]);
+    }
+
+    #[test]
+    fn current_shows_are_left_as_they_are() {
+        let (show, _) = read(json!({}).to_string().as_bytes()).unwrap();
+        let current = downgrade(&show, CURRENT).unwrap();
+        assert_eq!(current, serde_json::to_value(&show).unwrap());
+
+        let (again, version) = read(current.to_string().as_bytes()).unwrap();
+        assert_eq!(version, CURRENT);
+        assert_eq!(banks(&again), banks(&show));
+    }
+
+    #[test]
+    fn newer_shows_report_their_version() {
+        let file = json!({
+            VERSION_KEY: CURRENT + 1,
+            "pad_layout": { "banks": [{ "name": "Verse", "colour": "red" }] },
+        });
+        let (show, version) = read(file.to_string().as_bytes()).unwrap();
+        assert_eq!(version, CURRENT + 1);
+        assert_eq!(banks(&show), ["Verse"]);
+    }
+}
@@ -0,0 +1,289 @@
+//! Rhai scripting for cues and pads. Scripts run on the UI thread against a
+//! small sandboxed API: they can read the clock, tempo and pad state, and
+//! queue actions that the app applies once the script has finished.
+//!
+//! | Function | Effect |
+//! | --- | --- |
+//! | `hour()`, `minute()` | Wall clock time of day |
+//! | `timecode()` | Show timecode in seconds |
+//! | `bpm()`, `set_bpm(x)` | Read or set the tempo |
+//! | `bar()`, `beat()` | Bar and beat in the bar shown by the beat indicator, from 1 |
+//! | `pad_active(name)` | Whether the named pad is on |
+//! | `set_pad(name, on)`, `toggle_pad(name)` | Change a pad |
+//! | `skip()` | Skip the cue that ran the script |
+//! | `send_osc(target, address)`, `send_osc(target, address, [args])` | Send OSC over UDP |
+//! | `log(message)`, `print(message)` | Write to the log console |
+
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::osc::OscArg;
+use crate::ui::script_editor::ScriptTarget;
+use crate::HaloApp;
+use chrono::Timelike;
+use rhai::{Array, Dynamic, Engine};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Caps how much work a single script may do so a runaway loop can't stall
+/// the UI.
+const MAX_OPERATIONS: u64 = 100_000;
+
+pub enum ScriptAction {
+    SetBpm(f32),
+    /// `None` toggles the pad.
+    SetPad(String, Option<bool>),
+    SendOsc {
+        target: String,
+        address: String,
+        args: Vec<OscArg>,
+    },
+    Skip,
+    Log(String),
+}
+
+#[derive(Default)]
+struct ScriptState {
+    bpm: f32,
+    bar: u32,
+    beat: u32,
+    timecode: f64,
+    active_pads: Vec<String>,
+    actions: Vec<ScriptAction>,
+}
+
+/// What the app exposes to a script for the duration of one run.
+pub struct ScriptInputs {
+    pub bpm: f32,
+    /// Bar and beat from the beat indicator.
+    pub bar: u32,
+    pub beat: u32,
+    pub timecode: f64,
+    pub active_pads: Vec<String>,
+}
+
+pub struct Scripting {
+    engine: Engine,
+    state: Rc<RefCell<ScriptState>>,
+}
+
+fn osc_arg(value: Dynamic) -> OscArg {
+    if let Ok(int) = value.as_int() {
+        OscArg::Int(int as i32)
+    } else if let Ok(float) = value.as_float() {
+        OscArg::Float(float as f32)
+    } else if let Ok(boolean) = value.as_bool() {
+        OscArg::Int(boolean as i32)
+    } else {
+        OscArg::Str(value.to_string())
+    }
+}
+
+impl Scripting {
+    pub fn new() -> Self {
+        let state = Rc::new(RefCell::new(ScriptState::default()));
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_call_levels(32);
+        engine.set_max_string_size(64 * 1024);
+        engine.set_max_array_size(10_000);
+
+        engine.register_fn("hour", || chrono::Local::now().hour() as i64);
+        engine.register_fn("minute", || chrono::Local::now().minute() as i64);
+
+        let s = state.clone();
+        engine.register_fn("timecode", move || s.borrow().timecode);
+        let s = state.clone();
+        engine.register_fn("bpm", move || s.borrow().bpm as f64);
+        let s = state.clone();
+        engine.register_fn("bar", move || s.borrow().bar as i64);
+        let s = state.clone();
+        engine.register_fn("beat", move || s.borrow().beat as i64);
+        let s = state.clone();
+        engine.register_fn("set_bpm", move |bpm: f64| {
+            s.borrow_mut()
+                .actions
+                .push(ScriptAction::SetBpm(bpm as f32));
+        });
+        let s = state.clone();
+        engine.register_fn("set_bpm", move |bpm: i64| {
+            s.borrow_mut()
+                .actions
+                .push(ScriptAction::SetBpm(bpm as f32));
+        });
+
+        let s = state.clone();
+        engine.register_fn("pad_active", move |name: &str| {
+            s.borrow().active_pads.iter().any(|pad| pad == name)
+        });
+        let s = state.clone();
+        engine.register_fn("set_pad", move |name: &str, on: bool| {
+            s.borrow_mut()
+                .actions
+                .push(ScriptAction::SetPad(name.to_string(), Some(on)));
+        });
+        let s = state.clone();
+        engine.register_fn("toggle_pad", move |name: &str| {
+            s.borrow_mut()
+                .actions
+                .push(ScriptAction::SetPad(name.to_string(), None));
+        });
+
+        let s = state.clone();
+        engine.register_fn("skip", move || {
+            s.borrow_mut().actions.push(ScriptAction::Skip);
+        });
+
+        let s = state.clone();
+        engine.register_fn("send_osc", move |target: &str, address: &str| {
+            s.borrow_mut().actions.push(ScriptAction::SendOsc {
+                target: target.to_string(),
+                address: address.to_string(),
+                args: Vec::new(),
+            });
+        });
+        let s = state.clone();
+        engine.register_fn(
+            "send_osc",
+            move |target: &str, address: &str, args: Array| {
+                s.borrow_mut().actions.push(ScriptAction::SendOsc {
+                    target: target.to_string(),
+                    address: address.to_string(),
+                    args: args.into_iter().map(osc_arg).collect(),
+                });
+            },
+        );
+
+        let s = state.clone();
+        engine.register_fn("log", move |message: &str| {
+            s.borrow_mut()
+                .actions
+                .push(ScriptAction::Log(message.to_string()));
+        });
+        let s = state.clone();
+        engine.on_print(move |message| {
+            s.borrow_mut()
+                .actions
+                .push(ScriptAction::Log(message.to_string()));
+        });
+
+        Self { engine, state }
+    }
+
+    /// Runs `source` and returns the actions it queued, or the error message
+    /// if it failed to parse or run. Actions queued before an error are
+    /// discarded.
+    pub fn run(&self, source: &str, inputs: ScriptInputs) -> Result<Vec<ScriptAction>, String> {
+        *self.state.borrow_mut() = ScriptState {
+            bpm: inputs.bpm,
+            bar: inputs.bar,
+            beat: inputs.beat,
+            timecode: inputs.timecode,
+            active_pads: inputs.active_pads,
+            actions: Vec::new(),
+        };
+        let result = self.engine.run(source).map_err(|err| err.to_string());
+        let actions = std::mem::take(&mut self.state.borrow_mut().actions);
+        result.map(|_| actions)
+    }
+}
+
+impl HaloApp {
+    fn script_source(&self, target: ScriptTarget) -> Option<(String, String)> {
+        match target {
+            ScriptTarget::Cue(index) => self.show.cues.get(index).map(|cue| {
+                (
+                    tr!("cue-number", number = cue.number.to_string()),
+                    cue.script.clone(),
+                )
+            }),
+            ScriptTarget::Pad(index) => self.show.pads.get(index).map(|pad| {
+                (
+                    tr!("script-pad", label = pad.label.as_str()),
+                    pad.script.clone(),
+                )
+            }),
+        }
+    }
+
+    /// Runs the script attached to `target`, applies what it asked for and
+    /// reports failures to the log console. Returns the error, if any.
+    pub(crate) fn run_script(&mut self, target: ScriptTarget) -> Option<String> {
+        let (name, source) = self.script_source(target)?;
+        if source.trim().is_empty() {
+            return None;
+        }
+
+        let (bar, beat) = self.show.time_signature.bar_beat(self.beat_indicator.beat);
+        let inputs = ScriptInputs {
+            bpm: self.bpm,
+            bar,
+            beat,
+            timecode: self.elapsed.as_secs_f64(),
+            active_pads: self
+                .show
+                .pads
+                .iter()
+                .filter(|pad| pad.active)
+                .map(|pad| pad.label.clone())
+                .collect(),
+        };
+        let actions = match self.scripting.run(&source, inputs) {
+            Ok(actions) => actions,
+            Err(err) => {
+                self.notify(
+                    LogLevel::Error,
+                    tr!("script-failed", name = name.as_str(), error = err.as_str()),
+                );
+                return Some(err);
+            }
+        };
+
+        for action in actions {
+            match action {
+                ScriptAction::SetBpm(bpm) => self.bpm = bpm.clamp(20.0, 300.0),
+                ScriptAction::SetPad(label, on) => {
+                    match self.show.pads.iter().position(|pad| pad.label == label) {
+                        Some(index) => {
+                            let active = on.unwrap_or(!self.show.pads[index].active);
+                            self.latch_pad(index, active);
+                        }
+                        None => {
+                            self.log
+                                .warn(tr!("script-no-pad", name = name.as_str(), pad = label))
+                        }
+                    }
+                }
+                ScriptAction::SendOsc {
+                    target,
+                    address,
+                    args,
+                } => {
+                    if let Err(err) = self.send_osc(&target, &address, &args) {
+                        self.notify(
+                            LogLevel::Error,
+                            tr!(
+                                "script-osc-failed",
+                                name = name.as_str(),
+                                target = target,
+                                error = err.to_string()
+                            ),
+                        );
+                    }
+                }
+                ScriptAction::Skip => match target {
+                    ScriptTarget::Cue(index) => {
+                        if let Some(cue) = self.show.cues.get_mut(index) {
+                            cue.skipped = true;
+                            self.log.info(tr!("script-skipped", name = name.as_str()));
+                        }
+                    }
+                    ScriptTarget::Pad(_) => {
+                        self.log.warn(tr!("script-skip-pad", name = name.as_str()))
+                    }
+                },
+                ScriptAction::Log(message) => self.log.info(format!("{}: {}", name, message)),
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,165 @@
+//! Statistics for the current session, for stage management: how long the
+//! show has been running, how long since it was saved, and when each cue
+//! actually fired against when it was programmed to. Kept in memory only;
+//! the post-show report is the record.
+
+use crate::cue::{Cue, CueNumber};
+use crate::i18n::tr;
+use crate::HaloApp;
+use chrono::{DateTime, Local};
+use std::time::{Duration, Instant};
+
+/// One cue going, as the session saw it.
+#[derive(Clone)]
+pub struct CueFire {
+    pub number: CueNumber,
+    pub name: String,
+    /// Where the cue sits on the show timeline.
+    pub programmed: Duration,
+    /// How long after the run began it went, in wall-clock time, so holds
+    /// and stops count against it.
+    pub actual: Duration,
+    /// Time of day it went.
+    pub at: DateTime<Local>,
+    /// Seconds after its time on the show clock it fired, or on incoming
+    /// timecode while chasing, negative if early. Only for cues the clock
+    /// fired rather than the operator.
+    pub late: Option<f64>,
+}
+
+impl CueFire {
+    /// How late the cue went against its programmed time, negative if early.
+    pub fn drift(&self) -> f64 {
+        self.actual.as_secs_f64() - self.programmed.as_secs_f64()
+    }
+
+    /// Whether the cue fired further from its time than `threshold`.
+    pub fn is_late(&self, threshold: Duration) -> bool {
+        self.late
+            .is_some_and(|late| late.abs() > threshold.as_secs_f64())
+    }
+}
+
+pub struct Session {
+    pub opened: Instant,
+    /// When the show was last saved or loaded, if it has been this session.
+    pub saved: Option<Instant>,
+    /// When the current run began: the first start from the top of the show.
+    pub run_started: Option<(Instant, DateTime<Local>)>,
+    /// Time spent playing before the current stretch.
+    played: Duration,
+    /// Since when playback has been running, while it is.
+    playing_since: Option<Instant>,
+    pub fires: Vec<CueFire>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            opened: Instant::now(),
+            saved: None,
+            run_started: None,
+            played: Duration::ZERO,
+            playing_since: None,
+            fires: Vec::new(),
+        }
+    }
+}
+
+impl Session {
+    /// Follows the transport, called every frame. A run begins when playback
+    /// starts with the playhead at the top of the show.
+    pub fn update(&mut self, running: bool, elapsed: Duration) {
+        match (running, self.playing_since) {
+            (true, None) => {
+                let now = Instant::now();
+                self.playing_since = Some(now);
+                if self.run_started.is_none() || elapsed.is_zero() {
+                    self.run_started = Some((now, Local::now()));
+                }
+            }
+            (false, Some(since)) => {
+                self.played += since.elapsed();
+                self.playing_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Total time spent playing this session.
+    pub fn play_time(&self) -> Duration {
+        self.played
+            + self
+                .playing_since
+                .map_or(Duration::ZERO, |since| since.elapsed())
+    }
+
+    /// Wall-clock time since the current run began.
+    pub fn run_time(&self) -> Option<Duration> {
+        self.run_started.map(|(started, _)| started.elapsed())
+    }
+
+    pub fn record(&mut self, cue: &Cue, programmed: Duration, late: Option<f64>) {
+        self.fires.push(CueFire {
+            number: cue.number,
+            name: cue.name.clone(),
+            programmed,
+            actual: self.run_time().unwrap_or(programmed),
+            at: Local::now(),
+            late,
+        });
+    }
+
+    /// The last time the cue numbered `number` fired this session.
+    pub fn last_fire(&self, number: CueNumber) -> Option<&CueFire> {
+        self.fires.iter().rev().find(|fire| fire.number == number)
+    }
+
+    /// How many cues fired further from their time than `threshold`.
+    pub fn late_count(&self, threshold: Duration) -> usize {
+        self.fires
+            .iter()
+            .filter(|fire| fire.is_late(threshold))
+            .count()
+    }
+
+    /// Starts the statistics afresh, keeping when the show was saved.
+    pub fn clear(&mut self) {
+        *self = Self {
+            saved: self.saved,
+            ..Self::default()
+        };
+    }
+}
+
+/// `0.32s late` or `0.10s early`.
+pub fn format_late(late: f64) -> String {
+    if late < 0.0 {
+        tr!("session-early", seconds = format!("{:.2}", -late))
+    } else {
+        tr!("session-late", seconds = format!("{:.2}", late))
+    }
+}
+
+impl HaloApp {
+    pub(crate) fn update_session(&mut self) {
+        self.session
+            .update(self.transport.is_running(), self.elapsed);
+    }
+
+    /// Adds the cue at `index` to the session's fire history as it goes.
+    pub(crate) fn record_cue_fire(&mut self, index: usize, late: Option<f64>) {
+        if let Some(cue) = self.show.cues.get(index) {
+            let programmed = self.show.cue_start(cue);
+            self.session.record(cue, programmed, late);
+            let threshold = self.show.timecode.late_cue;
+            if let Some(late) = late.filter(|late| late.abs() > threshold.as_secs_f64()) {
+                self.log.warn(tr!(
+                    "session-fired-off-time",
+                    number = cue.number.to_string(),
+                    late = format_late(late)
+                ));
+            }
+        }
+    }
+}
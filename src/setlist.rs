@@ -0,0 +1,88 @@
+//! Setlist playback: the show is played one song at a time. The timecode
+//! reads from the start of the current song, the clock holds at the end of
+//! each song, and the operator (or a remote trigger) moves on to the next.
+
+use crate::i18n::tr;
+use crate::song::Song;
+use crate::HaloApp;
+use std::time::Duration;
+
+impl HaloApp {
+    /// The song being played in setlist mode.
+    pub(crate) fn setlist_song(&self) -> Option<&Song> {
+        if !self.setlist_mode {
+            return None;
+        }
+        self.show.songs.get(self.current_song)
+    }
+
+    /// Where the engine should stop the clock: the end of the current song.
+    pub(crate) fn setlist_hold(&self) -> Option<Duration> {
+        self.setlist_song().map(|song| song.end())
+    }
+
+    /// The playhead position shown as timecode, which in setlist mode
+    /// restarts at zero for every song.
+    pub(crate) fn display_elapsed(&self) -> Duration {
+        let start = self
+            .setlist_song()
+            .map_or(Duration::ZERO, |song| song.start_time);
+        self.elapsed.saturating_sub(start)
+    }
+
+    pub(crate) fn set_setlist_mode(&mut self, enabled: bool) {
+        self.setlist_mode = enabled;
+        if !enabled || self.show.songs.is_empty() {
+            return;
+        }
+        // Pick up the song under the playhead, or the first one.
+        let index = self
+            .show
+            .songs
+            .iter()
+            .position(|song| song.start_time <= self.elapsed && self.elapsed < song.end())
+            .unwrap_or(0);
+        if index != self.current_song || self.show.songs[index].end() <= self.elapsed {
+            self.go_to_song(index);
+        }
+    }
+
+    /// Cues up the song at `index` at its start, keeping the transport
+    /// running or stopped as it was.
+    pub(crate) fn go_to_song(&mut self, index: usize) {
+        let Some(song) = self.show.songs.get(index) else {
+            self.log.warn(tr!("setlist-no-song", number = index + 1));
+            return;
+        };
+        self.current_song = index;
+        self.locate(song.start_time);
+        for i in 0..self.show.cues.len() {
+            if self.show.cue_start(&self.show.cues[i]) >= self.elapsed {
+                self.show.cues[i].skipped = false;
+            }
+        }
+    }
+
+    pub(crate) fn next_song(&mut self) {
+        if self.current_song + 1 < self.show.songs.len() {
+            self.go_to_song(self.current_song + 1);
+        }
+    }
+
+    pub(crate) fn previous_song(&mut self) {
+        if self.current_song > 0 {
+            self.go_to_song(self.current_song - 1);
+        }
+    }
+
+    /// "Song 3/12: Chorus Anthem" for the header.
+    pub(crate) fn setlist_label(&self) -> Option<String> {
+        let song = self.setlist_song()?;
+        Some(tr!(
+            "setlist-label",
+            number = self.current_song + 1,
+            count = self.show.songs.len(),
+            name = song.name.as_str()
+        ))
+    }
+}
@@ -0,0 +1,171 @@
+use crate::i18n;
+use eframe::egui::{Key, KeyboardShortcut, ModifierNames, Modifiers};
+
+/// When a shortcut does anything.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum When {
+    Always,
+    /// Outside performance mode.
+    Editing,
+    Setlist,
+    LearningTiming,
+}
+
+pub struct Shortcut {
+    pub key: Key,
+    pub modifiers: Modifiers,
+    pub when: When,
+    /// The id of what it does in the locale files.
+    pub description: &'static str,
+}
+
+impl Shortcut {
+    /// The keys as the platform writes them, such as `Ctrl+K` or `⌘K`.
+    pub fn name(&self) -> String {
+        KeyboardShortcut::new(self.modifiers, self.key)
+            .format(&ModifierNames::NAMES, cfg!(target_os = "macos"))
+    }
+
+    /// What it does, in the chosen language.
+    pub fn description(&self) -> String {
+        i18n::translate(self.description, None)
+    }
+}
+
+/// Keyboard shortcuts handled by the main window, then the keys for working
+/// without a mouse. Keep this in sync with `HaloApp::handle_shortcuts`; it
+/// also feeds the operator handoff export.
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut {
+        key: Key::K,
+        modifiers: Modifiers::COMMAND,
+        when: When::Always,
+        description: "shortcut-palette",
+    },
+    Shortcut {
+        key: Key::Questionmark,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-help",
+    },
+    Shortcut {
+        key: Key::Space,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-start-stop",
+    },
+    Shortcut {
+        key: Key::K,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-pause",
+    },
+    Shortcut {
+        key: Key::Backspace,
+        modifiers: Modifiers::NONE,
+        when: When::Editing,
+        description: "shortcut-reset",
+    },
+    Shortcut {
+        key: Key::T,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-readout",
+    },
+    Shortcut {
+        key: Key::P,
+        modifiers: Modifiers::NONE,
+        when: When::Editing,
+        description: "shortcut-patch",
+    },
+    Shortcut {
+        key: Key::N,
+        modifiers: Modifiers::NONE,
+        when: When::Setlist,
+        description: "shortcut-next-song",
+    },
+    Shortcut {
+        key: Key::F12,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-panic",
+    },
+    Shortcut {
+        key: Key::B,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-back",
+    },
+    Shortcut {
+        key: Key::L,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-performance",
+    },
+    Shortcut {
+        key: Key::G,
+        modifiers: Modifiers::NONE,
+        when: When::LearningTiming,
+        description: "shortcut-learn-go",
+    },
+    Shortcut {
+        key: Key::PageUp,
+        modifiers: Modifiers::NONE,
+        when: When::Editing,
+        description: "shortcut-quick-up",
+    },
+    Shortcut {
+        key: Key::PageDown,
+        modifiers: Modifiers::NONE,
+        when: When::Editing,
+        description: "shortcut-quick-down",
+    },
+    Shortcut {
+        key: Key::OpenBracket,
+        modifiers: Modifiers::NONE,
+        when: When::Editing,
+        description: "shortcut-nudge-second-earlier",
+    },
+    Shortcut {
+        key: Key::CloseBracket,
+        modifiers: Modifiers::NONE,
+        when: When::Editing,
+        description: "shortcut-nudge-second-later",
+    },
+    Shortcut {
+        key: Key::Comma,
+        modifiers: Modifiers::NONE,
+        when: When::Editing,
+        description: "shortcut-nudge-frame-earlier",
+    },
+    Shortcut {
+        key: Key::Period,
+        modifiers: Modifiers::NONE,
+        when: When::Editing,
+        description: "shortcut-nudge-frame-later",
+    },
+    Shortcut {
+        key: Key::Tab,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-focus",
+    },
+    Shortcut {
+        key: Key::Escape,
+        modifiers: Modifiers::NONE,
+        when: When::Always,
+        description: "shortcut-unfocus",
+    },
+    Shortcut {
+        key: Key::F10,
+        modifiers: Modifiers::SHIFT,
+        when: When::Editing,
+        description: "shortcut-pad-menu",
+    },
+    Shortcut {
+        key: Key::Enter,
+        modifiers: Modifiers::COMMAND,
+        when: When::Always,
+        description: "shortcut-fire",
+    },
+];
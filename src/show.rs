@@ -0,0 +1,647 @@
+use crate::binding::{Binding, BindingAction};
+use crate::busk::BuskSettings;
+use crate::clocks::Clocks;
+use crate::crossfade::CrossfadeSettings;
+use crate::cue::CueValue;
+use crate::cue::{self, Cue};
+use crate::dmx::UNIVERSE_SIZE;
+use crate::dmx_fader::DmxFader;
+use crate::end_action::EndAction;
+use crate::fixture::{self, Attribute, Fixture, FixtureProfile};
+use crate::flash::Flash;
+use crate::follow_spot::FollowSpot;
+use crate::group_master::GroupMaster;
+use crate::haze::HazeSettings;
+use crate::i18n::tr;
+use crate::macros::{Macro, MacroAction};
+use crate::marker::Marker;
+use crate::package;
+use crate::panic::SafeState;
+use crate::pixel::PixelMap;
+use crate::schedule::{Location, ScheduledTrigger};
+use crate::schema;
+use crate::show_lock::ShowLock;
+use crate::smart_bulb::SmartBulbSettings;
+use crate::snap::BeatGrid;
+use crate::snapshot::Snapshot;
+use crate::song::Song;
+use crate::speed_master::SpeedMaster;
+use crate::tag::Tag;
+use crate::time_signature::TimeSignature;
+use crate::timecode::TimecodeSettings;
+use crate::variable::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// What letting go of a pad does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PadRelease {
+    /// On with one press, off with the next.
+    #[default]
+    Toggle,
+    /// On only while held down.
+    Momentary,
+    /// Off by itself this many seconds after going on.
+    Seconds(f32),
+    /// Off by itself this many bars after going on, at the show's tempo.
+    Bars(u32),
+}
+
+impl PadRelease {
+    pub fn is_toggle(&self) -> bool {
+        *self == PadRelease::Toggle
+    }
+
+    pub fn kind(self) -> String {
+        match self {
+            PadRelease::Toggle => tr!("pad-release-toggle"),
+            PadRelease::Momentary => tr!("pad-release-momentary"),
+            PadRelease::Seconds(_) => tr!("pad-release-seconds"),
+            PadRelease::Bars(_) => tr!("pad-release-bars"),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Pad {
+    pub label: String,
+    /// Rhai script run when the pad is switched on.
+    #[serde(default)]
+    pub script: String,
+    /// Macro replayed when the pad is switched on.
+    #[serde(default)]
+    pub run_macro: Option<String>,
+    /// Colour and icon shown on the pad.
+    #[serde(default, skip_serializing_if = "Tag::is_empty")]
+    pub tag: Tag,
+    /// The bank the pad sits in, from 0.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub bank: usize,
+    /// How many grid columns the pad spans.
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub width: usize,
+    #[serde(default, skip_serializing_if = "PadRelease::is_toggle")]
+    pub release: PadRelease,
+    /// How long the pad's level takes to fall to nothing once it goes off.
+    #[serde(default, skip_serializing_if = "Duration::is_zero")]
+    pub fade_out: Duration,
+    /// Fixtures brought up while the pad is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flash: Option<Flash>,
+    #[serde(skip)]
+    pub active: bool,
+    /// When a timed pad goes off by itself.
+    #[serde(skip)]
+    pub release_at: Option<Instant>,
+    /// When the pad last went off, for its fade-out.
+    #[serde(skip)]
+    pub released_at: Option<Instant>,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+fn one() -> usize {
+    1
+}
+
+fn is_one(n: &usize) -> bool {
+    *n == 1
+}
+
+impl Pad {
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            script: String::new(),
+            run_macro: None,
+            tag: Tag::default(),
+            bank: 0,
+            width: 1,
+            release: PadRelease::Toggle,
+            fade_out: Duration::ZERO,
+            flash: None,
+            active: false,
+            release_at: None,
+            released_at: None,
+        }
+    }
+
+    /// How far on the pad is at `now`: 1 while on, falling to 0 over its
+    /// fade-out once off.
+    pub fn level(&self, now: Instant) -> f32 {
+        if self.active {
+            return 1.0;
+        }
+        match self.released_at {
+            Some(at) if !self.fade_out.is_zero() => {
+                1.0 - (now.duration_since(at).as_secs_f32() / self.fade_out.as_secs_f32()).min(1.0)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// A page of the pad grid, which can come up by itself when the setlist
+/// reaches one of its songs or playback one of its cue list sections.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PadBank {
+    pub name: String,
+    /// Ids of the songs that bring the bank up in setlist mode.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub songs: Vec<u32>,
+    /// Section labels that bring the bank up, ahead of a song's bank.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<String>,
+}
+
+impl PadBank {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            songs: Vec::new(),
+            sections: Vec::new(),
+        }
+    }
+}
+
+/// The shape of the override pad grid and its banks. Pads flow into each
+/// bank's grid in order, a row at a time.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PadLayout {
+    pub columns: usize,
+    /// Rows shown per bank; pads past them still show, below.
+    pub rows: usize,
+    pub banks: Vec<PadBank>,
+}
+
+impl Default for PadLayout {
+    fn default() -> Self {
+        Self {
+            columns: 4,
+            rows: 3,
+            banks: vec![PadBank::new(&tr!("pad-default-bank", number = 1))],
+        }
+    }
+}
+
+impl PadLayout {
+    pub const MAX_COLUMNS: usize = 16;
+    pub const MAX_ROWS: usize = 16;
+
+    /// The pads of `bank` as rows of the grid, by their index in `pads`.
+    /// A pad too wide for what is left of a row starts the next one.
+    pub fn rows(&self, pads: &[Pad], bank: usize) -> Vec<Vec<usize>> {
+        let columns = self.columns.max(1);
+        let mut rows: Vec<Vec<usize>> = Vec::new();
+        let mut used = columns;
+        for (i, pad) in pads.iter().enumerate().filter(|(_, pad)| pad.bank == bank) {
+            let width = pad.width.clamp(1, columns);
+            if used + width > columns {
+                rows.push(Vec::new());
+                used = 0;
+            }
+            rows.last_mut().unwrap().push(i);
+            used += width;
+        }
+        rows
+    }
+}
+
+/// Everything that makes up a show: the cue list, the patch and the pad
+/// layout, plus the paperwork a substitute operator needs.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Show {
+    /// The version of the file format, so a later Halo knows what to
+    /// migrate from. Always [`schema::CURRENT`] once loaded.
+    pub schema_version: u32,
+    pub name: String,
+    pub cues: Vec<Cue>,
+    /// Notes on the timeline between the cues, kept in time order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub markers: Vec<Marker>,
+    /// The set, in running order. Cues in a song are timed from its start.
+    pub songs: Vec<Song>,
+    /// What happens when playback reaches the end of the cue list, outside
+    /// setlist mode.
+    #[serde(skip_serializing_if = "EndAction::is_continue")]
+    pub end_action: EndAction,
+    pub fixtures: Vec<Fixture>,
+    pub pads: Vec<Pad>,
+    /// Grid size and banks the pads are laid out in.
+    pub pad_layout: PadLayout,
+    pub macros: Vec<Macro>,
+    /// Incoming MIDI, OSC and HTTP triggers mapped to actions.
+    pub bindings: Vec<Binding>,
+    /// MIDI controllers for the A/B crossfade faders.
+    pub crossfade: CrossfadeSettings,
+    pub pixel_maps: Vec<PixelMap>,
+    pub busk: BuskSettings,
+    /// Faders scaling the speed of the pixel maps and busk assigned to them.
+    pub speed_masters: Vec<SpeedMaster>,
+    /// Faders capping the intensity of fixture groups.
+    pub group_masters: Vec<GroupMaster>,
+    /// Moving heads that follow PSN trackers.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub follow_spots: Vec<FollowSpot>,
+    /// The DMX wing channel that moves the grand master.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grand_master_fader: Option<DmxFader>,
+    /// The hazers' duty cycle and Smoke pad override.
+    pub haze: HazeSettings,
+    /// Fixtures mapped to Hue lights and WLED controllers.
+    pub smart_bulbs: SmartBulbSettings,
+    /// Actions run at a time of day, sunrise or sunset.
+    pub schedule: Vec<ScheduledTrigger>,
+    /// The venue's position, for the sunrise and sunset triggers.
+    pub location: Location,
+    /// How far ahead of their next cue dark fixtures move in black.
+    pub move_in_black_lead: Duration,
+    /// What each power circuit's breaker takes, in watts, for warning when
+    /// the estimated draw goes over.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_limit: Option<f32>,
+    /// Time zone clocks and countdowns for the big readout.
+    #[serde(skip_serializing_if = "Clocks::is_empty")]
+    pub clocks: Clocks,
+    /// How long Back takes to fade to the previous cue.
+    pub back_fade: Duration,
+    /// What the Panic button fades to.
+    pub safe_state: SafeState,
+    /// Beats to the bar for the beat indicator and bar-synced effects.
+    pub time_signature: TimeSignature,
+    /// Tempo and downbeat of the beats cues snap to on the timeline.
+    pub beat_grid: BeatGrid,
+    /// The show's timecode start value and offset for incoming timecode.
+    pub timecode: TimecodeSettings,
+    /// Free-form contact details (stage manager, production, venue tech).
+    pub contacts: String,
+    /// General standby notes for whoever is running the show.
+    pub notes: String,
+    /// Audio track the timeline is programmed against.
+    pub audio_file: Option<PathBuf>,
+    /// Gain trim for the audio file, in dB.
+    pub audio_trim: f32,
+    /// Named values cue conditions test, such as `encore` or `venue`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, Value>,
+    /// Keeps the show in performance mode without the passcode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock: Option<ShowLock>,
+    /// Captured output kept for comparing later captures against.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<Snapshot>,
+}
+
+impl Default for Show {
+    fn default() -> Self {
+        Self {
+            schema_version: schema::CURRENT,
+            name: "Untitled Show".to_string(),
+            cues: cue::default_cues(),
+            markers: Vec::new(),
+            songs: Vec::new(),
+            end_action: EndAction::Continue,
+            fixtures: fixture::default_rig(),
+            pads: [
+                "Smoke", "Strobe", "Laser", "Flash", "Burst", "Pulse", "Wave", "Spark", "Fade",
+                "Chase", "Sweep", "Blast",
+            ]
+            .into_iter()
+            .map(Pad::new)
+            .collect(),
+            pad_layout: PadLayout::default(),
+            macros: Vec::new(),
+            bindings: Vec::new(),
+            crossfade: CrossfadeSettings::default(),
+            pixel_maps: Vec::new(),
+            busk: BuskSettings::default(),
+            speed_masters: Vec::new(),
+            group_masters: Vec::new(),
+            follow_spots: Vec::new(),
+            grand_master_fader: None,
+            haze: HazeSettings::default(),
+            smart_bulbs: SmartBulbSettings::default(),
+            schedule: Vec::new(),
+            location: Location::default(),
+            move_in_black_lead: Duration::from_secs(3),
+            circuit_limit: None,
+            clocks: Clocks::default(),
+            back_fade: Duration::from_secs(2),
+            safe_state: SafeState::default(),
+            time_signature: TimeSignature::default(),
+            beat_grid: BeatGrid::default(),
+            timecode: TimecodeSettings::default(),
+            contacts: String::new(),
+            notes: String::new(),
+            audio_file: None,
+            audio_trim: 0.0,
+            variables: BTreeMap::new(),
+            lock: None,
+            snapshots: Vec::new(),
+        }
+    }
+}
+
+impl Show {
+    /// Loads a plain JSON show file or a `.haloshow` package, whichever `path`
+    /// turns out to be.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::load_versioned(path).map(|(show, _)| show)
+    }
+
+    /// Loads a show along with the version of the file format it was saved
+    /// in, which is past [`schema::CURRENT`] for a show from a newer Halo.
+    pub fn load_versioned(path: &Path) -> io::Result<(Self, u32)> {
+        if package::is_package(path)? {
+            return package::load(path);
+        }
+        schema::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+
+    /// Saves the show as an older `version` of the file format, for an
+    /// older Halo to open.
+    pub fn save_as_version(&self, path: &Path, version: u32) -> io::Result<()> {
+        let show = schema::downgrade(self, version)?;
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &show)?;
+        Ok(())
+    }
+
+    /// The show as it would be saved, to compare against later and tell
+    /// whether anything has changed since.
+    pub fn snapshot(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn song(&self, id: u32) -> Option<&Song> {
+        self.songs.iter().find(|song| song.id == id)
+    }
+
+    /// When `cue` fires on the show timeline, taking its song into account.
+    pub fn cue_start(&self, cue: &Cue) -> Duration {
+        let offset = cue
+            .song
+            .and_then(|id| self.song(id))
+            .map_or(Duration::ZERO, |song| song.start_time);
+        offset + cue.start_time
+    }
+
+    /// Whether playback takes `cue`: it is armed and its condition, if it
+    /// has one, holds.
+    pub fn plays(&self, cue: &Cue) -> bool {
+        !cue.disarmed
+            && cue
+                .condition
+                .as_ref()
+                .is_none_or(|condition| condition.holds(&self.variables))
+    }
+
+    /// The cue list with every start time on the show timeline, for output.
+    /// Cues whose condition fails go out disarmed.
+    pub fn timeline_cues(&self) -> Vec<Cue> {
+        self.cues
+            .iter()
+            .map(|cue| {
+                let mut cue = cue.clone();
+                cue.start_time = self.cue_start(&cue);
+                cue.disarmed = !self.plays(&cue);
+                cue
+            })
+            .collect()
+    }
+
+    /// Cue positions in the order the output merges the cues: by when they
+    /// fire on the show timeline, with cues firing together in list order.
+    pub fn playback_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.cues.len()).collect();
+        order.sort_by_key(|&index| self.cue_start(&self.cues[index]));
+        order
+    }
+
+    /// Whether `sent` is what `timeline_cues` gives now, without building
+    /// it.
+    pub fn is_timeline(&self, sent: &[Cue]) -> bool {
+        sent.len() == self.cues.len()
+            && sent.iter().zip(&self.cues).all(|(sent, cue)| {
+                sent.start_time == self.cue_start(cue)
+                    && sent.disarmed != self.plays(cue)
+                    && sent.eq_off_timeline(cue)
+            })
+    }
+
+    /// Moves the cue at `index` into the song with id `song`, or out of any
+    /// song, keeping it at the same point on the show timeline.
+    pub fn set_cue_song(&mut self, index: usize, song: Option<u32>) {
+        let Some(cue) = self.cues.get(index) else {
+            return;
+        };
+        let start = self.cue_start(cue);
+        let offset = song
+            .and_then(|id| self.song(id))
+            .map_or(Duration::ZERO, |song| song.start_time);
+        let cue = &mut self.cues[index];
+        cue.song = song;
+        cue.start_time = start.saturating_sub(offset);
+    }
+
+    /// Removes the song at `index`, leaving its cues where they are on the
+    /// show timeline.
+    pub fn remove_song(&mut self, index: usize) {
+        let Some(id) = self.songs.get(index).map(|song| song.id) else {
+            return;
+        };
+        for i in 0..self.cues.len() {
+            if self.cues[i].song == Some(id) {
+                self.set_cue_song(i, None);
+            }
+        }
+        self.songs.remove(index);
+    }
+
+    /// Renames the pad at `index`, along with the triggers, macro steps and
+    /// haze override that refer to it by label.
+    pub fn rename_pad(&mut self, index: usize, label: &str) {
+        let Some(pad) = self.pads.get_mut(index) else {
+            return;
+        };
+        let old = std::mem::replace(&mut pad.label, label.to_string());
+        for binding in &mut self.bindings {
+            if let BindingAction::TogglePad(pad) = &mut binding.action {
+                if *pad == old {
+                    *pad = label.to_string();
+                }
+            }
+        }
+        for step in self.macros.iter_mut().flat_map(|m| &mut m.steps) {
+            if let MacroAction::SetPad { pad, .. } = &mut step.action {
+                if *pad == old {
+                    *pad = label.to_string();
+                }
+            }
+        }
+        if self.haze.pad == old {
+            self.haze.pad = label.to_string();
+        }
+    }
+
+    /// Removes pad bank `bank` and the pads in it. The last bank stays.
+    pub fn remove_pad_bank(&mut self, bank: usize) {
+        if bank >= self.pad_layout.banks.len() || self.pad_layout.banks.len() == 1 {
+            return;
+        }
+        self.pad_layout.banks.remove(bank);
+        self.pads.retain(|pad| pad.bank != bank);
+        for pad in &mut self.pads {
+            if pad.bank > bank {
+                pad.bank -= 1;
+            }
+        }
+    }
+
+    /// Replaces what fixture `to` has programmed in every cue with what
+    /// fixture `from` has, for the attributes `to` has. Returns the number of
+    /// cues changed.
+    pub fn copy_fixture_values(&mut self, from: usize, to: usize) -> usize {
+        let Some(target) = self.fixtures.iter().find(|fixture| fixture.id == to) else {
+            return 0;
+        };
+        let channels = target.profile.channels.clone();
+        let mut changed = 0;
+        for cue in &mut self.cues {
+            let copied: Vec<CueValue> = cue
+                .values
+                .iter()
+                .filter(|value| value.fixture_id == from && channels.contains(&value.attribute))
+                .map(|value| CueValue {
+                    fixture_id: to,
+                    ..*value
+                })
+                .collect();
+            let had_values = cue.values.iter().any(|value| value.fixture_id == to);
+            if copied.is_empty() && !had_values {
+                continue;
+            }
+            cue.values.retain(|value| value.fixture_id != to);
+            cue.values.extend(copied);
+            changed += 1;
+        }
+        changed
+    }
+
+    /// Adds a copy of fixture `id` at the first free address after the last
+    /// patched channel of its universe, programmed the same in every cue.
+    /// Returns the new fixture's id, or `None` if the universe is full.
+    pub fn clone_fixture(&mut self, id: usize) -> Option<usize> {
+        let original = self
+            .fixtures
+            .iter()
+            .find(|fixture| fixture.id == id)?
+            .clone();
+        let footprint = original.profile.channels.len() as u16;
+        let address = self
+            .fixtures
+            .iter()
+            .filter(|fixture| fixture.universe == original.universe)
+            .map(|fixture| fixture.address + fixture.profile.channels.len() as u16)
+            .max()
+            .unwrap_or(1);
+        if address + footprint > UNIVERSE_SIZE as u16 + 1 {
+            return None;
+        }
+        let new_id = self
+            .fixtures
+            .iter()
+            .map(|fixture| fixture.id + 1)
+            .max()
+            .unwrap_or(0);
+        self.fixtures.push(Fixture {
+            id: new_id,
+            name: tr!("patch-copy-name", name = original.name.as_str()),
+            address,
+            ..original
+        });
+        self.copy_fixture_values(id, new_id);
+        Some(new_id)
+    }
+
+    /// Swaps every fixture of the type named `old` for `profile`, remapping
+    /// what the cues hold for them by function. Intensity is folded into the
+    /// colour on types without a dimmer channel; anything else the new type
+    /// can't do is dropped. Returns the number of fixtures swapped and the
+    /// number of cue values dropped.
+    pub fn replace_profile(&mut self, old: &str, profile: &FixtureProfile) -> (usize, usize) {
+        let mut swapped = Vec::new();
+        for fixture in &mut self.fixtures {
+            if fixture.profile.name == old {
+                fixture.profile = profile.clone();
+                swapped.push(fixture.id);
+            }
+        }
+
+        let colors = [Attribute::Red, Attribute::Green, Attribute::Blue];
+        let fold_intensity = !profile.channels.contains(&Attribute::Intensity)
+            && colors.iter().all(|color| profile.channels.contains(color));
+        let mut dropped = 0;
+        for cue in &mut self.cues {
+            for &id in &swapped {
+                let level = |attribute| {
+                    cue.values
+                        .iter()
+                        .find(|value| value.fixture_id == id && value.attribute == attribute)
+                        .map(|value| value.value)
+                };
+                if fold_intensity {
+                    if let Some(intensity) = level(Attribute::Intensity) {
+                        let folded: Vec<CueValue> = colors
+                            .iter()
+                            .map(|&attribute| CueValue {
+                                fixture_id: id,
+                                attribute,
+                                value: (level(attribute).unwrap_or(255) as u16 * intensity as u16
+                                    / 255) as u8,
+                            })
+                            .collect();
+                        cue.values.retain(|value| {
+                            value.fixture_id != id
+                                || !(value.attribute == Attribute::Intensity
+                                    || colors.contains(&value.attribute))
+                        });
+                        cue.values.extend(folded);
+                    }
+                }
+                let before = cue.values.len();
+                cue.values.retain(|value| {
+                    value.fixture_id != id || profile.channels.contains(&value.attribute)
+                });
+                dropped += before - cue.values.len();
+            }
+        }
+        (swapped.len(), dropped)
+    }
+
+    /// Profiles used by the patch that are not built into Halo, without
+    /// duplicates.
+    pub fn custom_profiles(&self) -> Vec<&FixtureProfile> {
+        let mut profiles: Vec<&FixtureProfile> = Vec::new();
+        for fixture in &self.fixtures {
+            if !fixture.profile.is_builtin() && !profiles.contains(&&fixture.profile) {
+                profiles.push(&fixture.profile);
+            }
+        }
+        profiles
+    }
+}
@@ -0,0 +1,251 @@
+//! Comparing two versions of a show's cues, such as tonight's against the
+//! last rehearsal's backup, or one song's cue list against another's: which
+//! cues were added or removed, which moved or changed length, and which
+//! levels changed in each. Cues are matched by number, and by song too when
+//! whole shows are compared; fixtures by name, since ids differ from show to
+//! show.
+
+use crate::cue::{Cue, CueNumber};
+use crate::fixture::Attribute;
+use crate::i18n::tr;
+use crate::show::Show;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Which of a show's cues to compare.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum CueList {
+    #[default]
+    All,
+    /// The cues outside any song.
+    Loose,
+    Song(u32),
+}
+
+impl CueList {
+    fn includes(self, cue: &Cue) -> bool {
+        match self {
+            CueList::All => true,
+            CueList::Loose => cue.song.is_none(),
+            CueList::Song(id) => cue.song == Some(id),
+        }
+    }
+
+    pub fn label(self, show: &Show) -> String {
+        match self {
+            CueList::All => tr!("diff-all-cues"),
+            CueList::Loose => tr!("diff-loose-cues"),
+            CueList::Song(id) => show
+                .song(id)
+                .map_or_else(|| tr!("diff-missing-song"), |song| song.name.clone()),
+        }
+    }
+
+    /// Every list `show` has, whole show first.
+    pub fn all(show: &Show) -> Vec<CueList> {
+        let mut lists = vec![CueList::All, CueList::Loose];
+        lists.extend(show.songs.iter().map(|song| CueList::Song(song.id)));
+        lists
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A level that differs; `None` on the side whose cue doesn't set it.
+pub struct ValueChange {
+    pub fixture: String,
+    pub attribute: Attribute,
+    pub before: Option<u8>,
+    pub after: Option<u8>,
+}
+
+/// One cue that differs between the two versions.
+pub struct CueChange {
+    pub kind: ChangeKind,
+    pub number: CueNumber,
+    pub name: String,
+    /// The song the cue is in, when whole shows are compared.
+    pub song: Option<String>,
+    /// Start times, from the start of the cue's song, when they differ.
+    pub start: Option<(Duration, Duration)>,
+    pub duration: Option<(Duration, Duration)>,
+    pub renamed: Option<(String, String)>,
+    pub values: Vec<ValueChange>,
+    /// Anything else about the cue that changed, such as its notes or
+    /// script, by the name it has in the show file.
+    pub other: Vec<String>,
+}
+
+/// The two sides of a comparison.
+pub struct Side<'a> {
+    pub show: &'a Show,
+    pub list: CueList,
+}
+
+impl<'a> Side<'a> {
+    fn levels(&self, cue: &Cue) -> BTreeMap<(String, Attribute), u8> {
+        cue.values
+            .iter()
+            .filter_map(|value| {
+                let fixture = self
+                    .show
+                    .fixtures
+                    .iter()
+                    .find(|f| f.id == value.fixture_id)?;
+                Some(((fixture.name.clone(), value.attribute), value.value))
+            })
+            .collect()
+    }
+
+    /// The side's cues by what they are matched by.
+    fn cues(&self, by_song: bool) -> BTreeMap<Key, &'a Cue> {
+        let show = self.show;
+        show.cues
+            .iter()
+            .filter(|cue| self.list.includes(cue))
+            .map(|cue| {
+                let song = if by_song {
+                    self.song_name(cue)
+                } else {
+                    String::new()
+                };
+                ((song, cue.number), cue)
+            })
+            .collect()
+    }
+
+    fn song_name(&self, cue: &Cue) -> String {
+        cue.song
+            .and_then(|id| self.show.song(id))
+            .map(|song| song.name.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// What a cue is matched by: its number, after its song's name when whole
+/// shows are compared.
+type Key = (String, CueNumber);
+
+/// The fields compared one by one rather than listed by name.
+const COMPARED: [&str; 6] = ["number", "name", "start_time", "duration", "values", "song"];
+
+/// The other fields of the two cues that differ, by name.
+fn other_changes(before: &Cue, after: &Cue) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(before)), Ok(serde_json::Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
+    fields
+        .into_iter()
+        .filter(|field| !COMPARED.contains(&field.as_str()))
+        .filter(|field| before.get(*field) != after.get(*field))
+        .map(|field| field.replace('_', " "))
+        .collect()
+}
+
+fn value_changes(
+    before: &BTreeMap<(String, Attribute), u8>,
+    after: &BTreeMap<(String, Attribute), u8>,
+) -> Vec<ValueChange> {
+    let mut keys: Vec<&(String, Attribute)> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| ValueChange {
+            fixture: key.0.clone(),
+            attribute: key.1,
+            before: before.get(key).copied(),
+            after: after.get(key).copied(),
+        })
+        .collect()
+}
+
+/// The cues that differ between `before` and `after`, in cue order.
+pub fn diff(before: &Side, after: &Side) -> Vec<CueChange> {
+    let by_song = before.list == CueList::All && after.list == CueList::All;
+    let (old, new) = (before.cues(by_song), after.cues(by_song));
+    let mut keys: Vec<&Key> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut changes = Vec::new();
+    for key in keys {
+        let song = (!key.0.is_empty()).then(|| key.0.clone());
+        let change = match (old.get(key), new.get(key)) {
+            (Some(cue), None) => CueChange {
+                kind: ChangeKind::Removed,
+                number: cue.number,
+                name: cue.name.clone(),
+                song,
+                start: None,
+                duration: None,
+                renamed: None,
+                values: value_changes(&before.levels(cue), &BTreeMap::new()),
+                other: Vec::new(),
+            },
+            (None, Some(cue)) => CueChange {
+                kind: ChangeKind::Added,
+                number: cue.number,
+                name: cue.name.clone(),
+                song,
+                start: None,
+                duration: None,
+                renamed: None,
+                values: value_changes(&BTreeMap::new(), &after.levels(cue)),
+                other: Vec::new(),
+            },
+            (Some(old), Some(new)) => {
+                let change = CueChange {
+                    kind: ChangeKind::Changed,
+                    number: new.number,
+                    name: new.name.clone(),
+                    song,
+                    start: (old.start_time != new.start_time)
+                        .then_some((old.start_time, new.start_time)),
+                    duration: (old.duration != new.duration)
+                        .then_some((old.duration, new.duration)),
+                    renamed: (old.name != new.name).then(|| (old.name.clone(), new.name.clone())),
+                    values: value_changes(&before.levels(old), &after.levels(new)),
+                    other: other_changes(old, new),
+                };
+                if change.is_unchanged() {
+                    continue;
+                }
+                change
+            }
+            (None, None) => continue,
+        };
+        changes.push(change);
+    }
+    changes
+}
+
+impl CueChange {
+    fn is_unchanged(&self) -> bool {
+        self.start.is_none()
+            && self.duration.is_none()
+            && self.renamed.is_none()
+            && self.values.is_empty()
+            && self.other.is_empty()
+    }
+}
+
+/// How many cues were added, removed and changed, for a summary line.
+pub fn counts(changes: &[CueChange]) -> (usize, usize, usize) {
+    let count = |kind| changes.iter().filter(|change| change.kind == kind).count();
+    (
+        count(ChangeKind::Added),
+        count(ChangeKind::Removed),
+        count(ChangeKind::Changed),
+    )
+}
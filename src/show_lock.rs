@@ -0,0 +1,42 @@
+//! A passcode that keeps a show in performance mode. A locked show opens in
+//! performance mode and can't leave it without the passcode, so an installed
+//! system can't be edited by accident or by whoever happens to be nearby.
+//! The passcode is stored salted and hashed rather than as text, which keeps
+//! it from being read straight out of the show file; it is not a defence
+//! against someone determined to get in.
+
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShowLock {
+    salt: u64,
+    hash: u64,
+}
+
+impl ShowLock {
+    pub fn new(passcode: &str) -> Self {
+        let salt = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_nanos() as u64);
+        Self {
+            salt,
+            hash: hash(salt, passcode),
+        }
+    }
+
+    pub fn unlocks(&self, passcode: &str) -> bool {
+        hash(self.salt, passcode) == self.hash
+    }
+}
+
+/// FNV-1a over the salt and passcode, which unlike the standard library's
+/// hashers is the same from one build to the next.
+fn hash(salt: u64, passcode: &str) -> u64 {
+    salt.to_le_bytes()
+        .iter()
+        .chain(passcode.as_bytes())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+}
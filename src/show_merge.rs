@@ -0,0 +1,302 @@
+//! Merging part of another show into this one: songs with their cues, the
+//! cues outside any song, group masters, macros and busk palette colours,
+//! picked one by one. Cues find their fixtures here by name, since ids
+//! differ from show to show, and anything clashing with what is already
+//! here — a song, master or macro of the same name, or a cue of the same
+//! number — is kept alongside, replaced or skipped as asked.
+
+use crate::cue::{Cue, CueNumber};
+use crate::group_master::GroupMaster;
+use crate::i18n::tr;
+use crate::show::Show;
+use crate::song::{self, Song};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Conflict {
+    /// Renames or renumbers what comes in so both are kept.
+    KeepBoth,
+    /// What comes in takes the place of what was here.
+    Replace,
+    /// What was here stays and what comes in is left out.
+    Skip,
+}
+
+impl Conflict {
+    pub const ALL: [Conflict; 3] = [Conflict::KeepBoth, Conflict::Replace, Conflict::Skip];
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            Conflict::KeepBoth => tr!("conflict-keep-both"),
+            Conflict::Replace => tr!("conflict-replace"),
+            Conflict::Skip => tr!("conflict-skip"),
+        })
+    }
+}
+
+/// What to take from the other show.
+#[derive(Clone, Default)]
+pub struct MergeSelection {
+    /// Songs, with their cues, by id in the other show.
+    pub songs: Vec<u32>,
+    /// The cues outside any song.
+    pub loose_cues: bool,
+    /// Group masters and macros, by position in the other show.
+    pub group_masters: Vec<usize>,
+    pub macros: Vec<usize>,
+    /// The busk palette's colours not already in this one.
+    pub palette: bool,
+}
+
+/// How a merge went, for the log.
+#[derive(Default)]
+pub struct MergeReport {
+    pub songs: usize,
+    pub cues: usize,
+    pub group_masters: usize,
+    pub macros: usize,
+    pub colors: usize,
+    /// Things left out because they clashed and the conflict said skip.
+    pub skipped: usize,
+    /// Cue values for fixtures this show hasn't got.
+    pub dropped_values: usize,
+}
+
+impl fmt::Display for MergeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = [
+            (self.songs, tr!("merge-songs", count = self.songs)),
+            (self.cues, tr!("merge-cues", count = self.cues)),
+            (
+                self.group_masters,
+                tr!("merge-group-masters", count = self.group_masters),
+            ),
+            (self.macros, tr!("merge-macros", count = self.macros)),
+            (self.colors, tr!("merge-colours", count = self.colors)),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(_, part)| part)
+        .collect();
+        if parts.is_empty() {
+            parts.push(tr!("merge-nothing"));
+        }
+        f.write_str(&tr!("merge-merged", parts = parts.join(", ")))?;
+        if self.skipped > 0 {
+            f.write_str(&tr!("merge-skipped", count = self.skipped))?;
+        }
+        if self.dropped_values > 0 {
+            f.write_str(&tr!("merge-dropped", count = self.dropped_values))?;
+        }
+        Ok(())
+    }
+}
+
+/// `name`, or `name 2`, `name 3` and so on, whichever `taken` says is free.
+pub fn unique_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} {}", name, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_default()
+}
+
+/// Adds, replaces or skips one named item per `conflict`. Returns whether it
+/// went in.
+fn merge_named<T>(
+    into: &mut Vec<T>,
+    mut item: T,
+    conflict: Conflict,
+    name: impl Fn(&mut T) -> &mut String,
+) -> bool {
+    let names: Vec<String> = into.iter_mut().map(|other| name(other).clone()).collect();
+    match (
+        names.iter().position(|other| other == name(&mut item)),
+        conflict,
+    ) {
+        (None, _) => into.push(item),
+        (Some(_), Conflict::KeepBoth) => {
+            let renamed = unique_name(name(&mut item), |candidate| {
+                names.iter().any(|other| other == candidate)
+            });
+            *name(&mut item) = renamed;
+            into.push(item);
+        }
+        (Some(index), Conflict::Replace) => into[index] = item,
+        (Some(_), Conflict::Skip) => return false,
+    }
+    true
+}
+
+impl Show {
+    /// Merges what `selection` picks from `other` into the show.
+    pub fn merge(
+        &mut self,
+        other: &Show,
+        selection: &MergeSelection,
+        conflict: Conflict,
+    ) -> MergeReport {
+        let mut report = MergeReport::default();
+        // Fixture ids there to ids here, by name.
+        let fixtures: HashMap<usize, usize> = other
+            .fixtures
+            .iter()
+            .filter_map(|theirs| {
+                let ours = self.fixtures.iter().find(|ours| ours.name == theirs.name)?;
+                Some((theirs.id, ours.id))
+            })
+            .collect();
+
+        let mut songs: HashMap<u32, Option<u32>> = HashMap::new();
+        for song in other
+            .songs
+            .iter()
+            .filter(|song| selection.songs.contains(&song.id))
+        {
+            let existing = self.songs.iter().position(|ours| ours.name == song.name);
+            let id = match (existing, conflict) {
+                (Some(index), Conflict::Replace) => {
+                    let Song { id, start_time, .. } = self.songs[index];
+                    self.cues.retain(|cue| cue.song != Some(id));
+                    self.songs[index] = Song {
+                        id,
+                        start_time,
+                        ..song.clone()
+                    };
+                    Some(id)
+                }
+                (Some(_), Conflict::Skip) => None,
+                (existing, _) => {
+                    let id = song::next_id(&self.songs);
+                    let start = self.songs.iter().map(Song::end).max().unwrap_or_default();
+                    let name = match existing {
+                        Some(_) => unique_name(&song.name, |candidate| {
+                            self.songs.iter().any(|ours| ours.name == candidate)
+                        }),
+                        None => song.name.clone(),
+                    };
+                    self.songs.push(Song {
+                        id,
+                        name,
+                        start_time: start,
+                        ..song.clone()
+                    });
+                    Some(id)
+                }
+            };
+            match id {
+                Some(_) => report.songs += 1,
+                None => report.skipped += 1,
+            }
+            songs.insert(song.id, id);
+        }
+
+        let cues = other.cues.iter().filter(|cue| match cue.song {
+            Some(id) => selection.songs.contains(&id),
+            None => selection.loose_cues,
+        });
+        for cue in cues {
+            let song = match cue.song {
+                Some(id) => match songs.get(&id) {
+                    Some(&Some(ours)) => Some(ours),
+                    // The song itself was skipped.
+                    _ => continue,
+                },
+                None => None,
+            };
+            let mut cue = Cue {
+                song,
+                ..cue.clone()
+            };
+            let before = cue.values.len();
+            cue.values
+                .retain_mut(|value| match fixtures.get(&value.fixture_id) {
+                    Some(&id) => {
+                        value.fixture_id = id;
+                        true
+                    }
+                    None => false,
+                });
+            report.dropped_values += before - cue.values.len();
+            for part in &mut cue.parts {
+                part.fixtures = part
+                    .fixtures
+                    .iter()
+                    .filter_map(|id| fixtures.get(id).copied())
+                    .collect();
+            }
+            let existing = self.cues.iter().position(|ours| ours.number == cue.number);
+            match (existing, conflict) {
+                (Some(index), Conflict::Replace) => self.cues[index] = cue,
+                (Some(_), Conflict::Skip) => {
+                    report.skipped += 1;
+                    continue;
+                }
+                (Some(_), Conflict::KeepBoth) => {
+                    let last = self.cues.iter().map(|ours| ours.number).max();
+                    let number = match last {
+                        Some(last) => last.point_after(None),
+                        None => Some(CueNumber::FIRST),
+                    };
+                    // No room past the last cue: skipped rather than clashing.
+                    let Some(number) = number else {
+                        report.skipped += 1;
+                        continue;
+                    };
+                    cue.number = number;
+                    self.cues.push(cue);
+                }
+                (None, _) => {
+                    let at = self.cues.partition_point(|ours| ours.number < cue.number);
+                    self.cues.insert(at, cue);
+                }
+            }
+            report.cues += 1;
+        }
+
+        for master in selection
+            .group_masters
+            .iter()
+            .filter_map(|&index| other.group_masters.get(index))
+        {
+            // Controllers belong to the rig they were learnt on.
+            let master = GroupMaster {
+                control: None,
+                fader: None,
+                ..master.clone()
+            };
+            if merge_named(&mut self.group_masters, master, conflict, |m| &mut m.name) {
+                report.group_masters += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+        for recorded in selection
+            .macros
+            .iter()
+            .filter_map(|&index| other.macros.get(index))
+        {
+            if merge_named(&mut self.macros, recorded.clone(), conflict, |m| {
+                &mut m.name
+            }) {
+                report.macros += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+        if selection.palette {
+            for color in &other.busk.palette {
+                if !self.busk.palette.contains(color) {
+                    self.busk.palette.push(*color);
+                    report.colors += 1;
+                }
+            }
+        }
+        report
+    }
+}
@@ -0,0 +1,215 @@
+//! Runs a show through the output engine on a virtual clock, frame by frame
+//! at the engine's rate, with nothing sent anywhere. The same show always
+//! gives the same frames, so they can be kept as a golden file and checked
+//! against after changes to fades, effects or the merge:
+//!
+//! ```text
+//! halo-tc-ui --simulate <show | default> [--seconds N] [--golden FILE] [--bless]
+//! ```
+//!
+//! Without `--golden` the frames are printed. With it they are compared to
+//! the file, which is written instead if it doesn't exist yet or `--bless`
+//! is given. Each line is a frame number and playhead time followed by the
+//! channels that changed since the frame before, as `universe.channel=value`;
+//! frames where nothing changed are left out.
+
+use crate::audio::AudioLevels;
+use crate::dmx::{DmxFrame, OUTPUT_HZ, UNIVERSE_SIZE};
+use crate::dmx_input::DmxInput;
+use crate::engine::{Program, Renderer};
+use crate::plugin::PluginHost;
+use crate::psn::Trackers;
+use crate::show::Show;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Played on after the last cue ends, to catch anything still moving.
+const TAIL: Duration = Duration::from_secs(1);
+
+struct Options {
+    show: String,
+    seconds: Option<f32>,
+    golden: Option<PathBuf>,
+    bless: bool,
+}
+
+fn parse(args: &[String]) -> Result<Options, String> {
+    let mut args = args.iter();
+    let show = args.next().ok_or("no show file given")?.clone();
+    let mut options = Options {
+        show,
+        seconds: None,
+        golden: None,
+        bless: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seconds" => {
+                let seconds = args.next().ok_or("--seconds needs a number")?;
+                options.seconds = Some(
+                    seconds
+                        .parse()
+                        .map_err(|_| format!("not a number of seconds: {}", seconds))?,
+                );
+            }
+            "--golden" => {
+                options.golden = Some(args.next().ok_or("--golden needs a file")?.into());
+            }
+            "--bless" => options.bless = true,
+            other => return Err(format!("unknown option {}", other)),
+        }
+    }
+    Ok(options)
+}
+
+/// The program the engine would get for `show` with nothing live: no
+/// crossfade, panic, input or outputs.
+fn program(show: &Show) -> Program {
+    Program {
+        cues: show.timeline_cues(),
+        fixtures: show.fixtures.clone(),
+        pixel_maps: show.pixel_maps.clone(),
+        move_in_black_lead: show.move_in_black_lead,
+        beats_per_bar: show.time_signature.beats_per_bar(),
+        speed_rates: show
+            .speed_masters
+            .iter()
+            .map(|master| master.rate)
+            .collect(),
+        group_masters: show.group_masters.clone(),
+        ..Program::default()
+    }
+}
+
+/// Plays `program` from the top for `length`, returning the frames as lines.
+fn frames(mut program: Program, length: Duration) -> Vec<String> {
+    let period = Duration::from_secs_f32(1.0 / OUTPUT_HZ);
+    // Only differences from here are used, so any instant will do.
+    let origin = Instant::now();
+    let mut renderer = Renderer::new(origin, &program, origin);
+    renderer.start(origin);
+    let (plugins, input) = (PluginHost::default(), DmxInput::default());
+    let trackers = Trackers::default();
+
+    let mut lines = Vec::new();
+    let mut last = DmxFrame::default();
+    let count = (length.as_secs_f64() / period.as_secs_f64()).ceil() as u32 + 1;
+    for n in 0..count {
+        let now = origin + period * n;
+        let rendered = renderer.render(
+            &mut program,
+            &plugins,
+            AudioLevels::default(),
+            &input,
+            &trackers,
+            now,
+        );
+        let mut line = String::new();
+        for universe in rendered.frame.universe_numbers() {
+            for channel in 1..=UNIVERSE_SIZE as u16 {
+                let value = rendered.frame.get(universe, channel);
+                if value != last.get(universe, channel) {
+                    let _ = write!(line, " {}.{}={}", universe, channel, value);
+                }
+            }
+        }
+        if !line.is_empty() {
+            lines.push(format!(
+                "{} {:.3}{}",
+                n,
+                rendered.elapsed.as_secs_f64(),
+                line
+            ));
+        }
+        last = rendered.frame;
+    }
+    lines
+}
+
+/// Runs the simulation the command line asks for, returning the process
+/// exit code.
+pub fn main(args: &[String]) -> i32 {
+    let options = match parse(args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}", err);
+            eprintln!(
+                "usage: halo-tc-ui --simulate <show | default> [--seconds N] [--golden FILE] [--bless]"
+            );
+            return 2;
+        }
+    };
+    let show = if options.show == "default" {
+        Show::default()
+    } else {
+        match Show::load(Path::new(&options.show)) {
+            Ok(show) => show,
+            Err(err) => {
+                eprintln!("Could not load {}: {}", options.show, err);
+                return 2;
+            }
+        }
+    };
+    let program = program(&show);
+    let length = match options.seconds {
+        Some(seconds) => Duration::from_secs_f32(seconds.max(0.0)),
+        None => {
+            program
+                .cues
+                .iter()
+                .map(|cue| cue.start_time + cue.duration)
+                .max()
+                .unwrap_or_default()
+                + TAIL
+        }
+    };
+    let lines = frames(program, length);
+
+    let Some(golden) = options.golden else {
+        for line in &lines {
+            println!("{}", line);
+        }
+        return 0;
+    };
+    if options.bless || !golden.exists() {
+        let mut text = lines.join("\n");
+        text.push('\n');
+        return match fs::write(&golden, text) {
+            Ok(()) => {
+                println!("Wrote {} frames to {}", lines.len(), golden.display());
+                0
+            }
+            Err(err) => {
+                eprintln!("Could not write {}: {}", golden.display(), err);
+                2
+            }
+        };
+    }
+    let expected = match fs::read_to_string(&golden) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("Could not read {}: {}", golden.display(), err);
+            return 2;
+        }
+    };
+    let expected: Vec<&str> = expected.lines().collect();
+    let differs = (0..lines.len().max(expected.len()))
+        .find(|&i| lines.get(i).map(String::as_str) != expected.get(i).copied());
+    match differs {
+        None => {
+            println!("{} frames match {}", lines.len(), golden.display());
+            0
+        }
+        Some(i) => {
+            eprintln!("Frames differ from {} at line {}:", golden.display(), i + 1);
+            eprintln!("  expected: {}", expected.get(i).unwrap_or(&"(nothing)"));
+            eprintln!(
+                "  got:      {}",
+                lines.get(i).map_or("(nothing)", String::as_str)
+            );
+            1
+        }
+    }
+}
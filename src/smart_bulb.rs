@@ -0,0 +1,386 @@
+//! Smart bulbs as outputs: fixtures mapped to Philips Hue lights or WLED
+//! controllers instead of DMX, so house lights on smart bulbs can join a
+//! small show. Neither takes anything like a DMX frame rate, so a bridge
+//! thread sends only what has changed, as fast as each device allows, and a
+//! slow bridge never holds up the engine.
+
+use crate::dmx::DmxFrame;
+use crate::fixture::Fixture;
+use crate::i18n::tr;
+use crate::pixel_stream::WLED_PORT;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Hue bridges handle about ten light commands a second in all.
+const HUE_INTERVAL: Duration = Duration::from_millis(100);
+const WLED_INTERVAL: Duration = Duration::from_millis(33);
+/// WLED drops back to its own effects this long after the last realtime
+/// packet, so unchanged colours are repeated before then.
+const WLED_KEEPALIVE: Duration = Duration::from_secs(1);
+/// The most LEDs one DRGB packet can carry.
+pub const WLED_MAX_LEDS: u16 = 490;
+const HTTP_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BulbDevice {
+    /// A light on the Hue bridge, by its number there.
+    Hue { light: u32 },
+    /// A WLED controller, every LED showing the fixture's colour.
+    Wled { host: String, leds: u16 },
+}
+
+impl BulbDevice {
+    pub fn kinds() -> [BulbDevice; 2] {
+        [
+            BulbDevice::Hue { light: 1 },
+            BulbDevice::Wled {
+                host: String::new(),
+                leds: 30,
+            },
+        ]
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BulbDevice::Hue { .. } => "Hue",
+            BulbDevice::Wled { .. } => "WLED",
+        }
+    }
+}
+
+impl fmt::Display for BulbDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BulbDevice::Hue { light } => {
+                f.write_str(&tr!("bulb-hue-light", light = light.to_string()))
+            }
+            BulbDevice::Wled { host, .. } => write!(f, "WLED {}", host),
+        }
+    }
+}
+
+/// A fixture whose colour goes to a smart bulb.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmartBulb {
+    pub fixture: usize,
+    pub device: BulbDevice,
+}
+
+/// The smart bulb bridge, saved with the show.
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmartBulbSettings {
+    pub enabled: bool,
+    /// The Hue bridge's address.
+    pub hue_bridge: String,
+    /// The user name the bridge gave when paired.
+    pub hue_username: String,
+    pub bulbs: Vec<SmartBulb>,
+}
+
+struct Shared {
+    settings: SmartBulbSettings,
+    /// The colour of each bulb in the latest frame.
+    colors: Vec<[u8; 3]>,
+    /// The last failure, and which bulb it was for.
+    error: Option<(usize, String)>,
+}
+
+/// Sends the latest colours to the bulbs from its own thread, until dropped.
+pub struct BulbBridge {
+    shared: Arc<Mutex<Shared>>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl BulbBridge {
+    pub fn start() -> Self {
+        let shared = Arc::new(Mutex::new(Shared {
+            settings: SmartBulbSettings::default(),
+            colors: Vec::new(),
+            error: None,
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let shared = shared.clone();
+            let shutdown = shutdown.clone();
+            thread::Builder::new()
+                .name("halo-smart-bulbs".to_string())
+                .spawn(move || run(shared, shutdown))
+                .ok()
+        };
+        Self {
+            shared,
+            shutdown,
+            thread,
+        }
+    }
+
+    /// Hands the bridge the colour each bulb's fixture has in `frame`.
+    pub fn update(&self, settings: &SmartBulbSettings, fixtures: &[Fixture], frame: &DmxFrame) {
+        let colors = settings
+            .bulbs
+            .iter()
+            .map(|bulb| {
+                fixtures
+                    .iter()
+                    .find(|fixture| fixture.id == bulb.fixture)
+                    .map_or([0; 3], |fixture| fixture.output_color(frame))
+            })
+            .collect();
+        let mut shared = self.shared.lock().unwrap();
+        if shared.settings != *settings {
+            shared.settings = settings.clone();
+        }
+        shared.colors = colors;
+    }
+
+    pub fn error(&self) -> Option<String> {
+        let shared = self.shared.lock().unwrap();
+        let (index, error) = shared.error.as_ref()?;
+        let device = shared
+            .settings
+            .bulbs
+            .get(*index)
+            .map(|bulb| bulb.device.to_string())
+            .unwrap_or_default();
+        Some(format!("{}: {}", device, error))
+    }
+}
+
+impl Drop for BulbBridge {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// What was last sent to one bulb, and when.
+#[derive(Clone, Copy)]
+struct Sent {
+    color: Option<[u8; 3]>,
+    at: Option<Instant>,
+}
+
+fn run(shared: Arc<Mutex<Shared>>, shutdown: Arc<AtomicBool>) {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok();
+    let mut bulbs: Vec<SmartBulb> = Vec::new();
+    let mut sent: Vec<Sent> = Vec::new();
+    let mut hue_sent: Option<Instant> = None;
+    // Where to start looking for a changed Hue light, so every light gets
+    // its turn when they all change at once.
+    let mut hue_next = 0;
+
+    while !shutdown.load(Ordering::Relaxed) {
+        thread::sleep(Duration::from_millis(10));
+        let (settings, colors) = {
+            let shared = shared.lock().unwrap();
+            (shared.settings.clone(), shared.colors.clone())
+        };
+        if settings.bulbs != bulbs {
+            bulbs = settings.bulbs.clone();
+            sent = vec![
+                Sent {
+                    color: None,
+                    at: None,
+                };
+                bulbs.len()
+            ];
+            hue_next = 0;
+        }
+        let now = Instant::now();
+        let mut results = Vec::new();
+
+        // WLED takes a packet per controller at up to 30 a second.
+        if let Some(socket) = &socket {
+            for (i, (bulb, &color)) in bulbs.iter().zip(&colors).enumerate() {
+                let BulbDevice::Wled { host, leds } = &bulb.device else {
+                    continue;
+                };
+                let since = sent[i].at.map(|at| now.saturating_duration_since(at));
+                let due = match since {
+                    None => true,
+                    Some(since) => {
+                        (sent[i].color != Some(color) && since >= WLED_INTERVAL)
+                            || since >= WLED_KEEPALIVE
+                    }
+                };
+                if due && !host.is_empty() {
+                    results.push((i, send_wled(socket, host, *leds, color)));
+                    sent[i] = Sent {
+                        color: Some(color),
+                        at: Some(now),
+                    };
+                }
+            }
+        }
+
+        // Hue lights share the bridge's budget, taking turns.
+        let hue_due = hue_sent.is_none_or(|at| now.saturating_duration_since(at) >= HUE_INTERVAL);
+        if hue_due && !settings.hue_bridge.is_empty() && !bulbs.is_empty() {
+            let changed = (0..bulbs.len())
+                .map(|offset| (hue_next + offset) % bulbs.len())
+                .find(|&i| {
+                    matches!(bulbs[i].device, BulbDevice::Hue { .. })
+                        && colors
+                            .get(i)
+                            .is_some_and(|&color| sent[i].color != Some(color))
+                });
+            if let Some(i) = changed {
+                let BulbDevice::Hue { light } = bulbs[i].device else {
+                    unreachable!("only Hue lights are picked");
+                };
+                results.push((i, send_hue(&settings, light, colors[i])));
+                sent[i] = Sent {
+                    color: Some(colors[i]),
+                    at: Some(now),
+                };
+                hue_sent = Some(now);
+                hue_next = i + 1;
+            }
+        }
+
+        if !results.is_empty() {
+            let mut shared = shared.lock().unwrap();
+            for (i, result) in results {
+                match result {
+                    Err(err) => shared.error = Some((i, err.to_string())),
+                    Ok(()) if shared.error.as_ref().is_some_and(|(bulb, _)| *bulb == i) => {
+                        shared.error = None;
+                    }
+                    Ok(()) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Sends a WLED realtime DRGB packet setting every LED to `color`.
+fn send_wled(socket: &UdpSocket, host: &str, leds: u16, color: [u8; 3]) -> io::Result<()> {
+    let leds = leds.clamp(1, WLED_MAX_LEDS) as usize;
+    // Protocol 2 is DRGB; the second byte is how many seconds to hold the
+    // colours before WLED goes back to its own effects.
+    let mut packet = vec![2, 2];
+    for _ in 0..leds {
+        packet.extend_from_slice(&color);
+    }
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:{}", host, WLED_PORT)
+    };
+    socket.send_to(&packet, address.as_str()).map(drop)
+}
+
+fn send_hue(settings: &SmartBulbSettings, light: u32, color: [u8; 3]) -> io::Result<()> {
+    if settings.hue_username.is_empty() {
+        return Err(io::Error::other(tr!("bulbs-pair-first")));
+    }
+    let brightness = color.into_iter().max().unwrap_or(0);
+    let body = if brightness == 0 {
+        serde_json::json!({ "on": false, "transitiontime": 1 })
+    } else {
+        let [x, y] = hue_xy(color);
+        serde_json::json!({
+            "on": true,
+            "bri": (brightness as u32 * 253 / 255 + 1),
+            "xy": [x, y],
+            "transitiontime": 1,
+        })
+    };
+    let path = format!("/api/{}/lights/{}/state", settings.hue_username, light);
+    let response = http(&settings.hue_bridge, "PUT", &path, &body.to_string())?;
+    match hue_error(&response) {
+        Some(error) => Err(io::Error::other(error)),
+        None => Ok(()),
+    }
+}
+
+/// The CIE xy point Hue lights take for an sRGB colour, using the wide gamut
+/// conversion Philips recommends.
+fn hue_xy(color: [u8; 3]) -> [f32; 2] {
+    let [r, g, b] = color.map(|c| {
+        let c = c as f32 / 255.0;
+        if c > 0.04045 {
+            ((c + 0.055) / 1.055).powf(2.4)
+        } else {
+            c / 12.92
+        }
+    });
+    let x = r * 0.664_511 + g * 0.154_324 + b * 0.162_028;
+    let y = r * 0.283_881 + g * 0.668_433 + b * 0.047_685;
+    let z = r * 0.000_088 + g * 0.072_310 + b * 0.986_039;
+    let sum = x + y + z;
+    if sum <= 0.0 {
+        // White, for a colour too dark to have a hue.
+        return [0.3127, 0.3290];
+    }
+    [x / sum, y / sum]
+}
+
+/// The description of the first error in a Hue API response, if any.
+fn hue_error(response: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(response).ok()?;
+    value
+        .as_array()?
+        .iter()
+        .find_map(|item| item.get("error")?.get("description")?.as_str())
+        .map(str::to_string)
+}
+
+/// Asks the Hue bridge at `bridge` for a user name. Its link button must have
+/// been pressed in the last 30 seconds.
+pub fn pair_hue(bridge: &str) -> io::Result<String> {
+    let body = serde_json::json!({ "devicetype": "halo#show" }).to_string();
+    let response = http(bridge, "POST", "/api", &body)?;
+    if let Some(error) = hue_error(&response) {
+        return Err(io::Error::other(error));
+    }
+    let value: serde_json::Value = serde_json::from_str(&response)
+        .map_err(|_| io::Error::other("not a Hue bridge response"))?;
+    value
+        .as_array()
+        .and_then(|items| items.first())
+        .and_then(|item| item.get("success")?.get("username")?.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| io::Error::other(tr!("bulbs-no-user-name")))
+}
+
+/// Sends a plain HTTP request with a JSON body and returns the response body.
+pub(crate) fn http(host: &str, method: &str, path: &str, body: &str) -> io::Result<String> {
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+    let addr = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::other("no such host"))?;
+    let mut stream = TcpStream::connect_timeout(&addr, HTTP_TIMEOUT)?;
+    stream.set_read_timeout(Some(HTTP_TIMEOUT))?;
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        path,
+        host,
+        body.len(),
+        body
+    )?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (_, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| io::Error::other("not an HTTP response"))?;
+    Ok(body.to_string())
+}
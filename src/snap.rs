@@ -0,0 +1,115 @@
+//! Snapping cues dragged on the timeline: to whole seconds, timecode
+//! frames, the beats or bars of the show's beat grid, or the hits found in
+//! the audio. The beat grid is a tempo and the time of a downbeat, saved
+//! with the show so it stays put while the live BPM follows the band.
+
+use crate::i18n::tr;
+use crate::spectrogram::AudioAnalysis;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// What dragged cues land on.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum SnapTo {
+    Seconds,
+    Frames,
+    Beats,
+    Bars,
+    #[default]
+    Hits,
+}
+
+impl SnapTo {
+    pub const ALL: [SnapTo; 5] = [
+        SnapTo::Seconds,
+        SnapTo::Frames,
+        SnapTo::Beats,
+        SnapTo::Bars,
+        SnapTo::Hits,
+    ];
+}
+
+impl fmt::Display for SnapTo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            SnapTo::Seconds => tr!("snap-seconds"),
+            SnapTo::Frames => tr!("snap-frames"),
+            SnapTo::Beats => tr!("snap-beats"),
+            SnapTo::Bars => tr!("snap-bars"),
+            SnapTo::Hits => tr!("snap-hits"),
+        })
+    }
+}
+
+/// Beats laid over the timeline at a fixed tempo from a downbeat.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BeatGrid {
+    pub bpm: f32,
+    /// Show time of a first beat of a bar; the grid runs both ways from it.
+    pub downbeat: Duration,
+}
+
+impl Default for BeatGrid {
+    fn default() -> Self {
+        Self {
+            bpm: 120.0,
+            downbeat: Duration::ZERO,
+        }
+    }
+}
+
+impl BeatGrid {
+    fn beat_length(&self) -> f32 {
+        60.0 / self.bpm.max(1.0)
+    }
+
+    /// The beats between `from` and `to` seconds, as their time and whether
+    /// each starts a bar.
+    pub fn beats(
+        &self,
+        from: f32,
+        to: f32,
+        beats_per_bar: u32,
+    ) -> impl Iterator<Item = (f32, bool)> {
+        let length = self.beat_length();
+        let downbeat = self.downbeat.as_secs_f32();
+        let first = ((from - downbeat) / length).ceil() as i64;
+        let last = ((to - downbeat) / length).floor() as i64;
+        (first..=last).map(move |beat| {
+            (
+                downbeat + beat as f32 * length,
+                beat.rem_euclid(beats_per_bar.max(1) as i64) == 0,
+            )
+        })
+    }
+}
+
+/// The nearest multiple of `step` seconds to `time`, counted from `origin`.
+fn nearest(time: f32, origin: f32, step: f32) -> f32 {
+    origin + ((time - origin) / step).round() * step
+}
+
+/// Where a cue dragged to `time` lands. Grid snaps always land on the
+/// nearest line; hits only pull within `within` of one.
+pub fn snap(
+    to: SnapTo,
+    time: Duration,
+    grid: &BeatGrid,
+    beats_per_bar: u32,
+    frame: f32,
+    audio: &AudioAnalysis,
+    within: Duration,
+) -> Duration {
+    let secs = time.as_secs_f32();
+    let downbeat = grid.downbeat.as_secs_f32();
+    let snapped = match to {
+        SnapTo::Seconds => secs.round(),
+        SnapTo::Frames => nearest(secs, 0.0, frame),
+        SnapTo::Beats => nearest(secs, downbeat, grid.beat_length()),
+        SnapTo::Bars => nearest(secs, downbeat, grid.beat_length() * beats_per_bar as f32),
+        SnapTo::Hits => return audio.nearest_transient(time, within).unwrap_or(time),
+    };
+    Duration::from_secs_f32(snapped.max(0.0))
+}
@@ -0,0 +1,145 @@
+use crate::cue::{self, Cue, CueNumber, CueValue};
+use crate::dmx::{DmxFrame, UNIVERSE_SIZE};
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::macros::{Macro, MacroAction, MacroStep};
+use crate::show::Pad;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A captured copy of the output, kept so it can be compared against a later
+/// capture (e.g. tonight's cue 10 against the reference from programming).
+/// Snapshots are saved with the show, so a reference taken while programming
+/// is still there to check against on the night.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub name: String,
+    pub captured_at: chrono::DateTime<chrono::Local>,
+    pub frame: DmxFrame,
+}
+
+impl Snapshot {
+    pub fn capture(name: &str, frame: &DmxFrame) -> Self {
+        Self {
+            name: name.to_string(),
+            captured_at: chrono::Local::now(),
+            frame: frame.clone(),
+        }
+    }
+}
+
+pub struct ChannelDiff {
+    pub universe: u16,
+    pub channel: u16,
+    pub before: u8,
+    pub after: u8,
+    /// The fixture and attribute currently patched at this address, if any.
+    /// Unpatched channels that differ usually point at patch drift or a park.
+    pub patched: Option<(String, Attribute)>,
+}
+
+/// Compares two frames channel-by-channel across the union of their universes.
+pub fn diff(before: &DmxFrame, after: &DmxFrame, fixtures: &[Fixture]) -> Vec<ChannelDiff> {
+    let mut universes: Vec<u16> = before
+        .universe_numbers()
+        .chain(after.universe_numbers())
+        .collect();
+    universes.sort_unstable();
+    universes.dedup();
+
+    let mut diffs = Vec::new();
+    for universe in universes {
+        for channel in 1..=UNIVERSE_SIZE as u16 {
+            let a = before.get(universe, channel);
+            let b = after.get(universe, channel);
+            if a == b {
+                continue;
+            }
+            let patched = fixtures.iter().find_map(|fixture| {
+                fixture
+                    .attribute_at(universe, channel)
+                    .map(|attribute| (fixture.name.clone(), attribute))
+            });
+            diffs.push(ChannelDiff {
+                universe,
+                channel,
+                before: a,
+                after: b,
+                patched,
+            });
+        }
+    }
+    diffs
+}
+
+/// Every patched attribute's level in `frame`, as cue values.
+pub fn look(frame: &DmxFrame, fixtures: &[Fixture]) -> Vec<CueValue> {
+    fixtures
+        .iter()
+        .flat_map(|fixture| {
+            fixture.profile.channels.iter().filter_map(|&attribute| {
+                let channel = fixture.channel_for(attribute)?;
+                Some(CueValue {
+                    fixture_id: fixture.id,
+                    attribute,
+                    value: frame.get(fixture.universe, channel),
+                })
+            })
+        })
+        .collect()
+}
+
+impl HaloApp {
+    /// Records everything on stage, busk and effects included, as a new cue
+    /// at the playhead, placed after the last cue starting before it. With
+    /// the preference on, it also gets a pad that fires it.
+    pub(crate) fn record_look(&mut self) {
+        let time = chrono::Local::now();
+        let name = tr!("look-name", label = time.format("%H:%M:%S").to_string());
+        let mut recorded = Cue::new(CueNumber::FIRST, &name, 0, 0);
+        recorded.start_time = self.elapsed;
+        recorded.duration = Duration::from_secs(1);
+        recorded.values = look(&self.programmed_output, &self.show.fixtures);
+        let after = self
+            .show
+            .cues
+            .iter()
+            .rposition(|cue| self.show.cue_start(cue) <= self.elapsed);
+        let Some(pasted) = cue::paste_cues(&mut self.show.cues, after, &[recorded], 0.0) else {
+            self.warn_no_room(1);
+            return;
+        };
+        let index = pasted.start;
+        if let Some(song) = after.and_then(|i| self.show.cues[i].song) {
+            self.show.set_cue_song(index, Some(song));
+        }
+        let number = self.show.cues[index].number;
+        self.selected_cue = Some(index);
+
+        if self.preferences.look_pads {
+            let label = tr!("look-name", label = number.to_string());
+            self.show.macros.retain(|recorded| recorded.name != label);
+            self.show.macros.push(Macro {
+                name: label.clone(),
+                steps: vec![MacroStep {
+                    offset: Duration::ZERO,
+                    action: MacroAction::FireCue(number),
+                }],
+            });
+            let mut pad = Pad::new(&label);
+            pad.run_macro = Some(label);
+            self.show.pads.push(pad);
+        }
+        self.notify(
+            LogLevel::Info,
+            tr!(
+                "look-recorded",
+                name = name.as_str(),
+                number = number.to_string(),
+                time = Self::format_duration(self.elapsed)
+            ),
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use crate::end_action::EndAction;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// A song (or any other block of a set) on the show timeline. Cues that
+/// belong to a song are timed from the song's start, so moving or reordering
+/// songs retimes their cues with them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Song {
+    /// Stable id that cues refer to, unaffected by reordering.
+    pub id: u32,
+    pub name: String,
+    pub start_time: Duration,
+    pub length: Duration,
+    /// What happens when setlist playback reaches the end of the song.
+    #[serde(default, skip_serializing_if = "EndAction::is_continue")]
+    pub end_action: EndAction,
+    /// The song's own audio, played from its start in place of the show's
+    /// audio file; see `playback`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_file: Option<PathBuf>,
+    /// Gain trim for the song's audio, in dB.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub audio_trim: f32,
+}
+
+fn is_zero(trim: &f32) -> bool {
+    *trim == 0.0
+}
+
+impl Song {
+    pub fn new(id: u32, name: &str, start_time: Duration, length: Duration) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            start_time,
+            length,
+            end_action: EndAction::Continue,
+            audio_file: None,
+            audio_trim: 0.0,
+        }
+    }
+
+    pub fn end(&self) -> Duration {
+        self.start_time + self.length
+    }
+}
+
+/// An id not used by any song in `songs`.
+pub fn next_id(songs: &[Song]) -> u32 {
+    songs.iter().map(|song| song.id + 1).max().unwrap_or(1)
+}
+
+/// Moves the song at `from` to `to` and lays the set out again.
+pub fn move_song(songs: &mut Vec<Song>, from: usize, to: usize) {
+    if from >= songs.len() || to >= songs.len() || from == to {
+        return;
+    }
+    let song = songs.remove(from);
+    songs.insert(to, song);
+    pack(songs);
+}
+
+/// Lays the songs out back to back in list order, starting where the
+/// earliest one started.
+pub fn pack(songs: &mut [Song]) {
+    let Some(mut start) = songs.iter().map(|song| song.start_time).min() else {
+        return;
+    };
+    for song in songs {
+        song.start_time = start;
+        start += song.length;
+    }
+}
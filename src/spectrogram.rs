@@ -0,0 +1,204 @@
+//! Analysis of the show's audio file for placing cues by eye: a waveform, a
+//! spectrogram and the transients (drum hits and other sharp onsets) that
+//! cue start times snap to. The file is decoded by an `ffmpeg` binary on the
+//! `PATH`, as pixel map video is; see `media`.
+
+use crate::audio;
+use std::f32::consts::TAU;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Sample rate the audio is decoded at; plenty for finding hits.
+const SAMPLE_RATE: u32 = 22_050;
+/// Samples per analysis window, a power of two for the FFT.
+const FFT_SIZE: usize = 1024;
+/// Samples between columns, about 23ms.
+const HOP: usize = FFT_SIZE / 2;
+/// Rows in the spectrogram, log-spaced from 40 Hz to 11 kHz.
+pub const BANDS: usize = 48;
+/// Columns either side averaged for the transient threshold.
+const THRESHOLD_SPAN: usize = 8;
+/// How far above the local average a column's onset strength has to be.
+const THRESHOLD_SCALE: f32 = 1.5;
+/// Closest two transients can be.
+const MIN_GAP: Duration = Duration::from_millis(80);
+
+/// One slice of the audio, [`HOP`] samples long.
+pub struct Column {
+    /// Loudest sample in the slice, from 0 to 1.
+    pub peak: f32,
+    /// Level of each band from low to high, on the dB scale the audio meter
+    /// uses.
+    pub bands: [u8; BANDS],
+}
+
+pub struct AudioAnalysis {
+    pub columns: Vec<Column>,
+    /// Onsets in the audio, in time order.
+    pub transients: Vec<Duration>,
+}
+
+impl AudioAnalysis {
+    pub fn analyse(path: &Path) -> io::Result<Self> {
+        let mut child = Command::new("ffmpeg")
+            .args(["-v", "error", "-i"])
+            .arg(path)
+            .args(["-vn", "-ac", "1", "-ar", &SAMPLE_RATE.to_string()])
+            .args(["-f", "f32le", "-"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| io::Error::new(err.kind(), format!("could not run ffmpeg: {}", err)))?;
+
+        let mut columns = Vec::new();
+        let mut onsets = Vec::new();
+        let mut analyser = Analyser::new();
+        let mut stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let mut sample = [0u8; 4];
+        while stdout.read_exact(&mut sample).is_ok() {
+            if let Some((column, onset)) = analyser.push(f32::from_le_bytes(sample)) {
+                columns.push(column);
+                onsets.push(onset);
+            }
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!(
+                "ffmpeg failed: {}",
+                message.trim()
+            )));
+        }
+        if columns.is_empty() {
+            return Err(io::Error::other("no audio decoded"));
+        }
+        Ok(Self {
+            transients: transients(&onsets),
+            columns,
+        })
+    }
+
+    /// How long each column covers.
+    pub fn column_length() -> Duration {
+        Duration::from_secs_f64(HOP as f64 / SAMPLE_RATE as f64)
+    }
+
+    pub fn length(&self) -> Duration {
+        Self::column_length() * self.columns.len() as u32
+    }
+
+    /// The column playing at `time`, if the audio runs that long.
+    pub fn column_at(&self, time: Duration) -> Option<&Column> {
+        let index = time.as_secs_f64() / Self::column_length().as_secs_f64();
+        self.columns.get(index as usize)
+    }
+
+    /// The transient closest to `time`, if one is `within` of it.
+    pub fn nearest_transient(&self, time: Duration, within: Duration) -> Option<Duration> {
+        let after = self.transients.partition_point(|&t| t < time);
+        let before = after.checked_sub(1).map(|i| self.transients[i]);
+        [before, self.transients.get(after).copied()]
+            .into_iter()
+            .flatten()
+            .min_by_key(|&t| t.abs_diff(time))
+            .filter(|&t| t.abs_diff(time) <= within)
+    }
+}
+
+/// Picks the peaks in onset strength that stand out from their
+/// surroundings.
+fn transients(onsets: &[f32]) -> Vec<Duration> {
+    let column = AudioAnalysis::column_length();
+    let mut found: Vec<Duration> = Vec::new();
+    for (i, &onset) in onsets.iter().enumerate() {
+        let near =
+            &onsets[i.saturating_sub(THRESHOLD_SPAN)..(i + THRESHOLD_SPAN + 1).min(onsets.len())];
+        let mean = near.iter().sum::<f32>() / near.len() as f32;
+        let is_peak = near.iter().all(|&other| other <= onset);
+        if !is_peak || onset <= mean * THRESHOLD_SCALE + 0.02 {
+            continue;
+        }
+        let time = column * i as u32;
+        if found.last().is_none_or(|&last| time - last >= MIN_GAP) {
+            found.push(time);
+        }
+    }
+    found
+}
+
+/// Turns samples into columns, one per [`HOP`].
+struct Analyser {
+    window: Vec<f32>,
+    samples: Vec<f32>,
+    /// Where each band starts in the FFT bins, with the end of the last.
+    edges: Vec<usize>,
+    previous: [f32; BANDS],
+}
+
+impl Analyser {
+    fn new() -> Self {
+        let window = (0..FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (TAU * i as f32 / FFT_SIZE as f32).cos())
+            .collect();
+        let bin_hz = SAMPLE_RATE as f32 / FFT_SIZE as f32;
+        let (low, high) = (40.0_f32, 11_000.0_f32);
+        let edges = (0..=BANDS)
+            .map(|i| {
+                let hz = low * (high / low).powf(i as f32 / BANDS as f32);
+                ((hz / bin_hz) as usize).clamp(1, FFT_SIZE / 2)
+            })
+            .collect();
+        Self {
+            window,
+            samples: Vec::with_capacity(FFT_SIZE),
+            edges,
+            previous: [0.0; BANDS],
+        }
+    }
+
+    /// Adds a sample, returning a column and its onset strength each time a
+    /// hop's worth has come in.
+    fn push(&mut self, sample: f32) -> Option<(Column, f32)> {
+        self.samples.push(sample);
+        if self.samples.len() < FFT_SIZE {
+            return None;
+        }
+        let mut re: Vec<f32> = self
+            .samples
+            .iter()
+            .zip(&self.window)
+            .map(|(s, w)| s * w)
+            .collect();
+        let mut im = vec![0.0; FFT_SIZE];
+        audio::fft(&mut re, &mut im);
+
+        let scale = 2.0 / self.window.iter().sum::<f32>();
+        let mut column = Column {
+            peak: self.samples[HOP..]
+                .iter()
+                .fold(0.0_f32, |peak, s| peak.max(s.abs())),
+            bands: [0; BANDS],
+        };
+        let mut onset = 0.0;
+        for band in 0..BANDS {
+            let (first, last) = (
+                self.edges[band],
+                self.edges[band + 1].max(self.edges[band] + 1),
+            );
+            let power: f32 = (first..last)
+                .map(|k| (re[k] * re[k] + im[k] * im[k]) * scale * scale)
+                .sum();
+            let level = audio::normalize(power.sqrt());
+            column.bands[band] = (level * 255.0) as u8;
+            // Spectral flux: how much louder the bands got since the last
+            // column, which jumps at a hit.
+            onset += (level - self.previous[band]).max(0.0);
+            self.previous[band] = level;
+        }
+        self.samples.drain(..HOP);
+        Some((column, onset / BANDS as f32))
+    }
+}
@@ -0,0 +1,116 @@
+//! Speed masters: faders that scale how fast the effects assigned to them
+//! run, so a group of chases can be slowed right down for a quiet moment
+//! without reprogramming them. Each master drives its own effect clock, which
+//! the output engine advances by the master's rate every frame, so moving
+//! the fader changes speed without making the effects jump.
+
+use crate::crossfade::FaderControl;
+use crate::dmx_fader::DmxFader;
+use crate::i18n::tr;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+
+/// The fastest a speed master can run its effects.
+pub const MAX_RATE: f32 = 2.0;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeedMaster {
+    pub name: String,
+    /// 1 runs the effects as programmed, 0 holds them still and 2 doubles
+    /// their speed.
+    pub rate: f32,
+    /// The MIDI controller that moves the fader.
+    pub control: Option<FaderControl>,
+    /// The DMX wing channel that moves the fader.
+    pub fader: Option<DmxFader>,
+}
+
+impl Default for SpeedMaster {
+    fn default() -> Self {
+        Self {
+            name: "Speed".to_string(),
+            rate: 1.0,
+            control: None,
+            fader: None,
+        }
+    }
+}
+
+/// Effect time for each speed master, in seconds.
+#[derive(Default)]
+pub struct EffectClocks {
+    times: Vec<f32>,
+}
+
+impl EffectClocks {
+    /// Moves every master's clock on by `elapsed` seconds at its rate. New
+    /// masters start level with `time`, the free-running effect time.
+    pub fn advance(&mut self, rates: &[f32], elapsed: f32, time: f32) {
+        self.times.resize(rates.len(), time);
+        for (clock, rate) in self.times.iter_mut().zip(rates) {
+            *clock += elapsed * rate;
+        }
+    }
+
+    /// The effect time for an effect on `master`, or `time` for one on no
+    /// master.
+    pub fn time(&self, master: Option<usize>, time: f32) -> f32 {
+        master
+            .and_then(|index| self.times.get(index))
+            .copied()
+            .unwrap_or(time)
+    }
+}
+
+impl HaloApp {
+    /// Sets speed masters from a MIDI controller, or assigns the controller
+    /// to the master being learned.
+    pub(crate) fn receive_speed_controller(&mut self, channel: u8, controller: u8, value: u8) {
+        if let Some(index) = self.learn_speed_master.take() {
+            if let Some(master) = self.show.speed_masters.get_mut(index) {
+                master.control = Some(FaderControl {
+                    channel,
+                    controller,
+                });
+                self.log.info(tr!(
+                    "learned-cc",
+                    controller = controller,
+                    channel = channel,
+                    name = master.name.as_str()
+                ));
+            }
+            return;
+        }
+        // The middle of the fader's travel is normal speed.
+        let rate = (value as f32 / 64.0).min(MAX_RATE);
+        for master in &mut self.show.speed_masters {
+            if master
+                .control
+                .is_some_and(|control| control.matches(channel, controller))
+            {
+                master.rate = rate;
+            }
+        }
+    }
+
+    /// Drops the speed master at `index`, leaving its effects on none.
+    pub(crate) fn remove_speed_master(&mut self, index: usize) {
+        if index >= self.show.speed_masters.len() {
+            return;
+        }
+        self.show.speed_masters.remove(index);
+        let reassign = |master: &mut Option<usize>| {
+            *master = match *master {
+                Some(i) if i == index => None,
+                Some(i) if i > index => Some(i - 1),
+                other => other,
+            };
+        };
+        for map in &mut self.show.pixel_maps {
+            reassign(&mut map.speed_master);
+        }
+        reassign(&mut self.show.busk.speed_master);
+        self.learn_speed_master = None;
+    }
+}
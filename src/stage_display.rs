@@ -0,0 +1,65 @@
+//! The stage display: a window of its own showing only the timecode, the
+//! next cue and a countdown to it, in type big enough to read across a
+//! stage, for a stage manager's monitor or a drummer's. It belongs to the
+//! machine rather than the show, so its settings live in the preferences
+//! along with where the window was last left, which puts it back on the same
+//! monitor next time.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StageDisplaySettings {
+    /// Open the display, and reopen it at startup.
+    pub open: bool,
+    pub show_timecode: bool,
+    pub show_next_cue: bool,
+    pub show_countdown: bool,
+    pub text: [u8; 3],
+    pub background: [u8; 3],
+    /// The background while the next cue is in standby.
+    pub standby: [u8; 3],
+    /// Flash between the standby colour and the background rather than
+    /// holding it.
+    pub flash_on_standby: bool,
+    /// How long before the next cue it goes into standby.
+    pub standby_time: Duration,
+    /// Fill the monitor the window is on.
+    pub fullscreen: bool,
+    /// The window's top-left corner in screen points where it was last
+    /// left, which picks the monitor.
+    pub position: Option<[f32; 2]>,
+}
+
+impl Default for StageDisplaySettings {
+    fn default() -> Self {
+        Self {
+            open: false,
+            show_timecode: true,
+            show_next_cue: true,
+            show_countdown: true,
+            text: [255, 255, 255],
+            background: [0, 0, 0],
+            standby: [160, 0, 0],
+            flash_on_standby: true,
+            standby_time: Duration::from_secs(10),
+            fullscreen: false,
+            position: None,
+        }
+    }
+}
+
+impl StageDisplaySettings {
+    /// Whether a cue `remaining` away is in standby.
+    pub fn is_standby(&self, remaining: Duration) -> bool {
+        !self.standby_time.is_zero() && remaining <= self.standby_time
+    }
+}
+
+/// A countdown as the display shows it: minutes and seconds, rounded up so
+/// it reads 0:00 as the cue goes.
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    format!("-{}:{:02}", secs / 60, secs % 60)
+}
@@ -0,0 +1,147 @@
+//! What Halo does as it starts, for installations that have to come back by
+//! themselves after a power cut: open the show that was open, switch the
+//! output on, connect MIDI and start playing, with nobody at the computer.
+
+use crate::error::HaloError;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::midi;
+use crate::timecode;
+use crate::HaloApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupSettings {
+    /// Open the show that was last opened or saved.
+    pub load_last_show: bool,
+    pub last_show: Option<PathBuf>,
+    /// Switch the Art-Net, sACN and plugin outputs on, even if they were
+    /// left off.
+    pub enable_output: bool,
+    /// Take the first MIDI ports there are when none are chosen.
+    pub connect_midi: bool,
+    pub start_playback: bool,
+    /// The timecode to start playing from; anything before the show's start
+    /// plays from the top.
+    pub start_at: Duration,
+    /// How long to wait before starting, for the network and devices to
+    /// come up after the computer does.
+    pub start_delay: Duration,
+}
+
+impl Default for StartupSettings {
+    fn default() -> Self {
+        Self {
+            load_last_show: false,
+            last_show: None,
+            enable_output: false,
+            connect_midi: false,
+            start_playback: false,
+            start_at: Duration::ZERO,
+            start_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl HaloApp {
+    /// Opens the last show and switches the output on, as the startup
+    /// preferences say. Playback starts later, from [`Self::update_startup`].
+    pub(crate) fn apply_startup_preferences(&mut self) {
+        let startup = self.preferences.startup.clone();
+        if startup.load_last_show {
+            if let Some(path) = startup.last_show {
+                match self.open_show(&path) {
+                    Ok(()) => {
+                        self.log
+                            .info(tr!("startup-opened", path = path.display().to_string()));
+                        self.set_show_path(path);
+                    }
+                    Err(err) => self.report_error(HaloError::show_file(path, err)),
+                }
+            }
+        }
+        if startup.enable_output {
+            self.preferences.art_net_output.enabled = true;
+            self.preferences.sacn_output.enabled = true;
+            for output in &mut self.preferences.plugin_outputs {
+                output.enabled = true;
+            }
+        }
+        if startup.start_playback {
+            self.startup_playback = Some(Instant::now() + startup.start_delay);
+        }
+    }
+
+    /// Chooses the first MIDI ports there are for whichever of input and
+    /// output has none chosen. A chosen port that isn't plugged in yet is
+    /// connected when it is, like any other.
+    pub(crate) fn connect_startup_midi(&mut self) {
+        if !self.preferences.startup.connect_midi {
+            return;
+        }
+        let mut chosen = false;
+        if self.preferences.midi_input.is_none() {
+            if let Some(port) = midi::input_ports().into_iter().next() {
+                self.log
+                    .info(tr!("startup-midi-input", port = port.as_str()));
+                self.preferences.midi_input = Some(port);
+                chosen = true;
+            }
+        }
+        if self.preferences.midi_output.is_none() {
+            if let Some(port) = midi::output_ports().into_iter().next() {
+                self.log
+                    .info(tr!("startup-midi-output", port = port.as_str()));
+                self.preferences.midi_output = Some(port);
+                chosen = true;
+            }
+        }
+        if chosen {
+            let _ = self.preferences.save();
+        }
+    }
+
+    /// Starts playback once the startup delay is up.
+    pub(crate) fn update_startup(&mut self, ctx: &egui::Context) {
+        let Some(at) = self.startup_playback else {
+            return;
+        };
+        let now = Instant::now();
+        if now < at {
+            ctx.request_repaint_after(at - now);
+            return;
+        }
+        self.startup_playback = None;
+        let position = self
+            .preferences
+            .startup
+            .start_at
+            .saturating_sub(self.show.timecode.start);
+        self.locate(position);
+        self.start_playback();
+        self.notify(
+            LogLevel::Info,
+            tr!(
+                "startup-playing",
+                time = timecode::format(self.show.timecode.start + position)
+            ),
+        );
+    }
+
+    /// Notes the show file now open, for opening again at the next startup.
+    /// A show that failed to open, or a new one not yet saved, leaves the
+    /// last one noted.
+    pub(crate) fn remember_show_path(&mut self) {
+        let Some(path) = &self.show_path else {
+            return;
+        };
+        if self.preferences.startup.last_show.as_ref() != Some(path) {
+            self.preferences.startup.last_show = Some(path.clone());
+            let _ = self.preferences.save();
+        }
+    }
+}
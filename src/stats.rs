@@ -0,0 +1,166 @@
+//! Rolling frame-time measurement for the UI and the DMX output, and the
+//! time each stage of an output frame takes.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Frames kept for the average and the frame-time graph.
+pub const HISTORY: usize = 240;
+
+/// A longer gap between frames means nothing was being drawn or sent, for
+/// example while the window was idle, rather than frames being dropped.
+const IDLE_GAP: Duration = Duration::from_secs(1);
+
+/// How long a drop keeps the status bar warning lit.
+const DROP_WARNING: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub struct FrameTimer {
+    /// The intended frame period, if frames should arrive at a fixed rate.
+    target: Option<Duration>,
+    last: Option<Instant>,
+    frame_times: VecDeque<Duration>,
+    dropped: u64,
+    last_drop: Option<Instant>,
+}
+
+impl FrameTimer {
+    pub fn new(target_hz: Option<f32>) -> Self {
+        Self {
+            target: target_hz.map(|hz| Duration::from_secs_f32(1.0 / hz)),
+            last: None,
+            frame_times: VecDeque::with_capacity(HISTORY),
+            dropped: 0,
+            last_drop: None,
+        }
+    }
+
+    /// Records a frame at `now`, counting the frames that should have come
+    /// in between if it is late.
+    pub fn tick(&mut self, now: Instant) {
+        let Some(last) = self.last.replace(now) else {
+            return;
+        };
+        let frame_time = now - last;
+        if frame_time > IDLE_GAP {
+            return;
+        }
+        if let Some(target) = self.target {
+            // Allow half a period of jitter before calling a frame missed.
+            let missed = ((frame_time.as_secs_f32() / target.as_secs_f32()) - 0.5).floor() as u64;
+            if missed > 0 {
+                self.dropped += missed;
+                self.last_drop = Some(now);
+            }
+        }
+        if self.frame_times.len() == HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+    }
+
+    pub fn target(&self) -> Option<Duration> {
+        self.target
+    }
+
+    pub fn frame_times(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.frame_times.iter().copied()
+    }
+
+    /// Average frames per second over the history.
+    pub fn fps(&self) -> f32 {
+        let total: Duration = self.frame_times.iter().sum();
+        if total.is_zero() {
+            return 0.0;
+        }
+        self.frame_times.len() as f32 / total.as_secs_f32()
+    }
+
+    pub fn worst_frame_time(&self) -> Duration {
+        self.frame_times.iter().max().copied().unwrap_or_default()
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Whether frames have been dropped recently enough to warn about.
+    pub fn is_dropping(&self) -> bool {
+        self.last_drop
+            .is_some_and(|last| last.elapsed() < DROP_WARNING)
+    }
+}
+
+/// Times the stages of a frame one after another, each lap ending one.
+pub struct StageClock {
+    lap_start: Instant,
+    stages: Vec<(&'static str, Duration)>,
+}
+
+impl StageClock {
+    pub fn start() -> Self {
+        Self {
+            lap_start: Instant::now(),
+            stages: Vec::new(),
+        }
+    }
+
+    /// Ends the stage called `name` and starts the next.
+    pub fn lap(&mut self, name: &'static str) {
+        let now = Instant::now();
+        self.stages.push((name, now - self.lap_start));
+        self.lap_start = now;
+    }
+
+    pub fn finish(self) -> Vec<(&'static str, Duration)> {
+        self.stages
+    }
+}
+
+/// How long one stage of the output frame takes.
+#[derive(Clone)]
+pub struct StageTime {
+    pub name: &'static str,
+    pub last: Duration,
+    /// Over the last [`HISTORY`] frames.
+    pub average: Duration,
+    pub worst: Duration,
+}
+
+/// Recent times of each stage of the output frame, in the order they run.
+#[derive(Default)]
+pub struct StageTimes {
+    history: Vec<(&'static str, VecDeque<Duration>)>,
+}
+
+impl StageTimes {
+    pub fn record(&mut self, stages: &[(&'static str, Duration)]) {
+        for &(name, time) in stages {
+            let index = match self.history.iter().position(|(n, _)| *n == name) {
+                Some(index) => index,
+                None => {
+                    self.history.push((name, VecDeque::with_capacity(HISTORY)));
+                    self.history.len() - 1
+                }
+            };
+            let history = &mut self.history[index].1;
+            if history.len() == HISTORY {
+                history.pop_front();
+            }
+            history.push_back(time);
+        }
+    }
+
+    pub fn summary(&self) -> Vec<StageTime> {
+        self.history
+            .iter()
+            .filter(|(_, times)| !times.is_empty())
+            .map(|(name, times)| StageTime {
+                name,
+                last: times.back().copied().unwrap_or_default(),
+                average: times.iter().sum::<Duration>() / times.len() as u32,
+                worst: times.iter().max().copied().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,165 @@
+//! Turning a recording of another console's output into cues. The
+//! recording is read through for the looks that hold still, and each becomes
+//! a cue starting when the change into it began, fading for as long as the
+//! change took. Only channels in Halo's patch count, so the patch should
+//! match the other console's first.
+
+use crate::cue::{Cue, CueNumber, CueValue};
+use crate::fixture::Fixture;
+use crate::i18n::tr;
+use crate::recording::Reader;
+use crate::snapshot;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Cues need some fade to play; a snap gets one frame.
+const SNAP: Duration = Duration::from_millis(25);
+
+#[derive(Clone, Copy)]
+pub struct ImportSettings {
+    /// How long the look has to stay put to count as a cue rather than a
+    /// step in a fade or an effect.
+    pub hold: Duration,
+    /// Level changes up to this much are noise, not a change of look.
+    pub tolerance: u8,
+    /// Cues only store what changed from the cue before, leaving the rest
+    /// to track through.
+    pub changes_only: bool,
+}
+
+impl Default for ImportSettings {
+    fn default() -> Self {
+        Self {
+            hold: Duration::from_secs(1),
+            tolerance: 2,
+            changes_only: true,
+        }
+    }
+}
+
+/// A look that held still in the recording.
+pub struct Section {
+    /// When the change into the look began.
+    pub start: Duration,
+    /// How long the change took; zero for a snap.
+    pub fade: Duration,
+    pub look: Vec<CueValue>,
+}
+
+/// Whether any level in `a` is more than `tolerance` from the same one in
+/// `b`. Looks of the same patch line up value for value.
+fn moved(a: &[CueValue], b: &[CueValue], tolerance: u8) -> bool {
+    a.iter()
+        .zip(b)
+        .any(|(a, b)| a.value.abs_diff(b.value) > tolerance)
+}
+
+/// The looks in the recording at `path` that held for at least
+/// `settings.hold`, in order. The first is the look the recording starts
+/// in. A recording whose frames go back in time is refused.
+pub fn analyze(
+    path: &Path,
+    fixtures: &[Fixture],
+    settings: &ImportSettings,
+) -> io::Result<Vec<Section>> {
+    let mut reader = Reader::open(path)?;
+    let mut sections = Vec::new();
+    // The look at the last change, and when that was.
+    let mut settled: Option<(Vec<CueValue>, Duration)> = None;
+    // When the change towards the next look began, until it holds.
+    let mut changing = Some(Duration::ZERO);
+    let mut previous = Duration::ZERO;
+    while let Some((at, frame)) = reader.next_frame()? {
+        if at < previous {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                tr!("recording-backwards", at = at.as_millis() as u64),
+            ));
+        }
+        previous = at;
+        let look = snapshot::look(frame, fixtures);
+        match &settled {
+            Some((last, _)) if !moved(last, &look, settings.tolerance) => {}
+            _ => {
+                changing.get_or_insert(at);
+                settled = Some((look, at));
+                continue;
+            }
+        }
+        if let (Some(start), Some((look, changed))) = (changing, &settled) {
+            if at - *changed >= settings.hold {
+                sections.push(Section {
+                    start,
+                    fade: *changed - start,
+                    look: look.clone(),
+                });
+                changing = None;
+            }
+        }
+    }
+    // The recording stopped in a look before it had held long enough.
+    if let (Some(start), Some((look, changed))) = (changing, settled) {
+        sections.push(Section {
+            start,
+            fade: changed - start,
+            look,
+        });
+    }
+    Ok(sections)
+}
+
+/// Cues playing `sections`, starting `offset` into the show. They are all
+/// numbered 1 until pasted into the cue list.
+pub fn cues(sections: &[Section], settings: &ImportSettings, offset: Duration) -> Vec<Cue> {
+    let mut previous: Option<&[CueValue]> = None;
+    sections
+        .iter()
+        .enumerate()
+        .map(|(i, section)| {
+            let mut cue = Cue::new(
+                CueNumber::FIRST,
+                &tr!("recording-cue-name", number = i + 1),
+                0,
+                0,
+            );
+            cue.start_time = offset + section.start;
+            cue.duration = section.fade.max(SNAP);
+            cue.values = match previous.filter(|_| settings.changes_only) {
+                Some(before) => section
+                    .look
+                    .iter()
+                    .zip(before)
+                    .filter(|(now, before)| now.value != before.value)
+                    .map(|(now, _)| *now)
+                    .collect(),
+                None => section.look.clone(),
+            };
+            previous = Some(&section.look);
+            cue
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn frames_going_back_in_time_are_refused() {
+        let mut file = b"HALODMX1".to_vec();
+        for millis in [0u32, 100, 50] {
+            file.extend_from_slice(&millis.to_le_bytes());
+            // No universes changed.
+            file.extend_from_slice(&0u16.to_le_bytes());
+        }
+        let path =
+            std::env::temp_dir().join(format!("halo-backwards-{}.halodmx", std::process::id()));
+        fs::write(&path, file).unwrap();
+        let result = analyze(&path, &[], &ImportSettings::default());
+        fs::remove_file(&path).unwrap();
+        let err = result.err().expect("a backwards recording to be refused");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,96 @@
+//! Colour and icon tags on cues and pads, so the operator can pick out, say,
+//! the pyro-adjacent cues or the ones synced to video in a dense show at a
+//! glance.
+
+use crate::i18n::tr;
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TagColor {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Cyan,
+    Blue,
+    Purple,
+    Pink,
+}
+
+impl TagColor {
+    pub const ALL: [TagColor; 8] = [
+        TagColor::Red,
+        TagColor::Orange,
+        TagColor::Yellow,
+        TagColor::Green,
+        TagColor::Cyan,
+        TagColor::Blue,
+        TagColor::Purple,
+        TagColor::Pink,
+    ];
+
+    pub fn color32(self) -> Color32 {
+        match self {
+            TagColor::Red => Color32::from_rgb(220, 50, 50),
+            TagColor::Orange => Color32::from_rgb(235, 140, 30),
+            TagColor::Yellow => Color32::from_rgb(230, 210, 40),
+            TagColor::Green => Color32::from_rgb(60, 180, 75),
+            TagColor::Cyan => Color32::from_rgb(40, 190, 200),
+            TagColor::Blue => Color32::from_rgb(50, 110, 230),
+            TagColor::Purple => Color32::from_rgb(150, 80, 210),
+            TagColor::Pink => Color32::from_rgb(230, 100, 170),
+        }
+    }
+}
+
+impl fmt::Display for TagColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            TagColor::Red => tr!("colour-red"),
+            TagColor::Orange => tr!("colour-orange"),
+            TagColor::Yellow => tr!("colour-yellow"),
+            TagColor::Green => tr!("colour-green"),
+            TagColor::Cyan => tr!("colour-cyan"),
+            TagColor::Blue => tr!("colour-blue"),
+            TagColor::Purple => tr!("colour-purple"),
+            TagColor::Pink => tr!("colour-pink"),
+        })
+    }
+}
+
+/// Icons a tag can carry, with what they usually flag.
+pub const ICONS: [(&str, &str); 8] = [
+    ("🔥", "tag-icon-pyro"),
+    ("🎥", "tag-icon-video"),
+    ("🔊", "tag-icon-sound"),
+    ("💨", "tag-icon-haze"),
+    ("⚡", "tag-icon-strobe"),
+    ("⚠", "tag-icon-warning"),
+    ("⭐", "tag-icon-key-moment"),
+    ("🎤", "tag-icon-talent"),
+];
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tag {
+    pub color: Option<TagColor>,
+    /// One of [`ICONS`], or empty for none.
+    pub icon: String,
+}
+
+impl Tag {
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.icon.is_empty()
+    }
+
+    /// The icon followed by a space, ready to put in front of a label.
+    pub fn prefix(&self) -> String {
+        if self.icon.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", self.icon)
+        }
+    }
+}
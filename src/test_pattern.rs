@@ -0,0 +1,104 @@
+//! Commissioning tools for the output: built-in test patterns that replace
+//! one universe's levels, for proving a node or cable run end to end, and
+//! muting universes so they stop going out at all while something
+//! downstream is being worked on.
+
+use crate::dmx::{DmxFrame, UNIVERSE_SIZE};
+use crate::i18n::tr;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Seconds each channel stays lit in the chase.
+const CHASE_STEP: f32 = 0.25;
+/// Seconds between the alternating pattern swapping over.
+const ALTERNATE_STEP: f32 = 1.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Pattern {
+    /// Every channel at full.
+    Full,
+    /// Channel 1 at zero rising to full at channel 512.
+    Ramp,
+    /// Odd and even channels at full in turn, swapping every second.
+    Alternating,
+    /// One channel at full at a time, walking up the universe.
+    Chase,
+}
+
+impl Pattern {
+    pub const ALL: [Pattern; 4] = [
+        Pattern::Full,
+        Pattern::Ramp,
+        Pattern::Alternating,
+        Pattern::Chase,
+    ];
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            Pattern::Full => tr!("pattern-full"),
+            Pattern::Ramp => tr!("pattern-ramp"),
+            Pattern::Alternating => tr!("pattern-alternating"),
+            Pattern::Chase => tr!("pattern-chase"),
+        })
+    }
+}
+
+/// A test pattern on one universe.
+#[derive(Clone, Copy, PartialEq)]
+pub struct TestPattern {
+    pub universe: u16,
+    pub pattern: Pattern,
+}
+
+impl TestPattern {
+    /// The level of a 1-based channel `seconds` into the pattern.
+    fn level(&self, channel: u16, seconds: f32) -> u8 {
+        let index = channel as usize - 1;
+        match self.pattern {
+            Pattern::Full => 255,
+            Pattern::Ramp => (index * 255 / (UNIVERSE_SIZE - 1)) as u8,
+            Pattern::Alternating => {
+                let swapped = (seconds / ALTERNATE_STEP) as usize % 2;
+                if index % 2 == swapped {
+                    255
+                } else {
+                    0
+                }
+            }
+            Pattern::Chase => {
+                let lit = (seconds / CHASE_STEP) as usize % UNIVERSE_SIZE;
+                if index == lit {
+                    255
+                } else {
+                    0
+                }
+            }
+        }
+    }
+
+    /// Replaces the universe in `frame` with the pattern as it stands
+    /// `seconds` in.
+    pub fn apply(&self, frame: &mut DmxFrame, seconds: f32) {
+        let data = frame.universe_mut(self.universe);
+        for (index, level) in data.iter_mut().enumerate() {
+            *level = self.level(index as u16 + 1, seconds);
+        }
+    }
+}
+
+/// `frame` without the `muted` universes, for the outputs.
+pub fn unmuted<'a>(muted: &[u16], frame: Cow<'a, DmxFrame>) -> Cow<'a, DmxFrame> {
+    if !muted
+        .iter()
+        .any(|&universe| frame.universe(universe).is_some())
+    {
+        return frame;
+    }
+    let mut frame = frame.into_owned();
+    for &universe in muted {
+        frame.remove_universe(universe);
+    }
+    Cow::Owned(frame)
+}
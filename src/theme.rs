@@ -0,0 +1,112 @@
+//! Selectable UI themes and the colour of the LED-style readouts, so the
+//! screen can be matched to the lighting at FOH.
+
+use crate::i18n::tr;
+use eframe::egui::{self, Color32};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    Dark,
+    Light,
+    /// Pure black and white with heavy outlines, for bright or glaring rooms.
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Dark, Theme::Light, Theme::HighContrast];
+
+    pub fn visuals(self) -> egui::Visuals {
+        match self {
+            Theme::Dark => egui::Visuals::dark(),
+            Theme::Light => egui::Visuals::light(),
+            Theme::HighContrast => {
+                let mut visuals = egui::Visuals::dark();
+                visuals.override_text_color = Some(Color32::WHITE);
+                visuals.panel_fill = Color32::BLACK;
+                visuals.window_fill = Color32::BLACK;
+                visuals.extreme_bg_color = Color32::BLACK;
+                visuals.faint_bg_color = Color32::from_gray(24);
+                visuals.window_stroke = egui::Stroke::new(2.0, Color32::WHITE);
+                visuals.selection.bg_fill = Color32::YELLOW;
+                visuals.selection.stroke = egui::Stroke::new(2.0, Color32::BLACK);
+                visuals.hyperlink_color = Color32::YELLOW;
+                for widget in [
+                    &mut visuals.widgets.noninteractive,
+                    &mut visuals.widgets.inactive,
+                    &mut visuals.widgets.hovered,
+                    &mut visuals.widgets.active,
+                    &mut visuals.widgets.open,
+                ] {
+                    widget.bg_stroke = egui::Stroke::new(1.5, Color32::WHITE);
+                    widget.fg_stroke = egui::Stroke::new(2.0, Color32::WHITE);
+                }
+                visuals.widgets.inactive.weak_bg_fill = Color32::BLACK;
+                visuals.widgets.inactive.bg_fill = Color32::BLACK;
+                visuals.widgets.hovered.weak_bg_fill = Color32::from_gray(60);
+                visuals.widgets.active.weak_bg_fill = Color32::from_gray(90);
+                visuals
+            }
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            Theme::Dark => tr!("theme-dark"),
+            Theme::Light => tr!("theme-light"),
+            Theme::HighContrast => tr!("theme-high-contrast"),
+        })
+    }
+}
+
+/// Colour of the LED timecode and the beat indicator.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LedColor {
+    Green,
+    Amber,
+    Red,
+    White,
+}
+
+impl LedColor {
+    pub const ALL: [LedColor; 4] = [
+        LedColor::Green,
+        LedColor::Amber,
+        LedColor::Red,
+        LedColor::White,
+    ];
+
+    /// A lit segment.
+    pub fn lit(self) -> Color32 {
+        match self {
+            LedColor::Green => Color32::GREEN,
+            LedColor::Amber => Color32::from_rgb(255, 176, 0),
+            LedColor::Red => Color32::from_rgb(255, 40, 40),
+            LedColor::White => Color32::WHITE,
+        }
+    }
+
+    /// An unlit segment, dim enough to read as off against either theme.
+    pub fn unlit(self) -> Color32 {
+        match self {
+            LedColor::Green => Color32::DARK_GREEN,
+            LedColor::Amber => Color32::from_rgb(90, 60, 0),
+            LedColor::Red => Color32::from_rgb(90, 10, 10),
+            LedColor::White => Color32::from_gray(70),
+        }
+    }
+}
+
+impl fmt::Display for LedColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            LedColor::Green => tr!("colour-green"),
+            LedColor::Amber => tr!("colour-amber"),
+            LedColor::Red => tr!("colour-red"),
+            LedColor::White => tr!("colour-white"),
+        })
+    }
+}
@@ -0,0 +1,70 @@
+//! The show's time signature, which sets how many beats the beat indicator
+//! counts to a bar and how long bar-synced effects take to cycle.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeSignature {
+    /// Beats to the bar.
+    pub beats: u8,
+    /// The note value of a beat: 4 for crotchets, 8 for quavers.
+    pub unit: u8,
+}
+
+impl TimeSignature {
+    pub const PRESETS: [TimeSignature; 7] = [
+        TimeSignature::new(4, 4),
+        TimeSignature::new(3, 4),
+        TimeSignature::new(2, 4),
+        TimeSignature::new(5, 4),
+        TimeSignature::new(6, 8),
+        TimeSignature::new(7, 8),
+        TimeSignature::new(12, 8),
+    ];
+
+    pub const fn new(beats: u8, unit: u8) -> Self {
+        Self { beats, unit }
+    }
+
+    pub fn beats_per_bar(&self) -> u32 {
+        self.beats.max(1) as u32
+    }
+
+    /// The bar and beat of `beat`, counted from zero.
+    pub fn position(&self, beat: u32) -> BarBeat {
+        let (bar, beat) = self.bar_beat(beat);
+        BarBeat { bar, beat }
+    }
+
+    /// The 1-based bar and beat within it of `beat`, counted from zero.
+    pub fn bar_beat(&self, beat: u32) -> (u32, u32) {
+        let per_bar = self.beats_per_bar();
+        (beat / per_bar + 1, beat % per_bar + 1)
+    }
+}
+
+/// A point in the musical timeline, 1-based: bar 17 beat 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BarBeat {
+    pub bar: u32,
+    pub beat: u32,
+}
+
+impl fmt::Display for BarBeat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.bar, self.beat)
+    }
+}
+
+impl Default for TimeSignature {
+    fn default() -> Self {
+        Self::new(4, 4)
+    }
+}
+
+impl fmt::Display for TimeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.beats, self.unit)
+    }
+}
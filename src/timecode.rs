@@ -0,0 +1,238 @@
+//! External timecode: MIDI timecode (MTC) received on the MIDI input, which
+//! Halo can chase, and the show's timecode conventions. Shows are often
+//! numbered from a start value such as 01:00:00:00 rather than zero, and a
+//! global offset lines Halo up with a source that runs early or late. A
+//! pre-roll counts down in negative timecode before the show starts.
+//!
+//! Joining timecode that is already rolling, at the first lock or when the
+//! source jumps, moves straight to the look at that point, fades part-way
+//! and all; cues that started just before it can still fire their actions.
+
+use crate::i18n::tr;
+use crate::transport::Transport;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Rolling timecode that stops arriving for this long has stopped.
+const DROPOUT: Duration = Duration::from_millis(500);
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimecodeSettings {
+    /// The timecode at the start of the show.
+    pub start: Duration,
+    /// Seconds added to incoming timecode, negative when it runs late.
+    pub offset: f32,
+    /// How long Start counts down before playing from the top; zero starts
+    /// straight away.
+    pub preroll: Duration,
+    /// Drift between Halo's clock and chased timecode beyond this flashes a
+    /// warning.
+    pub drift_warning: Duration,
+    /// Drift from incoming timecode beyond this makes Halo jump to it.
+    pub chase_tolerance: Duration,
+    /// Cues that started this long before the point Halo jumps to still fire
+    /// their scripts, triggers and macros; zero fires none.
+    pub late_join: Duration,
+    /// A cue firing further than this from its time on incoming timecode
+    /// counts as late, in the cue list and the post-show report.
+    pub late_cue: Duration,
+}
+
+impl Default for TimecodeSettings {
+    fn default() -> Self {
+        Self {
+            start: Duration::ZERO,
+            offset: 0.0,
+            preroll: Duration::ZERO,
+            drift_warning: Duration::from_millis(40),
+            chase_tolerance: Duration::from_millis(100),
+            late_join: Duration::ZERO,
+            late_cue: Duration::from_millis(100),
+        }
+    }
+}
+
+impl TimecodeSettings {
+    /// The show position incoming timecode `position` stands for.
+    pub fn show_position(&self, position: Duration) -> Duration {
+        let position = position.as_secs_f64() + self.offset as f64 - self.start.as_secs_f64();
+        Duration::from_secs_f64(position.max(0.0))
+    }
+}
+
+/// Timecode as it arrives from the MIDI input.
+#[derive(Clone, Copy)]
+pub struct Timecode {
+    pub position: Duration,
+    pub fps: f32,
+    /// Quarter frames of running timecode, rather than a full frame message
+    /// sent to locate.
+    pub rolling: bool,
+}
+
+impl Timecode {
+    /// Reads the hours byte, which carries the frame rate in bits 5 and 6,
+    /// and the minutes, seconds and frames.
+    pub fn new(hours: u8, minutes: u8, seconds: u8, frames: u8, rolling: bool) -> Self {
+        let fps = match (hours >> 5) & 0x03 {
+            0 => 24.0,
+            1 => 25.0,
+            2 => 29.97,
+            _ => 30.0,
+        };
+        let secs = (hours & 0x1F) as u64 * 3600 + minutes as u64 * 60 + seconds as u64;
+        Self {
+            position: Duration::from_secs(secs) + Duration::from_secs_f32(frames as f32 / fps),
+            fps,
+            rolling,
+        }
+    }
+}
+
+/// Assembles MTC quarter frames into whole timecode values.
+#[derive(Default)]
+pub struct MtcDecoder {
+    pieces: [u8; 8],
+    /// The next piece expected, so a value is only read once all eight have
+    /// arrived in order.
+    next: u8,
+}
+
+impl MtcDecoder {
+    /// Takes the data byte of a quarter frame message, returning the
+    /// timecode once the last of the eight pieces arrives.
+    pub fn quarter_frame(&mut self, data: u8) -> Option<Timecode> {
+        let piece = data >> 4;
+        if piece != self.next {
+            self.next = 0;
+            return None;
+        }
+        self.pieces[piece as usize] = data & 0x0F;
+        self.next = (piece + 1) % 8;
+        if piece != 7 {
+            return None;
+        }
+        let p = &self.pieces;
+        let timecode = Timecode::new(
+            p[6] | p[7] << 4,
+            p[4] | p[5] << 4,
+            p[2] | p[3] << 4,
+            p[0] | p[1] << 4,
+            true,
+        );
+        // The eight pieces take two frames to send.
+        Some(Timecode {
+            position: timecode.position + Duration::from_secs_f32(2.0 / timecode.fps),
+            ..timecode
+        })
+    }
+}
+
+/// Reads an MTC full frame message, sent by sources that locate.
+pub fn full_frame(message: &[u8]) -> Option<Timecode> {
+    match message {
+        [0xF0, 0x7F, _, 0x01, 0x01, hours, minutes, seconds, frames, 0xF7] => {
+            Some(Timecode::new(*hours, *minutes, *seconds, *frames, false))
+        }
+        _ => None,
+    }
+}
+
+/// Formats a timecode position the way the LED display does.
+pub fn format(position: Duration) -> String {
+    let secs = position.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+        position.subsec_millis()
+    )
+}
+
+impl HaloApp {
+    /// The timecode to display: the show's start value plus the playhead,
+    /// or counting up to it during a pre-roll.
+    pub(crate) fn format_timecode(&self) -> String {
+        let start = self.show.timecode.start;
+        let Some(end) = self.transport.preroll_end() else {
+            return format(start + self.display_elapsed());
+        };
+        let remaining = end.saturating_duration_since(Instant::now());
+        match start.checked_sub(remaining) {
+            Some(position) => format(position),
+            None => format!("-{}", format(remaining - start)),
+        }
+    }
+
+    /// Incoming timecode as a show position now, carried on from when it
+    /// was received while it rolls.
+    pub(crate) fn external_position(&self) -> Option<Duration> {
+        let (timecode, received) = self.timecode_in?;
+        let position = if timecode.rolling {
+            timecode.position + received.elapsed()
+        } else {
+            timecode.position
+        };
+        Some(self.show.timecode.show_position(position))
+    }
+
+    /// How far Halo's clock is ahead of chased timecode, in seconds, while
+    /// chasing it.
+    pub(crate) fn timecode_drift(&self) -> Option<f64> {
+        if !self.chases_timecode() {
+            return None;
+        }
+        let external = self.external_position()?;
+        Some(self.elapsed.as_secs_f64() - external.as_secs_f64())
+    }
+
+    /// Whether the drift from chased timecode is past the warning threshold.
+    pub(crate) fn timecode_drifting(&self) -> bool {
+        self.timecode_drift()
+            .is_some_and(|drift| drift.abs() > self.show.timecode.drift_warning.as_secs_f64())
+    }
+
+    /// Whether the transport follows MIDI timecode on the input port.
+    pub(crate) fn chases_timecode(&self) -> bool {
+        self.midi_in.is_some() && self.preferences.midi_timecode_in
+    }
+
+    /// Moves the playhead to incoming timecode, starting playback when it
+    /// rolls.
+    pub(crate) fn receive_timecode(&mut self, timecode: Timecode) {
+        self.timecode_in = Some((timecode, Instant::now()));
+        if !self.chases_timecode() || self.panic.is_some() {
+            return;
+        }
+        let settings = &self.show.timecode;
+        let position = settings.show_position(timecode.position);
+        if position.abs_diff(self.elapsed) > settings.chase_tolerance {
+            let window = settings.late_join;
+            self.elapsed = position;
+            self.engine.join(position, window);
+        }
+        if timecode.rolling && !self.transport.is_running() {
+            self.set_transport(Transport::Chasing { rolling: true });
+        }
+    }
+
+    /// Parks the transport when chased timecode stops arriving.
+    pub(crate) fn check_timecode_dropout(&mut self) {
+        let stopped = self
+            .timecode_in
+            .is_some_and(|(timecode, received)| timecode.rolling && received.elapsed() > DROPOUT);
+        if !stopped {
+            return;
+        }
+        if let Some((timecode, _)) = &mut self.timecode_in {
+            timecode.rolling = false;
+        }
+        if self.chases_timecode() && self.transport.is_running() {
+            self.log.info(tr!("timecode-stopped"));
+            self.set_transport(Transport::Chasing { rolling: false });
+        }
+    }
+}
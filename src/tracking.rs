@@ -0,0 +1,254 @@
+//! Tracking: a cue stores only the values it changes, and everything else
+//! carries on from the cues before it, as on a theatre console. An edit to a
+//! cue therefore tracks forward until a later cue changes the same value.
+//! Cue-only edits stop at the next cue, and a blocked cue holds every value
+//! it inherits so that nothing edited before it tracks through.
+//!
+//! "Before" and "next" go by when the cues fire, as the output merges them,
+//! rather than by list position: each function takes the cue positions in
+//! that order, from `Show::playback_order`.
+
+use crate::cue::{Cue, CueValue};
+use crate::fixture::Attribute;
+use crate::i18n::tr;
+use std::collections::HashMap;
+use std::fmt;
+
+/// How edits to a cue's values carry on into the cues after it.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum EditMode {
+    /// Changes carry on until a later cue changes the same value.
+    #[default]
+    Track,
+    /// Changes only affect the cue edited; the next cue restores what was
+    /// there before.
+    CueOnly,
+}
+
+impl EditMode {
+    pub const ALL: [EditMode; 2] = [EditMode::Track, EditMode::CueOnly];
+}
+
+impl fmt::Display for EditMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            EditMode::Track => tr!("edit-mode-track"),
+            EditMode::CueOnly => tr!("edit-mode-cue-only"),
+        })
+    }
+}
+
+/// Where a value on stage during a cue came from.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ValueState {
+    /// The cue changes it.
+    Move,
+    /// The cue stores it at the level it already had, holding it there.
+    Blocked,
+    /// It carries on from an earlier cue.
+    Tracked,
+}
+
+#[derive(Clone, Copy)]
+pub struct TrackedValue {
+    pub value: u8,
+    /// Position of the cue that last stored the value.
+    pub cue: usize,
+    pub state: ValueState,
+}
+
+pub type TrackedState = HashMap<(usize, Attribute), TrackedValue>;
+
+/// Plays the cue at `index` over `state`.
+fn play(state: &mut TrackedState, index: usize, cue: &Cue) {
+    for tracked in state.values_mut() {
+        tracked.state = ValueState::Tracked;
+    }
+    for value in &cue.values {
+        let key = (value.fixture_id, value.attribute);
+        let state_of = match state.get(&key) {
+            Some(before) if before.value == value.value => ValueState::Blocked,
+            _ => ValueState::Move,
+        };
+        state.insert(
+            key,
+            TrackedValue {
+                value: value.value,
+                cue: index,
+                state: state_of,
+            },
+        );
+    }
+}
+
+/// The values on stage after each cue, by list position, with the cues
+/// played in `order`.
+pub fn history(cues: &[Cue], order: &[usize]) -> Vec<TrackedState> {
+    let mut history = vec![TrackedState::new(); cues.len()];
+    let mut state = TrackedState::new();
+    for &index in order {
+        play(&mut state, index, &cues[index]);
+        history[index] = state.clone();
+    }
+    history
+}
+
+/// The values on stage after the cue at `index`, or before the first cue
+/// for `None`, with the cues played in `order`.
+pub fn state_after(cues: &[Cue], order: &[usize], index: Option<usize>) -> TrackedState {
+    let mut state = TrackedState::new();
+    let Some(index) = index else {
+        return state;
+    };
+    for &played in order {
+        play(&mut state, played, &cues[played]);
+        if played == index {
+            break;
+        }
+    }
+    state
+}
+
+/// The cue before and after the one at `index` in `order`.
+fn neighbours(order: &[usize], index: usize) -> (Option<usize>, Option<usize>) {
+    let Some(at) = order.iter().position(|&i| i == index) else {
+        return (None, None);
+    };
+    (
+        at.checked_sub(1).map(|before| order[before]),
+        order.get(at + 1).copied(),
+    )
+}
+
+/// Runs `edit` on the cue at `index`. In cue-only mode every value the edit
+/// changes on stage is put back in the next cue to fire, unless that cue
+/// sets it anyway.
+pub fn edit_cue<R>(
+    cues: &mut [Cue],
+    order: &[usize],
+    index: usize,
+    mode: EditMode,
+    edit: impl FnOnce(&mut Cue) -> R,
+) -> Option<R> {
+    let before = state_after(cues, order, Some(index));
+    let result = edit(cues.get_mut(index)?);
+    if mode == EditMode::CueOnly {
+        let after = state_after(cues, order, Some(index));
+        let mut changed: Vec<(usize, Attribute)> = before
+            .keys()
+            .chain(after.keys())
+            .filter(|key| before.get(key).map(|t| t.value) != after.get(key).map(|t| t.value))
+            .copied()
+            .collect();
+        changed.sort_unstable();
+        changed.dedup();
+        let (_, next) = neighbours(order, index);
+        if let Some(next) = next.and_then(|next| cues.get_mut(next)) {
+            for (fixture_id, attribute) in changed {
+                let sets_it = next
+                    .values
+                    .iter()
+                    .any(|v| v.fixture_id == fixture_id && v.attribute == attribute);
+                if !sets_it {
+                    let restore = before
+                        .get(&(fixture_id, attribute))
+                        .map_or(0, |tracked| tracked.value);
+                    next.set_value(fixture_id, attribute, restore);
+                }
+            }
+        }
+    }
+    Some(result)
+}
+
+/// Blocks or unblocks the cue at `index`. Blocking stores every value that
+/// tracks into the cue, so edits to earlier cues stop there; unblocking
+/// drops the stored values that match what tracks in, letting them track
+/// again.
+pub fn set_block(cues: &mut [Cue], order: &[usize], index: usize, block: bool) {
+    let (previous, _) = neighbours(order, index);
+    let before = state_after(cues, order, previous);
+    let Some(cue) = cues.get_mut(index) else {
+        return;
+    };
+    cue.block = block;
+    if block {
+        let mut inherited: Vec<(&(usize, Attribute), &TrackedValue)> = before
+            .iter()
+            .filter(|((fixture_id, attribute), _)| {
+                !cue.values
+                    .iter()
+                    .any(|v| v.fixture_id == *fixture_id && v.attribute == *attribute)
+            })
+            .collect();
+        inherited.sort_by_key(|(key, _)| **key);
+        for (&(fixture_id, attribute), tracked) in inherited {
+            cue.values.push(CueValue {
+                fixture_id,
+                attribute,
+                value: tracked.value,
+            });
+        }
+    } else {
+        cue.values.retain(|value| {
+            before
+                .get(&(value.fixture_id, value.attribute))
+                .is_none_or(|tracked| tracked.value != value.value)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cue::CueNumber;
+
+    /// Cue 1 is listed first but fires after cue 2, as the output plays them.
+    fn cues() -> (Vec<Cue>, Vec<usize>) {
+        let late = Cue::new(CueNumber::FIRST, "", 10, 1)
+            .with_value(1, Attribute::Intensity, 100)
+            .with_value(2, Attribute::Red, 255);
+        let early = Cue::new(CueNumber::from_whole(2).unwrap(), "", 0, 1)
+            .with_value(2, Attribute::Blue, 10)
+            .with_value(1, Attribute::Intensity, 50)
+            .with_value(1, Attribute::Blue, 30)
+            .with_value(1, Attribute::Red, 20);
+        (vec![late, early], vec![1, 0])
+    }
+
+    #[test]
+    fn history_follows_playback_order() {
+        let (cues, order) = cues();
+        let history = history(&cues, &order);
+        let intensity = |state: &TrackedState| state[&(1, Attribute::Intensity)].value;
+        assert_eq!(intensity(&history[1]), 50);
+        assert_eq!(intensity(&history[0]), 100);
+        // Cue 1 plays second, so it tracks what cue 2 set.
+        assert!(history[0][&(1, Attribute::Red)].state == ValueState::Tracked);
+        assert!(!history[1].contains_key(&(2, Attribute::Red)));
+    }
+
+    #[test]
+    fn block_stores_inherited_values_in_fixture_and_attribute_order() {
+        let (mut cues, order) = cues();
+        set_block(&mut cues, &order, 0, true);
+        let stored: Vec<(usize, Attribute, u8)> = cues[0]
+            .values
+            .iter()
+            .map(|value| (value.fixture_id, value.attribute, value.value))
+            .collect();
+        assert_eq!(
+            stored,
+            [
+                (1, Attribute::Intensity, 100),
+                (2, Attribute::Red, 255),
+                (1, Attribute::Red, 20),
+                (1, Attribute::Blue, 30),
+                (2, Attribute::Blue, 10),
+            ]
+        );
+
+        set_block(&mut cues, &order, 0, false);
+        assert_eq!(cues[0].values.len(), 2);
+    }
+}
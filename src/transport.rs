@@ -0,0 +1,172 @@
+//! The transport as one explicit state rather than flags that could
+//! disagree. Every clock source — the operator's buttons, chased timecode,
+//! a backup following its main, panic and end actions — moves it through
+//! [`HaloApp::set_transport`], so starting and stopping the engine clock and
+//! releasing the output happen the same way whatever asked for the change.
+
+use crate::i18n::tr;
+use crate::macros::MacroAction;
+use crate::midi::MachineCommand;
+use crate::HaloApp;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Transport {
+    #[default]
+    Stopped,
+    /// Counting down to start, which it does at `until`.
+    PreRoll {
+        until: Instant,
+    },
+    Running,
+    /// Frozen where it is with the output held, to carry on from there.
+    Paused,
+    /// Following incoming timecode: rolling while it rolls, parked where it
+    /// last was when it stops.
+    Chasing {
+        rolling: bool,
+    },
+}
+
+impl Transport {
+    /// Whether the playhead is moving.
+    pub fn is_running(self) -> bool {
+        matches!(
+            self,
+            Transport::Running | Transport::Chasing { rolling: true }
+        )
+    }
+
+    /// Whether the output is still the show's look rather than released per
+    /// the stop behaviour.
+    fn holds_output(self) -> bool {
+        self.is_running() || self == Transport::Paused
+    }
+
+    /// When the pre-roll countdown ends, while there is one.
+    pub fn preroll_end(self) -> Option<Instant> {
+        match self {
+            Transport::PreRoll { until } => Some(until),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Transport::Stopped => tr!("transport-stopped"),
+            Transport::PreRoll { .. } => tr!("transport-pre-roll"),
+            Transport::Running => tr!("transport-running"),
+            Transport::Paused => tr!("transport-paused"),
+            Transport::Chasing { rolling: true } => tr!("transport-chasing"),
+            Transport::Chasing { rolling: false } => tr!("transport-chasing-parked"),
+        }
+    }
+}
+
+impl HaloApp {
+    /// Whether the show is playing or counting down to play.
+    pub(crate) fn rolling(&self) -> bool {
+        self.transport.is_running() || self.transport.preroll_end().is_some()
+    }
+
+    /// Moves the transport to `next`, starting or stopping the engine clock
+    /// and releasing the output as the change needs. Starting is refused
+    /// while the rig is in the safe state.
+    pub(crate) fn set_transport(&mut self, next: Transport) {
+        let previous = self.transport;
+        if next == previous {
+            return;
+        }
+        let starting = next.is_running() && !previous.is_running();
+        if starting {
+            if self.panic.is_some() {
+                self.log.warn(tr!("transport-resume-first"));
+                return;
+            }
+            // Starting again after holding at the end of a song moves on to
+            // the next one.
+            if self.setlist_hold().is_some_and(|end| self.elapsed >= end) {
+                self.next_song();
+            }
+        }
+        self.transport = next;
+        if starting {
+            self.release = None;
+            self.engine.start_clock();
+            // The current beat falls now, for the indicator and the click.
+            self.beat_indicator.sync(self.beat_indicator.beat);
+            if !self.follows_midi_clock() {
+                self.click_clock.sync(self.beat_indicator.beat);
+            }
+        } else if previous.is_running() && !next.is_running() {
+            self.engine.stop_clock();
+        }
+        if previous.holds_output() && !next.holds_output() {
+            self.release_output();
+        }
+        match next {
+            Transport::Running if starting => self.send_machine_command(MachineCommand::Play),
+            Transport::Paused => self.send_machine_command(MachineCommand::Pause),
+            Transport::Stopped if previous.holds_output() => {
+                self.send_machine_command(MachineCommand::Stop)
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves the playhead to `position`, and MMC followers with it.
+    pub(crate) fn locate(&mut self, position: Duration) {
+        self.elapsed = position;
+        self.engine.seek(position);
+        self.send_machine_command(MachineCommand::Locate(self.show.timecode.start + position));
+    }
+
+    /// Starts playback, counting down first when starting from the top.
+    pub(crate) fn start_playback(&mut self) {
+        if self.rolling() {
+            return;
+        }
+        let preroll = self.show.timecode.preroll;
+        if preroll.is_zero() || !self.display_elapsed().is_zero() || self.panic.is_some() {
+            self.set_transport(Transport::Running);
+            return;
+        }
+        self.set_transport(Transport::PreRoll {
+            until: Instant::now() + preroll,
+        });
+    }
+
+    /// Stops playback, or cancels the pre-roll.
+    pub(crate) fn stop_playback(&mut self) {
+        self.set_transport(Transport::Stopped);
+    }
+
+    /// Freezes playback where it is, keeping the output as it is; starting
+    /// again carries on from there. Chased timecode can't be paused, only
+    /// stopped at its source.
+    pub(crate) fn pause_playback(&mut self) {
+        if self.transport == Transport::Running {
+            self.set_transport(Transport::Paused);
+        }
+    }
+
+    /// Pauses playback, or carries on from the pause.
+    pub(crate) fn toggle_pause(&mut self) {
+        self.perform(if self.transport == Transport::Paused {
+            MacroAction::Start
+        } else {
+            MacroAction::Pause
+        });
+    }
+
+    /// Starts playback once the pre-roll has counted down.
+    pub(crate) fn update_preroll(&mut self) {
+        if self
+            .transport
+            .preroll_end()
+            .is_some_and(|end| Instant::now() >= end)
+        {
+            self.set_transport(Transport::Running);
+        }
+    }
+}
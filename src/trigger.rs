@@ -0,0 +1,369 @@
+//! Outbound cue triggers: MIDI, OSC, HTTP webhooks and serial, relay and GPIO
+//! outputs sent when a cue starts or ends, so video, pyro, audio and stage
+//! machinery can run off the same timeline.
+
+use crate::hardware;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::osc;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+const NOTE_ON: u8 = 0x90;
+const PROGRAM_CHANGE: u8 = 0xC0;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TriggerWhen {
+    Start,
+    End,
+}
+
+impl TriggerWhen {
+    pub const ALL: [TriggerWhen; 2] = [TriggerWhen::Start, TriggerWhen::End];
+}
+
+impl fmt::Display for TriggerWhen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&match self {
+            TriggerWhen::Start => tr!("trigger-on-start"),
+            TriggerWhen::End => tr!("trigger-on-end"),
+        })
+    }
+}
+
+/// Something sent to another system. MIDI channels are 1 to 16.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TriggerAction {
+    MidiNote {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    MidiProgram {
+        channel: u8,
+        program: u8,
+    },
+    Osc {
+        /// `host:port`
+        target: String,
+        address: String,
+        /// Space-separated; see `osc::parse_args`.
+        args: String,
+    },
+    Webhook {
+        /// `GET` or `POST`.
+        method: String,
+        /// Plain `http://` only.
+        url: String,
+        body: String,
+    },
+    Serial {
+        /// A device path, or `COM3` and the like on Windows.
+        port: String,
+        baud: u32,
+        /// Text with escapes; see `hardware::parse_bytes`.
+        data: String,
+    },
+    Relay {
+        port: String,
+        /// 1-based.
+        relay: u8,
+        on: bool,
+        /// Switches back after this long, unless zero.
+        pulse: Duration,
+    },
+    Gpio {
+        pin: u32,
+        high: bool,
+        /// Switches back after this long, unless zero.
+        pulse: Duration,
+    },
+}
+
+impl TriggerAction {
+    /// One of each kind, with defaults, for picking a kind in the editor.
+    pub fn kinds() -> [TriggerAction; 7] {
+        [
+            TriggerAction::MidiNote {
+                channel: 1,
+                note: 60,
+                velocity: 127,
+            },
+            TriggerAction::MidiProgram {
+                channel: 1,
+                program: 0,
+            },
+            TriggerAction::Osc {
+                target: "127.0.0.1:8000".to_string(),
+                address: "/cue".to_string(),
+                args: String::new(),
+            },
+            TriggerAction::Webhook {
+                method: "POST".to_string(),
+                url: "http://".to_string(),
+                body: String::new(),
+            },
+            TriggerAction::Serial {
+                port: hardware::DEFAULT_PORT.to_string(),
+                baud: 9600,
+                data: String::new(),
+            },
+            TriggerAction::Relay {
+                port: hardware::DEFAULT_PORT.to_string(),
+                relay: 1,
+                on: true,
+                pulse: Duration::ZERO,
+            },
+            TriggerAction::Gpio {
+                pin: 17,
+                high: true,
+                pulse: Duration::ZERO,
+            },
+        ]
+    }
+
+    pub fn kind(&self) -> String {
+        match self {
+            TriggerAction::MidiNote { .. } => tr!("source-midi-note"),
+            TriggerAction::MidiProgram { .. } => tr!("source-midi-program"),
+            TriggerAction::Osc { .. } => "OSC".to_string(),
+            TriggerAction::Webhook { .. } => "Webhook".to_string(),
+            TriggerAction::Serial { .. } => tr!("trigger-serial"),
+            TriggerAction::Relay { .. } => tr!("trigger-relay"),
+            TriggerAction::Gpio { .. } => "GPIO".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct CueTrigger {
+    pub when: TriggerWhen,
+    pub action: TriggerAction,
+}
+
+impl Default for CueTrigger {
+    fn default() -> Self {
+        Self {
+            when: TriggerWhen::Start,
+            action: TriggerAction::kinds()[2].clone(),
+        }
+    }
+}
+
+/// Sends a webhook and returns the HTTP status. Bodies are sent as JSON.
+fn webhook(method: &str, url: &str, body: &str) -> io::Result<u16> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| io::Error::other("only http:// URLs are supported"))?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+    let addr = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::other("no such host"))?;
+    let body = if method == "GET" { "" } else { body };
+    let mut stream = TcpStream::connect_timeout(&addr, WEBHOOK_TIMEOUT)?;
+    stream.set_read_timeout(Some(WEBHOOK_TIMEOUT))?;
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        path,
+        host,
+        body.len(),
+        body
+    )?;
+    let mut status_line = [0u8; 12];
+    stream.read_exact(&mut status_line)?;
+    // "HTTP/1.1 200"
+    std::str::from_utf8(&status_line[9..12])
+        .ok()
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| io::Error::other("not an HTTP response"))
+}
+
+impl HaloApp {
+    /// Sends the triggers set on the cue at `index` for `when`.
+    pub(crate) fn run_cue_triggers(&mut self, index: usize, when: TriggerWhen) {
+        let Some(cue) = self.show.cues.get(index) else {
+            return;
+        };
+        let name = tr!("cue-number", number = cue.number.to_string());
+        let triggers: Vec<TriggerAction> = cue
+            .triggers
+            .iter()
+            .filter(|trigger| trigger.when == when)
+            .map(|trigger| trigger.action.clone())
+            .collect();
+        for action in triggers {
+            self.fire_trigger(&name, action);
+        }
+    }
+
+    /// Sends one trigger, on behalf of `name` in any errors.
+    pub(crate) fn fire_trigger(&mut self, name: &str, action: TriggerAction) {
+        match action {
+            TriggerAction::MidiNote {
+                channel,
+                note,
+                velocity,
+            } => self.send_midi(name, &[NOTE_ON | channel_bits(channel), note, velocity]),
+            TriggerAction::MidiProgram { channel, program } => {
+                self.send_midi(name, &[PROGRAM_CHANGE | channel_bits(channel), program])
+            }
+            TriggerAction::Osc {
+                target,
+                address,
+                args,
+            } => {
+                if let Err(err) = self.send_osc(&target, &address, &osc::parse_args(&args)) {
+                    self.notify(
+                        LogLevel::Error,
+                        tr!(
+                            "trigger-osc-failed",
+                            name = name,
+                            target = target,
+                            error = err.to_string()
+                        ),
+                    );
+                }
+            }
+            TriggerAction::Webhook { method, url, body } => {
+                spawn_webhook(
+                    name.to_string(),
+                    method,
+                    url,
+                    body,
+                    self.trigger_sender.clone(),
+                );
+            }
+            TriggerAction::Serial { port, baud, data } => match hardware::parse_bytes(&data) {
+                Ok(bytes) => spawn_output(
+                    tr!("trigger-serial-output", name = name, port = port.as_str()),
+                    move || hardware::send_serial(&port, baud, &bytes),
+                    self.trigger_sender.clone(),
+                ),
+                Err(err) => self.notify(
+                    LogLevel::Error,
+                    tr!(
+                        "trigger-serial-data",
+                        name = name,
+                        port = port,
+                        error = err.to_string()
+                    ),
+                ),
+            },
+            TriggerAction::Relay {
+                port,
+                relay,
+                on,
+                pulse,
+            } => spawn_output(
+                tr!(
+                    "trigger-relay-output",
+                    name = name,
+                    relay = relay,
+                    port = port.as_str()
+                ),
+                move || {
+                    hardware::set_relay(&port, relay, on)?;
+                    if !pulse.is_zero() {
+                        thread::sleep(pulse);
+                        hardware::set_relay(&port, relay, !on)?;
+                    }
+                    Ok(())
+                },
+                self.trigger_sender.clone(),
+            ),
+            TriggerAction::Gpio { pin, high, pulse } => spawn_output(
+                tr!("trigger-gpio-output", name = name, pin = pin),
+                move || {
+                    hardware::set_gpio(pin, high)?;
+                    if !pulse.is_zero() {
+                        thread::sleep(pulse);
+                        hardware::set_gpio(pin, !high)?;
+                    }
+                    Ok(())
+                },
+                self.trigger_sender.clone(),
+            ),
+        }
+    }
+
+    fn send_midi(&mut self, name: &str, message: &[u8]) {
+        match &self.midi_out {
+            Some(output) => output.send(message),
+            None => self.log.warn(tr!("trigger-no-midi-output", name = name)),
+        }
+    }
+
+    /// Logs webhooks and hardware outputs that failed since the last frame.
+    pub(crate) fn handle_trigger_results(&mut self) {
+        let failures: Vec<String> = self.trigger_results.try_iter().collect();
+        for failure in failures {
+            self.notify(LogLevel::Error, failure);
+        }
+    }
+}
+
+fn channel_bits(channel: u8) -> u8 {
+    channel.clamp(1, 16) - 1
+}
+
+/// Sends a webhook off the UI thread, reporting failures to `failures`.
+fn spawn_webhook(
+    name: String,
+    method: String,
+    url: String,
+    body: String,
+    failures: Sender<String>,
+) {
+    thread::spawn(move || {
+        let failure = match webhook(&method, &url, &body) {
+            Ok(status) if status < 400 => return,
+            Ok(status) => tr!(
+                "trigger-webhook-answered",
+                name = name,
+                url = url,
+                status = status
+            ),
+            Err(err) => tr!(
+                "trigger-webhook-failed",
+                name = name,
+                url = url,
+                error = err.to_string()
+            ),
+        };
+        let _ = failures.send(failure);
+    });
+}
+
+/// Writes to a serial port, relay or GPIO pin off the UI thread, since
+/// opening a port can block and pulses wait, reporting failures to
+/// `failures` as `what` failed.
+fn spawn_output(
+    what: String,
+    output: impl FnOnce() -> io::Result<()> + Send + 'static,
+    failures: Sender<String>,
+) {
+    thread::spawn(move || {
+        if let Err(err) = output() {
+            let _ = failures.send(tr!("trigger-failed", what = what, error = err.to_string()));
+        }
+    });
+}
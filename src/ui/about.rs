@@ -0,0 +1,73 @@
+use crate::i18n::tr;
+use crate::update;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    pub(crate) fn draw_about_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_about;
+        let mut check = false;
+        egui::Window::new(tr!("about-title"))
+            .id(egui::Id::new("about"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.heading("Halo");
+                ui.label(tr!("about-tagline"));
+                ui.add_space(8.0);
+                egui::Grid::new("about").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("about-version"));
+                    ui.label(update::VERSION);
+                    ui.end_row();
+
+                    ui.label(tr!("about-build"));
+                    ui.label(tr!(
+                        "about-build-for",
+                        profile = if cfg!(debug_assertions) {
+                            tr!("about-debug")
+                        } else {
+                            tr!("about-release")
+                        },
+                        os = std::env::consts::OS,
+                        arch = std::env::consts::ARCH
+                    ));
+                    ui.end_row();
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let checking = self.update_check.is_some();
+                    check = ui
+                        .add_enabled(!checking, egui::Button::new(tr!("about-check-updates")))
+                        .clicked();
+                    match &self.update_status {
+                        _ if checking => {
+                            ui.spinner();
+                        }
+                        Some(Ok(Some(release))) => {
+                            ui.label(tr!(
+                                "about-available",
+                                version = release.version.to_string()
+                            ));
+                            ui.hyperlink_to(tr!("about-download"), &release.url);
+                        }
+                        Some(Ok(None)) => {
+                            ui.label(tr!("about-up-to-date"));
+                        }
+                        Some(Err(err)) => {
+                            ui.colored_label(
+                                egui::Color32::RED,
+                                tr!("about-check-failed", error = err.to_string()),
+                            );
+                        }
+                        None => {}
+                    }
+                });
+            });
+        self.show_about = open;
+        if check {
+            self.check_for_updates(ctx);
+        }
+    }
+}
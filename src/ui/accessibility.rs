@@ -0,0 +1,64 @@
+use eframe::egui;
+
+/// The context menu key, as on Windows: it opens the menu of the focused
+/// widget without a right-click.
+const MENU_KEY: (egui::Modifiers, egui::Key) = (egui::Modifiers::SHIFT, egui::Key::F10);
+
+/// Gives `response` the name screen readers announce, for controls whose
+/// text is an icon or nothing at all.
+pub(crate) fn named(response: egui::Response, name: impl Into<String>) -> egui::Response {
+    let name = name.into();
+    response
+        .ctx
+        .accesskit_node_builder(response.id, |node| node.set_label(name));
+    response
+}
+
+/// A right-click menu on `response` that also opens, below it, with
+/// Shift+F10 while it has keyboard focus. Escape or clicking elsewhere
+/// closes the keyboard one.
+pub(crate) fn context_menu(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    mut add_contents: impl FnMut(&mut egui::Ui),
+) {
+    response.context_menu(|ui| add_contents(ui));
+    let popup = response.id.with("keyboard_menu");
+    let (modifiers, key) = MENU_KEY;
+    if response.has_focus() && ui.input_mut(|i| i.consume_key(modifiers, key)) {
+        ui.memory_mut(|memory| memory.toggle_popup(popup));
+    }
+    egui::popup_below_widget(
+        ui,
+        popup,
+        response,
+        egui::PopupCloseBehavior::CloseOnClickOutside,
+        |ui| {
+            ui.set_min_width(160.0);
+            add_contents(ui);
+        },
+    );
+}
+
+/// Outlines whichever widget has keyboard focus, so it can be found when
+/// working without a mouse. egui only shades it, which is easy to miss on
+/// the dark themes.
+pub(crate) fn draw_focus_ring(ctx: &egui::Context) {
+    let Some(response) = ctx
+        .memory(|memory| memory.focused())
+        .and_then(|id| ctx.read_response(id))
+    else {
+        return;
+    };
+    // Only the part not scrolled out of view.
+    let rect = response.interact_rect;
+    if !rect.is_positive() {
+        return;
+    }
+    let stroke = egui::Stroke::new(2.0, ctx.style().visuals.selection.stroke.color);
+    ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Tooltip,
+        egui::Id::new("focus_ring"),
+    ))
+    .rect_stroke(rect.expand(2.0), 3.0, stroke, egui::StrokeKind::Outside);
+}
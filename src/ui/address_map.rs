@@ -0,0 +1,303 @@
+use crate::dmx::UNIVERSE_SIZE;
+use crate::fixture::Fixture;
+use crate::i18n::tr;
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+
+const COLUMNS: usize = 32;
+
+/// What the address map shows and what is being dragged on it.
+pub(crate) struct AddressMap {
+    universe: u16,
+    /// Selected by clicking it in the map or the patch list.
+    pub(crate) selected: Option<usize>,
+    /// The fixture being dragged, and which of its channels was grabbed.
+    dragging: Option<(usize, u16)>,
+}
+
+impl Default for AddressMap {
+    fn default() -> Self {
+        Self {
+            universe: 1,
+            selected: None,
+            dragging: None,
+        }
+    }
+}
+
+fn footprint(fixture: &Fixture) -> u16 {
+    fixture.profile.channels.len() as u16
+}
+
+/// A colour per fixture that stays put as others are added.
+fn fixture_color(id: usize) -> egui::Color32 {
+    let hue = (id as f32 * 0.618_034).fract();
+    egui::ecolor::Hsva::new(hue, 0.55, 0.75, 1.0).into()
+}
+
+/// Whether `fixture` would overlap another fixture or run off the end of the
+/// universe if it started at `address`.
+fn collides(fixtures: &[Fixture], fixture: &Fixture, universe: u16, address: u16) -> bool {
+    let end = address + footprint(fixture);
+    end > UNIVERSE_SIZE as u16 + 1
+        || fixtures.iter().any(|other| {
+            other.id != fixture.id
+                && other.universe == universe
+                && other.address < end
+                && address < other.address + footprint(other)
+        })
+}
+
+impl HaloApp {
+    /// Every channel of one universe, with fixtures drawn as coloured spans.
+    /// Dragging a span re-addresses its fixture, as do the arrow keys with
+    /// the map focused.
+    pub(crate) fn draw_address_map(&mut self, ui: &mut egui::Ui) {
+        let map = &mut self.patch_tools.map;
+        ui.horizontal(|ui| {
+            ui.label(tr!("address-map-title"));
+            ui.label(tr!("universe-label"));
+            ui.add(egui::DragValue::new(&mut map.universe).range(1..=32767));
+            let mut used: Vec<u16> = self.show.fixtures.iter().map(|f| f.universe).collect();
+            used.sort_unstable();
+            used.dedup();
+            for universe in used {
+                ui.selectable_value(&mut map.universe, universe, universe.to_string());
+            }
+        });
+
+        let universe = map.universe;
+        let fixtures = &self.show.fixtures;
+        let cell = (ui.available_width() / COLUMNS as f32).clamp(14.0, 28.0);
+        let rows = UNIVERSE_SIZE.div_ceil(COLUMNS);
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(cell * COLUMNS as f32, cell * rows as f32),
+            egui::Sense::click_and_drag(),
+        );
+        let cell_rect = |channel: u16| {
+            let index = channel as usize - 1;
+            let min = rect.min
+                + egui::vec2(
+                    (index % COLUMNS) as f32 * cell,
+                    (index / COLUMNS) as f32 * cell,
+                );
+            egui::Rect::from_min_size(min, egui::vec2(cell, cell)).shrink(1.0)
+        };
+        let channel_at = |pos: egui::Pos2| {
+            let offset = pos - rect.min;
+            let column = (offset.x / cell).floor().clamp(0.0, COLUMNS as f32 - 1.0) as usize;
+            let row = (offset.y / cell).floor().clamp(0.0, rows as f32 - 1.0) as usize;
+            (row * COLUMNS + column + 1).min(UNIVERSE_SIZE) as u16
+        };
+        let fixture_at = |channel: u16| {
+            fixtures.iter().find(|fixture| {
+                fixture.universe == universe
+                    && (fixture.address..fixture.address + footprint(fixture)).contains(&channel)
+            })
+        };
+
+        let selected = map
+            .selected
+            .and_then(|id| fixtures.iter().find(|fixture| fixture.id == id))
+            .filter(|fixture| fixture.universe == universe);
+        let response = accessibility::named(
+            response,
+            match selected {
+                Some(fixture) => tr!(
+                    "address-map-named-selected",
+                    universe = universe,
+                    fixture = fixture.name.as_str(),
+                    address = format!("{}.{:03}", universe, fixture.address)
+                ),
+                None => tr!("address-map-named", universe = universe),
+            },
+        );
+        // Left and right move the selected fixture a channel, up and down a
+        // row, as long as it still fits.
+        let mut nudged = None;
+        if response.has_focus() {
+            ui.memory_mut(|memory| {
+                memory.set_focus_lock_filter(
+                    response.id,
+                    egui::EventFilter {
+                        horizontal_arrows: true,
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                )
+            });
+            let step = ui.input(|input| {
+                [
+                    (egui::Key::ArrowLeft, -1),
+                    (egui::Key::ArrowRight, 1),
+                    (egui::Key::ArrowUp, -(COLUMNS as i32)),
+                    (egui::Key::ArrowDown, COLUMNS as i32),
+                ]
+                .into_iter()
+                .find(|&(key, _)| input.key_pressed(key))
+                .map(|(_, step)| step)
+            });
+            if let (Some(step), Some(fixture)) = (step, selected) {
+                let address = fixture.address as i32 + step;
+                if address >= 1 && !collides(fixtures, fixture, universe, address as u16) {
+                    nudged = Some((fixture.id, address as u16));
+                }
+            }
+        }
+
+        let pointer = response.interact_pointer_pos().or(response.hover_pos());
+        let pointer_channel = pointer.map(channel_at);
+        if response.drag_started() {
+            map.dragging = pointer_channel.and_then(|channel| {
+                let fixture = fixture_at(channel)?;
+                Some((fixture.id, channel - fixture.address))
+            });
+            map.selected = map.dragging.map(|(id, _)| id).or(map.selected);
+        }
+        if response.clicked() {
+            map.selected = pointer_channel
+                .and_then(fixture_at)
+                .map(|fixture| fixture.id);
+        }
+        // Where the dragged fixture would land, and whether it fits there.
+        let target = map.dragging.and_then(|(id, grab)| {
+            let fixture = fixtures.iter().find(|fixture| fixture.id == id)?;
+            let address = pointer_channel?.saturating_sub(grab).max(1);
+            Some((
+                fixture,
+                address,
+                collides(fixtures, fixture, universe, address),
+            ))
+        });
+
+        let painter = ui.painter_at(rect);
+        let text = ui.visuals().text_color();
+        let selection = egui::Stroke::new(2.0, ui.visuals().selection.stroke.color);
+        for channel in 1..=UNIVERSE_SIZE as u16 {
+            let cell_rect = cell_rect(channel);
+            let occupants = fixtures
+                .iter()
+                .filter(|fixture| {
+                    fixture.universe == universe
+                        && (fixture.address..fixture.address + footprint(fixture))
+                            .contains(&channel)
+                })
+                .count();
+            match fixture_at(channel) {
+                Some(fixture) => {
+                    let mut color = fixture_color(fixture.id);
+                    if target.is_some_and(|(dragged, _, _)| dragged.id == fixture.id) {
+                        color = color.gamma_multiply(0.35);
+                    }
+                    painter.rect_filled(cell_rect, 1.0, color);
+                    if fixture.address == channel {
+                        painter.text(
+                            cell_rect.left_center() + egui::vec2(2.0, 0.0),
+                            egui::Align2::LEFT_CENTER,
+                            &fixture.name,
+                            egui::FontId::proportional(cell * 0.45),
+                            egui::Color32::BLACK,
+                        );
+                    }
+                    if map.selected == Some(fixture.id) {
+                        painter.rect_stroke(cell_rect, 1.0, selection, egui::StrokeKind::Inside);
+                    }
+                }
+                None => {
+                    painter.rect_filled(cell_rect, 1.0, ui.visuals().extreme_bg_color);
+                }
+            }
+            if occupants > 1 {
+                painter.rect_stroke(
+                    cell_rect,
+                    1.0,
+                    egui::Stroke::new(2.0, egui::Color32::RED),
+                    egui::StrokeKind::Inside,
+                );
+            }
+            if (channel as usize - 1).is_multiple_of(COLUMNS) {
+                painter.text(
+                    cell_rect.left_top(),
+                    egui::Align2::LEFT_TOP,
+                    channel.to_string(),
+                    egui::FontId::monospace(cell * 0.3),
+                    text.gamma_multiply(0.6),
+                );
+            }
+        }
+
+        if let Some((fixture, address, collision)) = target {
+            let color = if collision {
+                egui::Color32::RED
+            } else {
+                fixture_color(fixture.id)
+            };
+            let end = (address + footprint(fixture)).min(UNIVERSE_SIZE as u16 + 1);
+            for channel in address..end {
+                painter.rect_stroke(
+                    cell_rect(channel),
+                    1.0,
+                    egui::Stroke::new(2.0, color),
+                    egui::StrokeKind::Inside,
+                );
+            }
+            // Highlight what the drop would land on.
+            for other in fixtures.iter().filter(|other| {
+                other.id != fixture.id
+                    && other.universe == universe
+                    && other.address < address + footprint(fixture)
+                    && address < other.address + footprint(other)
+            }) {
+                for channel in other.address..other.address + footprint(other) {
+                    painter.rect_filled(
+                        cell_rect(channel),
+                        1.0,
+                        egui::Color32::RED.gamma_multiply(0.5),
+                    );
+                }
+            }
+            response.clone().on_hover_text_at_pointer(format!(
+                "{} → {}.{:03}{}",
+                fixture.name,
+                universe,
+                address,
+                if collision {
+                    tr!("address-map-collides")
+                } else {
+                    String::new()
+                }
+            ));
+        }
+        let inspected = target
+            .is_none()
+            .then(|| pointer_channel.and_then(fixture_at))
+            .flatten();
+
+        let moved = target
+            .filter(|(fixture, address, collision)| {
+                response.drag_stopped()
+                    && !collision
+                    && (fixture.universe, fixture.address) != (universe, *address)
+            })
+            .map(|(fixture, address, _)| (fixture.id, address));
+        if response.drag_stopped() {
+            map.dragging = None;
+        }
+        if let Some(fixture) = inspected {
+            response
+                .clone()
+                .on_hover_ui_at_pointer(|ui| self.draw_fixture_inspection(ui, fixture));
+        }
+        if let Some((id, address)) = moved.or(nudged) {
+            if let Some(fixture) = self.show.fixtures.iter_mut().find(|f| f.id == id) {
+                fixture.address = address;
+                self.log.info(tr!(
+                    "address-map-moved",
+                    fixture = fixture.name.as_str(),
+                    address = format!("{}.{:03}", fixture.universe, fixture.address)
+                ));
+            }
+        }
+    }
+}
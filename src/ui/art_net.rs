@@ -0,0 +1,224 @@
+use crate::art_net::{NodeTarget, UniverseRoute, KEEP_ALIVE};
+use crate::dmx::OUTPUT_HZ;
+use crate::i18n::tr;
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+
+/// Frames a second to send, with the rates budget fixtures usually cope
+/// with to hand.
+fn rate_edit(ui: &mut egui::Ui, rate: &mut f32) -> egui::Response {
+    ui.horizontal(|ui| {
+        let response = ui.add(
+            egui::DragValue::new(rate)
+                .range(1.0..=OUTPUT_HZ)
+                .max_decimals(0)
+                .suffix(tr!("unit-hz")),
+        );
+        for preset in [30.0, 40.0, OUTPUT_HZ] {
+            ui.selectable_value(rate, preset, format!("{}", preset));
+        }
+        response
+    })
+    .inner
+    .on_hover_text(tr!("art-net-rate-hint"))
+}
+
+/// A port-address as Art-Net writes it: net, sub-net and universe.
+fn port_address_label(port_address: u16) -> String {
+    format!(
+        "{}:{}:{}",
+        port_address >> 8,
+        (port_address >> 4) & 0x0f,
+        port_address & 0x0f
+    )
+}
+
+impl HaloApp {
+    pub(crate) fn draw_art_net_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_art_net;
+        let before = self.preferences.art_net_output.clone();
+        let mut discover = None;
+        egui::Window::new(tr!("art-net-title"))
+            .id(egui::Id::new("art_net"))
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                let output = &mut self.preferences.art_net_output;
+                ui.checkbox(&mut output.enabled, tr!("art-net-enabled"));
+                egui::Grid::new("art_net_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("art-net-broadcast"));
+                        ui.text_edit_singleline(&mut output.broadcast);
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(
+                            &mut output.broadcast_unrouted,
+                            tr!("art-net-broadcast-unrouted"),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr!("art-net-broadcast-rate"));
+                        rate_edit(ui, &mut output.broadcast_rate);
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(&mut output.changes_only, tr!("art-net-changes-only"))
+                            .on_hover_text(tr!(
+                                "art-net-changes-only-hint",
+                                seconds = KEEP_ALIVE.as_secs()
+                            ));
+                        ui.end_row();
+                    });
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.strong(tr!("art-net-nodes"));
+                    let label = if self.art_net_discovery.is_some() {
+                        tr!("art-net-stop-discovery")
+                    } else {
+                        tr!("art-net-discover")
+                    };
+                    if ui.button(label).clicked() {
+                        discover = Some(self.art_net_discovery.is_none());
+                    }
+                    if self.art_net_discovery.is_some() {
+                        ui.spinner();
+                    }
+                });
+                if let Some(error) = &self.art_net_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                    ui.label(tr!("art-net-discovery-port"));
+                }
+                if let Some(discovery) = &self.art_net_discovery {
+                    let nodes = discovery.nodes();
+                    if nodes.is_empty() {
+                        ui.weak(tr!("art-net-polling"));
+                    }
+                    egui::Grid::new("art_net_nodes")
+                        .striped(true)
+                        .num_columns(4)
+                        .show(ui, |ui| {
+                            for node in &nodes {
+                                ui.monospace(node.ip.to_string());
+                                ui.label(&node.short_name).on_hover_text(&node.long_name);
+                                ui.label(
+                                    node.outputs
+                                        .iter()
+                                        .map(|&port| port_address_label(port))
+                                        .collect::<Vec<_>>()
+                                        .join(", "),
+                                );
+                                let address = node.ip.to_string();
+                                let targeted =
+                                    output.nodes.iter().any(|target| target.address == address);
+                                if ui
+                                    .add_enabled(!targeted, egui::Button::new(tr!("add")))
+                                    .on_hover_text(tr!("art-net-add-hint"))
+                                    .clicked()
+                                {
+                                    let mut universe = output
+                                        .nodes
+                                        .iter()
+                                        .flat_map(|target| &target.routes)
+                                        .map(|route| route.universe)
+                                        .max()
+                                        .unwrap_or(0);
+                                    let routes = node
+                                        .outputs
+                                        .iter()
+                                        .map(|&port_address| {
+                                            universe += 1;
+                                            UniverseRoute {
+                                                universe,
+                                                port_address,
+                                            }
+                                        })
+                                        .collect();
+                                    output.nodes.push(NodeTarget {
+                                        name: node.short_name.clone(),
+                                        address,
+                                        routes,
+                                        ..NodeTarget::default()
+                                    });
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+                ui.separator();
+
+                ui.strong(tr!("art-net-unicast"));
+                let mut remove = None;
+                for (i, node) in output.nodes.iter_mut().enumerate() {
+                    ui.push_id(("art_net_node", i), |ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut node.name).desired_width(120.0));
+                            ui.add(
+                                egui::TextEdit::singleline(&mut node.address)
+                                    .desired_width(120.0)
+                                    .hint_text(tr!("ip-address")),
+                            );
+                            if ui.small_button(tr!("art-net-add-universe")).clicked() {
+                                let last = node.routes.last().copied();
+                                node.routes.push(UniverseRoute {
+                                    universe: last.map_or(1, |route| route.universe + 1),
+                                    port_address: last.map_or(0, |route| route.port_address + 1),
+                                });
+                            }
+                            if accessibility::named(ui.small_button("✖"), tr!("remove")).clicked()
+                            {
+                                remove = Some(i);
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            ui.add_space(16.0);
+                            ui.label(tr!("art-net-rate"));
+                            rate_edit(ui, &mut node.rate);
+                        });
+                        let mut remove_route = None;
+                        for (j, route) in node.routes.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.add_space(16.0);
+                                ui.label(tr!("universe"));
+                                ui.add(egui::DragValue::new(&mut route.universe).range(1..=32767));
+                                ui.label(tr!("art-net-port-address"));
+                                ui.add(
+                                    egui::DragValue::new(&mut route.port_address)
+                                        .range(0..=32767)
+                                        .custom_formatter(|n, _| port_address_label(n as u16)),
+                                );
+                                if accessibility::named(ui.small_button("✖"), tr!("remove"))
+                                    .clicked()
+                                {
+                                    remove_route = Some(j);
+                                }
+                            });
+                        }
+                        if let Some(j) = remove_route {
+                            node.routes.remove(j);
+                        }
+                    });
+                }
+                if let Some(i) = remove {
+                    output.nodes.remove(i);
+                }
+                if ui.button(tr!("art-net-add-target")).clicked() {
+                    output.nodes.push(NodeTarget::default());
+                }
+            });
+        self.show_art_net = open;
+        if !open && self.art_net_discovery.is_some() {
+            discover = Some(false);
+        }
+
+        if self.preferences.art_net_output != before {
+            let _ = self.preferences.save();
+        }
+        if let Some(enabled) = discover {
+            self.set_art_net_discovery(ctx, enabled);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::i18n::tr;
+use crate::HaloApp;
+use chrono::Local;
+use eframe::egui;
+use std::time::Duration;
+
+const DIMMED: egui::Color32 = egui::Color32::from_black_alpha(240);
+const CLOCK: egui::Color32 = egui::Color32::from_gray(150);
+
+impl HaloApp {
+    /// The clock the window dims to after a while with no input, over
+    /// everything else. The engine and the output carry on as before;
+    /// anything touched wakes it.
+    pub(crate) fn draw_dimmed(&mut self, ctx: &egui::Context) {
+        if !self.awake.dimmed {
+            return;
+        }
+        let screen = ctx.screen_rect();
+        egui::Area::new(egui::Id::new("dimmed"))
+            .fixed_pos(screen.min)
+            .order(egui::Order::Tooltip)
+            .show(ctx, |ui| {
+                let (rect, _) =
+                    ui.allocate_exact_size(screen.size(), egui::Sense::click_and_drag());
+                let painter = ui.painter();
+                painter.rect_filled(rect, 0.0, DIMMED);
+                let centre = rect.center();
+                painter.text(
+                    centre,
+                    egui::Align2::CENTER_BOTTOM,
+                    Local::now().format("%H:%M:%S").to_string(),
+                    egui::FontId::monospace(96.0),
+                    CLOCK,
+                );
+                let status = if self.rolling() {
+                    format!("{}  ▶ {}", self.show.name, self.format_timecode())
+                } else {
+                    format!("{}  {}", self.show.name, self.format_timecode())
+                };
+                painter.text(
+                    centre + egui::vec2(0.0, 16.0),
+                    egui::Align2::CENTER_TOP,
+                    status,
+                    egui::FontId::proportional(24.0),
+                    CLOCK,
+                );
+                painter.text(
+                    centre + egui::vec2(0.0, 56.0),
+                    egui::Align2::CENTER_TOP,
+                    tr!("awake-touch-to-wake"),
+                    egui::FontId::proportional(14.0),
+                    ui.visuals().weak_text_color(),
+                );
+            });
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
+
+    pub(crate) fn draw_awake_preferences(&mut self, ui: &mut egui::Ui) {
+        let awake = &mut self.preferences.awake;
+        ui.checkbox(&mut awake.keep_awake, tr!("awake-keep-on"));
+        if self.awake.is_holding() {
+            ui.weak(tr!("awake-holding"));
+        }
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut awake.dim, tr!("awake-dim-after"));
+            let mut minutes = awake.dim_after.as_secs() / 60;
+            if ui
+                .add_enabled(
+                    awake.dim,
+                    egui::DragValue::new(&mut minutes)
+                        .range(1..=240)
+                        .suffix(tr!("awake-minutes")),
+                )
+                .changed()
+            {
+                awake.dim_after = Duration::from_secs(minutes * 60);
+            }
+            ui.label(tr!("awake-without-input"));
+        });
+        ui.label(tr!("awake-dim-hint"));
+    }
+}
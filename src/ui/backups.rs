@@ -0,0 +1,64 @@
+use crate::backup;
+use crate::error::HaloError;
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    pub(crate) fn draw_backups_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_backups;
+        let mut revert = None;
+        egui::Window::new(tr!("backups-title"))
+            .id(egui::Id::new("backups"))
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                let Some(path) = &self.show_path else {
+                    ui.label(tr!("backups-unsaved"));
+                    return;
+                };
+                let backups = backup::list(path);
+                if backups.is_empty() {
+                    if self.preferences.backup_count == 0 {
+                        ui.label(tr!("backups-off"));
+                    } else {
+                        ui.label(tr!("backups-none"));
+                    }
+                    return;
+                }
+
+                ui.label(tr!("backups-revert-hint"));
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("backups")
+                            .striped(true)
+                            .num_columns(3)
+                            .show(ui, |ui| {
+                                for (i, backup) in backups.iter().enumerate() {
+                                    let saved = backup.saved_at.format("%a %d %b %H:%M:%S");
+                                    if i == 0 {
+                                        ui.strong(tr!("backups-latest", saved = saved.to_string()));
+                                    } else {
+                                        ui.label(saved.to_string());
+                                    }
+                                    ui.label(format!("{:.1} KB", backup.size as f32 / 1024.0));
+                                    if ui.button(tr!("backups-revert")).clicked() {
+                                        revert = Some((backup.path.clone(), saved.to_string()));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        self.show_backups = open;
+
+        if let Some((path, saved)) = revert {
+            match self.open_show(&path) {
+                Ok(()) => self.log.info(tr!("backups-reverted", saved = saved)),
+                Err(err) => self.report_error(HaloError::show_file(path, err)),
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::binding::{self, Binding, BindingAction, BindingSource};
+use crate::cue::CueNumber;
+use crate::i18n::tr;
+use crate::show::Show;
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+
+/// One of each action, filled in from the show, for picking a kind.
+fn action_kinds(show: &Show) -> Vec<BindingAction> {
+    let first_cue = show.cues.first().map_or(CueNumber::FIRST, |cue| cue.number);
+    vec![
+        BindingAction::FireCue(first_cue),
+        BindingAction::Go,
+        BindingAction::Back,
+        BindingAction::TogglePad(
+            show.pads
+                .first()
+                .map_or_else(String::new, |p| p.label.clone()),
+        ),
+        BindingAction::Start,
+        BindingAction::Stop,
+        BindingAction::Pause,
+        BindingAction::Reset,
+        BindingAction::NextSong,
+        BindingAction::PreviousSong,
+        BindingAction::GoToSong(1),
+        BindingAction::PadBank(1),
+        BindingAction::Assert,
+        BindingAction::RunMacro(
+            show.macros
+                .first()
+                .map_or_else(String::new, |m| m.name.clone()),
+        ),
+        BindingAction::RecordLook,
+    ]
+}
+
+fn draw_source(ui: &mut egui::Ui, source: &mut BindingSource) {
+    egui::ComboBox::from_id_salt("source_kind")
+        .selected_text(source.kind())
+        .width(100.0)
+        .show_ui(ui, |ui| {
+            for kind in BindingSource::kinds() {
+                let selected = kind.kind() == source.kind();
+                if ui.selectable_label(selected, kind.kind()).clicked() && !selected {
+                    *source = kind;
+                }
+            }
+        });
+    let channel = |ui: &mut egui::Ui, channel: &mut u8| {
+        ui.label(tr!("binding-channel"));
+        ui.add(
+            egui::DragValue::new(channel)
+                .range(0..=16)
+                .custom_formatter(|n, _| if n == 0.0 { tr!("any") } else { n.to_string() }),
+        );
+    };
+    match source {
+        BindingSource::MidiNote { channel: ch, note } => {
+            channel(ui, ch);
+            ui.label(tr!("binding-note"));
+            ui.add(egui::DragValue::new(note).range(0..=127));
+        }
+        BindingSource::MidiControl {
+            channel: ch,
+            controller,
+        } => {
+            channel(ui, ch);
+            ui.label("CC");
+            ui.add(egui::DragValue::new(controller).range(0..=127));
+        }
+        BindingSource::MidiProgram {
+            channel: ch,
+            program,
+        } => {
+            channel(ui, ch);
+            ui.label(tr!("binding-program"));
+            ui.add(egui::DragValue::new(program).range(0..=127));
+        }
+        BindingSource::Osc { address } => {
+            ui.add(egui::TextEdit::singleline(address).desired_width(140.0));
+        }
+        BindingSource::Http { name } => {
+            ui.label("/api/trigger/");
+            ui.add(egui::TextEdit::singleline(name).desired_width(90.0));
+        }
+        BindingSource::HidKey { key } => {
+            ui.label(tr!("binding-key"));
+            ui.add(egui::DragValue::new(key));
+        }
+    }
+}
+
+pub(crate) fn draw_action(ui: &mut egui::Ui, show: &Show, action: &mut BindingAction) {
+    egui::ComboBox::from_id_salt("action_kind")
+        .selected_text(action.kind())
+        .width(100.0)
+        .show_ui(ui, |ui| {
+            for kind in action_kinds(show) {
+                let selected = kind.kind() == action.kind();
+                if ui.selectable_label(selected, kind.kind()).clicked() && !selected {
+                    *action = kind;
+                }
+            }
+        });
+    match action {
+        BindingAction::FireCue(number) => {
+            egui::ComboBox::from_id_salt("cue")
+                .selected_text(number.to_string())
+                .show_ui(ui, |ui| {
+                    for cue in &show.cues {
+                        ui.selectable_value(
+                            number,
+                            cue.number,
+                            format!("{} {}", cue.number, cue.name),
+                        );
+                    }
+                });
+        }
+        BindingAction::TogglePad(label) => {
+            egui::ComboBox::from_id_salt("pad")
+                .selected_text(label.as_str())
+                .show_ui(ui, |ui| {
+                    for pad in &show.pads {
+                        ui.selectable_value(label, pad.label.clone(), &pad.label);
+                    }
+                });
+        }
+        BindingAction::GoToSong(number) => {
+            let name = show
+                .songs
+                .get(number.saturating_sub(1))
+                .map_or("—", |song| song.name.as_str());
+            egui::ComboBox::from_id_salt("song")
+                .selected_text(format!("{} {}", number, name))
+                .show_ui(ui, |ui| {
+                    for (i, song) in show.songs.iter().enumerate() {
+                        ui.selectable_value(number, i + 1, format!("{} {}", i + 1, song.name));
+                    }
+                });
+        }
+        BindingAction::PadBank(number) => {
+            let name = show
+                .pad_layout
+                .banks
+                .get(number.saturating_sub(1))
+                .map_or("—", |bank| bank.name.as_str());
+            egui::ComboBox::from_id_salt("pad_bank")
+                .selected_text(format!("{} {}", number, name))
+                .show_ui(ui, |ui| {
+                    for (i, bank) in show.pad_layout.banks.iter().enumerate() {
+                        ui.selectable_value(number, i + 1, format!("{} {}", i + 1, bank.name));
+                    }
+                });
+        }
+        BindingAction::RunMacro(name) => {
+            egui::ComboBox::from_id_salt("macro")
+                .selected_text(name.as_str())
+                .show_ui(ui, |ui| {
+                    for recorded in &show.macros {
+                        ui.selectable_value(name, recorded.name.clone(), &recorded.name);
+                    }
+                });
+        }
+        _ => {}
+    }
+}
+
+impl HaloApp {
+    pub(crate) fn draw_bindings_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_bindings;
+        let conflicts = binding::conflicts(&self.show.bindings);
+        // Edited apart from the show so the action pickers can list its cues,
+        // pads and macros.
+        let mut bindings = std::mem::take(&mut self.show.bindings);
+        let mut remove = None;
+        egui::Window::new(tr!("bindings-title"))
+            .id(egui::Id::new("bindings"))
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.label(tr!("bindings-description"));
+                if !self.preferences.osc_input_enabled {
+                    ui.weak(tr!("bindings-osc-off"));
+                }
+                ui.separator();
+
+                egui::Grid::new("bindings")
+                    .striped(true)
+                    .num_columns(4)
+                    .show(ui, |ui| {
+                        ui.strong("#");
+                        ui.strong(tr!("bindings-source"));
+                        ui.strong(tr!("bindings-action"));
+                        ui.end_row();
+
+                        for (i, binding) in bindings.iter_mut().enumerate() {
+                            ui.label((i + 1).to_string());
+                            ui.push_id(("source", i), |ui| {
+                                ui.horizontal(|ui| draw_source(ui, &mut binding.source));
+                            });
+                            ui.push_id(("action", i), |ui| {
+                                ui.horizontal(|ui| {
+                                    draw_action(ui, &self.show, &mut binding.action)
+                                });
+                            });
+                            ui.horizontal(|ui| {
+                                let learning = self.learn_binding == Some(i);
+                                if ui
+                                    .selectable_label(learning, tr!("learn"))
+                                    .on_hover_text(tr!("bindings-learn-hint"))
+                                    .clicked()
+                                {
+                                    self.learn_binding = if learning { None } else { Some(i) };
+                                }
+                                if accessibility::named(ui.small_button("🗑"), tr!("remove"))
+                                    .on_hover_text(tr!("remove"))
+                                    .clicked()
+                                {
+                                    remove = Some(i);
+                                }
+                                if !conflicts[i].is_empty() {
+                                    let others: Vec<String> =
+                                        conflicts[i].iter().map(|j| (j + 1).to_string()).collect();
+                                    ui.colored_label(egui::Color32::RED, tr!("bindings-conflict"))
+                                        .on_hover_text(tr!(
+                                            "bindings-conflict-hint",
+                                            others = others.join(", ")
+                                        ));
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                if bindings.is_empty() {
+                    ui.label(tr!("bindings-none"));
+                }
+                if ui.button(tr!("bindings-add")).clicked() {
+                    bindings.push(Binding::default());
+                }
+                if self.setlist_mode
+                    && bindings
+                        .iter()
+                        .any(|b| matches!(b.source, BindingSource::MidiProgram { .. }))
+                {
+                    ui.weak(tr!("bindings-setlist"));
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.strong(tr!("bindings-recent"));
+                    if ui.small_button(tr!("clear")).clicked() {
+                        self.received_inputs.clear();
+                    }
+                });
+                if self.received_inputs.is_empty() {
+                    ui.weak(tr!("nothing-received"));
+                }
+                egui::Grid::new("received_inputs")
+                    .striped(true)
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for received in self.received_inputs.iter().rev() {
+                            ui.monospace(received.time.format("%H:%M:%S").to_string());
+                            ui.label(received.event.to_string());
+                            match &received.outcome {
+                                Some(outcome) => ui.label(format!("→ {}", outcome)),
+                                None => ui.weak(tr!("bindings-not-bound")),
+                            };
+                            ui.end_row();
+                        }
+                    });
+            });
+        if let Some(index) = remove {
+            bindings.remove(index);
+            self.learn_binding = None;
+        }
+        self.show.bindings = bindings;
+        if !open {
+            self.learn_binding = None;
+        }
+        self.show_bindings = open;
+    }
+}
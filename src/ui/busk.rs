@@ -0,0 +1,141 @@
+use crate::busk::BuskTemplate;
+use crate::i18n::tr;
+use crate::ui::accessibility;
+use crate::ui::speed_masters::speed_master_combo;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    pub(crate) fn draw_busk_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_busk;
+        let beats = self.launched.elapsed().as_secs_f32() * self.bpm / 60.0;
+        egui::Window::new(tr!("busk-title"))
+            .id(egui::Id::new("busk"))
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.toggle_value(&mut self.busk_enabled, tr!("busk-title"));
+                    let busk = &self.show.busk;
+                    match busk.look_at(beats) {
+                        Some(_) if busk.groups.is_empty() => {
+                            ui.label(tr!("busk-pick-groups"));
+                        }
+                        Some(look) if self.busk_enabled => {
+                            let beat = beats as u32 % busk.beats_per_look.max(1) + 1;
+                            ui.label(tr!(
+                                "busk-look",
+                                number = look.number + 1,
+                                template = look.template.to_string(),
+                                beat = beat,
+                                beats = busk.beats_per_look
+                            ));
+                        }
+                        Some(_) => {
+                            ui.label(tr!("busk-description"));
+                        }
+                        None => {
+                            ui.label(tr!("busk-needs"));
+                        }
+                    }
+                });
+                ui.separator();
+
+                let busk = &mut self.show.busk;
+                ui.label(tr!("busk-groups"));
+                let mut groups: Vec<(&str, usize)> = Vec::new();
+                for fixture in &self.show.fixtures {
+                    match groups
+                        .iter_mut()
+                        .find(|(name, _)| *name == fixture.profile.name)
+                    {
+                        Some((_, count)) => *count += 1,
+                        None => groups.push((&fixture.profile.name, 1)),
+                    }
+                }
+                ui.horizontal_wrapped(|ui| {
+                    for (name, count) in groups {
+                        let mut selected = busk.groups.iter().any(|group| group == name);
+                        if ui
+                            .checkbox(&mut selected, format!("{} ×{}", name, count))
+                            .changed()
+                        {
+                            busk.groups.retain(|group| group != name);
+                            if selected {
+                                busk.groups.push(name.to_string());
+                            }
+                        }
+                    }
+                });
+
+                ui.label(tr!("busk-palette"));
+                ui.horizontal_wrapped(|ui| {
+                    let mut remove = None;
+                    for (i, color) in busk.palette.iter_mut().enumerate() {
+                        ui.color_edit_button_srgb(color).context_menu(|ui| {
+                            if ui.button(tr!("remove")).clicked() {
+                                remove = Some(i);
+                                ui.close_menu();
+                            }
+                        });
+                    }
+                    if let Some(i) = remove {
+                        busk.palette.remove(i);
+                    }
+                    if accessibility::named(ui.button("+"), tr!("busk-add-colour"))
+                        .on_hover_text(tr!("busk-add-colour"))
+                        .clicked()
+                    {
+                        busk.palette.push([255, 255, 255]);
+                    }
+                });
+
+                ui.label(tr!("busk-templates"));
+                ui.horizontal_wrapped(|ui| {
+                    for template in BuskTemplate::ALL {
+                        let mut enabled = busk.templates.contains(&template);
+                        if ui.checkbox(&mut enabled, template.to_string()).changed() {
+                            busk.templates.retain(|t| *t != template);
+                            if enabled {
+                                busk.templates.push(template);
+                                busk.templates
+                                    .sort_by_key(|t| BuskTemplate::ALL.iter().position(|a| a == t));
+                            }
+                        }
+                    }
+                });
+
+                ui.separator();
+                egui::Grid::new("busk_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("busk-intensity"));
+                        ui.add(egui::Slider::new(&mut busk.intensity, 0.0..=1.0));
+                        ui.end_row();
+
+                        ui.label(tr!("busk-variation"));
+                        ui.add(egui::Slider::new(&mut busk.variation, 0.0..=1.0))
+                            .on_hover_text(tr!("busk-variation-hint"));
+                        ui.end_row();
+
+                        ui.label(tr!("busk-change-every"));
+                        ui.add(
+                            egui::DragValue::new(&mut busk.beats_per_look)
+                                .range(1..=64)
+                                .suffix(tr!("unit-beats")),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr!("speed-master-label"));
+                        speed_master_combo(
+                            ui,
+                            "busk_speed_master",
+                            &self.show.speed_masters,
+                            &mut busk.speed_master,
+                        );
+                        ui.end_row();
+                    });
+            });
+        self.show_busk = open;
+    }
+}
@@ -0,0 +1,194 @@
+use crate::clocks::{Countdown, Readout, ZoneClock};
+use crate::i18n::tr;
+use crate::HaloApp;
+use chrono::{Local, NaiveTime, Timelike, Utc};
+use eframe::egui;
+
+/// Hours, minutes and seconds fields for a time of day.
+fn time_of_day(ui: &mut egui::Ui, time: &mut NaiveTime) {
+    let (mut hours, mut minutes, mut seconds) = (time.hour(), time.minute(), time.second());
+    let mut changed = false;
+    for (value, max) in [(&mut hours, 23), (&mut minutes, 59), (&mut seconds, 59)] {
+        changed |= ui
+            .add(
+                egui::DragValue::new(value)
+                    .range(0..=max)
+                    .custom_formatter(|n, _| format!("{:02}", n)),
+            )
+            .changed();
+    }
+    if changed {
+        if let Some(new) = NaiveTime::from_hms_opt(hours, minutes, seconds) {
+            *time = new;
+        }
+    }
+}
+
+impl HaloApp {
+    /// What the big LED readout shows now.
+    pub(crate) fn format_readout(&self) -> String {
+        let clocks = &self.show.clocks;
+        match self.readout.valid(clocks) {
+            Readout::Timecode => self.format_timecode(),
+            Readout::TimeOfDay => Local::now().format("%H:%M:%S.%3f").to_string(),
+            Readout::Zone(i) => clocks.zones[i].format(Utc::now()),
+            Readout::Countdown(i) => clocks.countdowns[i].format(Local::now()),
+        }
+    }
+
+    /// Moves the readout on to the next clock.
+    pub(crate) fn cycle_readout(&mut self) {
+        self.readout = self.readout.next(&self.show.clocks);
+    }
+
+    /// The label of the button that moves the readout on.
+    pub(crate) fn next_readout_label(&self) -> String {
+        let clocks = &self.show.clocks;
+        match self.readout.next(clocks) {
+            Readout::Timecode => tr!("transport-show-timecode"),
+            Readout::TimeOfDay => tr!("transport-show-system-time"),
+            Readout::Zone(i) => tr!(
+                "transport-show-clock",
+                clock = clocks.zones[i].name.as_str()
+            ),
+            Readout::Countdown(i) => tr!(
+                "transport-show-clock",
+                clock = clocks.countdowns[i].name.as_str()
+            ),
+        }
+    }
+
+    /// What the readout is showing, under it, where that isn't obvious;
+    /// then the rest of the show's clocks, small.
+    pub(crate) fn draw_clock_strip(&self, ui: &mut egui::Ui, size: f32) {
+        let clocks = &self.show.clocks;
+        let caption = match self.readout.valid(clocks) {
+            Readout::Zone(i) => Some(format!(
+                "{} ({})",
+                clocks.zones[i].name,
+                clocks.zones[i].offset_label()
+            )),
+            Readout::Countdown(i) => Some(clocks.countdowns[i].caption(Local::now())),
+            _ => None,
+        };
+        if let Some(caption) = caption {
+            ui.label(egui::RichText::new(caption).size(size).strong());
+        }
+        if clocks.is_empty() {
+            return;
+        }
+        let now = Local::now();
+        let utc = Utc::now();
+        let mut parts: Vec<String> = clocks
+            .zones
+            .iter()
+            .map(|zone| format!("{} {}", zone.name, &zone.format(utc)[..8]))
+            .collect();
+        parts.extend(
+            clocks
+                .countdowns
+                .iter()
+                .map(|countdown| format!("{} {}", countdown.caption(now), countdown.format(now))),
+        );
+        ui.label(
+            egui::RichText::new(parts.join("   ·   "))
+                .font(egui::FontId::monospace(size * 0.8))
+                .weak(),
+        );
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_millis(250));
+    }
+
+    /// The show's time zone clocks and countdowns.
+    pub(crate) fn draw_clocks_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_clocks;
+        egui::Window::new(tr!("menu-clocks"))
+            .id(egui::Id::new("clocks"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let clocks = &mut self.show.clocks;
+                ui.label(tr!("clocks-description"));
+                ui.add_space(6.0);
+                ui.strong(tr!("clocks-zones"));
+                let mut remove = None;
+                egui::Grid::new("clock_zones")
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for (i, zone) in clocks.zones.iter_mut().enumerate() {
+                            ui.add(egui::TextEdit::singleline(&mut zone.name).desired_width(140.0));
+                            ui.horizontal(|ui| {
+                                ui.label("UTC");
+                                let mut hours = zone.utc_offset / 60;
+                                let mut minutes = zone.utc_offset.abs() % 60;
+                                let hours_changed = ui
+                                    .add(egui::DragValue::new(&mut hours).range(-12..=14))
+                                    .on_hover_text(tr!("clocks-daylight-hint"))
+                                    .changed();
+                                ui.label(":");
+                                let minutes_changed = ui
+                                    .add(
+                                        egui::DragValue::new(&mut minutes)
+                                            .range(0..=59)
+                                            .custom_formatter(|n, _| format!("{:02}", n)),
+                                    )
+                                    .changed();
+                                if hours_changed || minutes_changed {
+                                    let sign = if hours < 0 || zone.utc_offset < 0 && hours == 0 {
+                                        -1
+                                    } else {
+                                        1
+                                    };
+                                    zone.utc_offset = hours * 60 + sign * minutes;
+                                }
+                            });
+                            if ui.small_button(tr!("remove")).clicked() {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(i) = remove {
+                    clocks.zones.remove(i);
+                }
+                if ui.button(tr!("clocks-add-zone")).clicked() {
+                    let here = Local::now().offset().local_minus_utc() / 60;
+                    clocks.zones.push(ZoneClock::new(&tr!("clocks-zone"), here));
+                }
+
+                ui.add_space(6.0);
+                ui.strong(tr!("clocks-countdowns"));
+                let mut remove = None;
+                egui::Grid::new("clock_countdowns")
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for (i, countdown) in clocks.countdowns.iter_mut().enumerate() {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut countdown.name)
+                                    .desired_width(140.0),
+                            );
+                            ui.horizontal(|ui| time_of_day(ui, &mut countdown.at));
+                            if ui.small_button(tr!("remove")).clicked() {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(i) = remove {
+                    clocks.countdowns.remove(i);
+                }
+                if ui.button(tr!("clocks-add-countdown")).clicked() {
+                    let name = match clocks.countdowns.len() {
+                        0 => tr!("clocks-doors"),
+                        1 => tr!("clocks-show"),
+                        _ => tr!("clocks-countdown"),
+                    };
+                    let at = NaiveTime::from_hms_opt(19, 0, 0).unwrap_or_default();
+                    clocks.countdowns.push(Countdown::new(&name, at));
+                }
+                ui.weak(tr!("clocks-countdowns-hint"));
+            });
+        self.show_clocks = open;
+        self.readout = self.readout.valid(&self.show.clocks);
+    }
+}
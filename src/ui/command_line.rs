@@ -0,0 +1,111 @@
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    /// The command line on its own, with the history above it.
+    pub(crate) fn draw_command_line_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_command_line;
+        egui::Window::new(tr!("menu-command-line"))
+            .id(egui::Id::new("command_line"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(160.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for command in &self.command_line.history {
+                            ui.monospace(command);
+                        }
+                    });
+                ui.separator();
+                self.draw_command_line(ui);
+            });
+        self.show_command_line = open;
+    }
+
+    /// The line itself: Enter runs it, Up and Down step through the history
+    /// and Tab takes the first completion.
+    pub(crate) fn draw_command_line(&mut self, ui: &mut egui::Ui) {
+        let groups: Vec<String> = {
+            let mut groups: Vec<String> = self
+                .show
+                .fixtures
+                .iter()
+                .map(|fixture| fixture.profile.name.clone())
+                .collect();
+            groups.sort();
+            groups.dedup();
+            groups
+        };
+        let macros: Vec<String> = self.show.macros.iter().map(|m| m.name.clone()).collect();
+        let completions = crate::command_line::complete(&self.command_line.text, &groups, &macros);
+
+        let mut run = false;
+        ui.horizontal(|ui| {
+            let line = ui.add(
+                egui::TextEdit::singleline(&mut self.command_line.text)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text("1 thru 12 @ 75")
+                    .lock_focus(true)
+                    .desired_width(260.0),
+            );
+            if line.has_focus() {
+                let (up, down, tab) = ui.input_mut(|i| {
+                    (
+                        i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                        i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                        i.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+                    )
+                });
+                if up || down {
+                    self.command_line.recall(up);
+                }
+                if let Some(completion) = completions.first().filter(|_| tab) {
+                    self.command_line.text = completion.clone();
+                    move_cursor_to_end(ui, &line, completion);
+                }
+            }
+            let entered = line.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if entered || ui.button(tr!("command-line-enter")).clicked() {
+                run = true;
+                line.request_focus();
+            }
+        });
+        if run {
+            self.run_command_line();
+        }
+        if !completions.is_empty() && !self.command_line.text.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                ui.weak("Tab:");
+                for completion in completions.iter().take(6) {
+                    let word = completion.split_whitespace().last().unwrap_or_default();
+                    if ui.small_button(word).clicked() {
+                        self.command_line.text = completion.clone();
+                    }
+                }
+            });
+        }
+        match &self.command_line.result {
+            Some(Ok(message)) => {
+                ui.weak(message);
+            }
+            Some(Err(message)) => {
+                ui.colored_label(egui::Color32::RED, message);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Puts the text cursor after a completion, as typing would have.
+fn move_cursor_to_end(ui: &egui::Ui, line: &egui::Response, text: &str) {
+    if let Some(mut state) = egui::TextEdit::load_state(ui.ctx(), line.id) {
+        let end = egui::text::CCursor::new(text.chars().count());
+        state
+            .cursor
+            .set_char_range(Some(egui::text::CCursorRange::one(end)));
+        state.store(ui.ctx(), line.id);
+    }
+}
@@ -0,0 +1,346 @@
+use crate::i18n::{self, tr};
+use crate::macros::MacroAction;
+use crate::shortcuts::{When, SHORTCUTS};
+use crate::HaloApp;
+use eframe::egui;
+
+/// How many matches the palette lists.
+const MAX_MATCHES: usize = 12;
+
+/// The flag that shows a window.
+type WindowFlag = fn(&mut HaloApp) -> &mut bool;
+
+/// Windows the palette can open, by the id of their menu item, with the flag
+/// that shows each.
+const WINDOWS: &[(&str, WindowFlag)] = &[
+    ("menu-preferences", |app| &mut app.show_preferences),
+    ("menu-properties", |app| &mut app.show_properties),
+    ("menu-merge", |app| &mut app.show_merge_window),
+    ("menu-compare-shows", |app| &mut app.show_diff_window),
+    ("menu-handoff", |app| &mut app.show_handoff),
+    ("menu-report", |app| &mut app.show_report),
+    ("menu-session", |app| &mut app.show_session),
+    ("menu-fan", |app| &mut app.show_fan),
+    ("menu-renumber", |app| &mut app.show_renumber),
+    ("menu-fixture-replace", |app| &mut app.show_fixture_replace),
+    ("menu-songs", |app| &mut app.show_songs),
+    ("menu-crossfade", |app| &mut app.show_crossfade),
+    ("menu-variables", |app| &mut app.show_variables),
+    ("menu-spectrogram", |app| &mut app.show_spectrogram),
+    ("menu-learn-timing", |app| &mut app.show_learn_timing),
+    ("menu-tracksheet", |app| &mut app.show_tracksheet),
+    ("menu-follow-spots", |app| &mut app.show_follow_spots),
+    ("menu-daw", |app| &mut app.show_daw),
+    ("menu-schedule", |app| &mut app.show_schedule),
+    ("menu-busk", |app| &mut app.show_busk),
+    ("menu-designer-view", |app| &mut app.show_designer_view),
+    ("menu-visualizer", |app| &mut app.show_visualizer),
+    ("menu-quick-console", |app| &mut app.show_quick_console),
+    ("menu-command-line", |app| &mut app.show_command_line),
+    ("menu-macros", |app| &mut app.show_macros),
+    ("menu-bindings", |app| &mut app.show_bindings),
+    ("menu-midi-monitor", |app| &mut app.show_midi_monitor),
+    ("menu-osc-monitor", |app| &mut app.show_osc_monitor),
+    ("menu-packet-inspector", |app| {
+        &mut app.show_packet_inspector
+    }),
+    ("menu-log", |app| &mut app.show_log),
+    ("menu-frame-timings", |app| &mut app.show_frame_timings),
+    ("menu-output-monitor", |app| &mut app.show_output_monitor),
+    ("menu-art-net", |app| &mut app.show_art_net),
+    ("menu-sacn", |app| &mut app.show_sacn),
+    ("menu-pixel-maps", |app| &mut app.show_pixel_maps),
+    ("menu-effect-presets", |app| &mut app.show_effect_presets),
+    ("menu-smart-bulbs", |app| &mut app.show_smart_bulbs),
+    ("menu-plugins", |app| &mut app.show_plugins),
+    ("menu-speed-masters", |app| &mut app.show_speed_masters),
+    ("menu-group-masters", |app| &mut app.show_group_masters),
+    ("menu-haze", |app| &mut app.show_haze),
+    ("menu-snapshots", |app| &mut app.show_snapshots),
+    ("menu-safe-state", |app| &mut app.show_safe_state),
+    ("menu-recording", |app| &mut app.show_recording),
+    ("menu-power", |app| &mut app.show_power),
+    ("menu-stage-display", |app| {
+        &mut app.show_stage_display_settings
+    }),
+    ("menu-clocks", |app| &mut app.show_clocks),
+];
+
+/// What choosing a palette entry does.
+enum PaletteAction {
+    Perform(MacroAction),
+    Open(WindowFlag),
+    Panic,
+    ResumeFromPanic,
+    PerformanceMode,
+    TogglePatchView,
+}
+
+struct PaletteEntry {
+    label: String,
+    action: PaletteAction,
+}
+
+/// The palette's search, kept while it is open.
+#[derive(Default)]
+pub(crate) struct CommandPalette {
+    pub open: bool,
+    query: String,
+    /// The highlighted match, by position in the list.
+    selected: usize,
+}
+
+/// How well `query` matches `text`: its letters must all appear in order,
+/// and runs of them and the starts of words score higher. None if they
+/// aren't all there.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut at = 0;
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (at..text.len()).find(|&i| text[i] == wanted)?;
+        if found == at && at > 0 {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - at).min(10) as i32;
+        at = found + 1;
+    }
+    Some(score)
+}
+
+impl HaloApp {
+    /// Everything the palette can do right now.
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let perform = |label: String, action: MacroAction| PaletteEntry {
+            label,
+            action: PaletteAction::Perform(action),
+        };
+        let mut entries = vec![
+            perform(tr!("transport-start"), MacroAction::Start),
+            perform(tr!("transport-stop"), MacroAction::Stop),
+            perform(tr!("transport-pause"), MacroAction::Pause),
+            perform(tr!("transport-reset"), MacroAction::Reset),
+            perform(tr!("palette-go"), MacroAction::Go),
+            perform(tr!("palette-back"), MacroAction::Back),
+            perform(tr!("transport-assert"), MacroAction::Assert),
+            perform(tr!("action-next-song"), MacroAction::NextSong),
+            perform(tr!("action-previous-song"), MacroAction::PreviousSong),
+        ];
+        entries.push(if self.panic.is_some() {
+            PaletteEntry {
+                label: tr!("palette-resume"),
+                action: PaletteAction::ResumeFromPanic,
+            }
+        } else {
+            PaletteEntry {
+                label: tr!("palette-panic"),
+                action: PaletteAction::Panic,
+            }
+        });
+        entries.push(PaletteEntry {
+            label: tr!("palette-performance-mode"),
+            action: PaletteAction::PerformanceMode,
+        });
+        entries.extend(
+            self.show
+                .cues
+                .iter()
+                .filter(|cue| !cue.disarmed)
+                .map(|cue| {
+                    perform(
+                        format!("{} {} {}", tr!("action-go-cue"), cue.number, cue.name),
+                        MacroAction::FireCue(cue.number),
+                    )
+                }),
+        );
+        entries.extend(self.show.songs.iter().enumerate().map(|(i, song)| {
+            perform(
+                format!("{} {} {}", tr!("action-go-to-song"), i + 1, song.name),
+                MacroAction::GoToSong(i + 1),
+            )
+        }));
+        entries.extend(self.show.pads.iter().map(|pad| {
+            perform(
+                if pad.active {
+                    tr!("palette-pad-off", pad = pad.label.as_str())
+                } else {
+                    tr!("palette-pad-on", pad = pad.label.as_str())
+                },
+                MacroAction::SetPad {
+                    pad: pad.label.clone(),
+                    active: !pad.active,
+                },
+            )
+        }));
+        entries.extend(self.show.macros.iter().map(|recorded| {
+            perform(
+                format!("{} {}", tr!("action-run-macro"), recorded.name),
+                MacroAction::RunMacro(recorded.name.clone()),
+            )
+        }));
+        if !self.performance_mode {
+            entries.push(PaletteEntry {
+                label: tr!("palette-toggle-patch"),
+                action: PaletteAction::TogglePatchView,
+            });
+            entries.extend(WINDOWS.iter().map(|&(id, flag)| PaletteEntry {
+                label: tr!(
+                    "palette-open",
+                    window = i18n::translate(id, None).trim_end_matches("...").to_string()
+                ),
+                action: PaletteAction::Open(flag),
+            }));
+        }
+        entries
+    }
+
+    fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::Perform(action) => self.perform(action),
+            PaletteAction::Open(flag) => *flag(self) = true,
+            PaletteAction::Panic => self.engage_panic(),
+            PaletteAction::ResumeFromPanic => self.resume_from_panic(),
+            PaletteAction::PerformanceMode => self.performance_prompt = true,
+            PaletteAction::TogglePatchView => self.toggle_view(),
+        }
+    }
+
+    /// Ctrl+K: a search box over every action, cue, song, pad, macro and
+    /// window. Up and Down pick a match, Enter runs it and Escape closes.
+    pub(crate) fn draw_command_palette(&mut self, ctx: &egui::Context) {
+        if !self.command_palette.open {
+            return;
+        }
+        let query = self.command_palette.query.clone();
+        let mut matches: Vec<(i32, PaletteEntry)> = self
+            .palette_entries()
+            .into_iter()
+            .filter_map(|entry| Some((fuzzy_score(&query, &entry.label)?, entry)))
+            .collect();
+        matches.sort_by_key(|(score, _)| -score);
+        matches.truncate(MAX_MATCHES);
+
+        let palette = &mut self.command_palette;
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.key_pressed(egui::Key::Enter),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        if up {
+            palette.selected = palette.selected.saturating_sub(1);
+        }
+        if down {
+            palette.selected += 1;
+        }
+        palette.selected = palette.selected.min(matches.len().saturating_sub(1));
+
+        let mut chosen = enter.then_some(palette.selected);
+        egui::Window::new(tr!("menu-command-palette"))
+            .id(egui::Id::new("command_palette"))
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+            .fixed_size([420.0, 0.0])
+            .show(ctx, |ui| {
+                let field = ui.add(
+                    egui::TextEdit::singleline(&mut palette.query)
+                        .hint_text(tr!("palette-hint"))
+                        .desired_width(f32::INFINITY),
+                );
+                field.request_focus();
+                if field.changed() {
+                    palette.selected = 0;
+                }
+                ui.separator();
+                if matches.is_empty() {
+                    ui.weak(tr!("palette-no-matches"));
+                }
+                for (i, (_, entry)) in matches.iter().enumerate() {
+                    if ui
+                        .selectable_label(i == palette.selected, &entry.label)
+                        .clicked()
+                    {
+                        chosen = Some(i);
+                    }
+                }
+            });
+
+        if escape {
+            self.close_command_palette();
+        } else if let Some(index) = chosen.filter(|&i| i < matches.len()) {
+            let (_, entry) = matches.swap_remove(index);
+            self.log
+                .info(tr!("palette-ran", entry = entry.label.as_str()));
+            self.close_command_palette();
+            self.run_palette_action(entry.action);
+        }
+    }
+
+    pub(crate) fn toggle_command_palette(&mut self) {
+        if self.command_palette.open {
+            self.close_command_palette();
+        } else {
+            self.command_palette.open = true;
+        }
+    }
+
+    fn close_command_palette(&mut self) {
+        self.command_palette = CommandPalette::default();
+    }
+
+    /// Whether shortcuts marked `when` do anything right now.
+    fn shortcut_active(&self, when: When) -> bool {
+        match when {
+            When::Always => true,
+            When::Editing => !self.performance_mode,
+            When::Setlist => self.setlist_mode,
+            When::LearningTiming => self.learn_timing.is_some(),
+        }
+    }
+
+    /// `?`: the keyboard shortcuts, with the ones that do nothing right now
+    /// greyed out.
+    pub(crate) fn draw_shortcut_help(&mut self, ctx: &egui::Context) {
+        if !self.show_shortcut_help {
+            return;
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.show_shortcut_help = false;
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("menu-shortcuts"))
+            .id(egui::Id::new("shortcut_help"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcut_help")
+                    .num_columns(2)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        for shortcut in SHORTCUTS {
+                            let key = egui::RichText::new(shortcut.name()).monospace().strong();
+                            if self.shortcut_active(shortcut.when) {
+                                ui.label(key);
+                                ui.label(shortcut.description());
+                            } else {
+                                ui.weak(key);
+                                ui.weak(shortcut.description());
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.separator();
+                ui.weak(tr!("shortcuts-close"));
+            });
+        self.show_shortcut_help = open;
+    }
+}
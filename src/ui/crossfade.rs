@@ -0,0 +1,94 @@
+use crate::crossfade::{Fader, FaderControl};
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+const FADER_HEIGHT: f32 = 180.0;
+
+impl HaloApp {
+    fn draw_fader(&mut self, ui: &mut egui::Ui, fader: Fader) {
+        let name = if fader == Fader::A { "A" } else { "B" };
+        let role = if self.crossfade.outgoing() == fader {
+            tr!("crossfade-out")
+        } else {
+            tr!("crossfade-in")
+        };
+        ui.vertical(|ui| {
+            ui.strong(name);
+            ui.label(role);
+            ui.spacing_mut().slider_width = FADER_HEIGHT;
+            ui.add(
+                egui::Slider::new(self.crossfade.level_mut(fader), 0.0..=1.0)
+                    .vertical()
+                    .show_value(false),
+            );
+            ui.label(format!("{:.0}%", self.crossfade.level(fader) * 100.0));
+
+            let control = match fader {
+                Fader::A => &mut self.show.crossfade.a,
+                Fader::B => &mut self.show.crossfade.b,
+            };
+            ui.label(match control {
+                Some(FaderControl {
+                    channel: 0,
+                    controller,
+                }) => tr!("crossfade-cc-any", controller = *controller),
+                Some(FaderControl {
+                    channel,
+                    controller,
+                }) => tr!("crossfade-cc", controller = *controller, channel = *channel),
+                None => tr!("crossfade-no-midi"),
+            });
+            ui.horizontal(|ui| {
+                let learning = self.learn_fader == Some(fader);
+                if ui
+                    .selectable_label(learning, tr!("learn"))
+                    .on_hover_text(tr!("crossfade-learn-hint"))
+                    .clicked()
+                {
+                    self.learn_fader = (!learning).then_some(fader);
+                }
+                if ui
+                    .add_enabled(control.is_some(), egui::Button::new(tr!("clear")))
+                    .clicked()
+                {
+                    *control = None;
+                }
+            });
+        });
+    }
+
+    /// The A/B faders for crossfading from the current cue to the next by
+    /// hand.
+    pub(crate) fn draw_crossfade_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_crossfade;
+        egui::Window::new(tr!("crossfade-title"))
+            .id(egui::Id::new("crossfade"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let current = self.current_cue().map_or_else(
+                    || tr!("crossfade-top"),
+                    |cue| tr!("crossfade-cue", number = cue.number.to_string()),
+                );
+                match self.next_cue() {
+                    Some(next) => ui.label(tr!(
+                        "crossfade-from-to",
+                        from = current,
+                        number = next.number.to_string()
+                    )),
+                    None => ui.label(tr!("crossfade-no-next")),
+                };
+                ui.horizontal(|ui| {
+                    self.draw_fader(ui, Fader::A);
+                    ui.add_space(16.0);
+                    self.draw_fader(ui, Fader::B);
+                });
+                ui.label(tr!("crossfade-hint"));
+            });
+        self.show_crossfade = open;
+        if !open {
+            self.learn_fader = None;
+        }
+    }
+}
@@ -0,0 +1,1233 @@
+use crate::cue::{self, Cue, CueNumber, CuePart, CueValue};
+use crate::fade_curve::FadeCurve;
+use crate::fixture::{Attribute, AttributeGroup, Fixture};
+use crate::hardware;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::macros::MacroAction;
+use crate::marker::{self, Marker};
+use crate::session::format_late;
+use crate::time_signature::{BarBeat, TimeSignature};
+use crate::tracking;
+use crate::trigger::{CueTrigger, TriggerAction, TriggerWhen};
+use crate::ui::accessibility;
+use crate::ui::session::LATE;
+use crate::ui::tags::{draw_tag_picker, draw_tag_strip};
+use crate::ui::touch;
+use crate::ui::wheel::{self, SlotImages};
+use crate::variable::{Comparison, Condition, Value};
+use crate::variation::Variation;
+use crate::HaloApp;
+use eframe::egui;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Markers are drawn in amber so they stand apart from the cues.
+pub(crate) const MARKER_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 170, 60);
+
+/// A change to a marker from its row in the cue list.
+enum MarkerEdit {
+    ToPlayhead(usize),
+    Remove(usize),
+}
+
+impl HaloApp {
+    /// The cue list with progress bars, grouped into collapsible sections and
+    /// filtered by the search box. Double-clicking a cue fires it.
+    pub(crate) fn draw_cue_list(&mut self, ui: &mut egui::Ui) {
+        let mut jump = false;
+        ui.horizontal(|ui| {
+            ui.label(tr!("search-label"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.cue_filter)
+                    .hint_text(tr!("cues-search-hint"))
+                    .desired_width(200.0),
+            );
+            if !self.cue_filter.is_empty()
+                && accessibility::named(ui.small_button("✖"), tr!("cues-clear-search")).clicked()
+            {
+                self.cue_filter.clear();
+            }
+            jump = ui
+                .add_enabled(
+                    self.current_cue().is_some(),
+                    egui::Button::new(tr!("cues-jump")),
+                )
+                .clicked();
+            if ui
+                .button(tr!("cues-add-marker"))
+                .on_hover_text(tr!("cues-add-marker-hint"))
+                .clicked()
+            {
+                self.add_marker();
+            }
+            ui.toggle_value(&mut self.ripple_edit, tr!("cues-ripple"))
+                .on_hover_text(tr!("cues-ripple-hint"));
+        });
+
+        let query = self.cue_filter.trim().to_lowercase();
+        let current = self.current_cue().map(|cue| cue.number);
+        let mut fire = None;
+        let slots = marker::slots(&self.show);
+        let mut marker_edit = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let mut shown = 0;
+            for (title, range) in cue::sections(&self.show.cues) {
+                let matching: Vec<usize> = range
+                    .clone()
+                    .filter(|&i| self.show.cues[i].matches(&query))
+                    .collect();
+                if matching.is_empty() {
+                    continue;
+                }
+                shown += matching.len();
+
+                let Some(title) = title else {
+                    for i in matching {
+                        self.draw_markers(ui, &slots, i, &query, &mut marker_edit);
+                        self.draw_cue_row(ui, i, jump, current, &mut fire);
+                    }
+                    continue;
+                };
+                let has_current = matching
+                    .iter()
+                    .any(|&i| Some(self.show.cues[i].number) == current);
+                let force_open = !query.is_empty() || (jump && has_current);
+                egui::CollapsingHeader::new(
+                    egui::RichText::new(format!("{} ({})", title, range.len())).strong(),
+                )
+                .id_salt(("cue_section", range.start))
+                .default_open(true)
+                .open(force_open.then_some(true))
+                .show(ui, |ui| {
+                    for i in matching {
+                        self.draw_markers(ui, &slots, i, &query, &mut marker_edit);
+                        self.draw_cue_row(ui, i, jump, current, &mut fire);
+                    }
+                });
+            }
+            let after_cues = self.show.cues.len();
+            self.draw_markers(ui, &slots, after_cues, &query, &mut marker_edit);
+            if shown == 0 && !self.show.cues.is_empty() {
+                ui.label(tr!("cues-no-match"));
+            }
+        });
+        if let Some(number) = fire {
+            self.perform(MacroAction::FireCue(number));
+        }
+        match marker_edit {
+            Some(MarkerEdit::ToPlayhead(i)) => {
+                self.show.markers[i].time = self.elapsed;
+                self.show.markers.sort_by_key(|marker| marker.time);
+            }
+            Some(MarkerEdit::Remove(i)) => {
+                self.show.markers.remove(i);
+            }
+            None => {}
+        }
+    }
+
+    fn add_marker(&mut self) {
+        self.show.markers.push(Marker {
+            time: self.elapsed,
+            text: tr!("cues-marker"),
+        });
+        self.show.markers.sort_by_key(|marker| marker.time);
+    }
+
+    /// The markers that fall just before the cue at `index`.
+    fn draw_markers(
+        &mut self,
+        ui: &mut egui::Ui,
+        slots: &[usize],
+        index: usize,
+        query: &str,
+        edit: &mut Option<MarkerEdit>,
+    ) {
+        let shown: Vec<usize> = marker::before(&self.show, slots, index)
+            .filter(|(_, marker)| marker.matches(query))
+            .map(|(i, _)| i)
+            .collect();
+        for i in shown {
+            ui.push_id(("marker", i), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("◆").color(MARKER_COLOR));
+                    let marker = &mut self.show.markers[i];
+                    ui.add(
+                        egui::TextEdit::singleline(&mut marker.text)
+                            .text_color(MARKER_COLOR)
+                            .desired_width(200.0),
+                    );
+                    ui.label(
+                        egui::RichText::new(Self::format_duration(marker.time)).color(MARKER_COLOR),
+                    );
+                    if accessibility::named(ui.small_button("⏱"), tr!("cues-move-to-playhead"))
+                        .on_hover_text(tr!("cues-move-to-playhead"))
+                        .clicked()
+                    {
+                        *edit = Some(MarkerEdit::ToPlayhead(i));
+                    }
+                    if accessibility::named(ui.small_button("🗑"), tr!("remove"))
+                        .on_hover_text(tr!("remove"))
+                        .clicked()
+                    {
+                        *edit = Some(MarkerEdit::Remove(i));
+                    }
+                });
+            });
+        }
+    }
+
+    fn draw_cue_row(
+        &mut self,
+        ui: &mut egui::Ui,
+        i: usize,
+        jump: bool,
+        current: Option<CueNumber>,
+        fire: &mut Option<CueNumber>,
+    ) {
+        ui.horizontal(|ui| {
+            draw_tag_strip(ui, &self.show.cues[i].tag);
+            let mut armed = !self.show.cues[i].disarmed;
+            if touch::hint(
+                accessibility::named(ui.checkbox(&mut armed, ""), tr!("cues-armed")),
+                self.preferences.touch_mode,
+                tr!("cues-armed-hint"),
+            )
+            .changed()
+            {
+                self.show.cues[i].disarmed = !armed;
+            }
+            let cue = &self.show.cues[i];
+            let active_color = if !self.show.plays(cue) {
+                egui::Color32::from_rgb(90, 90, 90)
+            } else if cue.is_playing {
+                egui::Color32::from_rgb(100, 200, 100)
+            } else {
+                egui::Color32::from_rgb(150, 150, 150)
+            };
+
+            ui.label(
+                egui::RichText::new(format!("{:>6}", cue.number))
+                    .monospace()
+                    .color(active_color),
+            );
+
+            let mut name = egui::RichText::new(format!("{}{}", cue.tag.prefix(), cue.name))
+                .color(active_color)
+                .strong();
+            if cue.disarmed {
+                name = name.strikethrough();
+            }
+            if let Some(condition) = &cue.condition {
+                ui.label(egui::RichText::new("?").color(active_color).strong())
+                    .on_hover_text(tr!("cues-only-when", condition = condition.to_string()));
+            }
+            if let Some(at) = cue.at_beat {
+                ui.label(egui::RichText::new("♩").color(active_color).strong())
+                    .on_hover_text(tr!("cues-fires-at-bar", at = at.to_string()));
+            }
+            let response = touch::hint(
+                ui.selectable_label(self.selected_cue == Some(i), name),
+                self.preferences.touch_mode,
+                tr!("cues-double-click"),
+            );
+            let response = accessibility::named(
+                response,
+                if cue.is_playing {
+                    tr!(
+                        "cues-row-playing",
+                        number = cue.number.to_string(),
+                        name = cue.name.as_str()
+                    )
+                } else if cue.disarmed {
+                    tr!(
+                        "cues-row-disarmed",
+                        number = cue.number.to_string(),
+                        name = cue.name.as_str()
+                    )
+                } else {
+                    tr!(
+                        "cue-name",
+                        number = cue.number.to_string(),
+                        name = cue.name.as_str()
+                    )
+                },
+            );
+            // Ctrl+Enter is the keyboard's double-click.
+            let fired_by_key = response.has_focus()
+                && ui.input_mut(|input| {
+                    input.consume_key(egui::Modifiers::COMMAND, egui::Key::Enter)
+                });
+            if response.clicked() {
+                self.selected_cue = Some(i);
+            }
+            if response.double_clicked() || fired_by_key {
+                *fire = Some(cue.number);
+            }
+            if jump && current == Some(cue.number) {
+                response.scroll_to_me(Some(egui::Align::Center));
+            }
+
+            ui.label(
+                egui::RichText::new(Self::format_duration(self.show.cue_start(cue)))
+                    .color(active_color),
+            );
+            if let Some(last) = self
+                .session
+                .last_fire(cue.number)
+                .filter(|fire| fire.is_late(self.show.timecode.late_cue))
+            {
+                ui.label(egui::RichText::new("⏱").color(LATE).strong())
+                    .on_hover_text(tr!(
+                        "cues-went-late",
+                        late = format_late(last.late.unwrap_or_default()),
+                        at = last.at.format("%H:%M:%S").to_string()
+                    ));
+            }
+
+            // Progress bar, with the duration as its hint
+            touch::hint(
+                ui.add(
+                    egui::ProgressBar::new(cue.progress)
+                        .desired_width(200.0)
+                        .desired_height(30.0)
+                        .corner_radius(0.0),
+                ),
+                self.preferences.touch_mode,
+                tr!("cues-fade", timing = cue.timing_label()),
+            );
+        });
+    }
+
+    pub(crate) fn draw_renumber_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_renumber;
+        let mut apply = false;
+        egui::Window::new(tr!("renumber-title"))
+            .id(egui::Id::new("renumber"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("renumber_grid").show(ui, |ui| {
+                    ui.label(tr!("renumber-start"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.renumber_start)
+                            .speed(0.1)
+                            .range(0.0..=9999.0)
+                            .max_decimals(3),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr!("renumber-interval"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.renumber_interval)
+                            .speed(0.1)
+                            .range(0.001..=100.0)
+                            .max_decimals(3),
+                    );
+                    ui.end_row();
+                });
+                apply = ui.button(tr!("renumber-apply")).clicked();
+            });
+
+        if apply {
+            let (start, interval) = (
+                CueNumber::from_f32(self.renumber_start),
+                CueNumber::from_f32(self.renumber_interval),
+            );
+            if cue::renumber(&mut self.show.cues, start, interval).is_some() {
+                open = false;
+            } else {
+                self.notify(
+                    LogLevel::Warning,
+                    tr!(
+                        "renumber-too-high",
+                        count = self.show.cues.len(),
+                        start = start.to_string(),
+                        interval = interval.to_string(),
+                        max = CueNumber::MAX.to_string()
+                    ),
+                );
+            }
+        }
+        self.show_renumber = open;
+    }
+
+    /// Copies the selected cue, or with `section` every cue in its section,
+    /// with everything they store.
+    pub(crate) fn copy_cues(&mut self, section: bool) {
+        let Some(index) = self.selected_cue else {
+            return;
+        };
+        let range = if section {
+            cue::sections(&self.show.cues)
+                .into_iter()
+                .map(|(_, range)| range)
+                .find(|range| range.contains(&index))
+                .unwrap_or(index..index + 1)
+        } else {
+            index..index + 1
+        };
+        let Some(copied) = self.show.cues.get(range) else {
+            return;
+        };
+        self.cue_clipboard = copied.to_vec();
+        self.log.info(match copied {
+            [cue] => tr!("cues-copied-one", number = cue.number.to_string()),
+            _ => tr!("cues-copied", count = copied.len()),
+        });
+    }
+
+    /// Pastes the copied cues after the selected cue, moved by `offset`
+    /// seconds, and selects the first of them.
+    fn paste_cues(&mut self, offset: f32) {
+        let copied = std::mem::take(&mut self.cue_clipboard);
+        let pasted = cue::paste_cues(&mut self.show.cues, self.selected_cue, &copied, offset);
+        let count = copied.len();
+        self.cue_clipboard = copied;
+        let Some(pasted) = pasted else {
+            self.warn_no_room(count);
+            return;
+        };
+        self.log.info(tr!(
+            "cues-pasted",
+            count = count,
+            numbers = pasted
+                .clone()
+                .map(|i| self.show.cues[i].number.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        self.selected_cue = Some(pasted.start);
+    }
+
+    /// Warns that `count` cues couldn't be added, as there's no room for
+    /// their numbers below [`CueNumber::MAX`].
+    pub(crate) fn warn_no_room(&mut self, count: usize) {
+        self.notify(
+            LogLevel::Warning,
+            tr!(
+                "cues-no-room",
+                count = count,
+                max = CueNumber::MAX.to_string()
+            ),
+        );
+    }
+
+    /// Copies the selected cue to start at the playhead, right after it in
+    /// the list.
+    pub(crate) fn duplicate_cue_at_playhead(&mut self) {
+        let Some(cue) = self.selected_cue.and_then(|i| self.show.cues.get(i)) else {
+            return;
+        };
+        let offset = self.elapsed.as_secs_f32() - self.show.cue_start(cue).as_secs_f32();
+        let copied = [cue.clone()];
+        let Some(pasted) = cue::paste_cues(&mut self.show.cues, self.selected_cue, &copied, offset)
+        else {
+            self.warn_no_room(copied.len());
+            return;
+        };
+        let new = &self.show.cues[pasted.start];
+        self.log.info(tr!(
+            "cues-duplicated",
+            number = copied[0].number.to_string(),
+            new = new.number.to_string(),
+            at = Self::format_duration(self.show.cue_start(new))
+        ));
+        self.selected_cue = Some(pasted.start);
+    }
+
+    /// Asks how far to move the copied cues before pasting them.
+    pub(crate) fn draw_paste_cues_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_paste_cues;
+        let mut paste = false;
+        egui::Window::new(tr!("paste-title"))
+            .id(egui::Id::new("paste_cues"))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let Some(first) = self.cue_clipboard.first() else {
+                    ui.label(tr!("paste-nothing"));
+                    return;
+                };
+                let first_start = self.show.cue_start(first).as_secs_f32();
+                ui.label(match self.cue_clipboard.len() {
+                    1 => tr!(
+                        "cue-name",
+                        number = first.number.to_string(),
+                        name = first.name.as_str()
+                    ),
+                    n => tr!(
+                        "paste-cues-from",
+                        count = n,
+                        number = first.number.to_string()
+                    ),
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("paste-offset"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.paste_offset)
+                            .speed(0.1)
+                            .max_decimals(2)
+                            .suffix(tr!("unit-seconds")),
+                    );
+                    if ui
+                        .small_button(tr!("paste-to-playhead"))
+                        .on_hover_text(tr!("paste-to-playhead-hint"))
+                        .clicked()
+                    {
+                        self.paste_offset = self.elapsed.as_secs_f32() - first_start;
+                    }
+                });
+                let starts_at = Duration::from_secs_f32((first_start + self.paste_offset).max(0.0));
+                let at = Self::format_duration(starts_at);
+                ui.weak(
+                    match self.selected_cue.and_then(|i| self.show.cues.get(i)) {
+                        Some(cue) => tr!(
+                            "paste-starts-after",
+                            at = at,
+                            number = cue.number.to_string()
+                        ),
+                        None => tr!("paste-starts-at-end", at = at),
+                    },
+                );
+                paste = ui.button(tr!("paste-apply")).clicked();
+            });
+        if paste {
+            self.paste_cues(self.paste_offset);
+            open = false;
+        }
+        self.show_paste_cues = open;
+    }
+}
+
+impl HaloApp {
+    pub(crate) fn draw_cue_editor(&mut self, ctx: &egui::Context) {
+        let mut song_change = None;
+        let mut block_change = None;
+        let mut test_fire = None;
+        let Some(cue) = self
+            .selected_cue
+            .and_then(|index| self.show.cues.get_mut(index))
+        else {
+            self.show_cue_editor = false;
+            return;
+        };
+        let name = tr!("cue-number", number = cue.number.to_string());
+
+        egui::Window::new(tr!("cue-editor-title", number = cue.number.to_string()))
+            .id(egui::Id::new("cue_editor"))
+            .open(&mut self.show_cue_editor)
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("cue_editor_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("name-label"));
+                        ui.text_edit_singleline(&mut cue.name);
+                        ui.end_row();
+
+                        ui.label("");
+                        let mut armed = !cue.disarmed;
+                        if ui
+                            .checkbox(&mut armed, tr!("cues-armed"))
+                            .on_hover_text(tr!("cue-editor-armed-hint"))
+                            .changed()
+                        {
+                            cue.disarmed = !armed;
+                        }
+                        ui.end_row();
+
+                        ui.label("");
+                        let mut block = cue.block;
+                        if ui
+                            .checkbox(&mut block, tr!("cue-editor-block"))
+                            .on_hover_text(tr!("cue-editor-block-hint"))
+                            .changed()
+                        {
+                            block_change = Some(block);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-only-when"));
+                        draw_condition(ui, &mut cue.condition, &self.show.variables);
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-fire-at"));
+                        draw_beat(ui, &mut cue.at_beat, self.show.time_signature);
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-intensity-fade"));
+                        draw_fade_curve(ui, "intensity_curve", &mut cue.intensity_curve);
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-attribute-fade"));
+                        draw_fade_curve(ui, "attribute_curve", &mut cue.attribute_curve)
+                            .on_hover_text(tr!("cue-editor-attribute-fade-hint"));
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-vary"));
+                        let mut attributes: Vec<Attribute> =
+                            cue.values.iter().map(|value| value.attribute).collect();
+                        attributes.sort();
+                        attributes.dedup();
+                        ui.vertical(|ui| draw_variation(ui, &mut cue.variation, &attributes));
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-run-macro"));
+                        egui::ComboBox::from_id_salt("cue_macro")
+                            .selected_text(cue.run_macro.clone().unwrap_or_else(|| tr!("none")))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut cue.run_macro, None, tr!("none"));
+                                for recorded in &self.show.macros {
+                                    ui.selectable_value(
+                                        &mut cue.run_macro,
+                                        Some(recorded.name.clone()),
+                                        &recorded.name,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-song"));
+                        let song_name = |id: Option<u32>| {
+                            id.and_then(|id| self.show.songs.iter().find(|song| song.id == id))
+                                .map_or_else(|| tr!("none"), |song| song.name.clone())
+                        };
+                        let mut song = cue.song;
+                        egui::ComboBox::from_id_salt("cue_song")
+                            .selected_text(song_name(song))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut song, None, tr!("none"));
+                                for entry in &self.show.songs {
+                                    ui.selectable_value(&mut song, Some(entry.id), &entry.name);
+                                }
+                            });
+                        if song != cue.song {
+                            song_change = Some(song);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-section"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut cue.section)
+                                .hint_text(tr!("cue-editor-section-hint")),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr!("cue-editor-tag"));
+                        ui.vertical(|ui| draw_tag_picker(ui, &mut cue.tag));
+                        ui.end_row();
+
+                        ui.label(tr!("notes-label"));
+                        ui.add(
+                            egui::TextEdit::multiline(&mut cue.notes)
+                                .desired_rows(4)
+                                .hint_text(tr!("cue-editor-notes-hint")),
+                        );
+                        ui.end_row();
+                    });
+
+                ui.separator();
+                draw_values(ui, cue, &self.show.fixtures, &mut self.slot_images);
+                ui.separator();
+                draw_group_timing(ui, cue);
+                ui.separator();
+                draw_parts(ui, cue, &self.show.fixtures);
+                ui.separator();
+                test_fire = draw_triggers(ui, &mut cue.triggers);
+            });
+
+        if let Some(action) = test_fire {
+            self.fire_trigger(&name, action);
+        }
+
+        if let (Some(song), Some(index)) = (song_change, self.selected_cue) {
+            self.show.set_cue_song(index, song);
+        }
+        if let (Some(block), Some(index)) = (block_change, self.selected_cue) {
+            let order = self.show.playback_order();
+            tracking::set_block(&mut self.show.cues, &order, index, block);
+        }
+    }
+}
+
+/// Whether the cue moves some of `attributes` by a random amount each time
+/// it fires, and how.
+fn draw_variation(ui: &mut egui::Ui, variation: &mut Option<Variation>, attributes: &[Attribute]) {
+    let mut on = variation.is_some();
+    if ui
+        .checkbox(&mut on, tr!("cue-editor-vary-each"))
+        .on_hover_text(tr!("cue-editor-vary-hint"))
+        .changed()
+    {
+        *variation = on.then(Variation::default);
+    }
+    let Some(variation) = variation else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        ui.label(tr!("cue-editor-vary-by"));
+        ui.add(egui::DragValue::new(&mut variation.range).range(1..=255));
+        ui.label(tr!("cue-editor-vary-steps"));
+    });
+    ui.horizontal_wrapped(|ui| {
+        for &attribute in attributes {
+            let mut varied = variation.attributes.contains(&attribute);
+            if ui.checkbox(&mut varied, attribute.to_string()).changed() {
+                if varied {
+                    variation.attributes.push(attribute);
+                } else {
+                    variation.attributes.retain(|&a| a != attribute);
+                }
+            }
+        }
+    });
+    if !attributes
+        .iter()
+        .any(|attribute| variation.attributes.contains(attribute))
+    {
+        ui.weak(tr!("cue-editor-vary-tick"));
+    }
+    ui.checkbox(
+        &mut variation.per_fixture,
+        tr!("cue-editor-vary-per-fixture"),
+    );
+    ui.horizontal(|ui| {
+        let mut seeded = variation.seed.is_some();
+        if ui
+            .checkbox(&mut seeded, tr!("cue-editor-vary-seed"))
+            .on_hover_text(tr!("cue-editor-vary-seed-hint"))
+            .changed()
+        {
+            variation.seed = seeded.then_some(1);
+        }
+        if let Some(seed) = &mut variation.seed {
+            ui.add(egui::DragValue::new(seed));
+        }
+    });
+}
+
+/// Picks the show variable a cue depends on, if any, and the value it
+/// needs.
+/// Whether the cue fires at its time or at a bar and beat.
+fn draw_beat(ui: &mut egui::Ui, at_beat: &mut Option<BarBeat>, signature: TimeSignature) {
+    ui.horizontal(|ui| {
+        let mut on_beat = at_beat.is_some();
+        if ui
+            .checkbox(&mut on_beat, tr!("cue-editor-bar"))
+            .on_hover_text(tr!("cue-editor-bar-hint"))
+            .changed()
+        {
+            *at_beat = on_beat.then_some(BarBeat { bar: 1, beat: 1 });
+        }
+        if let Some(at) = at_beat {
+            ui.add(egui::DragValue::new(&mut at.bar).range(1..=9999));
+            ui.label(tr!("cue-editor-beat"));
+            ui.add(egui::DragValue::new(&mut at.beat).range(1..=signature.beats_per_bar()));
+        }
+    });
+}
+
+fn draw_condition(
+    ui: &mut egui::Ui,
+    condition: &mut Option<Condition>,
+    variables: &BTreeMap<String, Value>,
+) {
+    ui.horizontal(|ui| {
+        let selected = condition
+            .as_ref()
+            .map_or_else(|| tr!("cue-editor-always"), |c| c.variable.clone());
+        egui::ComboBox::from_id_salt("cue_condition")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                if ui
+                    .selectable_label(condition.is_none(), tr!("cue-editor-always"))
+                    .clicked()
+                {
+                    *condition = None;
+                }
+                for (name, value) in variables {
+                    let current = condition.as_ref().is_some_and(|c| &c.variable == name);
+                    if ui.selectable_label(current, name).clicked() && !current {
+                        *condition = Some(Condition {
+                            variable: name.clone(),
+                            comparison: Comparison::Is,
+                            value: value.clone(),
+                        });
+                    }
+                }
+            });
+        if variables.is_empty() && condition.is_none() {
+            ui.weak(tr!("cue-editor-add-variables"));
+        }
+        let Some(condition) = condition else {
+            return;
+        };
+        egui::ComboBox::from_id_salt("cue_condition_comparison")
+            .selected_text(condition.comparison.to_string())
+            .width(40.0)
+            .show_ui(ui, |ui| {
+                for comparison in Comparison::ALL {
+                    ui.selectable_value(
+                        &mut condition.comparison,
+                        comparison,
+                        comparison.to_string(),
+                    );
+                }
+            });
+        let mut text = condition.value.text();
+        if ui
+            .add(egui::TextEdit::singleline(&mut text).desired_width(80.0))
+            .changed()
+        {
+            condition.value = Value::parse(&text);
+        }
+    });
+}
+
+/// The levels the cue holds, gobo and colour wheels picked by slot name.
+fn draw_values(ui: &mut egui::Ui, cue: &mut Cue, fixtures: &[Fixture], images: &mut SlotImages) {
+    ui.horizontal(|ui| {
+        ui.strong(tr!("cue-editor-values"));
+        ui.menu_button("+", |ui| {
+            for fixture in fixtures {
+                let missing: Vec<_> = fixture
+                    .profile
+                    .channels
+                    .iter()
+                    .copied()
+                    .filter(|&attribute| {
+                        !cue.values
+                            .iter()
+                            .any(|v| v.fixture_id == fixture.id && v.attribute == attribute)
+                    })
+                    .collect();
+                if missing.is_empty() {
+                    continue;
+                }
+                ui.menu_button(&fixture.name, |ui| {
+                    for attribute in missing {
+                        if ui.button(attribute.to_string()).clicked() {
+                            let value = fixture
+                                .profile
+                                .slots(attribute)
+                                .first()
+                                .map_or(0, |slot| slot.value);
+                            cue.values.push(CueValue {
+                                fixture_id: fixture.id,
+                                attribute,
+                                value,
+                            });
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+        })
+        .response
+        .on_hover_text(tr!("cue-editor-add-value"));
+    });
+    let mut remove = None;
+    egui::CollapsingHeader::new(tr!("cue-editor-values-count", count = cue.values.len()))
+        .id_salt("cue_values")
+        .show(ui, |ui| {
+            egui::Grid::new("cue_values_grid")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (i, value) in cue.values.iter_mut().enumerate() {
+                        let Some(fixture) = fixtures.iter().find(|f| f.id == value.fixture_id)
+                        else {
+                            continue;
+                        };
+                        ui.label(&fixture.name);
+                        ui.label(value.attribute.to_string());
+                        wheel::draw_level_picker(
+                            ui,
+                            ("cue_value", i),
+                            &fixture.profile,
+                            value.attribute,
+                            &mut value.value,
+                            images,
+                        );
+                        if accessibility::named(ui.small_button("🗑"), tr!("remove"))
+                            .on_hover_text(tr!("remove"))
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    if let Some(i) = remove {
+        cue.values.remove(i);
+    }
+}
+
+/// A fade and a delay for each attribute group under a column each. Groups
+/// left at the cue's fade with no delay have no timing of their own.
+fn draw_group_timing(ui: &mut egui::Ui, cue: &mut Cue) {
+    ui.horizontal(|ui| {
+        ui.strong(tr!("cue-editor-timing"));
+        if !cue.timing.is_empty() && ui.small_button(tr!("transport-reset")).clicked() {
+            cue.timing.clear();
+        }
+    });
+    egui::Grid::new("cue_group_timing")
+        .num_columns(AttributeGroup::ALL.len() + 1)
+        .show(ui, |ui| {
+            ui.label("");
+            for group in AttributeGroup::ALL {
+                ui.label(group.to_string());
+            }
+            ui.end_row();
+            for fade_row in [true, false] {
+                ui.label(if fade_row { tr!("fade") } else { tr!("delay") });
+                for group in AttributeGroup::ALL {
+                    let (delay, fade) = cue.group_timing(group);
+                    let time = if fade_row { fade } else { delay };
+                    let mut secs = time.as_secs_f32();
+                    let response = ui.add(
+                        egui::DragValue::new(&mut secs)
+                            .range(0.0..=600.0)
+                            .speed(0.1)
+                            .max_decimals(1)
+                            .suffix(tr!("unit-seconds")),
+                    );
+                    if response.changed() {
+                        let time = Duration::from_secs_f32(secs);
+                        if fade_row {
+                            cue.set_group_timing(group, delay, time);
+                        } else {
+                            cue.set_group_timing(group, time, fade);
+                        }
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    if cue.timing.is_empty() {
+        ui.weak(tr!("cue-editor-timing-hint"));
+    }
+}
+
+/// Splits the cue's timing: each part fades its fixtures in its own time,
+/// and fixtures in no part take the cue's duration.
+fn draw_parts(ui: &mut egui::Ui, cue: &mut Cue, fixtures: &[Fixture]) {
+    ui.horizontal(|ui| {
+        ui.strong(tr!("cue-editor-parts"));
+        if accessibility::named(ui.small_button("+"), tr!("cue-editor-add-part"))
+            .on_hover_text(tr!("cue-editor-add-part"))
+            .clicked()
+        {
+            cue.parts.push(CuePart {
+                name: tr!("cue-editor-part", number = cue.parts.len() + 1),
+                fixtures: Vec::new(),
+                delay: Duration::ZERO,
+                fade: cue.duration,
+            });
+        }
+    });
+    let mut in_cue: Vec<&Fixture> = fixtures
+        .iter()
+        .filter(|fixture| cue.values.iter().any(|v| v.fixture_id == fixture.id))
+        .collect();
+    in_cue.sort_by_key(|fixture| fixture.id);
+    let mut remove = None;
+    for (i, part) in cue.parts.iter_mut().enumerate() {
+        ui.push_id(i, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut part.name).desired_width(70.0));
+                let label = tr!("fixture-count", count = part.fixtures.len());
+                ui.menu_button(label, |ui| {
+                    if in_cue.is_empty() {
+                        ui.label(tr!("cue-editor-no-levels"));
+                    }
+                    for fixture in &in_cue {
+                        let mut selected = part.fixtures.contains(&fixture.id);
+                        if ui.checkbox(&mut selected, &fixture.name).changed() {
+                            part.fixtures.retain(|&id| id != fixture.id);
+                            if selected {
+                                part.fixtures.push(fixture.id);
+                            }
+                        }
+                    }
+                });
+                if accessibility::named(ui.small_button("🗑"), tr!("remove"))
+                    .on_hover_text(tr!("remove"))
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+            });
+            ui.horizontal(|ui| {
+                for (label, time) in [
+                    (tr!("delay"), &mut part.delay),
+                    (tr!("fade"), &mut part.fade),
+                ] {
+                    ui.label(label);
+                    let mut secs = time.as_secs_f32();
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut secs)
+                                .range(0.0..=600.0)
+                                .speed(0.1)
+                                .max_decimals(1)
+                                .suffix(tr!("unit-seconds")),
+                        )
+                        .changed()
+                    {
+                        *time = Duration::from_secs_f32(secs);
+                    }
+                }
+            });
+        });
+    }
+    if let Some(i) = remove {
+        cue.parts.remove(i);
+    }
+    let timed: Vec<usize> = cue
+        .parts
+        .iter()
+        .flat_map(|part| part.fixtures.iter().copied())
+        .collect();
+    if !cue.parts.is_empty() {
+        let rest = in_cue
+            .iter()
+            .filter(|fixture| !timed.contains(&fixture.id))
+            .count();
+        ui.weak(tr!(
+            "cue-editor-rest",
+            count = rest,
+            seconds = format!("{:.1}", cue.duration.as_secs_f32())
+        ));
+    }
+}
+
+/// A fade curve picker, with the control points of a custom curve and a
+/// small preview of its shape.
+fn draw_fade_curve(ui: &mut egui::Ui, id: &str, curve: &mut FadeCurve) -> egui::Response {
+    ui.horizontal(|ui| {
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(40.0, 24.0), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+        let points = (0..=20)
+            .map(|i| {
+                let x = i as f32 / 20.0;
+                egui::pos2(
+                    rect.left() + x * rect.width(),
+                    rect.bottom() - curve.apply(x) * rect.height(),
+                )
+            })
+            .collect();
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, ui.visuals().text_color()),
+        ));
+
+        ui.vertical(|ui| {
+            egui::ComboBox::from_id_salt(id)
+                .selected_text(curve.to_string())
+                .width(80.0)
+                .show_ui(ui, |ui| {
+                    for preset in FadeCurve::PRESETS {
+                        ui.selectable_value(curve, preset, preset.to_string());
+                    }
+                    let custom = matches!(curve, FadeCurve::Bezier { .. });
+                    if ui
+                        .selectable_label(custom, tr!("cue-editor-custom-curve"))
+                        .clicked()
+                        && !custom
+                    {
+                        *curve = FadeCurve::CUSTOM;
+                    }
+                });
+            if let FadeCurve::Bezier { x1, y1, x2, y2 } = curve {
+                ui.horizontal(|ui| {
+                    for value in [x1, y1, x2, y2] {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .range(0.0..=1.0)
+                                .speed(0.01)
+                                .max_decimals(2),
+                        );
+                    }
+                });
+            }
+        });
+        response
+    })
+    .inner
+}
+
+/// The triggers a cue sends as it starts and ends. Returns a trigger to send
+/// now if its Test button was pressed.
+fn draw_triggers(ui: &mut egui::Ui, triggers: &mut Vec<CueTrigger>) -> Option<TriggerAction> {
+    ui.horizontal(|ui| {
+        ui.strong(tr!("cue-editor-triggers"));
+        if accessibility::named(ui.small_button("+"), tr!("cue-editor-add-trigger"))
+            .on_hover_text(tr!("cue-editor-add-trigger"))
+            .clicked()
+        {
+            triggers.push(CueTrigger::default());
+        }
+    });
+    let mut remove = None;
+    let mut test_fire = None;
+    for (i, trigger) in triggers.iter_mut().enumerate() {
+        ui.push_id(i, |ui| {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("when")
+                    .selected_text(trigger.when.to_string())
+                    .width(70.0)
+                    .show_ui(ui, |ui| {
+                        for when in TriggerWhen::ALL {
+                            ui.selectable_value(&mut trigger.when, when, when.to_string());
+                        }
+                    });
+                egui::ComboBox::from_id_salt("kind")
+                    .selected_text(trigger.action.kind())
+                    .width(100.0)
+                    .show_ui(ui, |ui| {
+                        for kind in TriggerAction::kinds() {
+                            let selected = kind.kind() == trigger.action.kind();
+                            if ui.selectable_label(selected, kind.kind()).clicked() && !selected {
+                                trigger.action = kind;
+                            }
+                        }
+                    });
+                if ui
+                    .small_button(tr!("cue-editor-test"))
+                    .on_hover_text(tr!("cue-editor-test-hint"))
+                    .clicked()
+                {
+                    test_fire = Some(trigger.action.clone());
+                }
+                if accessibility::named(ui.small_button("🗑"), tr!("remove"))
+                    .on_hover_text(tr!("remove"))
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+            });
+            ui.horizontal(|ui| match &mut trigger.action {
+                TriggerAction::MidiNote {
+                    channel,
+                    note,
+                    velocity,
+                } => {
+                    ui.label(tr!("binding-channel"));
+                    ui.add(egui::DragValue::new(channel).range(1..=16));
+                    ui.label(tr!("binding-note"));
+                    ui.add(egui::DragValue::new(note).range(0..=127));
+                    ui.label(tr!("cue-editor-velocity"));
+                    ui.add(egui::DragValue::new(velocity).range(0..=127));
+                }
+                TriggerAction::MidiProgram { channel, program } => {
+                    ui.label(tr!("binding-channel"));
+                    ui.add(egui::DragValue::new(channel).range(1..=16));
+                    ui.label(tr!("binding-program"));
+                    ui.add(egui::DragValue::new(program).range(0..=127));
+                }
+                TriggerAction::Osc {
+                    target,
+                    address,
+                    args,
+                } => {
+                    ui.add(
+                        egui::TextEdit::singleline(target)
+                            .hint_text("host:port")
+                            .desired_width(110.0),
+                    );
+                    ui.add(egui::TextEdit::singleline(address).desired_width(90.0));
+                    ui.add(
+                        egui::TextEdit::singleline(args)
+                            .hint_text(tr!("cue-editor-osc-args"))
+                            .desired_width(70.0),
+                    );
+                }
+                TriggerAction::Webhook { method, url, body } => {
+                    egui::ComboBox::from_id_salt("method")
+                        .selected_text(method.as_str())
+                        .width(60.0)
+                        .show_ui(ui, |ui| {
+                            for verb in ["GET", "POST"] {
+                                ui.selectable_value(method, verb.to_string(), verb);
+                            }
+                        });
+                    ui.add(egui::TextEdit::singleline(url).desired_width(140.0));
+                    if method == "POST" {
+                        ui.add(
+                            egui::TextEdit::singleline(body)
+                                .hint_text(tr!("cue-editor-json-body"))
+                                .desired_width(80.0),
+                        );
+                    }
+                }
+                TriggerAction::Serial { port, baud, data } => {
+                    ui.add(egui::TextEdit::singleline(port).desired_width(90.0));
+                    egui::ComboBox::from_id_salt("baud")
+                        .selected_text(baud.to_string())
+                        .width(60.0)
+                        .show_ui(ui, |ui| {
+                            for rate in hardware::BAUD_RATES {
+                                ui.selectable_value(baud, rate, rate.to_string());
+                            }
+                        });
+                    ui.add(
+                        egui::TextEdit::singleline(data)
+                            .hint_text(tr!("cue-editor-serial-hint"))
+                            .desired_width(100.0),
+                    )
+                    .on_hover_text(tr!("cue-editor-serial-data-hint"));
+                }
+                TriggerAction::Relay {
+                    port,
+                    relay,
+                    on,
+                    pulse,
+                } => {
+                    ui.add(egui::TextEdit::singleline(port).desired_width(90.0));
+                    ui.label(tr!("cue-editor-relay"));
+                    ui.add(egui::DragValue::new(relay).range(1..=8));
+                    ui.checkbox(on, tr!("cue-editor-relay-on"));
+                    pulse_edit(ui, pulse);
+                }
+                TriggerAction::Gpio { pin, high, pulse } => {
+                    ui.label(tr!("cue-editor-pin"));
+                    ui.add(egui::DragValue::new(pin).range(0..=1023));
+                    ui.checkbox(high, tr!("cue-editor-pin-high"));
+                    pulse_edit(ui, pulse);
+                }
+            });
+        });
+    }
+    if let Some(i) = remove {
+        triggers.remove(i);
+    }
+    test_fire
+}
+
+/// How long a relay or pin stays switched before switching back; zero leaves
+/// it switched.
+fn pulse_edit(ui: &mut egui::Ui, pulse: &mut Duration) {
+    ui.label(tr!("cue-editor-pulse"));
+    let mut secs = pulse.as_secs_f32();
+    if ui
+        .add(
+            egui::DragValue::new(&mut secs)
+                .range(0.0..=60.0)
+                .speed(0.05)
+                .max_decimals(2)
+                .suffix(tr!("unit-seconds")),
+        )
+        .on_hover_text(tr!("cue-editor-pulse-hint"))
+        .changed()
+    {
+        *pulse = Duration::from_secs_f32(secs);
+    }
+}
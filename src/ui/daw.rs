@@ -0,0 +1,186 @@
+use crate::daw::{self, DawFormat, DawMarker};
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::HaloApp;
+use eframe::egui;
+use std::fs;
+use std::path::Path;
+
+/// Frame rates Pro Tools timecode locations can be read and written at.
+const FRAME_RATES: [u32; 3] = [24, 25, 30];
+
+/// The marker files being exported and imported.
+pub(crate) struct DawTool {
+    format: DawFormat,
+    export_path: String,
+    import_path: String,
+    fps: u32,
+    /// Markers read from the import file, waiting to be applied.
+    markers: Vec<DawMarker>,
+    error: Option<String>,
+}
+
+impl Default for DawTool {
+    fn default() -> Self {
+        Self {
+            format: DawFormat::Reaper,
+            export_path: format!("markers.{}", DawFormat::Reaper.extension()),
+            import_path: String::new(),
+            fps: 25,
+            markers: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl HaloApp {
+    /// Exports the cue times as a DAW marker list and reads one back.
+    pub(crate) fn draw_daw_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_daw;
+        let mut export = false;
+        let mut read = false;
+        let mut apply = false;
+        egui::Window::new(tr!("daw-title"))
+            .id(egui::Id::new("daw"))
+            .open(&mut open)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.label(tr!("daw-description"));
+                let tool = &mut self.daw_tool;
+                egui::Grid::new("daw_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("report-format"));
+                    let previous = tool.format;
+                    egui::ComboBox::from_id_salt("daw_format")
+                        .selected_text(tool.format.to_string())
+                        .show_ui(ui, |ui| {
+                            for format in DawFormat::ALL {
+                                ui.selectable_value(&mut tool.format, format, format.to_string());
+                            }
+                        });
+                    if tool.format != previous {
+                        tool.export_path = Path::new(&tool.export_path)
+                            .with_extension(tool.format.extension())
+                            .to_string_lossy()
+                            .into_owned();
+                    }
+                    ui.end_row();
+
+                    ui.label(tr!("daw-timecode"));
+                    egui::ComboBox::from_id_salt("daw_fps")
+                        .selected_text(tr!("unit-fps", fps = tool.fps.to_string()))
+                        .show_ui(ui, |ui| {
+                            for fps in FRAME_RATES {
+                                ui.selectable_value(
+                                    &mut tool.fps,
+                                    fps,
+                                    tr!("unit-fps", fps = fps.to_string()),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr!("daw-fps-hint"));
+                    ui.end_row();
+
+                    ui.label(tr!("export-to"));
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut tool.export_path);
+                        export = ui.button(tr!("report-export")).clicked();
+                    });
+                    ui.end_row();
+
+                    ui.label(tr!("import-from"));
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut tool.import_path);
+                        read = ui.button(tr!("daw-read")).clicked();
+                    });
+                    ui.end_row();
+                });
+                if let Some(error) = &tool.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                if tool.markers.is_empty() {
+                    return;
+                }
+
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .max_height(260.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("daw_markers")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong(tr!("daw-marker"));
+                                ui.strong(tr!("inspector-time"));
+                                ui.strong(tr!("daw-does"));
+                                ui.end_row();
+                                for marker in &tool.markers {
+                                    ui.label(&marker.name);
+                                    ui.label(Self::format_duration(marker.time));
+                                    let cue = marker.cue_number().and_then(|number| {
+                                        self.show.cues.iter().find(|cue| cue.number == number)
+                                    });
+                                    match cue {
+                                        Some(cue) if self.show.cue_start(cue) == marker.time => ui
+                                            .weak(tr!(
+                                                "daw-already-there",
+                                                number = cue.number.to_string()
+                                            )),
+                                        Some(cue) => ui.label(tr!(
+                                            "daw-moves",
+                                            number = cue.number.to_string(),
+                                            from = Self::format_duration(self.show.cue_start(cue))
+                                        )),
+                                        None => ui.label(tr!("daw-new-cue")),
+                                    };
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                apply = ui.button(tr!("daw-apply")).clicked();
+            });
+        self.show_daw = open;
+
+        if export {
+            let tool = &mut self.daw_tool;
+            let text = daw::export(&self.show, tool.format, tool.fps);
+            let path = tool.export_path.trim().to_string();
+            match fs::write(&path, text) {
+                Ok(()) => {
+                    tool.error = None;
+                    self.log.info(tr!(
+                        "daw-exported",
+                        count = self.show.cues.len(),
+                        path = path.as_str()
+                    ));
+                }
+                Err(err) => tool.error = Some(tr!("daw-export-failed", error = err.to_string())),
+            }
+        }
+        if read {
+            let tool = &mut self.daw_tool;
+            match daw::load(
+                Path::new(tool.import_path.trim()),
+                tool.fps,
+                self.show.timecode.start,
+            ) {
+                Ok(markers) => {
+                    tool.markers = markers;
+                    tool.error = None;
+                }
+                Err(err) => {
+                    tool.markers.clear();
+                    tool.error = Some(tr!("daw-read-failed", error = err.to_string()));
+                }
+            }
+        }
+        if apply {
+            let markers = std::mem::take(&mut self.daw_tool.markers);
+            let (moved, added) = self.apply_daw_markers(&markers);
+            self.notify(
+                LogLevel::Info,
+                tr!("daw-applied", moved = moved, added = added),
+            );
+        }
+    }
+}
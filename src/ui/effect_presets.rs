@@ -0,0 +1,225 @@
+use crate::effect_preset::{self, EffectPreset};
+use crate::i18n::tr;
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+use std::path::Path;
+
+/// The effect preset library as last read, and the files being imported
+/// and exported.
+#[derive(Default)]
+pub(crate) struct EffectPresetTool {
+    presets: Vec<EffectPreset>,
+    /// Whether `presets` has been read since the window opened.
+    loaded: bool,
+    name: String,
+    category: String,
+    import_path: String,
+    export_path: String,
+    error: Option<String>,
+}
+
+enum PresetAction {
+    Apply(usize),
+    Export(usize),
+    Remove(usize),
+    Save,
+    Import,
+}
+
+impl HaloApp {
+    /// The effect preset library, grouped by category: presets apply to the
+    /// selected pixel map, which can be saved as a new one, and preset files
+    /// import into and export out of the library.
+    pub(crate) fn draw_effect_presets_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_effect_presets;
+        if !open {
+            self.effect_presets.loaded = false;
+            return;
+        }
+        let tool = &mut self.effect_presets;
+        if !tool.loaded {
+            tool.presets = effect_preset::preset_library();
+            tool.loaded = true;
+        }
+        let map = self
+            .selected_pixel_map
+            .and_then(|index| self.show.pixel_maps.get(index));
+        let mut action = None;
+        egui::Window::new(tr!("presets-title"))
+            .id(egui::Id::new("effect_presets"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                match map {
+                    Some(map) => ui.label(tr!("presets-applies-to", name = map.name.as_str())),
+                    None => ui.label(tr!("presets-select-map")),
+                };
+                ui.separator();
+                if tool.presets.is_empty() {
+                    ui.weak(tr!("presets-empty"));
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(260.0)
+                    .show(ui, |ui| {
+                        let mut index = 0;
+                        while index < tool.presets.len() {
+                            let category = tool.presets[index].category.clone();
+                            let end = tool.presets[index..]
+                                .iter()
+                                .position(|preset| preset.category != category)
+                                .map_or(tool.presets.len(), |len| index + len);
+                            let heading = if category.is_empty() {
+                                tr!("presets-uncategorised")
+                            } else {
+                                category.clone()
+                            };
+                            egui::CollapsingHeader::new(heading)
+                                .default_open(true)
+                                .show(ui, |ui| {
+                                    for i in index..end {
+                                        let preset = &tool.presets[i];
+                                        ui.horizontal(|ui| {
+                                            ui.label(&preset.name);
+                                            ui.weak(tr!(
+                                                "presets-generator-at",
+                                                generator = preset.generator.to_string(),
+                                                speed = format!("{:.2}", preset.speed)
+                                            ));
+                                            if ui
+                                                .add_enabled(
+                                                    map.is_some(),
+                                                    egui::Button::new(tr!("apply")).small(),
+                                                )
+                                                .clicked()
+                                            {
+                                                action = Some(PresetAction::Apply(i));
+                                            }
+                                            if ui
+                                                .small_button(tr!("report-export"))
+                                                .on_hover_text(tr!("presets-export-hint"))
+                                                .clicked()
+                                            {
+                                                action = Some(PresetAction::Export(i));
+                                            }
+                                            if accessibility::named(
+                                                ui.small_button("🗑"),
+                                                tr!("delete"),
+                                            )
+                                            .clicked()
+                                            {
+                                                action = Some(PresetAction::Remove(i));
+                                            }
+                                        });
+                                    }
+                                });
+                            index = end;
+                        }
+                    });
+                ui.separator();
+                egui::Grid::new("effect_preset_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("name-label"));
+                        ui.text_edit_singleline(&mut tool.name);
+                        ui.end_row();
+                        ui.label(tr!("presets-category"));
+                        ui.text_edit_singleline(&mut tool.category);
+                        ui.end_row();
+                        ui.label("");
+                        if ui
+                            .add_enabled(
+                                map.is_some() && !tool.name.trim().is_empty(),
+                                egui::Button::new(tr!("presets-save-from-map")),
+                            )
+                            .clicked()
+                        {
+                            action = Some(PresetAction::Save);
+                        }
+                        ui.end_row();
+                        ui.label(tr!("import-from"));
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut tool.import_path);
+                            if ui.button(tr!("import")).clicked() {
+                                action = Some(PresetAction::Import);
+                            }
+                        });
+                        ui.end_row();
+                        ui.label(tr!("export-to"));
+                        ui.text_edit_singleline(&mut tool.export_path);
+                        ui.end_row();
+                    });
+                if let Some(error) = &tool.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+        self.show_effect_presets = open;
+
+        if let Some(action) = action {
+            self.apply_preset_action(action);
+        }
+    }
+
+    fn apply_preset_action(&mut self, action: PresetAction) {
+        let tool = &mut self.effect_presets;
+        tool.error = None;
+        let result = match action {
+            PresetAction::Apply(i) => {
+                let map = self
+                    .selected_pixel_map
+                    .and_then(|index| self.show.pixel_maps.get_mut(index));
+                if let Some(map) = map {
+                    tool.presets[i].apply(map);
+                    self.log.info(tr!(
+                        "presets-applied",
+                        preset = tool.presets[i].name.as_str(),
+                        map = map.name.as_str()
+                    ));
+                }
+                Ok(())
+            }
+            PresetAction::Export(i) => {
+                let path = tool.export_path.trim().to_string();
+                if path.is_empty() {
+                    tool.export_path = tool.presets[i].file_name();
+                    tool.error = Some(tr!("presets-choose-file"));
+                    return;
+                }
+                tool.presets[i].save(Path::new(&path)).map(|()| {
+                    self.log.info(tr!(
+                        "presets-exported",
+                        preset = tool.presets[i].name.as_str(),
+                        path = path.as_str()
+                    ));
+                })
+            }
+            PresetAction::Remove(i) => effect_preset::remove_preset(&tool.presets[i]),
+            PresetAction::Save => {
+                let Some(map) = self
+                    .selected_pixel_map
+                    .and_then(|index| self.show.pixel_maps.get(index))
+                else {
+                    return;
+                };
+                let mut preset = EffectPreset::from_map(tool.name.trim(), map);
+                preset.category = tool.category.trim().to_string();
+                effect_preset::install_preset(&preset).map(|()| {
+                    self.log
+                        .info(tr!("presets-saved", preset = preset.name.as_str()));
+                    tool.name.clear();
+                })
+            }
+            PresetAction::Import => EffectPreset::load(Path::new(tool.import_path.trim()))
+                .and_then(|preset| {
+                    effect_preset::install_preset(&preset)?;
+                    self.log
+                        .info(tr!("presets-imported", preset = preset.name.as_str()));
+                    Ok(())
+                }),
+        };
+        if let Err(err) = result {
+            tool.error = Some(err.to_string());
+        }
+        tool.loaded = false;
+    }
+}
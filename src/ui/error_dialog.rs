@@ -0,0 +1,134 @@
+use crate::error::{Device, HaloError};
+use crate::i18n::tr;
+use crate::ui::preferences::PreferencesSection;
+use crate::ui::show_file::{FileAction, FileDialog};
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    /// Logs `error` and puts it in front of the operator, after any already
+    /// waiting. The same failure again while it is still up isn't repeated.
+    pub(crate) fn report_error(&mut self, error: HaloError) {
+        self.log.error(error.to_string());
+        if !self.errors.contains(&error) {
+            self.errors.push_back(error);
+        }
+    }
+
+    /// A device error that sorted itself out, such as a MIDI port plugged
+    /// in after startup, no longer needs the operator.
+    pub(crate) fn clear_device_error(&mut self, device: Device) {
+        self.errors.retain(
+            |error| !matches!(error, HaloError::Device { device: failed, .. } if *failed == device),
+        );
+    }
+
+    /// Opens the show again, or the device again from the preferences as
+    /// they are now.
+    fn retry(&mut self, ctx: &egui::Context, error: HaloError) {
+        match error {
+            HaloError::ShowFile { path, .. } => match self.open_show(&path) {
+                Ok(()) => {
+                    self.log
+                        .info(tr!("error-opened", path = path.display().to_string()));
+                    self.set_show_path(path);
+                }
+                Err(err) => self.report_error(HaloError::show_file(path, err)),
+            },
+            HaloError::Device { device, .. } => match device {
+                Device::AudioInput => self.apply_audio_preferences(),
+                Device::Click => self.apply_click_preferences(),
+                Device::Playback => self.apply_playback_preferences(),
+                Device::MidiInput => {
+                    self.midi_in = None;
+                    self.midi_error = None;
+                    self.connect_midi_input(ctx);
+                }
+                Device::MidiOutput => {
+                    self.midi_out = None;
+                    self.midi_error = None;
+                    self.connect_midi_output();
+                }
+                Device::Buttons => self.apply_hid_preferences(ctx),
+            },
+        }
+    }
+
+    /// Where to pick something else: the file dialog at the show that
+    /// failed, or Preferences at the device's section.
+    fn choose_another(&mut self, error: &HaloError) {
+        match error {
+            HaloError::ShowFile { path, .. } => {
+                self.file_dialog = Some(FileDialog::new(FileAction::Load, Some(path)));
+            }
+            HaloError::Device { device, .. } => {
+                let section = match device {
+                    Device::AudioInput | Device::Click | Device::Playback => {
+                        PreferencesSection::Audio
+                    }
+                    Device::MidiInput | Device::MidiOutput => PreferencesSection::Midi,
+                    Device::Buttons => PreferencesSection::RemoteControl,
+                };
+                self.open_preferences_at(section);
+            }
+        }
+    }
+
+    /// The oldest failure waiting, with what can be done about it.
+    pub(crate) fn draw_error_dialog(&mut self, ctx: &egui::Context) {
+        let Some(error) = self.errors.front().cloned() else {
+            return;
+        };
+        let mut retry = false;
+        let mut another = false;
+        let mut dismiss = false;
+        egui::Window::new(error.title())
+            .id(egui::Id::new("error_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_max_width(420.0);
+                match &error {
+                    HaloError::ShowFile { path, message } => {
+                        ui.label(tr!("error-not-a-show", path = path.display().to_string()));
+                        ui.colored_label(egui::Color32::RED, message);
+                        ui.label(tr!("error-show-still-open"));
+                    }
+                    HaloError::Device {
+                        device,
+                        name,
+                        message,
+                    } => {
+                        ui.label(tr!(
+                            "error-device-failed",
+                            device = device.to_string(),
+                            name = name.as_str()
+                        ));
+                        ui.colored_label(egui::Color32::RED, message);
+                        ui.label(tr!("error-device-hint", device = device.to_string()));
+                    }
+                }
+                if self.errors.len() > 1 {
+                    ui.weak(tr!("error-more", count = self.errors.len() - 1));
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    retry = ui.button(tr!("error-retry")).clicked();
+                    another = ui.button(error.alternative()).clicked();
+                    dismiss = ui.button(tr!("error-dismiss")).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape));
+                });
+            });
+
+        if !(retry || another || dismiss) {
+            return;
+        }
+        self.errors.pop_front();
+        if retry {
+            self.retry(ctx, error);
+        } else if another {
+            self.choose_another(&error);
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use crate::fan::{Fan, FanMode, FanOrder, FanTarget};
+use crate::fixture::Fixture;
+use crate::hue_shift::HueShift;
+use crate::i18n::tr;
+use crate::speed_master::SpeedMaster;
+use crate::tracking;
+use crate::ui::speed_masters::speed_master_combo;
+use crate::ui::tracksheet::draw_edit_mode;
+use crate::HaloApp;
+use eframe::egui;
+
+/// The fan window's settings, kept between uses.
+pub(crate) struct FanTool {
+    /// Ids of the fixtures to fan across.
+    selected: Vec<usize>,
+    target: FanTarget,
+    mode: FanMode,
+    order: FanOrder,
+    from: f32,
+    to: f32,
+    colors: [[u8; 3]; 2],
+    seed: u64,
+    reverse: bool,
+    wrap: bool,
+}
+
+impl Default for FanTool {
+    fn default() -> Self {
+        Self {
+            selected: Vec::new(),
+            target: FanTarget::Hue,
+            mode: FanMode::Linear,
+            order: FanOrder::Patch,
+            from: 0.0,
+            to: 360.0,
+            colors: [[255, 0, 0], [0, 0, 255]],
+            seed: 1,
+            reverse: false,
+            wrap: false,
+        }
+    }
+}
+
+/// What the patch can be fanned on: hue if any fixture has RGB, colour
+/// temperature if any can make white, then every attribute patched.
+fn targets(fixtures: &[Fixture]) -> Vec<FanTarget> {
+    let mut attributes: Vec<_> = fixtures
+        .iter()
+        .flat_map(|fixture| fixture.profile.channels.iter().copied())
+        .collect();
+    attributes.sort();
+    attributes.dedup();
+    let mut targets: Vec<FanTarget> = attributes.into_iter().map(FanTarget::Attribute).collect();
+    let has_rgb = fixtures
+        .iter()
+        .any(|fixture| FanTarget::Hue.applies_to(fixture));
+    let has_white = fixtures
+        .iter()
+        .any(|fixture| FanTarget::WhitePoint.applies_to(fixture));
+    if has_white {
+        targets.insert(0, FanTarget::WhitePoint);
+    }
+    if has_rgb {
+        targets.insert(0, FanTarget::Gradient);
+        targets.insert(0, FanTarget::Hue);
+    }
+    targets
+}
+
+/// The selected cue's hue shift, returning it if it was changed.
+fn draw_hue_shift(
+    ui: &mut egui::Ui,
+    mut shift: Option<HueShift>,
+    masters: &[SpeedMaster],
+) -> Option<Option<HueShift>> {
+    let before = shift;
+    let mut on = shift.is_some();
+    ui.checkbox(&mut on, tr!("fan-hue-shift"))
+        .on_hover_text(tr!("fan-hue-shift-hint"));
+    if on != shift.is_some() {
+        shift = on.then(HueShift::default);
+    }
+    if let Some(shift) = &mut shift {
+        ui.horizontal(|ui| {
+            ui.label(tr!("fan-hue-period"));
+            ui.add(
+                egui::DragValue::new(&mut shift.period)
+                    .range(0.1..=600.0)
+                    .speed(0.1)
+                    .suffix(tr!("unit-seconds")),
+            );
+            ui.checkbox(&mut shift.reverse, tr!("reverse"));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("speed-master-label"));
+            speed_master_combo(ui, "fan_hue_shift_speed", masters, &mut shift.speed_master);
+        });
+    }
+    (shift != before).then_some(shift)
+}
+
+impl HaloApp {
+    /// Spreads one attribute across a selection of fixtures in the selected
+    /// cue, and sets the cue's hue shift to chase a colour fan along them.
+    pub(crate) fn draw_fan_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_fan;
+        let mut apply = false;
+        let mut shift = None;
+        egui::Window::new(tr!("fan-title"))
+            .id(egui::Id::new("fan"))
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                let tool = &mut self.fan_tool;
+                match self
+                    .selected_cue
+                    .and_then(|index| self.show.cues.get(index))
+                {
+                    Some(cue) => ui.label(tr!(
+                        "fan-into-cue",
+                        number = cue.number.to_string(),
+                        name = cue.name.as_str()
+                    )),
+                    None => ui.label(tr!("fan-select-cue")),
+                };
+                ui.separator();
+
+                if ui
+                    .button(tr!("fan-rainbow"))
+                    .on_hover_text(tr!("fan-rainbow-hint"))
+                    .clicked()
+                {
+                    tool.target = FanTarget::Hue;
+                    tool.mode = FanMode::Linear;
+                    tool.from = 0.0;
+                    tool.to = 360.0;
+                    tool.wrap = true;
+                }
+                ui.horizontal(|ui| {
+                    ui.strong(tr!("fixtures"));
+                    if ui.small_button(tr!("all")).clicked() {
+                        tool.selected = self.show.fixtures.iter().map(|f| f.id).collect();
+                    }
+                    if ui.small_button(tr!("none")).clicked() {
+                        tool.selected.clear();
+                    }
+                });
+                let mut groups: Vec<&str> = self
+                    .show
+                    .fixtures
+                    .iter()
+                    .map(|fixture| fixture.profile.name.as_str())
+                    .collect();
+                groups.sort();
+                groups.dedup();
+                ui.horizontal_wrapped(|ui| {
+                    for group in groups {
+                        if ui
+                            .small_button(group)
+                            .on_hover_text(tr!("fan-select-type"))
+                            .clicked()
+                        {
+                            tool.selected = self
+                                .show
+                                .fixtures
+                                .iter()
+                                .filter(|fixture| fixture.profile.name == group)
+                                .map(|fixture| fixture.id)
+                                .collect();
+                        }
+                    }
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for fixture in &self.show.fixtures {
+                            let mut selected = tool.selected.contains(&fixture.id);
+                            if ui
+                                .checkbox(
+                                    &mut selected,
+                                    format!(
+                                        "{} ({}.{})",
+                                        fixture.name, fixture.universe, fixture.address
+                                    ),
+                                )
+                                .changed()
+                            {
+                                if selected {
+                                    tool.selected.push(fixture.id);
+                                } else {
+                                    tool.selected.retain(|&id| id != fixture.id);
+                                }
+                            }
+                        }
+                    });
+                ui.separator();
+
+                egui::Grid::new("fan_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("attribute-label"));
+                    egui::ComboBox::from_id_salt("fan_target")
+                        .selected_text(tool.target.to_string())
+                        .show_ui(ui, |ui| {
+                            for target in targets(&self.show.fixtures) {
+                                if ui
+                                    .selectable_value(&mut tool.target, target, target.to_string())
+                                    .changed()
+                                {
+                                    if target == FanTarget::WhitePoint {
+                                        // The whole selection to one white,
+                                        // unless asked to spread it.
+                                        tool.from = 3200.0;
+                                        tool.to = 3200.0;
+                                    } else {
+                                        tool.from = *target.range().start();
+                                        tool.to = *target.range().end();
+                                    }
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr!("fan-mode-label"));
+                    egui::ComboBox::from_id_salt("fan_mode")
+                        .selected_text(tool.mode.to_string())
+                        .show_ui(ui, |ui| {
+                            for mode in FanMode::ALL {
+                                ui.selectable_value(&mut tool.mode, mode, mode.to_string());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr!("fan-order-label"));
+                    egui::ComboBox::from_id_salt("fan_order")
+                        .selected_text(tool.order.to_string())
+                        .show_ui(ui, |ui| {
+                            for order in FanOrder::ALL {
+                                ui.selectable_value(&mut tool.order, order, order.to_string());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr!("fan-direction-label"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut tool.reverse, tr!("reverse"));
+                        ui.add_enabled(
+                            tool.mode == FanMode::Linear,
+                            egui::Checkbox::new(&mut tool.wrap, tr!("fan-wrap")),
+                        )
+                        .on_hover_text(tr!("fan-wrap-hint"));
+                    });
+                    ui.end_row();
+
+                    let range = tool.target.range();
+                    let suffix = match tool.target {
+                        FanTarget::Hue => "°",
+                        FanTarget::WhitePoint => "K",
+                        FanTarget::Attribute(_) | FanTarget::Gradient => "",
+                    };
+                    for (i, level) in [&mut tool.from, &mut tool.to].into_iter().enumerate() {
+                        ui.label(match (tool.mode == FanMode::Symmetrical, i) {
+                            (true, 0) => tr!("fan-middle"),
+                            (true, _) => tr!("fan-ends"),
+                            (false, 0) => tr!("fan-from"),
+                            (false, _) => tr!("fan-to"),
+                        });
+                        if tool.target == FanTarget::Gradient {
+                            ui.color_edit_button_srgb(&mut tool.colors[i]);
+                        } else {
+                            ui.add(
+                                egui::DragValue::new(level)
+                                    .range(range.clone())
+                                    .max_decimals(0)
+                                    .suffix(suffix),
+                            );
+                        }
+                        ui.end_row();
+                    }
+
+                    if tool.mode == FanMode::Random {
+                        ui.label(tr!("fan-seed"));
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut tool.seed));
+                            if ui
+                                .small_button("🎲")
+                                .on_hover_text(tr!("fan-another-seed"))
+                                .clicked()
+                            {
+                                tool.seed = tool.seed.wrapping_add(1);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+
+                draw_edit_mode(ui, &mut self.edit_mode);
+                apply = ui
+                    .add_enabled(
+                        self.selected_cue.is_some() && !tool.selected.is_empty(),
+                        egui::Button::new(tr!("apply")),
+                    )
+                    .clicked();
+
+                if let Some(cue) = self
+                    .selected_cue
+                    .and_then(|index| self.show.cues.get(index))
+                {
+                    ui.separator();
+                    shift = draw_hue_shift(ui, cue.hue_shift, &self.show.speed_masters);
+                }
+            });
+        self.show_fan = open;
+
+        if apply {
+            self.apply_fan();
+        }
+        if let (Some(changed), Some(index)) = (shift, self.selected_cue) {
+            self.show.cues[index].hue_shift = changed;
+        }
+    }
+
+    fn apply_fan(&mut self) {
+        let Some(index) = self.selected_cue else {
+            return;
+        };
+        let tool = &self.fan_tool;
+        let fan = Fan {
+            target: tool.target,
+            mode: tool.mode,
+            order: tool.order,
+            from: tool.from,
+            to: tool.to,
+            colors: tool.colors,
+            seed: tool.seed,
+            reverse: tool.reverse,
+            wrap: tool.wrap,
+        };
+        let fixtures: Vec<&Fixture> = self
+            .show
+            .fixtures
+            .iter()
+            .filter(|fixture| tool.selected.contains(&fixture.id))
+            .collect();
+        let order = self.show.playback_order();
+        let Some(count) =
+            tracking::edit_cue(&mut self.show.cues, &order, index, self.edit_mode, |cue| {
+                fan.apply(cue, &fixtures)
+            })
+        else {
+            return;
+        };
+        let number = self.show.cues[index].number;
+        if count == 0 {
+            self.log
+                .warn(tr!("fan-no-fixtures", target = fan.target.to_string()));
+        } else {
+            self.log.info(tr!(
+                "fan-done",
+                target = fan.target.to_string(),
+                count = count,
+                number = number.to_string()
+            ));
+        }
+    }
+}
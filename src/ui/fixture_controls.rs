@@ -0,0 +1,183 @@
+use crate::fixture::{ControlKind, Fixture};
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// A control macro waiting for the operator to confirm it.
+pub(crate) struct ControlPrompt {
+    kind: ControlKind,
+    fixtures: Vec<usize>,
+}
+
+/// Why `kind` wants confirming, beyond the names it goes to.
+fn caution(kind: ControlKind) -> String {
+    match kind {
+        ControlKind::LampOn => tr!("controls-lamp-on-caution"),
+        ControlKind::LampOff => tr!("controls-lamp-off-caution"),
+        ControlKind::Reset => tr!("controls-reset-caution"),
+    }
+}
+
+/// Buttons for each macro `fixture`'s profile has.
+fn control_buttons(ui: &mut egui::Ui, fixture: &Fixture) -> Option<ControlKind> {
+    let mut chosen = None;
+    for control in &fixture.profile.controls {
+        if ui.button(control.kind.to_string()).clicked() {
+            chosen = Some(control.kind);
+            ui.close_menu();
+        }
+    }
+    chosen
+}
+
+impl HaloApp {
+    /// Asks before running `kind` on `fixtures`.
+    fn prompt_control(&mut self, kind: ControlKind, fixtures: Vec<usize>) {
+        self.control_prompt = Some(ControlPrompt { kind, fixtures });
+    }
+
+    /// The control menu at the end of a fixture's patch row.
+    pub(crate) fn draw_fixture_control_menu(&mut self, ui: &mut egui::Ui, index: usize) {
+        let fixture = &self.show.fixtures[index];
+        if fixture.profile.controls.is_empty() {
+            ui.label("");
+            return;
+        }
+        let label = match self.fixture_controls.running_on(fixture.id) {
+            Some(kind) => format!("{} …", kind),
+            None => tr!("controls-menu"),
+        };
+        let id = fixture.id;
+        let mut chosen = None;
+        ui.menu_button(label, |ui| chosen = control_buttons(ui, fixture))
+            .response
+            .on_hover_text(tr!("controls-menu-hint"));
+        if let Some(kind) = chosen {
+            self.prompt_control(kind, vec![id]);
+        }
+    }
+
+    /// Lamp on, lamp off and reset for every fixture of a type at once.
+    pub(crate) fn draw_fixture_controls(&mut self, ui: &mut egui::Ui) {
+        let mut groups: BTreeMap<&str, Vec<&Fixture>> = BTreeMap::new();
+        for fixture in &self.show.fixtures {
+            if !fixture.profile.controls.is_empty() {
+                groups
+                    .entry(fixture.profile.name.as_str())
+                    .or_default()
+                    .push(fixture);
+            }
+        }
+        if groups.is_empty() {
+            return;
+        }
+        ui.label(tr!("controls-title"));
+        let mut chosen = None;
+        egui::Grid::new("fixture_controls")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (profile, fixtures) in &groups {
+                    ui.label(format!("{} ({})", profile, fixtures.len()));
+                    ui.horizontal(|ui| {
+                        if let Some(kind) = control_buttons(ui, fixtures[0]) {
+                            let ids = fixtures.iter().map(|fixture| fixture.id).collect();
+                            chosen = Some((kind, ids));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        if self.fixture_controls.is_running() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(tr!("controls-sending"));
+                if ui
+                    .button(tr!("transport-stop"))
+                    .on_hover_text(tr!("controls-stop-hint"))
+                    .clicked()
+                {
+                    self.fixture_controls.stop();
+                    self.log.info(tr!("controls-stopped"));
+                }
+            });
+        }
+        if let Some((kind, ids)) = chosen {
+            self.prompt_control(kind, ids);
+        }
+    }
+
+    /// Confirms a control macro before it goes to the rig.
+    pub(crate) fn draw_control_prompt(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = &self.control_prompt else {
+            return;
+        };
+        let fixtures: Vec<&Fixture> = self
+            .show
+            .fixtures
+            .iter()
+            .filter(|fixture| prompt.fixtures.contains(&fixture.id))
+            .collect();
+        let kind = prompt.kind;
+        let mut run = false;
+        let mut cancel = false;
+        egui::Window::new(tr!("controls-prompt-title", control = kind.to_string()))
+            .id(egui::Id::new("control_prompt"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_max_width(380.0);
+                let names: Vec<&str> = fixtures
+                    .iter()
+                    .map(|fixture| fixture.name.as_str())
+                    .collect();
+                ui.label(tr!(
+                    "controls-prompt",
+                    control = kind.to_string(),
+                    fixtures = names.join(", ")
+                ));
+                ui.label(caution(kind));
+                let length = fixtures
+                    .iter()
+                    .filter_map(|fixture| fixture.profile.control(kind))
+                    .enumerate()
+                    .map(|(i, control)| control.stagger * i as u32 + control.length())
+                    .max()
+                    .unwrap_or_default();
+                ui.weak(tr!(
+                    "controls-takes",
+                    seconds = format!("{:.0}", length.as_secs_f32())
+                ));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    run = ui.button(kind.to_string()).clicked();
+                    cancel = ui.button(tr!("cancel")).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape));
+                });
+            });
+
+        if run {
+            let count = self.fixture_controls.run(kind, &fixtures);
+            self.control_prompt = None;
+            self.log.info(tr!(
+                "controls-sent",
+                control = kind.to_string(),
+                count = count
+            ));
+        } else if cancel {
+            self.control_prompt = None;
+        }
+    }
+
+    /// Lets go of the macros that are done, and keeps the frames coming
+    /// while any is running so each step goes out on time.
+    pub(crate) fn update_fixture_controls(&mut self, ctx: &egui::Context) {
+        if !self.fixture_controls.is_running() {
+            return;
+        }
+        self.fixture_controls.prune(Instant::now());
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::fixture_replace::{self, Place, Replacement};
+use crate::i18n::tr;
+use crate::ui::patch::fixture_combo;
+use crate::HaloApp;
+use eframe::egui;
+
+/// The replace window's choices and the last replacement, kept for undo.
+#[derive(Default)]
+pub(crate) struct FixtureReplaceTool {
+    from: Option<usize>,
+    to: Option<usize>,
+    /// Places in the preview unticked, left as they are.
+    skipped: Vec<Place>,
+    last: Option<Replacement>,
+}
+
+impl HaloApp {
+    fn fixture_name(&self, id: usize) -> String {
+        self.show
+            .fixtures
+            .iter()
+            .find(|fixture| fixture.id == id)
+            .map_or_else(|| format!("#{}", id), |fixture| fixture.name.clone())
+    }
+
+    /// Finds every place the show uses one fixture and hands the ticked ones
+    /// to another, with the last replacement undoable.
+    pub(crate) fn draw_fixture_replace_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_fixture_replace;
+        let mut replace = None;
+        let mut undo = false;
+        egui::Window::new(tr!("replace-title"))
+            .id(egui::Id::new("fixture_replace"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let tool = &mut self.fixture_replace;
+                let before = tool.from;
+                ui.horizontal(|ui| {
+                    ui.label(tr!("replace-find"));
+                    fixture_combo(ui, "replace_from", &self.show.fixtures, &mut tool.from);
+                    ui.label(tr!("replace-with"));
+                    fixture_combo(ui, "replace_to", &self.show.fixtures, &mut tool.to);
+                });
+                if tool.from != before {
+                    tool.skipped.clear();
+                }
+                let Some(from) = tool.from else {
+                    ui.weak(tr!("replace-pick"));
+                    return;
+                };
+                ui.separator();
+
+                let found = fixture_replace::find(&self.show, from);
+                if found.is_empty() {
+                    ui.weak(tr!("replace-unused"));
+                } else {
+                    egui::ScrollArea::vertical()
+                        .max_height(260.0)
+                        .show(ui, |ui| {
+                            for found in &found {
+                                let mut ticked = !tool.skipped.contains(&found.place);
+                                if ui.checkbox(&mut ticked, &found.description).changed() {
+                                    if ticked {
+                                        tool.skipped.retain(|place| *place != found.place);
+                                    } else {
+                                        tool.skipped.push(found.place);
+                                    }
+                                }
+                            }
+                        });
+                }
+                let places: Vec<Place> = found
+                    .iter()
+                    .map(|found| found.place)
+                    .filter(|place| !tool.skipped.contains(place))
+                    .collect();
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    let ready = !places.is_empty() && tool.to.is_some_and(|to| to != from);
+                    if ui
+                        .add_enabled(
+                            ready,
+                            egui::Button::new(tr!("replace-button", count = places.len())),
+                        )
+                        .on_hover_text(tr!("replace-button-hint"))
+                        .clicked()
+                    {
+                        replace = tool.to.map(|to| (from, to, places));
+                    }
+                    if ui
+                        .add_enabled(tool.last.is_some(), egui::Button::new(tr!("replace-undo")))
+                        .on_hover_text(tr!("replace-undo-hint"))
+                        .clicked()
+                    {
+                        undo = true;
+                    }
+                });
+            });
+        self.show_fixture_replace = open;
+
+        if let Some((from, to, places)) = replace {
+            let replacement = fixture_replace::replace(&mut self.show, from, to, &places);
+            let mut message = tr!(
+                "replace-done",
+                from = self.fixture_name(from),
+                to = self.fixture_name(to),
+                count = replacement.places()
+            );
+            if replacement.dropped > 0 {
+                message += &tr!(
+                    "replace-dropped",
+                    count = replacement.dropped,
+                    to = self.fixture_name(to)
+                );
+            }
+            self.log.info(message);
+            self.fixture_replace.skipped.clear();
+            self.fixture_replace.last = Some(replacement);
+        }
+        if undo {
+            if let Some(replacement) = self.fixture_replace.last.take() {
+                let (from, to) = (replacement.from, replacement.to);
+                let (restored, kept) = replacement.undo(&mut self.show);
+                self.log.info(tr!(
+                    "replace-undone",
+                    from = self.fixture_name(from),
+                    count = restored,
+                    to = self.fixture_name(to)
+                ));
+                if kept > 0 {
+                    self.log.warn(tr!("replace-kept", count = kept));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::cue;
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    /// The designer's side of a dual-operator session: connects to the
+    /// operator's Halo and mirrors its playback.
+    pub(crate) fn draw_designer_view(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_designer_view;
+        let mut connect = None;
+        egui::Window::new(tr!("designer-title"))
+            .id(egui::Id::new("designer_view"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let connected = self.follow_client.is_some();
+                ui.horizontal(|ui| {
+                    ui.label(tr!("designer-operator"));
+                    ui.add_enabled(
+                        !connected,
+                        egui::TextEdit::singleline(&mut self.preferences.follow_address)
+                            .hint_text("host:port")
+                            .desired_width(140.0),
+                    );
+                    ui.label(tr!("designer-as"));
+                    ui.add_enabled(
+                        !connected,
+                        egui::TextEdit::singleline(&mut self.preferences.follow_name)
+                            .desired_width(90.0),
+                    );
+                    let label = if connected {
+                        tr!("disconnect")
+                    } else {
+                        tr!("connect")
+                    };
+                    if ui.button(label).clicked() {
+                        connect = Some(!connected);
+                    }
+                });
+
+                let Some(client) = &self.follow_client else {
+                    ui.label(tr!("designer-hint"));
+                    return;
+                };
+                if let Some(error) = client.error() {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                let Some(state) = client.state() else {
+                    ui.label(tr!("designer-connecting"));
+                    return;
+                };
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(&state.state.timecode)
+                            .font(egui::FontId::new(
+                                32.0,
+                                egui::FontFamily::Name("matrix".into()),
+                            ))
+                            .color(self.preferences.led_color.lit()),
+                    );
+                    ui.vertical(|ui| {
+                        ui.label(if state.state.running {
+                            format!("▶ {}", tr!("transport-running"))
+                        } else {
+                            format!("⏸ {}", tr!("transport-stopped"))
+                        });
+                        ui.label(tr!("designer-bpm", bpm = format!("{:.1}", state.state.bpm)));
+                    });
+                });
+                match &state.state.current_cue {
+                    Some(current) => ui.strong(tr!(
+                        "designer-current-cue",
+                        number = current.number.to_string(),
+                        name = current.name.as_str()
+                    )),
+                    None => ui.label(tr!("designer-no-cue")),
+                };
+                if let Some(refused) = client.refused() {
+                    ui.colored_label(egui::Color32::RED, refused);
+                }
+
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .id_salt("designer_cues")
+                    .max_height(220.0)
+                    .show(ui, |ui| {
+                        let mut section = None;
+                        for follow_cue in &state.cues {
+                            if section != Some(&follow_cue.section) {
+                                section = Some(&follow_cue.section);
+                                if !follow_cue.section.is_empty() {
+                                    ui.strong(&follow_cue.section);
+                                }
+                            }
+                            ui.horizontal(|ui| {
+                                let go = ui.add_enabled(
+                                    follow_cue.granted,
+                                    egui::Button::new(tr!("transport-go")).small(),
+                                );
+                                if go.clicked() {
+                                    client.go(follow_cue.number);
+                                }
+                                let text = format!("{}  {}", follow_cue.number, follow_cue.name);
+                                if follow_cue.playing {
+                                    ui.colored_label(egui::Color32::GREEN, text);
+                                } else {
+                                    ui.label(text);
+                                }
+                            });
+                        }
+                    });
+
+                ui.separator();
+                ui.label(tr!("designer-levels"));
+                egui::Grid::new("designer_levels")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for level in &state.levels {
+                            ui.label(&level.name);
+                            ui.add(
+                                egui::ProgressBar::new(level.level as f32 / 255.0)
+                                    .desired_width(200.0)
+                                    .text(format!("{}%", level.level as u32 * 100 / 255)),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.show_designer_view = open;
+
+        if let Some(follow) = connect {
+            if follow {
+                let _ = self.preferences.save();
+            }
+            self.set_following(ctx, follow);
+        }
+    }
+
+    /// The operator's side: designer views that have connected, and the
+    /// sections of the cue list each may fire.
+    pub(crate) fn draw_followers(&mut self, ui: &mut egui::Ui) {
+        if self.followers.is_empty() {
+            ui.label(tr!("designer-no-followers"));
+            return;
+        }
+        let sections: Vec<String> = cue::sections(&self.show.cues)
+            .into_iter()
+            .map(|(title, _)| title.unwrap_or_default())
+            .collect();
+        for follower in &mut self.followers {
+            ui.horizontal_wrapped(|ui| {
+                if follower.connected() {
+                    ui.strong(&follower.name);
+                } else {
+                    ui.weak(tr!("designer-gone", name = follower.name.as_str()));
+                }
+                ui.label(tr!("designer-may-fire"));
+                for section in &sections {
+                    let label = if section.is_empty() {
+                        tr!("designer-unsectioned")
+                    } else {
+                        section.clone()
+                    };
+                    let mut granted = follower.granted.contains(section);
+                    if ui.checkbox(&mut granted, label).changed() {
+                        follower.granted.retain(|s| s != section);
+                        if granted {
+                            follower.granted.push(section.clone());
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::fixture::{Attribute, Fixture};
+use crate::follow_spot::{FollowSpot, SpotFixture};
+use crate::i18n::tr;
+use crate::psn::Tracker;
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+
+fn is_mover(fixture: &Fixture) -> bool {
+    fixture.channel_for(Attribute::Pan).is_some() && fixture.channel_for(Attribute::Tilt).is_some()
+}
+
+fn draw_metres(ui: &mut egui::Ui, value: &mut f32) {
+    ui.add(
+        egui::DragValue::new(value)
+            .speed(0.05)
+            .suffix(tr!("unit-metres")),
+    );
+}
+
+/// Which tracker the spot follows, from the ones sending.
+fn draw_tracker(ui: &mut egui::Ui, index: usize, spot: &mut FollowSpot, trackers: &[Tracker]) {
+    let name = |tracker: &Tracker| {
+        if tracker.name.is_empty() {
+            tr!("follow-tracker", id = tracker.id)
+        } else {
+            format!("{} ({})", tracker.name, tracker.id)
+        }
+    };
+    let selected = trackers
+        .iter()
+        .find(|tracker| tracker.id == spot.tracker)
+        .map_or_else(|| tr!("follow-tracker-silent", id = spot.tracker), name);
+    egui::ComboBox::from_id_salt(("follow_tracker", index))
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for tracker in trackers {
+                ui.selectable_value(&mut spot.tracker, tracker.id, name(tracker));
+            }
+        });
+    ui.add(egui::DragValue::new(&mut spot.tracker).prefix("id "));
+}
+
+/// The heads on a spot and where each hangs, with menus to add a single
+/// head or every head of a type.
+fn draw_spot_fixtures(ui: &mut egui::Ui, index: usize, spot: &mut FollowSpot, rig: &[Fixture]) {
+    let mut remove = None;
+    egui::Grid::new(("follow_fixtures", index))
+        .num_columns(5)
+        .show(ui, |ui| {
+            for (i, hung) in spot.fixtures.iter_mut().enumerate() {
+                let name = rig
+                    .iter()
+                    .find(|fixture| fixture.id == hung.fixture)
+                    .map_or_else(|| tr!("follow-not-patched"), |fixture| fixture.name.clone());
+                ui.label(name);
+                for value in &mut hung.position {
+                    draw_metres(ui, value);
+                }
+                if ui.small_button(tr!("remove")).clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(i) = remove {
+        spot.fixtures.remove(i);
+    }
+    let movers: Vec<&Fixture> = rig
+        .iter()
+        .filter(|fixture| is_mover(fixture))
+        .filter(|fixture| spot.fixtures.iter().all(|hung| hung.fixture != fixture.id))
+        .collect();
+    if movers.is_empty() {
+        return;
+    }
+    let mut add = Vec::new();
+    ui.horizontal(|ui| {
+        ui.menu_button(tr!("follow-add-fixture"), |ui| {
+            for fixture in &movers {
+                if ui.button(&fixture.name).clicked() {
+                    add.push(fixture.id);
+                    ui.close_menu();
+                }
+            }
+        });
+        let mut groups: Vec<&str> = movers
+            .iter()
+            .map(|fixture| fixture.profile.name.as_str())
+            .collect();
+        groups.sort();
+        groups.dedup();
+        ui.menu_button(tr!("follow-add-group"), |ui| {
+            for group in groups {
+                if ui.button(group).clicked() {
+                    add.extend(
+                        movers
+                            .iter()
+                            .filter(|fixture| fixture.profile.name == group)
+                            .map(|fixture| fixture.id),
+                    );
+                    ui.close_menu();
+                }
+            }
+        });
+    });
+    spot.fixtures
+        .extend(add.into_iter().map(|fixture| SpotFixture {
+            fixture,
+            position: [0.0, 6.0, 0.0],
+        }));
+}
+
+impl HaloApp {
+    /// Moving heads following PSN trackers: which tracker each spot
+    /// follows, the heads on it and where they hang, and the smoothing,
+    /// height and trim of its aim.
+    pub(crate) fn draw_follow_spots_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_follow_spots;
+        let mut remove = None;
+        egui::Window::new(tr!("follow-title"))
+            .id(egui::Id::new("follow_spots"))
+            .open(&mut open)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                if let Some(error) = &self.psn_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                } else if self.psn.is_none() {
+                    ui.label(tr!("follow-turn-on-psn"));
+                }
+                let trackers = self.trackers.live();
+                if self.psn.is_some() {
+                    ui.label(tr!("follow-trackers-sending", count = trackers.len()));
+                }
+                if ui.button(tr!("follow-add-spot")).clicked() {
+                    self.show.follow_spots.push(FollowSpot {
+                        name: tr!(
+                            "follow-default-name",
+                            number = self.show.follow_spots.len() + 1
+                        ),
+                        ..FollowSpot::default()
+                    });
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (index, spot) in self.show.follow_spots.iter_mut().enumerate() {
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            accessibility::named(
+                                ui.checkbox(&mut spot.enabled, ""),
+                                tr!("enabled"),
+                            );
+                            ui.text_edit_singleline(&mut spot.name);
+                            if ui.small_button(tr!("remove")).clicked() {
+                                remove = Some(index);
+                            }
+                        });
+                        egui::Grid::new(("follow_spot", index))
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                ui.label(tr!("follow-follows"));
+                                ui.horizontal(|ui| draw_tracker(ui, index, spot, &trackers));
+                                ui.end_row();
+                                ui.label(tr!("follow-smoothing"));
+                                ui.add(
+                                    egui::DragValue::new(&mut spot.smoothing)
+                                        .range(0.0..=5.0)
+                                        .speed(0.01)
+                                        .suffix(tr!("unit-seconds")),
+                                );
+                                ui.end_row();
+                                ui.label(tr!("follow-height"));
+                                draw_metres(ui, &mut spot.height);
+                                ui.end_row();
+                                ui.label(tr!("follow-trim"));
+                                ui.horizontal(|ui| {
+                                    draw_metres(ui, &mut spot.trim[0]);
+                                    draw_metres(ui, &mut spot.trim[1]);
+                                    if ui.small_button(tr!("transport-reset")).clicked() {
+                                        spot.trim = [0.0, 0.0];
+                                    }
+                                })
+                                .response
+                                .on_hover_text(tr!("follow-trim-hint"));
+                                ui.end_row();
+                                ui.label(tr!("follow-pan-tilt-range"));
+                                ui.horizontal(|ui| {
+                                    for range in [&mut spot.pan_range, &mut spot.tilt_range] {
+                                        ui.add(
+                                            egui::DragValue::new(range)
+                                                .range(90.0..=720.0)
+                                                .suffix("°"),
+                                        );
+                                    }
+                                });
+                                ui.end_row();
+                            });
+                        ui.label(tr!("follow-heads"));
+                        draw_spot_fixtures(ui, index, spot, &self.show.fixtures);
+                    }
+                });
+            });
+        self.show_follow_spots = open;
+
+        if let Some(index) = remove {
+            self.show.follow_spots.remove(index);
+        }
+    }
+}
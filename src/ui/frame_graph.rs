@@ -0,0 +1,167 @@
+use crate::i18n::tr;
+use crate::stats::{FrameTimer, StageTime, HISTORY};
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+const UI_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 160, 255);
+const OUTPUT_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 220, 100);
+
+impl HaloApp {
+    /// Overlay graphing recent UI and output frame times against the output
+    /// frame period.
+    pub(crate) fn draw_frame_graph(&mut self, ctx: &egui::Context) {
+        if !self.show_frame_graph {
+            return;
+        }
+        egui::Area::new(egui::Id::new("frame_graph"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -32.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    let target = self.output_frames.target().unwrap_or_default();
+                    // Never scale below twice the target so jitter stays readable.
+                    let ceiling = self
+                        .ui_frames
+                        .worst_frame_time()
+                        .max(self.output_frames.worst_frame_time())
+                        .max(target * 2)
+                        .as_secs_f32();
+
+                    let (rect, _) =
+                        ui.allocate_exact_size(egui::vec2(320.0, 100.0), egui::Sense::hover());
+                    let painter = ui.painter_at(rect);
+                    painter.rect_filled(rect, 2.0, egui::Color32::from_black_alpha(160));
+
+                    let y_for = |time: Duration| {
+                        rect.bottom() - rect.height() * (time.as_secs_f32() / ceiling).min(1.0)
+                    };
+                    let target_y = y_for(target);
+                    painter.hline(
+                        rect.x_range(),
+                        target_y,
+                        egui::Stroke::new(1.0, egui::Color32::from_gray(120)),
+                    );
+
+                    for (timer, color) in [
+                        (&self.ui_frames, UI_COLOR),
+                        (&self.output_frames, OUTPUT_COLOR),
+                    ] {
+                        painter.add(egui::Shape::line(
+                            plot(timer, rect, &y_for),
+                            egui::Stroke::new(1.5, color),
+                        ));
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            UI_COLOR,
+                            tr!(
+                                "frame-ui-worst",
+                                ms = format!(
+                                    "{:.1}",
+                                    self.ui_frames.worst_frame_time().as_secs_f32() * 1000.0
+                                )
+                            ),
+                        );
+                        ui.colored_label(
+                            OUTPUT_COLOR,
+                            tr!(
+                                "frame-output-worst",
+                                ms = format!(
+                                    "{:.1}",
+                                    self.output_frames.worst_frame_time().as_secs_f32() * 1000.0
+                                )
+                            ),
+                        );
+                        ui.label(tr!(
+                            "frame-target",
+                            ms = format!("{:.1}", target.as_secs_f32() * 1000.0)
+                        ));
+                    });
+                    ui.label(tr!("frame-dropped", count = self.output_frames.dropped()));
+                });
+            });
+    }
+}
+
+impl HaloApp {
+    /// How long each stage of the output frame takes, against the frame
+    /// period, to find what a slow frame is spending its time on.
+    pub(crate) fn draw_frame_timings(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_frame_timings;
+        egui::Window::new(tr!("menu-frame-timings"))
+            .id(egui::Id::new("frame_timings"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let budget = self.output_frames.target().unwrap_or_default();
+                let micros = |time: Duration| format!("{:.0} µs", time.as_secs_f64() * 1e6);
+                egui::Grid::new("frame_timings_grid")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("frame-stage"));
+                        ui.strong(tr!("frame-last"));
+                        ui.strong(tr!("frame-average"));
+                        ui.strong(tr!("frame-worst"));
+                        ui.strong(tr!("frame-share"));
+                        ui.end_row();
+                        for stage in &self.stage_times {
+                            ui.label(stage_label(stage.name));
+                            ui.monospace(micros(stage.last));
+                            ui.monospace(micros(stage.average));
+                            ui.monospace(micros(stage.worst));
+                            let share = stage.average.as_secs_f32() / budget.as_secs_f32();
+                            ui.add(
+                                egui::ProgressBar::new(share.min(1.0))
+                                    .desired_width(120.0)
+                                    .text(format!("{:.1}%", share * 100.0)),
+                            );
+                            ui.end_row();
+                        }
+                        let total = |time: fn(&StageTime) -> Duration| {
+                            self.stage_times.iter().map(time).sum::<Duration>()
+                        };
+                        ui.strong(tr!("frame-total"));
+                        ui.monospace(micros(total(|stage| stage.last)));
+                        ui.monospace(micros(total(|stage| stage.average)));
+                        ui.monospace(micros(total(|stage| stage.worst)));
+                        ui.monospace(tr!("frame-of", time = micros(budget)));
+                        ui.end_row();
+                    });
+                if self.stage_times.is_empty() {
+                    ui.weak(tr!("frame-waiting"));
+                }
+                ui.weak(tr!("frame-history", count = HISTORY));
+            });
+        self.show_frame_timings = open;
+    }
+}
+
+/// Frame times as points, newest at the right edge.
+fn plot(timer: &FrameTimer, rect: egui::Rect, y_for: &impl Fn(Duration) -> f32) -> Vec<egui::Pos2> {
+    let step = rect.width() / (HISTORY - 1) as f32;
+    let times: Vec<Duration> = timer.frame_times().collect();
+    let offset = HISTORY - times.len();
+    times
+        .into_iter()
+        .enumerate()
+        .map(|(i, time)| egui::pos2(rect.left() + (offset + i) as f32 * step, y_for(time)))
+        .collect()
+}
+
+/// The engine's name for a stage, in the chosen language.
+fn stage_label(name: &str) -> String {
+    match name {
+        "Cues" => tr!("frame-stage-cues"),
+        "Merge" => tr!("frame-stage-merge"),
+        "Ownership" => tr!("frame-stage-ownership"),
+        "Effects and pixel maps" => tr!("frame-stage-effects"),
+        "Haze, input and follow spots" => tr!("frame-stage-haze"),
+        "Masters" => tr!("frame-stage-masters"),
+        "Processing and overrides" => tr!("frame-stage-processing"),
+        "Sending" => tr!("frame-stage-sending"),
+        _ => name.to_string(),
+    }
+}
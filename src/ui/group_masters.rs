@@ -0,0 +1,174 @@
+use crate::dmx_fader::{DmxFader, FaderTarget};
+use crate::group_master::GroupMaster;
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+/// The DMX wing channel a master follows, with a button to learn it and one
+/// to clear it. Returns whether learning was asked for or cancelled.
+pub(crate) fn draw_dmx_learn(
+    ui: &mut egui::Ui,
+    learning: bool,
+    fader: &mut Option<DmxFader>,
+) -> bool {
+    let learn = match fader {
+        _ if learning => tr!("masters-move-fader"),
+        Some(fader) => tr!("masters-dmx-fader", fader = fader.to_string()),
+        None => tr!("masters-dmx-learn"),
+    };
+    let clicked = ui
+        .small_button(learn)
+        .on_hover_text(tr!("masters-dmx-learn-hint"))
+        .clicked();
+    if fader.is_some() && ui.small_button(tr!("masters-clear-dmx")).clicked() {
+        *fader = None;
+    }
+    clicked
+}
+
+impl HaloApp {
+    pub(crate) fn draw_group_masters_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_group_masters;
+        let mut remove = None;
+        let mut dmx_learn = None;
+        let learning_dmx = self.learn_dmx_fader.as_ref().map(|learn| learn.target);
+        egui::Window::new(tr!("masters-title"))
+            .id(egui::Id::new("group_masters"))
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("add")).clicked() {
+                        self.show.group_masters.push(GroupMaster {
+                            name: tr!(
+                                "masters-default-name",
+                                number = self.show.group_masters.len() + 1
+                            ),
+                            ..GroupMaster::default()
+                        });
+                    }
+                    ui.weak(tr!("masters-hint"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("masters-grand"));
+                    let mut percent = self.grand_master * 100.0;
+                    if ui
+                        .add(
+                            egui::Slider::new(&mut percent, 0.0..=100.0)
+                                .max_decimals(0)
+                                .suffix("%"),
+                        )
+                        .changed()
+                    {
+                        self.grand_master = percent / 100.0;
+                    }
+                    if ui.small_button(tr!("masters-full")).clicked() {
+                        self.grand_master = 1.0;
+                    }
+                    let learning = learning_dmx == Some(FaderTarget::Grand);
+                    if draw_dmx_learn(ui, learning, &mut self.show.grand_master_fader) {
+                        dmx_learn = Some(FaderTarget::Grand);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("masters-flash"));
+                    let mut percent = self.flash_master * 100.0;
+                    if ui
+                        .add(
+                            egui::Slider::new(&mut percent, 0.0..=100.0)
+                                .max_decimals(0)
+                                .suffix("%"),
+                        )
+                        .on_hover_text(tr!("masters-flash-hint"))
+                        .changed()
+                    {
+                        self.flash_master = percent / 100.0;
+                    }
+                    if ui.small_button(tr!("masters-full")).clicked() {
+                        self.flash_master = 1.0;
+                    }
+                });
+                if self.show.group_masters.is_empty() {
+                    ui.label(tr!("masters-empty"));
+                    return;
+                }
+                let mut groups: Vec<&str> = self
+                    .show
+                    .fixtures
+                    .iter()
+                    .map(|fixture| fixture.profile.name.as_str())
+                    .collect();
+                groups.sort();
+                groups.dedup();
+                ui.separator();
+                ui.horizontal_top(|ui| {
+                    for (index, master) in self.show.group_masters.iter_mut().enumerate() {
+                        ui.vertical(|ui| {
+                            ui.set_width(72.0);
+                            ui.add(egui::TextEdit::singleline(&mut master.name));
+                            let mut percent = master.level * 100.0;
+                            if ui
+                                .add(
+                                    egui::Slider::new(&mut percent, 0.0..=100.0)
+                                        .vertical()
+                                        .max_decimals(0)
+                                        .suffix("%"),
+                                )
+                                .changed()
+                            {
+                                master.level = percent / 100.0;
+                            }
+                            if ui.small_button(tr!("masters-full")).clicked() {
+                                master.level = 1.0;
+                            }
+                            let label = match master.groups.len() {
+                                0 => tr!("masters-no-groups"),
+                                1 => master.groups[0].clone(),
+                                n => tr!("masters-groups", count = n),
+                            };
+                            ui.menu_button(label, |ui| {
+                                for &name in &groups {
+                                    let mut selected = master.groups.iter().any(|g| g == name);
+                                    if ui.checkbox(&mut selected, name).changed() {
+                                        master.groups.retain(|group| group != name);
+                                        if selected {
+                                            master.groups.push(name.to_string());
+                                        }
+                                    }
+                                }
+                            });
+                            let learning = self.learn_group_master == Some(index);
+                            let learn = match master.control {
+                                _ if learning => tr!("masters-move-control"),
+                                Some(control) => format!("CC {}", control.controller),
+                                None => tr!("masters-midi-learn"),
+                            };
+                            if ui.small_button(learn).clicked() {
+                                self.learn_group_master = (!learning).then_some(index);
+                            }
+                            if master.control.is_some() && ui.small_button(tr!("clear")).clicked() {
+                                master.control = None;
+                            }
+                            let target = FaderTarget::Group(index);
+                            if draw_dmx_learn(ui, learning_dmx == Some(target), &mut master.fader) {
+                                dmx_learn = Some(target);
+                            }
+                            if ui.small_button(tr!("remove")).clicked() {
+                                remove = Some(index);
+                            }
+                        });
+                    }
+                });
+            });
+        self.show_group_masters = open;
+
+        if let Some(target) = dmx_learn {
+            self.toggle_dmx_learn(target);
+        }
+        if let Some(index) = remove {
+            self.show.group_masters.remove(index);
+            self.learn_group_master = None;
+            self.learn_dmx_fader = None;
+        }
+    }
+}
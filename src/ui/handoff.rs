@@ -0,0 +1,60 @@
+use crate::export;
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    pub(crate) fn draw_handoff_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_handoff;
+        egui::Window::new(tr!("handoff-title"))
+            .id(egui::Id::new("handoff"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("handoff_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("handoff-show-name"));
+                        ui.text_edit_singleline(&mut self.show.name);
+                        ui.end_row();
+
+                        ui.label(tr!("handoff-contacts"));
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.show.contacts)
+                                .desired_rows(3)
+                                .hint_text(tr!("handoff-contacts-hint")),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr!("handoff-notes"));
+                        ui.add(egui::TextEdit::multiline(&mut self.show.notes).desired_rows(5));
+                        ui.end_row();
+
+                        ui.label(tr!("export-to"));
+                        ui.text_edit_singleline(&mut self.handoff_path);
+                        ui.end_row();
+                    });
+
+                ui.add_space(8.0);
+                if ui.button(tr!("handoff-export")).clicked() {
+                    let html = export::handoff_html(&self.show);
+                    self.handoff_status = Some(
+                        std::fs::write(&self.handoff_path, html)
+                            .map(|_| tr!("exported-to", path = self.handoff_path.as_str()))
+                            .map_err(|err| tr!("export-failed", error = err.to_string())),
+                    );
+                }
+
+                match &self.handoff_status {
+                    Some(Ok(message)) => {
+                        ui.label(egui::RichText::new(message).color(egui::Color32::GREEN));
+                    }
+                    Some(Err(message)) => {
+                        ui.label(egui::RichText::new(message).color(egui::Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+        self.show_handoff = open;
+    }
+}
@@ -0,0 +1,143 @@
+use crate::fixture::Attribute;
+use crate::haze;
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+impl HaloApp {
+    pub(crate) fn draw_haze_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_haze;
+        let smoke = self.smoke_level() > 0.0;
+        let end = haze::show_end(&self.show.timeline_cues());
+        let elapsed = self.elapsed;
+        egui::Window::new(tr!("haze-title"))
+            .id(egui::Id::new("haze"))
+            .open(&mut open)
+            .default_width(340.0)
+            .show(ctx, |ui| {
+                let haze = &mut self.show.haze;
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut haze.enabled, tr!("haze-enable"));
+                    if haze.enabled {
+                        let status = if smoke {
+                            tr!("haze-pad-on", pad = haze.pad.as_str())
+                        } else if !self.transport.is_running() {
+                            self.transport.label()
+                        } else if elapsed >= end {
+                            tr!("haze-show-ended")
+                        } else if haze.is_bursting(elapsed) {
+                            tr!("haze-hazing")
+                        } else {
+                            tr!("haze-waiting")
+                        };
+                        ui.weak(status);
+                    }
+                });
+                ui.separator();
+
+                ui.strong(tr!("haze-hazers"));
+                let hazers: Vec<_> = self
+                    .show
+                    .fixtures
+                    .iter()
+                    .filter(|fixture| fixture.channel_for(Attribute::Haze).is_some())
+                    .collect();
+                if hazers.is_empty() {
+                    ui.label(tr!("haze-none"));
+                }
+                for fixture in hazers {
+                    let mut selected = haze.fixtures.contains(&fixture.id);
+                    let channel = fixture.channel_for(Attribute::Haze).unwrap_or_default();
+                    if ui
+                        .checkbox(
+                            &mut selected,
+                            tr!(
+                                "haze-fixture",
+                                name = fixture.name.as_str(),
+                                channel = format!("{}.{}", fixture.universe, channel)
+                            ),
+                        )
+                        .changed()
+                    {
+                        if selected {
+                            haze.fixtures.push(fixture.id);
+                        } else {
+                            haze.fixtures.retain(|&id| id != fixture.id);
+                        }
+                    }
+                }
+                ui.separator();
+
+                egui::Grid::new("haze_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("output-label"));
+                        let mut percent = haze.output * 100.0;
+                        if ui
+                            .add(egui::Slider::new(&mut percent, 0.0..=100.0).suffix("%"))
+                            .changed()
+                        {
+                            haze.output = percent / 100.0;
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("haze-burst"));
+                        let mut burst = haze.burst.as_secs_f32();
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut burst)
+                                    .range(1.0..=600.0)
+                                    .suffix(tr!("unit-seconds")),
+                            )
+                            .changed()
+                        {
+                            haze.burst = Duration::from_secs_f32(burst);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("haze-every"));
+                        let mut interval = haze.interval.as_secs_f32() / 60.0;
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut interval)
+                                    .range(0.5..=60.0)
+                                    .speed(0.1)
+                                    .max_decimals(1)
+                                    .suffix(tr!("unit-minutes")),
+                            )
+                            .changed()
+                        {
+                            haze.interval = Duration::from_secs_f32(interval * 60.0);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("haze-pad"));
+                        egui::ComboBox::from_id_salt("haze_pad")
+                            .selected_text(haze.pad.as_str())
+                            .show_ui(ui, |ui| {
+                                for pad in &self.show.pads {
+                                    ui.selectable_value(
+                                        &mut haze.pad,
+                                        pad.label.clone(),
+                                        &pad.label,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr!("haze-pad-output"));
+                        let mut percent = haze.pad_output * 100.0;
+                        if ui
+                            .add(egui::Slider::new(&mut percent, 0.0..=100.0).suffix("%"))
+                            .changed()
+                        {
+                            haze.pad_output = percent / 100.0;
+                        }
+                        ui.end_row();
+                    });
+                ui.weak(tr!("haze-hint"));
+            });
+        self.show_haze = open;
+    }
+}
@@ -0,0 +1,134 @@
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::ownership::Owner;
+use crate::HaloApp;
+use eframe::egui;
+
+/// Travel of a typical moving head, as profiles don't carry their own.
+const PAN_DEGREES: f32 = 540.0;
+const TILT_DEGREES: f32 = 270.0;
+
+impl HaloApp {
+    /// What a fixture is putting out right now, attribute by attribute, and
+    /// what set each one. Shown when hovering a fixture in the visualizer
+    /// and the address map.
+    pub(crate) fn draw_fixture_inspection(&self, ui: &mut egui::Ui, fixture: &Fixture) {
+        self.owners_shown.set(true);
+        let unit = self.on_rig(fixture);
+        let fixture = unit.as_ref();
+        ui.strong(&fixture.name);
+        ui.label(tr!(
+            "inspect-profile-at",
+            profile = fixture.profile.name.as_str(),
+            address = format!("{}.{:03}", fixture.universe, fixture.address)
+        ));
+        let [r, g, b] = fixture.output_color(&self.output);
+        ui.horizontal(|ui| {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(32.0, 14.0), egui::Sense::hover());
+            ui.painter()
+                .rect_filled(rect, 2.0, egui::Color32::from_rgb(r, g, b));
+            ui.monospace(format!("#{:02X}{:02X}{:02X}", r, g, b));
+        });
+
+        egui::Grid::new(("fixture_inspection", fixture.id))
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for &attribute in &fixture.profile.channels {
+                    let Some(channel) = fixture.channel_for(attribute) else {
+                        continue;
+                    };
+                    let level = self.output.get(fixture.universe, channel);
+                    ui.label(attribute.to_string());
+                    ui.monospace(match attribute {
+                        Attribute::Intensity
+                        | Attribute::Haze
+                        | Attribute::WarmWhite
+                        | Attribute::CoolWhite => {
+                            format!("{:.0}%", level as f32 / 2.55)
+                        }
+                        Attribute::Pan => format!("{:.0}°", level as f32 / 255.0 * PAN_DEGREES),
+                        Attribute::Tilt => {
+                            format!("{:.0}°", level as f32 / 255.0 * TILT_DEGREES)
+                        }
+                        Attribute::Red
+                        | Attribute::Green
+                        | Attribute::Blue
+                        | Attribute::Control => level.to_string(),
+                        Attribute::Gobo | Attribute::ColorWheel => fixture
+                            .profile
+                            .slot_at(attribute, level)
+                            .map_or_else(|| level.to_string(), |slot| slot.name.clone()),
+                        Attribute::Cct => crate::cct::kelvin(fixture, &self.output)
+                            .map_or_else(|| level.to_string(), |k| format!("{:.0}K", k)),
+                    });
+                    match self.output_owners.attribute(fixture, attribute) {
+                        Some(owner) => ui.label(self.owner_name(owner)),
+                        None => ui.weak(tr!("inspect-nothing")),
+                    };
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// How an output owner reads in the UI, such as "Cue 3 Chorus" or
+    /// "Group master Blinders at 70%".
+    pub(crate) fn owner_name(&self, owner: Owner) -> String {
+        let cue = |index: usize| {
+            self.show.cues.get(index).map_or_else(
+                || tr!("owner-cue-index", index = index + 1),
+                |cue| {
+                    tr!(
+                        "owner-cue",
+                        number = cue.number.to_string(),
+                        name = cue.name.as_str()
+                    )
+                },
+            )
+        };
+        match owner {
+            Owner::Cue(index) => cue(index),
+            Owner::MoveInBlack(index) => tr!("owner-move-in-black", cue = cue(index)),
+            Owner::Busk => tr!("busk-title"),
+            Owner::PixelMap(index) => self.show.pixel_maps.get(index).map_or_else(
+                || tr!("owner-pixel-map"),
+                |map| tr!("owner-pixel-map-named", name = map.name.as_str()),
+            ),
+            Owner::HazeSchedule => tr!("owner-haze-schedule"),
+            Owner::SmokePad => tr!("owner-smoke-pad", pad = self.show.haze.pad.as_str()),
+            Owner::Input => tr!("owner-input"),
+            Owner::QuickConsole => tr!("owner-quick-console"),
+            Owner::FollowSpot(index) => self.show.follow_spots.get(index).map_or_else(
+                || tr!("owner-follow-spot"),
+                |spot| tr!("owner-follow-spot-named", name = spot.name.as_str()),
+            ),
+            Owner::Flash(index) => self.show.pads.get(index).map_or_else(
+                || tr!("pad-flash"),
+                |pad| tr!("owner-flash", pad = pad.label.as_str()),
+            ),
+            Owner::FlashSolo => tr!("owner-flash-solo"),
+            Owner::GroupMaster(index) => self.show.group_masters.get(index).map_or_else(
+                || tr!("owner-group-master"),
+                |master| {
+                    tr!(
+                        "owner-group-master-at",
+                        name = master.name.as_str(),
+                        percent = format!("{:.0}", master.level * 100.0)
+                    )
+                },
+            ),
+            Owner::GrandMaster => tr!(
+                "owner-grand-master",
+                percent = format!("{:.0}", self.grand_master * 100.0)
+            ),
+            Owner::Processing => tr!("owner-processing"),
+            Owner::Inhibited => tr!("owner-inhibited"),
+            Owner::Control(kind) => kind.to_string(),
+            Owner::ChannelCheck => tr!("owner-channel-check"),
+            Owner::TestPattern => tr!("owner-test-pattern"),
+            Owner::Panic => tr!("owner-panic"),
+            Owner::Release => tr!("owner-release"),
+            Owner::Recording => tr!("owner-recording"),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    /// Learn timing: start a take, GO for each cue in turn, then keep or
+    /// throw away the times.
+    pub(crate) fn draw_learn_timing_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_learn_timing;
+        let mut start = false;
+        let mut go = false;
+        let mut undo = false;
+        let mut finish = None;
+        egui::Window::new(tr!("learn-title"))
+            .id(egui::Id::new("learn_timing"))
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                let Some(learn) = &self.learn_timing else {
+                    ui.label(tr!("learn-description"));
+                    start = ui.button(tr!("learn-start")).clicked();
+                    return;
+                };
+
+                ui.label(tr!(
+                    "learn-progress",
+                    learned = learn.learned(),
+                    count = learn.total()
+                ));
+                if let Some(next) = learn.next() {
+                    let name = self
+                        .show
+                        .cues
+                        .iter()
+                        .find(|cue| cue.number == next)
+                        .map(|cue| cue.name.as_str())
+                        .unwrap_or_default();
+                    ui.label(tr!("learn-next", number = next.to_string(), name = name));
+                }
+                if !self.rolling() {
+                    ui.label(
+                        egui::RichText::new(tr!("learn-not-rolling"))
+                            .color(ui.visuals().warn_fg_color),
+                    );
+                }
+                ui.add_space(8.0);
+                go = ui
+                    .add(
+                        egui::Button::new(
+                            egui::RichText::new(tr!("transport-go")).heading().strong(),
+                        )
+                        .min_size(egui::vec2(120.0, 48.0)),
+                    )
+                    .on_hover_text("G")
+                    .clicked();
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    undo = ui
+                        .add_enabled(learn.learned() > 0, egui::Button::new(tr!("learn-undo")))
+                        .clicked();
+                    if ui
+                        .button(tr!("learn-done"))
+                        .on_hover_text(tr!("learn-done-hint"))
+                        .clicked()
+                    {
+                        finish = Some(true);
+                    }
+                    if ui
+                        .button(tr!("cancel"))
+                        .on_hover_text(tr!("learn-cancel-hint"))
+                        .clicked()
+                    {
+                        finish = Some(false);
+                    }
+                });
+            });
+        self.show_learn_timing = open;
+
+        if start {
+            self.start_learn_timing();
+        }
+        if go {
+            self.learn_go();
+        }
+        if undo {
+            self.undo_learn_go();
+        }
+        if let Some(keep) = finish {
+            self.finish_learn_timing(keep);
+        }
+    }
+}
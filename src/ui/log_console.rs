@@ -0,0 +1,42 @@
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    pub(crate) fn draw_log_console(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_log;
+        egui::Window::new(tr!("menu-log"))
+            .id(egui::Id::new("log_console"))
+            .open(&mut open)
+            .default_size([520.0, 260.0])
+            .show(ctx, |ui| {
+                if ui.button(tr!("clear")).clicked() {
+                    self.log.clear();
+                }
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        for entry in self.log.entries() {
+                            let color = match entry.level {
+                                LogLevel::Info => ui.visuals().text_color(),
+                                LogLevel::Warning => egui::Color32::YELLOW,
+                                LogLevel::Error => egui::Color32::RED,
+                            };
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{} {}",
+                                    entry.time.format("%H:%M:%S"),
+                                    entry.message
+                                ))
+                                .monospace()
+                                .color(color),
+                            );
+                        }
+                    });
+            });
+        self.show_log = open;
+    }
+}
@@ -0,0 +1,112 @@
+use crate::i18n::tr;
+use crate::macros::{MacroAction, MacroRecorder};
+use crate::ui::accessibility;
+use crate::ui::touch;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    pub(crate) fn draw_macros_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_macros;
+        let mut play = None;
+        let mut delete = None;
+        let touch_mode = self.preferences.touch_mode;
+        egui::Window::new(tr!("menu-macros"))
+            .id(egui::Id::new("macros"))
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                let recording = self
+                    .macro_recorder
+                    .as_ref()
+                    .map(|recorder| (recorder.name.clone(), recorder.step_count()));
+                match recording {
+                    Some((name, steps)) => {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(tr!(
+                                    "macros-recording",
+                                    name = name,
+                                    count = steps
+                                ))
+                                .color(egui::Color32::RED),
+                            );
+                            if ui.button(tr!("transport-stop")).clicked() {
+                                if let Some(recorder) = self.macro_recorder.take() {
+                                    let recorded = recorder.finish();
+                                    self.show.macros.retain(|m| m.name != recorded.name);
+                                    self.show.macros.push(recorded);
+                                }
+                            }
+                        });
+                    }
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("name-label"));
+                            ui.text_edit_singleline(&mut self.macro_name);
+                            if ui.button(tr!("macros-record")).clicked() {
+                                let name = if self.macro_name.trim().is_empty() {
+                                    tr!("macros-default-name", number = self.show.macros.len() + 1)
+                                } else {
+                                    self.macro_name.trim().to_string()
+                                };
+                                self.macro_recorder = Some(MacroRecorder::new(&name));
+                                self.macro_name.clear();
+                            }
+                        });
+                        ui.label(tr!("macros-hint"));
+                    }
+                }
+
+                ui.separator();
+                if self.show.macros.is_empty() {
+                    ui.label(tr!("macros-none"));
+                }
+                for (i, recorded) in self.show.macros.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if touch::hint(
+                            accessibility::named(ui.button("▶"), tr!("macros-play")),
+                            touch_mode,
+                            tr!("macros-play"),
+                        )
+                        .clicked()
+                        {
+                            play = Some(recorded.name.clone());
+                        }
+                        if touch::hint(
+                            accessibility::named(ui.button("🗑"), tr!("delete")),
+                            touch_mode,
+                            tr!("delete"),
+                        )
+                        .clicked()
+                        {
+                            delete = Some(i);
+                        }
+                        egui::CollapsingHeader::new(tr!(
+                            "macros-entry",
+                            name = recorded.name.as_str(),
+                            count = recorded.steps.len()
+                        ))
+                        .id_salt(("macro", i))
+                        .show(ui, |ui| {
+                            for step in &recorded.steps {
+                                ui.monospace(format!(
+                                    "+{:>6.2}s  {}",
+                                    step.offset.as_secs_f32(),
+                                    step.action
+                                ));
+                            }
+                        });
+                    });
+                }
+            });
+
+        if let Some(name) = play {
+            self.perform(MacroAction::RunMacro(name));
+        }
+        if let Some(index) = delete {
+            self.show.macros.remove(index);
+        }
+        self.show_macros = open;
+    }
+}
@@ -0,0 +1,104 @@
+use crate::i18n::tr;
+use crate::midi_monitor::Direction;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+impl HaloApp {
+    /// Every MIDI message in and out, newest at the bottom.
+    pub(crate) fn draw_midi_monitor(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_midi_monitor;
+        egui::Window::new(tr!("menu-midi-monitor"))
+            .id(egui::Id::new("midi_monitor"))
+            .open(&mut open)
+            .default_size([620.0, 320.0])
+            .show(ctx, |ui| {
+                let monitor = &self.midi_monitor;
+                let filter = &mut self.midi_monitor_filter;
+                ui.horizontal(|ui| {
+                    let mut paused = monitor.is_paused();
+                    let label = if paused {
+                        tr!("inspector-resume")
+                    } else {
+                        tr!("inspector-pause")
+                    };
+                    if ui.toggle_value(&mut paused, label).changed() {
+                        monitor.set_paused(paused);
+                    }
+                    if ui.button(tr!("clear")).clicked() {
+                        monitor.clear();
+                    }
+                    ui.separator();
+                    ui.checkbox(&mut filter.incoming, tr!("midi-monitor-in"));
+                    ui.checkbox(&mut filter.outgoing, tr!("midi-monitor-out"));
+                    ui.label(tr!("midi-monitor-channel"));
+                    ui.add(
+                        egui::DragValue::new(&mut filter.channel)
+                            .range(0..=16)
+                            .custom_formatter(
+                                |n, _| {
+                                    if n == 0.0 {
+                                        tr!("any")
+                                    } else {
+                                        n.to_string()
+                                    }
+                                },
+                            ),
+                    );
+                    let mut timing = monitor.records_timing();
+                    if ui
+                        .checkbox(&mut timing, tr!("midi-monitor-timing"))
+                        .on_hover_text(tr!("midi-monitor-timing-hint"))
+                        .changed()
+                    {
+                        monitor.set_timing(timing);
+                    }
+                });
+                if self.midi_in.is_none() && self.midi_out.is_none() {
+                    ui.weak(tr!("midi-monitor-no-ports"));
+                }
+                ui.separator();
+
+                let messages = monitor.messages();
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        egui::Grid::new("midi_messages")
+                            .striped(true)
+                            .num_columns(6)
+                            .show(ui, |ui| {
+                                ui.strong(tr!("inspector-time"));
+                                ui.strong("");
+                                ui.strong(tr!("midi-monitor-port"));
+                                ui.strong(tr!("midi-monitor-channel"));
+                                ui.strong(tr!("type"));
+                                ui.strong(tr!("midi-monitor-data"));
+                                ui.end_row();
+
+                                for message in messages.iter().filter(|m| filter.matches(m)) {
+                                    ui.monospace(message.time.format("%H:%M:%S%.3f").to_string());
+                                    let color = match message.direction {
+                                        Direction::In => egui::Color32::LIGHT_GREEN,
+                                        Direction::Out => egui::Color32::LIGHT_BLUE,
+                                    };
+                                    ui.colored_label(color, message.direction.to_string());
+                                    ui.label(&message.port);
+                                    ui.label(
+                                        message
+                                            .channel()
+                                            .map_or_else(String::new, |ch| ch.to_string()),
+                                    );
+                                    ui.label(message.kind());
+                                    ui.monospace(message.data()).on_hover_text(message.hex());
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                if !monitor.is_paused() {
+                    ui.ctx().request_repaint_after(Duration::from_millis(100));
+                }
+            });
+        self.show_midi_monitor = open;
+    }
+}
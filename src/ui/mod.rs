@@ -0,0 +1,72 @@
+mod about;
+pub(crate) mod accessibility;
+pub(crate) mod address_map;
+mod art_net;
+mod awake;
+mod backups;
+pub(crate) mod bindings;
+mod busk;
+mod clocks;
+mod command_line;
+pub(crate) mod command_palette;
+mod crossfade;
+mod cues;
+pub(crate) mod daw;
+pub(crate) mod effect_presets;
+mod error_dialog;
+pub(crate) mod fan;
+pub(crate) mod fixture_controls;
+pub(crate) mod fixture_replace;
+mod follow;
+mod follow_spots;
+mod frame_graph;
+pub(crate) mod group_masters;
+mod handoff;
+mod haze;
+mod inspect;
+mod learn_timing;
+mod log_console;
+mod macros;
+mod midi_monitor;
+pub(crate) mod osc_monitor;
+mod output_monitor;
+pub(crate) mod packet_inspector;
+mod pads;
+pub(crate) mod patch;
+pub(crate) mod patch_import;
+mod performance;
+mod pixel_map;
+mod plugins;
+mod power;
+pub(crate) mod preferences;
+pub(crate) mod profile_browser;
+pub(crate) mod quick_console;
+mod quit;
+pub(crate) mod recording;
+mod report;
+mod sacn;
+mod safe_state;
+mod schedule;
+pub(crate) mod script_editor;
+mod session;
+pub(crate) mod show_diff;
+pub(crate) mod show_file;
+pub(crate) mod show_merge;
+mod smart_bulbs;
+mod snapshot_diff;
+mod songs;
+pub(crate) mod spectrogram;
+mod speed_masters;
+pub(crate) mod stage_display;
+mod status_bar;
+mod tags;
+mod timecode;
+pub(crate) mod toasts;
+pub(crate) mod touch;
+pub(crate) mod tracksheet;
+mod variables;
+pub(crate) mod venue;
+pub(crate) mod viewports;
+mod visualizer;
+mod watchdog;
+pub(crate) mod wheel;
@@ -0,0 +1,124 @@
+use crate::i18n::tr;
+use crate::midi_monitor::Direction;
+use crate::osc_monitor::AddressNode;
+use crate::HaloApp;
+use eframe::egui;
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub(crate) enum OscMonitorTab {
+    #[default]
+    Traffic,
+    Addresses,
+}
+
+fn draw_address_node(ui: &mut egui::Ui, path: &str, node: &AddressNode) {
+    for note in &node.notes {
+        ui.label(note);
+    }
+    for (part, child) in &node.children {
+        let address = format!("{}/{}", path, part);
+        if child.children.is_empty() {
+            ui.monospace(&address);
+            ui.indent(&address, |ui| draw_address_node(ui, &address, child));
+        } else {
+            egui::CollapsingHeader::new(egui::RichText::new(&address).monospace())
+                .default_open(true)
+                .show(ui, |ui| draw_address_node(ui, &address, child));
+        }
+    }
+}
+
+impl HaloApp {
+    /// OSC traffic in and out, and the addresses Halo knows about.
+    pub(crate) fn draw_osc_monitor(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_osc_monitor;
+        egui::Window::new(tr!("menu-osc-monitor"))
+            .id(egui::Id::new("osc_monitor"))
+            .open(&mut open)
+            .default_size([560.0, 320.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(
+                        &mut self.osc_monitor_tab,
+                        OscMonitorTab::Traffic,
+                        tr!("osc-monitor-traffic"),
+                    );
+                    ui.selectable_value(
+                        &mut self.osc_monitor_tab,
+                        OscMonitorTab::Addresses,
+                        tr!("osc-monitor-addresses"),
+                    );
+                    ui.separator();
+                    let monitor = &mut self.osc_monitor;
+                    let label = if monitor.paused {
+                        tr!("inspector-resume")
+                    } else {
+                        tr!("inspector-pause")
+                    };
+                    ui.toggle_value(&mut monitor.paused, label);
+                    if ui.button(tr!("clear")).clicked() {
+                        monitor.clear();
+                    }
+                });
+                if !self.preferences.osc_input_enabled {
+                    ui.weak(tr!("osc-monitor-input-off"));
+                }
+                ui.separator();
+
+                match self.osc_monitor_tab {
+                    OscMonitorTab::Traffic => {
+                        egui::ScrollArea::vertical()
+                            .stick_to_bottom(true)
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| {
+                                egui::Grid::new("osc_traffic")
+                                    .striped(true)
+                                    .num_columns(5)
+                                    .show(ui, |ui| {
+                                        ui.strong(tr!("inspector-time"));
+                                        ui.strong("");
+                                        ui.strong(tr!("osc-monitor-peer"));
+                                        ui.strong(tr!("address"));
+                                        ui.strong(tr!("osc-monitor-arguments"));
+                                        ui.end_row();
+
+                                        for traffic in self.osc_monitor.traffic() {
+                                            ui.monospace(
+                                                traffic.time.format("%H:%M:%S%.3f").to_string(),
+                                            );
+                                            let color = match traffic.direction {
+                                                Direction::In => egui::Color32::LIGHT_GREEN,
+                                                Direction::Out => egui::Color32::LIGHT_BLUE,
+                                            };
+                                            ui.colored_label(color, traffic.direction.to_string());
+                                            ui.label(&traffic.peer);
+                                            ui.monospace(&traffic.message.address);
+                                            let args: Vec<String> = traffic
+                                                .message
+                                                .args
+                                                .iter()
+                                                .map(ToString::to_string)
+                                                .collect();
+                                            ui.monospace(args.join(" "));
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                    }
+                    OscMonitorTab::Addresses => {
+                        ui.label(tr!("osc-monitor-addresses-hint"));
+                        let tree = self.osc_address_tree();
+                        egui::ScrollArea::vertical()
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| {
+                                if tree.children.is_empty() {
+                                    ui.weak(tr!("osc-monitor-no-addresses"));
+                                }
+                                draw_address_node(ui, "", &tree);
+                            });
+                    }
+                }
+            });
+        self.show_osc_monitor = open;
+    }
+}
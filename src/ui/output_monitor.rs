@@ -0,0 +1,321 @@
+use crate::dmx::{ChannelCheck, UNIVERSE_SIZE};
+use crate::i18n::tr;
+use crate::inhibit;
+use crate::ownership::Owner;
+use crate::test_pattern::{Pattern, TestPattern};
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+
+const COLUMNS: usize = 32;
+
+impl HaloApp {
+    /// Channel levels of one universe of the live output.
+    pub(crate) fn draw_output_monitor(&mut self, ui: &mut egui::Ui) {
+        if !self.performance_mode {
+            self.draw_channel_check(ui);
+            self.draw_test_pattern(ui);
+            ui.separator();
+        }
+
+        let universes: Vec<u16> = self.output.universe_numbers().collect();
+        let Some(&first) = universes.first() else {
+            ui.label(tr!("monitor-nothing"));
+            return;
+        };
+        // For the hover text and the channel's sources.
+        self.owners_shown.set(true);
+        if !universes.contains(&self.monitor_universe) {
+            self.monitor_universe = first;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("universe-label"));
+            for universe in universes {
+                let label = if self.muted_universes.contains(&universe) {
+                    format!("{} 🔇", universe)
+                } else {
+                    universe.to_string()
+                };
+                ui.selectable_value(&mut self.monitor_universe, universe, label);
+            }
+            ui.separator();
+            let universe = self.monitor_universe;
+            let mut muted = self.muted_universes.contains(&universe);
+            if ui
+                .toggle_value(&mut muted, tr!("monitor-mute"))
+                .on_hover_text(tr!("monitor-mute-hint"))
+                .changed()
+            {
+                if muted {
+                    self.muted_universes.push(universe);
+                    self.muted_universes.sort_unstable();
+                    self.log.warn(tr!("monitor-muted", universe = universe));
+                } else {
+                    self.muted_universes.retain(|&u| u != universe);
+                    self.log.info(tr!("monitor-unmuted", universe = universe));
+                }
+            }
+            if self.muted_universes.len() > 1
+                && ui.small_button(tr!("monitor-unmute-all")).clicked()
+            {
+                self.muted_universes.clear();
+                self.log.info(tr!("monitor-unmuted-all"));
+            }
+        });
+        self.draw_channel_sources(ui);
+
+        let universe = self.monitor_universe;
+        let checked = self
+            .channel_check
+            .filter(|check| check.universe == universe)
+            .map(|check| check.channel);
+        let cell = (ui.available_width() / COLUMNS as f32).clamp(14.0, 40.0);
+        let rows = UNIVERSE_SIZE.div_ceil(COLUMNS);
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let (rect, response) = ui.allocate_exact_size(
+                egui::vec2(cell * COLUMNS as f32, cell * rows as f32),
+                egui::Sense::click(),
+            );
+            let response =
+                accessibility::named(response, tr!("monitor-levels", universe = universe));
+            let channel_at = |pointer: egui::Pos2| {
+                let offset = pointer - rect.min;
+                let index = (offset.y / cell) as usize * COLUMNS + (offset.x / cell) as usize;
+                (index < UNIVERSE_SIZE).then_some(index as u16 + 1)
+            };
+            if response.clicked() {
+                let clicked = response.interact_pointer_pos().and_then(channel_at);
+                self.monitor_channel = if clicked == self.monitor_channel {
+                    None
+                } else {
+                    clicked
+                };
+            }
+            let painter = ui.painter_at(rect);
+            let text = ui.visuals().text_color();
+            let fill = ui.visuals().selection.bg_fill;
+
+            for index in 0..UNIVERSE_SIZE {
+                let channel = index as u16 + 1;
+                let level = self.output.get(universe, channel);
+                let min = rect.min
+                    + egui::vec2(
+                        (index % COLUMNS) as f32 * cell,
+                        (index / COLUMNS) as f32 * cell,
+                    );
+                let cell_rect = egui::Rect::from_min_size(min, egui::vec2(cell, cell)).shrink(1.0);
+                painter.rect_filled(cell_rect, 1.0, ui.visuals().extreme_bg_color);
+                if checked == Some(channel) {
+                    painter.rect_stroke(
+                        cell_rect,
+                        1.0,
+                        egui::Stroke::new(2.0, egui::Color32::YELLOW),
+                        egui::StrokeKind::Inside,
+                    );
+                } else if self.monitor_channel == Some(channel) {
+                    painter.rect_stroke(
+                        cell_rect,
+                        1.0,
+                        egui::Stroke::new(2.0, ui.visuals().selection.stroke.color),
+                        egui::StrokeKind::Inside,
+                    );
+                }
+                if level > 0 {
+                    let height = cell_rect.height() * level as f32 / 255.0;
+                    let bar = egui::Rect::from_min_max(
+                        egui::pos2(cell_rect.left(), cell_rect.bottom() - height),
+                        cell_rect.max,
+                    );
+                    painter.rect_filled(bar, 1.0, fill);
+                }
+                if cell >= 24.0 {
+                    painter.text(
+                        cell_rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        level.to_string(),
+                        egui::FontId::monospace(cell * 0.35),
+                        text,
+                    );
+                }
+            }
+
+            if let Some(channel) = response.hover_pos().and_then(channel_at) {
+                let patched = self.show.fixtures.iter().find_map(|fixture| {
+                    fixture
+                        .attribute_at(universe, channel)
+                        .map(|attribute| format!("{} {}", fixture.name, attribute))
+                });
+                let owner = self
+                    .output_owners
+                    .get(universe, channel)
+                    .map(|owner| self.owner_name(owner));
+                response.on_hover_text(format!(
+                    "{}.{:03} = {}{}{}",
+                    universe,
+                    channel,
+                    self.output.get(universe, channel),
+                    patched.map(|p| format!("\n{}", p)).unwrap_or_default(),
+                    owner
+                        .map(|o| format!("\n{}", tr!("monitor-from", owner = o)))
+                        .unwrap_or_default()
+                ));
+            }
+        });
+    }
+
+    /// The chain of stages behind the channel clicked in the monitor, from
+    /// whatever set it to whatever scaled it on the way out.
+    fn draw_channel_sources(&mut self, ui: &mut egui::Ui) {
+        let Some(channel) = self.monitor_channel else {
+            ui.weak(tr!("monitor-click-channel"));
+            return;
+        };
+        let universe = self.monitor_universe;
+        let patched = self.show.fixtures.iter().find_map(|fixture| {
+            fixture
+                .attribute_at(universe, channel)
+                .map(|attribute| format!(" ({} {})", fixture.name, attribute))
+        });
+        let chain: Vec<String> = self
+            .output_owners
+            .chain(universe, channel)
+            .iter()
+            .map(|&owner| match owner {
+                Owner::Cue(index) => {
+                    let name = self.owner_name(owner);
+                    match self.show.cues.get(index) {
+                        Some(cue) if cue.is_playing => {
+                            tr!(
+                                "monitor-fading",
+                                name = name,
+                                percent = format!("{:.0}", cue.progress * 100.0)
+                            )
+                        }
+                        _ => name,
+                    }
+                }
+                _ => self.owner_name(owner),
+            })
+            .collect();
+        ui.horizontal_wrapped(|ui| {
+            ui.strong(format!(
+                "{}.{:03} = {}{}:",
+                universe,
+                channel,
+                self.output.get(universe, channel),
+                patched.unwrap_or_default()
+            ));
+            if chain.is_empty() {
+                ui.label(tr!("monitor-uncontrolled"));
+            } else {
+                ui.label(chain.join(" → "));
+            }
+            if accessibility::named(ui.small_button("✖"), tr!("close"))
+                .on_hover_text(tr!("close"))
+                .clicked()
+            {
+                self.monitor_channel = None;
+            }
+        });
+    }
+
+    /// Test patterns for commissioning: replace one universe with a pattern
+    /// until it's switched back to the show.
+    fn draw_test_pattern(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("monitor-test-pattern"));
+            let selected = self
+                .test_pattern
+                .map_or_else(|| tr!("clock-role-off"), |test| test.pattern.to_string());
+            let mut pattern = self.test_pattern.map(|test| test.pattern);
+            egui::ComboBox::from_id_salt("test_pattern")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut pattern, None, tr!("clock-role-off"));
+                    for kind in Pattern::ALL {
+                        ui.selectable_value(&mut pattern, Some(kind), kind.to_string());
+                    }
+                });
+            let universe = self
+                .test_pattern
+                .map_or(self.monitor_universe, |test| test.universe);
+            self.test_pattern = pattern.map(|pattern| TestPattern { universe, pattern });
+            if let Some(test) = &mut self.test_pattern {
+                ui.label(tr!("monitor-on-universe"));
+                ui.add(egui::DragValue::new(&mut test.universe).range(1..=32767));
+                self.monitor_universe = test.universe;
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 180, 0),
+                    tr!("monitor-show-not-here"),
+                );
+            }
+        });
+    }
+
+    /// Raw channel check: force one channel to a test level and step
+    /// through its neighbours with the arrow keys.
+    fn draw_channel_check(&mut self, ui: &mut egui::Ui) {
+        let inhibited = inhibit::channels(&self.rig());
+        ui.horizontal(|ui| {
+            let mut checking = self.channel_check.is_some();
+            if ui
+                .toggle_value(&mut checking, tr!("monitor-channel-check"))
+                .changed()
+            {
+                self.channel_check = checking.then(|| ChannelCheck::new(self.monitor_universe));
+            }
+            let Some(check) = &mut self.channel_check else {
+                ui.label(tr!("monitor-channel-check-hint"));
+                return;
+            };
+            ui.label(tr!("universe-label"));
+            ui.add(egui::DragValue::new(&mut check.universe).range(1..=32767));
+            ui.label(tr!("channel-label"));
+            ui.add(egui::DragValue::new(&mut check.channel).range(1..=UNIVERSE_SIZE as u16));
+            if accessibility::named(ui.button("◀"), tr!("monitor-previous-channel")).clicked() {
+                check.step(false, &inhibited);
+            }
+            if accessibility::named(ui.button("▶"), tr!("monitor-next-channel")).clicked() {
+                check.step(true, &inhibited);
+            }
+            ui.label(tr!("level-label"));
+            ui.add(egui::Slider::new(&mut check.level, 0..=255));
+            ui.checkbox(&mut check.solo, tr!("flash-mode-solo"))
+                .on_hover_text(tr!("monitor-solo-hint"));
+        });
+
+        let Some(check) = &mut self.channel_check else {
+            return;
+        };
+        if !ui.ctx().wants_keyboard_input() {
+            ui.input(|input| {
+                if input.key_pressed(egui::Key::ArrowRight) {
+                    check.step(true, &inhibited);
+                }
+                if input.key_pressed(egui::Key::ArrowLeft) {
+                    check.step(false, &inhibited);
+                }
+            });
+        }
+        self.monitor_universe = check.universe;
+        let patched = self.show.fixtures.iter().find_map(|fixture| {
+            fixture
+                .attribute_at(check.universe, check.channel)
+                .map(|attribute| {
+                    let inhibited = if fixture.inhibited {
+                        tr!("monitor-inhibited")
+                    } else {
+                        String::new()
+                    };
+                    format!("{} {}{}", fixture.name, attribute, inhibited)
+                })
+        });
+        ui.label(tr!(
+            "monitor-checking",
+            channel = format!("{}.{:03}", check.universe, check.channel),
+            level = check.level,
+            patched = patched.unwrap_or_else(|| tr!("monitor-not-patched"))
+        ));
+    }
+}
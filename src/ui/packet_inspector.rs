@@ -0,0 +1,202 @@
+use crate::i18n::tr;
+use crate::packet_inspector::{self, SequenceEvent};
+use crate::ui::preferences::PreferencesSection;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+const WARNING: egui::Color32 = egui::Color32::from_rgb(255, 180, 0);
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub(crate) enum InspectorTab {
+    #[default]
+    Sources,
+    Packets,
+}
+
+/// The inspector window's view of the capture.
+#[derive(Default)]
+pub(crate) struct InspectorView {
+    tab: InspectorTab,
+    paused: bool,
+    /// Only packets on this universe in the packet list.
+    universe: Option<u16>,
+    /// Only packets with something wrong in the packet list.
+    problems_only: bool,
+}
+
+impl HaloApp {
+    /// Art-Net and sACN packets coming in, by sender and universe, with
+    /// sequence gaps and duplicated universes picked out.
+    pub(crate) fn draw_packet_inspector(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_packet_inspector;
+        egui::Window::new(tr!("menu-packet-inspector"))
+            .id(egui::Id::new("packet_inspector"))
+            .open(&mut open)
+            .default_size([620.0, 360.0])
+            .show(ctx, |ui| {
+                let view = &mut self.inspector_view;
+                ui.horizontal(|ui| {
+                    ui.selectable_value(
+                        &mut view.tab,
+                        InspectorTab::Sources,
+                        tr!("inspector-sources"),
+                    );
+                    ui.selectable_value(
+                        &mut view.tab,
+                        InspectorTab::Packets,
+                        tr!("inspector-packets"),
+                    );
+                    ui.separator();
+                    let label = if view.paused {
+                        tr!("inspector-resume")
+                    } else {
+                        tr!("inspector-pause")
+                    };
+                    ui.toggle_value(&mut view.paused, label);
+                    if ui.button(tr!("clear")).clicked() {
+                        self.packet_inspector.clear();
+                    }
+                });
+                if !self.preferences.network_input.enabled() {
+                    ui.horizontal(|ui| {
+                        ui.weak(tr!("inspector-input-off"));
+                        if ui.small_button(tr!("menu-preferences")).clicked() {
+                            self.open_preferences_at(PreferencesSection::NetworkInput);
+                        }
+                    });
+                }
+                ui.separator();
+                match self.inspector_view.tab {
+                    InspectorTab::Sources => self.draw_inspector_sources(ui),
+                    InspectorTab::Packets => self.draw_inspector_packets(ui),
+                }
+            });
+        self.show_packet_inspector = open;
+        self.packet_inspector
+            .set_capturing(open && !self.inspector_view.paused);
+        if open {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+    }
+
+    fn draw_inspector_sources(&mut self, ui: &mut egui::Ui) {
+        let streams = self.packet_inspector.streams();
+        let duplicated = packet_inspector::duplicated(&streams);
+        for universe in &duplicated {
+            ui.colored_label(WARNING, tr!("inspector-duplicated", universe = *universe));
+        }
+        if streams.is_empty() {
+            ui.weak(tr!("nothing-received"));
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("inspector_sources")
+                    .striped(true)
+                    .num_columns(8)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("universe"));
+                        ui.strong(tr!("inspector-protocol"));
+                        ui.strong(tr!("inspector-from"));
+                        ui.strong(tr!("name"));
+                        ui.strong(tr!("inspector-priority"));
+                        ui.strong(tr!("inspector-rate"));
+                        ui.strong(tr!("inspector-packets"));
+                        ui.strong(tr!("inspector-lost-late"));
+                        ui.end_row();
+                        for stream in &streams {
+                            let universe = ui.selectable_label(
+                                self.inspector_view.universe == Some(stream.universe),
+                                stream.universe.to_string(),
+                            );
+                            if universe
+                                .on_hover_text(tr!("inspector-show-universe"))
+                                .clicked()
+                            {
+                                self.inspector_view.universe = Some(stream.universe);
+                                self.inspector_view.tab = InspectorTab::Packets;
+                            }
+                            ui.label(stream.protocol);
+                            ui.monospace(stream.from.to_string());
+                            ui.label(&stream.name);
+                            ui.label(stream.priority.to_string());
+                            if stream.is_quiet() {
+                                ui.weak(tr!(
+                                    "inspector-quiet",
+                                    seconds = format!("{:.0}", stream.since_last().as_secs_f32())
+                                ));
+                            } else {
+                                ui.monospace(format!("{:.1}{}", stream.rate, tr!("unit-hz")));
+                            }
+                            ui.monospace(stream.packets.to_string());
+                            let problems = format!("{} / {}", stream.lost, stream.out_of_order);
+                            if stream.lost + stream.out_of_order > 0 {
+                                ui.colored_label(WARNING, problems);
+                            } else {
+                                ui.monospace(problems);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn draw_inspector_packets(&mut self, ui: &mut egui::Ui) {
+        let view = &mut self.inspector_view;
+        ui.horizontal(|ui| {
+            let mut filtered = view.universe.is_some();
+            if ui.checkbox(&mut filtered, tr!("universe")).changed() {
+                view.universe = filtered.then_some(1);
+            }
+            if let Some(universe) = &mut view.universe {
+                ui.add(egui::DragValue::new(universe).range(1..=63999));
+            }
+            ui.checkbox(&mut view.problems_only, tr!("inspector-only-problems"));
+        });
+        let (universe, problems_only) = (view.universe, view.problems_only);
+        let packets: Vec<_> = self
+            .packet_inspector
+            .packets(problems_only)
+            .into_iter()
+            .filter(|packet| universe.is_none_or(|universe| packet.universe == universe))
+            .collect();
+        egui::ScrollArea::vertical()
+            .stick_to_bottom(true)
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("inspector_packets")
+                    .striped(true)
+                    .num_columns(6)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("inspector-time"));
+                        ui.strong(tr!("inspector-from"));
+                        ui.strong(tr!("inspector-protocol"));
+                        ui.strong(tr!("universe"));
+                        ui.strong(tr!("inspector-seq"));
+                        ui.strong("");
+                        ui.end_row();
+                        for packet in &packets {
+                            ui.monospace(packet.time.format("%H:%M:%S%.3f").to_string());
+                            ui.monospace(packet.from.to_string());
+                            ui.label(packet.protocol);
+                            ui.monospace(packet.universe.to_string());
+                            ui.monospace(packet.sequence.to_string());
+                            match packet.event {
+                                Some(SequenceEvent::Terminated) => {
+                                    ui.weak(SequenceEvent::Terminated.to_string());
+                                }
+                                Some(event) => {
+                                    ui.colored_label(WARNING, event.to_string());
+                                }
+                                None => {
+                                    ui.label("");
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
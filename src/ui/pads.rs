@@ -0,0 +1,394 @@
+use crate::cue;
+use crate::fixture::Fixture;
+use crate::flash::{Flash, FlashMode};
+use crate::i18n::tr;
+use crate::macros::MacroAction;
+use crate::show::{Pad, PadBank, PadLayout, PadRelease};
+use crate::song::Song;
+use crate::ui::accessibility;
+use crate::ui::script_editor::ScriptTarget;
+use crate::ui::tags::draw_tag_picker;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+const PAD_SPACING: f32 = 10.0;
+
+/// Changes to the pad list, made once the grid is drawn.
+enum PadEdit {
+    Rename(usize, String),
+    Delete(usize),
+    /// A new pad in this bank.
+    Add(usize),
+}
+
+/// The first "Pad <n>" label no pad has yet.
+fn new_label(pads: &[Pad]) -> String {
+    (1..)
+        .map(|n| tr!("pad-default-label", number = n))
+        .find(|label| pads.iter().all(|pad| &pad.label != label))
+        .unwrap()
+}
+
+/// How the pad lets go, and its fade-out.
+fn draw_release(ui: &mut egui::Ui, pad: &mut Pad) {
+    ui.horizontal(|ui| {
+        ui.label(tr!("pad-release-label"));
+        egui::ComboBox::from_id_salt("pad_release")
+            .selected_text(pad.release.kind())
+            .show_ui(ui, |ui| {
+                for kind in [
+                    PadRelease::Toggle,
+                    PadRelease::Momentary,
+                    PadRelease::Seconds(2.0),
+                    PadRelease::Bars(1),
+                ] {
+                    let selected = kind.kind() == pad.release.kind();
+                    if ui.selectable_label(selected, kind.kind()).clicked() && !selected {
+                        pad.release = kind;
+                    }
+                }
+            });
+        match &mut pad.release {
+            PadRelease::Seconds(seconds) => {
+                ui.add(
+                    egui::DragValue::new(seconds)
+                        .range(0.1..=600.0)
+                        .speed(0.1)
+                        .suffix(tr!("unit-seconds")),
+                );
+            }
+            PadRelease::Bars(bars) => {
+                ui.add(
+                    egui::DragValue::new(bars)
+                        .range(1..=64)
+                        .suffix(tr!("unit-bars")),
+                );
+            }
+            PadRelease::Toggle | PadRelease::Momentary => {}
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr!("pad-fade-out"));
+        let mut seconds = pad.fade_out.as_secs_f32();
+        if ui
+            .add(
+                egui::DragValue::new(&mut seconds)
+                    .range(0.0..=60.0)
+                    .speed(0.05)
+                    .suffix(tr!("unit-seconds")),
+            )
+            .changed()
+        {
+            pad.fade_out = Duration::from_secs_f32(seconds);
+        }
+    });
+}
+
+/// Whether the pad flashes, how, and which fixtures.
+fn draw_flash(ui: &mut egui::Ui, flash: &mut Option<Flash>, fixtures: &[Fixture]) {
+    let mut on = flash.is_some();
+    if ui.checkbox(&mut on, tr!("pad-flash-fixtures")).changed() {
+        *flash = on.then(Flash::default);
+    }
+    let Some(flash) = flash else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        for mode in [FlashMode::Add, FlashMode::Solo] {
+            ui.radio_value(&mut flash.mode, mode, mode.to_string());
+        }
+    })
+    .response
+    .on_hover_text(tr!("pad-flash-solo-hint"));
+    ui.horizontal(|ui| {
+        if ui.small_button(tr!("all")).clicked() {
+            flash.fixtures = fixtures.iter().map(|fixture| fixture.id).collect();
+        }
+        if ui.small_button(tr!("none")).clicked() {
+            flash.fixtures.clear();
+        }
+    });
+    egui::ScrollArea::vertical()
+        .max_height(240.0)
+        .show(ui, |ui| {
+            for fixture in fixtures {
+                draw_link(ui, &mut flash.fixtures, fixture.id, &fixture.name);
+            }
+        });
+}
+
+/// Ticks `value` in or out of `list`.
+fn draw_link<T: PartialEq>(ui: &mut egui::Ui, list: &mut Vec<T>, value: T, label: &str) {
+    let mut linked = list.contains(&value);
+    if ui.checkbox(&mut linked, label).changed() {
+        if linked {
+            list.push(value);
+        } else {
+            list.retain(|item| *item != value);
+        }
+    }
+}
+
+/// Grid size and bank names, the songs and sections that bring a bank up,
+/// and adding and removing banks.
+fn draw_layout_menu(
+    ui: &mut egui::Ui,
+    layout: &mut PadLayout,
+    bank: usize,
+    songs: &[Song],
+    sections: &[String],
+) -> Option<usize> {
+    let mut remove = None;
+    egui::Grid::new("pad_layout").num_columns(2).show(ui, |ui| {
+        ui.label(tr!("pad-columns"));
+        ui.add(egui::DragValue::new(&mut layout.columns).range(1..=PadLayout::MAX_COLUMNS));
+        ui.end_row();
+        ui.label(tr!("pad-rows"));
+        ui.add(egui::DragValue::new(&mut layout.rows).range(1..=PadLayout::MAX_ROWS));
+        ui.end_row();
+        ui.label(tr!("pad-bank-name"));
+        ui.text_edit_singleline(&mut layout.banks[bank].name);
+        ui.end_row();
+    });
+    let links = &mut layout.banks[bank];
+    if !songs.is_empty() {
+        ui.menu_button(tr!("pad-bank-songs"), |ui| {
+            for song in songs {
+                draw_link(ui, &mut links.songs, song.id, &song.name);
+            }
+        });
+    }
+    if !sections.is_empty() {
+        ui.menu_button(tr!("pad-bank-sections"), |ui| {
+            for section in sections {
+                draw_link(ui, &mut links.sections, section.clone(), section);
+            }
+        });
+    }
+    ui.horizontal(|ui| {
+        if ui.button(tr!("pad-add-bank")).clicked() {
+            let name = tr!("pad-default-bank", number = layout.banks.len() + 1);
+            layout.banks.push(PadBank::new(&name));
+        }
+        if ui
+            .add_enabled(
+                layout.banks.len() > 1,
+                egui::Button::new(tr!("pad-remove-bank")),
+            )
+            .on_hover_text(tr!("pad-remove-bank-hint"))
+            .clicked()
+        {
+            remove = Some(bank);
+            ui.close_menu();
+        }
+    });
+    remove
+}
+
+impl HaloApp {
+    /// The override pad grid, a bank at a time. Right-clicking a pad edits
+    /// what it triggers and how it looks, and empty cells add pads, unless
+    /// `editable` is false.
+    pub(crate) fn draw_pads(&mut self, ui: &mut egui::Ui, editable: bool) {
+        let mut pad_clicks = Vec::new();
+        let mut edits = Vec::new();
+        let mut remove_bank = None;
+        let pad_size = self.pad_size();
+        let now = Instant::now();
+        self.pad_bank = self
+            .pad_bank
+            .min(self.show.pad_layout.banks.len().saturating_sub(1));
+        let bank = self.pad_bank;
+
+        if self.show.pad_layout.banks.len() > 1 || editable {
+            ui.horizontal(|ui| {
+                for (i, name) in self.show.pad_layout.banks.iter().enumerate() {
+                    if ui.selectable_label(i == bank, &name.name).clicked() && i != bank {
+                        pad_clicks.push(MacroAction::PadBank(i + 1));
+                    }
+                }
+                if editable {
+                    ui.menu_button(tr!("pad-layout"), |ui| {
+                        let sections: Vec<String> = cue::sections(&self.show.cues)
+                            .into_iter()
+                            .filter_map(|(title, _)| title)
+                            .collect();
+                        remove_bank = draw_layout_menu(
+                            ui,
+                            &mut self.show.pad_layout,
+                            bank,
+                            &self.show.songs,
+                            &sections,
+                        );
+                    });
+                }
+            });
+            ui.add_space(PAD_SPACING);
+        }
+
+        let layout = &self.show.pad_layout;
+        let columns = layout.columns.max(1);
+        let mut rows = layout.rows(&self.show.pads, bank);
+        if editable {
+            rows.resize_with(rows.len().max(layout.rows), Vec::new);
+        }
+        let cell = |width: usize| {
+            egui::vec2(
+                width as f32 * pad_size + (width - 1) as f32 * PAD_SPACING,
+                pad_size,
+            )
+        };
+        for row in rows {
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = PAD_SPACING;
+                let mut used = 0;
+                for i in row {
+                    let pad = &mut self.show.pads[i];
+                    let width = pad.width.clamp(1, columns);
+                    used += width;
+                    let idle = pad
+                        .tag
+                        .color
+                        .map_or(egui::Color32::from_rgb(60, 60, 60), |color| {
+                            color.color32().gamma_multiply(0.5)
+                        });
+                    let response =
+                        ui.add(
+                            egui::Button::new(egui::RichText::new(format!(
+                                "{}{}",
+                                pad.tag.prefix(),
+                                pad.label
+                            )))
+                            .min_size(cell(width))
+                            // Fading pads dim back to idle.
+                            .fill(idle.lerp_to_gamma(
+                                egui::Color32::from_rgb(100, 200, 100),
+                                pad.level(now),
+                            )),
+                        );
+
+                    let response = accessibility::named(
+                        response,
+                        if pad.active {
+                            tr!("pad-accessible-on", label = pad.label.as_str())
+                        } else {
+                            tr!("pad-accessible-off", label = pad.label.as_str())
+                        },
+                    );
+
+                    if pad.release == PadRelease::Momentary {
+                        // Space holds a focused pad down like a press.
+                        let down = response.is_pointer_button_down_on()
+                            || (response.has_focus()
+                                && ui.input(|input| input.key_down(egui::Key::Space)));
+                        if down && self.held_pad.is_none() {
+                            self.held_pad = Some(i);
+                            pad_clicks.push(MacroAction::SetPad {
+                                pad: pad.label.clone(),
+                                active: true,
+                            });
+                        } else if !down && self.held_pad == Some(i) {
+                            self.held_pad = None;
+                            pad_clicks.push(MacroAction::SetPad {
+                                pad: pad.label.clone(),
+                                active: false,
+                            });
+                        }
+                    } else if response.clicked() {
+                        pad_clicks.push(MacroAction::SetPad {
+                            pad: pad.label.clone(),
+                            active: !pad.active,
+                        });
+                    }
+                    if editable {
+                        accessibility::context_menu(ui, &response, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("label-label"));
+                                let mut label = pad.label.clone();
+                                if ui.text_edit_singleline(&mut label).changed() {
+                                    edits.push(PadEdit::Rename(i, label));
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("width-label"));
+                                ui.add(egui::DragValue::new(&mut pad.width).range(1..=columns));
+                            });
+                            draw_release(ui, pad);
+                            ui.menu_button(tr!("pad-flash"), |ui| {
+                                draw_flash(ui, &mut pad.flash, &self.show.fixtures)
+                            });
+                            if ui.button(tr!("pad-edit-script")).clicked() {
+                                self.script_target = Some(ScriptTarget::Pad(i));
+                                ui.close_menu();
+                            }
+                            ui.menu_button(tr!("tag"), |ui| draw_tag_picker(ui, &mut pad.tag));
+                            ui.menu_button(tr!("pad-run-macro"), |ui| {
+                                if ui.radio(pad.run_macro.is_none(), tr!("none")).clicked() {
+                                    pad.run_macro = None;
+                                    ui.close_menu();
+                                }
+                                for recorded in &self.show.macros {
+                                    let selected = pad.run_macro.as_ref() == Some(&recorded.name);
+                                    if ui.radio(selected, &recorded.name).clicked() {
+                                        pad.run_macro = Some(recorded.name.clone());
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                            if self.show.pad_layout.banks.len() > 1 {
+                                ui.menu_button(tr!("pad-move-to-bank"), |ui| {
+                                    for (b, name) in self.show.pad_layout.banks.iter().enumerate() {
+                                        if ui.radio(pad.bank == b, &name.name).clicked() {
+                                            pad.bank = b;
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                            }
+                            ui.separator();
+                            if ui.button(tr!("pad-delete")).clicked() {
+                                edits.push(PadEdit::Delete(i));
+                                ui.close_menu();
+                            }
+                        });
+                    }
+                }
+                if editable {
+                    for _ in used..columns {
+                        let add = ui
+                            .add(egui::Button::new("+").min_size(cell(1)).frame(false))
+                            .on_hover_text(tr!("pad-add"));
+                        let add = accessibility::named(add, tr!("pad-add"));
+                        if add.clicked() {
+                            edits.push(PadEdit::Add(bank));
+                        }
+                    }
+                }
+            });
+            ui.add_space(PAD_SPACING - ui.spacing().item_spacing.y);
+        }
+
+        for edit in edits {
+            match edit {
+                PadEdit::Rename(i, label) => self.show.rename_pad(i, &label),
+                PadEdit::Delete(i) => {
+                    self.show.pads.remove(i);
+                    self.script_target = None;
+                }
+                PadEdit::Add(bank) => {
+                    let mut pad = Pad::new(&new_label(&self.show.pads));
+                    pad.bank = bank;
+                    self.show.pads.push(pad);
+                }
+            }
+        }
+        if let Some(bank) = remove_bank {
+            self.show.remove_pad_bank(bank);
+            self.script_target = None;
+        }
+        for action in pad_clicks {
+            self.perform(action);
+        }
+    }
+}
@@ -0,0 +1,719 @@
+use crate::driver::FixtureDriver;
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::midi;
+use crate::processing::{ChannelProcessing, Curve};
+use crate::rdm::Uid;
+use crate::ui::accessibility;
+use crate::ui::address_map::AddressMap;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+/// Selections for the copy and replace tools under the patch list.
+pub(crate) struct PatchTools {
+    copy_from: Option<usize>,
+    copy_to: Option<usize>,
+    replace_type: Option<String>,
+    replace_with: usize,
+    /// The fixture whose output processing is being edited.
+    processing: Option<usize>,
+    pub(crate) map: AddressMap,
+    rdm_universe: u16,
+    /// Start addresses typed in for discovered devices, not yet sent.
+    rdm_addresses: Vec<(Uid, u16)>,
+}
+
+impl Default for PatchTools {
+    fn default() -> Self {
+        Self {
+            copy_from: None,
+            copy_to: None,
+            replace_type: None,
+            replace_with: 0,
+            processing: None,
+            map: AddressMap::default(),
+            rdm_universe: 1,
+            rdm_addresses: Vec::new(),
+        }
+    }
+}
+
+/// Picks a patched fixture by id.
+pub(crate) fn fixture_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    fixtures: &[Fixture],
+    selected: &mut Option<usize>,
+) {
+    let text = selected
+        .and_then(|id| fixtures.iter().find(|fixture| fixture.id == id))
+        .map_or(tr!("select"), |fixture| fixture.name.clone());
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            for fixture in fixtures {
+                ui.selectable_value(selected, Some(fixture.id), &fixture.name);
+            }
+        });
+}
+
+/// Whether the fixture goes out as DMX or through a driver, and the
+/// driver's settings.
+fn draw_driver(ui: &mut egui::Ui, fixture: &mut Fixture, error: Option<&str>) {
+    ui.horizontal(|ui| {
+        ui.label(tr!("patch-send-as"));
+        let kind = fixture.driver.as_ref().map_or("DMX", FixtureDriver::kind);
+        egui::ComboBox::from_id_salt("driver_kind")
+            .selected_text(kind)
+            .show_ui(ui, |ui| {
+                if ui
+                    .selectable_label(fixture.driver.is_none(), "DMX")
+                    .clicked()
+                {
+                    fixture.driver = None;
+                }
+                for driver in FixtureDriver::kinds() {
+                    let selected = driver.kind() == kind;
+                    if ui.selectable_label(selected, driver.kind()).clicked() && !selected {
+                        fixture.driver = Some(driver);
+                    }
+                }
+            });
+        match &mut fixture.driver {
+            None => {}
+            Some(FixtureDriver::Osc { target, prefix }) => {
+                ui.add(
+                    egui::TextEdit::singleline(target)
+                        .hint_text("host:port")
+                        .desired_width(140.0),
+                );
+                ui.label(tr!("patch-address"));
+                ui.add(egui::TextEdit::singleline(prefix).desired_width(100.0));
+            }
+            Some(FixtureDriver::Http {
+                host, method, path, ..
+            }) => {
+                egui::ComboBox::from_id_salt("driver_method")
+                    .selected_text(method.as_str())
+                    .width(60.0)
+                    .show_ui(ui, |ui| {
+                        for verb in ["GET", "POST", "PUT"] {
+                            ui.selectable_value(method, verb.to_string(), verb);
+                        }
+                    });
+                ui.add(
+                    egui::TextEdit::singleline(host)
+                        .hint_text("host")
+                        .desired_width(120.0),
+                );
+                ui.add(egui::TextEdit::singleline(path).desired_width(140.0));
+            }
+            Some(FixtureDriver::Midi {
+                port,
+                channel,
+                controller,
+            }) => {
+                egui::ComboBox::from_id_salt("driver_port")
+                    .selected_text(if port.is_empty() {
+                        tr!("select")
+                    } else {
+                        port.clone()
+                    })
+                    .show_ui(ui, |ui| {
+                        for name in midi::output_ports() {
+                            ui.selectable_value(port, name.clone(), name);
+                        }
+                    });
+                ui.label(tr!("patch-channel"));
+                ui.add(egui::DragValue::new(channel).range(1..=16));
+                ui.label(tr!("patch-first-cc"));
+                ui.add(egui::DragValue::new(controller).range(0..=127));
+            }
+        }
+    });
+    match &mut fixture.driver {
+        None => return,
+        Some(FixtureDriver::Osc { .. }) => {
+            ui.label(tr!("patch-osc-hint"));
+        }
+        Some(FixtureDriver::Http { body, .. }) => {
+            ui.add(
+                egui::TextEdit::multiline(body)
+                    .code_editor()
+                    .desired_rows(2)
+                    .desired_width(f32::INFINITY),
+            );
+            ui.label(tr!("patch-http-hint"));
+        }
+        Some(FixtureDriver::Midi { .. }) => {
+            ui.label(tr!("patch-midi-hint"));
+        }
+    }
+    ui.label(tr!("patch-driver-hint"));
+    if let Some(error) = error.filter(|error| error.starts_with(&format!("{}:", fixture.name))) {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+    ui.add_space(6.0);
+}
+
+impl HaloApp {
+    pub(crate) fn draw_patch_view(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(tr!("patch-title"));
+            if ui.button(tr!("patch-import")).clicked() {
+                self.show_patch_import = true;
+            }
+            if ui.button(tr!("patch-venue")).clicked() {
+                self.show_venue = true;
+            }
+        });
+        ui.add_space(10.0);
+        self.draw_inhibited(ui);
+
+        let mut clone = None;
+        egui::Grid::new("patch_list")
+            .striped(true)
+            .num_columns(8)
+            .show(ui, |ui| {
+                ui.strong(tr!("fixture"));
+                ui.strong(tr!("patch-type"));
+                ui.strong(tr!("patch-address"));
+                ui.strong(tr!("patch-mib"))
+                    .on_hover_text(tr!("patch-mib-hint"));
+                ui.strong(tr!("patch-off"))
+                    .on_hover_text(tr!("patch-off-hint"));
+                ui.end_row();
+
+                for index in 0..self.show.fixtures.len() {
+                    let selected = &mut self.patch_tools.map.selected;
+                    let fixture = &mut self.show.fixtures[index];
+                    if ui
+                        .selectable_label(*selected == Some(fixture.id), &fixture.name)
+                        .clicked()
+                    {
+                        *selected = Some(fixture.id);
+                    }
+                    ui.label(&fixture.profile.name);
+                    ui.monospace(format!("{}.{:03}", fixture.universe, fixture.address));
+                    let can_move_in_black = fixture.channel_for(Attribute::Intensity).is_some()
+                        && fixture.profile.channels.len() > 1;
+                    accessibility::named(
+                        ui.add_enabled(
+                            can_move_in_black,
+                            egui::Checkbox::without_text(&mut fixture.move_in_black),
+                        ),
+                        tr!("patch-move-in-black-named", name = fixture.name.as_str()),
+                    );
+                    accessibility::named(
+                        ui.checkbox(&mut fixture.inhibited, ""),
+                        tr!("patch-inhibit-named", name = fixture.name.as_str()),
+                    );
+                    if ui
+                        .small_button(tr!("patch-clone"))
+                        .on_hover_text(tr!("patch-clone-hint"))
+                        .clicked()
+                    {
+                        clone = Some(fixture.id);
+                    }
+                    let editing = self.patch_tools.processing == Some(fixture.id);
+                    let label = if fixture.processing.is_empty()
+                        && fixture.position.is_default()
+                        && fixture.driver.is_none()
+                    {
+                        tr!("patch-output")
+                    } else {
+                        tr!("patch-output-changed")
+                    };
+                    if ui
+                        .selectable_label(editing, label)
+                        .on_hover_text(tr!("patch-output-hint"))
+                        .clicked()
+                    {
+                        self.patch_tools.processing = (!editing).then_some(fixture.id);
+                    }
+                    self.draw_fixture_control_menu(ui, index);
+                    ui.end_row();
+                }
+            });
+        if let Some(id) = clone {
+            let added = self
+                .show
+                .clone_fixture(id)
+                .and_then(|new_id| self.show.fixtures.iter().find(|f| f.id == new_id));
+            match added {
+                Some(fixture) => {
+                    self.log.info(tr!(
+                        "patch-cloned",
+                        name = fixture.name.as_str(),
+                        address = format!("{}.{:03}", fixture.universe, fixture.address)
+                    ));
+                }
+                None => self.log.warn(tr!("patch-clone-no-room")),
+            }
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("patch-move-in-black"));
+            let mut lead = self.show.move_in_black_lead.as_secs_f32();
+            if ui
+                .add(
+                    egui::DragValue::new(&mut lead)
+                        .range(0.0..=60.0)
+                        .speed(0.1)
+                        .suffix(tr!("unit-seconds")),
+                )
+                .changed()
+            {
+                self.show.move_in_black_lead = Duration::from_secs_f32(lead);
+            }
+            ui.label(tr!("patch-before-cue"));
+        });
+        self.draw_processing(ui);
+
+        ui.add_space(20.0);
+        self.draw_fixture_controls(ui);
+
+        ui.add_space(20.0);
+        self.draw_address_map(ui);
+
+        ui.add_space(20.0);
+        ui.label(tr!("patch-add-fixture"));
+        ui.horizontal(|ui| {
+            let selected = self
+                .profile_library
+                .get(self.new_fixture_profile)
+                .map(|profile| profile.name.clone())
+                .unwrap_or_default();
+            if ui
+                .button(format!("{} ▾", selected))
+                .on_hover_text(tr!("patch-browse"))
+                .clicked()
+            {
+                self.show_profile_browser = true;
+            }
+            ui.label(tr!("name-label"));
+            ui.text_edit_singleline(&mut self.new_fixture_name);
+            ui.label(tr!("universe-label"));
+            ui.add(egui::DragValue::new(&mut self.new_fixture_universe).range(1..=32767));
+            ui.label(tr!("address-label"));
+            ui.add(egui::DragValue::new(&mut self.new_fixture_address).range(1..=512));
+
+            if ui.button(tr!("add")).clicked() {
+                if let Some(profile) = self.profile_library.get(self.new_fixture_profile) {
+                    let id = self
+                        .show
+                        .fixtures
+                        .iter()
+                        .map(|fixture| fixture.id + 1)
+                        .max()
+                        .unwrap_or(0);
+                    let name = if self.new_fixture_name.trim().is_empty() {
+                        format!("{} {}", profile.name, id + 1)
+                    } else {
+                        self.new_fixture_name.trim().to_string()
+                    };
+                    let footprint = profile.channels.len() as u16;
+                    self.show.fixtures.push(Fixture::new(
+                        id,
+                        &name,
+                        profile.clone(),
+                        self.new_fixture_universe,
+                        self.new_fixture_address,
+                    ));
+                    self.new_fixture_address = (self.new_fixture_address + footprint).min(512);
+                    self.new_fixture_name.clear();
+                    let name = profile.name.clone();
+                    self.note_recent_profile(&name);
+                }
+            }
+        });
+
+        ui.add_space(20.0);
+        self.draw_patch_tools(ui);
+
+        ui.add_space(20.0);
+        self.draw_rdm(ui);
+    }
+
+    /// The fixtures inhibited for the night, so nobody forgets one is off.
+    fn draw_inhibited(&mut self, ui: &mut egui::Ui) {
+        let names: Vec<&str> = self
+            .show
+            .fixtures
+            .iter()
+            .filter(|fixture| fixture.inhibited)
+            .map(|fixture| fixture.name.as_str())
+            .collect();
+        if names.is_empty() {
+            return;
+        }
+        let mut restore = false;
+        ui.horizontal_wrapped(|ui| {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 180, 0),
+                tr!("patch-inhibited", count = names.len()),
+            );
+            ui.label(names.join(", "));
+            restore = ui.small_button(tr!("patch-restore-all")).clicked();
+        });
+        if restore {
+            for fixture in &mut self.show.fixtures {
+                fixture.inhibited = false;
+            }
+            self.log.info(tr!("patch-restored"));
+        }
+        ui.add_space(10.0);
+    }
+
+    /// Output processing for the fixture picked in the patch list, one row
+    /// per channel.
+    fn draw_processing(&mut self, ui: &mut egui::Ui) {
+        let Some(fixture) = self
+            .patch_tools
+            .processing
+            .and_then(|id| self.show.fixtures.iter_mut().find(|f| f.id == id))
+        else {
+            return;
+        };
+        ui.add_space(10.0);
+        ui.label(tr!("patch-processing", name = fixture.name.as_str()));
+        draw_driver(ui, fixture, self.driver_error.as_deref());
+        egui::Grid::new("processing")
+            .striped(true)
+            .num_columns(5)
+            .show(ui, |ui| {
+                ui.strong(tr!("patch-channel"));
+                ui.strong(tr!("patch-curve"));
+                ui.strong(tr!("patch-invert"));
+                ui.strong(tr!("patch-limits"));
+                ui.end_row();
+
+                for attribute in fixture.profile.channels.clone() {
+                    let index = fixture
+                        .processing
+                        .iter()
+                        .position(|p| p.attribute == attribute);
+                    let mut processing = index.map_or_else(
+                        || ChannelProcessing::new(attribute),
+                        |i| fixture.processing[i].clone(),
+                    );
+                    ui.push_id(attribute, |ui| {
+                        ui.label(attribute.to_string());
+                        egui::ComboBox::from_id_salt("curve")
+                            .selected_text(processing.curve.to_string())
+                            .show_ui(ui, |ui| {
+                                for curve in Curve::kinds() {
+                                    let selected =
+                                        curve.to_string() == processing.curve.to_string();
+                                    if ui.selectable_label(selected, curve.to_string()).clicked()
+                                        && !selected
+                                    {
+                                        processing.curve = curve;
+                                    }
+                                }
+                            });
+                    });
+                    accessibility::named(
+                        ui.checkbox(&mut processing.invert, ""),
+                        tr!("patch-invert"),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::DragValue::new(&mut processing.min)
+                                .range(0.0..=100.0)
+                                .suffix("%"),
+                        );
+                        ui.label(tr!("patch-to"));
+                        ui.add(
+                            egui::DragValue::new(&mut processing.max)
+                                .range(0.0..=100.0)
+                                .suffix("%"),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        if let Curve::Custom(points) = &mut processing.curve {
+                            ui.label(tr!("patch-levels"));
+                            for point in points.iter_mut() {
+                                ui.add(egui::DragValue::new(point));
+                            }
+                            if points.len() > 2
+                                && accessibility::named(
+                                    ui.small_button("−"),
+                                    tr!("patch-remove-point"),
+                                )
+                                .clicked()
+                            {
+                                points.pop();
+                            }
+                            if points.len() < 17
+                                && accessibility::named(
+                                    ui.small_button("+"),
+                                    tr!("patch-add-point"),
+                                )
+                                .clicked()
+                            {
+                                points.push(255);
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    match index {
+                        Some(i) if processing.is_passthrough() => {
+                            fixture.processing.remove(i);
+                        }
+                        Some(i) => fixture.processing[i] = processing,
+                        None if !processing.is_passthrough() => fixture.processing.push(processing),
+                        None => {}
+                    }
+                }
+            });
+        ui.label(tr!("patch-custom-curve-hint"));
+
+        if fixture.channel_for(Attribute::Pan).is_none()
+            || fixture.channel_for(Attribute::Tilt).is_none()
+        {
+            return;
+        }
+        ui.add_space(10.0);
+        ui.label(tr!("patch-position"));
+        let position = &mut fixture.position;
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut position.swap, tr!("patch-swap"));
+            ui.checkbox(&mut position.invert_pan, tr!("patch-invert-pan"));
+            ui.checkbox(&mut position.invert_tilt, tr!("patch-invert-tilt"));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("patch-home-offset"));
+            ui.add(egui::DragValue::new(&mut position.pan_offset).range(-255..=255));
+            ui.label(tr!("patch-tilt"));
+            ui.add(egui::DragValue::new(&mut position.tilt_offset).range(-255..=255));
+            if !position.is_default() && ui.small_button(tr!("transport-reset")).clicked() {
+                *position = Default::default();
+            }
+        });
+        ui.label(tr!("patch-position-hint"));
+    }
+
+    /// Tools for when units get swapped on site: copying one fixture's
+    /// programming onto another, and changing a fixture type show-wide.
+    fn draw_patch_tools(&mut self, ui: &mut egui::Ui) {
+        let tools = &mut self.patch_tools;
+        ui.label(tr!("patch-copy-values"));
+        ui.horizontal(|ui| {
+            ui.label(tr!("patch-from"));
+            fixture_combo(ui, "copy_from", &self.show.fixtures, &mut tools.copy_from);
+            ui.label(tr!("patch-to-label"));
+            fixture_combo(ui, "copy_to", &self.show.fixtures, &mut tools.copy_to);
+            let ready = tools.copy_from.is_some()
+                && tools.copy_to.is_some()
+                && tools.copy_from != tools.copy_to;
+            if ui
+                .add_enabled(ready, egui::Button::new(tr!("patch-copy")))
+                .on_hover_text(tr!("patch-copy-hint"))
+                .clicked()
+            {
+                if let (Some(from), Some(to)) = (tools.copy_from, tools.copy_to) {
+                    let changed = self.show.copy_fixture_values(from, to);
+                    self.log.info(tr!("patch-copied", count = changed));
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+        ui.label(tr!("patch-replace-type"));
+        ui.horizontal(|ui| {
+            let mut types: Vec<&str> = Vec::new();
+            for fixture in &self.show.fixtures {
+                if !types.contains(&fixture.profile.name.as_str()) {
+                    types.push(&fixture.profile.name);
+                }
+            }
+            egui::ComboBox::from_id_salt("replace_type")
+                .selected_text(tools.replace_type.clone().unwrap_or_else(|| tr!("select")))
+                .show_ui(ui, |ui| {
+                    for name in types {
+                        ui.selectable_value(&mut tools.replace_type, Some(name.to_string()), name);
+                    }
+                });
+            ui.label(tr!("patch-with"));
+            let with = self
+                .profile_library
+                .get(tools.replace_with)
+                .map(|profile| profile.name.clone())
+                .unwrap_or_default();
+            egui::ComboBox::from_id_salt("replace_with")
+                .selected_text(with)
+                .show_ui(ui, |ui| {
+                    for (i, profile) in self.profile_library.iter().enumerate() {
+                        ui.selectable_value(&mut tools.replace_with, i, &profile.name);
+                    }
+                });
+            if ui
+                .add_enabled(
+                    tools.replace_type.is_some(),
+                    egui::Button::new(tr!("conflict-replace")),
+                )
+                .clicked()
+            {
+                if let (Some(old), Some(profile)) = (
+                    tools.replace_type.take(),
+                    self.profile_library.get(tools.replace_with),
+                ) {
+                    let old_footprint = self
+                        .show
+                        .fixtures
+                        .iter()
+                        .find(|fixture| fixture.profile.name == old)
+                        .map_or(0, |fixture| fixture.profile.channels.len());
+                    let (swapped, dropped) = self.show.replace_profile(&old, profile);
+                    self.log.info(tr!(
+                        "patch-replaced",
+                        count = swapped,
+                        old = old.as_str(),
+                        new = profile.name.as_str(),
+                        dropped = dropped
+                    ));
+                    if profile.channels.len() > old_footprint {
+                        self.log.warn(tr!(
+                            "patch-replaced-footprint",
+                            new = profile.name.as_str(),
+                            old = old.as_str()
+                        ));
+                    }
+                }
+            }
+        });
+    }
+
+    /// Discovery and remote addressing of RDM responders behind an Art-Net
+    /// gateway.
+    fn draw_rdm(&mut self, ui: &mut egui::Ui) {
+        ui.label("RDM");
+        let mut enable = None;
+        ui.horizontal(|ui| {
+            ui.label(tr!("patch-gateway"));
+            ui.add_enabled(
+                self.rdm.is_none(),
+                egui::TextEdit::singleline(&mut self.preferences.rdm_gateway).desired_width(140.0),
+            );
+            let label = if self.rdm.is_some() {
+                tr!("disconnect")
+            } else {
+                tr!("connect")
+            };
+            if ui.button(label).clicked() {
+                enable = Some(self.rdm.is_none());
+            }
+            let Some(rdm) = &self.rdm else {
+                return;
+            };
+            ui.label(tr!("universe-label"));
+            ui.add(egui::DragValue::new(&mut self.patch_tools.rdm_universe).range(1..=32767));
+            if ui
+                .add_enabled(
+                    rdm.busy().is_none(),
+                    egui::Button::new(tr!("patch-discover")),
+                )
+                .clicked()
+            {
+                self.patch_tools.rdm_addresses.clear();
+                rdm.discover(self.patch_tools.rdm_universe);
+            }
+        });
+        if let Some(enabled) = enable {
+            if enabled {
+                let _ = self.preferences.save();
+            }
+            self.set_rdm_enabled(ui.ctx(), enabled);
+        }
+
+        if let Some(error) = &self.rdm_error {
+            ui.colored_label(egui::Color32::RED, error);
+            ui.label(tr!("patch-rdm-port"));
+        }
+        let Some(rdm) = &self.rdm else {
+            return;
+        };
+        if let Some(busy) = rdm.busy() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(busy);
+            });
+        } else if let Some(error) = rdm.error() {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+
+        let devices = rdm.devices();
+        if devices.is_empty() {
+            return;
+        }
+        let edits = &mut self.patch_tools.rdm_addresses;
+        egui::Grid::new("rdm_devices")
+            .striped(true)
+            .num_columns(6)
+            .show(ui, |ui| {
+                ui.strong("UID");
+                ui.strong(tr!("patch-device"));
+                ui.strong(tr!("patch-channels"));
+                ui.strong(tr!("patch-address"));
+                ui.strong(tr!("patch-patched-as"));
+                ui.end_row();
+
+                for device in &devices {
+                    ui.monospace(device.uid.to_string());
+                    let mut name = format!("{} {}", device.manufacturer, device.model);
+                    if !device.label.is_empty() {
+                        name = format!("{} ({})", name.trim(), device.label);
+                    }
+                    ui.label(name.trim());
+                    ui.label(device.footprint.to_string());
+
+                    let Some(current) = device.address else {
+                        ui.label(tr!("none"));
+                        ui.label("");
+                        ui.end_row();
+                        continue;
+                    };
+                    ui.horizontal(|ui| {
+                        let index = match edits.iter().position(|(uid, _)| *uid == device.uid) {
+                            Some(index) => index,
+                            None => {
+                                edits.push((device.uid, current));
+                                edits.len() - 1
+                            }
+                        };
+                        let address = &mut edits[index].1;
+                        ui.add(egui::DragValue::new(address).range(1..=512));
+                        if ui
+                            .add_enabled(
+                                *address != current,
+                                egui::Button::new(tr!("patch-set")).small(),
+                            )
+                            .on_hover_text(tr!("patch-set-hint"))
+                            .clicked()
+                        {
+                            rdm.set_address(device.uid, *address);
+                        }
+                        let mut identify = device.identifying;
+                        if ui
+                            .toggle_value(&mut identify, tr!("patch-identify"))
+                            .changed()
+                        {
+                            rdm.identify(device.uid, identify);
+                        }
+                    });
+
+                    let patched = self.show.fixtures.iter().find(|fixture| {
+                        fixture.universe == device.universe && fixture.address == current
+                    });
+                    match patched {
+                        Some(fixture) => ui.label(&fixture.name),
+                        None => ui.weak(tr!("patch-not-patched")),
+                    };
+                    ui.end_row();
+                }
+            });
+    }
+}
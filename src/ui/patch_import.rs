@@ -0,0 +1,181 @@
+use crate::fixture::Fixture;
+use crate::i18n::tr;
+use crate::patch_import::{self, ImportedFixture};
+use crate::HaloApp;
+use eframe::egui;
+use std::path::Path;
+
+/// The file being imported and how its fixture types map to Halo profiles.
+#[derive(Default)]
+pub(crate) struct PatchImport {
+    path: String,
+    fixtures: Vec<ImportedFixture>,
+    /// Each imported type, how many fixtures use it and the profile picked
+    /// for it; `None` leaves those fixtures out.
+    types: Vec<(String, usize, Option<usize>)>,
+    /// Replace the current patch rather than adding to it.
+    replace: bool,
+    error: Option<String>,
+}
+
+impl HaloApp {
+    pub(crate) fn draw_patch_import_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_patch_import;
+        let mut import = false;
+        let state = &mut self.patch_import;
+        egui::Window::new(tr!("import-patch-title"))
+            .id(egui::Id::new("patch_import"))
+            .open(&mut open)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.label(tr!("import-patch-hint"));
+                ui.horizontal(|ui| {
+                    ui.label(tr!("file-label"));
+                    ui.text_edit_singleline(&mut state.path);
+                    if ui.button(tr!("daw-read")).clicked() {
+                        state.types.clear();
+                        state.error = None;
+                        match patch_import::load(Path::new(state.path.trim())) {
+                            Ok(fixtures) => {
+                                for fixture in &fixtures {
+                                    match state
+                                        .types
+                                        .iter_mut()
+                                        .find(|(name, _, _)| *name == fixture.fixture_type)
+                                    {
+                                        Some((_, count, _)) => *count += 1,
+                                        None => state.types.push((
+                                            fixture.fixture_type.clone(),
+                                            1,
+                                            patch_import::guess_profile(
+                                                &fixture.fixture_type,
+                                                &self.profile_library,
+                                            ),
+                                        )),
+                                    }
+                                }
+                                state.fixtures = fixtures;
+                            }
+                            Err(err) => {
+                                state.fixtures.clear();
+                                state.error =
+                                    Some(tr!("import-patch-read-failed", error = err.to_string()));
+                            }
+                        }
+                    }
+                });
+                if let Some(error) = &state.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                if state.fixtures.is_empty() {
+                    return;
+                }
+
+                ui.separator();
+                ui.label(tr!(
+                    "import-patch-summary",
+                    fixtures = state.fixtures.len(),
+                    types = state.types.len()
+                ));
+                egui::Grid::new("import_types")
+                    .striped(true)
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("type"));
+                        ui.strong(tr!("fixtures"));
+                        ui.strong(tr!("import-patch-profile"));
+                        ui.end_row();
+                        for (i, (name, count, profile)) in state.types.iter_mut().enumerate() {
+                            if name.is_empty() {
+                                ui.label(tr!("import-patch-no-type"));
+                            } else {
+                                ui.label(name.as_str());
+                            }
+                            ui.label(count.to_string());
+                            let selected = profile
+                                .and_then(|p| self.profile_library.get(p))
+                                .map_or_else(|| tr!("import-patch-skip"), |p| p.name.clone());
+                            egui::ComboBox::from_id_salt(("import_profile", i))
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(profile, None, tr!("import-patch-skip"));
+                                    for (p, library) in self.profile_library.iter().enumerate() {
+                                        ui.selectable_value(profile, Some(p), &library.name);
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+                ui.checkbox(&mut state.replace, tr!("import-patch-replace"));
+                if state.replace {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 180, 0),
+                        tr!("import-patch-replace-warning"),
+                    );
+                }
+                import = ui.button(tr!("import")).clicked();
+            });
+        self.show_patch_import = open;
+        if import {
+            self.import_patch();
+        }
+    }
+
+    fn import_patch(&mut self) {
+        let state = &mut self.patch_import;
+        if state.replace {
+            self.show.fixtures.clear();
+        }
+        let mut next_id = self
+            .show
+            .fixtures
+            .iter()
+            .map(|fixture| fixture.id + 1)
+            .max()
+            .unwrap_or(0);
+        let (mut added, mut skipped) = (0, 0);
+        for imported in &state.fixtures {
+            let profile = state
+                .types
+                .iter()
+                .find(|(name, _, _)| *name == imported.fixture_type)
+                .and_then(|(_, _, profile)| *profile)
+                .and_then(|p| self.profile_library.get(p));
+            let Some(profile) = profile else {
+                skipped += 1;
+                continue;
+            };
+            self.show.fixtures.push(Fixture::new(
+                next_id,
+                &imported.name,
+                profile.clone(),
+                imported.universe,
+                imported.address,
+            ));
+            next_id += 1;
+            added += 1;
+        }
+        self.log.info(tr!(
+            "import-patch-done",
+            added = added,
+            path = state.path.trim(),
+            skipped = skipped
+        ));
+        let overlapping = self.show.fixtures.iter().filter(|fixture| {
+            let end = fixture.address + fixture.profile.channels.len() as u16;
+            self.show.fixtures.iter().any(|other| {
+                other.id != fixture.id
+                    && other.universe == fixture.universe
+                    && other.address < end
+                    && fixture.address < other.address + other.profile.channels.len() as u16
+            })
+        });
+        let overlapping = overlapping.count();
+        if overlapping > 0 {
+            self.log
+                .warn(tr!("import-patch-overlap", count = overlapping));
+        }
+        self.patch_import = PatchImport::default();
+        self.show_patch_import = false;
+    }
+}
@@ -0,0 +1,174 @@
+use crate::i18n::tr;
+use crate::macros::MacroAction;
+use crate::marker;
+use crate::ui::cues::MARKER_COLOR;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    /// The locked show view: large timecode and cue readouts, transport and
+    /// pads only.
+    pub(crate) fn draw_performance_view(&mut self, ui: &mut egui::Ui) {
+        let led = self.preferences.led_color.lit();
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.label(
+                egui::RichText::new(self.format_readout())
+                    .font(egui::FontId::new(
+                        200.0,
+                        egui::FontFamily::Name("matrix".into()),
+                    ))
+                    .color(led),
+            );
+            self.draw_dual_timecode(ui, 28.0);
+            self.draw_clock_strip(ui, 28.0);
+
+            if let Some(label) = self.setlist_label() {
+                ui.label(egui::RichText::new(label).size(32.0));
+            }
+
+            let current = self
+                .current_cue()
+                .map(|cue| format!("{}  {}{}", cue.number, cue.tag.prefix(), cue.name))
+                .unwrap_or_else(|| "—".to_string());
+            ui.label(egui::RichText::new(current).size(48.0).strong());
+
+            let next = self
+                .next_cue()
+                .map(|cue| {
+                    tr!(
+                        "performance-next",
+                        number = cue.number.to_string(),
+                        name = format!("{}{}", cue.tag.prefix(), cue.name),
+                        time = Self::format_duration(
+                            self.show.cue_start(cue).saturating_sub(self.elapsed)
+                        )
+                    )
+                })
+                .unwrap_or_else(|| tr!("performance-last-cue"));
+            ui.label(egui::RichText::new(next).size(32.0).weak());
+
+            if let Some(marker) = marker::next(&self.show, self.elapsed) {
+                ui.label(
+                    egui::RichText::new(tr!(
+                        "performance-marker",
+                        text = marker.text.as_str(),
+                        time = Self::format_duration(marker.time.saturating_sub(self.elapsed))
+                    ))
+                    .size(32.0)
+                    .color(MARKER_COLOR),
+                );
+            }
+
+            ui.add_space(20.0);
+            let label = if self.rolling() {
+                tr!("transport-stop")
+            } else {
+                tr!("transport-start")
+            };
+            if ui
+                .add(
+                    egui::Button::new(egui::RichText::new(label).size(32.0))
+                        .min_size(egui::vec2(200.0, 70.0)),
+                )
+                .clicked()
+            {
+                self.perform(if self.rolling() {
+                    MacroAction::Stop
+                } else {
+                    MacroAction::Start
+                });
+            }
+        });
+
+        ui.add_space(30.0);
+        self.draw_pads(ui, false);
+    }
+
+    pub(crate) fn set_performance_mode(&mut self, ctx: &egui::Context, enabled: bool) {
+        self.performance_mode = enabled;
+        if enabled {
+            self.channel_check = None;
+            self.test_pattern = None;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(enabled));
+        self.log.info(if enabled {
+            tr!("performance-entered")
+        } else {
+            tr!("performance-left")
+        });
+    }
+
+    /// Asks for confirmation before entering or leaving performance mode.
+    pub(crate) fn draw_performance_prompt(&mut self, ctx: &egui::Context) {
+        if !self.performance_prompt {
+            return;
+        }
+        let entering = !self.performance_mode;
+        let mut confirmed = false;
+        let modal = egui::Modal::new(egui::Id::new("performance_prompt")).show(ctx, |ui| {
+            ui.set_width(320.0);
+            if entering {
+                ui.heading(tr!("performance-enter-title"));
+                ui.label(tr!("performance-enter-body"));
+            } else {
+                ui.heading(tr!("performance-leave-title"));
+                ui.label(tr!("performance-leave-body"));
+                if self.show.lock.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("performance-passcode"));
+                        ui.add(egui::TextEdit::singleline(&mut self.passcode_entry).password(true))
+                            .request_focus();
+                    });
+                    if self.passcode_wrong {
+                        ui.label(
+                            egui::RichText::new(tr!("performance-wrong-passcode"))
+                                .color(egui::Color32::RED),
+                        );
+                    }
+                }
+            }
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .button(if entering {
+                        tr!("performance-enter")
+                    } else {
+                        tr!("performance-leave")
+                    })
+                    .clicked()
+                    || ui.input(|input| input.key_pressed(egui::Key::Enter))
+                {
+                    confirmed = true;
+                }
+                if ui.button(tr!("cancel")).clicked() {
+                    self.performance_prompt = false;
+                }
+            });
+        });
+        if modal.should_close() {
+            self.performance_prompt = false;
+        }
+        if !self.performance_prompt {
+            self.passcode_entry.clear();
+            self.passcode_wrong = false;
+        }
+        let locked = self
+            .show
+            .lock
+            .as_ref()
+            .is_some_and(|lock| !lock.unlocks(&self.passcode_entry));
+        if confirmed && !entering && locked {
+            self.passcode_wrong = true;
+            self.passcode_entry.clear();
+            self.log.warn(tr!("performance-wrong-passcode-log"));
+            return;
+        }
+        if confirmed {
+            self.passcode_entry.clear();
+            self.passcode_wrong = false;
+            self.performance_prompt = false;
+            self.set_performance_mode(ctx, entering);
+        }
+    }
+}
@@ -0,0 +1,422 @@
+use crate::audio::{AudioBand, AudioMeter, SPECTRUM_BANDS};
+use crate::i18n::tr;
+use crate::pixel::{
+    AudioModulation, ColorOrder, Generator, ModulationTarget, PixelClock, PixelMap,
+    PIXELS_PER_UNIVERSE,
+};
+use crate::pixel_stream::PixelOutput;
+use crate::plugin::PluginHost;
+use crate::ui::speed_masters::speed_master_combo;
+use crate::HaloApp;
+use eframe::egui;
+use std::path::PathBuf;
+
+impl HaloApp {
+    pub(crate) fn draw_pixel_map_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_pixel_maps;
+        let clock = self.pixel_clock();
+        egui::Window::new(tr!("pixel-maps-title"))
+            .id(egui::Id::new("pixel_maps"))
+            .open(&mut open)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let selected = self
+                        .selected_pixel_map
+                        .and_then(|i| self.show.pixel_maps.get(i))
+                        .map(|map| map.name.clone())
+                        .unwrap_or_else(|| tr!("select"));
+                    egui::ComboBox::from_id_salt("pixel_map_select")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for (i, map) in self.show.pixel_maps.iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut self.selected_pixel_map,
+                                    Some(i),
+                                    &map.name,
+                                );
+                            }
+                        });
+                    if ui.button(tr!("add")).clicked() {
+                        let mut map = PixelMap::default();
+                        map.name = tr!("pixel-map-name", number = self.show.pixel_maps.len() + 1);
+                        self.show.pixel_maps.push(map);
+                        self.selected_pixel_map = Some(self.show.pixel_maps.len() - 1);
+                    }
+                    if let Some(index) = self.selected_pixel_map {
+                        if ui.button(tr!("delete")).clicked() {
+                            self.show.pixel_maps.remove(index);
+                            self.selected_pixel_map = None;
+                        }
+                    }
+                    if ui.button(tr!("pixel-presets")).clicked() {
+                        self.show_effect_presets = true;
+                    }
+                });
+
+                let Some(map) = self
+                    .selected_pixel_map
+                    .and_then(|i| self.show.pixel_maps.get_mut(i))
+                else {
+                    return;
+                };
+
+                ui.separator();
+                egui::Grid::new("pixel_map_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("name-label"));
+                        ui.text_edit_singleline(&mut map.name);
+                        ui.end_row();
+
+                        ui.label(tr!("output-label"));
+                        ui.checkbox(&mut map.enabled, tr!("enabled"));
+                        ui.end_row();
+
+                        ui.label(tr!("pixel-size"));
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut map.width).range(1..=512));
+                            ui.label("×");
+                            ui.add(egui::DragValue::new(&mut map.height).range(1..=512));
+                            ui.checkbox(&mut map.serpentine, tr!("pixel-serpentine"));
+                        });
+                        ui.end_row();
+
+                        ui.label(tr!("pixel-send-as"));
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_salt("pixel_output")
+                                .selected_text(map.output.to_string())
+                                .show_ui(ui, |ui| {
+                                    for output in PixelOutput::ALL {
+                                        ui.selectable_value(
+                                            &mut map.output,
+                                            output,
+                                            output.to_string(),
+                                        );
+                                    }
+                                });
+                            if map.output != PixelOutput::Dmx {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut map.host)
+                                        .hint_text(tr!("pixel-controller-address"))
+                                        .desired_width(140.0),
+                                );
+                            }
+                        });
+                        ui.end_row();
+                        if map.output != PixelOutput::Dmx {
+                            if let Some(error) = &self.pixel_stream_error {
+                                ui.label("");
+                                ui.colored_label(egui::Color32::RED, error);
+                                ui.end_row();
+                            }
+                        }
+
+                        ui.label(tr!("pixel-patch"));
+                        ui.horizontal(|ui| {
+                            if map.output == PixelOutput::Dmx {
+                                ui.label(tr!("universe"));
+                                ui.add(egui::DragValue::new(&mut map.universe).range(1..=32767));
+                                ui.label(tr!("patch-address"));
+                                ui.add(egui::DragValue::new(&mut map.address).range(1..=510));
+                            }
+                            egui::ComboBox::from_id_salt("pixel_color_order")
+                                .selected_text(color_order_label(map.color_order))
+                                .show_ui(ui, |ui| {
+                                    for order in [ColorOrder::Rgb, ColorOrder::Grb, ColorOrder::Bgr]
+                                    {
+                                        ui.selectable_value(
+                                            &mut map.color_order,
+                                            order,
+                                            color_order_label(order),
+                                        );
+                                    }
+                                });
+                        });
+                        ui.end_row();
+
+                        ui.label(tr!("pixel-content"));
+                        egui::ComboBox::from_id_salt("pixel_generator")
+                            .selected_text(map.generator.to_string())
+                            .show_ui(ui, |ui| {
+                                for generator in Generator::ALL {
+                                    ui.selectable_value(
+                                        &mut map.generator,
+                                        generator,
+                                        generator.to_string(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        if map.generator == Generator::Plugin {
+                            ui.label(tr!("pixel-plugin"));
+                            let generators = self.plugin_host.generators();
+                            if generators.is_empty() {
+                                ui.weak(tr!("pixel-no-plugins"));
+                            } else {
+                                egui::ComboBox::from_id_salt("pixel_plugin")
+                                    .selected_text(map.plugin.as_str())
+                                    .show_ui(ui, |ui| {
+                                        for name in generators {
+                                            ui.selectable_value(
+                                                &mut map.plugin,
+                                                name.clone(),
+                                                name,
+                                            );
+                                        }
+                                    });
+                            }
+                            ui.end_row();
+                            if let Some(error) = self.plugin_host.error(&map.plugin) {
+                                ui.label("");
+                                ui.colored_label(egui::Color32::RED, error);
+                                ui.end_row();
+                            }
+                        }
+
+                        ui.label(tr!("pixel-colours"));
+                        ui.horizontal(|ui| {
+                            ui.color_edit_button_srgb(&mut map.colors[0]);
+                            ui.color_edit_button_srgb(&mut map.colors[1]);
+                        });
+                        ui.end_row();
+
+                        ui.label(tr!("speed-label"));
+                        ui.add(egui::Slider::new(&mut map.speed, 0.0..=4.0).suffix(tr!("unit-hz")));
+                        ui.end_row();
+
+                        ui.label(tr!("speed-master-label"));
+                        speed_master_combo(
+                            ui,
+                            "pixel_speed_master",
+                            &self.show.speed_masters,
+                            &mut map.speed_master,
+                        );
+                        ui.end_row();
+
+                        ui.label(tr!("pixel-brightness"));
+                        ui.add(egui::Slider::new(&mut map.brightness, 0.0..=1.0));
+                        ui.end_row();
+
+                        ui.label(tr!("pixel-audio"));
+                        ui.horizontal(|ui| {
+                            let mut reactive = map.modulation.is_some();
+                            if ui.checkbox(&mut reactive, tr!("pixel-react-to")).changed() {
+                                map.modulation = reactive.then(AudioModulation::default);
+                            }
+                            let Some(modulation) = &mut map.modulation else {
+                                return;
+                            };
+                            egui::ComboBox::from_id_salt("pixel_audio_band")
+                                .selected_text(modulation.band.to_string())
+                                .width(70.0)
+                                .show_ui(ui, |ui| {
+                                    for band in AudioBand::ALL {
+                                        ui.selectable_value(
+                                            &mut modulation.band,
+                                            band,
+                                            band.to_string(),
+                                        );
+                                    }
+                                });
+                            ui.label(tr!("pixel-react-on"));
+                            egui::ComboBox::from_id_salt("pixel_audio_target")
+                                .selected_text(modulation.target.to_string())
+                                .width(90.0)
+                                .show_ui(ui, |ui| {
+                                    for target in ModulationTarget::ALL {
+                                        ui.selectable_value(
+                                            &mut modulation.target,
+                                            target,
+                                            target.to_string(),
+                                        );
+                                    }
+                                });
+                        });
+                        ui.end_row();
+
+                        if let Some(modulation) = &mut map.modulation {
+                            ui.label(tr!("pixel-depth"));
+                            ui.add(egui::Slider::new(&mut modulation.depth, 0.0..=1.0));
+                            ui.end_row();
+                        }
+
+                        if map.generator != Generator::Media {
+                            return;
+                        }
+
+                        ui.label(tr!("pixel-media-file"));
+                        let mut path = map
+                            .media
+                            .as_ref()
+                            .map(|path| path.display().to_string())
+                            .unwrap_or_default();
+                        if ui.text_edit_singleline(&mut path).changed() {
+                            map.media =
+                                (!path.trim().is_empty()).then(|| PathBuf::from(path.trim()));
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("pixel-playback"));
+                        ui.horizontal(|ui| {
+                            if ui.button(tr!("load")).clicked() {
+                                self.pixel_media_error = map.load_media(clock).err().map(|err| {
+                                    let message = tr!("pixel-load-failed", error = err.to_string());
+                                    self.log.error(tr!(
+                                        "pixel-map-error",
+                                        name = map.name.as_str(),
+                                        error = message.as_str()
+                                    ));
+                                    message
+                                });
+                            }
+                            if ui
+                                .add_enabled(map.clip.is_some(), egui::Button::new(tr!("restart")))
+                                .clicked()
+                            {
+                                map.restart_media(clock);
+                            }
+                            ui.checkbox(&mut map.looping, tr!("loop"));
+                        });
+                        ui.end_row();
+
+                        ui.label(tr!("pixel-rate"));
+                        ui.add(egui::Slider::new(&mut map.playback_speed, 0.1..=4.0).suffix("×"));
+                        ui.end_row();
+
+                        ui.label("");
+                        if let Some(error) = &self.pixel_media_error {
+                            ui.colored_label(egui::Color32::RED, error);
+                        } else if map.media_needs_reload() {
+                            ui.label(tr!("pixel-resized"));
+                        } else if let Some(clip) = &map.clip {
+                            ui.label(tr!(
+                                "pixel-clip",
+                                frames = clip.frames.len(),
+                                seconds = format!("{:.1}", clip.length.as_secs_f32())
+                            ));
+                        } else {
+                            ui.label(tr!("pixel-media-hint"));
+                        }
+                        ui.end_row();
+                    });
+
+                if map.output == PixelOutput::Dmx {
+                    let (last_universe, last_channel) =
+                        map.pixel_address(map.pixel_count().saturating_sub(1));
+                    ui.label(tr!(
+                        "pixel-dmx-summary",
+                        count = map.pixel_count(),
+                        first = format!("{}.{:03}", map.universe, map.address),
+                        last = format!("{}.{:03}", last_universe, last_channel + 2),
+                        per_universe = PIXELS_PER_UNIVERSE
+                    ));
+                } else {
+                    ui.label(tr!(
+                        "pixel-stream-summary",
+                        count = map.pixel_count(),
+                        output = map.output.to_string(),
+                        host = map.host.as_str()
+                    ));
+                }
+
+                if let Some(modulation) = map.modulation {
+                    ui.separator();
+                    draw_spectrum(ui, &self.audio_meter, modulation.band);
+                    if self.audio_input.is_none() {
+                        ui.label(tr!("pixel-choose-audio"));
+                    }
+                }
+
+                ui.separator();
+                draw_preview(ui, map, clock, &self.plugin_host);
+            });
+        self.show_pixel_maps = open;
+    }
+}
+
+fn color_order_label(order: ColorOrder) -> &'static str {
+    match order {
+        ColorOrder::Rgb => "RGB",
+        ColorOrder::Grb => "GRB",
+        ColorOrder::Bgr => "BGR",
+    }
+}
+
+fn draw_preview(ui: &mut egui::Ui, map: &PixelMap, clock: PixelClock, plugins: &PluginHost) {
+    let width = map.width.max(1) as f32;
+    let height = map.height.max(1) as f32;
+    let cell = (ui.available_width() / width).clamp(2.0, 24.0);
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(cell * width, cell * height),
+        egui::Sense::hover(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, egui::Color32::BLACK);
+
+    for (i, [r, g, b]) in map.pixels(clock, plugins).into_iter().enumerate() {
+        let x = (i % map.width as usize) as f32;
+        let y = (i / map.width as usize) as f32;
+        let min = rect.min + egui::vec2(x * cell, y * cell);
+        painter.rect_filled(
+            egui::Rect::from_min_size(min, egui::vec2(cell, cell)).shrink(cell * 0.1),
+            cell * 0.2,
+            egui::Color32::from_rgb(r, g, b),
+        );
+    }
+}
+
+/// A live spectrum of the audio input, with a meter for each band on the
+/// right and the band driving the map highlighted.
+fn draw_spectrum(ui: &mut egui::Ui, meter: &AudioMeter, selected: AudioBand) {
+    let height = 60.0;
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), height),
+        egui::Sense::hover(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, egui::Color32::BLACK);
+
+    let bands = AudioBand::ALL.len();
+    let meter_width = 14.0;
+    let spectrum_width = rect.width() - (meter_width + 2.0) * bands as f32 - 6.0;
+    let bar_width = spectrum_width / SPECTRUM_BANDS as f32;
+    for (i, level) in meter.spectrum().into_iter().enumerate() {
+        let x = rect.left() + i as f32 * bar_width;
+        painter.rect_filled(
+            egui::Rect::from_min_max(
+                egui::pos2(x + 1.0, rect.bottom() - level * height),
+                egui::pos2(x + bar_width - 1.0, rect.bottom()),
+            ),
+            0.0,
+            egui::Color32::from_rgb(60, 160, 220),
+        );
+    }
+
+    let levels = meter.levels();
+    for (i, band) in AudioBand::ALL.into_iter().enumerate() {
+        let x = rect.left() + spectrum_width + 6.0 + i as f32 * (meter_width + 2.0);
+        let level = levels.level(band);
+        let color = if band == selected {
+            egui::Color32::from_rgb(255, 180, 0)
+        } else {
+            egui::Color32::from_gray(120)
+        };
+        painter.rect_filled(
+            egui::Rect::from_min_max(
+                egui::pos2(x, rect.bottom() - level * (height - 12.0)),
+                egui::pos2(x + meter_width, rect.bottom()),
+            ),
+            0.0,
+            color,
+        );
+        painter.text(
+            egui::pos2(x + meter_width / 2.0, rect.top() + 1.0),
+            egui::Align2::CENTER_TOP,
+            &band.to_string()[..1],
+            egui::FontId::proportional(9.0),
+            color,
+        );
+    }
+}
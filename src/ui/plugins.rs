@@ -0,0 +1,110 @@
+use crate::i18n::tr;
+use crate::plugin::{self, PluginOutput};
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    pub(crate) fn draw_plugins_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_plugins;
+        let before = self.preferences.plugin_outputs.clone();
+        let mut reload = false;
+        egui::Window::new(tr!("plugins-title"))
+            .id(egui::Id::new("plugins"))
+            .open(&mut open)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    match plugin::plugins_dir() {
+                        Some(dir) => {
+                            ui.label(tr!("plugins-folder", path = dir.display().to_string()))
+                        }
+                        None => ui.label(tr!("plugins-no-folder")),
+                    };
+                    if ui.button(tr!("plugins-reload")).clicked() {
+                        reload = true;
+                    }
+                });
+                ui.separator();
+
+                let plugins = self.plugin_host.info();
+                if plugins.is_empty() && self.plugin_read_errors.is_empty() {
+                    ui.label(tr!("plugins-none"));
+                }
+                egui::Grid::new("plugins")
+                    .striped(true)
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for info in &plugins {
+                            ui.strong(&info.name)
+                                .on_hover_text(info.path.display().to_string());
+                            let kind = match (info.generator, info.output) {
+                                (true, true) => tr!("plugins-generator-output"),
+                                (true, false) => tr!("plugins-generator"),
+                                (false, true) => tr!("plugins-output"),
+                                (false, false) => tr!("plugins-nothing"),
+                            };
+                            ui.label(kind);
+                            // The engine's error is the one affecting the output.
+                            let error = self
+                                .plugin_errors
+                                .iter()
+                                .find(|(name, _)| *name == info.name)
+                                .map(|(_, error)| error.clone())
+                                .or_else(|| info.error.clone());
+                            match error {
+                                Some(error) => ui.colored_label(egui::Color32::RED, error),
+                                None => ui.weak(tr!("plugins-ok")),
+                            };
+                            ui.end_row();
+                        }
+                        for (path, error) in &self.plugin_read_errors {
+                            ui.strong(path.display().to_string());
+                            ui.label("");
+                            ui.colored_label(egui::Color32::RED, error);
+                            ui.end_row();
+                        }
+                    });
+
+                let outputs = self.plugin_host.outputs();
+                if outputs.is_empty() {
+                    return;
+                }
+                ui.separator();
+                ui.strong(tr!("plugins-outputs"));
+                egui::Grid::new("plugin_outputs")
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for name in outputs {
+                            let settings = &mut self.preferences.plugin_outputs;
+                            let index = match settings.iter().position(|o| o.plugin == name) {
+                                Some(index) => index,
+                                None => {
+                                    settings.push(PluginOutput {
+                                        plugin: name.clone(),
+                                        target: String::new(),
+                                        enabled: false,
+                                    });
+                                    settings.len() - 1
+                                }
+                            };
+                            let output = &mut settings[index];
+                            ui.checkbox(&mut output.enabled, &name);
+                            ui.label(tr!("plugins-send-to"));
+                            ui.add(
+                                egui::TextEdit::singleline(&mut output.target)
+                                    .hint_text("host:port"),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.show_plugins = open;
+
+        if self.preferences.plugin_outputs != before {
+            let _ = self.preferences.save();
+        }
+        if reload {
+            self.load_plugins();
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::i18n::tr;
+use crate::power::{self, Estimate, PowerRating};
+use crate::HaloApp;
+use eframe::egui;
+
+/// One circuit's draw against the breaker, as a bar where there is a limit.
+fn draw_circuit(ui: &mut egui::Ui, name: &str, draw: f32, limit: Option<f32>) {
+    ui.label(name);
+    match limit {
+        Some(limit) => {
+            let fill = if draw > limit {
+                egui::Color32::RED
+            } else {
+                ui.visuals().selection.bg_fill
+            };
+            ui.add(
+                egui::ProgressBar::new((draw / limit).min(1.0))
+                    .desired_width(200.0)
+                    .fill(fill)
+                    .text(tr!(
+                        "power-of",
+                        draw = power::format_watts(draw),
+                        limit = power::format_watts(limit)
+                    )),
+            );
+        }
+        None => {
+            ui.monospace(power::format_watts(draw));
+        }
+    }
+    ui.end_row();
+}
+
+fn draw_estimate(ui: &mut egui::Ui, estimate: &Estimate, limit: Option<f32>) {
+    egui::Grid::new("power_circuits")
+        .num_columns(2)
+        .show(ui, |ui| {
+            if estimate.circuits.is_empty() {
+                draw_circuit(ui, &tr!("power-whole-rig"), estimate.total, limit);
+                return;
+            }
+            for (name, draw) in &estimate.circuits {
+                draw_circuit(ui, name, *draw, limit);
+            }
+            ui.strong(tr!("power-total"));
+            ui.monospace(power::format_watts(estimate.total));
+            ui.end_row();
+        });
+    if estimate.unrated > 0 {
+        ui.weak(tr!("power-unrated", count = estimate.unrated));
+    }
+}
+
+impl HaloApp {
+    /// The estimated draw of the output now and of each cue, against the
+    /// circuits' breakers, with the wattages and circuits it comes from.
+    pub(crate) fn draw_power_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_power;
+        egui::Window::new(tr!("menu-power"))
+            .id(egui::Id::new("power"))
+            .open(&mut open)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let mut limited = self.show.circuit_limit.is_some();
+                    if ui.checkbox(&mut limited, tr!("power-breakers")).changed() {
+                        self.show.circuit_limit = limited.then_some(2300.0);
+                    }
+                    if let Some(limit) = &mut self.show.circuit_limit {
+                        ui.add(
+                            egui::DragValue::new(limit)
+                                .range(100.0..=100_000.0)
+                                .speed(10.0)
+                                .suffix(tr!("unit-watts")),
+                        )
+                        .on_hover_text(tr!("power-breakers-hint"));
+                    }
+                });
+
+                ui.separator();
+                ui.strong(tr!("power-now"));
+                let now = power::estimate(&self.show.fixtures, &self.output);
+                draw_estimate(ui, &now, self.show.circuit_limit);
+
+                ui.separator();
+                egui::CollapsingHeader::new(tr!("power-peaks")).show(ui, |ui| {
+                    self.draw_cue_peaks(ui);
+                });
+                egui::CollapsingHeader::new(tr!("power-wattage")).show(ui, |ui| {
+                    self.draw_fixture_power(ui);
+                });
+            });
+        self.show_power = open;
+    }
+
+    fn draw_cue_peaks(&mut self, ui: &mut egui::Ui) {
+        let limit = self.show.circuit_limit;
+        let peaks = power::cue_peaks(
+            &self.show.fixtures,
+            &self.show.cues,
+            &self.show.playback_order(),
+        );
+        ui.weak(tr!("power-peaks-hint"));
+        egui::ScrollArea::vertical()
+            .id_salt("power_cue_peaks")
+            .max_height(220.0)
+            .show(ui, |ui| {
+                egui::Grid::new("power_cue_peaks")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("session-cue"));
+                        ui.strong(tr!("name"));
+                        ui.strong(tr!("power-peak"));
+                        ui.end_row();
+                        for (cue, peak) in self.show.cues.iter().zip(&peaks) {
+                            ui.label(cue.number.to_string());
+                            ui.label(&cue.name);
+                            let text = power::format_watts(peak.worst_circuit());
+                            if peak.is_over(limit) {
+                                ui.colored_label(egui::Color32::RED, format!("⚠ {}", text))
+                                    .on_hover_text(tr!("power-over"));
+                            } else {
+                                ui.monospace(text);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Each fixture's circuit, and the wattage of its type. A wattage is
+    /// set for every fixture of the same type at once.
+    fn draw_fixture_power(&mut self, ui: &mut egui::Ui) {
+        let mut rated = None;
+        egui::ScrollArea::vertical()
+            .id_salt("power_fixtures")
+            .max_height(260.0)
+            .show(ui, |ui| {
+                egui::Grid::new("power_fixtures")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("fixture"));
+                        ui.strong(tr!("type"));
+                        ui.strong(tr!("masters-full"))
+                            .on_hover_text(tr!("power-full-hint"));
+                        ui.strong(tr!("power-idle"))
+                            .on_hover_text(tr!("power-idle-hint"));
+                        ui.strong(tr!("power-circuit"));
+                        ui.end_row();
+                        for fixture in &mut self.show.fixtures {
+                            ui.label(&fixture.name);
+                            ui.label(&fixture.profile.name);
+                            let mut rating = fixture.profile.power.unwrap_or_default();
+                            let full = ui.add(
+                                egui::DragValue::new(&mut rating.full)
+                                    .range(0.0..=10_000.0)
+                                    .suffix(tr!("unit-watts")),
+                            );
+                            let idle = ui.add(
+                                egui::DragValue::new(&mut rating.idle)
+                                    .range(0.0..=rating.full)
+                                    .suffix(tr!("unit-watts")),
+                            );
+                            if full.changed() || idle.changed() {
+                                let rating = (rating != PowerRating::default()).then_some(rating);
+                                rated = Some((fixture.profile.name.clone(), rating));
+                            }
+                            ui.add(
+                                egui::TextEdit::singleline(&mut fixture.circuit)
+                                    .desired_width(80.0)
+                                    .hint_text(tr!("power-no-circuit")),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+        if let Some((name, rating)) = rated {
+            for fixture in &mut self.show.fixtures {
+                if fixture.profile.name == name {
+                    fixture.profile.power = rating;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1002 @@
+use crate::clock_sync::{BackupState, ClockRole};
+use crate::dmx_input::MergeMode;
+use crate::i18n::{self, tr, Language};
+use crate::interfaces::{NetworkInterface, Protocol};
+use crate::log::LogLevel;
+use crate::panic::StopOutput;
+use crate::playback::AudioRoute;
+use crate::theme::{LedColor, Theme};
+use crate::ui::accessibility;
+use crate::HaloApp;
+use crate::{audio, click, hid, midi};
+use eframe::egui;
+use std::time::Duration;
+
+/// A part of the Preferences window that the status bar can jump to.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PreferencesSection {
+    RemoteControl,
+    Midi,
+    Audio,
+    NetworkInterfaces,
+    NetworkInput,
+    ClockSync,
+}
+
+/// A section heading, scrolled into view if it is the one being jumped to.
+/// The jump is done once the heading is on screen.
+fn section_heading(
+    ui: &mut egui::Ui,
+    title: &str,
+    section: PreferencesSection,
+    focus: &mut Option<PreferencesSection>,
+) {
+    let response = ui.heading(title);
+    if *focus == Some(section) {
+        if ui.clip_rect().contains_rect(response.rect) {
+            *focus = None;
+        } else {
+            response.scroll_to_me(Some(egui::Align::TOP));
+        }
+    }
+}
+
+impl HaloApp {
+    /// Opens Preferences at `section`.
+    pub(crate) fn open_preferences_at(&mut self, section: PreferencesSection) {
+        self.show_preferences = true;
+        self.preferences_focus = Some(section);
+    }
+
+    pub(crate) fn draw_preferences(&mut self, ctx: &egui::Context) {
+        let before = self.preferences.clone();
+        let mut apply_scale = false;
+        let mut open = self.show_preferences;
+        let mut focus = self.preferences_focus;
+        egui::Window::new(tr!("preferences-title"))
+            .id(egui::Id::new("preferences"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.heading(tr!("preferences-appearance"));
+                    egui::Grid::new("appearance").num_columns(2).show(ui, |ui| {
+                        ui.label(tr!("preferences-language"));
+                        egui::ComboBox::from_id_salt("language")
+                            .selected_text(self.preferences.language.to_string())
+                            .show_ui(ui, |ui| {
+                                for language in Language::ALL {
+                                    ui.selectable_value(
+                                        &mut self.preferences.language,
+                                        language,
+                                        language.to_string(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr!("preferences-theme"));
+                        egui::ComboBox::from_id_salt("theme")
+                            .selected_text(self.preferences.theme.to_string())
+                            .show_ui(ui, |ui| {
+                                for theme in Theme::ALL {
+                                    ui.selectable_value(
+                                        &mut self.preferences.theme,
+                                        theme,
+                                        theme.to_string(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr!("preferences-led-colour"));
+                        egui::ComboBox::from_id_salt("led_color")
+                            .selected_text(self.preferences.led_color.to_string())
+                            .show_ui(ui, |ui| {
+                                for color in LedColor::ALL {
+                                    ui.selectable_value(
+                                        &mut self.preferences.led_color,
+                                        color,
+                                        egui::RichText::new(color.to_string()).color(color.lit()),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(
+                            &mut self.preferences.accent_downbeat,
+                            tr!("preferences-accent-downbeat"),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr!("preferences-ui-scale"));
+                        // Rescaling mid-drag would move the slider out from under
+                        // the pointer, so apply it once the drag ends.
+                        let response = ui.add(
+                            egui::Slider::new(&mut self.preferences.ui_scale, 0.75..=2.0)
+                                .step_by(0.05)
+                                .suffix("×"),
+                        );
+                        apply_scale =
+                            response.drag_stopped() || (response.changed() && !response.dragged());
+                        ui.end_row();
+
+                        ui.label(tr!("preferences-touch"));
+                        ui.checkbox(
+                            &mut self.preferences.touch_mode,
+                            tr!("preferences-touch-mode"),
+                        );
+                        ui.end_row();
+                    });
+
+                    ui.add_space(8.0);
+                    section_heading(
+                        ui,
+                        &tr!("preferences-remote-control"),
+                        PreferencesSection::RemoteControl,
+                        &mut focus,
+                    );
+                    ui.checkbox(
+                        &mut self.preferences.remote_enabled,
+                        tr!("preferences-remote-tcp"),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("port-label"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.preferences.remote_port)
+                                .range(1024..=65535),
+                        );
+                    });
+                    server_status(
+                        ui,
+                        self.remote.is_some(),
+                        &self.remote_error,
+                        self.preferences.remote_port,
+                    );
+
+                    ui.add_space(8.0);
+                    ui.checkbox(&mut self.preferences.http_enabled, tr!("preferences-http"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("port-label"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.preferences.http_port)
+                                .range(1024..=65535),
+                        );
+                    });
+                    server_status(
+                        ui,
+                        self.http.is_some(),
+                        &self.http_error,
+                        self.preferences.http_port,
+                    );
+
+                    ui.add_space(8.0);
+                    ui.checkbox(
+                        &mut self.preferences.osc_input_enabled,
+                        tr!("preferences-osc-input"),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("port-label"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.preferences.osc_input_port)
+                                .range(1024..=65535),
+                        );
+                    });
+                    server_status(
+                        ui,
+                        self.osc_input.is_some(),
+                        &self.osc_error,
+                        self.preferences.osc_input_port,
+                    );
+
+                    ui.add_space(8.0);
+                    ui.checkbox(&mut self.preferences.citp_enabled, tr!("preferences-citp"));
+                    if let Some(error) = &self.citp_error {
+                        ui.colored_label(egui::Color32::RED, error);
+                    } else if let Some(citp) = &self.citp {
+                        let peers = citp.peers();
+                        if peers.is_empty() {
+                            ui.weak(tr!("preferences-citp-waiting"));
+                        } else {
+                            ui.label(tr!("preferences-citp-connected", peers = peers.join(", ")));
+                        }
+                    }
+
+                    ui.add_space(8.0);
+                    ui.checkbox(&mut self.preferences.psn_enabled, tr!("preferences-psn"));
+                    if let Some(error) = &self.psn_error {
+                        ui.colored_label(egui::Color32::RED, error);
+                    } else if self.psn.is_some() {
+                        ui.label(tr!(
+                            "preferences-psn-listening",
+                            count = self.trackers.live().len()
+                        ));
+                    }
+
+                    ui.add_space(8.0);
+                    self.draw_hid_preferences(ui);
+
+                    ui.add_space(8.0);
+                    ui.label(tr!("preferences-designer-views"));
+                    self.draw_followers(ui);
+
+                    ui.add_space(8.0);
+                    section_heading(
+                        ui,
+                        &tr!("preferences-midi"),
+                        PreferencesSection::Midi,
+                        &mut focus,
+                    );
+                    egui::Grid::new("midi").num_columns(2).show(ui, |ui| {
+                        ui.label(tr!("input-label"));
+                        port_combo(
+                            ui,
+                            "midi_input",
+                            &mut self.preferences.midi_input,
+                            midi::input_ports,
+                        );
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(
+                            &mut self.preferences.midi_clock_in,
+                            tr!("preferences-midi-clock-in"),
+                        );
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(
+                            &mut self.preferences.midi_timecode_in,
+                            tr!("preferences-midi-timecode-in"),
+                        );
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(
+                            &mut self.preferences.midi_mmc_in,
+                            tr!("preferences-midi-mmc-in"),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr!("preferences-mmc-device"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.preferences.midi_mmc_device)
+                                .range(0..=midi::ALL_CALL)
+                                .custom_formatter(|n, _| {
+                                    if n as u8 == midi::ALL_CALL {
+                                        tr!("all")
+                                    } else {
+                                        n.to_string()
+                                    }
+                                }),
+                        )
+                        .on_hover_text(tr!("preferences-mmc-device-hint"));
+                        ui.end_row();
+
+                        ui.label(tr!("preferences-song-select"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("preferences-song-channel"));
+                            ui.add(
+                                egui::DragValue::new(&mut self.preferences.midi_song_channel)
+                                    .range(0..=16)
+                                    .custom_formatter(|n, _| {
+                                        if n == 0.0 {
+                                            tr!("any")
+                                        } else {
+                                            n.to_string()
+                                        }
+                                    }),
+                            );
+                        });
+                        ui.end_row();
+
+                        ui.label(tr!("output-label"));
+                        port_combo(
+                            ui,
+                            "midi_output",
+                            &mut self.preferences.midi_output,
+                            midi::output_ports,
+                        );
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(
+                            &mut self.preferences.midi_send_clock,
+                            tr!("preferences-midi-send-clock"),
+                        );
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.checkbox(
+                            &mut self.preferences.midi_mmc_out,
+                            tr!("preferences-midi-mmc-out"),
+                        );
+                        ui.end_row();
+                    });
+                    ui.label(tr!("preferences-song-select-hint"));
+                    if let Some(error) = &self.midi_error {
+                        ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                    }
+
+                    ui.add_space(8.0);
+                    section_heading(
+                        ui,
+                        &tr!("preferences-audio"),
+                        PreferencesSection::Audio,
+                        &mut focus,
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("input-label"));
+                        port_combo(
+                            ui,
+                            "audio_input",
+                            &mut self.preferences.audio_input,
+                            audio::input_devices,
+                        );
+                    });
+                    let error = self
+                        .audio_error
+                        .clone()
+                        .or_else(|| self.audio_meter.error());
+                    if let Some(error) = error {
+                        ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("preferences-click-output"));
+                        port_combo(
+                            ui,
+                            "click_output",
+                            &mut self.preferences.click_output,
+                            click::output_devices,
+                        );
+                        ui.add_enabled(
+                            self.preferences.click_output.is_some(),
+                            egui::Slider::new(&mut self.preferences.click_volume, 0.0..=1.0)
+                                .show_value(false)
+                                .text(tr!("level")),
+                        );
+                    });
+                    ui.label(tr!("preferences-click-hint"));
+                    let error = self
+                        .click_error
+                        .clone()
+                        .or_else(|| self.click_clock.error());
+                    if let Some(error) = error {
+                        ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                    }
+
+                    ui.label(tr!("preferences-audio-outputs"));
+                    draw_audio_routes(ui, &mut self.preferences.audio_routes);
+                    ui.label(tr!("preferences-audio-outputs-hint"));
+                    let error = self
+                        .playback_error
+                        .clone()
+                        .or_else(|| self.playback_clock.error());
+                    if let Some(error) = error {
+                        ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                    }
+
+                    ui.add_space(8.0);
+                    section_heading(
+                        ui,
+                        &tr!("preferences-network-interfaces"),
+                        PreferencesSection::NetworkInterfaces,
+                        &mut focus,
+                    );
+                    egui::Grid::new("interfaces").num_columns(2).show(ui, |ui| {
+                        for protocol in Protocol::ALL {
+                            ui.label(format!("{}:", protocol));
+                            interface_combo(
+                                ui,
+                                protocol,
+                                self.preferences.interfaces.get_mut(protocol),
+                                &self.interfaces,
+                            );
+                            ui.end_row();
+                        }
+                    });
+                    for (protocol, name) in self.interfaces_down() {
+                        ui.label(
+                            egui::RichText::new(tr!(
+                                "preferences-interface-down",
+                                name = name,
+                                protocol = protocol.to_string()
+                            ))
+                            .color(egui::Color32::RED),
+                        );
+                    }
+                    ui.label(tr!("preferences-interfaces-hint"));
+
+                    ui.add_space(8.0);
+                    section_heading(
+                        ui,
+                        &tr!("preferences-network-input"),
+                        PreferencesSection::NetworkInput,
+                        &mut focus,
+                    );
+                    let input = &mut self.preferences.network_input;
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("preferences-receive"));
+                        ui.checkbox(&mut input.art_net, "Art-Net");
+                        ui.checkbox(&mut input.sacn, "sACN");
+                    });
+                    egui::Grid::new("network_input")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label(tr!("preferences-universes"));
+                            draw_universe_list(ui, &mut input.universes);
+                            ui.end_row();
+
+                            ui.label(tr!("preferences-fader-wings"))
+                                .on_hover_text(tr!("preferences-fader-wings-hint"));
+                            draw_universe_list(ui, &mut input.fader_universes);
+                            ui.end_row();
+
+                            ui.label(tr!("preferences-merge"));
+                            ui.horizontal(|ui| {
+                                for mode in MergeMode::ALL {
+                                    ui.selectable_value(&mut input.mode, mode, mode.to_string());
+                                }
+                                if input.mode == MergeMode::Priority {
+                                    ui.label(tr!("preferences-halo-priority"));
+                                    ui.add(
+                                        egui::DragValue::new(&mut input.priority).range(0..=200),
+                                    );
+                                }
+                            });
+                            ui.end_row();
+                        });
+                    if let Some(error) = &self.network_input_error {
+                        ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                    } else if self.network_input.is_some() {
+                        let sources = self.dmx_input.sources();
+                        if sources.is_empty() {
+                            ui.label(tr!("preferences-listening-nothing"));
+                        }
+                        for source in sources {
+                            ui.label(if source.per_address() {
+                                tr!(
+                                    "preferences-source-per-address",
+                                    universe = source.universe,
+                                    protocol = source.protocol.to_string(),
+                                    name = source.name.as_str(),
+                                    priority = source.priority
+                                )
+                            } else {
+                                tr!(
+                                    "preferences-source",
+                                    universe = source.universe,
+                                    protocol = source.protocol.to_string(),
+                                    name = source.name.as_str(),
+                                    priority = source.priority
+                                )
+                            });
+                        }
+                        ui.ctx().request_repaint_after(Duration::from_secs(1));
+                    }
+
+                    ui.add_space(8.0);
+                    section_heading(
+                        ui,
+                        &tr!("preferences-main-backup"),
+                        PreferencesSection::ClockSync,
+                        &mut focus,
+                    );
+                    egui::Grid::new("clock_sync").num_columns(2).show(ui, |ui| {
+                        ui.label(tr!("preferences-clock"));
+                        egui::ComboBox::from_id_salt("clock_role")
+                            .selected_text(self.preferences.clock_role.to_string())
+                            .show_ui(ui, |ui| {
+                                for role in ClockRole::ALL {
+                                    ui.selectable_value(
+                                        &mut self.preferences.clock_role,
+                                        role,
+                                        role.to_string(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        match self.preferences.clock_role {
+                            ClockRole::Off => {}
+                            ClockRole::Main => {
+                                ui.label(tr!("port-label"));
+                                ui.add(
+                                    egui::DragValue::new(&mut self.preferences.clock_port)
+                                        .range(1024..=65535),
+                                );
+                                ui.end_row();
+                            }
+                            ClockRole::Backup => {
+                                ui.label(tr!("preferences-main"));
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut self.preferences.clock_main)
+                                        .hint_text("host:port"),
+                                );
+                                ui.end_row();
+
+                                ui.label("");
+                                ui.checkbox(
+                                    &mut self.preferences.tracking_backup,
+                                    tr!("preferences-tracking-backup"),
+                                );
+                                ui.end_row();
+                            }
+                        }
+                    });
+                    self.draw_clock_sync_status(ui);
+
+                    ui.add_space(8.0);
+                    ui.heading(tr!("preferences-output"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("preferences-stop-output"));
+                        egui::ComboBox::from_id_salt("stop_output")
+                            .selected_text(self.preferences.stop_output.to_string())
+                            .show_ui(ui, |ui| {
+                                for stop in StopOutput::ALL {
+                                    ui.selectable_value(
+                                        &mut self.preferences.stop_output,
+                                        stop,
+                                        stop.to_string(),
+                                    );
+                                }
+                            });
+                    });
+                    if self.preferences.stop_output == StopOutput::FadeOut {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("fade-label"));
+                            let mut secs = self.preferences.stop_fade.as_secs_f32();
+                            if ui
+                                .add(
+                                    egui::DragValue::new(&mut secs)
+                                        .range(0.0..=60.0)
+                                        .speed(0.1)
+                                        .suffix(tr!("unit-seconds")),
+                                )
+                                .changed()
+                            {
+                                self.preferences.stop_fade = Duration::from_secs_f32(secs);
+                            }
+                        });
+                    }
+                    ui.label(tr!("preferences-stop-output-hint"));
+                    ui.checkbox(
+                        &mut self.preferences.look_pads,
+                        tr!("preferences-look-pads"),
+                    );
+
+                    ui.add_space(8.0);
+                    ui.heading(tr!("preferences-backups"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("preferences-keep-last"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.preferences.backup_count).range(0..=100),
+                        );
+                        ui.label(tr!("preferences-saves"));
+                    });
+                    ui.label(tr!("preferences-backups-hint"));
+
+                    ui.add_space(8.0);
+                    ui.heading(tr!("preferences-startup"));
+                    self.draw_startup_preferences(ui);
+
+                    ui.add_space(8.0);
+                    ui.heading(tr!("preferences-display"));
+                    self.draw_awake_preferences(ui);
+
+                    ui.add_space(8.0);
+                    ui.heading(tr!("preferences-updates"));
+                    ui.checkbox(
+                        &mut self.preferences.check_for_updates,
+                        tr!("preferences-check-updates"),
+                    );
+                    ui.label(tr!("preferences-check-updates-hint"));
+                })
+            });
+        self.show_preferences = open;
+        self.preferences_focus = focus.filter(|_| open);
+
+        if self.preferences != before {
+            if self.preferences.remote_enabled != before.remote_enabled
+                || self.preferences.remote_port != before.remote_port
+            {
+                self.apply_json_remote_preferences(ctx);
+            }
+            if self.preferences.http_enabled != before.http_enabled
+                || self.preferences.http_port != before.http_port
+            {
+                self.apply_http_preferences(ctx);
+            }
+            if self.preferences.osc_input_enabled != before.osc_input_enabled
+                || self.preferences.osc_input_port != before.osc_input_port
+            {
+                self.apply_osc_preferences(ctx);
+            }
+            if self.preferences.citp_enabled != before.citp_enabled {
+                self.apply_citp_preferences(ctx);
+            }
+            if self.preferences.psn_enabled != before.psn_enabled {
+                self.apply_psn_preferences(ctx);
+            }
+            if self.preferences.hid_device != before.hid_device
+                || self.preferences.hid_debounce_ms != before.hid_debounce_ms
+            {
+                self.apply_hid_preferences(ctx);
+            }
+            if self.preferences.midi_input != before.midi_input
+                || self.preferences.midi_output != before.midi_output
+                || self.preferences.midi_send_clock != before.midi_send_clock
+            {
+                self.apply_midi_preferences(ctx);
+            }
+            if self.preferences.audio_input != before.audio_input {
+                self.apply_audio_preferences();
+            }
+            if self.preferences.click_output != before.click_output {
+                self.apply_click_preferences();
+            }
+            if self.preferences.audio_routes != before.audio_routes {
+                self.apply_playback_preferences();
+            }
+            if self.preferences.interfaces.osc != before.interfaces.osc {
+                self.apply_osc_preferences(ctx);
+            }
+            let interfaces = &self.preferences.interfaces;
+            let input = &self.preferences.network_input;
+            if interfaces.art_net != before.interfaces.art_net
+                || interfaces.sacn != before.interfaces.sacn
+                || input.art_net != before.network_input.art_net
+                || input.sacn != before.network_input.sacn
+                || input.universes != before.network_input.universes
+                || input.fader_universes != before.network_input.fader_universes
+            {
+                self.apply_network_input_preferences();
+            }
+            if self.preferences.clock_role != before.clock_role
+                || self.preferences.tracking_backup != before.tracking_backup
+                || self.preferences.clock_port != before.clock_port
+                || self.preferences.clock_main != before.clock_main
+            {
+                self.apply_clock_sync_preferences(ctx);
+            }
+            if self.preferences.language != before.language {
+                i18n::set_language(self.preferences.language);
+            }
+            if self.preferences.theme != before.theme {
+                self.apply_theme(ctx);
+            }
+            let _ = self.preferences.save();
+        }
+        if apply_scale || self.preferences.touch_mode != before.touch_mode {
+            self.apply_ui_scale(ctx);
+        }
+    }
+
+    pub(crate) fn apply_theme(&self, ctx: &egui::Context) {
+        let base = match self.preferences.theme {
+            Theme::Light => egui::Theme::Light,
+            Theme::Dark | Theme::HighContrast => egui::Theme::Dark,
+        };
+        ctx.set_theme(base);
+        ctx.set_visuals_of(base, self.preferences.theme.visuals());
+    }
+}
+
+impl HaloApp {
+    /// The USB button device and its debounce time.
+    fn draw_startup_preferences(&mut self, ui: &mut egui::Ui) {
+        let startup = &mut self.preferences.startup;
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut startup.load_last_show, tr!("preferences-open-last"));
+            if let Some(path) = &startup.last_show {
+                ui.weak(path.display().to_string());
+            }
+        });
+        ui.checkbox(&mut startup.enable_output, tr!("preferences-enable-output"));
+        ui.checkbox(&mut startup.connect_midi, tr!("preferences-connect-midi"));
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut startup.start_playback,
+                tr!("preferences-start-playback"),
+            );
+            ui.add_enabled_ui(startup.start_playback, |ui| {
+                let secs = startup.start_at.as_secs();
+                let (mut hours, mut minutes, mut seconds) =
+                    (secs / 3600, (secs % 3600) / 60, secs % 60);
+                let mut changed = false;
+                for (value, max) in [(&mut hours, 23), (&mut minutes, 59), (&mut seconds, 59)] {
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(value)
+                                .range(0..=max)
+                                .custom_formatter(|n, _| format!("{:02}", n)),
+                        )
+                        .changed();
+                }
+                if changed {
+                    startup.start_at = Duration::from_secs(hours * 3600 + minutes * 60 + seconds);
+                }
+                ui.label(tr!("preferences-start-after"));
+                let mut delay = startup.start_delay.as_secs_f32();
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut delay)
+                            .range(0.0..=300.0)
+                            .speed(0.5)
+                            .suffix(tr!("unit-seconds")),
+                    )
+                    .on_hover_text(tr!("preferences-start-delay-hint"))
+                    .changed()
+                {
+                    startup.start_delay = Duration::from_secs_f32(delay);
+                }
+            });
+        });
+        ui.label(tr!("preferences-startup-hint"));
+    }
+
+    fn draw_hid_preferences(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("preferences-usb-buttons"));
+            let device = &mut self.preferences.hid_device;
+            let selected = device
+                .as_ref()
+                .map_or_else(|| tr!("none"), |path| path.display().to_string());
+            egui::ComboBox::from_id_salt("hid_device")
+                .selected_text(selected)
+                .width(220.0)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(device, None, tr!("none"));
+                    for found in hid::devices() {
+                        ui.selectable_value(device, Some(found.path.clone()), found.name)
+                            .on_hover_text(found.path.display().to_string());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("preferences-debounce"));
+            ui.add(
+                egui::DragValue::new(&mut self.preferences.hid_debounce_ms)
+                    .range(0..=1000)
+                    .suffix(tr!("unit-ms")),
+            )
+            .on_hover_text(tr!("preferences-debounce-hint"));
+        });
+        if let Some(error) = &self.hid_error {
+            ui.colored_label(egui::Color32::RED, error);
+        } else if self.hid.is_some() {
+            ui.weak(tr!("preferences-usb-bind-hint"));
+        }
+    }
+
+    fn draw_clock_sync_status(&mut self, ui: &mut egui::Ui) {
+        match self.preferences.clock_role {
+            ClockRole::Off => {
+                ui.label(tr!("preferences-clock-off-hint"));
+            }
+            ClockRole::Main => {
+                server_status(
+                    ui,
+                    self.clock_server.is_some(),
+                    &self.clock_sync_error,
+                    self.preferences.clock_port,
+                );
+                let backups = self
+                    .clock_server
+                    .as_ref()
+                    .map(|server| server.backups())
+                    .unwrap_or_default();
+                if backups.is_empty() {
+                    ui.label(tr!("preferences-no-backup"));
+                } else {
+                    for backup in backups {
+                        ui.label(tr!(
+                            "preferences-backup-following",
+                            backup = backup.to_string()
+                        ));
+                    }
+                }
+                ui.ctx().request_repaint_after(Duration::from_secs(1));
+            }
+            ClockRole::Backup => {
+                let error = self.clock_client.as_ref().and_then(|client| client.error());
+                let estimate = self
+                    .clock_client
+                    .as_ref()
+                    .and_then(|client| client.estimate());
+                if let Some(error) = error {
+                    ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                } else if let (Some(estimate), Some(offset)) = (estimate, self.clock_offset) {
+                    ui.label(tr!(
+                        "preferences-clock-estimate",
+                        offset = format!("{:+.1}", offset * 1000.0),
+                        round_trip = format!("{:.1}", estimate.round_trip.as_secs_f64() * 1000.0),
+                        corrections = self.clock_corrections
+                    ));
+                } else {
+                    ui.label(tr!("preferences-waiting-main"));
+                }
+                if !self.preferences.tracking_backup {
+                    ui.label(tr!("preferences-main-drives"));
+                    return;
+                }
+                match self.backup_state {
+                    BackupState::Waiting | BackupState::Standby => {
+                        ui.label(tr!("preferences-output-held"));
+                        if ui
+                            .button(tr!("preferences-take-over"))
+                            .on_hover_text(tr!("preferences-take-over-hint"))
+                            .clicked()
+                        {
+                            self.take_over_output();
+                            self.notify(LogLevel::Warning, tr!("preferences-taken-over"));
+                        }
+                    }
+                    BackupState::TakenOver => {
+                        ui.label(
+                            egui::RichText::new(tr!("preferences-sending"))
+                                .color(egui::Color32::RED),
+                        );
+                        if ui
+                            .button(tr!("preferences-hand-back"))
+                            .on_hover_text(tr!("preferences-hand-back-hint"))
+                            .clicked()
+                        {
+                            self.hand_back_output();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Universe numbers, each removable from its right-click menu.
+fn draw_universe_list(ui: &mut egui::Ui, universes: &mut Vec<u16>) {
+    ui.horizontal_wrapped(|ui| {
+        let mut remove = None;
+        for (i, universe) in universes.iter_mut().enumerate() {
+            ui.add(egui::DragValue::new(universe).range(1..=63999))
+                .context_menu(|ui| {
+                    if ui.button(tr!("remove")).clicked() {
+                        remove = Some(i);
+                        ui.close_menu();
+                    }
+                });
+        }
+        if let Some(i) = remove {
+            universes.remove(i);
+        }
+        if accessibility::named(ui.button("+"), tr!("preferences-add-universe"))
+            .on_hover_text(tr!("preferences-add-universe"))
+            .clicked()
+        {
+            let next = universes.iter().max().map_or(1, |u| u + 1);
+            universes.push(next);
+        }
+    });
+}
+
+fn server_status(ui: &mut egui::Ui, running: bool, error: &Option<String>, port: u16) {
+    if let Some(error) = error {
+        ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+    } else if running {
+        ui.label(tr!("listening-on-port", port = port));
+    }
+}
+
+/// Picks a MIDI port or audio device by name. They are only listed while
+/// the combo box is open, as enumerating them opens the device APIs.
+/// The outputs the show audio plays on, each with the channels of the file
+/// it takes.
+fn draw_audio_routes(ui: &mut egui::Ui, routes: &mut Vec<AudioRoute>) {
+    let mut remove = None;
+    egui::Grid::new("audio_routes")
+        .num_columns(4)
+        .show(ui, |ui| {
+            for (i, route) in routes.iter_mut().enumerate() {
+                let mut device = Some(route.device.clone());
+                port_combo(
+                    ui,
+                    &format!("audio_route_{}", i),
+                    &mut device,
+                    click::output_devices,
+                );
+                match device {
+                    Some(device) => route.device = device,
+                    None => remove = Some(i),
+                }
+                ui.horizontal(|ui| {
+                    let mut first = route.channels.first().copied().unwrap_or(1);
+                    let mut last = route.channels.last().copied().unwrap_or(first);
+                    ui.label(tr!("preferences-channels"));
+                    let changed = ui
+                        .add(egui::DragValue::new(&mut first).range(1..=64))
+                        .changed();
+                    ui.label(tr!("preferences-channels-to"));
+                    let changed = ui
+                        .add(egui::DragValue::new(&mut last).range(1..=64))
+                        .changed()
+                        || changed;
+                    if changed {
+                        route.channels = (first..=last.max(first)).collect();
+                    }
+                });
+                ui.add(
+                    egui::Slider::new(&mut route.level, 0.0..=1.0)
+                        .show_value(false)
+                        .text(tr!("level")),
+                );
+                if accessibility::named(ui.small_button("🗑"), tr!("remove")).clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(i) = remove {
+        routes.remove(i);
+    }
+    ui.menu_button(tr!("preferences-add-output"), |ui| {
+        let devices = click::output_devices();
+        if devices.is_empty() {
+            ui.weak(tr!("preferences-no-audio-outputs"));
+        }
+        for device in devices {
+            if ui.button(&device).clicked() {
+                routes.push(AudioRoute::new(&device));
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+fn port_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    port: &mut Option<String>,
+    list_ports: fn() -> Vec<String>,
+) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(port.clone().unwrap_or_else(|| tr!("none")))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(port, None, tr!("none"));
+            for name in list_ports() {
+                let label = name.clone();
+                ui.selectable_value(port, Some(name), label);
+            }
+        });
+}
+
+/// Picks the interface for `protocol`, still naming a chosen interface that
+/// has disappeared.
+fn interface_combo(
+    ui: &mut egui::Ui,
+    protocol: Protocol,
+    selected: &mut Option<String>,
+    interfaces: &[NetworkInterface],
+) {
+    let text = match selected.as_deref() {
+        Some(name) => interfaces
+            .iter()
+            .find(|interface| interface.name == name)
+            .map_or_else(
+                || tr!("preferences-interface-missing", name = name),
+                |i| i.to_string(),
+            ),
+        None => tr!("any"),
+    };
+    egui::ComboBox::from_id_salt(("interface", protocol.to_string()))
+        .selected_text(text)
+        .width(220.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, tr!("any"));
+            for interface in interfaces {
+                ui.selectable_value(
+                    selected,
+                    Some(interface.name.clone()),
+                    interface.to_string(),
+                );
+            }
+        });
+}
@@ -0,0 +1,249 @@
+use crate::fixture::FixtureProfile;
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+/// How many recently patched profiles are remembered.
+const RECENT: usize = 8;
+
+/// Where the operator is in the library: a search, or a manufacturer and
+/// model picked, and the profile being previewed.
+#[derive(Default)]
+pub(crate) struct ProfileBrowser {
+    query: String,
+    manufacturer: Option<String>,
+    model: Option<String>,
+    preview: Option<usize>,
+}
+
+fn matches(profile: &FixtureProfile, query: &str) -> bool {
+    [
+        profile.name.as_str(),
+        profile.manufacturer(),
+        profile.model(),
+        &profile.mode(),
+    ]
+    .iter()
+    .any(|text| text.to_lowercase().contains(query))
+}
+
+/// Each distinct value of `key` over `profiles`, sorted.
+fn distinct<'a>(
+    profiles: impl Iterator<Item = &'a FixtureProfile>,
+    key: impl Fn(&'a FixtureProfile) -> &'a str,
+) -> Vec<&'a str> {
+    let mut values: Vec<&str> = profiles.map(key).collect();
+    values.sort_unstable_by_key(|value| value.to_lowercase());
+    values.dedup();
+    values
+}
+
+/// Everything a profile patches as, before patching it.
+fn draw_preview(ui: &mut egui::Ui, profile: &FixtureProfile) {
+    ui.heading(&profile.name);
+    ui.label(format!(
+        "{} · {} · {}",
+        profile.manufacturer(),
+        profile.model(),
+        profile.mode()
+    ));
+    ui.label(tr!("library-footprint", count = profile.channels.len()));
+    egui::Grid::new("profile_preview_channels")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            for (i, attribute) in profile.channels.iter().enumerate() {
+                ui.monospace(format!("{:>3}", i + 1));
+                ui.label(attribute.to_string());
+                ui.end_row();
+            }
+        });
+    for wheel in &profile.wheels {
+        let slots: Vec<&str> = wheel.slots.iter().map(|slot| slot.name.as_str()).collect();
+        ui.label(format!("{}: {}", wheel.attribute, slots.join(", ")));
+    }
+    if let Some(white) = profile.white {
+        ui.label(tr!("library-white", warm = white.warm, cool = white.cool));
+    }
+    if !profile.controls.is_empty() {
+        let controls: Vec<String> = profile
+            .controls
+            .iter()
+            .map(|control| control.kind.to_string())
+            .collect();
+        ui.label(tr!("library-controls", controls = controls.join(", ")));
+    }
+}
+
+impl HaloApp {
+    /// The profile library by manufacturer, model and mode, with search,
+    /// favourites and recently patched profiles, for picking what Add
+    /// Fixture patches.
+    pub(crate) fn draw_profile_browser_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_profile_browser;
+        let mut pick = None;
+        let mut star = None;
+        egui::Window::new(tr!("library-title"))
+            .id(egui::Id::new("profile_browser"))
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                let library = &self.profile_library;
+                let browser = &mut self.profile_browser;
+                let by_name = |name: &str| library.iter().position(|p| p.name == name);
+                ui.horizontal(|ui| {
+                    ui.label(tr!("search-label"));
+                    ui.text_edit_singleline(&mut browser.query);
+                });
+
+                let mut shortcut = |ui: &mut egui::Ui, label: &str, names: &[String]| {
+                    let found: Vec<usize> = names.iter().filter_map(|n| by_name(n)).collect();
+                    if found.is_empty() {
+                        return;
+                    }
+                    ui.horizontal_wrapped(|ui| {
+                        ui.strong(label);
+                        for i in found {
+                            if ui
+                                .selectable_label(browser.preview == Some(i), &library[i].name)
+                                .clicked()
+                            {
+                                browser.preview = Some(i);
+                            }
+                        }
+                    });
+                };
+                shortcut(
+                    ui,
+                    &tr!("library-favourites"),
+                    &self.preferences.favorite_profiles,
+                );
+                shortcut(
+                    ui,
+                    &tr!("library-recent"),
+                    &self.preferences.recent_profiles,
+                );
+                ui.separator();
+
+                let query = browser.query.trim().to_lowercase();
+                egui::ScrollArea::vertical()
+                    .id_salt("profile_lists")
+                    .max_height(220.0)
+                    .show(ui, |ui| {
+                        if !query.is_empty() {
+                            for (i, profile) in library.iter().enumerate() {
+                                if matches(profile, &query)
+                                    && ui
+                                        .selectable_label(
+                                            browser.preview == Some(i),
+                                            format!(
+                                                "{} {} ({})",
+                                                profile.manufacturer(),
+                                                profile.model(),
+                                                profile.mode()
+                                            ),
+                                        )
+                                        .clicked()
+                                {
+                                    browser.preview = Some(i);
+                                }
+                            }
+                            return;
+                        }
+                        ui.columns(3, |columns| {
+                            columns[0].strong(tr!("library-manufacturer"));
+                            for manufacturer in distinct(library.iter(), |p| p.manufacturer()) {
+                                let selected =
+                                    browser.manufacturer.as_deref() == Some(manufacturer);
+                                if columns[0]
+                                    .selectable_label(selected, manufacturer)
+                                    .clicked()
+                                {
+                                    browser.manufacturer = Some(manufacturer.to_string());
+                                    browser.model = None;
+                                }
+                            }
+                            columns[1].strong(tr!("library-model"));
+                            let Some(manufacturer) = browser.manufacturer.clone() else {
+                                return;
+                            };
+                            let made =
+                                || library.iter().filter(|p| p.manufacturer() == manufacturer);
+                            for model in distinct(made(), |p| p.model()) {
+                                let selected = browser.model.as_deref() == Some(model);
+                                if columns[1].selectable_label(selected, model).clicked() {
+                                    browser.model = Some(model.to_string());
+                                }
+                            }
+                            columns[2].strong(tr!("library-mode"));
+                            let Some(model) = &browser.model else {
+                                return;
+                            };
+                            for (i, profile) in library.iter().enumerate() {
+                                if profile.manufacturer() == manufacturer
+                                    && profile.model() == model
+                                    && columns[2]
+                                        .selectable_label(
+                                            browser.preview == Some(i),
+                                            profile.mode(),
+                                        )
+                                        .clicked()
+                                {
+                                    browser.preview = Some(i);
+                                }
+                            }
+                        });
+                    });
+
+                let Some(profile) = browser.preview.and_then(|i| library.get(i)) else {
+                    return;
+                };
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .id_salt("profile_preview")
+                    .max_height(240.0)
+                    .show(ui, |ui| draw_preview(ui, profile));
+                ui.horizontal(|ui| {
+                    let starred = self.preferences.favorite_profiles.contains(&profile.name);
+                    if ui
+                        .button(if starred {
+                            tr!("library-favourite-on")
+                        } else {
+                            tr!("library-favourite-off")
+                        })
+                        .clicked()
+                    {
+                        star = Some(profile.name.clone());
+                    }
+                    if ui.button(tr!("library-use")).clicked() {
+                        pick = browser.preview;
+                    }
+                });
+            });
+        self.show_profile_browser = open;
+
+        if let Some(name) = star {
+            let favorites = &mut self.preferences.favorite_profiles;
+            match favorites.iter().position(|favorite| *favorite == name) {
+                Some(i) => {
+                    favorites.remove(i);
+                }
+                None => favorites.push(name),
+            }
+            let _ = self.preferences.save();
+        }
+        if let Some(index) = pick {
+            self.new_fixture_profile = index;
+            self.show_profile_browser = false;
+        }
+    }
+
+    /// Puts a profile at the top of the recently patched list.
+    pub(crate) fn note_recent_profile(&mut self, name: &str) {
+        let recent = &mut self.preferences.recent_profiles;
+        recent.retain(|recent| recent != name);
+        recent.insert(0, name.to_string());
+        recent.truncate(RECENT);
+        let _ = self.preferences.save();
+    }
+}
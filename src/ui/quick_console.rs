@@ -0,0 +1,249 @@
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::quick_console::Relative;
+use crate::HaloApp;
+use eframe::egui;
+use std::collections::BTreeMap;
+use std::f32::consts::TAU;
+
+/// How far the encoder is dragged, in points, for one step.
+const ENCODER_STEP: f32 = 3.0;
+/// How far the encoder's mark goes round for one step.
+const ENCODER_TURN: f32 = TAU / 100.0;
+
+/// The encoder over the selected channels, and the turn it's in the middle
+/// of. Each turn works from the levels the selection had when it started,
+/// so turning back puts them where they were.
+#[derive(Default)]
+pub(crate) struct QuickEncoder {
+    /// Scale the levels rather than move them all by the same amount.
+    scale: bool,
+    /// The selection's levels when the turn started, by fixture id.
+    base: BTreeMap<usize, u8>,
+    /// Steps turned since then: percentage points, or percent when scaling.
+    turned: f32,
+    /// Where the mark is drawn, in steps, kept between turns.
+    angle: f32,
+}
+
+impl QuickEncoder {
+    fn change(&self) -> Relative {
+        if self.scale {
+            Relative::Scale(1.0 + self.turned / 100.0)
+        } else {
+            Relative::By(self.turned)
+        }
+    }
+}
+
+/// An endless knob, turned by dragging across or up, or by scrolling over
+/// it. Returns how many steps it turned this frame.
+fn encoder(ui: &mut egui::Ui, angle: f32) -> (egui::Response, f32) {
+    let (rect, response) = ui.allocate_exact_size(egui::Vec2::splat(44.0), egui::Sense::drag());
+    let drag = response.drag_delta();
+    let mut steps = (drag.x - drag.y) / ENCODER_STEP;
+    if response.hovered() {
+        steps += ui.input(|i| i.raw_scroll_delta.y) / ENCODER_STEP;
+    }
+    let visuals = ui.style().interact(&response);
+    let centre = rect.center();
+    let radius = rect.width() / 2.0 - 2.0;
+    let painter = ui.painter();
+    painter.circle(centre, radius, visuals.bg_fill, visuals.fg_stroke);
+    let mark = egui::Vec2::angled(angle * ENCODER_TURN - TAU / 4.0);
+    painter.line_segment(
+        [centre + mark * radius * 0.4, centre + mark * radius * 0.9],
+        egui::Stroke::new(2.0, visuals.fg_stroke.color),
+    );
+    (
+        response.on_hover_cursor(egui::CursorIcon::ResizeHorizontal),
+        steps,
+    )
+}
+
+impl HaloApp {
+    /// What `fixture`'s intensity is at: its quick level, or what the cues
+    /// give. None without an intensity channel.
+    fn quick_level(&self, fixture: &Fixture) -> Option<u8> {
+        let channel = fixture.channel_for(Attribute::Intensity)?;
+        Some(
+            self.quick_levels
+                .get(&fixture.id)
+                .copied()
+                .unwrap_or_else(|| self.output.get(fixture.universe, channel)),
+        )
+    }
+
+    /// The level each of the fixtures `ids` is at, leaving out those
+    /// without an intensity channel.
+    pub(crate) fn quick_levels_of(&self, ids: &[usize]) -> BTreeMap<usize, u8> {
+        self.show
+            .fixtures
+            .iter()
+            .filter(|fixture| ids.contains(&fixture.id))
+            .filter_map(|fixture| Some((fixture.id, self.quick_level(fixture)?)))
+            .collect()
+    }
+
+    /// Holds each fixture in `levels` at its level there, changed.
+    pub(crate) fn set_quick_levels(&mut self, levels: &BTreeMap<usize, u8>, change: Relative) {
+        for (&id, &level) in levels {
+            self.quick_levels.insert(id, change.apply(level));
+        }
+    }
+
+    /// The ids of the fixtures at the selected channels.
+    fn quick_selection_ids(&self) -> Vec<usize> {
+        self.quick_selection
+            .iter()
+            .filter_map(|&channel| self.show.fixtures.get(channel - 1))
+            .map(|fixture| fixture.id)
+            .collect()
+    }
+
+    /// Moves the selected channels by `points`, for the keyboard.
+    pub(crate) fn nudge_quick_selection(&mut self, points: f32) {
+        let levels = self.quick_levels_of(&self.quick_selection_ids());
+        self.set_quick_levels(&levels, Relative::By(points));
+    }
+
+    /// The encoder, with whether it moves or scales the selection and how
+    /// far the turn has gone.
+    fn draw_quick_encoder(&mut self, ui: &mut egui::Ui) {
+        let selected = !self.quick_selection.is_empty();
+        ui.add_enabled_ui(selected, |ui| {
+            let (response, steps) = encoder(ui, self.quick_encoder.angle);
+            let response = response.on_hover_text(tr!("quick-encoder-hint"));
+            let turning = selected && (response.dragged() || (response.hovered() && steps != 0.0));
+            if turning {
+                if self.quick_encoder.base.is_empty() {
+                    self.quick_encoder.base = self.quick_levels_of(&self.quick_selection_ids());
+                    self.quick_encoder.turned = 0.0;
+                }
+                let encoder = &mut self.quick_encoder;
+                let most = if encoder.scale { 900.0 } else { 100.0 };
+                let turned = (encoder.turned + steps).clamp(-100.0, most);
+                encoder.angle += turned - encoder.turned;
+                encoder.turned = turned;
+                let levels = encoder.base.clone();
+                let change = encoder.change();
+                self.set_quick_levels(&levels, change);
+            } else if !response.hovered() {
+                self.quick_encoder.base.clear();
+                self.quick_encoder.turned = 0.0;
+            }
+
+            let encoder = &mut self.quick_encoder;
+            ui.vertical(|ui| {
+                ui.radio_value(&mut encoder.scale, false, tr!("quick-move"))
+                    .on_hover_text(tr!("quick-move-hint"));
+                ui.radio_value(&mut encoder.scale, true, tr!("quick-scale"))
+                    .on_hover_text(tr!("quick-scale-hint"));
+            });
+            let turned = encoder.turned.round();
+            if turned != 0.0 {
+                ui.monospace(if encoder.scale {
+                    format!("×{:.2}", 1.0 + turned / 100.0)
+                } else {
+                    format!("{:+}%", turned)
+                });
+            }
+        });
+    }
+
+    /// A fader per fixture with an intensity channel, numbered by its place
+    /// in the patch, the command line and the encoder.
+    pub(crate) fn draw_quick_console_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_quick_console;
+        egui::Window::new(tr!("menu-quick-console"))
+            .id(egui::Id::new("quick_console"))
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| self.draw_command_line(ui));
+                    if ui
+                        .add_enabled(
+                            !self.quick_levels.is_empty(),
+                            egui::Button::new(tr!("quick-release-all")),
+                        )
+                        .clicked()
+                    {
+                        self.quick_levels.clear();
+                    }
+                    ui.separator();
+                    self.draw_quick_encoder(ui);
+                });
+                ui.weak(tr!("quick-hint"));
+                ui.separator();
+
+                egui::ScrollArea::horizontal().show(ui, |ui| {
+                    ui.horizontal_top(|ui| {
+                        let mut toggled = None;
+                        for (index, fixture) in self.show.fixtures.iter().enumerate() {
+                            let Some(level) = self.quick_level(fixture) else {
+                                continue;
+                            };
+                            let number = index + 1;
+                            let held = self.quick_levels.get(&fixture.id).copied();
+                            let mut percent = level as f32 / 2.55;
+                            ui.vertical(|ui| {
+                                ui.set_width(48.0);
+                                let selected = self.quick_selection.contains(&number);
+                                let label = egui::RichText::new(number.to_string()).strong();
+                                let label = egui::Label::new(if selected {
+                                    label.color(egui::Color32::YELLOW)
+                                } else {
+                                    label
+                                })
+                                .sense(egui::Sense::click());
+                                if ui.add(label).on_hover_text(&fixture.name).clicked() {
+                                    toggled = Some(number);
+                                }
+                                let (fader, entry) = ui
+                                    .scope(|ui| {
+                                        if held.is_none() {
+                                            ui.visuals_mut().override_text_color =
+                                                Some(ui.visuals().weak_text_color());
+                                        }
+                                        let fader = ui.add(
+                                            egui::Slider::new(&mut percent, 0.0..=100.0)
+                                                .vertical()
+                                                .show_value(false),
+                                        );
+                                        let entry = ui.add_sized(
+                                            [44.0, 18.0],
+                                            egui::DragValue::new(&mut percent)
+                                                .range(0.0..=100.0)
+                                                .max_decimals(0),
+                                        );
+                                        (fader, entry)
+                                    })
+                                    .inner;
+                                if fader.changed() || entry.changed() {
+                                    self.quick_levels
+                                        .insert(fixture.id, (percent * 2.55).round() as u8);
+                                }
+                                if fader.secondary_clicked() || entry.secondary_clicked() {
+                                    self.quick_levels.remove(&fixture.id);
+                                }
+                                ui.add(
+                                    egui::Label::new(egui::RichText::new(&fixture.name).small())
+                                        .truncate(),
+                                );
+                            });
+                        }
+                        if let Some(number) = toggled {
+                            if let Some(at) = self.quick_selection.iter().position(|&n| n == number)
+                            {
+                                self.quick_selection.remove(at);
+                            } else {
+                                self.quick_selection.push(number);
+                            }
+                        }
+                    });
+                });
+            });
+        self.show_quick_console = open;
+    }
+}
@@ -0,0 +1,120 @@
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Instant;
+
+impl HaloApp {
+    pub(crate) fn has_unsaved_changes(&self) -> bool {
+        self.show.snapshot() != self.saved_show
+    }
+
+    /// Catches the window being closed, by Quit or by the window manager, to
+    /// offer to save first. Once quitting is certain the output is released
+    /// as it is on stop, and Halo waits for a fade out before closing.
+    pub(crate) fn handle_quit(&mut self, ctx: &egui::Context) {
+        if self.quit_after_save && self.file_dialog.is_none() {
+            self.quit_after_save = false;
+            if !self.has_unsaved_changes() {
+                self.quit(ctx);
+            }
+        }
+        if let Some(at) = self.quit_at {
+            let now = Instant::now();
+            if now >= at {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            } else {
+                ctx.request_repaint_after(at - now);
+            }
+        }
+
+        if !ctx.input(|i| i.viewport().close_requested()) {
+            return;
+        }
+        if !self.quitting && self.has_unsaved_changes() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.quit_prompt = true;
+            return;
+        }
+        self.quitting = true;
+        // Closing again during the fade quits straight away.
+        if self.quit_at.is_none() {
+            self.stop_playback();
+            let fade = self.release_output();
+            if !fade.is_zero() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                self.quit_at = Some(Instant::now() + fade);
+                self.notify(
+                    LogLevel::Info,
+                    tr!(
+                        "quit-fading",
+                        seconds = format!("{:.1}", fade.as_secs_f32())
+                    ),
+                );
+                return;
+            }
+        }
+        self.stop_output(ctx);
+    }
+
+    fn quit(&mut self, ctx: &egui::Context) {
+        self.quitting = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    /// Stops the output threads, leaving the released output on air.
+    fn stop_output(&mut self, ctx: &egui::Context) {
+        if let Some(midi_out) = &mut self.midi_out {
+            midi_out.set_running(false);
+        }
+        self.midi_out = None;
+        // Make sure the engine has the release before its last frame.
+        self.sync_engine(ctx);
+        self.engine.shutdown();
+        self.log.info(tr!("quit-output-stopped"));
+    }
+
+    pub(crate) fn draw_quit_prompt(&mut self, ctx: &egui::Context) {
+        if !self.quit_prompt {
+            return;
+        }
+
+        let mut save = false;
+        let mut discard = false;
+        let mut cancel = false;
+        // Save As is only drawn outside performance mode.
+        let can_save = self.show_path.is_some() || !self.performance_mode;
+        egui::Window::new(tr!("quit-title"))
+            .id(egui::Id::new("quit_prompt"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(tr!("quit-save-changes", show = self.show.name.as_str()));
+                ui.label(tr!("quit-changes-lost"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    save = ui
+                        .add_enabled(can_save, egui::Button::new(tr!("quit-save")))
+                        .on_disabled_hover_text(tr!("quit-save-disabled"))
+                        .clicked();
+                    discard = ui.button(tr!("quit-dont-save")).clicked();
+                    cancel = ui.button(tr!("cancel")).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape));
+                });
+            });
+
+        if save {
+            self.quit_prompt = false;
+            self.save_show();
+            // Without a show file, or if writing it failed, the Save As
+            // dialog is open; quit once it has saved.
+            self.quit_after_save = true;
+        } else if discard {
+            self.quit_prompt = false;
+            self.quit(ctx);
+        } else if cancel {
+            self.quit_prompt = false;
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use crate::cue;
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::recording::{self, Player, RecordSource, Recorder};
+use crate::stream_import::{self, ImportSettings, Section};
+use crate::HaloApp;
+use eframe::egui;
+use std::path::Path;
+use std::time::Duration;
+
+/// The Output Recording window's files, and what the engine last said it
+/// was doing with them.
+pub(crate) struct OutputRecording {
+    record_path: String,
+    source: RecordSource,
+    play_path: String,
+    looping: bool,
+    import_path: String,
+    import: ImportSettings,
+    /// Where in the show the imported cues start, in seconds.
+    import_offset: f32,
+    /// The looks found in the recording last analyzed.
+    sections: Vec<Section>,
+    /// How long has been recorded, while recording.
+    pub recording: Option<Duration>,
+    /// Position and length of the recording playing.
+    pub playback: Option<(Duration, Duration)>,
+    pub error: Option<String>,
+}
+
+impl Default for OutputRecording {
+    fn default() -> Self {
+        let path = format!("output-recording.{}", recording::EXTENSION);
+        Self {
+            record_path: path.clone(),
+            source: RecordSource::Output,
+            play_path: path.clone(),
+            looping: false,
+            import_path: path,
+            import: ImportSettings::default(),
+            import_offset: 0.0,
+            sections: Vec::new(),
+            recording: None,
+            playback: None,
+            error: None,
+        }
+    }
+}
+
+impl HaloApp {
+    fn start_recording(&mut self) {
+        let path = self.output_recording.record_path.trim().to_string();
+        let source = self.output_recording.source;
+        match Recorder::create(Path::new(&path), source) {
+            Ok(recorder) => {
+                self.engine.record(Some(recorder));
+                self.output_recording.error = None;
+                self.log.info(tr!(
+                    "recording-started",
+                    source = source.to_string(),
+                    path = path.as_str()
+                ));
+            }
+            Err(err) => self.output_recording.error = Some(format!("{}: {}", path, err)),
+        }
+    }
+
+    fn stop_recording(&mut self) {
+        self.engine.record(None);
+        self.log.info(tr!(
+            "recording-stopped",
+            path = self.output_recording.record_path.trim()
+        ));
+    }
+
+    fn analyze_recording(&mut self) {
+        let recording = &mut self.output_recording;
+        let path = recording.import_path.trim().to_string();
+        match stream_import::analyze(Path::new(&path), &self.show.fixtures, &recording.import) {
+            Ok(sections) => {
+                recording.sections = sections;
+                recording.error = None;
+            }
+            Err(err) => {
+                recording.sections.clear();
+                recording.error = Some(format!("{}: {}", path, err));
+            }
+        }
+    }
+
+    /// Adds the looks found by [`Self::analyze_recording`] to the end of the
+    /// cue list.
+    fn import_recording(&mut self) {
+        let recording = &self.output_recording;
+        let offset = Duration::from_secs_f32(recording.import_offset.max(0.0));
+        let imported = stream_import::cues(&recording.sections, &recording.import, offset);
+        let Some(added) = cue::paste_cues(&mut self.show.cues, None, &imported, 0.0) else {
+            self.warn_no_room(imported.len());
+            return;
+        };
+        let message = tr!(
+            "recording-imported",
+            count = added.len(),
+            path = recording.import_path.trim()
+        );
+        self.notify(LogLevel::Info, message);
+        self.output_recording.sections.clear();
+    }
+
+    fn play_recording(&mut self) {
+        let path = self.output_recording.play_path.trim().to_string();
+        match Player::open(Path::new(&path), self.output_recording.looping) {
+            Ok(player) => {
+                self.engine.play(Some(player));
+                self.output_recording.error = None;
+                self.log
+                    .info(tr!("recording-playing", path = path.as_str()));
+            }
+            Err(err) => self.output_recording.error = Some(format!("{}: {}", path, err)),
+        }
+    }
+
+    fn stop_playing_recording(&mut self) {
+        self.engine.play(None);
+        self.log.info(tr!("recording-playback-stopped"));
+    }
+
+    /// Recording every frame of the output to a file, and playing one back
+    /// in place of the show.
+    pub(crate) fn draw_recording_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_recording;
+        egui::Window::new(tr!("menu-recording"))
+            .id(egui::Id::new("output_recording"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.strong(tr!("recording-record"));
+                let idle = self.output_recording.recording.is_none();
+                ui.horizontal(|ui| {
+                    ui.label(tr!("file-label"));
+                    ui.add_enabled(
+                        idle,
+                        egui::TextEdit::singleline(&mut self.output_recording.record_path),
+                    );
+                });
+                ui.add_enabled_ui(idle, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("recording-source"));
+                        egui::ComboBox::from_id_salt("record_source")
+                            .selected_text(self.output_recording.source.to_string())
+                            .show_ui(ui, |ui| {
+                                for source in RecordSource::ALL {
+                                    ui.selectable_value(
+                                        &mut self.output_recording.source,
+                                        source,
+                                        source.to_string(),
+                                    );
+                                }
+                            });
+                    });
+                });
+                if self.output_recording.source == RecordSource::Input
+                    && !self.preferences.network_input.enabled()
+                {
+                    ui.weak(tr!("recording-input-off"));
+                }
+                ui.horizontal(|ui| match self.output_recording.recording {
+                    Some(length) => {
+                        if ui.button(tr!("recording-stop")).clicked() {
+                            self.stop_recording();
+                        }
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            tr!("recording-length", length = Self::format_duration(length)),
+                        );
+                    }
+                    None => {
+                        if ui
+                            .button(tr!("recording-start"))
+                            .on_hover_text(tr!("recording-start-hint"))
+                            .clicked()
+                        {
+                            self.start_recording();
+                        }
+                    }
+                });
+
+                ui.separator();
+                ui.strong(tr!("recording-play-back"));
+                ui.horizontal(|ui| {
+                    ui.label(tr!("file-label"));
+                    ui.add_enabled(
+                        self.output_recording.playback.is_none(),
+                        egui::TextEdit::singleline(&mut self.output_recording.play_path),
+                    );
+                });
+                ui.add_enabled(
+                    self.output_recording.playback.is_none(),
+                    egui::Checkbox::new(&mut self.output_recording.looping, tr!("loop")),
+                );
+                ui.horizontal(|ui| match self.output_recording.playback {
+                    Some((position, length)) => {
+                        if ui.button(tr!("recording-stop-playback")).clicked() {
+                            self.stop_playing_recording();
+                        }
+                        ui.monospace(format!(
+                            "{} / {}",
+                            Self::format_duration(position),
+                            Self::format_duration(length)
+                        ));
+                    }
+                    None => {
+                        if ui
+                            .button(tr!("recording-play"))
+                            .on_hover_text(tr!("recording-play-hint"))
+                            .clicked()
+                        {
+                            self.play_recording();
+                        }
+                    }
+                });
+                if self.output_recording.playback.is_some() {
+                    ui.weak(tr!("recording-playing-hint"));
+                }
+
+                ui.separator();
+                self.draw_recording_import(ui);
+                if let Some(error) = &self.output_recording.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+        self.show_recording = open;
+    }
+
+    /// Finding the looks in a recording of another console and adding them
+    /// as cues.
+    fn draw_recording_import(&mut self, ui: &mut egui::Ui) {
+        ui.strong(tr!("recording-import"));
+        let recording = &mut self.output_recording;
+        ui.horizontal(|ui| {
+            ui.label(tr!("file-label"));
+            ui.text_edit_singleline(&mut recording.import_path);
+        });
+        let mut settings_changed = false;
+        egui::Grid::new("recording_import")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr!("recording-hold"));
+                let mut hold = recording.import.hold.as_secs_f32();
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut hold)
+                            .range(0.1..=60.0)
+                            .speed(0.05)
+                            .suffix(tr!("unit-seconds")),
+                    )
+                    .on_hover_text(tr!("recording-hold-hint"))
+                    .changed()
+                {
+                    recording.import.hold = Duration::from_secs_f32(hold);
+                    settings_changed = true;
+                }
+                ui.end_row();
+
+                ui.label(tr!("recording-tolerance"));
+                settings_changed |= ui
+                    .add(egui::DragValue::new(&mut recording.import.tolerance).range(0..=32))
+                    .on_hover_text(tr!("recording-tolerance-hint"))
+                    .changed();
+                ui.end_row();
+
+                ui.label(tr!("recording-start-at"));
+                ui.add(
+                    egui::DragValue::new(&mut recording.import_offset)
+                        .range(0.0..=f32::MAX)
+                        .speed(0.1)
+                        .suffix(tr!("unit-seconds")),
+                )
+                .on_hover_text(tr!("recording-start-at-hint"));
+                ui.end_row();
+            });
+        ui.checkbox(
+            &mut recording.import.changes_only,
+            tr!("recording-changes-only"),
+        );
+        if settings_changed {
+            recording.sections.clear();
+        }
+        if ui
+            .button(tr!("recording-analyze"))
+            .on_hover_text(tr!("recording-analyze-hint"))
+            .clicked()
+        {
+            self.analyze_recording();
+        }
+
+        let sections = &self.output_recording.sections;
+        if sections.is_empty() {
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .show(ui, |ui| {
+                egui::Grid::new("recording_sections")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("recording-look"));
+                        ui.strong(tr!("recording-starts"));
+                        ui.strong(tr!("fade"));
+                        ui.end_row();
+                        for (i, section) in sections.iter().enumerate() {
+                            ui.label(format!("{}", i + 1));
+                            ui.monospace(format!("{:.2}s", section.start.as_secs_f32()));
+                            ui.monospace(format!("{:.2}s", section.fade.as_secs_f32()));
+                            ui.end_row();
+                        }
+                    });
+            });
+        if ui
+            .button(tr!("recording-add-cues", count = sections.len()))
+            .on_hover_text(tr!("recording-add-cues-hint"))
+            .clicked()
+        {
+            self.import_recording();
+        }
+    }
+}
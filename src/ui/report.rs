@@ -0,0 +1,68 @@
+use crate::export::{self, ReportFormat};
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use std::path::Path;
+
+impl HaloApp {
+    pub(crate) fn draw_report_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_report;
+        egui::Window::new(tr!("report-title"))
+            .id(egui::Id::new("report"))
+            .open(&mut open)
+            .default_width(380.0)
+            .show(ctx, |ui| {
+                ui.label(tr!("report-description"));
+                ui.add_space(4.0);
+                egui::Grid::new("report_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("report-format"));
+                        let previous = self.report_format;
+                        egui::ComboBox::from_id_salt("report_format")
+                            .selected_text(self.report_format.to_string())
+                            .show_ui(ui, |ui| {
+                                for format in ReportFormat::ALL {
+                                    ui.selectable_value(
+                                        &mut self.report_format,
+                                        format,
+                                        format.to_string(),
+                                    );
+                                }
+                            });
+                        if self.report_format != previous {
+                            self.report_path = Path::new(&self.report_path)
+                                .with_extension(self.report_format.extension())
+                                .to_string_lossy()
+                                .into_owned();
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("export-to"));
+                        ui.text_edit_singleline(&mut self.report_path);
+                        ui.end_row();
+                    });
+
+                ui.add_space(8.0);
+                if ui.button(tr!("report-export")).clicked() {
+                    let report = export::show_report(&self.show, self.report_format);
+                    self.report_status = Some(
+                        std::fs::write(&self.report_path, report)
+                            .map(|_| tr!("exported-to", path = self.report_path.as_str()))
+                            .map_err(|err| tr!("export-failed", error = err.to_string())),
+                    );
+                }
+
+                match &self.report_status {
+                    Some(Ok(message)) => {
+                        ui.label(egui::RichText::new(message).color(egui::Color32::GREEN));
+                    }
+                    Some(Err(message)) => {
+                        ui.label(egui::RichText::new(message).color(egui::Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+        self.show_report = open;
+    }
+}
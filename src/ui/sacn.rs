@@ -0,0 +1,75 @@
+use crate::i18n::tr;
+use crate::sacn::{SacnUniverse, MAX_PRIORITY};
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+
+fn priority_edit(ui: &mut egui::Ui, priority: &mut u8) -> egui::Response {
+    ui.add(egui::DragValue::new(priority).range(0..=MAX_PRIORITY))
+        .on_hover_text(tr!("sacn-priority-hint"))
+}
+
+impl HaloApp {
+    pub(crate) fn draw_sacn_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_sacn;
+        let before = self.preferences.sacn_output.clone();
+        egui::Window::new(tr!("sacn-title"))
+            .id(egui::Id::new("sacn"))
+            .open(&mut open)
+            .default_width(440.0)
+            .show(ctx, |ui| {
+                let output = &mut self.preferences.sacn_output;
+                ui.checkbox(&mut output.enabled, tr!("sacn-enabled"));
+                egui::Grid::new("sacn_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("sacn-source-name"));
+                        ui.text_edit_singleline(&mut output.source_name);
+                        ui.end_row();
+
+                        ui.label(tr!("sacn-priority"));
+                        priority_edit(ui, &mut output.priority);
+                        ui.end_row();
+                    });
+                ui.separator();
+
+                ui.strong(tr!("sacn-universes"));
+                ui.weak(tr!("sacn-universes-hint"));
+                let mut remove = None;
+                egui::Grid::new("sacn_universes")
+                    .num_columns(4)
+                    .show(ui, |ui| {
+                        for (i, universe) in output.universes.iter_mut().enumerate() {
+                            ui.add(egui::DragValue::new(&mut universe.universe).range(1..=63999));
+                            priority_edit(ui, &mut universe.priority);
+                            ui.checkbox(&mut universe.per_address, tr!("sacn-per-address"));
+                            if accessibility::named(ui.small_button("✖"), tr!("remove")).clicked()
+                            {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(i) = remove {
+                    output.universes.remove(i);
+                }
+                if ui.button(tr!("sacn-add-universe")).clicked() {
+                    let next = output
+                        .universes
+                        .iter()
+                        .map(|universe| universe.universe + 1)
+                        .max()
+                        .unwrap_or(1);
+                    output.universes.push(SacnUniverse {
+                        priority: output.priority,
+                        ..SacnUniverse::new(next)
+                    });
+                }
+            });
+        self.show_sacn = open;
+
+        if self.preferences.sacn_output != before {
+            let _ = self.preferences.save();
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::cue::CueValue;
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+impl HaloApp {
+    /// Panic while playing, or Resume while in the safe state. Shown in the
+    /// header in every mode.
+    pub(crate) fn draw_panic_button(&mut self, ui: &mut egui::Ui) {
+        if self.panic.is_some() {
+            if ui
+                .button(egui::RichText::new(tr!("transport-resume")).strong())
+                .on_hover_text(tr!("safe-resume-hint"))
+                .clicked()
+            {
+                self.resume_from_panic();
+            }
+            ui.label(
+                egui::RichText::new(tr!("safe-engaged"))
+                    .strong()
+                    .color(egui::Color32::from_rgb(255, 180, 0)),
+            );
+        } else if ui
+            .add(
+                egui::Button::new(
+                    egui::RichText::new(tr!("safe-panic"))
+                        .strong()
+                        .color(egui::Color32::WHITE),
+                )
+                .fill(egui::Color32::from_rgb(180, 0, 0)),
+            )
+            .on_hover_text(tr!("safe-panic-hint"))
+            .clicked()
+        {
+            self.engage_panic();
+        }
+    }
+
+    /// Sets up the safe look: a level for each fixture attribute, with
+    /// anything left at zero going dark.
+    pub(crate) fn draw_safe_state_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_safe_state;
+        let safe = &mut self.show.safe_state;
+        egui::Window::new(tr!("safe-title"))
+            .id(egui::Id::new("safe_state"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.label(tr!("safe-description"));
+                ui.horizontal(|ui| {
+                    ui.label(tr!("fade-label"));
+                    let mut secs = safe.fade.as_secs_f32();
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut secs)
+                                .range(0.0..=60.0)
+                                .speed(0.1)
+                                .suffix(tr!("unit-seconds")),
+                        )
+                        .changed()
+                    {
+                        safe.fade = Duration::from_secs_f32(secs);
+                    }
+                    if ui
+                        .button(tr!("safe-capture"))
+                        .on_hover_text(tr!("safe-capture-hint"))
+                        .clicked()
+                    {
+                        safe.values = self
+                            .show
+                            .fixtures
+                            .iter()
+                            .flat_map(|fixture| {
+                                fixture.profile.channels.iter().filter_map(|&attribute| {
+                                    let channel = fixture.channel_for(attribute)?;
+                                    let value = self.output.get(fixture.universe, channel);
+                                    (value > 0).then_some(CueValue {
+                                        fixture_id: fixture.id,
+                                        attribute,
+                                        value,
+                                    })
+                                })
+                            })
+                            .collect();
+                    }
+                    if ui.button(tr!("clear")).clicked() {
+                        safe.values.clear();
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("safe_state")
+                        .striped(true)
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            for fixture in &self.show.fixtures {
+                                ui.label(&fixture.name);
+                                ui.horizontal(|ui| {
+                                    for &attribute in &fixture.profile.channels {
+                                        let index = safe.values.iter().position(|v| {
+                                            v.fixture_id == fixture.id && v.attribute == attribute
+                                        });
+                                        let mut value = index.map_or(0, |i| safe.values[i].value);
+                                        ui.label(attribute.to_string());
+                                        if ui.add(egui::DragValue::new(&mut value)).changed() {
+                                            match (index, value) {
+                                                (Some(i), 0) => {
+                                                    safe.values.remove(i);
+                                                }
+                                                (Some(i), _) => safe.values[i].value = value,
+                                                (None, _) => safe.values.push(CueValue {
+                                                    fixture_id: fixture.id,
+                                                    attribute,
+                                                    value,
+                                                }),
+                                            }
+                                        }
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        self.show_safe_state = open;
+    }
+}
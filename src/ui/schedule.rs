@@ -0,0 +1,129 @@
+use crate::i18n::tr;
+use crate::schedule::{ScheduleTime, ScheduledTrigger};
+use crate::ui::accessibility;
+use crate::ui::bindings::draw_action;
+use crate::HaloApp;
+use chrono::Local;
+use eframe::egui;
+
+fn draw_time(ui: &mut egui::Ui, when: &mut ScheduleTime) {
+    egui::ComboBox::from_id_salt("schedule_kind")
+        .selected_text(when.kind())
+        .width(100.0)
+        .show_ui(ui, |ui| {
+            for kind in ScheduleTime::kinds() {
+                let selected = kind.kind() == when.kind();
+                if ui.selectable_label(selected, kind.kind()).clicked() && !selected {
+                    *when = kind;
+                }
+            }
+        });
+    match when {
+        ScheduleTime::TimeOfDay { hour, minute } => {
+            ui.add(
+                egui::DragValue::new(hour)
+                    .range(0..=23)
+                    .custom_formatter(|n, _| format!("{:02}", n)),
+            );
+            ui.label(":");
+            ui.add(
+                egui::DragValue::new(minute)
+                    .range(0..=59)
+                    .custom_formatter(|n, _| format!("{:02}", n)),
+            );
+        }
+        ScheduleTime::Sunrise { offset_minutes } | ScheduleTime::Sunset { offset_minutes } => {
+            ui.add(
+                egui::DragValue::new(offset_minutes)
+                    .range(-720..=720)
+                    .prefix(tr!("schedule-offset-prefix"))
+                    .suffix(tr!("unit-minutes")),
+            );
+        }
+    }
+}
+
+impl HaloApp {
+    pub(crate) fn draw_schedule_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_schedule;
+        // Edited apart from the show so the action pickers can list its cues,
+        // pads and macros.
+        let mut schedule = std::mem::take(&mut self.show.schedule);
+        let mut remove = None;
+        let now = Local::now();
+        egui::Window::new(tr!("schedule-title"))
+            .id(egui::Id::new("schedule"))
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.label(tr!("schedule-hint"));
+                ui.horizontal(|ui| {
+                    let location = &mut self.show.location;
+                    ui.label(tr!("schedule-latitude"));
+                    ui.add(
+                        egui::DragValue::new(&mut location.latitude)
+                            .range(-90.0..=90.0)
+                            .speed(0.01)
+                            .suffix("°"),
+                    );
+                    ui.label(tr!("schedule-longitude"));
+                    ui.add(
+                        egui::DragValue::new(&mut location.longitude)
+                            .range(-180.0..=180.0)
+                            .speed(0.01)
+                            .suffix("°"),
+                    );
+                });
+                ui.separator();
+
+                egui::Grid::new("schedule")
+                    .striped(true)
+                    .num_columns(5)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("schedule-on"));
+                        ui.strong(tr!("schedule-when"));
+                        ui.strong(tr!("schedule-action"));
+                        ui.strong(tr!("schedule-next"));
+                        ui.end_row();
+
+                        for (i, trigger) in schedule.iter_mut().enumerate() {
+                            accessibility::named(
+                                ui.checkbox(&mut trigger.enabled, ""),
+                                tr!("enabled"),
+                            );
+                            ui.push_id(("when", i), |ui| {
+                                ui.horizontal(|ui| draw_time(ui, &mut trigger.when));
+                            });
+                            ui.push_id(("action", i), |ui| {
+                                ui.horizontal(|ui| {
+                                    draw_action(ui, &self.show, &mut trigger.action)
+                                });
+                            });
+                            let next = trigger.next_after(now, self.show.location).map_or_else(
+                                || tr!("schedule-never"),
+                                |time| time.format("%a %H:%M").to_string(),
+                            );
+                            if trigger.enabled {
+                                ui.label(next);
+                            } else {
+                                ui.weak(next);
+                            }
+                            if accessibility::named(ui.small_button("✖"), tr!("remove")).clicked()
+                            {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                if ui.button(tr!("add")).clicked() {
+                    schedule.push(ScheduledTrigger::default());
+                }
+            });
+        if let Some(index) = remove {
+            schedule.remove(index);
+        }
+        self.show.schedule = schedule;
+        self.show_schedule = open;
+    }
+}
@@ -0,0 +1,78 @@
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum ScriptTarget {
+    Cue(usize),
+    Pad(usize),
+}
+
+impl HaloApp {
+    pub(crate) fn draw_script_editor(&mut self, ctx: &egui::Context) {
+        let Some(target) = self.script_target else {
+            return;
+        };
+        let (title, source) = match target {
+            ScriptTarget::Cue(index) => match self.show.cues.get_mut(index) {
+                Some(cue) => (
+                    tr!("script-cue-title", number = cue.number.to_string()),
+                    &mut cue.script,
+                ),
+                None => {
+                    self.script_target = None;
+                    return;
+                }
+            },
+            ScriptTarget::Pad(index) => match self.show.pads.get_mut(index) {
+                Some(pad) => (
+                    tr!("script-pad-title", pad = pad.label.as_str()),
+                    &mut pad.script,
+                ),
+                None => {
+                    self.script_target = None;
+                    return;
+                }
+            },
+        };
+
+        let mut open = true;
+        let mut run = false;
+        egui::Window::new(title)
+            .id(egui::Id::new("script_editor"))
+            .open(&mut open)
+            .default_size([480.0, 320.0])
+            .show(ctx, |ui| {
+                ui.label(match target {
+                    ScriptTarget::Cue(_) => tr!("script-cue-hint"),
+                    ScriptTarget::Pad(_) => tr!("script-pad-hint"),
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(source)
+                                .code_editor()
+                                .desired_rows(12)
+                                .desired_width(f32::INFINITY)
+                                .hint_text("if hour() >= 22 { skip(); }"),
+                        );
+                    });
+                ui.horizontal(|ui| {
+                    run = ui.button(tr!("script-test-run")).clicked();
+                    ui.label(tr!("script-errors-logged"));
+                });
+                if let Some(error) = &self.script_error {
+                    ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                }
+            });
+
+        if run {
+            self.script_error = self.run_script(target);
+        }
+        if !open {
+            self.script_target = None;
+            self.script_error = None;
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::export::{self, ReportFormat};
+use crate::i18n::tr;
+use crate::session::format_late;
+use crate::HaloApp;
+use eframe::egui;
+use std::path::Path;
+
+/// Cues that fired late on timecode, here and in the cue list.
+pub(crate) const LATE: egui::Color32 = egui::Color32::from_rgb(255, 140, 0);
+
+impl HaloApp {
+    /// Run time, time since saving and every cue fired this session, with
+    /// the post-show report export.
+    pub(crate) fn draw_session_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_session;
+        egui::Window::new(tr!("session-title"))
+            .id(egui::Id::new("session"))
+            .open(&mut open)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                let session = &self.session;
+                egui::Grid::new("session_stats")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("session-open-for"));
+                        ui.monospace(Self::format_duration(session.opened.elapsed()));
+                        ui.end_row();
+
+                        ui.label(tr!("session-played-for"));
+                        ui.monospace(Self::format_duration(session.play_time()));
+                        ui.end_row();
+
+                        ui.label(tr!("session-run-time"));
+                        match session.run_time() {
+                            Some(run) => ui.monospace(Self::format_duration(run)),
+                            None => ui.weak(tr!("session-not-started")),
+                        };
+                        ui.end_row();
+
+                        ui.label(tr!("session-last-saved"));
+                        match session.saved {
+                            Some(saved) => ui.monospace(tr!(
+                                "session-ago",
+                                duration = Self::format_duration(saved.elapsed())
+                            )),
+                            None => ui.weak(tr!("session-not-saved")),
+                        };
+                        ui.end_row();
+                    });
+
+                ui.separator();
+                let threshold = self.show.timecode.late_cue;
+                ui.horizontal(|ui| {
+                    ui.label(tr!("session-cues-fired", count = session.fires.len()));
+                    let late = session.late_count(threshold);
+                    if late > 0 {
+                        ui.colored_label(LATE, tr!("session-late-count", count = late));
+                    }
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        egui::Grid::new("session_fires")
+                            .num_columns(6)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong(tr!("session-went-at"));
+                                ui.strong(tr!("session-cue"));
+                                ui.strong(tr!("session-programmed"));
+                                ui.strong(tr!("session-actual"));
+                                ui.strong(tr!("session-drift"));
+                                ui.strong(tr!("session-late-header"));
+                                ui.end_row();
+                                for fire in &session.fires {
+                                    ui.monospace(fire.at.format("%H:%M:%S").to_string());
+                                    ui.label(format!("{} {}", fire.number, fire.name));
+                                    ui.monospace(Self::format_duration(fire.programmed));
+                                    ui.monospace(Self::format_duration(fire.actual));
+                                    let drift = fire.drift();
+                                    let text =
+                                        egui::RichText::new(format!("{:+.1}s", drift)).monospace();
+                                    ui.label(if drift.abs() >= 1.0 {
+                                        text.color(egui::Color32::YELLOW)
+                                    } else {
+                                        text
+                                    });
+                                    match fire.late {
+                                        Some(late) if fire.is_late(threshold) => {
+                                            ui.colored_label(LATE, format_late(late));
+                                        }
+                                        _ => {
+                                            ui.label("");
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                if ui
+                    .button(tr!("clear"))
+                    .on_hover_text(tr!("session-clear-hint"))
+                    .clicked()
+                {
+                    self.session.clear();
+                }
+
+                ui.separator();
+                ui.label(tr!("session-report"));
+                ui.horizontal(|ui| {
+                    let previous = self.session_report_format;
+                    egui::ComboBox::from_id_salt("session_report_format")
+                        .selected_text(self.session_report_format.to_string())
+                        .show_ui(ui, |ui| {
+                            for format in ReportFormat::ALL {
+                                ui.selectable_value(
+                                    &mut self.session_report_format,
+                                    format,
+                                    format.to_string(),
+                                );
+                            }
+                        });
+                    if self.session_report_format != previous {
+                        self.session_report_path = Path::new(&self.session_report_path)
+                            .with_extension(self.session_report_format.extension())
+                            .to_string_lossy()
+                            .into_owned();
+                    }
+                    ui.text_edit_singleline(&mut self.session_report_path);
+                    if ui.button(tr!("report-export")).clicked() {
+                        let report = export::session_report(
+                            &self.show,
+                            &self.session,
+                            self.session_report_format,
+                        );
+                        self.session_report_status = Some(
+                            std::fs::write(&self.session_report_path, report)
+                                .map(|_| {
+                                    tr!("exported-to", path = self.session_report_path.as_str())
+                                })
+                                .map_err(|err| tr!("export-failed", error = err.to_string())),
+                        );
+                    }
+                });
+                match &self.session_report_status {
+                    Some(Ok(message)) => {
+                        ui.label(egui::RichText::new(message).color(egui::Color32::GREEN));
+                    }
+                    Some(Err(message)) => {
+                        ui.label(egui::RichText::new(message).color(egui::Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+        self.show_session = open;
+    }
+}
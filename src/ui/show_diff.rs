@@ -0,0 +1,270 @@
+use crate::backup;
+use crate::i18n::tr;
+use crate::show::Show;
+use crate::show_diff::{self, ChangeKind, CueChange, CueList, Side};
+use crate::timecode;
+use crate::HaloApp;
+use eframe::egui;
+use std::path::Path;
+
+const ADDED: egui::Color32 = egui::Color32::from_rgb(90, 200, 110);
+const REMOVED: egui::Color32 = egui::Color32::from_rgb(230, 90, 90);
+const CHANGED: egui::Color32 = egui::Color32::from_rgb(255, 180, 0);
+
+/// What this show is compared against.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum Reference {
+    /// The show as it was last saved or opened.
+    #[default]
+    Saved,
+    /// A show file, or one of this show's backups.
+    File,
+    /// This show as it is, for comparing one of its cue lists with another.
+    ThisShow,
+}
+
+/// The compare window's choices and the last comparison.
+#[derive(Default)]
+pub(crate) struct ShowDiff {
+    reference: Reference,
+    path: String,
+    before_list: CueList,
+    after_list: CueList,
+    /// The reference show, once read.
+    other: Option<Show>,
+    changes: Vec<CueChange>,
+    compared: bool,
+    error: Option<String>,
+}
+
+fn level(value: Option<u8>) -> String {
+    value.map_or_else(|| "–".to_string(), |value| value.to_string())
+}
+
+fn list_picker(ui: &mut egui::Ui, id: &str, show: &Show, list: &mut CueList) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(list.label(show))
+        .show_ui(ui, |ui| {
+            for option in CueList::all(show) {
+                ui.selectable_value(list, option, option.label(show));
+            }
+        });
+}
+
+fn draw_change(ui: &mut egui::Ui, i: usize, change: &CueChange) {
+    let (mark, color) = match change.kind {
+        ChangeKind::Added => ("+", ADDED),
+        ChangeKind::Removed => ("−", REMOVED),
+        ChangeKind::Changed => ("~", CHANGED),
+    };
+    let mut title = format!(
+        "{} {}",
+        mark,
+        tr!("cue-number", number = change.number.to_string())
+    );
+    if !change.name.is_empty() {
+        title = format!("{} {}", title, change.name);
+    }
+    if let Some(song) = &change.song {
+        title = format!("{} ({})", title, song);
+    }
+    egui::CollapsingHeader::new(egui::RichText::new(title).color(color))
+        .id_salt(("show_diff_change", i))
+        .default_open(change.kind == ChangeKind::Changed)
+        .show(ui, |ui| {
+            if let Some((before, after)) = &change.renamed {
+                ui.label(tr!(
+                    "diff-renamed",
+                    before = before.as_str(),
+                    after = after.as_str()
+                ));
+            }
+            if let Some((before, after)) = change.start {
+                ui.label(tr!(
+                    "diff-start",
+                    after = timecode::format(after),
+                    before = timecode::format(before)
+                ));
+            }
+            if let Some((before, after)) = change.duration {
+                ui.label(tr!(
+                    "diff-duration",
+                    after = format!("{:.1}", after.as_secs_f32()),
+                    before = format!("{:.1}", before.as_secs_f32())
+                ));
+            }
+            if !change.other.is_empty() {
+                ui.label(tr!("diff-also-changed", fields = change.other.join(", ")));
+            }
+            if change.values.is_empty() {
+                return;
+            }
+            egui::Grid::new(("show_diff_values", i))
+                .striped(true)
+                .num_columns(4)
+                .show(ui, |ui| {
+                    ui.strong(tr!("fixture"));
+                    ui.strong(tr!("attribute"));
+                    ui.strong(tr!("diff-before"));
+                    ui.strong(tr!("diff-after"));
+                    ui.end_row();
+                    for value in &change.values {
+                        ui.label(&value.fixture);
+                        ui.label(value.attribute.to_string());
+                        ui.monospace(level(value.before));
+                        ui.monospace(level(value.after));
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+impl HaloApp {
+    /// Reads the reference show, if it isn't this one, and compares.
+    fn compare_shows(&mut self) {
+        let state = &mut self.show_diff;
+        state.error = None;
+        let read = match state.reference {
+            Reference::Saved => {
+                Some(serde_json::from_str::<Show>(&self.saved_show).map_err(|err| err.to_string()))
+            }
+            Reference::File => {
+                Some(Show::load(Path::new(state.path.trim())).map_err(|err| err.to_string()))
+            }
+            Reference::ThisShow => None,
+        };
+        state.other = None;
+        match read {
+            Some(Ok(show)) => state.other = Some(show),
+            Some(Err(err)) => {
+                state.changes.clear();
+                state.compared = false;
+                state.error = Some(tr!("diff-read-failed", error = err));
+                return;
+            }
+            None => {}
+        }
+        let reference = state.other.as_ref().unwrap_or(&self.show);
+        state.changes = show_diff::diff(
+            &Side {
+                show: reference,
+                list: state.before_list,
+            },
+            &Side {
+                show: &self.show,
+                list: state.after_list,
+            },
+        );
+        state.compared = true;
+    }
+
+    /// Compares this show's cues with another version of them: what changed
+    /// since the last save, since a backup, or between two cue lists.
+    pub(crate) fn draw_show_diff_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_diff_window;
+        let mut compare = false;
+        egui::Window::new(tr!("menu-compare-shows"))
+            .id(egui::Id::new("show_diff"))
+            .open(&mut open)
+            .default_size([480.0, 440.0])
+            .show(ctx, |ui| {
+                let backups = self
+                    .show_path
+                    .as_deref()
+                    .map(backup::list)
+                    .unwrap_or_default();
+                let state = &mut self.show_diff;
+                egui::Grid::new("show_diff_sources")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("diff-compare-label"));
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(
+                                &mut state.reference,
+                                Reference::Saved,
+                                tr!("diff-last-save"),
+                            );
+                            ui.selectable_value(
+                                &mut state.reference,
+                                Reference::File,
+                                tr!("diff-show-file"),
+                            );
+                            ui.selectable_value(
+                                &mut state.reference,
+                                Reference::ThisShow,
+                                tr!("diff-this-show"),
+                            );
+                        });
+                        ui.end_row();
+                        if state.reference == Reference::File {
+                            ui.label(tr!("file-label"));
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut state.path)
+                                        .desired_width(260.0),
+                                );
+                                ui.add_enabled_ui(!backups.is_empty(), |ui| {
+                                    ui.menu_button(tr!("preferences-backups"), |ui| {
+                                        for backup in &backups {
+                                            let saved = backup.saved_at.format("%Y-%m-%d %H:%M");
+                                            if ui.button(saved.to_string()).clicked() {
+                                                state.path = backup.path.display().to_string();
+                                                ui.close_menu();
+                                            }
+                                        }
+                                    });
+                                });
+                            });
+                            ui.end_row();
+                        }
+                        ui.label(tr!("diff-cues-label"));
+                        let reference = match state.reference {
+                            Reference::ThisShow => Some(&self.show),
+                            _ => state.other.as_ref(),
+                        };
+                        match reference {
+                            Some(show) => {
+                                list_picker(ui, "show_diff_before", show, &mut state.before_list)
+                            }
+                            None => {
+                                ui.weak(tr!("diff-all-cues"));
+                            }
+                        }
+                        ui.end_row();
+                        ui.label(tr!("diff-with-this"));
+                        list_picker(ui, "show_diff_after", &self.show, &mut state.after_list);
+                        ui.end_row();
+                    });
+                compare = ui.button(tr!("diff-compare")).clicked();
+                if let Some(error) = &state.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                ui.separator();
+                if !state.compared {
+                    ui.weak(tr!("diff-pick"));
+                    return;
+                }
+                let (added, removed, changed) = show_diff::counts(&state.changes);
+                if state.changes.is_empty() {
+                    ui.label(tr!("diff-none"));
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    ui.colored_label(ADDED, tr!("diff-added", count = added));
+                    ui.colored_label(REMOVED, tr!("diff-removed", count = removed));
+                    ui.colored_label(CHANGED, tr!("diff-changed", count = changed));
+                });
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        for (i, change) in state.changes.iter().enumerate() {
+                            draw_change(ui, i, change);
+                        }
+                    });
+            });
+        self.show_diff_window = open;
+        if compare {
+            self.compare_shows();
+        }
+    }
+}
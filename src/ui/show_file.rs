@@ -0,0 +1,409 @@
+use crate::backup;
+use crate::error::HaloError;
+use crate::fixture;
+use crate::i18n::{self, tr};
+use crate::log::LogLevel;
+use crate::package;
+use crate::schema;
+use crate::show::Show;
+use crate::show_lock::ShowLock;
+use crate::HaloApp;
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum FileAction {
+    Load,
+    Save,
+    SavePackage,
+}
+
+pub(crate) struct FileDialog {
+    action: FileAction,
+    path: String,
+    error: Option<String>,
+    /// The file format to save as; older ones are for an older Halo.
+    version: u32,
+}
+
+impl FileDialog {
+    pub(crate) fn new(action: FileAction, current: Option<&Path>) -> Self {
+        let mut path = current
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        if action == FileAction::SavePackage {
+            path = Path::new(&path)
+                .with_extension(package::EXTENSION)
+                .display()
+                .to_string();
+        }
+        Self {
+            action,
+            path,
+            error: None,
+            version: schema::CURRENT,
+        }
+    }
+}
+
+impl HaloApp {
+    /// Saves to the current show file, or asks for a path if there is none yet.
+    pub(crate) fn save_show(&mut self) {
+        match self.show_path.clone() {
+            Some(path) => {
+                if let Err(err) = self.write_show(&path) {
+                    let mut dialog = FileDialog::new(FileAction::Save, Some(&path));
+                    dialog.error = Some(err.to_string());
+                    self.file_dialog = Some(dialog);
+                }
+            }
+            None => self.file_dialog = Some(FileDialog::new(FileAction::Save, None)),
+        }
+    }
+
+    fn write_show(&mut self, path: &Path) -> std::io::Result<()> {
+        let is_package = path
+            .extension()
+            .is_some_and(|ext| ext == package::EXTENSION);
+        if is_package {
+            package::save(&self.show, path)?;
+        } else {
+            self.show.save(path)?;
+        }
+        // A failed backup shouldn't fail the save itself.
+        let keep = self.preferences.backup_count;
+        if keep > 0 {
+            if let Err(err) = backup::create(path, keep) {
+                self.notify(
+                    LogLevel::Warning,
+                    tr!("show-backup-failed", error = err.to_string()),
+                );
+            }
+        }
+        self.saved_show = self.show.snapshot();
+        self.session.saved = Some(Instant::now());
+        self.notify(
+            LogLevel::Info,
+            tr!("show-saved", path = path.display().to_string()),
+        );
+        Ok(())
+    }
+
+    /// Saves a copy of the show in an older file format, leaving the show
+    /// file as it is.
+    fn export_show(&mut self, path: &Path, version: u32) -> std::io::Result<()> {
+        self.show.save_as_version(path, version)?;
+        self.notify(
+            LogLevel::Info,
+            tr!(
+                "show-saved-copy",
+                version = version,
+                path = path.display().to_string()
+            ),
+        );
+        Ok(())
+    }
+
+    /// Makes `path`, just opened, the file Save writes to, unless the show
+    /// came from a newer Halo: writing over it would lose what this one
+    /// doesn't understand, so Save asks where to put it instead.
+    pub(crate) fn set_show_path(&mut self, path: PathBuf) {
+        self.show_path = self.newer_show.is_none().then_some(path);
+    }
+
+    pub(crate) fn open_show(&mut self, path: &Path) -> std::io::Result<()> {
+        let (show, version) = Show::load_versioned(path)?;
+        self.show = show;
+        self.newer_show = (version > schema::CURRENT).then_some(version);
+        if self.newer_show.is_some() {
+            // Reverting to a backup leaves the path alone, so it goes here
+            // too; see `set_show_path`.
+            self.show_path = None;
+            self.log.warn(tr!(
+                "show-newer-warning",
+                path = path.display().to_string(),
+                version = version
+            ));
+        }
+        self.saved_show = self.show.snapshot();
+        self.session.clear();
+        self.session.saved = Some(Instant::now());
+        self.profile_library = fixture::profile_library();
+        self.selected_cue = None;
+        self.selected_pixel_map = None;
+        self.diff_before = None;
+        self.diff_after = None;
+        self.setlist_mode = false;
+        self.current_song = 0;
+        self.load_pixel_media();
+        self.reset();
+        self.enter_locked = self.show.lock.is_some();
+        // The new show takes over from a standstill, released like any stop.
+        self.stop_playback();
+        self.release_output();
+        Ok(())
+    }
+
+    pub(crate) fn draw_file_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = &mut self.file_dialog else {
+            return;
+        };
+
+        let title = match dialog.action {
+            FileAction::Load => tr!("show-load-title"),
+            FileAction::Save => tr!("show-save-as-title"),
+            FileAction::SavePackage => tr!("show-save-package-title"),
+        };
+
+        let mut open = true;
+        let mut confirmed = false;
+        egui::Window::new(title)
+            .id(egui::Id::new("file_dialog"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("path-label"));
+                    let response =
+                        ui.add(egui::TextEdit::singleline(&mut dialog.path).desired_width(320.0));
+                    confirmed =
+                        response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                });
+                if dialog.action == FileAction::Save {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("show-format-label"));
+                        let name = |version: u32| {
+                            if version == schema::CURRENT {
+                                tr!("show-format-this", version = version)
+                            } else {
+                                tr!("show-format-older", version = version)
+                            }
+                        };
+                        egui::ComboBox::from_id_salt("show_format")
+                            .selected_text(name(dialog.version))
+                            .show_ui(ui, |ui| {
+                                for (version, change) in schema::versions() {
+                                    ui.selectable_value(
+                                        &mut dialog.version,
+                                        version,
+                                        name(version),
+                                    )
+                                    .on_hover_text(i18n::translate(change, None));
+                                }
+                            });
+                    });
+                    if dialog.version != schema::CURRENT {
+                        ui.label(tr!("show-format-older-hint"));
+                    }
+                }
+                if dialog.action == FileAction::SavePackage {
+                    ui.label(tr!("show-package-hint", extension = package::EXTENSION));
+                }
+                if let Some(error) = &dialog.error {
+                    ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                }
+                confirmed |= ui
+                    .button(if dialog.action == FileAction::Load {
+                        tr!("load")
+                    } else {
+                        tr!("save")
+                    })
+                    .clicked();
+            });
+
+        if !open {
+            self.file_dialog = None;
+            return;
+        }
+        if !confirmed || dialog.path.trim().is_empty() {
+            return;
+        }
+
+        let action = dialog.action;
+        let version = dialog.version;
+        let mut path = PathBuf::from(dialog.path.trim());
+        if action == FileAction::SavePackage {
+            path.set_extension(package::EXTENSION);
+        }
+        if version != schema::CURRENT {
+            match self.export_show(&path, version) {
+                Ok(()) => self.file_dialog = None,
+                Err(err) => {
+                    if let Some(dialog) = &mut self.file_dialog {
+                        dialog.error = Some(err.to_string());
+                    }
+                }
+            }
+            return;
+        }
+        let result = match action {
+            FileAction::Load => self.open_show(&path),
+            FileAction::Save | FileAction::SavePackage => self.write_show(&path),
+        };
+        match result {
+            Ok(()) => {
+                if action == FileAction::Load {
+                    self.set_show_path(path);
+                } else {
+                    self.show_path = Some(path);
+                }
+                self.file_dialog = None;
+            }
+            Err(err) if action == FileAction::Load => {
+                self.file_dialog = None;
+                self.report_error(HaloError::show_file(path, err));
+            }
+            Err(err) => {
+                if let Some(dialog) = &mut self.file_dialog {
+                    dialog.error = Some(err.to_string());
+                }
+            }
+        }
+    }
+
+    /// Opening a show from a newer Halo: it loaded as far as this one
+    /// understands it, and saving over it would lose the rest.
+    pub(crate) fn draw_newer_show_prompt(&mut self, ctx: &egui::Context) {
+        let Some(version) = self.newer_show else {
+            return;
+        };
+        let mut keep = false;
+        let mut save_copy = false;
+        egui::Window::new(tr!("show-newer-title"))
+            .id(egui::Id::new("newer_show"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(tr!(
+                    "show-newer-body",
+                    version = version,
+                    current = schema::CURRENT
+                ));
+                ui.label(tr!("show-newer-advice"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    save_copy = ui.button(tr!("show-save-copy")).clicked();
+                    keep = ui.button(tr!("show-keep-working")).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape));
+                });
+            });
+        if save_copy {
+            self.newer_show = None;
+            self.file_dialog = Some(FileDialog::new(FileAction::Save, None));
+        } else if keep {
+            self.newer_show = None;
+        }
+    }
+
+    pub(crate) fn draw_show_properties(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr!("show-properties-title"))
+            .id(egui::Id::new("show_properties"))
+            .open(&mut self.show_properties)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("show_properties_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("name-label"));
+                        ui.text_edit_singleline(&mut self.show.name);
+                        ui.end_row();
+
+                        ui.label(tr!("show-audio-file"));
+                        draw_audio_file(ui, &mut self.show.audio_file, &mut self.show.audio_trim);
+                        ui.end_row();
+
+                        ui.label(tr!("file-label"));
+                        ui.label(
+                            self.show_path
+                                .as_ref()
+                                .map(|path| path.display().to_string())
+                                .unwrap_or_else(|| tr!("show-not-saved")),
+                        );
+                        ui.end_row();
+
+                        ui.label(tr!("show-back-fade"));
+                        let mut fade = self.show.back_fade.as_secs_f32();
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut fade)
+                                    .range(0.0..=30.0)
+                                    .speed(0.1)
+                                    .suffix(tr!("unit-seconds")),
+                            )
+                            .on_hover_text(tr!("show-back-fade-hint"))
+                            .changed()
+                        {
+                            self.show.back_fade = Duration::from_secs_f32(fade);
+                        }
+                        ui.end_row();
+                    });
+
+                ui.separator();
+                ui.strong(tr!("show-lock"));
+                if self.show.lock.is_some() {
+                    ui.label(tr!("show-locked-hint"));
+                    if ui.button(tr!("show-remove-lock")).clicked() {
+                        self.show.lock = None;
+                        self.log.info(tr!("show-lock-removed"));
+                    }
+                    return;
+                }
+                ui.label(tr!("show-lock-hint"));
+                egui::Grid::new("show_lock_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("show-passcode"));
+                        ui.add(egui::TextEdit::singleline(&mut self.lock_passcode).password(true));
+                        ui.end_row();
+
+                        ui.label(tr!("show-passcode-again"));
+                        ui.add(egui::TextEdit::singleline(&mut self.lock_confirm).password(true));
+                        ui.end_row();
+                    });
+                let mismatch = self.lock_passcode != self.lock_confirm;
+                if mismatch && !self.lock_confirm.is_empty() {
+                    ui.label(
+                        egui::RichText::new(tr!("show-passcode-mismatch"))
+                            .color(egui::Color32::RED),
+                    );
+                }
+                let ready = !self.lock_passcode.is_empty() && !mismatch;
+                if ui
+                    .add_enabled(ready, egui::Button::new(tr!("show-lock-button")))
+                    .on_hover_text(tr!("show-lock-button-hint"))
+                    .clicked()
+                {
+                    self.show.lock = Some(ShowLock::new(&self.lock_passcode));
+                    self.lock_passcode.clear();
+                    self.lock_confirm.clear();
+                    self.enter_locked = true;
+                    self.log.info(tr!("show-locked"));
+                }
+            });
+    }
+}
+
+/// An audio file path with its gain trim.
+pub(crate) fn draw_audio_file(ui: &mut egui::Ui, file: &mut Option<PathBuf>, trim: &mut f32) {
+    ui.horizontal(|ui| {
+        let mut audio = file
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        if ui.text_edit_singleline(&mut audio).changed() {
+            *file = (!audio.trim().is_empty()).then(|| PathBuf::from(audio.trim()));
+        }
+        ui.add_enabled(
+            file.is_some(),
+            egui::DragValue::new(trim)
+                .range(-24.0..=12.0)
+                .speed(0.1)
+                .max_decimals(1)
+                .suffix(tr!("unit-db")),
+        )
+        .on_hover_text(tr!("gain-trim"));
+    });
+}
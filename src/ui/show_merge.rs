@@ -0,0 +1,228 @@
+use crate::fixture::{self, FixtureProfile};
+use crate::i18n::tr;
+use crate::show::Show;
+use crate::show_merge::{self, Conflict, MergeSelection};
+use crate::HaloApp;
+use eframe::egui;
+use std::path::Path;
+
+/// The show being merged from and what is picked from it.
+pub(crate) struct ShowMerge {
+    path: String,
+    other: Option<Show>,
+    selection: MergeSelection,
+    /// Fixture profiles to add to the library, by name.
+    profiles: Vec<String>,
+    conflict: Conflict,
+    error: Option<String>,
+}
+
+impl Default for ShowMerge {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            other: None,
+            selection: MergeSelection::default(),
+            profiles: Vec::new(),
+            conflict: Conflict::KeepBoth,
+            error: None,
+        }
+    }
+}
+
+/// A checkbox adding `item` to or taking it out of `picked`.
+fn pick<T: PartialEq + Copy>(ui: &mut egui::Ui, picked: &mut Vec<T>, item: T, label: String) {
+    let mut on = picked.contains(&item);
+    if ui.checkbox(&mut on, label).changed() {
+        if on {
+            picked.push(item);
+        } else {
+            picked.retain(|other| *other != item);
+        }
+    }
+}
+
+/// The profiles patched in `show` that aren't built in, once each.
+fn custom_profiles(show: &Show) -> Vec<&FixtureProfile> {
+    let mut profiles: Vec<&FixtureProfile> = Vec::new();
+    for fixture in &show.fixtures {
+        if !fixture.profile.is_builtin() && !profiles.iter().any(|p| p.name == fixture.profile.name)
+        {
+            profiles.push(&fixture.profile);
+        }
+    }
+    profiles
+}
+
+impl HaloApp {
+    /// Reads another show and merges the songs, cues, masters, macros,
+    /// palette colours and fixture profiles picked from it into this one.
+    pub(crate) fn draw_show_merge_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_merge_window;
+        let mut merge = false;
+        let state = &mut self.show_merge;
+        egui::Window::new(tr!("merge-title"))
+            .id(egui::Id::new("show_merge"))
+            .open(&mut open)
+            .default_width(440.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("merge-show-file"));
+                    ui.text_edit_singleline(&mut state.path);
+                    if ui.button(tr!("daw-read")).clicked() {
+                        state.selection = MergeSelection::default();
+                        state.profiles.clear();
+                        match Show::load(Path::new(state.path.trim())) {
+                            Ok(show) => {
+                                state.other = Some(show);
+                                state.error = None;
+                            }
+                            Err(err) => {
+                                state.other = None;
+                                state.error =
+                                    Some(tr!("diff-read-failed", error = err.to_string()));
+                            }
+                        }
+                    }
+                });
+                if let Some(error) = &state.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                let Some(other) = &state.other else {
+                    return;
+                };
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        let selection = &mut state.selection;
+                        let loose = other.cues.iter().filter(|cue| cue.song.is_none()).count();
+                        if !other.songs.is_empty() || loose > 0 {
+                            ui.strong(tr!("merge-cue-lists"));
+                        }
+                        for song in &other.songs {
+                            let cues = other
+                                .cues
+                                .iter()
+                                .filter(|cue| cue.song == Some(song.id))
+                                .count();
+                            pick(
+                                ui,
+                                &mut selection.songs,
+                                song.id,
+                                tr!("merge-song-cues", song = song.name.as_str(), count = cues),
+                            );
+                        }
+                        if loose > 0 {
+                            ui.checkbox(
+                                &mut selection.loose_cues,
+                                tr!("merge-loose-cues", count = loose),
+                            );
+                        }
+                        if !other.group_masters.is_empty() {
+                            ui.strong(tr!("merge-group-masters-header"));
+                        }
+                        for (index, master) in other.group_masters.iter().enumerate() {
+                            pick(
+                                ui,
+                                &mut selection.group_masters,
+                                index,
+                                format!("{} ({})", master.name, master.groups.join(", ")),
+                            );
+                        }
+                        if !other.macros.is_empty() {
+                            ui.strong(tr!("menu-macros"));
+                        }
+                        for (index, recorded) in other.macros.iter().enumerate() {
+                            pick(ui, &mut selection.macros, index, recorded.name.clone());
+                        }
+                        ui.strong(tr!("merge-palette"));
+                        ui.checkbox(
+                            &mut selection.palette,
+                            tr!("merge-busk-colours", count = other.busk.palette.len()),
+                        );
+                        let profiles = custom_profiles(other);
+                        if !profiles.is_empty() {
+                            ui.strong(tr!("merge-profiles"));
+                        }
+                        for profile in profiles {
+                            let mut on = state.profiles.contains(&profile.name);
+                            if ui.checkbox(&mut on, &profile.name).changed() {
+                                if on {
+                                    state.profiles.push(profile.name.clone());
+                                } else {
+                                    state.profiles.retain(|name| *name != profile.name);
+                                }
+                            }
+                        }
+                    });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(tr!("merge-conflict"));
+                    egui::ComboBox::from_id_salt("merge_conflict")
+                        .selected_text(state.conflict.to_string())
+                        .show_ui(ui, |ui| {
+                            for conflict in Conflict::ALL {
+                                ui.selectable_value(
+                                    &mut state.conflict,
+                                    conflict,
+                                    conflict.to_string(),
+                                );
+                            }
+                        });
+                })
+                .response
+                .on_hover_text(tr!("merge-conflict-hint"));
+                ui.label(tr!("merge-fixtures-hint"));
+                merge = ui.button(tr!("merge-button")).clicked();
+            });
+        self.show_merge_window = open;
+
+        if merge {
+            self.merge_show();
+        }
+    }
+
+    fn merge_show(&mut self) {
+        let state = &mut self.show_merge;
+        let Some(other) = &state.other else {
+            return;
+        };
+        let mut report = self
+            .show
+            .merge(other, &state.selection, state.conflict)
+            .to_string();
+        let mut profiles = 0;
+        for profile in custom_profiles(other)
+            .into_iter()
+            .filter(|profile| state.profiles.contains(&profile.name))
+        {
+            let taken = |name: &str| self.profile_library.iter().any(|p| p.name == name);
+            let mut profile = profile.clone();
+            match state.conflict {
+                _ if !taken(&profile.name) => {}
+                Conflict::Skip => continue,
+                Conflict::Replace => {}
+                Conflict::KeepBoth => {
+                    profile.name = show_merge::unique_name(&profile.name, taken);
+                }
+            }
+            match fixture::install_profile(&profile) {
+                Ok(()) => profiles += 1,
+                Err(err) => {
+                    state.error = Some(tr!(
+                        "merge-profile-failed",
+                        profile = profile.name.as_str(),
+                        error = err.to_string()
+                    ))
+                }
+            }
+        }
+        if profiles > 0 {
+            report.push_str(&tr!("merge-profiles-added", count = profiles));
+            self.profile_library = fixture::profile_library();
+        }
+        self.log
+            .info(tr!("merge-done", path = state.path.trim(), report = report));
+    }
+}
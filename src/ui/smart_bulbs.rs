@@ -0,0 +1,178 @@
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::smart_bulb::{self, BulbDevice, SmartBulb, WLED_MAX_LEDS};
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+use std::sync::mpsc;
+use std::thread;
+
+impl HaloApp {
+    pub(crate) fn draw_smart_bulbs_window(&mut self, ctx: &egui::Context) {
+        self.check_hue_pairing();
+        let mut open = self.show_smart_bulbs;
+        let mut pair = false;
+        let standby = self.holds_output_for_main();
+        egui::Window::new(tr!("bulbs-title"))
+            .id(egui::Id::new("smart_bulbs"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let settings = &mut self.show.smart_bulbs;
+                ui.checkbox(&mut settings.enabled, tr!("bulbs-enable"));
+                if settings.enabled && standby {
+                    ui.weak(tr!("standing-by"));
+                }
+                if let Some(error) = &self.smart_bulb_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                ui.separator();
+
+                ui.strong(tr!("bulbs-hue-bridge"));
+                egui::Grid::new("hue_bridge").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("address-label"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut settings.hue_bridge)
+                            .hint_text("192.168.1.2"),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr!("bulbs-paired"));
+                    ui.horizontal(|ui| {
+                        if settings.hue_username.is_empty() {
+                            ui.weak(tr!("no"));
+                        } else {
+                            ui.label(tr!("yes"));
+                        }
+                        if self.hue_pairing.is_some() {
+                            ui.spinner();
+                        } else if ui
+                            .add_enabled(
+                                !settings.hue_bridge.is_empty(),
+                                egui::Button::new(tr!("bulbs-pair")),
+                            )
+                            .on_hover_text(tr!("bulbs-pair-hint"))
+                            .clicked()
+                        {
+                            pair = true;
+                        }
+                    });
+                    ui.end_row();
+                });
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.strong(tr!("bulbs-bulbs"));
+                    if accessibility::named(ui.small_button("+"), tr!("bulbs-add"))
+                        .on_hover_text(tr!("bulbs-add"))
+                        .clicked()
+                    {
+                        let fixture = self.show.fixtures.first().map_or(0, |f| f.id);
+                        settings.bulbs.push(SmartBulb {
+                            fixture,
+                            device: BulbDevice::kinds()[0].clone(),
+                        });
+                    }
+                });
+                if settings.bulbs.is_empty() {
+                    ui.label(tr!("bulbs-empty"));
+                }
+                let mut remove = None;
+                for (i, bulb) in settings.bulbs.iter_mut().enumerate() {
+                    ui.push_id(i, |ui| {
+                        ui.horizontal(|ui| {
+                            let name = self
+                                .show
+                                .fixtures
+                                .iter()
+                                .find(|fixture| fixture.id == bulb.fixture)
+                                .map_or_else(
+                                    || tr!("bulbs-missing"),
+                                    |fixture| fixture.name.clone(),
+                                );
+                            egui::ComboBox::from_id_salt("fixture")
+                                .selected_text(name)
+                                .width(110.0)
+                                .show_ui(ui, |ui| {
+                                    for fixture in &self.show.fixtures {
+                                        ui.selectable_value(
+                                            &mut bulb.fixture,
+                                            fixture.id,
+                                            &fixture.name,
+                                        );
+                                    }
+                                });
+                            egui::ComboBox::from_id_salt("kind")
+                                .selected_text(bulb.device.kind())
+                                .width(60.0)
+                                .show_ui(ui, |ui| {
+                                    for kind in BulbDevice::kinds() {
+                                        let selected = kind.kind() == bulb.device.kind();
+                                        if ui.selectable_label(selected, kind.kind()).clicked()
+                                            && !selected
+                                        {
+                                            bulb.device = kind;
+                                        }
+                                    }
+                                });
+                            match &mut bulb.device {
+                                BulbDevice::Hue { light } => {
+                                    ui.label(tr!("bulbs-light"));
+                                    ui.add(egui::DragValue::new(light).range(1..=63));
+                                }
+                                BulbDevice::Wled { host, leds } => {
+                                    ui.add(
+                                        egui::TextEdit::singleline(host)
+                                            .hint_text("host")
+                                            .desired_width(100.0),
+                                    );
+                                    ui.label(tr!("bulbs-leds"));
+                                    ui.add(egui::DragValue::new(leds).range(1..=WLED_MAX_LEDS));
+                                }
+                            }
+                            if accessibility::named(ui.small_button("🗑"), tr!("remove"))
+                                .on_hover_text(tr!("remove"))
+                                .clicked()
+                            {
+                                remove = Some(i);
+                            }
+                        });
+                    });
+                }
+                if let Some(i) = remove {
+                    settings.bulbs.remove(i);
+                }
+            });
+        self.show_smart_bulbs = open;
+
+        if pair {
+            let bridge = self.show.smart_bulbs.hue_bridge.clone();
+            let (sender, receiver) = mpsc::channel();
+            let ctx = ctx.clone();
+            thread::spawn(move || {
+                let _ = sender.send(smart_bulb::pair_hue(&bridge).map_err(|err| err.to_string()));
+                ctx.request_repaint();
+            });
+            self.hue_pairing = Some(receiver);
+        }
+    }
+
+    /// Picks up the answer to a pairing request, if it has come.
+    fn check_hue_pairing(&mut self) {
+        let Some(result) = self
+            .hue_pairing
+            .as_ref()
+            .and_then(|receiver| receiver.try_recv().ok())
+        else {
+            return;
+        };
+        self.hue_pairing = None;
+        match result {
+            Ok(username) => {
+                self.show.smart_bulbs.hue_username = username;
+                self.log.info(tr!("bulbs-paired-done"));
+            }
+            Err(err) => self.notify(LogLevel::Error, tr!("bulbs-pair-failed", error = err)),
+        }
+    }
+}
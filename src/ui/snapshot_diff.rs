@@ -0,0 +1,137 @@
+use crate::i18n::tr;
+use crate::snapshot::{self, Snapshot};
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    pub(crate) fn draw_snapshot_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_snapshots;
+        egui::Window::new(tr!("snapshots-title"))
+            .id(egui::Id::new("snapshots"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("name-label"));
+                    ui.text_edit_singleline(&mut self.snapshot_name);
+                    if ui.button(tr!("snapshots-capture")).clicked() {
+                        let name = if self.snapshot_name.trim().is_empty() {
+                            tr!(
+                                "snapshots-default-name",
+                                number = self.show.snapshots.len() + 1
+                            )
+                        } else {
+                            self.snapshot_name.trim().to_string()
+                        };
+                        self.show
+                            .snapshots
+                            .push(Snapshot::capture(&name, &self.output));
+                        self.snapshot_name.clear();
+                    }
+                });
+
+                ui.separator();
+
+                let label = |index: Option<usize>| {
+                    index
+                        .and_then(|i| self.show.snapshots.get(i))
+                        .map(|s| format!("{} ({})", s.name, s.captured_at.format("%H:%M:%S")))
+                        .unwrap_or_else(|| tr!("select"))
+                };
+
+                let mut before = self.diff_before;
+                let mut after = self.diff_after;
+                let mut delete = None;
+                egui::Grid::new("snapshot_pick").show(ui, |ui| {
+                    for (id, title, selection) in [
+                        (
+                            "snapshot_reference",
+                            tr!("snapshots-reference-label"),
+                            &mut before,
+                        ),
+                        ("snapshot_compare", tr!("diff-compare-label"), &mut after),
+                    ] {
+                        ui.label(title);
+                        egui::ComboBox::from_id_salt(id)
+                            .selected_text(label(*selection))
+                            .width(260.0)
+                            .show_ui(ui, |ui| {
+                                for (i, snapshot) in self.show.snapshots.iter().enumerate() {
+                                    ui.selectable_value(selection, Some(i), &snapshot.name);
+                                }
+                            });
+                        if ui
+                            .add_enabled(selection.is_some(), egui::Button::new(tr!("delete")))
+                            .on_hover_text(tr!("snapshots-delete-hint"))
+                            .clicked()
+                        {
+                            delete = *selection;
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(index) = delete.filter(|&i| i < self.show.snapshots.len()) {
+                    self.show.snapshots.remove(index);
+                    // The picks after the deleted one move up with it.
+                    for selection in [&mut before, &mut after] {
+                        *selection = match *selection {
+                            Some(i) if i == index => None,
+                            Some(i) if i > index => Some(i - 1),
+                            other => other,
+                        };
+                    }
+                }
+                self.diff_before = before;
+                self.diff_after = after;
+
+                let (Some(before), Some(after)) = (
+                    before.and_then(|i| self.show.snapshots.get(i)),
+                    after.and_then(|i| self.show.snapshots.get(i)),
+                ) else {
+                    return;
+                };
+
+                let diffs = snapshot::diff(&before.frame, &after.frame, &self.show.fixtures);
+                ui.separator();
+                if diffs.is_empty() {
+                    ui.label(
+                        egui::RichText::new(tr!("snapshots-identical")).color(egui::Color32::GREEN),
+                    );
+                    return;
+                }
+
+                ui.label(tr!("snapshots-differ", count = diffs.len()));
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("snapshot_diff")
+                            .striped(true)
+                            .num_columns(4)
+                            .show(ui, |ui| {
+                                ui.strong(tr!("address"));
+                                ui.strong(tr!("snapshots-patched"));
+                                ui.strong(tr!("snapshots-reference"));
+                                ui.strong(tr!("diff-compare"));
+                                ui.end_row();
+
+                                for diff in &diffs {
+                                    ui.monospace(format!("{}.{:03}", diff.universe, diff.channel));
+                                    match &diff.patched {
+                                        Some((name, attribute)) => {
+                                            ui.label(format!("{} {}", name, attribute))
+                                        }
+                                        None => ui.label(
+                                            egui::RichText::new(tr!("snapshots-unpatched"))
+                                                .color(egui::Color32::YELLOW),
+                                        ),
+                                    };
+                                    ui.monospace(diff.before.to_string());
+                                    ui.monospace(diff.after.to_string());
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        self.show_snapshots = open;
+    }
+}
@@ -0,0 +1,267 @@
+use crate::cue::CueNumber;
+use crate::end_action::EndAction;
+use crate::i18n::tr;
+use crate::macros::MacroAction;
+use crate::song::{self, Song};
+use crate::ui::accessibility;
+use crate::ui::show_file::draw_audio_file;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+/// Length given to a newly added song.
+const DEFAULT_SONG_LENGTH: Duration = Duration::from_secs(180);
+
+/// Edits a duration as whole and fractional seconds.
+fn seconds(ui: &mut egui::Ui, duration: &mut Duration) -> egui::Response {
+    let mut secs = duration.as_secs_f32();
+    let response = ui.add(
+        egui::DragValue::new(&mut secs)
+            .speed(0.5)
+            .range(0.0..=86_400.0)
+            .suffix(tr!("unit-seconds"))
+            .max_decimals(1),
+    );
+    if response.changed() {
+        *duration = Duration::from_secs_f32(secs);
+    }
+    response
+}
+
+/// Picks what happens at the end of the list or a song. `cues` are the ones
+/// a loop can go back to and `songs` the ones a chain can go on to, by id.
+fn end_action_picker(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    action: &mut EndAction,
+    cues: &[(CueNumber, String)],
+    songs: &[(u32, String)],
+) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt((&id_salt, "kind"))
+            .selected_text(action.to_string())
+            .show_ui(ui, |ui| {
+                for kind in EndAction::kinds(songs.first().map(|(id, _)| *id)) {
+                    if ui
+                        .selectable_label(action.same_kind(&kind), kind.to_string())
+                        .clicked()
+                        && !action.same_kind(&kind)
+                    {
+                        *action = kind;
+                    }
+                }
+            });
+        match action {
+            EndAction::Loop(target) => {
+                let selected = target
+                    .and_then(|number| cues.iter().find(|(n, _)| *n == number))
+                    .map_or_else(|| tr!("songs-the-start"), |(n, name)| format!("{n} {name}"));
+                egui::ComboBox::from_id_salt((&id_salt, "cue"))
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(target, None, tr!("songs-the-start"));
+                        for (number, name) in cues {
+                            ui.selectable_value(target, Some(*number), format!("{number} {name}"));
+                        }
+                    });
+            }
+            EndAction::Chain(target) => {
+                let selected = songs
+                    .iter()
+                    .find(|(id, _)| id == target)
+                    .map_or_else(|| tr!("songs-removed"), |(_, name)| name.clone());
+                egui::ComboBox::from_id_salt((&id_salt, "song"))
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        for (id, name) in songs {
+                            ui.selectable_value(target, *id, name);
+                        }
+                    });
+            }
+            EndAction::Continue | EndAction::Stop => {}
+        }
+    });
+}
+
+impl HaloApp {
+    /// The set: songs in running order with their start and length. Moving a
+    /// song lays the set out again back to back.
+    pub(crate) fn draw_songs_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_songs;
+        let mut move_song = None;
+        let mut remove = None;
+        egui::Window::new(tr!("songs-title"))
+            .id(egui::Id::new("songs"))
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("songs-add")).clicked() {
+                        let start = self
+                            .show
+                            .songs
+                            .iter()
+                            .map(Song::end)
+                            .max()
+                            .unwrap_or_default();
+                        let id = song::next_id(&self.show.songs);
+                        self.show.songs.push(Song::new(
+                            id,
+                            &tr!("macro-song", number = self.show.songs.len() + 1),
+                            start,
+                            DEFAULT_SONG_LENGTH,
+                        ));
+                    }
+                    if ui
+                        .add_enabled(
+                            !self.show.songs.is_empty(),
+                            egui::Button::new(tr!("songs-close-gaps")),
+                        )
+                        .on_hover_text(tr!("songs-close-gaps-hint"))
+                        .clicked()
+                    {
+                        song::pack(&mut self.show.songs);
+                    }
+                });
+                ui.label(tr!("songs-cues-hint"));
+                let songs: Vec<(u32, String)> = self
+                    .show
+                    .songs
+                    .iter()
+                    .map(|song| (song.id, song.name.clone()))
+                    .collect();
+                ui.horizontal(|ui| {
+                    ui.label(tr!("songs-cue-list-end"));
+                    let cues: Vec<(CueNumber, String)> = self
+                        .show
+                        .cues
+                        .iter()
+                        .map(|cue| (cue.number, cue.name.clone()))
+                        .collect();
+                    end_action_picker(
+                        ui,
+                        "show_end_action",
+                        &mut self.show.end_action,
+                        &cues,
+                        &songs,
+                    );
+                });
+                ui.separator();
+
+                if self.show.songs.is_empty() {
+                    ui.label(tr!("songs-empty"));
+                    return;
+                }
+                let count = self.show.songs.len();
+                egui::Grid::new("songs_grid")
+                    .num_columns(8)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr!("name"));
+                        ui.strong(tr!("transport-start"));
+                        ui.strong(tr!("length"));
+                        ui.strong(tr!("menu-cues"));
+                        ui.strong(tr!("songs-at-end"))
+                            .on_hover_text(tr!("songs-at-end-hint"));
+                        ui.strong(tr!("preferences-audio"))
+                            .on_hover_text(tr!("songs-audio-hint"));
+                        ui.label("");
+                        ui.label("");
+                        ui.end_row();
+
+                        for (i, song) in self.show.songs.iter_mut().enumerate() {
+                            ui.text_edit_singleline(&mut song.name);
+                            seconds(ui, &mut song.start_time);
+                            seconds(ui, &mut song.length);
+                            let cues: Vec<(CueNumber, String)> = self
+                                .show
+                                .cues
+                                .iter()
+                                .filter(|cue| cue.song == Some(song.id))
+                                .map(|cue| (cue.number, cue.name.clone()))
+                                .collect();
+                            ui.label(cues.len().to_string());
+                            end_action_picker(
+                                ui,
+                                ("song_end_action", song.id),
+                                &mut song.end_action,
+                                &cues,
+                                &songs,
+                            );
+                            draw_audio_file(ui, &mut song.audio_file, &mut song.audio_trim);
+                            ui.horizontal(|ui| {
+                                if accessibility::named(
+                                    ui.add_enabled(i > 0, egui::Button::new("⬆")),
+                                    tr!("move-up"),
+                                )
+                                .clicked()
+                                {
+                                    move_song = Some((i, i - 1));
+                                }
+                                if accessibility::named(
+                                    ui.add_enabled(i + 1 < count, egui::Button::new("⬇")),
+                                    tr!("move-down"),
+                                )
+                                .clicked()
+                                {
+                                    move_song = Some((i, i + 1));
+                                }
+                            });
+                            if accessibility::named(ui.button("🗑"), tr!("songs-remove"))
+                                .on_hover_text(tr!("songs-remove"))
+                                .clicked()
+                            {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if let Some((from, to)) = move_song {
+            song::move_song(&mut self.show.songs, from, to);
+        }
+        if let Some(index) = remove {
+            self.show.remove_song(index);
+        }
+        self.show_songs = open;
+    }
+
+    /// The setlist toggle and, in setlist mode, the current song with buttons
+    /// to move between songs.
+    pub(crate) fn draw_setlist_controls(&mut self, ui: &mut egui::Ui) {
+        let mut setlist_mode = self.setlist_mode;
+        let toggle = ui.add_enabled(
+            !self.show.songs.is_empty(),
+            egui::Checkbox::new(&mut setlist_mode, tr!("songs-setlist")),
+        );
+        if toggle.changed() {
+            self.set_setlist_mode(setlist_mode);
+        }
+        let Some(label) = self.setlist_label() else {
+            return;
+        };
+        if accessibility::named(
+            ui.add_enabled(self.current_song > 0, egui::Button::new("⏮")),
+            tr!("action-previous-song"),
+        )
+        .on_hover_text(tr!("action-previous-song"))
+        .clicked()
+        {
+            self.perform(MacroAction::PreviousSong);
+        }
+        ui.label(egui::RichText::new(label).strong());
+        if accessibility::named(
+            ui.add_enabled(
+                self.current_song + 1 < self.show.songs.len(),
+                egui::Button::new("⏭"),
+            ),
+            tr!("action-next-song"),
+        )
+        .on_hover_text(tr!("action-next-song"))
+        .clicked()
+        {
+            self.perform(MacroAction::NextSong);
+        }
+    }
+}
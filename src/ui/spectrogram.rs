@@ -0,0 +1,454 @@
+use crate::i18n::tr;
+use crate::snap::{self, SnapTo};
+use crate::spectrogram::{AudioAnalysis, BANDS};
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// How close, in points, the pointer has to be to grab a cue or snap to a
+/// hit.
+const GRAB: f32 = 6.0;
+const WAVEFORM_HEIGHT: f32 = 60.0;
+const SPECTROGRAM_HEIGHT: f32 = 140.0;
+
+/// The spectrogram window's analysis and view, kept between uses.
+pub(crate) struct SpectrogramView {
+    /// The analysis of the file at the path, once done.
+    analysis: Option<(PathBuf, Arc<AudioAnalysis>)>,
+    loading: Option<(PathBuf, Receiver<io::Result<AudioAnalysis>>)>,
+    error: Option<(PathBuf, String)>,
+    show_spectrogram: bool,
+    snap: bool,
+    snap_to: SnapTo,
+    show_beat_grid: bool,
+    /// Seconds at the left edge and across the view.
+    start: f32,
+    span: f32,
+    /// The cue being dragged, by index.
+    dragging: Option<usize>,
+    /// The spectrogram as drawn, with the view it was drawn for.
+    texture: Option<((u32, u32, usize), egui::TextureHandle)>,
+}
+
+impl Default for SpectrogramView {
+    fn default() -> Self {
+        Self {
+            analysis: None,
+            loading: None,
+            error: None,
+            show_spectrogram: true,
+            snap: true,
+            snap_to: SnapTo::default(),
+            show_beat_grid: false,
+            start: 0.0,
+            span: 30.0,
+            dragging: None,
+            texture: None,
+        }
+    }
+}
+
+/// Dark blue through red to yellow as a band gets louder.
+fn heat(level: u8) -> egui::Color32 {
+    let t = level as f32 / 255.0;
+    let r = (t * 2.0).min(1.0);
+    let g = (t * 2.0 - 1.0).clamp(0.0, 1.0);
+    let b = (0.4 - (t - 0.25).abs() * 1.6).max(0.0) + 0.1 * (1.0 - t);
+    egui::Color32::from_rgb((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8)
+}
+
+impl HaloApp {
+    /// Starts analysing the show's audio file when it changes, and picks up
+    /// the analysis once it's done.
+    fn update_spectrogram(&mut self, ctx: &egui::Context) {
+        let view = &mut self.spectrogram;
+        if let Some((path, receiver)) = &view.loading {
+            match receiver.try_recv() {
+                Ok(Ok(analysis)) => {
+                    view.analysis = Some((path.clone(), Arc::new(analysis)));
+                    view.texture = None;
+                    view.loading = None;
+                }
+                Ok(Err(err)) => {
+                    view.error = Some((path.clone(), err.to_string()));
+                    view.loading = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => view.loading = None,
+            }
+        }
+        let Some(path) = self.show.audio_file.clone() else {
+            return;
+        };
+        let is_current = |current: Option<&PathBuf>| current == Some(&path);
+        if is_current(view.analysis.as_ref().map(|(p, _)| p))
+            || is_current(view.loading.as_ref().map(|(p, _)| p))
+            || is_current(view.error.as_ref().map(|(p, _)| p))
+        {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let ctx = ctx.clone();
+        let file = path.clone();
+        thread::spawn(move || {
+            let _ = sender.send(AudioAnalysis::analyse(&file));
+            ctx.request_repaint();
+        });
+        view.loading = Some((path, receiver));
+        view.error = None;
+    }
+
+    /// The show's audio as a waveform and spectrogram under the cues, with
+    /// hits marked so cues can be dragged onto them.
+    pub(crate) fn draw_spectrogram_window(&mut self, ctx: &egui::Context) {
+        self.update_spectrogram(ctx);
+        let mut open = self.show_spectrogram;
+        egui::Window::new(tr!("spectrogram-title"))
+            .id(egui::Id::new("spectrogram"))
+            .open(&mut open)
+            .default_width(720.0)
+            .show(ctx, |ui| {
+                let Some(path) = self.show.audio_file.clone() else {
+                    ui.label(tr!("spectrogram-no-audio"));
+                    return;
+                };
+                let view = &mut self.spectrogram;
+                if view.loading.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr!(
+                            "spectrogram-analysing",
+                            path = path.display().to_string()
+                        ));
+                    });
+                    return;
+                }
+                if let Some((_, err)) = &view.error {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        tr!("spectrogram-read-failed", error = err.as_str()),
+                    );
+                    if ui.button(tr!("try-again")).clicked() {
+                        view.error = None;
+                    }
+                    return;
+                }
+                let Some(analysis) = view.analysis.as_ref().map(|(_, a)| a.clone()) else {
+                    return;
+                };
+                let length = analysis.length().as_secs_f32();
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut view.show_spectrogram, tr!("spectrogram-title"));
+                    ui.checkbox(&mut view.snap, tr!("spectrogram-snap"))
+                        .on_hover_text(tr!("spectrogram-snap-hint"));
+                    egui::ComboBox::from_id_salt("snap_to")
+                        .selected_text(view.snap_to.to_string())
+                        .show_ui(ui, |ui| {
+                            for to in SnapTo::ALL {
+                                ui.selectable_value(&mut view.snap_to, to, to.to_string());
+                            }
+                        });
+                    ui.label(tr!("spectrogram-zoom"));
+                    ui.add(
+                        egui::Slider::new(&mut view.span, 2.0..=length.max(2.0))
+                            .logarithmic(true)
+                            .suffix(tr!("unit-seconds"))
+                            .max_decimals(0),
+                    );
+                    ui.weak(tr!("spectrogram-hits", count = analysis.transients.len()));
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut view.show_beat_grid, tr!("spectrogram-beat-grid"));
+                    let grid = &mut self.show.beat_grid;
+                    ui.add(
+                        egui::DragValue::new(&mut grid.bpm)
+                            .range(20.0..=300.0)
+                            .speed(0.1)
+                            .suffix(tr!("unit-bpm")),
+                    );
+                    if ui
+                        .small_button(tr!("spectrogram-use-current"))
+                        .on_hover_text(tr!("spectrogram-use-current-hint"))
+                        .clicked()
+                    {
+                        grid.bpm = self.bpm;
+                    }
+                    ui.label(tr!("spectrogram-downbeat"));
+                    let mut downbeat = grid.downbeat.as_secs_f32();
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut downbeat)
+                                .range(0.0..=length)
+                                .speed(0.01)
+                                .suffix(tr!("unit-seconds")),
+                        )
+                        .changed()
+                    {
+                        grid.downbeat = Duration::from_secs_f32(downbeat);
+                    }
+                    if ui
+                        .small_button(tr!("spectrogram-at-playhead"))
+                        .on_hover_text(tr!("spectrogram-at-playhead-hint"))
+                        .clicked()
+                    {
+                        grid.downbeat = self.elapsed;
+                    }
+                });
+                self.draw_audio_view(ui, &analysis);
+            });
+        self.show_spectrogram = open;
+    }
+
+    fn draw_audio_view(&mut self, ui: &mut egui::Ui, analysis: &AudioAnalysis) {
+        let view = &mut self.spectrogram;
+        let length = analysis.length().as_secs_f32();
+        view.start = view.start.clamp(0.0, (length - view.span).max(0.0));
+        let height = WAVEFORM_HEIGHT
+            + if view.show_spectrogram {
+                SPECTROGRAM_HEIGHT
+            } else {
+                0.0
+            };
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(ui.available_width(), height),
+            egui::Sense::click_and_drag(),
+        );
+        let response = accessibility::named(response, tr!("spectrogram-accessible"));
+        let (start, span) = (view.start, view.span);
+        let per_second = rect.width() / span;
+        let x_of = |secs: f32| rect.left() + (secs - start) * per_second;
+        let secs_at = |x: f32| start + (x - rect.left()) / per_second;
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, egui::Color32::from_gray(16));
+
+        // Waveform along the top, a line per point from the loudest sample
+        // under it.
+        let wave = egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), WAVEFORM_HEIGHT));
+        let column = AudioAnalysis::column_length().as_secs_f32();
+        for x in 0..rect.width() as usize {
+            let left = secs_at(rect.left() + x as f32);
+            let first = (left / column) as usize;
+            let last = (((left + 1.0 / per_second) / column) as usize).max(first + 1);
+            let peak = analysis
+                .columns
+                .get(first..last.min(analysis.columns.len()))
+                .unwrap_or_default()
+                .iter()
+                .fold(0.0_f32, |peak, column| peak.max(column.peak));
+            if peak > 0.0 {
+                let x = rect.left() + x as f32 + 0.5;
+                let half = peak.min(1.0) * WAVEFORM_HEIGHT / 2.0;
+                painter.line_segment(
+                    [
+                        egui::pos2(x, wave.center().y - half),
+                        egui::pos2(x, wave.center().y + half),
+                    ],
+                    egui::Stroke::new(1.0, egui::Color32::from_rgb(90, 170, 220)),
+                );
+            }
+        }
+
+        // The spectrogram below, drawn into a texture whenever the view
+        // moves.
+        if view.show_spectrogram {
+            let width = rect.width().max(1.0) as usize;
+            let key = (start.to_bits(), span.to_bits(), width);
+            if view.texture.as_ref().is_none_or(|(drawn, _)| *drawn != key) {
+                let mut image = egui::ColorImage::new([width, BANDS], egui::Color32::BLACK);
+                for x in 0..width {
+                    let Some(column) = analysis.column_at(Duration::from_secs_f32(
+                        secs_at(rect.left() + x as f32).max(0.0),
+                    )) else {
+                        continue;
+                    };
+                    for (band, &level) in column.bands.iter().enumerate() {
+                        image[(x, BANDS - 1 - band)] = heat(level);
+                    }
+                }
+                let texture =
+                    ui.ctx()
+                        .load_texture("spectrogram", image, egui::TextureOptions::LINEAR);
+                view.texture = Some((key, texture));
+            }
+            if let Some((_, texture)) = &view.texture {
+                let area =
+                    egui::Rect::from_min_max(egui::pos2(rect.left(), wave.bottom()), rect.max);
+                painter.image(
+                    texture.id(),
+                    area,
+                    egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                    egui::Color32::WHITE,
+                );
+            }
+        }
+
+        // Hits, as ticks along the top and faint lines down.
+        for hit in &analysis.transients {
+            let x = x_of(hit.as_secs_f32());
+            if rect.x_range().contains(x) {
+                painter.line_segment(
+                    [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+                    egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(255, 220, 0, 40)),
+                );
+                painter.line_segment(
+                    [egui::pos2(x, rect.top()), egui::pos2(x, rect.top() + 6.0)],
+                    egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 220, 0)),
+                );
+            }
+        }
+
+        // Beats as faint lines, bars brighter, unless they'd be a blur.
+        let beat_width = 60.0 / self.show.beat_grid.bpm.max(1.0) * per_second;
+        if view.show_beat_grid && beat_width >= 4.0 {
+            let beats = self.show.beat_grid.beats(
+                start,
+                start + span,
+                self.show.time_signature.beats_per_bar(),
+            );
+            for (secs, bar) in beats {
+                let x = x_of(secs);
+                let alpha = if bar { 110 } else { 40 };
+                painter.line_segment(
+                    [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+                    egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(120, 255, 160, alpha),
+                    ),
+                );
+            }
+        }
+
+        // Cues, with the selected one picked out.
+        let starts: Vec<f32> = self
+            .show
+            .cues
+            .iter()
+            .map(|cue| self.show.cue_start(cue).as_secs_f32())
+            .collect();
+        for (index, (&start, cue)) in starts.iter().zip(&self.show.cues).enumerate() {
+            let x = x_of(start);
+            if !rect.x_range().contains(x) {
+                continue;
+            }
+            let selected = self.selected_cue == Some(index);
+            let color = if selected {
+                egui::Color32::from_rgb(80, 160, 255)
+            } else {
+                egui::Color32::WHITE
+            };
+            painter.line_segment(
+                [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+                egui::Stroke::new(if selected { 2.0 } else { 1.0 }, color),
+            );
+            painter.text(
+                egui::pos2(x + 3.0, rect.top() + 8.0),
+                egui::Align2::LEFT_TOP,
+                cue.number.to_string(),
+                egui::FontId::proportional(11.0),
+                color,
+            );
+        }
+
+        let playhead = x_of(self.elapsed.as_secs_f32());
+        if rect.x_range().contains(playhead) {
+            painter.line_segment(
+                [
+                    egui::pos2(playhead, rect.top()),
+                    egui::pos2(playhead, rect.bottom()),
+                ],
+                egui::Stroke::new(1.5, egui::Color32::RED),
+            );
+        }
+
+        // Grab a cue near the pointer, or pan the view.
+        let nearest_cue = |x: f32| {
+            starts
+                .iter()
+                .enumerate()
+                .map(|(index, &start)| (index, (x_of(start) - x).abs()))
+                .filter(|&(_, distance)| distance <= GRAB)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(index, _)| index)
+        };
+        let frame = self.nudge_frame();
+        let view = &mut self.spectrogram;
+        if response.drag_started() {
+            view.dragging = response
+                .interact_pointer_pos()
+                .and_then(|pos| nearest_cue(pos.x));
+            if view.dragging.is_some() {
+                self.selected_cue = view.dragging;
+            }
+        }
+        if response.dragged() {
+            match (view.dragging, response.interact_pointer_pos()) {
+                (Some(index), Some(pos)) => {
+                    let mut time = Duration::from_secs_f32(secs_at(pos.x).max(0.0));
+                    if view.snap && !ui.input(|i| i.modifiers.shift) {
+                        time = snap::snap(
+                            view.snap_to,
+                            time,
+                            &self.show.beat_grid,
+                            self.show.time_signature.beats_per_bar(),
+                            frame,
+                            analysis,
+                            Duration::from_secs_f32(GRAB / per_second),
+                        );
+                    }
+                    let cue = &self.show.cues[index];
+                    let offset = self.show.cue_start(cue) - cue.start_time;
+                    self.show
+                        .move_cue(index, time.saturating_sub(offset), self.ripple_edit);
+                }
+                _ => view.start -= response.drag_delta().x / per_second,
+            }
+        }
+        if response.drag_stopped() {
+            view.dragging = None;
+        }
+        if response.clicked() {
+            if let Some(index) = response
+                .interact_pointer_pos()
+                .and_then(|pos| nearest_cue(pos.x))
+            {
+                self.selected_cue = Some(index);
+            }
+        }
+        if response.hovered() {
+            let view = &mut self.spectrogram;
+            let scroll = ui.input(|i| i.smooth_scroll_delta);
+            if let (Some(pos), true) = (response.hover_pos(), scroll.y != 0.0) {
+                // Zoom about the pointer, keeping the time under it still.
+                let anchor = secs_at(pos.x);
+                view.span = (view.span * (-scroll.y / 200.0).exp()).clamp(2.0, length.max(2.0));
+                view.start = anchor - (pos.x - rect.left()) / rect.width() * view.span;
+            }
+            view.start -= scroll.x / per_second;
+        }
+        let hint = if self.spectrogram.dragging.is_some() {
+            tr!("spectrogram-hold-shift")
+        } else {
+            tr!("spectrogram-drag-hint")
+        };
+        response.on_hover_text(hint);
+
+        ui.horizontal(|ui| {
+            let view = &self.spectrogram;
+            ui.weak(HaloApp::format_duration(Duration::from_secs_f32(
+                view.start.max(0.0),
+            )));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.weak(HaloApp::format_duration(Duration::from_secs_f32(
+                    (view.start + view.span).max(0.0),
+                )));
+            });
+        });
+    }
+}
@@ -0,0 +1,104 @@
+use crate::dmx_fader::FaderTarget;
+use crate::i18n::tr;
+use crate::speed_master::{SpeedMaster, MAX_RATE};
+use crate::ui::group_masters::draw_dmx_learn;
+use crate::HaloApp;
+use eframe::egui;
+
+/// Picks the speed master an effect runs on, or none to run as programmed.
+pub(crate) fn speed_master_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    masters: &[SpeedMaster],
+    selected: &mut Option<usize>,
+) {
+    let text = selected
+        .and_then(|index| masters.get(index))
+        .map_or_else(|| tr!("none"), |master| master.name.clone());
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, tr!("none"));
+            for (index, master) in masters.iter().enumerate() {
+                ui.selectable_value(selected, Some(index), &master.name);
+            }
+        });
+}
+
+impl HaloApp {
+    pub(crate) fn draw_speed_masters_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_speed_masters;
+        let mut remove = None;
+        let mut dmx_learn = None;
+        let learning_dmx = self.learn_dmx_fader.as_ref().map(|learn| learn.target);
+        egui::Window::new(tr!("speed-title"))
+            .id(egui::Id::new("speed_masters"))
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.toggle_value(&mut self.effects_frozen, tr!("speed-freeze"))
+                        .on_hover_text(tr!("speed-freeze-hint"));
+                    if ui.button(tr!("add")).clicked() {
+                        self.show.speed_masters.push(SpeedMaster {
+                            name: tr!(
+                                "speed-default-name",
+                                number = self.show.speed_masters.len() + 1
+                            ),
+                            ..SpeedMaster::default()
+                        });
+                    }
+                });
+                if self.show.speed_masters.is_empty() {
+                    ui.label(tr!("speed-empty"));
+                    return;
+                }
+                ui.separator();
+                ui.horizontal_top(|ui| {
+                    for (index, master) in self.show.speed_masters.iter_mut().enumerate() {
+                        ui.vertical(|ui| {
+                            ui.set_width(72.0);
+                            ui.add(egui::TextEdit::singleline(&mut master.name));
+                            ui.add(
+                                egui::Slider::new(&mut master.rate, 0.0..=MAX_RATE)
+                                    .vertical()
+                                    .max_decimals(2)
+                                    .suffix("×"),
+                            );
+                            if ui.small_button("1×").clicked() {
+                                master.rate = 1.0;
+                            }
+                            let learning = self.learn_speed_master == Some(index);
+                            let learn = match master.control {
+                                _ if learning => tr!("masters-move-control"),
+                                Some(control) => format!("CC {}", control.controller),
+                                None => tr!("masters-midi-learn"),
+                            };
+                            if ui.small_button(learn).clicked() {
+                                self.learn_speed_master = (!learning).then_some(index);
+                            }
+                            if master.control.is_some() && ui.small_button(tr!("clear")).clicked() {
+                                master.control = None;
+                            }
+                            let target = FaderTarget::Speed(index);
+                            if draw_dmx_learn(ui, learning_dmx == Some(target), &mut master.fader) {
+                                dmx_learn = Some(target);
+                            }
+                            if ui.small_button(tr!("remove")).clicked() {
+                                remove = Some(index);
+                            }
+                        });
+                    }
+                });
+            });
+        self.show_speed_masters = open;
+
+        if let Some(target) = dmx_learn {
+            self.toggle_dmx_learn(target);
+        }
+        if let Some(index) = remove {
+            self.remove_speed_master(index);
+            self.learn_dmx_fader = None;
+        }
+    }
+}
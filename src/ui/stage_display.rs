@@ -0,0 +1,235 @@
+use crate::i18n::tr;
+use crate::stage_display::{self, StageDisplaySettings};
+use crate::HaloApp;
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+/// How long the display has to stay put after being moved before where it
+/// is gets saved.
+const SETTLE: Duration = Duration::from_secs(1);
+
+/// The stage display window while it is open.
+#[derive(Default)]
+pub(crate) struct StageDisplay {
+    /// Whether the window is up, so it is placed only as it opens and not
+    /// pulled back while being dragged.
+    showing: bool,
+    /// Where it was moved to and when, until it settles there.
+    moved: Option<(egui::Pos2, Instant)>,
+}
+
+fn color([r, g, b]: [u8; 3]) -> egui::Color32 {
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// A line of text as large as fits `width`, taking monospace digits as
+/// about 0.6 of their height wide, and no taller than `max_height`.
+fn big_line(ui: &mut egui::Ui, text: &str, width: f32, max_height: f32, color: egui::Color32) {
+    let size = (width / (text.chars().count().max(1) as f32 * 0.62)).min(max_height);
+    ui.add(
+        egui::Label::new(
+            egui::RichText::new(text)
+                .font(egui::FontId::monospace(size))
+                .color(color),
+        )
+        .wrap_mode(egui::TextWrapMode::Truncate),
+    );
+}
+
+impl HaloApp {
+    /// What the stage display shows, filling `ui`.
+    fn draw_stage_display_content(&self, ui: &mut egui::Ui) {
+        let settings = &self.preferences.stage_display;
+        let next = self.next_cue().map(|cue| {
+            let remaining = self.show.cue_start(cue).saturating_sub(self.elapsed);
+            (cue, remaining)
+        });
+        let standby = next.is_some_and(|(_, remaining)| settings.is_standby(remaining));
+        let lit = !settings.flash_on_standby || ui.input(|input| input.time).fract() < 0.5;
+        let background = if standby && lit {
+            color(settings.standby)
+        } else {
+            color(settings.background)
+        };
+        let rect = ui.max_rect();
+        ui.painter().rect_filled(rect, 0.0, background);
+
+        let text = color(settings.text);
+        let lines = [
+            settings.show_timecode,
+            settings.show_next_cue,
+            settings.show_countdown,
+        ]
+        .iter()
+        .filter(|shown| **shown)
+        .count()
+        .max(1) as f32;
+        let width = rect.width() * 0.92;
+        let height = rect.height() / lines * 0.8;
+        ui.vertical_centered(|ui| {
+            ui.add_space(rect.height() * 0.04);
+            if settings.show_timecode {
+                big_line(ui, &self.format_timecode(), width, height, text);
+            }
+            if settings.show_next_cue {
+                let label = match next {
+                    Some((cue, _)) if cue.name.is_empty() => {
+                        tr!("stage-next", number = cue.number.to_string())
+                    }
+                    Some((cue, _)) => tr!(
+                        "stage-next-named",
+                        number = cue.number.to_string(),
+                        name = cue.name.as_str()
+                    ),
+                    None => tr!("stage-no-more"),
+                };
+                big_line(ui, &label, width, height * 0.6, text);
+            }
+            if settings.show_countdown {
+                let countdown = next.map_or_else(
+                    || "–".to_string(),
+                    |(_, remaining)| stage_display::format_countdown(remaining),
+                );
+                big_line(ui, &countdown, width, height, text);
+            }
+        });
+    }
+
+    /// The stage display in its own OS window, whatever the operator's
+    /// screen is doing. Backends without multi-viewport support get an
+    /// ordinary window instead.
+    pub(crate) fn draw_stage_display(&mut self, ctx: &egui::Context) {
+        let settings = &self.preferences.stage_display;
+        if !settings.open {
+            self.stage_display.showing = false;
+            return;
+        }
+        let mut builder = egui::ViewportBuilder::default()
+            .with_title(tr!("stage-viewport-title"))
+            .with_inner_size([800.0, 450.0])
+            .with_fullscreen(settings.fullscreen);
+        if let Some(position) = settings.position.filter(|_| !self.stage_display.showing) {
+            builder = builder.with_position(position);
+        }
+        self.stage_display.showing = true;
+        let id = egui::ViewportId::from_hash_of("stage_display");
+        let (close, at) = ctx.show_viewport_immediate(id, builder, |ctx, class| {
+            if class == egui::ViewportClass::Embedded {
+                let mut open = true;
+                egui::Window::new(tr!("menu-stage-display"))
+                    .id(egui::Id::new("stage_display"))
+                    .open(&mut open)
+                    .default_size([480.0, 270.0])
+                    .frame(egui::Frame::NONE)
+                    .show(ctx, |ui| self.draw_stage_display_content(ui));
+                return (!open, None);
+            }
+            egui::CentralPanel::default()
+                .frame(egui::Frame::NONE)
+                .show(ctx, |ui| self.draw_stage_display_content(ui));
+            ctx.request_repaint();
+            (
+                ctx.input(|input| input.viewport().close_requested()),
+                ctx.input(|input| input.viewport().outer_rect.map(|rect| rect.min)),
+            )
+        });
+        if close {
+            self.set_stage_display_open(false);
+            return;
+        }
+        self.note_stage_display_position(at);
+    }
+
+    /// Saves where the display has been moved to once it has stayed there
+    /// for a moment, rather than on every step of the drag.
+    fn note_stage_display_position(&mut self, at: Option<egui::Pos2>) {
+        let Some(at) = at else {
+            return;
+        };
+        let settings = &mut self.preferences.stage_display;
+        if settings.position == Some([at.x, at.y]) || settings.fullscreen {
+            self.stage_display.moved = None;
+            return;
+        }
+        match self.stage_display.moved {
+            Some((moved, since)) if moved == at => {
+                if since.elapsed() >= SETTLE {
+                    settings.position = Some([at.x, at.y]);
+                    self.stage_display.moved = None;
+                    let _ = self.preferences.save();
+                }
+            }
+            _ => self.stage_display.moved = Some((at, Instant::now())),
+        }
+    }
+
+    fn set_stage_display_open(&mut self, open: bool) {
+        self.preferences.stage_display.open = open;
+        let _ = self.preferences.save();
+    }
+
+    /// Opening the stage display and choosing what it shows and how.
+    pub(crate) fn draw_stage_display_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_stage_display_settings;
+        let before = self.preferences.stage_display.clone();
+        egui::Window::new(tr!("menu-stage-display"))
+            .id(egui::Id::new("stage_display_settings"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let settings = &mut self.preferences.stage_display;
+                ui.horizontal(|ui| {
+                    ui.toggle_value(&mut settings.open, tr!("stage-open"))
+                        .on_hover_text(tr!("stage-open-hint"));
+                    ui.checkbox(&mut settings.fullscreen, tr!("stage-fullscreen"))
+                        .on_hover_text(tr!("stage-fullscreen-hint"));
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut settings.show_timecode, tr!("timecode-title"));
+                    ui.checkbox(&mut settings.show_next_cue, tr!("stage-next-cue"));
+                    ui.checkbox(&mut settings.show_countdown, tr!("stage-countdown"));
+                });
+                egui::Grid::new("stage_display_colors")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("stage-text"));
+                        ui.color_edit_button_srgb(&mut settings.text);
+                        ui.end_row();
+                        ui.label(tr!("stage-background"));
+                        ui.color_edit_button_srgb(&mut settings.background);
+                        ui.end_row();
+                        ui.label(tr!("stage-standby"));
+                        ui.color_edit_button_srgb(&mut settings.standby);
+                        ui.end_row();
+                        ui.label(tr!("stage-standby-from"));
+                        let mut secs = settings.standby_time.as_secs_f32();
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut secs)
+                                    .range(0.0..=120.0)
+                                    .speed(0.5)
+                                    .suffix(tr!("stage-standby-suffix")),
+                            )
+                            .on_hover_text(tr!("stage-standby-hint"))
+                            .changed()
+                        {
+                            settings.standby_time = Duration::from_secs_f32(secs);
+                        }
+                        ui.end_row();
+                    });
+                ui.checkbox(&mut settings.flash_on_standby, tr!("stage-flash"));
+                if ui.button(tr!("transport-reset")).clicked() {
+                    *settings = StageDisplaySettings {
+                        open: settings.open,
+                        position: settings.position,
+                        ..Default::default()
+                    };
+                }
+            });
+        self.show_stage_display_settings = open;
+        if self.preferences.stage_display != before {
+            let _ = self.preferences.save();
+        }
+    }
+}
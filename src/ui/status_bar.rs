@@ -0,0 +1,544 @@
+use crate::clock_sync::{BackupState, ClockRole};
+use crate::i18n::tr;
+use crate::interfaces::Protocol;
+use crate::midi_monitor::Direction;
+use crate::power;
+use crate::ui::preferences::PreferencesSection;
+use crate::ui::touch;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+const OK: egui::Color32 = egui::Color32::from_rgb(100, 200, 100);
+const WARNING: egui::Color32 = egui::Color32::from_rgb(255, 180, 0);
+
+/// How a connection or service is doing, for its status bar indicator.
+enum Health {
+    Off,
+    Ok,
+    Warning,
+    Failed,
+}
+
+impl Health {
+    fn color(&self, ui: &egui::Ui) -> egui::Color32 {
+        match self {
+            Health::Off => ui.visuals().weak_text_color(),
+            Health::Ok => OK,
+            Health::Warning => WARNING,
+            Health::Failed => egui::Color32::RED,
+        }
+    }
+}
+
+impl HaloApp {
+    /// Frame rates and the health of each connection. Clicking an indicator
+    /// opens its settings.
+    pub(crate) fn draw_status_bar(&mut self, ui: &mut egui::Ui) {
+        let rates = tr!(
+            "status-rates",
+            ui = format!("{:.1}", self.ui_frames.fps()),
+            output = format!("{:.1}", self.output_frames.fps())
+        );
+        touch::hint(
+            ui.toggle_value(&mut self.show_frame_graph, rates),
+            self.preferences.touch_mode,
+            tr!("status-frame-graph"),
+        );
+        if self.output_frames.is_dropping() {
+            ui.colored_label(
+                WARNING,
+                tr!(
+                    "status-dropping-frames",
+                    count = self.output_frames.dropped()
+                ),
+            );
+        }
+        ui.label(self.transport.label());
+        let recording = match (
+            self.output_recording.recording,
+            self.output_recording.playback,
+        ) {
+            (Some(_), _) => Some((
+                tr!("status-rec"),
+                egui::Color32::RED,
+                tr!("status-rec-hint"),
+            )),
+            (None, Some(_)) => Some((tr!("status-playback"), WARNING, tr!("status-playback-hint"))),
+            (None, None) => None,
+        };
+        if let Some((text, color, hover)) = recording {
+            let response = ui
+                .add(
+                    egui::Label::new(egui::RichText::new(text).color(color).strong())
+                        .sense(egui::Sense::click()),
+                )
+                .on_hover_text(hover);
+            if response.clicked() {
+                self.show_recording = true;
+            }
+        }
+        if self.show.circuit_limit.is_some() {
+            let now = power::estimate(&self.show.fixtures, &self.output);
+            if now.is_over(self.show.circuit_limit) {
+                let response = ui
+                    .add(
+                        egui::Label::new(
+                            egui::RichText::new(format!(
+                                "⚡ {}",
+                                power::format_watts(now.worst_circuit())
+                            ))
+                            .color(egui::Color32::RED)
+                            .strong(),
+                        )
+                        .sense(egui::Sense::click()),
+                    )
+                    .on_hover_text(tr!("status-power-hint"));
+                if response.clicked() {
+                    self.show_power = true;
+                }
+            }
+        }
+        if let Some(venue) = &self.venue {
+            let response = ui
+                .add(
+                    egui::Label::new(tr!("status-venue", name = venue.name.as_str()))
+                        .sense(egui::Sense::click()),
+                )
+                .on_hover_text(tr!("status-venue-hint"));
+            if response.clicked() {
+                self.show_venue = true;
+            }
+        }
+        let capped: Vec<String> = self
+            .show
+            .group_masters
+            .iter()
+            .filter(|master| master.is_inhibiting())
+            .map(|master| format!("{} {:.0}%", master.name, master.level * 100.0))
+            .collect();
+        if !capped.is_empty() {
+            let response = ui
+                .add(
+                    egui::Label::new(
+                        egui::RichText::new(tr!("status-capped", groups = capped.join(", ")))
+                            .color(WARNING),
+                    )
+                    .sense(egui::Sense::click()),
+                )
+                .on_hover_text(tr!("status-capped-hint"));
+            if response.clicked() {
+                self.show_group_masters = true;
+            }
+        }
+        let testing = self
+            .test_pattern
+            .map(|test| tr!("status-test-pattern", universe = test.universe));
+        let muted = (!self.muted_universes.is_empty()).then(|| {
+            let universes: Vec<String> = self.muted_universes.iter().map(u16::to_string).collect();
+            tr!("status-muted", universes = universes.join(", "))
+        });
+        let commissioning: Vec<String> = testing.into_iter().chain(muted).collect();
+        if !commissioning.is_empty() {
+            let response = ui
+                .add(
+                    egui::Label::new(
+                        egui::RichText::new(tr!(
+                            "status-universes",
+                            state = commissioning.join("; ")
+                        ))
+                        .color(WARNING),
+                    )
+                    .sense(egui::Sense::click()),
+                )
+                .on_hover_text(tr!("status-universes-hint"));
+            if response.clicked() {
+                self.show_output_monitor = true;
+            }
+        }
+
+        let mut jump = None;
+        let indicators = [
+            (
+                self.interfaces_health(),
+                PreferencesSection::NetworkInterfaces,
+            ),
+            (
+                self.network_input_health(),
+                PreferencesSection::NetworkInput,
+            ),
+            (self.midi_health(), PreferencesSection::Midi),
+            (self.audio_health(), PreferencesSection::Audio),
+            (self.remote_health(), PreferencesSection::RemoteControl),
+            (self.clock_sync_health(), PreferencesSection::ClockSync),
+        ];
+        for ((health, label, details), section) in indicators {
+            ui.separator();
+            let response = ui.add(
+                egui::Label::new(egui::RichText::new(label).color(health.color(ui)))
+                    .sense(egui::Sense::click()),
+            );
+            if touch::hint(response, self.preferences.touch_mode, details).clicked() {
+                jump = Some(section);
+            }
+        }
+        ui.separator();
+        let (health, label, details) = self.art_net_health();
+        let response = ui.add(
+            egui::Label::new(egui::RichText::new(label).color(health.color(ui)))
+                .sense(egui::Sense::click()),
+        );
+        if touch::hint(response, self.preferences.touch_mode, details).clicked() {
+            self.show_art_net = true;
+        }
+        ui.separator();
+        // Link peer discovery isn't implemented yet, so there are never any.
+        let (health, link) = if self.link_enabled {
+            (Health::Warning, tr!("status-link-no-peers"))
+        } else {
+            (Health::Off, tr!("status-link-off"))
+        };
+        ui.colored_label(health.color(ui), link);
+
+        // Keep the input rates moving with nothing else repainting.
+        if self.network_input.is_some() {
+            ui.ctx().request_repaint_after(Duration::from_secs(1));
+        }
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let unread = self.toasts.unread;
+            let bell = if unread > 0 {
+                egui::RichText::new(format!("🔔 {}", unread)).color(WARNING)
+            } else {
+                egui::RichText::new("🔔")
+            };
+            touch::hint(
+                ui.toggle_value(&mut self.show_notifications, bell),
+                self.preferences.touch_mode,
+                tr!("status-notifications"),
+            );
+        });
+
+        if let Some(section) = jump {
+            self.open_preferences_at(section);
+        }
+    }
+
+    fn interfaces_health(&self) -> (Health, String, String) {
+        let chosen: Vec<String> = Protocol::ALL
+            .into_iter()
+            .filter_map(|protocol| {
+                let name = self.preferences.interfaces.get(protocol)?;
+                Some(format!("{}: {}", protocol, name))
+            })
+            .collect();
+        if chosen.is_empty() {
+            return (
+                Health::Off,
+                tr!("status-network-any"),
+                tr!("status-network-any-hint"),
+            );
+        }
+        let down = self.interfaces_down();
+        if down.is_empty() {
+            return (Health::Ok, tr!("status-network-up"), chosen.join("\n"));
+        }
+        let details = down
+            .iter()
+            .map(|(protocol, name)| {
+                tr!(
+                    "status-interface-down",
+                    protocol = protocol.to_string(),
+                    name = *name
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        (Health::Failed, tr!("status-network-down"), details)
+    }
+
+    fn clock_sync_health(&self) -> (Health, String, String) {
+        match self.preferences.clock_role {
+            ClockRole::Off => (
+                Health::Off,
+                tr!("status-clock-local"),
+                tr!("status-clock-local-hint"),
+            ),
+            ClockRole::Main => {
+                if let Some(error) = &self.clock_sync_error {
+                    return (Health::Failed, tr!("status-clock-error"), error.clone());
+                }
+                let backups = self
+                    .clock_server
+                    .as_ref()
+                    .map(|server| server.backups())
+                    .unwrap_or_default();
+                if backups.is_empty() {
+                    return (
+                        Health::Warning,
+                        tr!("status-clock-main"),
+                        tr!("preferences-no-backup"),
+                    );
+                }
+                let details = backups
+                    .iter()
+                    .map(|backup| tr!("preferences-backup-following", backup = backup.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                (Health::Ok, tr!("status-clock-main"), details)
+            }
+            ClockRole::Backup if self.backup_state == BackupState::TakenOver => (
+                Health::Failed,
+                tr!("status-clock-taken-over"),
+                tr!("status-clock-taken-over-hint"),
+            ),
+            ClockRole::Backup => {
+                let error = self.clock_client.as_ref().and_then(|client| client.error());
+                if let Some(error) = error {
+                    return (Health::Failed, tr!("status-clock-lost"), error);
+                }
+                let Some(offset) = self.clock_offset else {
+                    return (
+                        Health::Warning,
+                        tr!("status-clock-waiting"),
+                        tr!(
+                            "status-clock-waiting-for",
+                            main = self.preferences.clock_main.as_str()
+                        ),
+                    );
+                };
+                (
+                    Health::Ok,
+                    tr!(
+                        "status-clock-offset",
+                        offset = format!("{:+.1}", offset * 1000.0)
+                    ),
+                    tr!(
+                        "status-clock-following",
+                        main = self.preferences.clock_main.as_str(),
+                        corrections = self.clock_corrections
+                    ),
+                )
+            }
+        }
+    }
+
+    fn art_net_health(&self) -> (Health, String, String) {
+        if let Some(error) = &self.art_net_error {
+            return (Health::Failed, tr!("status-art-net-error"), error.clone());
+        }
+        if self.preferences.art_net_output.enabled && self.holds_output_for_main() {
+            return (
+                Health::Off,
+                tr!("status-art-net-standby"),
+                tr!("status-art-net-standby-hint"),
+            );
+        }
+        let Some(slowest) = self
+            .art_net_rates
+            .iter()
+            .min_by(|a, b| a.achieved.total_cmp(&b.achieved))
+        else {
+            return (
+                Health::Off,
+                tr!("status-art-net-off"),
+                tr!("status-art-net-off-hint"),
+            );
+        };
+        let behind = self
+            .art_net_rates
+            .iter()
+            .any(|rate| rate.achieved < rate.target * 0.9);
+        let details = self
+            .art_net_rates
+            .iter()
+            .map(|rate| {
+                tr!(
+                    "status-art-net-rate",
+                    address = rate.address.to_string(),
+                    achieved = format!("{:.1}", rate.achieved),
+                    target = format!("{:.0}", rate.target)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let health = if behind { Health::Warning } else { Health::Ok };
+        (
+            health,
+            tr!(
+                "status-art-net-hz",
+                rate = format!("{:.0}", slowest.achieved)
+            ),
+            details,
+        )
+    }
+
+    fn network_input_health(&self) -> (Health, String, String) {
+        let settings = &self.preferences.network_input;
+        if let Some(error) = &self.network_input_error {
+            return (Health::Failed, tr!("status-dmx-in-error"), error.clone());
+        }
+        if self.network_input.is_none() || !settings.enabled() {
+            return (
+                Health::Off,
+                tr!("status-dmx-in-off"),
+                tr!("status-dmx-in-off-hint"),
+            );
+        }
+        let sources = self.dmx_input.sources();
+        let mut label = Vec::new();
+        for (protocol, enabled) in [("Art-Net", settings.art_net), ("sACN", settings.sacn)] {
+            if enabled {
+                let rate: f32 = sources
+                    .iter()
+                    .filter(|source| source.protocol == protocol)
+                    .map(|source| source.rate)
+                    .sum();
+                label.push(format!("{} {:.0}/s", protocol, rate));
+            }
+        }
+        let details = if sources.is_empty() {
+            tr!("status-dmx-in-nothing")
+        } else {
+            sources
+                .iter()
+                .map(|source| {
+                    tr!(
+                        "status-dmx-in-source",
+                        universe = source.universe,
+                        protocol = source.protocol.to_string(),
+                        name = source.name.as_str(),
+                        rate = format!("{:.0}", source.rate)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let health = if sources.is_empty() {
+            Health::Warning
+        } else {
+            Health::Ok
+        };
+        (
+            health,
+            tr!("status-dmx-in", rates = label.join(", ")),
+            details,
+        )
+    }
+
+    fn midi_health(&self) -> (Health, String, String) {
+        let input = self.preferences.midi_input.as_deref();
+        let output = self.preferences.midi_output.as_deref();
+        if input.is_none() && output.is_none() {
+            return (
+                Health::Off,
+                tr!("status-midi-off"),
+                tr!("status-midi-off-hint"),
+            );
+        }
+        let port = |name: Option<&str>, connected: bool, direction: Direction| match name {
+            Some(name) if connected => tr!(
+                "status-midi-connected-port",
+                direction = direction.to_string(),
+                name = name
+            ),
+            Some(name) => tr!(
+                "status-midi-disconnected-port",
+                direction = direction.to_string(),
+                name = name
+            ),
+            None => tr!("status-midi-no-port", direction = direction.to_string()),
+        };
+        let mut details = format!(
+            "{}\n{}",
+            port(input, self.midi_in.is_some(), Direction::In),
+            port(output, self.midi_out.is_some(), Direction::Out)
+        );
+        if let Some(error) = &self.midi_error {
+            details = format!("{}\n{}", details, error);
+        }
+        let connected = (input.is_none() || self.midi_in.is_some())
+            && (output.is_none() || self.midi_out.is_some());
+        if connected {
+            (Health::Ok, tr!("status-midi-connected"), details)
+        } else {
+            (Health::Failed, tr!("status-midi-disconnected"), details)
+        }
+    }
+
+    fn audio_health(&self) -> (Health, String, String) {
+        let click_error = self
+            .click_error
+            .clone()
+            .or_else(|| self.click_clock.error());
+        if let Some(error) = click_error {
+            return (Health::Failed, tr!("status-audio-click-error"), error);
+        }
+        let Some(device) = &self.preferences.audio_input else {
+            return (
+                Health::Off,
+                tr!("status-audio-off"),
+                tr!("status-audio-off-hint"),
+            );
+        };
+        let error = self
+            .audio_error
+            .clone()
+            .or_else(|| self.audio_meter.error());
+        match error {
+            Some(error) => (Health::Failed, tr!("status-audio-error"), error),
+            None if self.audio_input.is_some() => {
+                (Health::Ok, tr!("status-audio-on"), device.clone())
+            }
+            None => (Health::Failed, tr!("status-audio-stopped"), device.clone()),
+        }
+    }
+
+    /// The TCP, HTTP and OSC servers together.
+    fn remote_health(&self) -> (Health, String, String) {
+        let servers = [
+            (
+                "TCP",
+                self.preferences.remote_enabled,
+                self.remote.is_some(),
+                &self.remote_error,
+                self.preferences.remote_port,
+            ),
+            (
+                "HTTP",
+                self.preferences.http_enabled,
+                self.http.is_some(),
+                &self.http_error,
+                self.preferences.http_port,
+            ),
+            (
+                "OSC",
+                self.preferences.osc_input_enabled,
+                self.osc_input.is_some(),
+                &self.osc_error,
+                self.preferences.osc_input_port,
+            ),
+        ];
+        let mut health = Health::Off;
+        let mut details = Vec::new();
+        for (name, enabled, running, error, port) in servers {
+            if !enabled {
+                details.push(tr!("status-server-off", name = name));
+            } else if let Some(error) = error {
+                health = Health::Failed;
+                details.push(format!("{}: {}", name, error));
+            } else if running {
+                if matches!(health, Health::Off) {
+                    health = Health::Ok;
+                }
+                details.push(tr!("status-server-listening", name = name, port = port));
+            }
+        }
+        let label = match health {
+            Health::Off => tr!("status-remote-off"),
+            Health::Failed => tr!("status-remote-error"),
+            _ => tr!("status-remote-on"),
+        };
+        (health, label, details.join("\n"))
+    }
+}
@@ -0,0 +1,65 @@
+use crate::i18n::{self, tr};
+use crate::tag::{Tag, TagColor, ICONS};
+use crate::ui::accessibility;
+use eframe::egui;
+
+/// Colour swatches and icons to pick a tag from, with a button to clear it.
+pub(crate) fn draw_tag_picker(ui: &mut egui::Ui, tag: &mut Tag) {
+    ui.horizontal_wrapped(|ui| {
+        for color in TagColor::ALL {
+            let selected = tag.color == Some(color);
+            let (rect, response) =
+                ui.allocate_exact_size(egui::vec2(18.0, 18.0), egui::Sense::click());
+            ui.painter().rect_filled(rect, 3.0, color.color32());
+            if selected {
+                ui.painter().rect_stroke(
+                    rect,
+                    3.0,
+                    egui::Stroke::new(2.0, ui.visuals().strong_text_color()),
+                    egui::StrokeKind::Outside,
+                );
+            }
+            let response = accessibility::named(
+                response,
+                if selected {
+                    tr!("tag-colour-chosen", colour = color.to_string())
+                } else {
+                    tr!("tag-colour", colour = color.to_string())
+                },
+            );
+            if response.on_hover_text(color.to_string()).clicked() {
+                tag.color = (!selected).then_some(color);
+            }
+        }
+    });
+    ui.horizontal_wrapped(|ui| {
+        for (icon, meaning) in ICONS {
+            if ui
+                .selectable_label(tag.icon == icon, icon)
+                .on_hover_text(i18n::translate(meaning, None))
+                .clicked()
+            {
+                tag.icon = if tag.icon == icon {
+                    String::new()
+                } else {
+                    icon.to_string()
+                };
+            }
+        }
+        if !tag.is_empty() && ui.small_button(tr!("clear")).clicked() {
+            *tag = Tag::default();
+        }
+    });
+}
+
+/// A strip in the tag's colour at the start of a row, with space kept for
+/// untagged rows so the rest lines up.
+pub(crate) fn draw_tag_strip(ui: &mut egui::Ui, tag: &Tag) {
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(6.0, ui.spacing().interact_size.y),
+        egui::Sense::hover(),
+    );
+    if let Some(color) = tag.color {
+        ui.painter().rect_filled(rect, 2.0, color.color32());
+    }
+}
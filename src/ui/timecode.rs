@@ -0,0 +1,222 @@
+use crate::i18n::tr;
+use crate::timecode;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+impl HaloApp {
+    /// Incoming and internal timecode side by side with the offset between
+    /// them, while chasing timecode. The offset flashes red once it passes
+    /// the drift warning.
+    pub(crate) fn draw_dual_timecode(&self, ui: &mut egui::Ui, size: f32) {
+        let (Some(external), Some(drift)) = (self.external_position(), self.timecode_drift())
+        else {
+            return;
+        };
+        let start = self.show.timecode.start;
+        let rolling = self
+            .timecode_in
+            .is_some_and(|(timecode, _)| timecode.rolling);
+        let led = self.preferences.led_color.lit();
+        let drifting = self.timecode_drifting();
+        let flash = drifting && ui.input(|input| input.time).fract() < 0.5;
+        ui.ctx().request_repaint_after(Duration::from_millis(250));
+
+        let font = egui::FontId::monospace(size);
+        let format = |color, background| egui::TextFormat {
+            font_id: font.clone(),
+            color,
+            background,
+            ..Default::default()
+        };
+        let mut job = egui::text::LayoutJob::default();
+        job.append(
+            &format!(
+                "EXT {}{}   INT {}   ",
+                timecode::format(start + external),
+                if rolling { "" } else { " ■" },
+                timecode::format(start + self.elapsed)
+            ),
+            0.0,
+            format(led, egui::Color32::TRANSPARENT),
+        );
+        let offset = match (flash, drifting) {
+            (true, _) => format(egui::Color32::WHITE, egui::Color32::RED),
+            (false, true) => format(egui::Color32::RED, egui::Color32::TRANSPARENT),
+            _ => format(ui.visuals().text_color(), egui::Color32::TRANSPARENT),
+        };
+        job.append(&format!("Δ {:+.0} ms", drift * 1000.0), 0.0, offset);
+        ui.label(job);
+    }
+
+    /// The show's timecode start value, the offset applied to incoming
+    /// timecode and the pre-roll, all of which take effect straight away.
+    pub(crate) fn draw_timecode_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_timecode;
+        let settings = &mut self.show.timecode;
+        egui::Window::new(tr!("timecode-title"))
+            .id(egui::Id::new("timecode"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("timecode_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("timecode-start"));
+                        ui.horizontal(|ui| {
+                            let secs = settings.start.as_secs();
+                            let (mut hours, mut minutes, mut seconds) =
+                                (secs / 3600, (secs % 3600) / 60, secs % 60);
+                            let mut changed = false;
+                            for (value, max) in
+                                [(&mut hours, 23), (&mut minutes, 59), (&mut seconds, 59)]
+                            {
+                                changed |= ui
+                                    .add(
+                                        egui::DragValue::new(value)
+                                            .range(0..=max)
+                                            .custom_formatter(|n, _| format!("{:02}", n)),
+                                    )
+                                    .changed();
+                            }
+                            if changed {
+                                settings.start =
+                                    Duration::from_secs(hours * 3600 + minutes * 60 + seconds);
+                            }
+                            if ui.button("01:00:00").clicked() {
+                                settings.start = Duration::from_secs(3600);
+                            }
+                            if ui.button(tr!("timecode-zero")).clicked() {
+                                settings.start = Duration::ZERO;
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label(tr!("timecode-offset"));
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::DragValue::new(&mut settings.offset)
+                                    .range(-3600.0..=3600.0)
+                                    .speed(0.01)
+                                    .fixed_decimals(3)
+                                    .suffix(tr!("unit-seconds")),
+                            )
+                            .on_hover_text(tr!("timecode-offset-hint"));
+                            if ui.button(tr!("timecode-zero")).clicked() {
+                                settings.offset = 0.0;
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label(tr!("timecode-preroll"));
+                        let mut secs = settings.preroll.as_secs_f32();
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut secs)
+                                    .range(0.0..=60.0)
+                                    .speed(0.1)
+                                    .suffix(tr!("unit-seconds")),
+                            )
+                            .on_hover_text(tr!("timecode-preroll-hint"))
+                            .changed()
+                        {
+                            settings.preroll = Duration::from_secs_f32(secs);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("timecode-drift"));
+                        let mut millis = settings.drift_warning.as_millis() as u64;
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut millis)
+                                    .range(1..=1000)
+                                    .suffix(tr!("unit-ms")),
+                            )
+                            .on_hover_text(tr!("timecode-drift-hint"))
+                            .changed()
+                        {
+                            settings.drift_warning = Duration::from_millis(millis);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("timecode-chase"));
+                        let mut millis = settings.chase_tolerance.as_millis() as u64;
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut millis)
+                                    .range(10..=2000)
+                                    .suffix(tr!("unit-ms")),
+                            )
+                            .on_hover_text(tr!("timecode-chase-hint"))
+                            .changed()
+                        {
+                            settings.chase_tolerance = Duration::from_millis(millis);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("timecode-late-join"));
+                        let mut secs = settings.late_join.as_secs_f32();
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut secs)
+                                    .range(0.0..=600.0)
+                                    .speed(0.1)
+                                    .suffix(tr!("unit-seconds")),
+                            )
+                            .on_hover_text(tr!("timecode-late-join-hint"))
+                            .changed()
+                        {
+                            settings.late_join = Duration::from_secs_f32(secs);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("timecode-late-cue"));
+                        let mut millis = settings.late_cue.as_millis() as u64;
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut millis)
+                                    .range(10..=2000)
+                                    .suffix(tr!("unit-ms")),
+                            )
+                            .on_hover_text(tr!("timecode-late-cue-hint"))
+                            .changed()
+                        {
+                            settings.late_cue = Duration::from_millis(millis);
+                        }
+                        ui.end_row();
+
+                        ui.label(tr!("timecode-incoming"));
+                        match self.timecode_in {
+                            Some((incoming, _)) => {
+                                let show = timecode::format(
+                                    settings.start + settings.show_position(incoming.position),
+                                );
+                                ui.label(if incoming.rolling {
+                                    tr!(
+                                        "timecode-incoming-rolling",
+                                        position = timecode::format(incoming.position),
+                                        fps = incoming.fps.to_string(),
+                                        show = show
+                                    )
+                                } else {
+                                    tr!(
+                                        "timecode-incoming-stopped",
+                                        position = timecode::format(incoming.position),
+                                        fps = incoming.fps.to_string(),
+                                        show = show
+                                    )
+                                });
+                            }
+                            None => {
+                                ui.label(tr!("timecode-none"));
+                            }
+                        }
+                        ui.end_row();
+                    });
+                if !self.preferences.midi_timecode_in {
+                    ui.label(tr!("timecode-turn-on-chase"));
+                }
+            });
+        self.show_timecode = open;
+    }
+}
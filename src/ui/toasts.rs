@@ -0,0 +1,138 @@
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::ui::accessibility;
+use crate::HaloApp;
+use eframe::egui;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a toast stays up.
+const TOAST_TIME: Duration = Duration::from_secs(6);
+
+/// Oldest notifications are dropped from the history past this many.
+const MAX_HISTORY: usize = 200;
+
+pub(crate) struct Notification {
+    level: LogLevel,
+    message: String,
+    time: chrono::DateTime<chrono::Local>,
+    shown: Instant,
+}
+
+/// Short-lived notices in the corner of the window, for things the operator
+/// should see mid-show without opening the log console, and the history of
+/// them behind the bell in the status bar.
+#[derive(Default)]
+pub(crate) struct Toasts {
+    history: VecDeque<Notification>,
+    /// How many of the newest notifications are still up as toasts.
+    showing: usize,
+    /// Notifications since the history was last opened.
+    pub(crate) unread: usize,
+}
+
+fn level_color(ui: &egui::Ui, level: LogLevel) -> egui::Color32 {
+    match level {
+        LogLevel::Info => ui.visuals().text_color(),
+        LogLevel::Warning => egui::Color32::YELLOW,
+        LogLevel::Error => egui::Color32::RED,
+    }
+}
+
+impl HaloApp {
+    /// Logs `message` and shows it as a toast.
+    pub(crate) fn notify(&mut self, level: LogLevel, message: impl Into<String>) {
+        let message = message.into();
+        self.log.push(level, message.clone());
+        let toasts = &mut self.toasts;
+        if toasts.history.len() == MAX_HISTORY {
+            toasts.history.pop_front();
+        }
+        toasts.history.push_back(Notification {
+            level,
+            message,
+            time: chrono::Local::now(),
+            shown: Instant::now(),
+        });
+        toasts.showing = (toasts.showing + 1).min(toasts.history.len());
+        toasts.unread += 1;
+    }
+
+    pub(crate) fn draw_toasts(&mut self, ctx: &egui::Context) {
+        let toasts = &mut self.toasts;
+        let live = toasts
+            .history
+            .iter()
+            .rev()
+            .take(toasts.showing)
+            .take_while(|toast| toast.shown.elapsed() < TOAST_TIME)
+            .count();
+        toasts.showing = live;
+        if toasts.showing == 0 {
+            return;
+        }
+        let mut dismiss = false;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -40.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let first = toasts.history.len() - toasts.showing;
+                for toast in toasts.history.range(first..) {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(&toast.message)
+                                    .color(level_color(ui, toast.level)),
+                            );
+                            if accessibility::named(ui.small_button("✖"), tr!("toasts-dismiss-all"))
+                                .on_hover_text(tr!("toasts-dismiss-all"))
+                                .clicked()
+                            {
+                                dismiss = true;
+                            }
+                        });
+                    });
+                }
+            });
+        if dismiss {
+            toasts.showing = 0;
+        }
+        ctx.request_repaint_after(Duration::from_millis(500));
+    }
+
+    /// Every notification since Halo started, newest first.
+    pub(crate) fn draw_notifications_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_notifications;
+        let toasts = &mut self.toasts;
+        egui::Window::new(tr!("status-notifications"))
+            .id(egui::Id::new("notifications"))
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                toasts.unread = 0;
+                if toasts.history.is_empty() {
+                    ui.label(tr!("toasts-empty"));
+                    return;
+                }
+                if ui.button(tr!("clear")).clicked() {
+                    toasts.history.clear();
+                    toasts.showing = 0;
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for notification in toasts.history.iter().rev() {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{}  {}",
+                                notification.time.format("%H:%M:%S"),
+                                notification.message
+                            ))
+                            .monospace()
+                            .color(level_color(ui, notification.level)),
+                        );
+                    }
+                });
+            });
+        self.show_notifications = open;
+    }
+}
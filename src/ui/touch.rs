@@ -0,0 +1,67 @@
+use crate::HaloApp;
+use eframe::egui;
+
+/// How long a press has to be held to show a hint in touch mode.
+const LONG_PRESS: f64 = 0.5;
+
+/// Pad size with and without touch mode.
+const PAD_SIZE: f32 = 80.0;
+const TOUCH_PAD_SIZE: f32 = 120.0;
+
+/// Attaches a hint to `response`: a hover tooltip normally, or in touch mode,
+/// where there is no hover, a tooltip shown while the widget is long-pressed.
+pub(crate) fn hint(
+    response: egui::Response,
+    touch_mode: bool,
+    text: impl Into<egui::WidgetText>,
+) -> egui::Response {
+    if !touch_mode {
+        return response.on_hover_text(text);
+    }
+    if !response.is_pointer_button_down_on() {
+        return response;
+    }
+    let held = response.ctx.input(|input| {
+        input
+            .pointer
+            .press_start_time()
+            .map(|start| input.time - start)
+    });
+    match held {
+        Some(held) if held >= LONG_PRESS => response.show_tooltip_text(text),
+        Some(held) => response
+            .ctx
+            .request_repaint_after_secs((LONG_PRESS - held) as f32),
+        None => {}
+    }
+    response
+}
+
+impl HaloApp {
+    /// Applies the interface scale and, in touch mode, larger hit targets.
+    pub(crate) fn apply_ui_scale(&self, ctx: &egui::Context) {
+        ctx.set_zoom_factor(self.preferences.ui_scale);
+        let touch_mode = self.preferences.touch_mode;
+        ctx.all_styles_mut(|style| {
+            style.spacing = egui::style::Spacing::default();
+            if touch_mode {
+                style.spacing.interact_size = egui::vec2(48.0, 40.0);
+                style.spacing.button_padding = egui::vec2(14.0, 10.0);
+                style.spacing.item_spacing = egui::vec2(12.0, 10.0);
+                style.spacing.icon_width = 24.0;
+                style.spacing.icon_width_inner = 14.0;
+                style.spacing.slider_width = 200.0;
+                style.spacing.combo_height = 400.0;
+                style.spacing.scroll.bar_width = 18.0;
+            }
+        });
+    }
+
+    pub(crate) fn pad_size(&self) -> f32 {
+        if self.preferences.touch_mode {
+            TOUCH_PAD_SIZE
+        } else {
+            PAD_SIZE
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::tracking::{self, EditMode, TrackedState, ValueState};
+use crate::HaloApp;
+use eframe::egui;
+
+const BLOCKED: egui::Color32 = egui::Color32::from_rgb(230, 120, 120);
+
+/// A tracksheet cell being typed into.
+pub(crate) struct CellEdit {
+    cue: usize,
+    fixture_id: usize,
+    attribute: Attribute,
+    text: String,
+    /// What the cell showed, so leaving it unchanged doesn't store a value.
+    original: String,
+}
+
+/// Picks whether edits track on or stay in the cue edited.
+pub(crate) fn draw_edit_mode(ui: &mut egui::Ui, mode: &mut EditMode) {
+    ui.horizontal(|ui| {
+        ui.label(tr!("tracksheet-edit"));
+        for option in EditMode::ALL {
+            ui.selectable_value(mode, option, option.to_string());
+        }
+    })
+    .response
+    .on_hover_text(tr!("tracksheet-edit-hint"));
+}
+
+/// The value a cue outputs for one attribute, styled by where it came
+/// from.
+fn draw_cell(
+    ui: &mut egui::Ui,
+    state: &TrackedState,
+    fixture: &Fixture,
+    attribute: Attribute,
+    cue_number: impl Fn(usize) -> String,
+) -> egui::Response {
+    let Some(tracked) = state.get(&(fixture.id, attribute)) else {
+        return ui
+            .add(egui::Label::new(egui::RichText::new("—").weak()).sense(egui::Sense::click()))
+            .on_hover_text(tr!("tracksheet-never-set"));
+    };
+    let text = tracked.value.to_string();
+    let (cell, hint) = match tracked.state {
+        ValueState::Move => (
+            egui::RichText::new(text).strong(),
+            tr!("tracksheet-set-here"),
+        ),
+        ValueState::Blocked => (
+            egui::RichText::new(text).color(BLOCKED),
+            tr!("tracksheet-blocked"),
+        ),
+        ValueState::Tracked => (
+            egui::RichText::new(text).weak(),
+            tr!("tracksheet-tracks-from", number = cue_number(tracked.cue)),
+        ),
+    };
+    ui.add(egui::Label::new(cell).sense(egui::Sense::click()))
+        .on_hover_text(hint)
+}
+
+impl HaloApp {
+    /// Every cue's output for the chosen fixtures, one column per attribute,
+    /// showing which cue set each value and where it tracks. Cells are
+    /// edited in place.
+    pub(crate) fn draw_tracksheet(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_tracksheet;
+        let mut commit = None;
+        egui::Window::new(tr!("tracksheet-title"))
+            .id(egui::Id::new("tracksheet"))
+            .open(&mut open)
+            .default_size([720.0, 400.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("fixtures-label"));
+                    let selected = self
+                        .tracksheet_fixture
+                        .and_then(|id| self.show.fixtures.iter().find(|f| f.id == id))
+                        .map_or_else(|| tr!("all"), |f| f.name.clone());
+                    egui::ComboBox::from_id_salt("tracksheet_fixture")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.tracksheet_fixture, None, tr!("all"));
+                            for fixture in &self.show.fixtures {
+                                ui.selectable_value(
+                                    &mut self.tracksheet_fixture,
+                                    Some(fixture.id),
+                                    &fixture.name,
+                                );
+                            }
+                        });
+                    ui.separator();
+                    draw_edit_mode(ui, &mut self.edit_mode);
+                });
+                ui.horizontal(|ui| {
+                    ui.strong("255");
+                    ui.label(tr!("tracksheet-legend-changed"));
+                    ui.weak("255");
+                    ui.label(tr!("tracksheet-legend-tracked"));
+                    ui.colored_label(BLOCKED, "255");
+                    ui.label(tr!("tracksheet-legend-blocked"));
+                    ui.separator();
+                    ui.weak(tr!("tracksheet-hint"));
+                });
+                ui.separator();
+
+                let fixtures: Vec<&Fixture> = self
+                    .show
+                    .fixtures
+                    .iter()
+                    .filter(|f| self.tracksheet_fixture.is_none_or(|id| f.id == id))
+                    .collect();
+                let columns: Vec<(&Fixture, Attribute)> = fixtures
+                    .iter()
+                    .flat_map(|&fixture| {
+                        fixture
+                            .profile
+                            .channels
+                            .iter()
+                            .map(move |&attribute| (fixture, attribute))
+                    })
+                    .collect();
+                let cues = &self.show.cues;
+                let history = tracking::history(cues, &self.show.playback_order());
+                let cue_number = |index: usize| cues[index].number.to_string();
+                egui::ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        egui::Grid::new("tracksheet")
+                            .striped(true)
+                            .num_columns(columns.len() + 1)
+                            .show(ui, |ui| {
+                                ui.label("");
+                                let mut previous = None;
+                                for (fixture, _) in &columns {
+                                    if previous == Some(fixture.id) {
+                                        ui.label("");
+                                    } else {
+                                        ui.strong(&fixture.name);
+                                    }
+                                    previous = Some(fixture.id);
+                                }
+                                ui.end_row();
+
+                                ui.strong(tr!("session-cue"));
+                                for (_, attribute) in &columns {
+                                    ui.strong(attribute.to_string());
+                                }
+                                ui.end_row();
+
+                                for (index, (cue, state)) in cues.iter().zip(&history).enumerate() {
+                                    let label = if cue.block {
+                                        format!("{} {} (B)", cue.number, cue.name)
+                                    } else {
+                                        format!("{} {}", cue.number, cue.name)
+                                    };
+                                    ui.label(label);
+                                    for &(fixture, attribute) in &columns {
+                                        let editing = self.tracksheet_edit.as_mut().filter(|e| {
+                                            e.cue == index
+                                                && e.fixture_id == fixture.id
+                                                && e.attribute == attribute
+                                        });
+                                        if let Some(edit) = editing {
+                                            let response = ui.add(
+                                                egui::TextEdit::singleline(&mut edit.text)
+                                                    .desired_width(36.0),
+                                            );
+                                            response.request_focus();
+                                            if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                                self.tracksheet_edit = None;
+                                            } else if response.lost_focus() {
+                                                commit = self.tracksheet_edit.take();
+                                            }
+                                            continue;
+                                        }
+                                        let response =
+                                            draw_cell(ui, state, fixture, attribute, cue_number);
+                                        if response.double_clicked() {
+                                            let text = state
+                                                .get(&(fixture.id, attribute))
+                                                .map_or_else(String::new, |t| t.value.to_string());
+                                            self.tracksheet_edit = Some(CellEdit {
+                                                cue: index,
+                                                fixture_id: fixture.id,
+                                                attribute,
+                                                original: text.clone(),
+                                                text,
+                                            });
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        self.show_tracksheet = open;
+        if !open {
+            self.tracksheet_edit = None;
+        }
+        if let Some(edit) = commit {
+            self.commit_cell_edit(edit);
+        }
+    }
+
+    /// Writes a tracksheet cell into its cue, tracking or not as the edit
+    /// mode says. An empty cell drops the cue's value so it tracks.
+    fn commit_cell_edit(&mut self, edit: CellEdit) {
+        let text = edit.text.trim();
+        if text == edit.original {
+            return;
+        }
+        let value = if text.is_empty() {
+            None
+        } else {
+            match text.parse::<u8>() {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    self.log.warn(tr!("tracksheet-bad-level", text = text));
+                    return;
+                }
+            }
+        };
+        let order = self.show.playback_order();
+        tracking::edit_cue(
+            &mut self.show.cues,
+            &order,
+            edit.cue,
+            self.edit_mode,
+            |cue| match value {
+                Some(value) => cue.set_value(edit.fixture_id, edit.attribute, value),
+                None => cue.clear_value(edit.fixture_id, edit.attribute),
+            },
+        );
+    }
+}
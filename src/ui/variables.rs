@@ -0,0 +1,89 @@
+use crate::i18n::tr;
+use crate::ui::accessibility;
+use crate::variable::Value;
+use crate::HaloApp;
+use eframe::egui;
+
+impl HaloApp {
+    /// The show variables cue conditions test, each editable in place.
+    pub(crate) fn draw_variables_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_variables;
+        let mut remove = None;
+        egui::Window::new(tr!("variables-title"))
+            .id(egui::Id::new("variables"))
+            .open(&mut open)
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                ui.label(tr!("variables-description"));
+                ui.separator();
+                egui::Grid::new("variables_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (name, value) in &mut self.show.variables {
+                            ui.monospace(name);
+                            match value {
+                                Value::Bool(flag) => {
+                                    ui.checkbox(flag, flag.to_string());
+                                }
+                                value => {
+                                    let mut text = value.text();
+                                    if ui.text_edit_singleline(&mut text).changed() {
+                                        *value = Value::parse(&text);
+                                    }
+                                }
+                            }
+                            if accessibility::named(ui.small_button("🗑"), tr!("remove")).clicked()
+                            {
+                                remove = Some(name.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+                let users = |name: &str| {
+                    self.show
+                        .cues
+                        .iter()
+                        .filter(|cue| cue.condition.as_ref().is_some_and(|c| c.variable == name))
+                        .count()
+                };
+                if let Some(name) = &remove {
+                    if users(name) > 0 {
+                        self.log.warn(tr!(
+                            "variables-still-used",
+                            count = users(name),
+                            name = name.as_str()
+                        ));
+                    }
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_variable.0)
+                            .hint_text("encore")
+                            .desired_width(100.0),
+                    );
+                    ui.label("=");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_variable.1)
+                            .hint_text("true")
+                            .desired_width(100.0),
+                    );
+                    let name = self.new_variable.0.trim().to_string();
+                    let valid = !name.is_empty() && !name.contains(char::is_whitespace);
+                    if ui
+                        .add_enabled(valid, egui::Button::new(tr!("add")))
+                        .clicked()
+                    {
+                        let value = Value::parse(&self.new_variable.1);
+                        self.show.variables.insert(name, value);
+                        self.new_variable = Default::default();
+                    }
+                });
+            });
+        if let Some(name) = remove {
+            self.show.variables.remove(&name);
+        }
+        self.show_variables = open;
+    }
+}
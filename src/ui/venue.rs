@@ -0,0 +1,230 @@
+use crate::i18n::tr;
+use crate::ui::accessibility;
+use crate::venue::{VenueMap, VenueUnit, EXTENSION};
+use crate::HaloApp;
+use eframe::egui;
+use std::path::PathBuf;
+
+/// The mapping file being edited.
+#[derive(Default)]
+pub(crate) struct VenueTool {
+    path: String,
+    error: Option<String>,
+}
+
+impl VenueTool {
+    pub(crate) fn new(path: Option<&PathBuf>) -> Self {
+        Self {
+            path: path
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            error: None,
+        }
+    }
+}
+
+impl HaloApp {
+    /// Loads, edits and saves the venue mapping that binds the show's
+    /// logical fixtures to the units in this venue's rig.
+    pub(crate) fn draw_venue_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_venue;
+        let mut load = false;
+        let mut save = false;
+        let mut clear = false;
+        egui::Window::new(tr!("venue-title"))
+            .id(egui::Id::new("venue"))
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label(tr!("venue-description"));
+                let tool = &mut self.venue_tool;
+                ui.horizontal(|ui| {
+                    ui.label(tr!("file-label"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut tool.path)
+                            .hint_text(format!("venue.{}", EXTENSION)),
+                    );
+                    load = ui.button(tr!("load")).clicked();
+                    save = ui
+                        .add_enabled(self.venue.is_some(), egui::Button::new(tr!("save")))
+                        .clicked();
+                });
+                if let Some(error) = &tool.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr!("venue-new-from-patch"))
+                        .on_hover_text(tr!("venue-new-from-patch-hint"))
+                        .clicked()
+                    {
+                        self.venue = Some(VenueMap::from_patch(
+                            &tr!("venue-new-name"),
+                            &self.show.fixtures,
+                        ));
+                    }
+                    clear = ui
+                        .add_enabled(
+                            self.venue.is_some(),
+                            egui::Button::new(tr!("venue-use-show-patch")),
+                        )
+                        .on_hover_text(tr!("venue-use-show-patch-hint"))
+                        .clicked();
+                });
+                ui.separator();
+
+                let Some(venue) = &mut self.venue else {
+                    ui.label(tr!("venue-none"));
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    ui.label(tr!("venue-label"));
+                    ui.text_edit_singleline(&mut venue.name);
+                });
+                let mut remove = None;
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("venue_units")
+                            .num_columns(5)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong(tr!("fixture"));
+                                ui.strong(tr!("universe"));
+                                ui.strong(tr!("address"));
+                                ui.strong(tr!("type"));
+                                ui.end_row();
+                                for (i, unit) in venue.units.iter_mut().enumerate() {
+                                    egui::ComboBox::from_id_salt(("venue_fixture", i))
+                                        .selected_text(&unit.fixture)
+                                        .show_ui(ui, |ui| {
+                                            for fixture in &self.show.fixtures {
+                                                ui.selectable_value(
+                                                    &mut unit.fixture,
+                                                    fixture.name.clone(),
+                                                    &fixture.name,
+                                                );
+                                            }
+                                        });
+                                    ui.add(egui::DragValue::new(&mut unit.universe));
+                                    ui.add(egui::DragValue::new(&mut unit.address).range(1..=512));
+                                    let selected = unit.profile.as_ref().map_or_else(
+                                        || tr!("venue-as-programmed"),
+                                        |p| p.name.clone(),
+                                    );
+                                    egui::ComboBox::from_id_salt(("venue_profile", i))
+                                        .selected_text(selected)
+                                        .show_ui(ui, |ui| {
+                                            ui.selectable_value(
+                                                &mut unit.profile,
+                                                None,
+                                                tr!("venue-as-programmed"),
+                                            );
+                                            for profile in &self.profile_library {
+                                                ui.selectable_value(
+                                                    &mut unit.profile,
+                                                    Some(profile.clone()),
+                                                    &profile.name,
+                                                );
+                                            }
+                                        });
+                                    if accessibility::named(ui.small_button("🗑"), tr!("remove"))
+                                        .clicked()
+                                    {
+                                        remove = Some(i);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                if let Some(i) = remove {
+                    venue.units.remove(i);
+                }
+                if ui.button(tr!("venue-add-unit")).clicked() {
+                    let last = venue.units.last();
+                    venue.units.push(VenueUnit {
+                        fixture: last.map_or_else(
+                            || {
+                                self.show
+                                    .fixtures
+                                    .first()
+                                    .map(|f| f.name.clone())
+                                    .unwrap_or_default()
+                            },
+                            |unit| unit.fixture.clone(),
+                        ),
+                        universe: last.map_or(1, |unit| unit.universe),
+                        address: 1,
+                        profile: None,
+                    });
+                }
+
+                let unmapped = venue.unmapped(&self.show.fixtures);
+                if !unmapped.is_empty() {
+                    let names: Vec<&str> = unmapped.iter().map(|f| f.name.as_str()).collect();
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 180, 0),
+                        tr!("venue-unmapped", fixtures = names.join(", ")),
+                    );
+                }
+                let unknown = venue.unknown(&self.show.fixtures);
+                if !unknown.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 180, 0),
+                        tr!("venue-unknown", fixtures = unknown.join(", ")),
+                    );
+                }
+            });
+        self.show_venue = open;
+
+        if load {
+            self.load_venue();
+        }
+        if save {
+            self.save_venue();
+        }
+        if clear {
+            self.venue = None;
+            self.preferences.venue_file = None;
+            let _ = self.preferences.save();
+            self.log.info(tr!("venue-cleared"));
+        }
+    }
+
+    fn load_venue(&mut self) {
+        let path = PathBuf::from(self.venue_tool.path.trim());
+        match VenueMap::load(&path) {
+            Ok(_) => {
+                self.venue_tool.error = None;
+                self.preferences.venue_file = Some(path);
+                let _ = self.preferences.save();
+                self.apply_venue_preferences();
+            }
+            Err(err) => {
+                self.venue_tool.error = Some(tr!("venue-load-failed", error = err.to_string()));
+            }
+        }
+    }
+
+    fn save_venue(&mut self) {
+        let Some(venue) = &self.venue else {
+            return;
+        };
+        let path = PathBuf::from(self.venue_tool.path.trim());
+        match venue.save(&path) {
+            Ok(()) => {
+                self.venue_tool.error = None;
+                self.log.info(tr!(
+                    "venue-saved",
+                    name = venue.name.as_str(),
+                    path = path.display().to_string()
+                ));
+                self.preferences.venue_file = Some(path);
+                let _ = self.preferences.save();
+            }
+            Err(err) => {
+                self.venue_tool.error = Some(tr!("venue-save-failed", error = err.to_string()));
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+
+/// A panel that can be detached into its own OS window, to spread Halo over
+/// several monitors.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Panel {
+    CueList,
+    OutputMonitor,
+    Visualizer,
+}
+
+impl Panel {
+    fn title(self) -> String {
+        match self {
+            Panel::CueList => tr!("panel-cue-list"),
+            Panel::OutputMonitor => tr!("menu-output-monitor"),
+            Panel::Visualizer => tr!("menu-visualizer"),
+        }
+    }
+
+    /// Stays the same whatever the language.
+    fn id(self) -> &'static str {
+        match self {
+            Panel::CueList => "cue_list",
+            Panel::OutputMonitor => "output_monitor",
+            Panel::Visualizer => "visualizer",
+        }
+    }
+
+    fn viewport_id(self) -> egui::ViewportId {
+        egui::ViewportId::from_hash_of(self.id())
+    }
+}
+
+impl HaloApp {
+    pub(crate) fn is_detached(&self, panel: Panel) -> bool {
+        self.detached.contains(&panel)
+    }
+
+    pub(crate) fn set_detached(&mut self, panel: Panel, detached: bool) {
+        self.detached.retain(|p| *p != panel);
+        if detached {
+            self.detached.push(panel);
+        }
+    }
+
+    fn draw_panel(&mut self, ui: &mut egui::Ui, panel: Panel) {
+        match panel {
+            Panel::CueList => self.draw_cue_list(ui),
+            Panel::OutputMonitor => self.draw_output_monitor(ui),
+            Panel::Visualizer => self.draw_visualizer(ui),
+        }
+    }
+
+    /// The output monitor and visualizer as windows inside the main window,
+    /// when they aren't detached.
+    pub(crate) fn draw_panel_windows(&mut self, ctx: &egui::Context) {
+        for (panel, mut open) in [
+            (Panel::OutputMonitor, self.show_output_monitor),
+            (Panel::Visualizer, self.show_visualizer),
+        ] {
+            if !open || self.is_detached(panel) {
+                continue;
+            }
+            let mut detach = false;
+            egui::Window::new(panel.title())
+                .id(egui::Id::new(panel.id()))
+                .open(&mut open)
+                .default_width(560.0)
+                .show(ctx, |ui| {
+                    if ui.button(tr!("panel-detach")).clicked() {
+                        detach = true;
+                    }
+                    self.draw_panel(ui, panel);
+                });
+            match panel {
+                Panel::OutputMonitor => self.show_output_monitor = open,
+                Panel::Visualizer => self.show_visualizer = open,
+                Panel::CueList => {}
+            }
+            if detach {
+                self.set_detached(panel, true);
+            }
+        }
+    }
+
+    /// Detached panels, each in its own OS window. Closing one puts the panel
+    /// back in the main window. Backends without multi-viewport support get an
+    /// ordinary window instead.
+    pub(crate) fn draw_detached_panels(&mut self, ctx: &egui::Context) {
+        for panel in self.detached.clone() {
+            let builder = egui::ViewportBuilder::default()
+                .with_title(tr!("panel-window-title", panel = panel.title()))
+                .with_inner_size([640.0, 480.0]);
+            let dock = ctx.show_viewport_immediate(panel.viewport_id(), builder, |ctx, class| {
+                if class == egui::ViewportClass::Embedded {
+                    let mut open = true;
+                    egui::Window::new(panel.title())
+                        .id(egui::Id::new(panel.id()))
+                        .open(&mut open)
+                        .show(ctx, |ui| self.draw_panel(ui, panel));
+                    return !open;
+                }
+                egui::CentralPanel::default().show(ctx, |ui| self.draw_panel(ui, panel));
+                ctx.input(|input| input.viewport().close_requested())
+            });
+            if dock {
+                self.set_detached(panel, false);
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::fixture::{Attribute, Fixture};
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use std::f32::consts::TAU;
+
+const CELL: f32 = 90.0;
+
+impl HaloApp {
+    /// A plan view of the rig, each fixture drawn in the colour it is
+    /// outputting. Moving heads also show where they are pointing, and
+    /// hovering a fixture shows its live values.
+    pub(crate) fn draw_visualizer(&mut self, ui: &mut egui::Ui) {
+        if self.show.fixtures.is_empty() {
+            ui.label(tr!("visualizer-empty"));
+            return;
+        }
+
+        let columns = ((ui.available_width() / CELL) as usize).max(1);
+        let rows = self.show.fixtures.len().div_ceil(columns);
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let (rect, response) = ui.allocate_exact_size(
+                egui::vec2(columns as f32 * CELL, rows as f32 * CELL),
+                egui::Sense::hover(),
+            );
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 4.0, egui::Color32::from_gray(12));
+
+            for (i, fixture) in self.show.fixtures.iter().enumerate() {
+                let center = rect.min
+                    + egui::vec2(
+                        ((i % columns) as f32 + 0.5) * CELL,
+                        ((i / columns) as f32 + 0.45) * CELL,
+                    );
+                let radius = CELL * 0.3;
+                painter.circle(
+                    center,
+                    radius,
+                    self.fixture_color(fixture),
+                    egui::Stroke::new(1.0, egui::Color32::from_gray(90)),
+                );
+
+                let unit = self.on_rig(fixture);
+                let pan = unit.channel_for(Attribute::Pan);
+                let tilt = unit.channel_for(Attribute::Tilt);
+                if let (Some(pan), Some(tilt)) = (pan, tilt) {
+                    let angle = self.output.get(unit.universe, pan) as f32 / 255.0 * TAU;
+                    let reach = self.output.get(unit.universe, tilt) as f32 / 255.0 * radius;
+                    painter.line_segment(
+                        [
+                            center,
+                            center + egui::vec2(angle.cos(), angle.sin()) * reach,
+                        ],
+                        egui::Stroke::new(2.0, egui::Color32::WHITE),
+                    );
+                }
+
+                painter.text(
+                    center + egui::vec2(0.0, radius + 4.0),
+                    egui::Align2::CENTER_TOP,
+                    &fixture.name,
+                    egui::FontId::proportional(11.0),
+                    egui::Color32::from_gray(200),
+                );
+            }
+
+            let hovered = response.hover_pos().and_then(|pointer| {
+                let offset = pointer - rect.min;
+                let index = (offset.y / CELL) as usize * columns + (offset.x / CELL) as usize;
+                ((offset.x / CELL) < columns as f32)
+                    .then(|| self.show.fixtures.get(index))
+                    .flatten()
+            });
+            if let Some(fixture) = hovered {
+                response.on_hover_ui_at_pointer(|ui| self.draw_fixture_inspection(ui, fixture));
+            }
+        });
+    }
+
+    fn fixture_color(&self, fixture: &Fixture) -> egui::Color32 {
+        let [r, g, b] = self.on_rig(fixture).output_color(&self.output);
+        egui::Color32::from_rgb(r, g, b)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::i18n::tr;
+use crate::HaloApp;
+use eframe::egui;
+use std::time::Duration;
+
+/// How long the warning stays up after the engine is restarted.
+const WARNING_TIME: Duration = Duration::from_secs(15);
+
+impl HaloApp {
+    /// A flashing banner across the top of the window after the watchdog
+    /// restarts the output engine. Clicking it dismisses it.
+    pub(crate) fn draw_engine_warning(&mut self, ctx: &egui::Context) {
+        let Some(restarted) = self.engine_restarted else {
+            return;
+        };
+        if restarted.elapsed() > WARNING_TIME {
+            self.engine_restarted = None;
+            return;
+        }
+        let lit = restarted.elapsed().as_millis() / 500 % 2 == 0;
+        let fill = if lit {
+            egui::Color32::from_rgb(200, 0, 0)
+        } else {
+            egui::Color32::from_rgb(110, 0, 0)
+        };
+        egui::TopBottomPanel::top("engine_warning")
+            .frame(egui::Frame::default().fill(fill).inner_margin(6.0))
+            .show(ctx, |ui| {
+                let text = if self.engine_restarts > 1 {
+                    tr!("watchdog-restarted-times", count = self.engine_restarts)
+                } else {
+                    tr!("watchdog-restarted")
+                };
+                let response = ui
+                    .vertical_centered(|ui| {
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(text)
+                                    .strong()
+                                    .size(18.0)
+                                    .color(egui::Color32::WHITE),
+                            )
+                            .sense(egui::Sense::click()),
+                        )
+                    })
+                    .inner;
+                if response.on_hover_text(tr!("watchdog-dismiss")).clicked() {
+                    self.engine_restarted = None;
+                }
+            });
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+}
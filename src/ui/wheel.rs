@@ -0,0 +1,93 @@
+use crate::fixture::{Attribute, FixtureProfile, WheelSlot};
+use eframe::egui;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Size slot pictures are drawn at in the picker.
+const THUMBNAIL: f32 = 24.0;
+
+/// Slot pictures loaded so far, by path. A picture that couldn't be read is
+/// remembered as `None` so it isn't tried again every frame.
+#[derive(Default)]
+pub(crate) struct SlotImages(HashMap<PathBuf, Option<egui::TextureHandle>>);
+
+impl SlotImages {
+    fn get(&mut self, ctx: &egui::Context, path: &Path) -> Option<egui::TextureHandle> {
+        self.0
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                let image = image::open(path).ok()?.into_rgba8();
+                let size = [image.width() as usize, image.height() as usize];
+                let pixels = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+                Some(ctx.load_texture(path.to_string_lossy(), pixels, Default::default()))
+            })
+            .clone()
+    }
+}
+
+/// A slot's picture, or its colour on a colour wheel.
+fn slot_thumbnail(ui: &mut egui::Ui, slot: &WheelSlot, images: &mut SlotImages) {
+    let texture = slot
+        .image
+        .as_deref()
+        .and_then(|path| images.get(ui.ctx(), path));
+    match (texture, slot.color) {
+        (Some(texture), _) => {
+            ui.add(egui::Image::new(&texture).fit_to_exact_size(egui::Vec2::splat(THUMBNAIL)));
+        }
+        (None, Some([r, g, b])) => {
+            let (rect, _) =
+                ui.allocate_exact_size(egui::Vec2::splat(THUMBNAIL), egui::Sense::hover());
+            ui.painter()
+                .rect_filled(rect.shrink(4.0), 3.0, egui::Color32::from_rgb(r, g, b));
+        }
+        (None, None) => {
+            ui.allocate_exact_size(egui::Vec2::splat(THUMBNAIL), egui::Sense::hover());
+        }
+    }
+}
+
+/// Picks a level for an attribute by the profile's slot names, falling back
+/// to a plain value for attributes without named slots.
+pub(crate) fn draw_level_picker(
+    ui: &mut egui::Ui,
+    id: impl std::hash::Hash,
+    profile: &FixtureProfile,
+    attribute: Attribute,
+    level: &mut u8,
+    images: &mut SlotImages,
+) -> egui::Response {
+    let slots = profile.slots(attribute);
+    if slots.is_empty() {
+        return ui.add(egui::DragValue::new(level).range(0..=255));
+    }
+    let selected = profile
+        .slot_at(attribute, *level)
+        .map_or_else(|| level.to_string(), |slot| slot.name.clone());
+    let mut changed = false;
+    let mut response = egui::ComboBox::from_id_salt(id)
+        .selected_text(selected)
+        .width(110.0)
+        .show_ui(ui, |ui| {
+            for slot in slots {
+                let current = profile
+                    .slot_at(attribute, *level)
+                    .is_some_and(|at| at.value == slot.value);
+                let row = ui
+                    .horizontal(|ui| {
+                        slot_thumbnail(ui, slot, images);
+                        ui.selectable_label(current, &slot.name)
+                    })
+                    .inner;
+                if row.clicked() {
+                    *level = slot.value;
+                    changed = true;
+                }
+            }
+        })
+        .response;
+    if changed {
+        response.mark_changed();
+    }
+    response.on_hover_text(format!("DMX {}", level))
+}
@@ -0,0 +1,113 @@
+//! Checking GitHub for a newer release of Halo. The request is made with the
+//! system's `curl` on a background thread, so a venue with no internet just
+//! gets an error in the About dialog, and it can be turned off altogether in
+//! Preferences.
+
+use crate::i18n::tr;
+use crate::log::LogLevel;
+use crate::HaloApp;
+use eframe::egui;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/robmorgan/halo-tc-ui/releases/latest";
+
+/// The version this build was made from.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A published release.
+#[derive(Clone)]
+pub struct Release {
+    pub version: String,
+    /// The release page, with its downloads.
+    pub url: String,
+}
+
+/// The parts of a version number, ignoring a leading `v` and anything after
+/// a `-`, such as `-beta`.
+fn version_parts(version: &str) -> Vec<u32> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('-').next().unwrap_or_default();
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+fn is_newer(latest: &str, current: &str) -> bool {
+    version_parts(latest) > version_parts(current)
+}
+
+/// Asks GitHub for the latest release, returning it if it is newer than
+/// this build.
+fn fetch_latest() -> Result<Option<Release>, String> {
+    let output = Command::new("curl")
+        .args(["-fsSL", "--max-time", "10"])
+        .args(["-H", "Accept: application/vnd.github+json"])
+        .args(["-A", &format!("Halo/{}", VERSION)])
+        .arg(LATEST_RELEASE_URL)
+        .output()
+        .map_err(|err| tr!("update-curl-failed", error = err.to_string()))?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if error.is_empty() {
+            tr!("update-no-response")
+        } else {
+            error
+        });
+    }
+    let release: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|err| err.to_string())?;
+    let version = release["tag_name"]
+        .as_str()
+        .ok_or_else(|| tr!("update-no-version"))?;
+    let url = release["html_url"].as_str().unwrap_or_default();
+    Ok(is_newer(version, VERSION).then(|| Release {
+        version: version.trim_start_matches('v').to_string(),
+        url: url.to_string(),
+    }))
+}
+
+/// An update check running in the background.
+pub struct UpdateCheck(Receiver<Result<Option<Release>, String>>);
+
+impl UpdateCheck {
+    pub fn start(ctx: egui::Context) -> Self {
+        let (sender, result) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(fetch_latest());
+            ctx.request_repaint();
+        });
+        Self(result)
+    }
+}
+
+impl HaloApp {
+    pub(crate) fn check_for_updates(&mut self, ctx: &egui::Context) {
+        if self.update_check.is_none() {
+            self.update_check = Some(UpdateCheck::start(ctx.clone()));
+        }
+    }
+
+    /// Picks up the result of the update check, telling the operator when
+    /// there is a new release.
+    pub(crate) fn handle_update_check(&mut self) {
+        let Some(result) = self
+            .update_check
+            .as_ref()
+            .and_then(|check| check.0.try_recv().ok())
+        else {
+            return;
+        };
+        self.update_check = None;
+        if let Ok(Some(release)) = &result {
+            self.notify(
+                LogLevel::Info,
+                tr!("update-available", version = release.version.as_str()),
+            );
+        }
+        self.update_status = Some(result);
+    }
+}
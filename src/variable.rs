@@ -0,0 +1,150 @@
+//! Show variables and the conditions cues carry on them, so one show file can
+//! tour different rigs and running orders: a cue conditioned on `encore =
+//! true` only plays when the variable says so, and is treated as disarmed
+//! otherwise. Variables are set in the Variables window or over OSC at
+//! `/halo/var/<name>`.
+
+use crate::i18n::tr;
+use crate::osc::{OscArg, OscMessage};
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The OSC address prefix that sets a variable, followed by its name.
+pub const OSC_PREFIX: &str = "/halo/var/";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Value {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl Value {
+    /// Reads a value as typed: `true` and `false`, a number, or text, with
+    /// or without quotes.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        match text {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => match text.parse() {
+                Ok(number) => Value::Number(number),
+                Err(_) => Value::Text(
+                    text.strip_prefix('"')
+                        .and_then(|text| text.strip_suffix('"'))
+                        .unwrap_or(text)
+                        .to_string(),
+                ),
+            },
+        }
+    }
+
+    /// The value as typed, without quotes round text.
+    pub fn text(&self) -> String {
+        match self {
+            Value::Text(text) => text.clone(),
+            value => value.to_string(),
+        }
+    }
+
+    /// The value an OSC argument carries.
+    pub fn from_osc(arg: &OscArg) -> Self {
+        match arg {
+            OscArg::Int(value) => Value::Number(*value as f64),
+            OscArg::Float(value) => Value::Number(*value as f64),
+            OscArg::Str(text) => Value::parse(text),
+        }
+    }
+
+    /// Whether two values match, with `true` and `false` also matching the
+    /// numbers 1 and 0 for controllers that can only send numbers.
+    fn matches(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Bool(flag), Value::Number(number))
+            | (Value::Number(number), Value::Bool(flag)) => *number == f64::from(u8::from(*flag)),
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(flag) => write!(f, "{}", flag),
+            Value::Number(number) => write!(f, "{}", number),
+            Value::Text(text) => write!(f, "{:?}", text),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    Is,
+    IsNot,
+}
+
+impl Comparison {
+    pub const ALL: [Comparison; 2] = [Comparison::Is, Comparison::IsNot];
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Comparison::Is => "=",
+            Comparison::IsNot => "≠",
+        })
+    }
+}
+
+/// A test of one show variable, such as `venue = "A"`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Condition {
+    pub variable: String,
+    pub comparison: Comparison,
+    pub value: Value,
+}
+
+impl Condition {
+    /// Whether the condition holds. A variable that isn't set matches
+    /// nothing, so `encore = true` fails and `encore ≠ true` holds.
+    pub fn holds(&self, variables: &BTreeMap<String, Value>) -> bool {
+        let matches = variables
+            .get(&self.variable)
+            .is_some_and(|value| value.matches(&self.value));
+        match self.comparison {
+            Comparison::Is => matches,
+            Comparison::IsNot => !matches,
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.variable, self.comparison, self.value)
+    }
+}
+
+impl HaloApp {
+    /// Sets a variable from an OSC message to `/halo/var/<name>`. Returns
+    /// whether the message was one.
+    pub(crate) fn set_variable_from_osc(&mut self, message: &OscMessage) -> bool {
+        let Some(name) = message.address.strip_prefix(OSC_PREFIX) else {
+            return false;
+        };
+        let Some(arg) = message.args.first() else {
+            self.log.warn(tr!(
+                "variables-osc-no-value",
+                address = message.address.as_str()
+            ));
+            return true;
+        };
+        let value = Value::from_osc(arg);
+        self.log
+            .info(tr!("variables-set", name = name, value = value.to_string()));
+        self.show.variables.insert(name.to_string(), value);
+        true
+    }
+}
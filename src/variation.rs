@@ -0,0 +1,68 @@
+//! Variation: a cue option that moves some of its levels by a random amount
+//! each time it fires, so an installation playing the same cues all day
+//! doesn't look the same every time round. The output engine rolls the
+//! amounts as it fires the cue and keeps them until it fires again.
+//!
+//! With a seed the rolls come out the same run after run, the first firing
+//! always getting the first roll; without one they differ every time Halo
+//! starts.
+
+use crate::busk;
+use crate::cue::CueValue;
+use crate::fixture::Attribute;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Variation {
+    /// The attributes that vary; the rest play as programmed.
+    pub attributes: Vec<Attribute>,
+    /// How far a level can move either way, in DMX steps.
+    pub range: u8,
+    /// Each fixture moves by its own amount rather than all together.
+    pub per_fixture: bool,
+    pub seed: Option<u64>,
+}
+
+impl Default for Variation {
+    fn default() -> Self {
+        Self {
+            attributes: vec![Attribute::Pan, Attribute::Tilt],
+            range: 10,
+            per_fixture: true,
+            seed: Some(1),
+        }
+    }
+}
+
+impl Variation {
+    /// `values` as the cue plays them the `firing`th time it fires, from 0.
+    pub fn roll(&self, values: &[CueValue], firing: u64) -> Vec<CueValue> {
+        let seed = match self.seed {
+            Some(seed) => seed.wrapping_mul(1 << 32).wrapping_add(firing),
+            None => RandomState::new().hash_one(firing),
+        };
+        let span = self.range as f32 * 2.0 + 1.0;
+        values
+            .iter()
+            .map(|value| {
+                if !self.attributes.contains(&value.attribute) {
+                    return *value;
+                }
+                let salt = if self.per_fixture {
+                    value.fixture_id as u64 * 64 + value.attribute as u64
+                } else {
+                    value.attribute as u64
+                };
+                let offset = (busk::chance(seed.wrapping_add(salt * 0x1_0000)) * span) as i32
+                    - self.range as i32;
+                CueValue {
+                    value: (value.value as i32 + offset).clamp(0, 255) as u8,
+                    ..*value
+                }
+            })
+            .collect()
+    }
+}
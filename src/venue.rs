@@ -0,0 +1,160 @@
+//! Venue mappings, so a touring show keeps one show file: cues are
+//! programmed on the show's patch, which stands for logical fixtures ("FOH
+//! Wash 1" to "FOH Wash 6"), and each venue's mapping file says which units
+//! in that rig play each one. A logical fixture can map to several units, or
+//! to none where the venue has nothing to put there, and a unit can be a
+//! different type from the one programmed on.
+
+use crate::fixture::{Fixture, FixtureProfile};
+use crate::i18n::tr;
+use crate::HaloApp;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+/// Extension for venue mapping files.
+pub const EXTENSION: &str = "halovenue";
+
+/// A unit in the venue's rig, and the logical fixture it plays.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueUnit {
+    /// Name of the logical fixture in the show's patch.
+    pub fixture: String,
+    pub universe: u16,
+    /// 1-based DMX start address.
+    pub address: u16,
+    /// The unit's profile, where it isn't the type programmed on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<FixtureProfile>,
+}
+
+impl VenueUnit {
+    /// A copy of the logical fixture at this unit's address and with its
+    /// profile. The copy keeps the logical fixture's id, so cue values for
+    /// it play on every unit mapped to it.
+    pub fn play(&self, logical: &Fixture) -> Fixture {
+        let mut fixture = logical.clone();
+        fixture.universe = self.universe;
+        fixture.address = self.address;
+        if let Some(profile) = &self.profile {
+            fixture.profile = profile.clone();
+            // Processing is per channel of the programmed type.
+            fixture.processing.clear();
+        }
+        fixture
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VenueMap {
+    /// The venue, as shown in the status bar.
+    pub name: String,
+    pub units: Vec<VenueUnit>,
+}
+
+impl VenueMap {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), self)?;
+        Ok(())
+    }
+
+    /// A mapping that plays each fixture on the unit it's patched to, to
+    /// start a venue's mapping from.
+    pub fn from_patch(name: &str, fixtures: &[Fixture]) -> Self {
+        Self {
+            name: name.to_string(),
+            units: fixtures
+                .iter()
+                .map(|fixture| VenueUnit {
+                    fixture: fixture.name.clone(),
+                    universe: fixture.universe,
+                    address: fixture.address,
+                    profile: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// The venue's rig: each unit playing its logical fixture.
+    pub fn patch(&self, fixtures: &[Fixture]) -> Vec<Fixture> {
+        self.units
+            .iter()
+            .filter_map(|unit| {
+                let logical = fixtures.iter().find(|f| f.name == unit.fixture)?;
+                Some(unit.play(logical))
+            })
+            .collect()
+    }
+
+    /// Logical fixtures no unit plays at this venue.
+    pub fn unmapped<'a>(&self, fixtures: &'a [Fixture]) -> Vec<&'a Fixture> {
+        fixtures
+            .iter()
+            .filter(|fixture| !self.units.iter().any(|unit| unit.fixture == fixture.name))
+            .collect()
+    }
+
+    /// Names in the mapping that aren't in the show's patch.
+    pub fn unknown(&self, fixtures: &[Fixture]) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .units
+            .iter()
+            .filter(|unit| !fixtures.iter().any(|f| f.name == unit.fixture))
+            .map(|unit| unit.fixture.as_str())
+            .collect();
+        names.dedup();
+        names
+    }
+}
+
+impl HaloApp {
+    /// The fixtures output goes to: the venue's rig if a mapping is loaded,
+    /// otherwise the show's patch.
+    pub(crate) fn rig(&self) -> Cow<'_, [Fixture]> {
+        match &self.venue {
+            Some(venue) => Cow::Owned(venue.patch(&self.show.fixtures)),
+            None => Cow::Borrowed(&self.show.fixtures),
+        }
+    }
+
+    /// Where a logical fixture's output can be read back: the first unit
+    /// playing it at this venue, or the fixture itself.
+    pub(crate) fn on_rig<'a>(&self, fixture: &'a Fixture) -> Cow<'a, Fixture> {
+        self.venue
+            .as_ref()
+            .and_then(|venue| venue.units.iter().find(|unit| unit.fixture == fixture.name))
+            .map_or(Cow::Borrowed(fixture), |unit| {
+                Cow::Owned(unit.play(fixture))
+            })
+    }
+
+    /// Loads the venue mapping named in the preferences, if any.
+    pub(crate) fn apply_venue_preferences(&mut self) {
+        self.venue = None;
+        let Some(path) = self.preferences.venue_file.clone() else {
+            return;
+        };
+        match VenueMap::load(&path) {
+            Ok(venue) => {
+                self.log.info(tr!(
+                    "venue-loaded",
+                    name = venue.name.as_str(),
+                    count = venue.units.len()
+                ));
+                self.venue = Some(venue);
+            }
+            Err(err) => self.log.error(tr!(
+                "venue-mapping-failed",
+                path = path.display().to_string(),
+                error = err.to_string()
+            )),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Runs the default show through the output engine on the virtual clock (see
+//! `--simulate`) and checks the frames against the golden file, so a change
+//! to fades, effects or the merge that moves a single channel fails here.
+//!
+//! After a change that is meant to move the output, rewrite the file with
+//! `halo-tc-ui --simulate default --golden golden/default-show.txt --bless`
+//! and check the difference before committing it.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+const GOLDEN: &str = "golden/default-show.txt";
+
+fn simulate(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_halo-tc-ui"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .arg("--simulate")
+        .args(args)
+        .output()
+        .expect("the simulation runs")
+}
+
+#[test]
+fn default_show_matches_golden_file() {
+    // Without the file the simulation would write it and pass.
+    assert!(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(GOLDEN).exists(),
+        "{} is missing",
+        GOLDEN
+    );
+    let output = simulate(&["default", "--golden", GOLDEN]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("frames match"));
+}
+
+#[test]
+fn same_show_gives_same_frames() {
+    let first = simulate(&["default", "--seconds", "20"]);
+    let second = simulate(&["default", "--seconds", "20"]);
+    assert!(first.status.success());
+    assert!(!first.stdout.is_empty());
+    assert_eq!(first.stdout, second.stdout);
+}